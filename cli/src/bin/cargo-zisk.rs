@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Context, Result};
 use cargo_zisk::{
     commands::{
-        ZiskBuild, ZiskCheckSetup, ZiskClean, ZiskProve, ZiskRomSetup, ZiskRun, ZiskSdk,
-        ZiskVerify, ZiskVerifyConstraints,
+        ZiskBuild, ZiskCache, ZiskCheckElf, ZiskCheckSetup, ZiskClean, ZiskExecute, ZiskProve,
+        ZiskRomSetup, ZiskRun, ZiskSdk, ZiskTest, ZiskVerify, ZiskVerifyConstraints,
     },
     ZISK_VERSION_MESSAGE,
 };
@@ -19,12 +19,16 @@ use clap::Parser;
 )]
 pub enum Cargo {
     Build(ZiskBuild),
+    Cache(ZiskCache),
+    CheckElf(ZiskCheckElf),
     CheckSetup(ZiskCheckSetup),
     Clean(ZiskClean),
+    Execute(ZiskExecute),
     Prove(ZiskProve),
     RomSetup(ZiskRomSetup),
     Run(ZiskRun),
     Sdk(ZiskSdk),
+    Test(ZiskTest),
     Verify(ZiskVerify),
     VerifyConstraints(ZiskVerifyConstraints),
 }
@@ -37,12 +41,21 @@ fn main() -> Result<()> {
         Cargo::Build(cmd) => {
             cmd.run().context("Error executing Build command")?;
         }
+        Cargo::Cache(cmd) => {
+            cmd.command.run().context("Error executing Cache command")?;
+        }
+        Cargo::CheckElf(cmd) => {
+            cmd.run().context("Error executing CheckElf command")?;
+        }
         Cargo::CheckSetup(cmd) => {
             cmd.run().context("Error executing CheckSetup command")?;
         }
         Cargo::Clean(cmd) => {
             cmd.run().context("Error executing Clean command")?;
         }
+        Cargo::Execute(mut cmd) => {
+            cmd.run().context("Error executing Execute command")?;
+        }
         Cargo::Prove(mut cmd) => {
             cmd.run().context("Error executing Prove command")?;
         }
@@ -55,6 +68,9 @@ fn main() -> Result<()> {
         Cargo::Sdk(cmd) => {
             cmd.command.run().context("Error executing SDK command")?;
         }
+        Cargo::Test(cmd) => {
+            cmd.run().context("Error executing Test command")?;
+        }
         Cargo::Verify(cmd) => {
             cmd.run().map_err(|e| anyhow!("Error executing Verify command: {}", e))?;
         }
@@ -1,6 +1,22 @@
-use crate::{ZISK_TARGET, ZISK_VERSION_MESSAGE};
+use crate::{RUSTUP_TOOLCHAIN_NAME, ZISK_TARGET, ZISK_VERSION_MESSAGE};
 use anyhow::{anyhow, Context, Result};
-use std::process::{Command, Stdio};
+use colored::Colorize;
+use rom_setup::{get_elf_data_hash, get_file_hash};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Default file name of the build manifest, written next to the ELF files
+const BUILD_MANIFEST_FILE: &str = "zisk-build.json";
+
+/// Environment variables that add compiler flags from outside the project sources, ignored by
+/// reproducible builds
+const RUSTFLAGS_ENV_VARS: [&str; 3] =
+    ["RUSTFLAGS", "CARGO_ENCODED_RUSTFLAGS", "CARGO_TARGET_RISCV64IMA_ZISK_ZKVM_ELF_RUSTFLAGS"];
 
 // Structure representing the 'build' subcommand of cargo.
 #[derive(clap::Args)]
@@ -17,14 +33,122 @@ pub struct ZiskBuild {
 
     #[clap(long)]
     no_default_features: bool,
+
+    /// Build with pinned dependencies, remapped paths and no symbols, and write a build manifest
+    /// with the hash of the generated ELF files
+    #[clap(long)]
+    reproducible: bool,
+
+    /// Fail if the zisk toolchain version (`rustc +zisk --version`) is not this one
+    #[clap(long)]
+    toolchain_version: Option<String>,
+
+    /// Build manifest path, defaults to zisk-build.json next to the ELF files
+    #[clap(long)]
+    build_manifest: Option<PathBuf>,
+
+    /// Rebuild reproducibly with the options of a build manifest and check that the generated
+    /// ELF files are the same
+    #[clap(long, conflicts_with_all = ["features", "all_features", "release", "no_default_features", "reproducible", "build_manifest"])]
+    verify: Option<PathBuf>,
+}
+
+/// Describes a reproducible build: the toolchain, sources and options used, and the resulting ELF
+/// files, so that anyone can rebuild them and check that a ROM comes from these sources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildManifest {
+    pub zisk_version: String,
+    /// First line of `rustc +zisk -vV`
+    pub toolchain: String,
+    pub toolchain_commit: String,
+    pub target: String,
+    pub release: bool,
+    pub features: Option<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+    /// Blake3 hash of the Cargo.lock file
+    pub cargo_lock_hash: String,
+    pub elfs: Vec<BuildManifestElf>,
+}
+
+/// ELF file generated by a reproducible build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildManifestElf {
+    pub name: String,
+    /// Path relative to the workspace root
+    pub path: PathBuf,
+    /// Blake3 hash of the file, the same used to identify the ROM setup
+    pub hash: String,
+}
+
+/// Result of comparing an ELF file of a build manifest with the rebuilt one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildElfCheck {
+    Ok,
+    /// The rebuilt ELF file has a different hash, given
+    Mismatch(String),
+    /// The ELF file was not rebuilt
+    Missing,
+}
+
+impl BuildManifest {
+    /// Compares the ELF files of the manifest with the ones of `rebuilt`, matching them by name
+    pub fn compare(&self, rebuilt: &BuildManifest) -> Vec<(&BuildManifestElf, BuildElfCheck)> {
+        self.elfs
+            .iter()
+            .map(|elf| {
+                let check = match rebuilt.elfs.iter().find(|rebuilt| rebuilt.name == elf.name) {
+                    Some(rebuilt) if rebuilt.hash == elf.hash => BuildElfCheck::Ok,
+                    Some(rebuilt) => BuildElfCheck::Mismatch(rebuilt.hash.clone()),
+                    None => BuildElfCheck::Missing,
+                };
+                (elf, check)
+            })
+            .collect()
+    }
 }
 
 impl ZiskBuild {
     pub fn run(&self) -> Result<()> {
+        if let Some(manifest) = &self.verify {
+            return self.verify(manifest);
+        }
+        if self.reproducible {
+            let manifest = self.build_reproducible()?;
+            let manifest_path = match &self.build_manifest {
+                Some(path) => path.clone(),
+                None => self.default_manifest_path(&manifest)?,
+            };
+            fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+                .with_context(|| format!("Failed to write build manifest {:?}", manifest_path))?;
+
+            for elf in &manifest.elfs {
+                println!("{} {} {}", "ELF".bright_green().bold(), elf.path.display(), elf.hash);
+            }
+            println!("Build manifest written to {}", manifest_path.display());
+            return Ok(());
+        }
+
         // Construct the cargo run command
         let mut command = Command::new("cargo");
         command.args(["+zisk", "build"]);
-        // Add the feature selection flags
+        self.add_build_args(&mut command);
+
+        // Set up the command to inherit the parent's stdout and stderr
+        command.stdout(Stdio::inherit());
+        command.stderr(Stdio::inherit());
+
+        // Execute the command
+        let status = command.status().context("Failed to execute cargo run command")?;
+        if !status.success() {
+            return Err(anyhow!("Cargo run command failed with status {}", status));
+        }
+
+        Ok(())
+    }
+
+    /// Adds the feature selection, profile and target flags
+    fn add_build_args(&self, command: &mut Command) {
         if let Some(features) = &self.features {
             command.arg("--features").arg(features);
         }
@@ -39,17 +163,280 @@ impl ZiskBuild {
         }
 
         command.args(["--target", ZISK_TARGET]);
+    }
 
-        // Set up the command to inherit the parent's stdout and stderr
-        command.stdout(Stdio::inherit());
+    /// Builds the project so that the ELF files only depend on the sources, Cargo.lock and
+    /// toolchain, and returns the build manifest
+    fn build_reproducible(&self) -> Result<BuildManifest> {
+        let (toolchain, toolchain_commit) = get_toolchain_version()?;
+        if let Some(expected) = &self.toolchain_version {
+            if &toolchain != expected {
+                return Err(anyhow!(
+                    "Toolchain version is {}, but {} is required",
+                    toolchain,
+                    expected
+                ));
+            }
+        }
+
+        let workspace_root = get_workspace_root()?;
+        let cargo_lock = workspace_root.join("Cargo.lock");
+        let cargo_lock_hash =
+            get_file_hash(&cargo_lock).context("Reproducible builds require a Cargo.lock file")?;
+
+        let mut command = Command::new("cargo");
+        command.arg(format!("+{}", RUSTUP_TOOLCHAIN_NAME)).arg("build").arg("--locked");
+        self.add_build_args(&mut command);
+
+        // Remap the paths embedded in the binary (e.g. in panic locations), and strip symbols
+        // and debug info, which contain paths and are not part of the ROM. They are passed as
+        // configuration, so that the target flags of the project configuration, which are part of
+        // the sources, still apply
+        let sysroot = get_toolchain_sysroot()?;
+        let rustflags = [
+            format!("--remap-path-prefix={}=/zisk/src", workspace_root.display()),
+            format!("--remap-path-prefix={}=/zisk/cargo", get_cargo_home().display()),
+            format!("--remap-path-prefix={}=/zisk/toolchain", sysroot.display()),
+            "-Cstrip=symbols".to_string(),
+        ];
+        command.arg("--config").arg(format!(
+            "target.{}.rustflags={}",
+            ZISK_TARGET,
+            serde_json::to_string(&rustflags)?
+        ));
+        for var in RUSTFLAGS_ENV_VARS {
+            if std::env::var_os(var).is_some() {
+                println!("{} Ignoring {} in reproducible build", "[WARN]".yellow(), var);
+                command.env_remove(var);
+            }
+        }
+        command.env("CARGO_INCREMENTAL", "0").env("SOURCE_DATE_EPOCH", "0");
+
+        command.args(["--message-format", "json-render-diagnostics"]);
+        command.stdout(Stdio::piped());
         command.stderr(Stdio::inherit());
 
-        // Execute the command
-        let status = command.status().context("Failed to execute cargo run command")?;
+        let mut child = command.spawn().context("Failed to execute cargo build command")?;
+        let stdout = child.stdout.take().context("Failed to read cargo build output")?;
+
+        // Collect the executables built, from the cargo JSON messages
+        let mut elfs = Vec::new();
+        for line in BufReader::new(stdout).lines() {
+            let message: serde_json::Value = match serde_json::from_str(&line?) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            if message["reason"] != "compiler-artifact" {
+                continue;
+            }
+            if let Some(executable) = message["executable"].as_str() {
+                let path = PathBuf::from(executable);
+                elfs.push(BuildManifestElf {
+                    name: message["target"]["name"].as_str().unwrap_or_default().to_string(),
+                    hash: get_elf_data_hash(&path)?,
+                    path: path.strip_prefix(&workspace_root).unwrap_or(&path).to_path_buf(),
+                });
+            }
+        }
+
+        let status = child.wait().context("Failed to execute cargo build command")?;
         if !status.success() {
-            return Err(anyhow!("Cargo run command failed with status {}", status));
+            return Err(anyhow!("Cargo build command failed with status {}", status));
+        }
+        if elfs.is_empty() {
+            return Err(anyhow!("Cargo build did not generate any ELF file"));
+        }
+        elfs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(BuildManifest {
+            zisk_version: env!("CARGO_PKG_VERSION").to_string(),
+            toolchain,
+            toolchain_commit,
+            target: ZISK_TARGET.to_string(),
+            release: self.release,
+            features: self.features.clone(),
+            all_features: self.all_features,
+            no_default_features: self.no_default_features,
+            cargo_lock_hash,
+            elfs,
+        })
+    }
+
+    fn default_manifest_path(&self, manifest: &BuildManifest) -> Result<PathBuf> {
+        let elf = get_workspace_root()?.join(&manifest.elfs[0].path);
+        Ok(elf.with_file_name(BUILD_MANIFEST_FILE))
+    }
+
+    /// Rebuilds the project with the options of the build manifest at `path`, and compares the
+    /// generated ELF files with the ones it describes
+    fn verify(&self, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read build manifest {:?}", path))?;
+        let expected: BuildManifest = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse build manifest {:?}", path))?;
+
+        let cargo_lock = get_workspace_root()?.join("Cargo.lock");
+        if get_file_hash(&cargo_lock)? != expected.cargo_lock_hash {
+            return Err(anyhow!("Cargo.lock does not match the one of the build manifest"));
+        }
+        let (toolchain, toolchain_commit) = get_toolchain_version()?;
+        if toolchain_commit != expected.toolchain_commit {
+            return Err(anyhow!(
+                "Toolchain version is {}, but the build manifest requires {} ({})",
+                toolchain,
+                expected.toolchain,
+                expected.toolchain_commit
+            ));
+        }
+        if expected.zisk_version != env!("CARGO_PKG_VERSION") {
+            println!(
+                "{} Build manifest was generated by cargo-zisk {}",
+                "[WARN]".yellow(),
+                expected.zisk_version
+            );
+        }
+
+        let build = ZiskBuild {
+            features: expected.features.clone(),
+            all_features: expected.all_features,
+            release: expected.release,
+            no_default_features: expected.no_default_features,
+            reproducible: true,
+            toolchain_version: None,
+            build_manifest: None,
+            verify: None,
+        };
+        let rebuilt = build.build_reproducible()?;
+
+        let mut failed = 0;
+        for (elf, check) in expected.compare(&rebuilt) {
+            match check {
+                BuildElfCheck::Ok => {
+                    println!("{} {} {}", "[OK]".green().bold(), elf.name, elf.hash);
+                }
+                BuildElfCheck::Mismatch(hash) => {
+                    failed += 1;
+                    println!(
+                        "{} {} expected {}, got {}",
+                        "[MISMATCH]".red().bold(),
+                        elf.name,
+                        elf.hash,
+                        hash
+                    );
+                }
+                BuildElfCheck::Missing => {
+                    failed += 1;
+                    println!("{} {} was not built", "[MISSING]".red().bold(), elf.name);
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(anyhow!("{} ELF files do not match the build manifest", failed));
         }
 
         Ok(())
     }
 }
+
+/// Returns the version and commit hash of the zisk toolchain
+fn get_toolchain_version() -> Result<(String, String)> {
+    let output = Command::new("rustc")
+        .arg(format!("+{}", RUSTUP_TOOLCHAIN_NAME))
+        .arg("-vV")
+        .output()
+        .context("Failed to get the zisk toolchain version")?;
+    if !output.status.success() {
+        return Err(anyhow!("Failed to get the zisk toolchain version, is it installed?"));
+    }
+    let version = String::from_utf8_lossy(&output.stdout);
+    let toolchain = version.lines().next().unwrap_or_default().to_string();
+    let commit = version
+        .lines()
+        .find_map(|line| line.strip_prefix("commit-hash: "))
+        .unwrap_or_default()
+        .to_string();
+    Ok((toolchain, commit))
+}
+
+fn get_toolchain_sysroot() -> Result<PathBuf> {
+    let output = Command::new("rustc")
+        .arg(format!("+{}", RUSTUP_TOOLCHAIN_NAME))
+        .args(["--print", "sysroot"])
+        .output()
+        .context("Failed to get the zisk toolchain sysroot")?;
+    Ok(PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+fn get_workspace_root() -> Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .output()
+        .context("Failed to locate the cargo workspace")?;
+    if !output.status.success() {
+        return Err(anyhow!("Failed to locate the cargo workspace"));
+    }
+    let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    manifest.parent().map(Path::to_path_buf).context("Invalid workspace manifest path")
+}
+
+fn get_cargo_home() -> PathBuf {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(super::get_home_dir()).join(".cargo"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(elfs: &[(&str, &str)]) -> BuildManifest {
+        BuildManifest {
+            zisk_version: "0.1.0".to_string(),
+            toolchain: "rustc 1.0.0".to_string(),
+            toolchain_commit: "commit".to_string(),
+            target: ZISK_TARGET.to_string(),
+            release: true,
+            features: None,
+            all_features: false,
+            no_default_features: false,
+            cargo_lock_hash: "lock".to_string(),
+            elfs: elfs
+                .iter()
+                .map(|(name, hash)| BuildManifestElf {
+                    name: name.to_string(),
+                    path: PathBuf::from(format!("target/{}", name)),
+                    hash: hash.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let expected = manifest(&[("guest", "aa"), ("other", "bb"), ("removed", "cc")]);
+        let rebuilt = manifest(&[("added", "dd"), ("other", "ee"), ("guest", "aa")]);
+
+        let checks: Vec<_> = expected
+            .compare(&rebuilt)
+            .into_iter()
+            .map(|(elf, check)| (elf.name.as_str(), check))
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                ("guest", BuildElfCheck::Ok),
+                ("other", BuildElfCheck::Mismatch("ee".to_string())),
+                ("removed", BuildElfCheck::Missing),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_same_build() {
+        let expected = manifest(&[("guest", "aa"), ("other", "bb")]);
+        let rebuilt: BuildManifest =
+            serde_json::from_str(&serde_json::to_string_pretty(&expected).unwrap()).unwrap();
+        assert!(expected.compare(&rebuilt).iter().all(|(_, check)| *check == BuildElfCheck::Ok));
+        assert!(manifest(&[]).compare(&rebuilt).is_empty());
+    }
+}
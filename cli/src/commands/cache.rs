@@ -0,0 +1,306 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use indicatif::HumanBytes;
+use rom_setup::{get_elf_data_hash, CacheArtifactStatus, CacheEntry, RomCache};
+
+use crate::ZISK_VERSION_MESSAGE;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Structure representing the 'cache' subcommand of cargo.
+#[derive(Args)]
+#[command(author, about = "Manage the ROM setup cache", long_about = None, args_conflicts_with_subcommands = true, version = ZISK_VERSION_MESSAGE)]
+pub struct ZiskCache {
+    #[clap(subcommand)]
+    pub command: ZiskCacheCommands,
+}
+
+// Enum defining the available subcommands for `ZiskCache`.
+#[derive(Subcommand)]
+pub enum ZiskCacheCommands {
+    /// List the ROM setups in the cache
+    List(CacheListCmd),
+    /// Show the artifacts of a ROM setup
+    Info(CacheInfoCmd),
+    /// Remove outdated ROM setups, or the oldest ones by age or size budget
+    Prune(CachePruneCmd),
+    /// Re-check the integrity of the cached artifacts
+    Verify(CacheVerifyCmd),
+}
+
+impl ZiskCacheCommands {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            ZiskCacheCommands::List(cmd) => cmd.run(),
+            ZiskCacheCommands::Info(cmd) => cmd.run(),
+            ZiskCacheCommands::Prune(cmd) => cmd.run(),
+            ZiskCacheCommands::Verify(cmd) => cmd.run(),
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct CacheDirArgs {
+    /// Cache dir path, defaults to $HOME/.zisk/cache
+    #[clap(short = 'o', long)]
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl CacheDirArgs {
+    fn cache(&self) -> RomCache {
+        RomCache::new(self.cache_dir.clone().unwrap_or_else(RomCache::default_path))
+    }
+}
+
+#[derive(Args)]
+pub struct CacheListCmd {
+    #[clap(flatten)]
+    pub cache_dir: CacheDirArgs,
+}
+
+impl CacheListCmd {
+    pub fn run(&self) -> Result<()> {
+        let cache = self.cache_dir.cache();
+        let entries = cache.entries()?;
+
+        println!("Cache directory: {}", cache.path().display());
+        if entries.is_empty() {
+            println!("No ROM setups found");
+        }
+        for entry in &entries {
+            print_entry_summary(entry);
+        }
+
+        let unmanaged = cache.unmanaged_files(&entries)?;
+        if !unmanaged.is_empty() {
+            println!(
+                "{} {} files without a manifest, generated by an older version",
+                "[WARN]".yellow(),
+                unmanaged.len()
+            );
+        }
+
+        let total: u64 = entries.iter().map(|entry| entry.manifest.size()).sum();
+        println!("Total: {} setups, {}", entries.len(), HumanBytes(total));
+        Ok(())
+    }
+}
+
+#[derive(Args)]
+pub struct CacheInfoCmd {
+    /// ELF file path or ELF hash prefix
+    pub program: String,
+
+    #[clap(flatten)]
+    pub cache_dir: CacheDirArgs,
+}
+
+impl CacheInfoCmd {
+    pub fn run(&self) -> Result<()> {
+        let cache = self.cache_dir.cache();
+        let entries = find_entries(&cache, Some(&self.program))?;
+
+        for entry in &entries {
+            let manifest = &entry.manifest;
+            println!("{}", manifest.elf.display().to_string().bold());
+            println!("  ELF hash:      {}", manifest.elf_hash);
+            println!(
+                "  PIL hash:      {}{}",
+                manifest.pilout_hash,
+                if manifest.is_outdated() { " (outdated)" } else { "" }
+            );
+            println!("  ROM rows:      {}", manifest.rows);
+            println!("  Blowup factor: {}", manifest.blowup_factor);
+            println!("  Created:       {} ago", format_age(manifest.created));
+            println!("  Manifest:      {}", entry.manifest_path.display());
+            println!("  Artifacts:");
+            for artifact in &manifest.artifacts {
+                println!("    {: >10}  {}", HumanBytes(artifact.size).to_string(), artifact.file);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Args)]
+pub struct CachePruneCmd {
+    /// Remove the setups generated for a PIL other than the current one (default if no other
+    /// criteria is given)
+    #[clap(long, default_value_t = false)]
+    pub outdated: bool,
+
+    /// Remove the setups older than this number of days
+    #[clap(long)]
+    pub older_than: Option<u64>,
+
+    /// Remove the oldest setups until the cache fits in this size, e.g. 20G
+    #[clap(long, value_parser = parse_size)]
+    pub max_size: Option<u64>,
+
+    /// Show what would be removed without removing anything
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub cache_dir: CacheDirArgs,
+}
+
+impl CachePruneCmd {
+    pub fn run(&self) -> Result<()> {
+        let cache = self.cache_dir.cache();
+        let entries = cache.entries()?;
+
+        let outdated = self.outdated || (self.older_than.is_none() && self.max_size.is_none());
+        let now = now();
+
+        // Entries are sorted oldest first, so the size budget is filled with the newest ones
+        let mut remove = vec![false; entries.len()];
+        let mut budget = self.max_size.unwrap_or(u64::MAX);
+        for (i, entry) in entries.iter().enumerate().rev() {
+            let manifest = &entry.manifest;
+            let too_old = self
+                .older_than
+                .is_some_and(|days| now.saturating_sub(manifest.created) > days * SECONDS_PER_DAY);
+            if (outdated && manifest.is_outdated()) || too_old || manifest.size() > budget {
+                remove[i] = true;
+            } else {
+                budget -= manifest.size();
+            }
+        }
+
+        let (removed, kept): (Vec<_>, Vec<_>) =
+            entries.into_iter().zip(remove).partition(|(_, remove)| *remove);
+        let removed: Vec<CacheEntry> = removed.into_iter().map(|(entry, _)| entry).collect();
+        let kept: Vec<CacheEntry> = kept.into_iter().map(|(entry, _)| entry).collect();
+
+        if removed.is_empty() {
+            println!("Nothing to prune");
+            return Ok(());
+        }
+
+        for entry in &removed {
+            print_entry_summary(entry);
+        }
+
+        if self.dry_run {
+            let size: u64 = removed.iter().map(|entry| entry.manifest.size()).sum();
+            println!("Would remove {} setups, up to {}", removed.len(), HumanBytes(size));
+        } else {
+            let freed = cache.remove(&removed, &kept)?;
+            println!(
+                "{} Removed {} setups, freed {}",
+                "[OK]".green().bold(),
+                removed.len(),
+                HumanBytes(freed)
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Args)]
+pub struct CacheVerifyCmd {
+    /// ELF file path or ELF hash prefix, all setups are verified if not given
+    pub program: Option<String>,
+
+    #[clap(flatten)]
+    pub cache_dir: CacheDirArgs,
+}
+
+impl CacheVerifyCmd {
+    pub fn run(&self) -> Result<()> {
+        let cache = self.cache_dir.cache();
+        let entries = find_entries(&cache, self.program.as_deref())?;
+
+        let mut failed = 0;
+        for entry in &entries {
+            println!("{}", entry.manifest.elf.display().to_string().bold());
+            for (artifact, status) in entry.manifest.verify(cache.path()) {
+                if status != CacheArtifactStatus::Ok {
+                    failed += 1;
+                }
+                let status = match status {
+                    CacheArtifactStatus::Ok => "[OK]".green().bold(),
+                    CacheArtifactStatus::Missing => "[MISSING]".red().bold(),
+                    CacheArtifactStatus::Modified => "[MODIFIED]".red().bold(),
+                };
+                println!("  {: <10} {}", status, artifact.file);
+            }
+        }
+
+        if failed > 0 {
+            anyhow::bail!(
+                "{} artifacts failed verification, run `cargo-zisk rom-setup` again",
+                failed
+            );
+        }
+        println!("{} All artifacts verified", "[OK]".green().bold());
+        Ok(())
+    }
+}
+
+/// Returns the cache entries matching an ELF file path or ELF hash prefix, or all of them
+fn find_entries(cache: &RomCache, program: Option<&str>) -> Result<Vec<CacheEntry>> {
+    let entries = cache.entries()?;
+    let Some(program) = program else {
+        return Ok(entries);
+    };
+
+    let path = PathBuf::from(program);
+    let hash = if path.is_file() { get_elf_data_hash(&path)? } else { program.to_string() };
+
+    let entries: Vec<CacheEntry> =
+        entries.into_iter().filter(|entry| entry.manifest.elf_hash.starts_with(&hash)).collect();
+    if entries.is_empty() {
+        anyhow::bail!("No ROM setup found in {} for {}", cache.path().display(), program);
+    }
+    Ok(entries)
+}
+
+fn print_entry_summary(entry: &CacheEntry) {
+    let manifest = &entry.manifest;
+    let pil = if manifest.is_outdated() {
+        format!("{} (outdated)", &manifest.pilout_hash).yellow()
+    } else {
+        manifest.pilout_hash.normal()
+    };
+    println!(
+        "{}  {}  pil {}  rows {}  blowup {}  {}  {} ago",
+        &manifest.elf_hash[..manifest.elf_hash.len().min(12)],
+        manifest.elf.display(),
+        pil,
+        manifest.rows,
+        manifest.blowup_factor,
+        HumanBytes(manifest.size()),
+        format_age(manifest.created)
+    );
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn format_age(created: u64) -> String {
+    let age = now().saturating_sub(created);
+    match age {
+        age if age >= SECONDS_PER_DAY => format!("{}d", age / SECONDS_PER_DAY),
+        age if age >= 60 * 60 => format!("{}h", age / (60 * 60)),
+        age => format!("{}m", age / 60),
+    }
+}
+
+/// Parses a size in bytes, with an optional K, M or G suffix
+fn parse_size(size: &str) -> Result<u64, String> {
+    let size = size.trim();
+    let (number, shift) = match size.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&size[..i], 10),
+        Some((i, 'M' | 'm')) => (&size[..i], 20),
+        Some((i, 'G' | 'g')) => (&size[..i], 30),
+        _ => (size, 0),
+    };
+    number.parse::<u64>().map(|n| n << shift).map_err(|_| format!("Invalid size: {}", size))
+}
@@ -0,0 +1,45 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use zisk_core::check_elf;
+
+/// Checks that all the instructions of a guest ELF file are supported by ZisK
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+#[command(propagate_version = true)]
+pub struct ZiskCheckElf {
+    /// ELF file path
+    #[clap(short = 'e', long)]
+    pub elf: PathBuf,
+
+    /// Only fail on unsupported instructions, reporting foreign CSR accesses as warnings
+    #[clap(long, default_value_t = false)]
+    pub allow_csrs: bool,
+}
+
+impl ZiskCheckElf {
+    pub fn run(&self) -> Result<()> {
+        println!("{} CheckElf", format!("{: >12}", "Command").bright_green().bold());
+        println!();
+
+        let report = check_elf(&self.elf)
+            .map_err(|e| anyhow!("Error checking ELF file {}: {}", self.elf.display(), e))?;
+
+        print!("{}", report);
+        println!();
+
+        if !report.unsupported.is_empty() || (!self.allow_csrs && !report.foreign_csrs.is_empty()) {
+            return Err(anyhow!("ELF file {} is not compatible with ZisK", self.elf.display()));
+        }
+
+        println!(
+            "{} {}",
+            format!("{: >12}", "Compatible").bright_green().bold(),
+            self.elf.display()
+        );
+
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
-use clap::{Parser, ValueEnum};
+use asm_runner::AsmRunnerOptions;
+use clap::{Args, Parser, ValueEnum};
+use executor::{SharedInputPath, ZiskExecutionPlan};
 use proofman_common::VerboseMode;
+use std::any::Any;
 use std::env;
 use std::fmt::Display;
 use std::path::PathBuf;
 use std::str::FromStr;
 use witness::WitnessLibrary;
+use zisk_core::{zisk_ops::ZiskOp, ResourceLimits, DEFAULT_MAX_STEPS_STR};
 
 #[derive(Parser, Debug, Clone, ValueEnum)]
 pub enum Field {
@@ -49,6 +53,12 @@ pub fn get_default_proving_key() -> PathBuf {
     PathBuf::from(proving_key)
 }
 
+/// Gets the default sha256f script file location in the home installation directory.
+pub fn get_default_sha256f_script() -> PathBuf {
+    let sha256f_script = format!("{}/.zisk/bin/sha256f_script.json", get_home_dir());
+    PathBuf::from(sha256f_script)
+}
+
 /// Gets the default zisk folder location in the home installation directory.
 pub fn get_home_zisk_path() -> PathBuf {
     let zisk_path = format!("{}/.zisk", get_home_dir());
@@ -84,6 +94,17 @@ pub fn get_default_verkey() -> String {
     verkey
 }
 
+/// Returns the message of a caught panic
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
 /// If the target_os is macOS returns an error indicating that the command is not supported.
 pub fn cli_fail_if_macos() -> anyhow::Result<()> {
     if cfg!(target_os = "macos") {
@@ -109,4 +130,61 @@ pub type ZiskLibInitFn<F> = fn(
     Option<PathBuf>, // Asm ROM path
     Option<PathBuf>, // Inputs path
     PathBuf,         // Sha256f script path
+    bool,            // Allow panic
+    ResourceLimits,  // Resource limits
 ) -> Result<Box<dyn WitnessLibrary<F>>, Box<dyn std::error::Error>>;
+
+pub type ZiskBatchLibInitFn<F> =
+    fn(
+        VerboseMode,
+        PathBuf,          // Rom path
+        Option<PathBuf>,  // Asm path
+        Option<PathBuf>,  // Asm ROM path
+        SharedInputPath,  // Inputs path, set before every proof
+        PathBuf,          // Sha256f script path
+        bool,             // Allow panic
+        ResourceLimits,   // Resource limits
+        AsmRunnerOptions, // Asm runner options, kept for the whole batch
+    ) -> Result<Box<dyn WitnessLibrary<F>>, Box<dyn std::error::Error>>;
+
+pub type ZiskExecuteFn = fn(
+    VerboseMode,
+    PathBuf,         // Rom path
+    Option<PathBuf>, // Asm path
+    Option<PathBuf>, // Inputs path
+    PathBuf,         // Sha256f script path
+    ResourceLimits,  // Resource limits
+) -> Result<ZiskExecutionPlan, Box<dyn std::error::Error>>;
+
+/// Resource limits of the guest program execution
+#[derive(Args, Debug, Clone)]
+pub struct ResourceLimitsArgs {
+    /// Sets the maximum number of steps to execute
+    #[clap(long, value_name = "MAX_STEPS", default_value = DEFAULT_MAX_STEPS_STR)]
+    pub max_steps: u64,
+    /// Sets the maximum heap size of the guest program, in bytes
+    #[clap(long, value_name = "MAX_HEAP_SIZE")]
+    pub max_heap_size: Option<u64>,
+    /// Sets the maximum input data size, in bytes
+    #[clap(long, value_name = "MAX_INPUT_SIZE")]
+    pub max_input_size: Option<u64>,
+    /// Sets the maximum output data size, in bytes
+    #[clap(long, value_name = "MAX_OUTPUT_SIZE")]
+    pub max_output_size: Option<u64>,
+    /// Sets the maximum number of calls of a precompile, e.g. `keccak=1000`.  Can be repeated.
+    #[clap(long, value_name = "PRECOMPILE=CALLS", value_parser = ResourceLimits::parse_precompile_limit)]
+    pub max_precompile_calls: Vec<(ZiskOp, u64)>,
+}
+
+impl ResourceLimitsArgs {
+    /// Returns the resource limits of the guest program
+    pub fn resource_limits(&self) -> ResourceLimits {
+        ResourceLimits {
+            max_steps: self.max_steps,
+            max_heap_size: self.max_heap_size,
+            max_input_size: self.max_input_size,
+            max_output_size: self.max_output_size,
+            max_precompile_calls: self.max_precompile_calls.iter().copied().collect(),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    commands::{
+        get_default_sha256f_script, get_default_witness_computation_lib, panic_message,
+        ResourceLimitsArgs, ZiskExecuteFn,
+    },
+    ZISK_VERSION_MESSAGE,
+};
+use anyhow::Result;
+use colored::Colorize;
+use executor::ZiskExecutionPlan;
+use libloading::{Library, Symbol};
+use rom_setup::{get_elf_data_hash, DEFAULT_CACHE_PATH};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    time::Instant,
+};
+use zisk_pil::{
+    ARITH_AIR_IDS, ARITH_EQ_AIR_IDS, ARITH_EQ_LT_TABLE_AIR_IDS, ARITH_RANGE_TABLE_AIR_IDS,
+    ARITH_TABLE_AIR_IDS, BINARY_ADD_AIR_IDS, BINARY_AIR_IDS, BINARY_EXTENSION_AIR_IDS,
+    BINARY_EXTENSION_TABLE_AIR_IDS, BINARY_TABLE_AIR_IDS, BLAKE_2_BR_AIR_IDS,
+    BLAKE_2_BR_TABLE_AIR_IDS, DMA_AIR_IDS, INPUT_DATA_AIR_IDS, KECCAKF_AIR_IDS,
+    KECCAKF_TABLE_AIR_IDS, MAIN_AIR_IDS, MEM_AIR_IDS, MEM_ALIGN_AIR_IDS, MEM_ALIGN_ROM_AIR_IDS,
+    ROM_AIR_IDS, ROM_DATA_AIR_IDS, SHA_256_F_AIR_IDS, SHA_256_F_TABLE_AIR_IDS,
+    SPECIFIED_RANGES_AIR_IDS, ZISK_AIRGROUP_ID,
+};
+
+/// Names of the AIRs, by AIR ID
+const AIR_NAMES: &[(&[usize], &str)] = &[
+    (MAIN_AIR_IDS, "Main"),
+    (ROM_AIR_IDS, "Rom"),
+    (MEM_AIR_IDS, "Mem"),
+    (ROM_DATA_AIR_IDS, "RomData"),
+    (INPUT_DATA_AIR_IDS, "InputData"),
+    (MEM_ALIGN_AIR_IDS, "MemAlign"),
+    (MEM_ALIGN_ROM_AIR_IDS, "MemAlignRom"),
+    (ARITH_AIR_IDS, "Arith"),
+    (ARITH_TABLE_AIR_IDS, "ArithTable"),
+    (ARITH_RANGE_TABLE_AIR_IDS, "ArithRangeTable"),
+    (ARITH_EQ_AIR_IDS, "ArithEq"),
+    (ARITH_EQ_LT_TABLE_AIR_IDS, "ArithEqLtTable"),
+    (BINARY_AIR_IDS, "Binary"),
+    (BINARY_ADD_AIR_IDS, "BinaryAdd"),
+    (BINARY_TABLE_AIR_IDS, "BinaryTable"),
+    (BINARY_EXTENSION_AIR_IDS, "BinaryExtension"),
+    (BINARY_EXTENSION_TABLE_AIR_IDS, "BinaryExtensionTable"),
+    (KECCAKF_AIR_IDS, "Keccakf"),
+    (KECCAKF_TABLE_AIR_IDS, "KeccakfTable"),
+    (SHA_256_F_AIR_IDS, "Sha256f"),
+    (SHA_256_F_TABLE_AIR_IDS, "Sha256fTable"),
+    (BLAKE_2_BR_AIR_IDS, "Blake2br"),
+    (BLAKE_2_BR_TABLE_AIR_IDS, "Blake2brTable"),
+    (DMA_AIR_IDS, "Dma"),
+    (SPECIFIED_RANGES_AIR_IDS, "SpecifiedRanges"),
+];
+
+// Structure representing the 'execute' subcommand of cargo.
+#[derive(clap::Args)]
+#[command(author, about = "Execute a program with the prover executor, without proving", long_about = None, version = ZISK_VERSION_MESSAGE)]
+#[command(group(
+    clap::ArgGroup::new("input_mode")
+        .args(["asm", "emulator"])
+        .multiple(false)
+        .required(false)
+))]
+pub struct ZiskExecute {
+    /// Witness computation dynamic library path
+    #[clap(short = 'w', long)]
+    pub witness_lib: Option<PathBuf>,
+
+    /// ELF file path
+    #[clap(short = 'e', long)]
+    pub elf: PathBuf,
+
+    /// ASM file path, defaults to the one generated by rom-setup, if any
+    /// Optional, mutually exclusive with `--emulator`
+    #[clap(short = 's', long)]
+    pub asm: Option<PathBuf>,
+
+    /// Use prebuilt emulator (mutually exclusive with `--asm`)
+    #[clap(short = 'l', long, action = clap::ArgAction::SetTrue)]
+    pub emulator: bool,
+
+    /// Input path
+    #[clap(short = 'i', long)]
+    pub input: Option<PathBuf>,
+
+    /// Sha256f script path
+    #[clap(long)]
+    pub sha256f_script: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub resource_limits: ResourceLimitsArgs,
+
+    /// Verbosity (-v, -vv)
+    #[arg(short ='v', long, action = clap::ArgAction::Count, help = "Increase verbosity level")]
+    pub verbose: u8, // Using u8 to hold the number of `-v`
+}
+
+impl ZiskExecute {
+    pub fn run(&mut self) -> Result<()> {
+        println!("{} Execute", format!("{: >12}", "Command").bright_green().bold());
+        println!();
+
+        let sha256f_script = self.sha256f_script.clone().unwrap_or_else(get_default_sha256f_script);
+        if !sha256f_script.exists() {
+            return Err(anyhow::anyhow!(
+                "Sha256f script file not found at {}",
+                sha256f_script.display()
+            ));
+        }
+
+        // Without an explicit runner, use the assembly emulator generated by rom-setup if there
+        // is one, as the prover does, and the emulator otherwise
+        let emulator = cfg!(target_os = "macos") || self.emulator;
+        if emulator {
+            self.asm = None;
+        } else if self.asm.is_none() {
+            let stem = self.elf.file_stem().unwrap().to_str().unwrap();
+            let hash = get_elf_data_hash(&self.elf)
+                .map_err(|e| anyhow::anyhow!("Error computing ELF hash: {}", e))?;
+            let asm_path = std::env::var("HOME")
+                .ok()
+                .map(PathBuf::from)
+                .unwrap()
+                .join(DEFAULT_CACHE_PATH)
+                .join(format!("{stem}-{hash}-mt.bin"));
+            if asm_path.exists() {
+                self.asm = Some(asm_path);
+            }
+        } else if !self.asm.as_ref().unwrap().exists() {
+            return Err(anyhow::anyhow!(
+                "ASM file not found at {}",
+                self.asm.as_ref().unwrap().display()
+            ));
+        }
+        if self.asm.is_some() && self.input.is_none() {
+            return Err(anyhow::anyhow!("The assembly emulator needs an input file"));
+        }
+
+        self.print_command_info(&sha256f_script);
+
+        let start = Instant::now();
+
+        let library = unsafe { Library::new(self.get_witness_computation_lib())? };
+        let execute: Symbol<ZiskExecuteFn> = unsafe { library.get(b"execute_library")? };
+        // Execution errors panic in the executor, so catch them to report them as errors
+        let plan = panic::catch_unwind(AssertUnwindSafe(|| {
+            execute(
+                self.verbose.into(),
+                self.elf.clone(),
+                self.asm.clone(),
+                self.input.clone(),
+                sha256f_script,
+                self.resource_limits.resource_limits(),
+            )
+        }))
+        .map_err(|e| anyhow::anyhow!("Execution failed: {}", panic_message(&*e)))?
+        .map_err(|e| anyhow::anyhow!("Error executing the program: {}", e))?;
+
+        let elapsed = start.elapsed().as_secs_f64();
+        self.print_plan(&plan, elapsed);
+
+        Ok(())
+    }
+
+    fn print_command_info(&self, sha256f_script: &std::path::Path) {
+        println!(
+            "{: >12} {}",
+            "Witness Lib".bright_green().bold(),
+            self.get_witness_computation_lib().display()
+        );
+
+        println!("{: >12} {}", "Elf".bright_green().bold(), self.elf.display());
+
+        if self.asm.is_some() {
+            let asm_path = self.asm.as_ref().unwrap().display();
+            println!("{: >12} {}", "ASM runner".bright_green().bold(), asm_path);
+        } else {
+            println!(
+                "{: >12} {}",
+                "Emulator".bright_green().bold(),
+                "Running in emulator mode".bright_yellow()
+            );
+        }
+
+        if self.input.is_some() {
+            let inputs_path = self.input.as_ref().unwrap().display();
+            println!("{: >12} {}", "Inputs".bright_green().bold(), inputs_path);
+        }
+
+        println!("{: >12} {}", "Sha256f".bright_green().bold(), sha256f_script.display());
+
+        println!();
+    }
+
+    fn print_plan(&self, plan: &ZiskExecutionPlan, elapsed: f64) {
+        // The public values are the program output, printed as the emulator does
+        let len = plan.public_values.iter().map(|(index, _)| *index as usize + 1).max();
+        let mut output = vec![0u32; len.unwrap_or(0)];
+        for (index, value) in &plan.public_values {
+            output[*index as usize] = *value;
+        }
+        for value in &output {
+            println!("{:08x}", value);
+        }
+        println!();
+
+        println!(
+            "{}",
+            "    Zisk: --- EXECUTE SUMMARY ----------------------".bright_green().bold()
+        );
+        println!(
+            "                time: {:.2} seconds, steps: {}",
+            elapsed, plan.result.executed_steps
+        );
+        println!("                guest program {}", plan.result.exit_status);
+
+        println!("              ► Public values");
+        for (index, value) in &plan.public_values {
+            println!("                [{}] {} (0x{:08x})", index, value, value);
+        }
+
+        println!("              ► Instances");
+        for (airgroup_id, air_id, count) in &plan.instances {
+            let name = AIR_NAMES
+                .iter()
+                .find(|(ids, _)| *airgroup_id == ZISK_AIRGROUP_ID && ids.contains(air_id))
+                .map_or("Unknown", |(_, name)| *name);
+            println!("                {: <20} {}", name, count);
+        }
+        let total: usize = plan.instances.iter().map(|(_, _, count)| count).sum();
+        println!("                {: <20} {}", "Total", total);
+    }
+
+    /// Gets the witness computation library file location.
+    /// Uses the default one if not specified by user.
+    pub fn get_witness_computation_lib(&self) -> PathBuf {
+        if self.witness_lib.is_none() {
+            get_default_witness_computation_lib()
+        } else {
+            self.witness_lib.clone().unwrap()
+        }
+    }
+}
@@ -1,21 +1,29 @@
 mod build;
+mod cache;
+mod check_elf;
 mod check_setup;
 mod clean;
 mod common;
+mod execute;
 mod prove;
 mod rom_setup;
 mod run;
 mod sdk;
+mod test;
 mod verify_constraints;
 mod verify_stark;
 
 pub use build::*;
+pub use cache::*;
+pub use check_elf::*;
 pub use check_setup::*;
 pub use clean::*;
 pub use common::*;
+pub use execute::*;
 pub use prove::*;
 pub use rom_setup::*;
 pub use run::*;
 pub use sdk::*;
+pub use test::*;
 pub use verify_constraints::*;
 pub use verify_stark::*;
@@ -1,12 +1,17 @@
 use crate::{
-    commands::{cli_fail_if_macos, Field, ZiskLibInitFn},
-    proof_log,
+    commands::{
+        cli_fail_if_macos, panic_message, Field, ResourceLimitsArgs, ZiskBatchLibInitFn,
+        ZiskLibInitFn,
+    },
+    proof_bundle::{ProofBundle, ProofBundleOptions, PROOF_BUNDLE_FILE},
+    proof_log::{self, BatchProofEntry, BatchProofLog},
     ux::print_banner,
     ZISK_VERSION_MESSAGE,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
+use asm_runner::{AsmRunnerOptions, AsmRunnerOptionsBuilder};
 use colored::Colorize;
-use executor::ZiskExecutionResult;
+use executor::{SharedInputPath, ZiskExecutionResult};
 use libloading::{Library, Symbol};
 use log::info;
 use p3_goldilocks::Goldilocks;
@@ -15,14 +20,18 @@ use proofman_common::{
     initialize_logger, json_to_debug_instances_map, DebugInfo, ModeName, ProofOptions,
 };
 use rom_setup::{
-    gen_elf_hash, get_elf_bin_file_path, get_elf_data_hash, get_rom_blowup_factor,
-    DEFAULT_CACHE_PATH,
+    gen_elf_hash, get_elf_bin_file_path, get_elf_data_hash, get_file_hash,
+    get_proving_key_verkey_path, get_rom_blowup_factor, DEFAULT_CACHE_PATH,
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env, fs,
+    panic::{self, AssertUnwindSafe},
     path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Instant,
 };
+use witness::WitnessLibrary;
 
 use super::{get_default_proving_key, get_default_witness_computation_lib};
 
@@ -60,6 +69,15 @@ pub struct ZiskProve {
     #[clap(short = 'i', long)]
     pub input: Option<PathBuf>,
 
+    /// Directory of input files, proven one after the other with the program loaded once
+    #[clap(long, conflicts_with_all = ["input", "inputs_manifest", "debug"])]
+    pub input_dir: Option<PathBuf>,
+
+    /// File listing input files, one path per line relative to the file, proven one after the
+    /// other with the program loaded once
+    #[clap(long, conflicts_with_all = ["input", "debug"])]
+    pub inputs_manifest: Option<PathBuf>,
+
     /// Setup folder path
     #[clap(short = 'k', long)]
     pub proving_key: Option<PathBuf>,
@@ -87,6 +105,13 @@ pub struct ZiskProve {
     #[clap(short = 'd', long)]
     pub debug: Option<Option<String>>,
 
+    /// Proves the execution even if the guest program panics or ends with a non-zero exit code
+    #[clap(long, default_value_t = false)]
+    pub allow_panic: bool,
+
+    #[command(flatten)]
+    pub resource_limits: ResourceLimitsArgs,
+
     // PRECOMPILES OPTIONS
     /// Sha256f script path
     pub sha256f_script: Option<PathBuf>,
@@ -125,20 +150,9 @@ impl ZiskProve {
 
         let start = std::time::Instant::now();
 
-        if self.output_dir.join("proofs").exists() {
-            // In distributed mode two different processes may enter here at the same time and try to remove the same directory
-            if let Err(e) = fs::remove_dir_all(self.output_dir.join("proofs")) {
-                if e.kind() != std::io::ErrorKind::NotFound {
-                    panic!("Failed to remove the proofs directory: {:?}", e);
-                }
-            }
-        }
-
-        if let Err(e) = fs::create_dir_all(self.output_dir.join("proofs")) {
-            if e.kind() != std::io::ErrorKind::AlreadyExists {
-                // prevent collision in distributed mode
-                panic!("Failed to create the proofs directory: {:?}", e);
-            }
+        let batch_inputs = self.batch_inputs()?;
+        if batch_inputs.is_none() {
+            prepare_proofs_dir(&self.output_dir);
         }
 
         let default_cache_path =
@@ -190,10 +204,15 @@ impl ZiskProve {
                 .map_err(|e| anyhow::anyhow!("Error generating elf hash: {}", e));
         }
 
-        self.print_command_info(&sha256f_script);
+        self.print_command_info(&sha256f_script, batch_inputs.as_deref());
+
         let mut custom_commits_map: HashMap<String, PathBuf> = HashMap::new();
         custom_commits_map.insert("rom".to_string(), rom_bin_path);
 
+        if let Some(inputs) = batch_inputs {
+            return self.run_batch(&inputs, asm_rom, sha256f_script, custom_commits_map);
+        }
+
         let mut witness_lib;
         let proof_id;
         if debug_info.std_mode.name == ModeName::Debug {
@@ -209,6 +228,8 @@ impl ZiskProve {
                         asm_rom,
                         self.input.clone(),
                         sha256f_script,
+                        self.allow_panic,
+                        self.resource_limits.resource_limits(),
                     )
                     .expect("Failed to initialize witness library");
 
@@ -242,6 +263,8 @@ impl ZiskProve {
                         asm_rom,
                         self.input.clone(),
                         sha256f_script,
+                        self.allow_panic,
+                        self.resource_limits.resource_limits(),
                     )
                     .expect("Failed to initialize witness library");
 
@@ -266,11 +289,7 @@ impl ZiskProve {
 
         let elapsed = start.elapsed();
 
-        let result: ZiskExecutionResult = *witness_lib
-            .get_execution_result()
-            .ok_or_else(|| anyhow::anyhow!("No execution result found"))?
-            .downcast::<ZiskExecutionResult>()
-            .map_err(|_| anyhow::anyhow!("Failed to downcast execution result"))?;
+        let result = get_execution_result(&*witness_lib)?;
 
         let elapsed = elapsed.as_secs_f64();
         println!();
@@ -280,18 +299,280 @@ impl ZiskProve {
         }
         info!("              ► Statistics");
         info!("                time: {} seconds, steps: {}", elapsed, result.executed_steps);
+        if !result.exit_status.success() {
+            info!("{} Guest program {}", "[WARN]".yellow(), result.exit_status);
+        }
 
         if let Some(proof_id) = proof_id {
             let logs = proof_log::ProofLog::new(result.executed_steps, proof_id, elapsed);
             let log_path = self.output_dir.join("result.json");
             proof_log::ProofLog::write_json_log(&log_path, &logs)
                 .map_err(|e| anyhow::anyhow!("Error generating log: {}", e))?;
+
+            if self.aggregation {
+                self.write_proof_bundle(
+                    &self.output_dir,
+                    self.input.as_deref(),
+                    proof_id,
+                    result.executed_steps,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the inputs to prove in a batch, from the input directory or the inputs manifest
+    fn batch_inputs(&self) -> Result<Option<Vec<PathBuf>>> {
+        let inputs = if let Some(input_dir) = &self.input_dir {
+            let mut inputs = Vec::new();
+            for entry in fs::read_dir(input_dir)
+                .with_context(|| format!("Failed to read input dir {}", input_dir.display()))?
+            {
+                let path = entry?.path();
+                if path.is_file() {
+                    inputs.push(path);
+                }
+            }
+            inputs.sort();
+            inputs
+        } else if let Some(manifest) = &self.inputs_manifest {
+            let contents = fs::read_to_string(manifest).with_context(|| {
+                format!("Failed to read inputs manifest {}", manifest.display())
+            })?;
+            let base = manifest.parent().unwrap_or(Path::new(""));
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| base.join(line))
+                .collect()
+        } else {
+            return Ok(None);
+        };
+
+        if inputs.is_empty() {
+            return Err(anyhow::anyhow!("No inputs found to prove"));
+        }
+        Ok(Some(inputs))
+    }
+
+    /// Proves the inputs of a batch one after the other with the same witness library and proof
+    /// manager, so that the ZisK ROM, the assembly emulator and the proving key setup are loaded
+    /// only once, and writes a summary report.
+    /// The proofs of every input are written to their own subdirectory of the output dir, and a
+    /// failed input does not stop the batch; after a panic, the witness library is initialized
+    /// again.
+    fn run_batch(
+        &self,
+        inputs: &[PathBuf],
+        asm_rom: Option<PathBuf>,
+        sha256f_script: PathBuf,
+        custom_commits_map: HashMap<String, PathBuf>,
+    ) -> Result<()> {
+        let start = Instant::now();
+
+        let input_path: SharedInputPath = Arc::new(RwLock::new(None));
+        let library = unsafe { Library::new(self.get_witness_computation_lib())? };
+        let init_witness_lib = || -> Result<Box<dyn WitnessLibrary<Goldilocks>>> {
+            match self.field {
+                Field::Goldilocks => {
+                    let witness_lib_constructor: Symbol<ZiskBatchLibInitFn<Goldilocks>> =
+                        unsafe { library.get(b"init_library_batch")? };
+                    witness_lib_constructor(
+                        self.verbose.into(),
+                        self.elf.clone(),
+                        self.asm.clone(),
+                        asm_rom.clone(),
+                        input_path.clone(),
+                        sha256f_script.clone(),
+                        self.allow_panic,
+                        self.resource_limits.resource_limits(),
+                        self.asm_runner_options(),
+                    )
+                    .map_err(|e| anyhow::anyhow!("Failed to initialize witness library: {}", e))
+                }
+            }
+        };
+        let mut witness_lib = init_witness_lib()?;
+
+        let proofman = ProofMan::<Goldilocks>::new(
+            self.get_proving_key(),
+            custom_commits_map,
+            false,
+            self.aggregation,
+            self.final_snark,
+            self.verbose.into(),
+        )
+        .map_err(|e| anyhow::anyhow!("Error initializing proof manager: {}", e))?;
+
+        let mut entries = Vec::with_capacity(inputs.len());
+        let mut names = HashSet::new();
+        for (i, input) in inputs.iter().enumerate() {
+            // Inputs with the same file name in different directories get their own output dir
+            let mut name = input
+                .file_stem()
+                .map_or_else(|| format!("input-{}", i), |stem| stem.to_string_lossy().into_owned());
+            if !names.insert(name.clone()) {
+                name = format!("{}-{}", name, i);
+                names.insert(name.clone());
+            }
+            let output_dir = self.output_dir.join(name);
+
+            println!(
+                "{} [{}/{}] {}",
+                format!("{: >12}", "Proving").bright_green().bold(),
+                i + 1,
+                inputs.len(),
+                input.display()
+            );
+
+            let input_start = Instant::now();
+            // Execution errors panic in the witness computation, so catch them to go on with the
+            // next input
+            let result = match panic::catch_unwind(AssertUnwindSafe(|| {
+                self.prove_batch_input(
+                    &proofman,
+                    &mut *witness_lib,
+                    &input_path,
+                    input,
+                    &output_dir,
+                )
+            })) {
+                Ok(result) => result,
+                Err(e) => {
+                    // The panic may have left the library in the middle of an execution, e.g.
+                    // with the assembly emulator running or a poisoned lock, so the old one is
+                    // dropped, stopping its assembly emulator, and a new one is started
+                    drop(witness_lib);
+                    witness_lib = init_witness_lib()?;
+                    Err(anyhow::anyhow!("Proof generation panicked: {}", panic_message(&*e)))
+                }
+            };
+            let time = input_start.elapsed().as_secs_f64();
+
+            let entry = match result {
+                Ok((proof_id, steps)) => {
+                    println!(
+                        "{} {} ({:.2} seconds, {} steps)",
+                        "[OK]".green().bold(),
+                        input.display(),
+                        time,
+                        steps
+                    );
+                    BatchProofEntry {
+                        input: input.clone(),
+                        output_dir,
+                        id: proof_id,
+                        cycles: Some(steps),
+                        time,
+                        error: None,
+                    }
+                }
+                Err(e) => {
+                    println!("{} {}: {:#}", "[FAIL]".red().bold(), input.display(), e);
+                    BatchProofEntry {
+                        input: input.clone(),
+                        output_dir,
+                        id: None,
+                        cycles: None,
+                        time,
+                        error: Some(format!("{:#}", e)),
+                    }
+                }
+            };
+            entries.push(entry);
         }
 
+        let log = BatchProofLog::new(entries, start.elapsed().as_secs_f64());
+        let log_path = self.output_dir.join("batch_summary.json");
+        fs::create_dir_all(&self.output_dir)?;
+        BatchProofLog::write_json_log(&log_path, &log)
+            .map_err(|e| anyhow::anyhow!("Error generating log: {}", e))?;
+
+        println!();
+        info!("{}", "    Zisk: --- BATCH PROVE SUMMARY ------------------".bright_green().bold());
+        info!(
+            "                proven: {}, failed: {}, time: {:.2} seconds",
+            inputs.len() - log.failed(),
+            log.failed(),
+            start.elapsed().as_secs_f64()
+        );
+        info!("                Summary: {}", log_path.display());
+
+        if log.failed() > 0 {
+            return Err(anyhow::anyhow!(
+                "{} of {} inputs failed to prove, see {}",
+                log.failed(),
+                inputs.len(),
+                log_path.display()
+            ));
+        }
         Ok(())
     }
 
-    fn print_command_info(&self, sha256f_script: &Path) {
+    /// Returns the options of the resident assembly emulator of a batch: its output and metrics
+    /// are shown with `-v`, and its verbose log with `-vv`
+    fn asm_runner_options(&self) -> AsmRunnerOptions {
+        AsmRunnerOptionsBuilder::new()
+            .log_output(self.verbose > 0)
+            .metrics(self.verbose > 0)
+            .verbose(self.verbose > 1)
+            .build()
+    }
+
+    /// Proves one input of a batch into `output_dir`, returning the proof ID and executed steps
+    fn prove_batch_input(
+        &self,
+        proofman: &ProofMan<Goldilocks>,
+        witness_lib: &mut dyn WitnessLibrary<Goldilocks>,
+        input_path: &SharedInputPath,
+        input: &Path,
+        output_dir: &Path,
+    ) -> Result<(Option<String>, u64)> {
+        prepare_proofs_dir(output_dir);
+        *input_path.write().unwrap() = Some(input.to_path_buf());
+
+        let start = Instant::now();
+        let proof_id = proofman
+            .generate_proof_from_lib(
+                &mut *witness_lib,
+                output_dir.to_path_buf(),
+                ProofOptions::new(
+                    false,
+                    self.verbose.into(),
+                    self.aggregation,
+                    self.final_snark,
+                    self.verify_proofs,
+                    DebugInfo::default(),
+                ),
+            )
+            .map_err(|e| anyhow::anyhow!("Error generating proof: {}", e))?;
+        let elapsed = start.elapsed().as_secs_f64();
+
+        let result = get_execution_result(&*witness_lib)?;
+        if !result.exit_status.success() {
+            info!("{} Guest program {}", "[WARN]".yellow(), result.exit_status);
+        }
+        if let Some(proof_id) = &proof_id {
+            let logs = proof_log::ProofLog::new(result.executed_steps, proof_id.clone(), elapsed);
+            proof_log::ProofLog::write_json_log(&output_dir.join("result.json"), &logs)
+                .map_err(|e| anyhow::anyhow!("Error generating log: {}", e))?;
+
+            if self.aggregation {
+                self.write_proof_bundle(
+                    output_dir,
+                    Some(input),
+                    proof_id.clone(),
+                    result.executed_steps,
+                )?;
+            }
+        }
+
+        Ok((proof_id, result.executed_steps))
+    }
+
+    fn print_command_info(&self, sha256f_script: &Path, batch_inputs: Option<&[PathBuf]>) {
         println!("{} Prove", format!("{: >12}", "Command").bright_green().bold());
         println!(
             "{: >12} {}",
@@ -317,6 +598,16 @@ impl ZiskProve {
             println!("{: >12} {}", "Inputs".bright_green().bold(), inputs_path);
         }
 
+        if let Some(batch_inputs) = batch_inputs {
+            let source = self.input_dir.as_ref().or(self.inputs_manifest.as_ref()).unwrap();
+            println!(
+                "{: >12} {} ({} inputs)",
+                "Batch".bright_green().bold(),
+                source.display(),
+                batch_inputs.len()
+            );
+        }
+
         println!(
             "{: >12} {}",
             "Proving key".bright_green().bold(),
@@ -331,6 +622,44 @@ impl ZiskProve {
         println!();
     }
 
+    /// Writes the final proof, its public values and the proof metadata into a proof bundle
+    fn write_proof_bundle(
+        &self,
+        output_dir: &Path,
+        input: Option<&Path>,
+        proof_id: String,
+        steps: u64,
+    ) -> Result<()> {
+        let proof_path = output_dir.join("proofs").join("vadcop_final_proof.json");
+        let publics_path = output_dir.join("publics.json");
+        if !proof_path.exists() || !publics_path.exists() {
+            info!("{} Final proof not found, skipping the proof bundle", "[WARN]".yellow());
+            return Ok(());
+        }
+
+        let options = ProofBundleOptions {
+            field: self.field.to_string(),
+            aggregation: self.aggregation,
+            final_snark: self.final_snark,
+            asm: self.asm.is_some(),
+            verkey_hash: get_file_hash(&get_proving_key_verkey_path(&self.get_proving_key()))?,
+            input_hash: input.map(get_file_hash).transpose()?,
+        };
+        let bundle = ProofBundle::new(
+            &proof_path,
+            &publics_path,
+            get_elf_data_hash(&self.elf)?,
+            options,
+            proof_id,
+            steps,
+        )?;
+
+        let bundle_path = output_dir.join(PROOF_BUNDLE_FILE);
+        bundle.save(&bundle_path)?;
+        info!("                Proof bundle: {}", bundle_path.display());
+        Ok(())
+    }
+
     /// Gets the witness computation library file location.
     /// Uses the default one if not specified by user.
     pub fn get_witness_computation_lib(&self) -> PathBuf {
@@ -351,3 +680,32 @@ impl ZiskProve {
         }
     }
 }
+
+/// Creates an empty proofs directory in the output dir
+fn prepare_proofs_dir(output_dir: &Path) {
+    if output_dir.join("proofs").exists() {
+        // In distributed mode two different processes may enter here at the same time and try to remove the same directory
+        if let Err(e) = fs::remove_dir_all(output_dir.join("proofs")) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                panic!("Failed to remove the proofs directory: {:?}", e);
+            }
+        }
+    }
+
+    if let Err(e) = fs::create_dir_all(output_dir.join("proofs")) {
+        if e.kind() != std::io::ErrorKind::AlreadyExists {
+            // prevent collision in distributed mode
+            panic!("Failed to create the proofs directory: {:?}", e);
+        }
+    }
+}
+
+fn get_execution_result(
+    witness_lib: &dyn WitnessLibrary<Goldilocks>,
+) -> Result<ZiskExecutionResult> {
+    Ok(*witness_lib
+        .get_execution_result()
+        .ok_or_else(|| anyhow::anyhow!("No execution result found"))?
+        .downcast::<ZiskExecutionResult>()
+        .map_err(|_| anyhow::anyhow!("Failed to downcast execution result"))?)
+}
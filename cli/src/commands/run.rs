@@ -147,6 +147,10 @@ impl ZiskRun {
         // Execute the command
         let status = command.status().context("Failed to execute cargo run command")?;
         if !status.success() {
+            // Exit with the guest exit code, which the runner returns as its own exit code
+            if let Some(code) = status.code() {
+                std::process::exit(code);
+            }
             return Err(anyhow!("Cargo run command failed with status {}", status));
         }
 
@@ -0,0 +1,375 @@
+use crate::{ZISK_TARGET, ZISK_VERSION_MESSAGE};
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+use zisk_core::{elf_bytes2rom, ZiskRom, DEFAULT_MAX_STEPS_STR};
+use ziskemu::{EmulationExitStatus, EmulationResult, ZiskEmulator};
+
+/// Exit code of a guest test that panics without its expected message, as in
+/// `ziskos::test_harness`
+const PANIC_MISMATCH_EXIT_CODE: u64 = 3;
+
+// Structure representing the 'test' subcommand of cargo.
+#[derive(clap::Args)]
+#[command(author, about = "Build the guest tests and run each of them in the ZisK emulator", long_about = None, version = ZISK_VERSION_MESSAGE)]
+pub struct ZiskTest {
+    #[clap(short = 'F', long)]
+    features: Option<String>,
+
+    #[clap(long)]
+    all_features: bool,
+
+    #[clap(long)]
+    release: bool,
+
+    #[clap(long)]
+    no_default_features: bool,
+
+    /// Test targets to run, all the integration tests by default
+    #[clap(long = "test", value_name = "NAME")]
+    tests: Vec<String>,
+
+    /// Run the unit tests of the library, which must be built with `harness = false`
+    #[clap(long)]
+    lib: bool,
+
+    /// Run the unit tests of the binaries, which must be built with `harness = false`
+    #[clap(long)]
+    bins: bool,
+
+    /// Run the ignored tests too
+    #[clap(long)]
+    include_ignored: bool,
+
+    /// Maximum number of steps of every test
+    #[clap(short = 'n', long, default_value = DEFAULT_MAX_STEPS_STR)]
+    max_steps: u64,
+
+    /// Only run the tests whose name is exactly the filter
+    #[clap(long)]
+    exact: bool,
+
+    /// Show the output of the tests that pass too
+    #[clap(long)]
+    show_output: bool,
+
+    /// Only run the tests whose name contains this filter
+    filter: Option<String>,
+}
+
+/// A test listed by a guest test program
+#[derive(Debug, PartialEq, Eq)]
+struct GuestTest {
+    name: String,
+    should_panic: bool,
+    ignored: bool,
+}
+
+/// Result of a guest test
+struct GuestTestResult {
+    name: String,
+    result: EmulationResult,
+    passed: bool,
+}
+
+impl ZiskTest {
+    pub fn run(&self) -> Result<()> {
+        let test_elfs = self.build_tests()?;
+
+        let mut results = Vec::new();
+        let mut filtered_out = 0;
+        let mut ignored = 0;
+        for elf in &test_elfs {
+            let rom = elf_bytes2rom(&fs::read(elf)?)
+                .map_err(|e| anyhow!("Failed to transpile {}: {}", elf.display(), e))?;
+            let Some(tests) = self.list_tests(elf, &rom)? else {
+                println!(
+                    "{} Skipping {}, which does not use the ziskos test harness",
+                    "[WARN]".yellow(),
+                    elf.display()
+                );
+                continue;
+            };
+
+            let (tests, skipped): (Vec<_>, Vec<_>) =
+                tests.into_iter().partition(|test| self.matches(&test.name));
+            filtered_out += skipped.len();
+
+            println!("{} {}", "Running".bright_green().bold(), elf.display());
+            println!("\nrunning {} tests", tests.len());
+            for test in tests {
+                if test.ignored && !self.include_ignored {
+                    println!("test {} ... {}", test.name, "ignored".yellow());
+                    ignored += 1;
+                    continue;
+                }
+                let result = self.run_test(&rom, &test)?;
+                let status = if result.passed { "ok".green() } else { "FAILED".red() };
+                println!("test {} ... {} ({} steps)", result.name, status, result.result.steps);
+                results.push(result);
+            }
+            println!();
+        }
+
+        self.print_summary(&results, ignored, filtered_out)
+    }
+
+    /// Builds the test targets for the ZisK target, returning the generated ELF files
+    fn build_tests(&self) -> Result<Vec<PathBuf>> {
+        let mut command = Command::new("cargo");
+        command.args(["+zisk", "test", "--no-run"]);
+
+        if let Some(features) = &self.features {
+            command.arg("--features").arg(features);
+        }
+        if self.all_features {
+            command.arg("--all-features");
+        }
+        if self.no_default_features {
+            command.arg("--no-default-features");
+        }
+        if self.release {
+            command.arg("--release");
+        }
+        // Unit tests use the libtest harness, which does not run inside ZisK, so only build the
+        // integration tests unless the unit tests are requested
+        if self.tests.is_empty() && !self.lib && !self.bins {
+            command.args(["--test", "*"]);
+        }
+        for test in &self.tests {
+            command.arg("--test").arg(test);
+        }
+        if self.lib {
+            command.arg("--lib");
+        }
+        if self.bins {
+            command.arg("--bins");
+        }
+
+        command.args(["--target", ZISK_TARGET]);
+        command.args(["--message-format", "json-render-diagnostics"]);
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::inherit());
+
+        let mut child = command.spawn().context("Failed to execute cargo test command")?;
+        let stdout = child.stdout.take().context("Failed to read cargo test output")?;
+
+        // Collect the test executables built, from the cargo JSON messages
+        let mut elfs = Vec::new();
+        for line in BufReader::new(stdout).lines() {
+            let message: serde_json::Value = match serde_json::from_str(&line?) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            if message["reason"] != "compiler-artifact" {
+                continue;
+            }
+            // Unit tests are built with the kind of their target, but with the test profile
+            let is_test = message["profile"]["test"] == true;
+            if let (true, Some(executable)) = (is_test, message["executable"].as_str()) {
+                elfs.push(PathBuf::from(executable));
+            }
+        }
+
+        let status = child.wait().context("Failed to execute cargo test command")?;
+        if !status.success() {
+            return Err(anyhow!("Cargo test command failed with status {}", status));
+        }
+        if elfs.is_empty() {
+            return Err(anyhow!("Cargo test did not generate any test ELF file"));
+        }
+        Ok(elfs)
+    }
+
+    /// Lists the tests of a test program, running it without input, or returns None if the
+    /// program does not list its tests as the ziskos test harness does
+    fn list_tests(&self, elf: &Path, rom: &ZiskRom) -> Result<Option<Vec<GuestTest>>> {
+        let result = ZiskEmulator::builder()
+            .rom(rom)
+            .max_steps(self.max_steps)
+            .run()
+            .map_err(|e| anyhow!("Failed to list the tests of {}: {}", elf.display(), e))?;
+        if !result.exit_status.success() {
+            return Ok(None);
+        }
+
+        Ok(parse_test_list(&String::from_utf8_lossy(&result.stdout)))
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match &self.filter {
+            Some(filter) if self.exact => name == filter,
+            Some(filter) => name.contains(filter.as_str()),
+            None => true,
+        }
+    }
+
+    /// Runs a test in its own emulation, passing its name as the input data
+    fn run_test(&self, rom: &ZiskRom, test: &GuestTest) -> Result<GuestTestResult> {
+        let result = ZiskEmulator::builder()
+            .rom(rom)
+            .input(test.name.as_bytes())
+            .max_steps(self.max_steps)
+            .run()
+            .map_err(|e| anyhow!("Failed to run test {}: {}", test.name, e))?;
+
+        let passed = test_passed(test, &result.exit_status);
+        Ok(GuestTestResult { name: test.name.clone(), result, passed })
+    }
+
+    fn print_summary(
+        &self,
+        results: &[GuestTestResult],
+        ignored: usize,
+        filtered_out: usize,
+    ) -> Result<()> {
+        let (passed, failed): (Vec<_>, Vec<_>) = results.iter().partition(|result| result.passed);
+
+        let shown = if self.show_output { results.iter().collect() } else { failed.clone() };
+        for result in shown.iter().filter(|result| !result.result.stdout.is_empty()) {
+            println!("---- {} stdout ----", result.name);
+            print!("{}", String::from_utf8_lossy(&result.result.stdout));
+            println!();
+        }
+        for result in shown.iter().filter(|result| !result.result.stderr.is_empty()) {
+            println!("---- {} stderr ----", result.name);
+            print!("{}", String::from_utf8_lossy(&result.result.stderr));
+            println!();
+        }
+
+        if !failed.is_empty() {
+            println!("failures:");
+            for result in &failed {
+                let reason = match &result.result.exit_status {
+                    EmulationExitStatus::Completed(status) if status.success() => {
+                        "did not panic".to_string()
+                    }
+                    EmulationExitStatus::Completed(status)
+                        if status.exit_code == PANIC_MISMATCH_EXIT_CODE && !status.panicked() =>
+                    {
+                        "panicked without the expected message".to_string()
+                    }
+                    EmulationExitStatus::Completed(status) => status.to_string(),
+                    EmulationExitStatus::MaxStepsReached => {
+                        format!("did not end within {} steps", self.max_steps)
+                    }
+                    EmulationExitStatus::ResourceLimitExceeded(e) => e.to_string(),
+                };
+                println!("    {} {}", result.name, reason);
+            }
+            println!();
+        }
+
+        let status = if failed.is_empty() { "ok".green() } else { "FAILED".red() };
+        println!(
+            "test result: {}. {} passed; {} failed; {} ignored; {} filtered out",
+            status,
+            passed.len(),
+            failed.len(),
+            ignored,
+            filtered_out
+        );
+
+        if !failed.is_empty() {
+            return Err(anyhow!("{} tests failed", failed.len()));
+        }
+        Ok(())
+    }
+}
+
+/// Parses the tests listed by a guest test program, one per line as `<name>: test` or
+/// `<name>: should_panic`, followed by ` ignored` for the ignored tests, or returns None if the
+/// output does not follow this format
+fn parse_test_list(stdout: &str) -> Option<Vec<GuestTest>> {
+    stdout
+        .lines()
+        .map(|line| {
+            let (name, kind) = line.split_once(": ")?;
+            let (kind, ignored) = match kind.strip_suffix(" ignored") {
+                Some(kind) => (kind, true),
+                None => (kind, false),
+            };
+            let should_panic = match kind {
+                "test" => false,
+                "should_panic" => true,
+                _ => return None,
+            };
+            Some(GuestTest { name: name.to_string(), should_panic, ignored })
+        })
+        .collect()
+}
+
+/// Returns true if a test ended as expected.  A test that panics without its expected message
+/// ends with another exit code instead, so it does not count as panicked.
+fn test_passed(test: &GuestTest, exit_status: &EmulationExitStatus) -> bool {
+    match exit_status {
+        EmulationExitStatus::Completed(status) if test.should_panic => status.panicked(),
+        exit_status => exit_status.success(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_core::{GuestExitStatus, ResourceLimit, ResourceLimitExceeded};
+
+    fn test(name: &str, should_panic: bool, ignored: bool) -> GuestTest {
+        GuestTest { name: name.to_string(), should_panic, ignored }
+    }
+
+    #[test]
+    fn test_parse_test_list() {
+        let stdout = "adds: test\noverflows: should_panic\nslow: test ignored\n";
+        assert_eq!(
+            parse_test_list(stdout),
+            Some(vec![
+                test("adds", false, false),
+                test("overflows", true, false),
+                test("slow", false, true),
+            ])
+        );
+        assert_eq!(parse_test_list(""), Some(vec![]));
+
+        assert_eq!(parse_test_list("adds: test\nHello world!\n"), None);
+        assert_eq!(parse_test_list("adds: bench\n"), None);
+        assert_eq!(parse_test_list("adds test\n"), None);
+    }
+
+    #[test]
+    fn test_test_passed() {
+        let completed = |exit_code, panic_message: Option<&str>| {
+            EmulationExitStatus::Completed(GuestExitStatus {
+                exit_code,
+                panic_message: panic_message.map(str::to_string),
+            })
+        };
+        let passed = completed(0, None);
+        let panicked = completed(101, Some("panicked at src/main.rs:1:1:\nboom"));
+        let mismatch = completed(PANIC_MISMATCH_EXIT_CODE, None);
+
+        let normal = test("adds", false, false);
+        assert!(test_passed(&normal, &passed));
+        assert!(!test_passed(&normal, &panicked));
+        assert!(!test_passed(&normal, &completed(2, None)));
+        assert!(!test_passed(&normal, &EmulationExitStatus::MaxStepsReached));
+
+        let should_panic = test("overflows", true, false);
+        assert!(test_passed(&should_panic, &panicked));
+        assert!(!test_passed(&should_panic, &passed));
+        assert!(!test_passed(&should_panic, &mismatch));
+        assert!(!test_passed(&should_panic, &EmulationExitStatus::MaxStepsReached));
+        assert!(!test_passed(
+            &should_panic,
+            &EmulationExitStatus::ResourceLimitExceeded(ResourceLimitExceeded {
+                limit: ResourceLimit::HeapSize,
+                used: 2,
+                max: 1,
+            })
+        ));
+    }
+}
@@ -16,6 +16,7 @@ use std::{
     env, fs,
     path::{Path, PathBuf},
 };
+use zisk_core::ResourceLimits;
 
 use crate::{
     commands::{cli_fail_if_gpu_mode, cli_fail_if_macos, Field, ZiskLibInitFn},
@@ -174,6 +175,9 @@ impl ZiskVerifyConstraints {
                     asm_rom,
                     self.input.clone(),
                     sha256f_script,
+                    // Failed executions are checked like any other
+                    true,
+                    ResourceLimits::default(),
                 )
                 .expect("Failed to initialize witness library");
 
@@ -2,15 +2,17 @@ use anyhow::{anyhow, Ok, Result};
 use clap::Parser;
 use colored::Colorize;
 use proofman_common::initialize_logger;
-use std::io::Read;
-use std::{fs::File, path::PathBuf};
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
 
 use p3_field::PrimeCharacteristicRing;
 use p3_goldilocks::Goldilocks;
 
 use proofman::verify_proof_from_file;
+use rom_setup::{get_file_hash, ProgramVkey};
 
 use crate::commands::cli_fail_if_macos;
+use crate::proof_bundle::{read_publics, ProofBundle};
 use crate::ZISK_VERSION_MESSAGE;
 
 use super::{get_default_stark_info, get_default_verifier_bin, get_default_verkey};
@@ -19,8 +21,19 @@ use super::{get_default_stark_info, get_default_verifier_bin, get_default_verkey
 #[command(author, about, long_about = None, version = ZISK_VERSION_MESSAGE)]
 #[command(propagate_version = true)]
 pub struct ZiskVerify {
-    #[clap(short = 'p', long)]
-    pub proof: String,
+    #[clap(short = 'p', long, required_unless_present = "bundle")]
+    pub proof: Option<String>,
+
+    /// Proof bundle generated by prove, with the proof, its public values and metadata, all of
+    /// them checked; its ELF hash and ROM root are checked against the program verification key,
+    /// which is required
+    #[clap(
+        short = 'b',
+        long,
+        conflicts_with_all = ["proof", "public_inputs"],
+        requires = "program_vkey"
+    )]
+    pub bundle: Option<PathBuf>,
 
     #[clap(short = 's', long)]
     pub stark_info: Option<String>,
@@ -34,6 +47,11 @@ pub struct ZiskVerify {
     #[clap(short = 'u', long)]
     pub public_inputs: Option<PathBuf>,
 
+    /// Program verification key generated by rom-setup, to check that the proof corresponds to
+    /// a specific ELF; requires the public inputs or a bundle
+    #[clap(long)]
+    pub program_vkey: Option<PathBuf>,
+
     /// Verbosity (-v, -vv)
     #[arg(short = 'v', long, action = clap::ArgAction::Count, help = "Increase verbosity level")]
     pub verbose: u8, // Using u8 to hold the number of `-v`
@@ -50,25 +68,81 @@ impl ZiskVerify {
 
         initialize_logger(self.verbose.into());
 
-        let publics = if let Some(publics) = &self.public_inputs {
-            let mut contents = String::new();
-            let mut file = File::open(publics).unwrap();
-
-            let _ = file
-                .read_to_string(&mut contents)
-                .map_err(|err| format!("Failed to read public inputs file: {}", err));
-            let verkey_json_string: Vec<String> = serde_json::from_str(&contents).unwrap();
-            let verkey_json: Vec<u64> = verkey_json_string
-                .iter()
-                .map(|s| s.parse::<u64>().expect("Failed to parse string as u64"))
-                .collect();
-            Some(verkey_json.into_iter().map(Goldilocks::from_u64).collect::<Vec<Goldilocks>>())
+        let bundle = self.bundle.as_deref().map(ProofBundle::from_file).transpose()?;
+
+        let publics = if let Some(bundle) = &bundle {
+            let publics = bundle.check()?;
+            if bundle.options.verkey_hash != get_file_hash(&PathBuf::from(self.get_verkey()))? {
+                return Err(anyhow!(
+                    "Proof bundle was generated with a different proving key than {}",
+                    self.get_verkey()
+                ));
+            }
+            if bundle.zisk_version != env!("CARGO_PKG_VERSION") {
+                println!(
+                    "{}:     {} {}",
+                    Self::NAME,
+                    "Proof bundle was generated by ZisK".bright_yellow(),
+                    bundle.zisk_version
+                );
+            }
+            println!("{}:     ELF hash: {}", Self::NAME, bundle.elf_hash);
+            println!("{}:     Outputs: {:?}", Self::NAME, bundle.outputs);
+            Some(publics)
+        } else if let Some(publics) = &self.public_inputs {
+            Some(read_publics(publics)?)
         } else {
             None
         };
 
+        if let Some(program_vkey) = &self.program_vkey {
+            let vkey = ProgramVkey::from_file(program_vkey)?;
+            vkey.check_verkey(&PathBuf::from(self.get_verkey()))?;
+            let Some(publics) = &publics else {
+                return Err(anyhow!(
+                    "The program verification key requires the public inputs (-u) or a bundle (-b)"
+                ));
+            };
+            vkey.check_publics(publics)?;
+            if let Some(bundle) = &bundle {
+                if bundle.elf_hash != vkey.elf_hash {
+                    return Err(anyhow!(
+                        "Proof bundle ELF hash {} does not match the program verification key",
+                        bundle.elf_hash
+                    ));
+                }
+            }
+            println!(
+                "{}:     {} {}",
+                Self::NAME,
+                "Proof corresponds to program verification key".bright_green(),
+                vkey.digest()
+            );
+        }
+
+        let publics = publics
+            .map(|publics| publics.into_iter().map(Goldilocks::from_u64).collect::<Vec<_>>());
+
+        // The verifier reads the proof from a file, so the proof of a bundle is written to a
+        // temporary one, removed when dropped
+        let bundle_proof = bundle
+            .as_ref()
+            .map(|bundle| -> Result<NamedTempFile> {
+                let mut file = tempfile::Builder::new()
+                    .prefix("zisk_bundle_proof_")
+                    .suffix(".json")
+                    .tempfile()?;
+                serde_json::to_writer(&mut file, &bundle.proof)?;
+                Ok(file)
+            })
+            .transpose()?;
+        let proof = match &bundle_proof {
+            Some(file) => file.path().display().to_string(),
+            None => self.proof.clone().unwrap(),
+        };
+
         let valid = verify_proof_from_file::<Goldilocks>(
-            self.proof.clone(),
+            proof.clone(),
             self.get_stark_info(),
             self.get_verifier_bin(),
             self.get_verkey(),
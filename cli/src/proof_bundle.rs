@@ -0,0 +1,274 @@
+use anyhow::{anyhow, Context, Result};
+use rom_setup::ROM_ROOT_PUBLICS;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use zisk_pil::PILOUT_HASH;
+
+/// Version of the proof bundle format
+pub const PROOF_BUNDLE_VERSION: u32 = 1;
+
+/// File name of the proof bundle, in the prove output directory
+pub const PROOF_BUNDLE_FILE: &str = "proof_bundle.json";
+
+/// Options the proof was generated with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundleOptions {
+    pub field: String,
+    pub aggregation: bool,
+    pub final_snark: bool,
+    /// Executed with the assembly emulator or with the Rust emulator
+    pub asm: bool,
+    /// Blake3 hash of the final verification key of the proving key
+    pub verkey_hash: String,
+    /// Blake3 hash of the input file, if any
+    pub input_hash: Option<String>,
+}
+
+/// Self-describing proof: the final proof together with its public values and everything needed
+/// to know which program, PIL and proving key it corresponds to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub version: u32,
+    pub zisk_version: String,
+    pub pilout_hash: String,
+    /// Blake3 hash of the ELF file
+    pub elf_hash: String,
+    /// Merkle root of the ROM custom commit
+    pub rom_root: [u64; ROM_ROOT_PUBLICS],
+    pub options: ProofBundleOptions,
+    pub proof_id: String,
+    pub steps: u64,
+    /// Public values, as in the publics file of the proof
+    pub publics: Vec<String>,
+    /// Public outputs of the program, decoded from the public values
+    pub outputs: Vec<u32>,
+    /// Final proof, as in the proof file
+    pub proof: serde_json::Value,
+}
+
+impl ProofBundle {
+    /// Creates the bundle of the final proof at `proof_path`, with the public values at
+    /// `publics_path`
+    pub fn new(
+        proof_path: &Path,
+        publics_path: &Path,
+        elf_hash: String,
+        options: ProofBundleOptions,
+        proof_id: String,
+        steps: u64,
+    ) -> Result<Self> {
+        let proof = fs::read_to_string(proof_path)
+            .with_context(|| format!("Failed to read proof file {}", proof_path.display()))?;
+        let proof = serde_json::from_str(&proof)
+            .with_context(|| format!("Failed to parse proof file {}", proof_path.display()))?;
+        let publics = read_publics(publics_path)?;
+
+        Ok(Self {
+            version: PROOF_BUNDLE_VERSION,
+            zisk_version: env!("CARGO_PKG_VERSION").to_string(),
+            pilout_hash: PILOUT_HASH.to_string(),
+            elf_hash,
+            rom_root: rom_root(&publics)?,
+            options,
+            proof_id,
+            steps,
+            outputs: outputs(&publics),
+            publics: publics.iter().map(|value| value.to_string()).collect(),
+            proof,
+        })
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read proof bundle {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse proof bundle {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write proof bundle {}", path.display()))
+    }
+
+    /// Returns the public values of the proof
+    pub fn publics(&self) -> Result<Vec<u64>> {
+        parse_publics(&self.publics)
+    }
+
+    /// Checks that the metadata of the bundle is consistent with its public values and with the
+    /// current PIL, returning the public values
+    pub fn check(&self) -> Result<Vec<u64>> {
+        if self.version != PROOF_BUNDLE_VERSION {
+            return Err(anyhow!(
+                "Unsupported proof bundle version {}, expected {}",
+                self.version,
+                PROOF_BUNDLE_VERSION
+            ));
+        }
+        if self.pilout_hash != PILOUT_HASH {
+            return Err(anyhow!(
+                "Proof was generated for PIL {}, but this binary uses {}",
+                self.pilout_hash,
+                PILOUT_HASH
+            ));
+        }
+        let publics = self.publics()?;
+        if rom_root(&publics)? != self.rom_root {
+            return Err(anyhow!("ROM root does not match the public values of the proof"));
+        }
+        if outputs(&publics) != self.outputs {
+            return Err(anyhow!("Outputs do not match the public values of the proof"));
+        }
+        Ok(publics)
+    }
+}
+
+/// Reads a publics file, a JSON array of the public values as strings
+pub fn read_publics(path: &Path) -> Result<Vec<u64>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read public inputs file {}", path.display()))?;
+    let publics: Vec<String> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse public inputs file {}", path.display()))?;
+    parse_publics(&publics)
+}
+
+fn parse_publics(publics: &[String]) -> Result<Vec<u64>> {
+    publics
+        .iter()
+        .map(|value| {
+            value.parse::<u64>().with_context(|| format!("Invalid public value {}", value))
+        })
+        .collect()
+}
+
+fn rom_root(publics: &[u64]) -> Result<[u64; ROM_ROOT_PUBLICS]> {
+    publics
+        .get(..ROM_ROOT_PUBLICS)
+        .and_then(|root| root.try_into().ok())
+        .ok_or_else(|| anyhow!("Public values do not contain the ROM root"))
+}
+
+/// Decodes the public outputs, which follow the ROM root in the public values
+fn outputs(publics: &[u64]) -> Vec<u32> {
+    publics.iter().skip(ROM_ROOT_PUBLICS).map(|value| *value as u32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Returns a fresh temporary directory of this test
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("zisk_proof_bundle_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Creates a bundle whose public values are the ROM root `[1, 2, 3, 4]` and the outputs
+    /// `[5, 6]`
+    fn bundle(test: &str) -> ProofBundle {
+        let dir = temp_dir(test);
+        let proof_path = dir.join("vadcop_final_proof.json");
+        let publics_path = dir.join("publics.json");
+        fs::write(&proof_path, r#"{"proof": [1, 2, 3]}"#).unwrap();
+        fs::write(&publics_path, r#"["1", "2", "3", "4", "5", "6"]"#).unwrap();
+
+        let options = ProofBundleOptions {
+            field: "goldilocks".to_string(),
+            aggregation: true,
+            final_snark: false,
+            asm: true,
+            verkey_hash: "verkey-hash".to_string(),
+            input_hash: None,
+        };
+        ProofBundle::new(
+            &proof_path,
+            &publics_path,
+            "elf-hash".to_string(),
+            options,
+            "id".into(),
+            7,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_read_publics() {
+        let dir = temp_dir("read_publics");
+        let path = dir.join("publics.json");
+
+        fs::write(&path, r#"["1", "18446744073709551615"]"#).unwrap();
+        assert_eq!(read_publics(&path).unwrap(), [1, u64::MAX]);
+        fs::write(&path, "[]").unwrap();
+        assert!(read_publics(&path).unwrap().is_empty());
+
+        // Values must be strings of u64
+        fs::write(&path, "[1, 2]").unwrap();
+        assert!(read_publics(&path).is_err());
+        fs::write(&path, r#"["1", "-2"]"#).unwrap();
+        let e = read_publics(&path).unwrap_err();
+        assert!(format!("{:#}", e).contains("Invalid public value -2"));
+
+        fs::remove_file(&path).unwrap();
+        assert!(read_publics(&path).is_err());
+    }
+
+    #[test]
+    fn test_new_and_check() {
+        let bundle = bundle("new_and_check");
+        assert_eq!(bundle.version, PROOF_BUNDLE_VERSION);
+        assert_eq!(bundle.pilout_hash, PILOUT_HASH);
+        assert_eq!(bundle.rom_root, [1, 2, 3, 4]);
+        assert_eq!(bundle.outputs, [5, 6]);
+        assert_eq!(bundle.proof, serde_json::json!({"proof": [1, 2, 3]}));
+        assert_eq!(bundle.check().unwrap(), [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_check_mismatches() {
+        let bundle = bundle("check_mismatches");
+
+        let mut other = bundle.clone();
+        other.version = PROOF_BUNDLE_VERSION + 1;
+        let e = other.check().unwrap_err();
+        assert!(e.to_string().contains("Unsupported proof bundle version"));
+
+        let mut other = bundle.clone();
+        other.pilout_hash = "other".to_string();
+        let e = other.check().unwrap_err();
+        assert!(e.to_string().contains("PIL other"));
+
+        let mut other = bundle.clone();
+        other.rom_root[3] = 5;
+        let e = other.check().unwrap_err();
+        assert!(e.to_string().contains("ROM root"));
+
+        let mut other = bundle.clone();
+        other.outputs.push(7);
+        let e = other.check().unwrap_err();
+        assert!(e.to_string().contains("Outputs"));
+
+        // Public values without the ROM root
+        let mut other = bundle.clone();
+        other.publics.truncate(ROM_ROOT_PUBLICS - 1);
+        assert!(other.check().is_err());
+    }
+
+    #[test]
+    fn test_save_and_from_file() {
+        let bundle = bundle("save_and_from_file");
+        let path = temp_dir("save_and_from_file").join(PROOF_BUNDLE_FILE);
+        bundle.save(&path).unwrap();
+
+        let read = ProofBundle::from_file(&path).unwrap();
+        assert_eq!(serde_json::to_value(&read).unwrap(), serde_json::to_value(&bundle).unwrap());
+        assert_eq!(read.check().unwrap(), [1, 2, 3, 4, 5, 6]);
+
+        fs::write(&path, "{}").unwrap();
+        assert!(ProofBundle::from_file(&path).is_err());
+    }
+}
@@ -23,3 +23,51 @@ impl ProofLog {
         Ok(())
     }
 }
+
+/// Result of proving one of the inputs of a batch
+#[derive(Serialize)]
+pub struct BatchProofEntry {
+    pub input: PathBuf,
+    pub output_dir: PathBuf,
+    pub id: Option<String>,
+    pub cycles: Option<u64>,
+    pub time: f64,
+    pub error: Option<String>,
+}
+
+/// Summary report of a batch of proofs
+#[derive(Serialize)]
+pub struct BatchProofLog {
+    total: usize,
+    proven: usize,
+    failed: usize,
+    time: f64,
+    entries: Vec<BatchProofEntry>,
+}
+
+impl BatchProofLog {
+    pub fn new(entries: Vec<BatchProofEntry>, time: f64) -> Self {
+        let failed = entries.iter().filter(|entry| entry.error.is_some()).count();
+        BatchProofLog {
+            total: entries.len(),
+            proven: entries.len() - failed,
+            failed,
+            time,
+            entries,
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    pub fn write_json_log(
+        file_path: &PathBuf,
+        entries: &BatchProofLog,
+    ) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(entries)?;
+        let mut file = File::create(file_path)?;
+        file.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
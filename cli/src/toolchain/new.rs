@@ -1,39 +1,152 @@
 use anyhow::Result;
 use clap::Parser;
-use std::{fs, path::Path, process::Command};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use yansi::Paint;
 
+/// ZisK repository, used for the `ziskos` and `ziskemu` dependencies of new projects
+const ZISK_REPO_URL: &str = "https://github.com/0xPolygonHermez/zisk.git";
+
+/// A file of the project template
+struct TemplateFile {
+    path: &'static str,
+    contents: &'static str,
+    /// Only created for projects with a library crate
+    lib_only: bool,
+}
+
+/// Project template, embedded so that projects can be created without network access
+const TEMPLATE: &[TemplateFile] = &[
+    TemplateFile {
+        path: "Cargo.toml",
+        contents: include_str!("template/Cargo.toml.tmpl"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: ".gitignore",
+        contents: include_str!("template/gitignore"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "guest/Cargo.toml",
+        contents: include_str!("template/guest/Cargo.toml.tmpl"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "guest/build.rs",
+        contents: include_str!("template/guest/build.rs"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "guest/src/main.rs",
+        contents: include_str!("template/guest/src/main.rs"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "lib/Cargo.toml",
+        contents: include_str!("template/lib/Cargo.toml.tmpl"),
+        lib_only: true,
+    },
+    TemplateFile {
+        path: "lib/src/lib.rs",
+        contents: include_str!("template/lib/src/lib.rs"),
+        lib_only: true,
+    },
+    TemplateFile {
+        path: "host/Cargo.toml",
+        contents: include_str!("template/host/Cargo.toml.tmpl"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "host/build.rs",
+        contents: include_str!("template/host/build.rs"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "host/src/lib.rs",
+        contents: include_str!("template/host/src/lib.rs"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "host/src/main.rs",
+        contents: include_str!("template/host/src/main.rs"),
+        lib_only: false,
+    },
+    TemplateFile {
+        path: "host/tests/guest.rs",
+        contents: include_str!("template/host/tests/guest.rs"),
+        lib_only: false,
+    },
+];
+
 #[derive(Parser)]
 #[command(name = "new", about = "Setup a new project that runs inside the ZisK.")]
 pub struct NewCmd {
     name: String,
+
+    /// Put the program logic in a library crate, shared by the guest and the host
+    #[clap(long)]
+    lib: bool,
+
+    /// Use crates patched to run on the ZisK precompiles
+    #[clap(long)]
+    precompiles: bool,
+
+    /// Use a local ZisK repository for the ZisK dependencies, instead of the git one
+    #[clap(long)]
+    zisk_path: Option<PathBuf>,
 }
 
 impl NewCmd {
     pub fn run(&self) -> Result<()> {
         let root = Path::new(&self.name);
-        let repo_url = "https://{}@github.com/0xPolygonHermez/zisk_template";
-        // Create the root directory if it doesn't exist.
-        if !root.exists() {
-            fs::create_dir(&self.name)?;
+        let name = root
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid project name: {}", self.name))?;
+        if name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid project name {}, it must be a valid crate name",
+                name
+            ));
         }
-
-        // Clone the repository.
-        let output = Command::new("git")
-            .arg("clone")
-            .arg(repo_url)
-            .arg(root.as_os_str())
-            .arg("--recurse-submodules")
-            .arg("--depth=1")
-            .output()
-            .expect("failed to execute command");
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("failed to clone repository: {}", stderr));
+        if root.exists() && fs::read_dir(root)?.next().is_some() {
+            return Err(anyhow::anyhow!("Directory {} already exists and is not empty", self.name));
         }
 
-        // Remove the .git directory.
-        fs::remove_dir_all(root.join(".git"))?;
+        let (ziskos_dep, ziskemu_dep) = match &self.zisk_path {
+            Some(zisk_path) => {
+                let zisk_path = fs::canonicalize(zisk_path)?;
+                (
+                    format!("{{ path = {:?} }}", zisk_path.join("ziskos/entrypoint")),
+                    format!("{{ path = {:?} }}", zisk_path.join("emulator")),
+                )
+            }
+            None => (
+                format!("{{ git = \"{}\" }}", ZISK_REPO_URL),
+                format!("{{ git = \"{}\" }}", ZISK_REPO_URL),
+            ),
+        };
+        let vars = [
+            ("name", name.to_string()),
+            ("crate_name", name.replace('-', "_")),
+            ("ziskos_dep", ziskos_dep),
+            ("ziskemu_dep", ziskemu_dep),
+        ];
+        let flags = [("lib", self.lib), ("precompiles", self.precompiles)];
+
+        for file in TEMPLATE.iter().filter(|file| self.lib || !file.lib_only) {
+            let path = root.join(file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, render(file.contents, &vars, &flags))?;
+        }
 
         println!(
             "    \x1b[1m{}\x1b[0m {} ({})",
@@ -45,3 +158,92 @@ impl NewCmd {
         Ok(())
     }
 }
+
+/// Renders a template, replacing the `{{var}}` placeholders and keeping the lines between
+/// `{{#flag}}` and `{{/flag}}` only if the flag is set, or between `{{^flag}}` and `{{/flag}}` only
+/// if it is not set. Section tags must be on their own line.
+fn render(template: &str, vars: &[(&str, String)], flags: &[(&str, bool)]) -> String {
+    let flag = |name: &str| {
+        flags
+            .iter()
+            .find(|(flag, _)| *flag == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("Unknown template flag {}", name))
+    };
+
+    let mut output = String::new();
+    // Whether each of the enclosing sections is skipped
+    let mut skipped = Vec::new();
+    for line in template.lines() {
+        let tag = line.trim();
+        if let Some(name) = tag.strip_prefix("{{#").and_then(|tag| tag.strip_suffix("}}")) {
+            skipped.push(!flag(name));
+        } else if let Some(name) = tag.strip_prefix("{{^").and_then(|tag| tag.strip_suffix("}}")) {
+            skipped.push(flag(name));
+        } else if tag.starts_with("{{/") {
+            skipped.pop();
+        } else if !skipped.contains(&true) {
+            let mut line = line.to_string();
+            for (name, value) in vars {
+                line = line.replace(&format!("{{{{{}}}}}", name), value);
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_vars() {
+        let vars = [("name", "guest".to_string()), ("dep", "{ path = \"zisk\" }".to_string())];
+        assert_eq!(
+            render("name = \"{{name}}\"\ndep = {{dep}} # {{name}}\n{{other}}", &vars, &[]),
+            "name = \"guest\"\ndep = { path = \"zisk\" } # guest\n{{other}}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_sections() {
+        let template = "a\n{{#lib}}\nlib\n  {{^precompiles}}\nlib, no precompiles\n  \
+                        {{/precompiles}}\n{{/lib}}\n{{^lib}}\nno lib\n{{/lib}}\nb";
+        let render_with =
+            |lib, precompiles| render(template, &[], &[("lib", lib), ("precompiles", precompiles)]);
+        assert_eq!(render_with(true, false), "a\nlib\nlib, no precompiles\nb\n");
+        assert_eq!(render_with(true, true), "a\nlib\nb\n");
+        assert_eq!(render_with(false, false), "a\nno lib\nb\n");
+        assert_eq!(render_with(false, true), "a\nno lib\nb\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown template flag lib")]
+    fn test_render_unknown_flag() {
+        render("{{#lib}}\n{{/lib}}", &[], &[]);
+    }
+
+    #[test]
+    fn test_render_template() {
+        let vars = [
+            ("name", "my-guest".to_string()),
+            ("crate_name", "my_guest".to_string()),
+            ("ziskos_dep", "{ path = \"ziskos\" }".to_string()),
+            ("ziskemu_dep", "{ path = \"ziskemu\" }".to_string()),
+        ];
+        for (lib, precompiles) in [(false, false), (false, true), (true, false), (true, true)] {
+            let flags = [("lib", lib), ("precompiles", precompiles)];
+            for file in TEMPLATE {
+                let output = render(file.contents, &vars, &flags);
+                assert!(!output.contains("{{"), "{} has unrendered tags:\n{}", file.path, output);
+            }
+
+            let workspace = render(TEMPLATE[0].contents, &vars, &flags);
+            assert!(workspace.contains("ziskos = { path = \"ziskos\" }"));
+            assert_eq!(workspace.contains("\"lib\"]"), lib);
+            assert_eq!(workspace.contains("[patch.crates-io]"), precompiles);
+        }
+    }
+}
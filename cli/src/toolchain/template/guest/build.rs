@@ -0,0 +1,16 @@
+// Generates the sample input file of the program, build/input.bin, with the number of iterations
+// to compute. It is read by the program when it runs natively (`cargo run`), and can be passed to
+// `ziskemu` or `cargo-zisk` with `-i build/input.bin`.
+
+use std::{fs, path::PathBuf};
+
+const N: u64 = 20;
+
+fn main() {
+    let root = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    let output_dir = root.join("build");
+    fs::create_dir_all(&output_dir).expect("Failed to create the build directory");
+    fs::write(output_dir.join("input.bin"), N.to_le_bytes()).expect("Failed to write input.bin");
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,57 @@
+// This example program takes a number `n` as input and computes its hash `n` times sequentially.
+
+// Mark the main function as the entry point for ZisK
+#![no_main]
+ziskos::entrypoint!(main);
+
+{{#lib}}
+use {{crate_name}}_lib::hash_n;
+{{/lib}}
+{{^lib}}
+use byteorder::ByteOrder;
+{{#precompiles}}
+use tiny_keccak::{Hasher, Keccak};
+{{/precompiles}}
+{{^precompiles}}
+use sha2::{Digest, Sha256};
+{{/precompiles}}
+{{/lib}}
+use ziskos::{read_input, set_output};
+
+fn main() {
+    // Read the input data as a byte array from ziskos
+    let input: Vec<u8> = read_input();
+
+    // Get the 'n' value converting the input byte array into a u64 value
+    let n: u64 = u64::from_le_bytes(input.try_into().unwrap());
+
+{{#lib}}
+    // Write the hash to ziskos output, as 32-bits values
+    for (i, value) in hash_n(n).into_iter().enumerate() {
+        set_output(i, value);
+    }
+{{/lib}}
+{{^lib}}
+    let mut hash = [0u8; 32];
+
+    for _ in 0..n {
+{{#precompiles}}
+        // Keccak-256 runs on the Keccak-f precompile through the patched tiny-keccak crate
+        let mut hasher = Keccak::v256();
+        hasher.update(&hash);
+        hasher.finalize(&mut hash);
+{{/precompiles}}
+{{^precompiles}}
+        let mut hasher = Sha256::new();
+        hasher.update(hash);
+        hash = hasher.finalize().into();
+{{/precompiles}}
+    }
+
+    // Split 'hash' value into chunks of 32 bits and write them to ziskos output
+    for i in 0..8 {
+        let val = byteorder::BigEndian::read_u32(&hash[i * 4..i * 4 + 4]);
+        set_output(i, val);
+    }
+{{/lib}}
+}
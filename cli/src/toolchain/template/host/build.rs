@@ -0,0 +1,50 @@
+// Builds the guest program for ZisK with `cargo-zisk`, and exposes the path of its ELF file to the
+// host as the `GUEST_ELF` environment variable. Set `ZISK_SKIP_GUEST_BUILD` to use the ELF file
+// built last instead, e.g. when `cargo-zisk` is not installed.
+
+use std::{env, path::PathBuf, process::Command};
+
+/// Variables of the host build environment that must not reach the guest build
+const RUST_ENV_VARS: [&str; 5] = [
+    "RUSTC",
+    "RUSTC_WRAPPER",
+    "RUSTC_WORKSPACE_WRAPPER",
+    "RUSTFLAGS",
+    "RUSTUP_TOOLCHAIN",
+];
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    let guest = root.join("guest");
+    // A separate target directory, since the one of the host build is locked while it runs
+    let target_dir = root.join("target").join("elf");
+    let elf = target_dir.join("riscv64ima-zisk-zkvm-elf/release/{{name}}");
+
+    println!("cargo:rerun-if-changed={}", guest.display());
+{{#lib}}
+    println!("cargo:rerun-if-changed={}", root.join("lib").display());
+{{/lib}}
+    println!("cargo:rerun-if-env-changed=ZISK_SKIP_GUEST_BUILD");
+    println!("cargo:rustc-env=GUEST_ELF={}", elf.display());
+
+    if env::var_os("ZISK_SKIP_GUEST_BUILD").is_some() {
+        return;
+    }
+
+    let mut command = Command::new("cargo-zisk");
+    command.args(["build", "--release"]).current_dir(&guest);
+    // Do not pass the configuration of the host build to the guest build
+    for (key, _) in env::vars() {
+        if (key.starts_with("CARGO_") && key != "CARGO_HOME")
+            || RUST_ENV_VARS.contains(&key.as_str())
+        {
+            command.env_remove(key);
+        }
+    }
+    command.env("CARGO_TARGET_DIR", &target_dir);
+
+    let status = command
+        .status()
+        .expect("Failed to execute cargo-zisk, is it installed?");
+    assert!(status.success(), "Failed to build the guest program");
+}
@@ -0,0 +1,49 @@
+//! Helpers shared by the host programs and tests.
+
+use std::error::Error;
+
+use ziskemu::{EmulationResult, ZiskEmulator};
+
+/// ELF file of the guest program, built by the build script
+pub const GUEST_ELF: &str = env!("GUEST_ELF");
+
+/// Returns the input data of the guest program for `n` iterations
+pub fn input(n: u64) -> Vec<u8> {
+    n.to_le_bytes().to_vec()
+}
+
+/// Runs the guest program in the ZisK emulator
+pub fn run_guest(input: &[u8]) -> Result<EmulationResult, Box<dyn Error>> {
+    let elf = std::fs::read(GUEST_ELF)?;
+    Ok(ZiskEmulator::builder().elf_bytes(&elf).input(input).run()?)
+}
+
+/// Returns the public outputs the guest program must generate for `n` iterations, computed natively
+pub fn expected_output(n: u64) -> [u32; 8] {
+{{#lib}}
+    {{crate_name}}_lib::hash_n(n)
+{{/lib}}
+{{^lib}}
+{{#precompiles}}
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut hash = [0u8; 32];
+    for _ in 0..n {
+        let mut hasher = Keccak::v256();
+        hasher.update(&hash);
+        hasher.finalize(&mut hash);
+    }
+{{/precompiles}}
+{{^precompiles}}
+    use sha2::{Digest, Sha256};
+
+    let mut hash = [0u8; 32];
+    for _ in 0..n {
+        let mut hasher = Sha256::new();
+        hasher.update(hash);
+        hash = hasher.finalize().into();
+    }
+{{/precompiles}}
+    std::array::from_fn(|i| u32::from_be_bytes(hash[i * 4..i * 4 + 4].try_into().unwrap()))
+{{/lib}}
+}
@@ -0,0 +1,59 @@
+// Runs the guest program in the ZisK emulator and checks its public outputs.
+//
+// Usage: cargo run --release -p {{name}}-host -- [n] [--prove]
+//
+// With `--prove`, it also generates the program setup and a proof with `cargo-zisk`, writing the
+// input to build/input.bin and the proof to the proof directory.
+
+use std::{error::Error, process::Command};
+
+use {{crate_name}}_host::{expected_output, input, run_guest, GUEST_ELF};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let prove = args.iter().any(|arg| arg == "--prove");
+    let n = match args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(n) => n.parse()?,
+        None => 20,
+    };
+
+    let input = input(n);
+    let result = run_guest(&input)?;
+    if !result.exit_status.success() {
+        return Err(format!("Guest program failed: {:?}", result.exit_status).into());
+    }
+    for (i, value) in result.public_outputs.iter().take(8).enumerate() {
+        println!("public {}: {:#010x}", i, value);
+    }
+    println!("Executed {} steps", result.steps);
+    if result.public_outputs[..8] != expected_output(n) {
+        return Err("Guest public outputs do not match the expected ones".into());
+    }
+
+    if prove {
+        std::fs::create_dir_all("build")?;
+        std::fs::write("build/input.bin", &input)?;
+        cargo_zisk(&["rom-setup", "-e", GUEST_ELF])?;
+        cargo_zisk(&[
+            "prove",
+            "-e",
+            GUEST_ELF,
+            "-i",
+            "build/input.bin",
+            "-o",
+            "proof",
+            "-a",
+            "-y",
+        ])?;
+    }
+
+    Ok(())
+}
+
+fn cargo_zisk(args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let status = Command::new("cargo-zisk").args(args).status()?;
+    if !status.success() {
+        return Err(format!("cargo-zisk {} failed with status {}", args[0], status).into());
+    }
+    Ok(())
+}
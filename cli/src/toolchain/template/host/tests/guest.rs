@@ -0,0 +1,16 @@
+use {{crate_name}}_host::{expected_output, input, run_guest};
+
+#[test]
+fn guest_computes_hash() {
+    for n in [0, 1, 20] {
+        let result = run_guest(&input(n)).unwrap();
+        assert!(result.exit_status.success(), "{:?}", result.exit_status);
+        assert_eq!(result.public_outputs[..8], expected_output(n));
+    }
+}
+
+#[test]
+fn guest_rejects_invalid_input() {
+    let result = run_guest(&[1, 2, 3]).unwrap();
+    assert!(!result.exit_status.success());
+}
@@ -0,0 +1,31 @@
+//! Logic of the program, shared by the guest, which proves it, and the host, which can compute
+//! the expected results natively.
+
+{{#precompiles}}
+use tiny_keccak::{Hasher, Keccak};
+{{/precompiles}}
+{{^precompiles}}
+use sha2::{Digest, Sha256};
+{{/precompiles}}
+
+/// Hashes 32 zero bytes `n` times sequentially, and returns the result as big-endian 32-bits
+/// values
+pub fn hash_n(n: u64) -> [u32; 8] {
+    let mut hash = [0u8; 32];
+
+    for _ in 0..n {
+{{#precompiles}}
+        // Keccak-256 runs on the Keccak-f precompile through the patched tiny-keccak crate
+        let mut hasher = Keccak::v256();
+        hasher.update(&hash);
+        hasher.finalize(&mut hash);
+{{/precompiles}}
+{{^precompiles}}
+        let mut hasher = Sha256::new();
+        hasher.update(hash);
+        hash = hasher.finalize().into();
+{{/precompiles}}
+    }
+
+    std::array::from_fn(|i| u32::from_be_bytes(hash[i * 4..i * 4 + 4].try_into().unwrap()))
+}
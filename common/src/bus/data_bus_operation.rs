@@ -3,7 +3,7 @@
 //! and managing the format of operation data.
 
 use crate::{BusId, PayloadType};
-use zisk_core::zisk_ops::ZiskOp;
+use zisk_core::zisk_ops::{ZiskOp, DMA_PARAMS};
 use zisk_core::{InstContext, ZiskInst, ZiskOperationType};
 
 /// The unique bus ID for operation-related data communication.
@@ -13,10 +13,14 @@ pub const OPERATION_BUS_ID: BusId = BusId(0);
 pub const OPERATION_BUS_DATA_SIZE: usize = 4;
 pub const OPERATION_BUS_KECCAKF_DATA_SIZE: usize = 5;
 pub const OPERATION_BUS_SHA256F_DATA_SIZE: usize = 5;
+pub const OPERATION_BUS_BLAKE2BR_DATA_SIZE: usize = 5;
+pub const OPERATION_BUS_DMA_DATA_SIZE: usize = 5;
 
-// worst case: 4 x 256 + 2 addr = 4 * 4 + 2 = 18 (secp256k1_add, arith_256_mod)
+// worst case: 4 x 256 + 2 addr = 4 * 4 + 2 = 18 (secp256k1_add, ed25519_add, arith_256_mod)
 // arith_256: 3 x 256 + 2 addr = 3 * 4 + 2 = 14
 // secp256k1_dbl: 2 x 256 + 1 addr = 2 * 4 + 1 = 9
+// curve25519_mod: 3 x 256 + 4 addr = 3 * 4 + 4 = 16
+// ed25519_dbl: 2 x 256 + 1 addr = 2 * 4 + 1 = 9
 // TODO: optimize and send only one value 64 upto 32-bits addr
 
 const DATA_256_BITS_SIZE: usize = 4;
@@ -32,6 +36,12 @@ pub const OPERATION_BUS_SECP256K1_ADD_DATA_SIZE: usize =
     OPERATION_BUS_DATA_SIZE + 2 * INDIRECTION_SIZE + 2 * POINT_256_BITS_SIZE;
 pub const OPERATION_BUS_SECP256K1_DBL_DATA_SIZE: usize =
     OPERATION_BUS_DATA_SIZE + POINT_256_BITS_SIZE;
+pub const OPERATION_BUS_CURVE25519_MOD_DATA_SIZE: usize =
+    OPERATION_BUS_DATA_SIZE + 4 * INDIRECTION_SIZE + 3 * DATA_256_BITS_SIZE;
+pub const OPERATION_BUS_ED25519_DBL_DATA_SIZE: usize =
+    OPERATION_BUS_DATA_SIZE + POINT_256_BITS_SIZE;
+pub const OPERATION_BUS_ED25519_ADD_DATA_SIZE: usize =
+    OPERATION_BUS_DATA_SIZE + 2 * INDIRECTION_SIZE + 2 * POINT_256_BITS_SIZE;
 
 /// Index of the operation value in the operation data payload.
 pub const OP: usize = 0;
@@ -51,10 +61,17 @@ pub type OperationData<D> = [D; OPERATION_BUS_DATA_SIZE];
 /// Type alias for precompiles operation data payload.
 pub type OperationKeccakData<D> = [D; OPERATION_BUS_KECCAKF_DATA_SIZE + 25]; // 25·64 = 1600 bits
 pub type OperationSha256Data<D> = [D; OPERATION_BUS_SHA256F_DATA_SIZE + 12]; // 12·64 = 768 bits
+pub type OperationBlake2brData<D> = [D; OPERATION_BUS_BLAKE2BR_DATA_SIZE + 32]; // 32·64 = 2048 bits
 pub type OperationArith256Data<D> = [D; OPERATION_BUS_ARITH_256_DATA_SIZE];
 pub type OperationArith256ModData<D> = [D; OPERATION_BUS_ARITH_256_MOD_DATA_SIZE];
 pub type OperationSecp256k1AddData<D> = [D; OPERATION_BUS_SECP256K1_ADD_DATA_SIZE];
 pub type OperationSecp256k1DblData<D> = [D; OPERATION_BUS_SECP256K1_DBL_DATA_SIZE];
+pub type OperationCurve25519ModData<D> = [D; OPERATION_BUS_CURVE25519_MOD_DATA_SIZE];
+pub type OperationEd25519DblData<D> = [D; OPERATION_BUS_ED25519_DBL_DATA_SIZE];
+pub type OperationEd25519AddData<D> = [D; OPERATION_BUS_ED25519_ADD_DATA_SIZE];
+/// Dma operations carry a variable number of source words: [op, op_type, a, b, step, dst,
+/// src|fill, count] plus the count source words of memcpy
+pub type OperationDmaData<D> = Vec<D>;
 
 pub enum ExtOperationData<D> {
     OperationData(OperationData<D>),
@@ -64,6 +81,11 @@ pub enum ExtOperationData<D> {
     OperationArith256ModData(OperationArith256ModData<D>),
     OperationSecp256k1AddData(OperationSecp256k1AddData<D>),
     OperationSecp256k1DblData(OperationSecp256k1DblData<D>),
+    OperationCurve25519ModData(OperationCurve25519ModData<D>),
+    OperationEd25519DblData(OperationEd25519DblData<D>),
+    OperationEd25519AddData(OperationEd25519AddData<D>),
+    OperationBlake2brData(OperationBlake2brData<D>),
+    OperationDmaData(OperationDmaData<D>),
 }
 
 const KECCAK_OP: u8 = ZiskOp::Keccak.code();
@@ -72,6 +94,12 @@ const ARITH256_OP: u8 = ZiskOp::Arith256.code();
 const ARITH256_MOD_OP: u8 = ZiskOp::Arith256Mod.code();
 const SECP256K1_ADD_OP: u8 = ZiskOp::Secp256k1Add.code();
 const SECP256K1_DBL_OP: u8 = ZiskOp::Secp256k1Dbl.code();
+const CURVE25519_MOD_OP: u8 = ZiskOp::Curve25519Mod.code();
+const ED25519_DBL_OP: u8 = ZiskOp::Ed25519Dbl.code();
+const ED25519_ADD_OP: u8 = ZiskOp::Ed25519Add.code();
+const BLAKE2BR_OP: u8 = ZiskOp::Blake2br.code();
+const MEMCPY_OP: u8 = ZiskOp::Memcpy.code();
+const MEMSET_OP: u8 = ZiskOp::Memset.code();
 
 // impl<D: Copy + Into<u8>> TryFrom<&[D]> for ExtOperationData<D> {
 impl<D: Copy + Into<u64>> TryFrom<&[D]> for ExtOperationData<D> {
@@ -113,6 +141,37 @@ impl<D: Copy + Into<u64>> TryFrom<&[D]> for ExtOperationData<D> {
                     data.try_into().map_err(|_| "Invalid OperationSecp256k1DblData size")?;
                 Ok(ExtOperationData::OperationSecp256k1DblData(array))
             }
+            CURVE25519_MOD_OP => {
+                let array: OperationCurve25519ModData<D> =
+                    data.try_into().map_err(|_| "Invalid OperationCurve25519ModData size")?;
+                Ok(ExtOperationData::OperationCurve25519ModData(array))
+            }
+            ED25519_DBL_OP => {
+                let array: OperationEd25519DblData<D> =
+                    data.try_into().map_err(|_| "Invalid OperationEd25519DblData size")?;
+                Ok(ExtOperationData::OperationEd25519DblData(array))
+            }
+            ED25519_ADD_OP => {
+                let array: OperationEd25519AddData<D> =
+                    data.try_into().map_err(|_| "Invalid OperationEd25519AddData size")?;
+                Ok(ExtOperationData::OperationEd25519AddData(array))
+            }
+            BLAKE2BR_OP => {
+                let array: OperationBlake2brData<D> =
+                    data.try_into().map_err(|_| "Invalid OperationBlake2brData size")?;
+                Ok(ExtOperationData::OperationBlake2brData(array))
+            }
+            MEMCPY_OP | MEMSET_OP => {
+                if data.len() < OPERATION_BUS_DMA_DATA_SIZE + DMA_PARAMS {
+                    return Err("Invalid OperationDmaData size");
+                }
+                let count: u64 = data[OPERATION_BUS_DMA_DATA_SIZE + 2].into();
+                let words = if op as u8 == MEMCPY_OP { count as usize } else { 0 };
+                if data.len() != OPERATION_BUS_DMA_DATA_SIZE + DMA_PARAMS + words {
+                    return Err("Invalid OperationDmaData size");
+                }
+                Ok(ExtOperationData::OperationDmaData(data.to_vec()))
+            }
             _ => {
                 let array: OperationData<D> =
                     data.try_into().map_err(|_| "Invalid OperationData size")?;
@@ -181,6 +240,30 @@ impl OperationBusData<u64> {
                 data[5..(5 + 12)].copy_from_slice(&inst_ctx.precompiled.input_data[..12]);
                 ExtOperationData::OperationSha256Data(data)
             }
+            ZiskOperationType::Blake2 => {
+                assert!(inst_ctx.precompiled.input_data.len() == 32);
+                let mut data: OperationBlake2brData<u64> =
+                    [0; OPERATION_BUS_BLAKE2BR_DATA_SIZE + 32];
+                data[0] = inst.op as u64; // OP
+                data[1] = inst.op_type as u64; // OP_TYPE
+                data[2] = a; // A
+                data[3] = b; // B
+                data[4] = inst_ctx.step; // STEP
+                data[5..(5 + 32)].copy_from_slice(&inst_ctx.precompiled.input_data[..32]);
+                ExtOperationData::OperationBlake2brData(data)
+            }
+            ZiskOperationType::Dma => {
+                let input_data = &inst_ctx.precompiled.input_data;
+                let mut data: OperationDmaData<u64> =
+                    Vec::with_capacity(OPERATION_BUS_DMA_DATA_SIZE + input_data.len());
+                data.push(inst.op as u64); // OP
+                data.push(inst.op_type as u64); // OP_TYPE
+                data.push(a); // A
+                data.push(b); // B
+                data.push(inst_ctx.step); // STEP
+                data.extend_from_slice(input_data); // [dst, src|fill, count, words...]
+                ExtOperationData::OperationDmaData(data)
+            }
             ZiskOperationType::ArithEq => {
                 match inst.op {
                     ARITH256_OP => {
@@ -223,6 +306,36 @@ impl OperationBusData<u64> {
                         data[4..].copy_from_slice(&inst_ctx.precompiled.input_data);
                         ExtOperationData::OperationSecp256k1DblData(data)
                     }
+                    CURVE25519_MOD_OP => {
+                        let mut data: OperationCurve25519ModData<u64> =
+                            [0; OPERATION_BUS_CURVE25519_MOD_DATA_SIZE];
+                        data[0] = inst.op as u64; // OP
+                        data[1] = inst.op_type as u64; // OP_TYPE
+                        data[2] = a; // A step
+                        data[3] = b; // B addr
+                        data[4..].copy_from_slice(&inst_ctx.precompiled.input_data);
+                        ExtOperationData::OperationCurve25519ModData(data)
+                    }
+                    ED25519_DBL_OP => {
+                        let mut data: OperationEd25519DblData<u64> =
+                            [0; OPERATION_BUS_ED25519_DBL_DATA_SIZE];
+                        data[0] = inst.op as u64; // OP
+                        data[1] = inst.op_type as u64; // OP_TYPE
+                        data[2] = a; // A step
+                        data[3] = b; // B addr
+                        data[4..].copy_from_slice(&inst_ctx.precompiled.input_data);
+                        ExtOperationData::OperationEd25519DblData(data)
+                    }
+                    ED25519_ADD_OP => {
+                        let mut data: OperationEd25519AddData<u64> =
+                            [0; OPERATION_BUS_ED25519_ADD_DATA_SIZE];
+                        data[0] = inst.op as u64; // OP
+                        data[1] = inst.op_type as u64; // OP_TYPE
+                        data[2] = a; // A step
+                        data[3] = b; // B addr
+                        data[4..].copy_from_slice(&inst_ctx.precompiled.input_data);
+                        ExtOperationData::OperationEd25519AddData(data)
+                    }
                     _ => {
                         ExtOperationData::OperationData([
                             inst.op as u64,      // OP
@@ -261,6 +374,11 @@ impl OperationBusData<u64> {
             ExtOperationData::OperationArith256ModData(d) => d[OP] as u8,
             ExtOperationData::OperationSecp256k1AddData(d) => d[OP] as u8,
             ExtOperationData::OperationSecp256k1DblData(d) => d[OP] as u8,
+            ExtOperationData::OperationCurve25519ModData(d) => d[OP] as u8,
+            ExtOperationData::OperationEd25519DblData(d) => d[OP] as u8,
+            ExtOperationData::OperationEd25519AddData(d) => d[OP] as u8,
+            ExtOperationData::OperationBlake2brData(d) => d[OP] as u8,
+            ExtOperationData::OperationDmaData(d) => d[OP] as u8,
         }
     }
 
@@ -281,6 +399,11 @@ impl OperationBusData<u64> {
             ExtOperationData::OperationArith256ModData(d) => d[OP_TYPE],
             ExtOperationData::OperationSecp256k1AddData(d) => d[OP_TYPE],
             ExtOperationData::OperationSecp256k1DblData(d) => d[OP_TYPE],
+            ExtOperationData::OperationCurve25519ModData(d) => d[OP_TYPE],
+            ExtOperationData::OperationEd25519DblData(d) => d[OP_TYPE],
+            ExtOperationData::OperationEd25519AddData(d) => d[OP_TYPE],
+            ExtOperationData::OperationBlake2brData(d) => d[OP_TYPE],
+            ExtOperationData::OperationDmaData(d) => d[OP_TYPE],
         }
     }
 
@@ -301,6 +424,11 @@ impl OperationBusData<u64> {
             ExtOperationData::OperationArith256ModData(d) => d[A],
             ExtOperationData::OperationSecp256k1AddData(d) => d[A],
             ExtOperationData::OperationSecp256k1DblData(d) => d[A],
+            ExtOperationData::OperationCurve25519ModData(d) => d[A],
+            ExtOperationData::OperationEd25519DblData(d) => d[A],
+            ExtOperationData::OperationEd25519AddData(d) => d[A],
+            ExtOperationData::OperationBlake2brData(d) => d[A],
+            ExtOperationData::OperationDmaData(d) => d[A],
         }
     }
 
@@ -321,6 +449,11 @@ impl OperationBusData<u64> {
             ExtOperationData::OperationArith256ModData(d) => d[B],
             ExtOperationData::OperationSecp256k1AddData(d) => d[B],
             ExtOperationData::OperationSecp256k1DblData(d) => d[B],
+            ExtOperationData::OperationCurve25519ModData(d) => d[B],
+            ExtOperationData::OperationEd25519DblData(d) => d[B],
+            ExtOperationData::OperationEd25519AddData(d) => d[B],
+            ExtOperationData::OperationBlake2brData(d) => d[B],
+            ExtOperationData::OperationDmaData(d) => d[B],
         }
     }
 
@@ -340,6 +473,11 @@ impl OperationBusData<u64> {
             ExtOperationData::OperationArith256ModData(d) => d[4..].to_vec(),
             ExtOperationData::OperationSecp256k1AddData(d) => d[4..].to_vec(),
             ExtOperationData::OperationSecp256k1DblData(d) => d[4..].to_vec(),
+            ExtOperationData::OperationCurve25519ModData(d) => d[4..].to_vec(),
+            ExtOperationData::OperationEd25519DblData(d) => d[4..].to_vec(),
+            ExtOperationData::OperationEd25519AddData(d) => d[4..].to_vec(),
+            ExtOperationData::OperationBlake2brData(d) => d[5..(5 + 32)].to_vec(),
+            ExtOperationData::OperationDmaData(d) => d[5..].to_vec(),
             _ => vec![],
         }
     }
@@ -0,0 +1,85 @@
+//! Executable that disassembles the ZisK ROM transpiled from a RISC-V ELF file.
+
+use std::{env, path::PathBuf, process};
+
+use zisk_core::{rom_dump, rom_dump_to_text, ElfDumpInfo, Riscv2zisk, RomDumpFilter};
+
+const USAGE: &str = "Usage: zisk-objdump <elf_riscv_file> [--from <address>] [--to <address>] \
+                     [--symbol <name>] [--no-bios] [--json]";
+
+/// Parses a decimal or 0x-prefixed hexadecimal address
+fn parse_address(value: &str) -> Result<u64, String> {
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse::<u64>(),
+    };
+    parsed.map_err(|e| format!("invalid address {}: {}", value, e))
+}
+
+/// Transpiles the ELF file and prints every ZisK instruction of the resulting ROM, next to the
+/// RISC-V instruction it comes from, its ELF symbol, operation type and cost.
+/// The output is text by default, or a JSON array of instructions with `--json`.
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut elf_file: Option<PathBuf> = None;
+    let mut filter = RomDumpFilter::default();
+    let mut json = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next().unwrap_or_else(|| {
+                eprintln!("Error parsing arguments: missing value for {}.  {}", name, USAGE);
+                process::exit(1);
+            })
+        };
+        let result = match arg.as_str() {
+            "--from" => parse_address(&value("--from")).map(|a| filter.from = Some(a)),
+            "--to" => parse_address(&value("--to")).map(|a| filter.to = Some(a)),
+            "--symbol" => {
+                filter.symbol = Some(value("--symbol"));
+                Ok(())
+            }
+            "--no-bios" => {
+                filter.skip_bios = true;
+                Ok(())
+            }
+            "--json" => {
+                json = true;
+                Ok(())
+            }
+            _ if elf_file.is_none() && !arg.starts_with("--") => {
+                elf_file = Some(PathBuf::from(&arg));
+                Ok(())
+            }
+            _ => Err(format!("unexpected argument {}", arg)),
+        };
+        if let Err(e) = result {
+            eprintln!("Error parsing arguments: {}.  {}", e, USAGE);
+            process::exit(1);
+        }
+    }
+
+    let Some(elf_file) = elf_file else {
+        eprintln!("Error parsing arguments: missing ELF file.  {}", USAGE);
+        process::exit(1);
+    };
+
+    let rom = Riscv2zisk::new(&elf_file).run().unwrap_or_else(|e| {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    });
+    let elf_info = ElfDumpInfo::from_elf_file(&elf_file).unwrap_or_else(|e| {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    });
+    let entries = rom_dump(&rom, &elf_info, &filter).unwrap_or_else(|e| {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    });
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries).unwrap());
+    } else {
+        print!("{}", rom_dump_to_text(&entries));
+    }
+}
@@ -1,10 +1,10 @@
 //! Reads RISC-V data from and ELF file and converts it to a ZiskRom
 
 use crate::{
-    add_end_jmp, is_elf_file,
+    add_end_jmp, is_elf_file, optimize_rom_insts,
     riscv2zisk_context::{add_entry_exit_jmp, add_zisk_code, add_zisk_init_data},
-    AsmGenerationMethod, RoData, ZiskInst, ZiskRom, ZiskRom2Asm, RAM_ADDR, RAM_SIZE, ROM_ADDR,
-    ROM_ADDR_MAX, ROM_ENTRY,
+    AsmGenerationMethod, RoData, RomOptimizerStats, ZiskInst, ZiskRom, ZiskRom2Asm, RAM_ADDR,
+    RAM_SIZE, ROM_ADDR, ROM_ADDR_MAX, ROM_ENTRY,
 };
 use elf::{
     abi::{SHF_EXECINSTR, SHF_WRITE, SHT_PROGBITS},
@@ -17,22 +17,42 @@ use std::{error::Error, path::Path};
 /// Executes the ROM transpilation process: from ELF to Zisk
 pub fn elf2rom(elf_file: &Path) -> Result<ZiskRom, Box<dyn Error>> {
     // Get all data from the ELF file copied to a memory buffer
-    let elf_file_path = std::path::PathBuf::from(elf_file);
-    let file_data = std::fs::read(elf_file_path)?;
+    let file_data = std::fs::read(elf_file)?;
+    elf_bytes2rom(&file_data)
+}
 
-    match is_elf_file(&file_data) {
-        Ok(is_file) => {
-            if !is_file {
-                panic!("ROM file is not a valid ELF file");
-            }
-        }
-        Err(_) => {
-            panic!("Error reading ROM file");
-        }
+/// Executes the ROM transpilation process: from ELF to Zisk, running the peephole optimizer over
+/// the transpiled instructions.  Returns the optimized ROM and the optimizer counters.
+pub fn elf2rom_optimized(elf_file: &Path) -> Result<(ZiskRom, RomOptimizerStats), Box<dyn Error>> {
+    let file_data = std::fs::read(elf_file)?;
+    elf_bytes2rom_optimized(&file_data)
+}
+
+/// Executes the ROM transpilation process from the ELF data already loaded in memory
+pub fn elf_bytes2rom(file_data: &[u8]) -> Result<ZiskRom, Box<dyn Error>> {
+    build_rom(file_data, false).map(|(rom, _)| rom)
+}
+
+/// Executes the ROM transpilation process from the ELF data already loaded in memory, running
+/// the peephole optimizer over the transpiled instructions
+pub fn elf_bytes2rom_optimized(
+    file_data: &[u8],
+) -> Result<(ZiskRom, RomOptimizerStats), Box<dyn Error>> {
+    build_rom(file_data, true)
+}
+
+/// Transpiles the ELF file data into a Zisk ROM, optionally optimizing the instructions before
+/// the ROM is preprocessed
+fn build_rom(
+    file_data: &[u8],
+    optimize: bool,
+) -> Result<(ZiskRom, RomOptimizerStats), Box<dyn Error>> {
+    if !is_elf_file(file_data).map_err(|_| "Error reading ROM file")? {
+        return Err("ROM file is not a valid ELF file".into());
     }
 
     // Parse the ELF data
-    let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice())?;
+    let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_data)?;
 
     // Create an empty ZiskRom instance
     let mut rom: ZiskRom = ZiskRom { next_init_inst_addr: ROM_ENTRY, ..Default::default() };
@@ -116,6 +136,12 @@ pub fn elf2rom(elf_file: &Path) -> Result<ZiskRom, Box<dyn Error>> {
 
     add_entry_exit_jmp(&mut rom, elf_bytes.ehdr.e_entry);
 
+    // Optimize the instructions, once all of them have been added
+    let mut optimizer_stats = RomOptimizerStats::default();
+    if optimize {
+        optimizer_stats = optimize_rom_insts(&mut rom.insts);
+    }
+
     // Preprocess the ROM (experimental)
     // Split the ROM instructions based on their address in order to get a better performance when
     // searching for the corresponding intruction to the pc program address
@@ -206,7 +232,7 @@ pub fn elf2rom(elf_file: &Path) -> Result<ZiskRom, Box<dyn Error>> {
 
     //println! {"elf2rom() got rom.insts.len={}", rom.insts.len()};
 
-    Ok(rom)
+    Ok((rom, optimizer_stats))
 }
 
 /// Executes the ELF file data transpilation process into a Zisk ROM, and saves the result into a
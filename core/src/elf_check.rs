@@ -0,0 +1,193 @@
+//! Checks that a RISC-V ELF file can be transpiled to a ZisK ROM, before any emulation or proving
+//! is attempted.
+//!
+//! Every word of every executable section is decoded, reporting:
+//! * Unsupported instructions: encodings that the RISC-V interpreter cannot decode (compressed
+//!   instructions, unknown opcodes or functions, reserved fence bits), decoded instructions that
+//!   the transpiler does not convert, and privileged system instructions (e.g. `mret`, `wfi`)
+//!   that would silently be transpiled as an `ecall`
+//! * Foreign CSR accesses: CSR instructions on addresses outside the ZisK precompile, fcall and
+//!   BIOS-initialized CSRs, which are only emulated as plain memory locations
+//!
+//! Findings are grouped by reason, with the address and containing symbol of every occurrence.
+
+use std::{collections::BTreeMap, error::Error, fmt, path::Path};
+
+use elf::{
+    abi::{SHF_EXECINSTR, SHT_PROGBITS},
+    endian::AnyEndian,
+    ElfBytes,
+};
+use riscv::{riscv_decode, Rvd};
+
+use crate::{read_elf_symbols, zisk_csr_name, ElfDumpInfo, Riscv2ZiskContext};
+
+/// Encodings of the `ecall` and `ebreak` instructions, the only supported system instructions
+const ECALL: u32 = 0x00000073;
+const EBREAK: u32 = 0x00100073;
+
+/// Maximum number of occurrences listed per finding when displaying a report
+const MAX_LISTED_OCCURRENCES: usize = 8;
+
+/// An instruction found by the ELF check
+#[derive(Debug, Clone)]
+pub struct ElfCheckOccurrence {
+    /// Instruction address
+    pub addr: u64,
+    /// Containing symbol, as `name` or `name+0xoffset`
+    pub symbol: Option<String>,
+    /// Original 32-bit instruction word
+    pub word: u32,
+}
+
+/// A group of instructions found by the ELF check for the same reason
+#[derive(Debug, Clone)]
+pub struct ElfCheckFinding {
+    /// Description of the problem
+    pub reason: String,
+    /// Instructions affected, in address order
+    pub occurrences: Vec<ElfCheckOccurrence>,
+}
+
+/// Result of checking an ELF file
+#[derive(Debug, Default, Clone)]
+pub struct ElfCheckReport {
+    /// Number of instructions decoded
+    pub instructions: usize,
+    /// Instructions that cannot be transpiled, or that are transpiled as a different one
+    pub unsupported: Vec<ElfCheckFinding>,
+    /// CSR instructions accessing CSRs not handled by ZisK
+    pub foreign_csrs: Vec<ElfCheckFinding>,
+}
+
+impl ElfCheckReport {
+    /// Returns true if no issues were found
+    pub fn is_compatible(&self) -> bool {
+        self.unsupported.is_empty() && self.foreign_csrs.is_empty()
+    }
+}
+
+impl fmt::Display for ElfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_findings(
+            f: &mut fmt::Formatter<'_>,
+            title: &str,
+            findings: &[ElfCheckFinding],
+        ) -> fmt::Result {
+            writeln!(f, "{}: {}", title, findings.len())?;
+            for finding in findings {
+                writeln!(f, "  {} (count={})", finding.reason, finding.occurrences.len())?;
+                for o in finding.occurrences.iter().take(MAX_LISTED_OCCURRENCES) {
+                    writeln!(
+                        f,
+                        "    0x{:08x}: {:08x}  {}",
+                        o.addr,
+                        o.word,
+                        o.symbol.as_deref().unwrap_or("?")
+                    )?;
+                }
+                if finding.occurrences.len() > MAX_LISTED_OCCURRENCES {
+                    writeln!(
+                        f,
+                        "    ... {} more",
+                        finding.occurrences.len() - MAX_LISTED_OCCURRENCES
+                    )?;
+                }
+            }
+            Ok(())
+        }
+
+        writeln!(f, "Instructions: {}", self.instructions)?;
+        write_findings(f, "Unsupported instructions", &self.unsupported)?;
+        write_findings(f, "Foreign CSR accesses", &self.foreign_csrs)
+    }
+}
+
+/// Decodes all the executable sections of an ELF file, reporting the instructions that ZisK does
+/// not support
+pub fn check_elf(elf_file: &Path) -> Result<ElfCheckReport, Box<dyn Error>> {
+    let file_data = std::fs::read(elf_file)?;
+    let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice())?;
+    let info = ElfDumpInfo { symbols: read_elf_symbols(&elf_bytes)?, ..Default::default() };
+
+    let mut rvd = Rvd::new();
+    rvd.init();
+
+    let mut report = ElfCheckReport::default();
+    let mut unsupported: BTreeMap<String, Vec<ElfCheckOccurrence>> = BTreeMap::new();
+    let mut foreign_csrs: BTreeMap<String, Vec<ElfCheckOccurrence>> = BTreeMap::new();
+
+    if let Some(section_headers) = elf_bytes.section_headers() {
+        for section_header in section_headers {
+            // Same selection of code sections as elf2rom()
+            if section_header.sh_type != SHT_PROGBITS
+                || section_header.sh_addr == 0
+                || (section_header.sh_flags & SHF_EXECINSTR as u64) == 0
+            {
+                continue;
+            }
+            let (data, _) = elf_bytes.section_data(&section_header)?;
+
+            for (i, chunk) in data.chunks_exact(4).enumerate() {
+                let word = u32::from_le_bytes(chunk.try_into().unwrap());
+
+                // The transpiler ignores zero words
+                if word == 0 {
+                    continue;
+                }
+                report.instructions += 1;
+
+                let addr = section_header.sh_addr + 4 * i as u64;
+                let occurrence = || ElfCheckOccurrence { addr, symbol: info.symbol_at(addr), word };
+
+                let inst = match riscv_decode(&rvd, word) {
+                    Ok(inst) => inst,
+                    Err(e) => {
+                        unsupported.entry(e.to_string()).or_default().push(occurrence());
+                        continue;
+                    }
+                };
+
+                if !Riscv2ZiskContext::can_convert(&inst, addr) {
+                    let reason = format!("{} not transpiled", inst.inst);
+                    unsupported.entry(reason).or_default().push(occurrence());
+                    continue;
+                }
+
+                // Other system instructions with funct3=0 are decoded as ecall
+                if inst.inst == "ecall" && word != ECALL && word != EBREAK {
+                    let reason = format!("{} transpiled as ecall", system_instruction_name(word));
+                    unsupported.entry(reason).or_default().push(occurrence());
+                    continue;
+                }
+
+                if inst.inst.starts_with("csr") && zisk_csr_name(inst.csr).is_none() {
+                    let reason = format!("csr=0x{:03x}", inst.csr);
+                    foreign_csrs.entry(reason).or_default().push(occurrence());
+                }
+            }
+        }
+    }
+
+    report.unsupported = unsupported
+        .into_iter()
+        .map(|(reason, occurrences)| ElfCheckFinding { reason, occurrences })
+        .collect();
+    report.foreign_csrs = foreign_csrs
+        .into_iter()
+        .map(|(reason, occurrences)| ElfCheckFinding { reason, occurrences })
+        .collect();
+
+    Ok(report)
+}
+
+/// Returns the name of a privileged system instruction
+fn system_instruction_name(word: u32) -> String {
+    match word {
+        0x10200073 => "sret".to_string(),
+        0x30200073 => "mret".to_string(),
+        0x10500073 => "wfi".to_string(),
+        _ if (word >> 25) == 0x09 => "sfence.vma".to_string(),
+        _ => format!("system instruction=0x{:08x}", word),
+    }
+}
@@ -1,7 +1,143 @@
-pub const FCALL_PARAMS_MAX_SIZE: usize = 32;
-pub const FCALL_RESULT_MAX_SIZE: usize = 32;
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+// Re-exported so that the hosts registering user fcalls do not depend on ziskos
+pub use ziskos::{
+    is_user_fcall_id, FcallDecode, FcallEncode, FCALL_USER_ID_FIRST, FCALL_USER_ID_LAST,
+};
+
+/// Minimum size of the result buffer of the built-in fcalls, enough for any fixed-size result; the
+/// variable-size results are never larger than the parameters
+pub const FCALL_RESULT_MIN_SIZE: usize = 32;
 
 // Definition of the fcall IDs, one per function
 pub const FCALL_ID_INVERSE_FP_EC: u64 = 1;
 pub const FCALL_ID_INVERSE_FN_EC: u64 = 2;
 pub const FCALL_ID_SQRT_FP_EC_PARITY: u64 = 3;
+
+/// Signature of a user-defined fcall function: it receives the parameter words written by the
+/// guest and returns the result words, or an error message
+pub type UserFcallFn = dyn Fn(&[u64]) -> Result<Vec<u64>, String> + Send + Sync;
+
+/// Registry of user-defined fcalls, i.e. host functions that serve the fcall ids in the range
+/// `FCALL_USER_ID_FIRST..=FCALL_USER_ID_LAST`, declared in the guest with `ziskos_user_fcall!`.
+///
+/// The registered functions must be deterministic: the same fcall can be executed several times,
+/// e.g. once per emulation thread when computing the minimal traces, and the proof requires every
+/// execution to get the same results.
+#[derive(Clone, Default)]
+pub struct UserFcalls {
+    fcalls: BTreeMap<u16, Arc<UserFcallFn>>,
+}
+
+impl UserFcalls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a function that receives and returns raw `u64` words.  Panics if `id` is not in
+    /// the user fcall range.
+    pub fn register<F>(&mut self, id: u16, f: F) -> &mut Self
+    where
+        F: Fn(&[u64]) -> Result<Vec<u64>, String> + Send + Sync + 'static,
+    {
+        assert!(
+            is_user_fcall_id(id as u64),
+            "user fcall id {id} is not in the range {FCALL_USER_ID_FIRST}..={FCALL_USER_ID_LAST}"
+        );
+        self.fcalls.insert(id, Arc::new(f));
+        self
+    }
+
+    /// Registers a function with typed parameters and result, matching the signature of the
+    /// `ziskos_user_fcall!` declaration in the guest, e.g. `|(a, b): (u64, Vec<u64>)| -> u64`
+    pub fn register_typed<P, R, F>(&mut self, id: u16, f: F) -> &mut Self
+    where
+        P: FcallDecode,
+        R: FcallEncode,
+        F: Fn(P) -> R + Send + Sync + 'static,
+    {
+        self.register(id, move |params| {
+            let mut words = params.iter().copied();
+            let params = P::decode(&mut words).ok_or("not enough parameters")?;
+            if words.next().is_some() {
+                return Err("too many parameters".to_string());
+            }
+            let mut result = Vec::new();
+            f(params).encode(&mut |word| result.push(word));
+            Ok(result)
+        })
+    }
+
+    /// Returns the registered fcall ids
+    pub fn ids(&self) -> impl Iterator<Item = u16> + '_ {
+        self.fcalls.keys().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fcalls.is_empty()
+    }
+
+    /// Calls the function registered for `id`, returning its result words
+    pub fn call(&self, id: u64, params: &[u64]) -> Result<Vec<u64>, String> {
+        let f = u16::try_from(id)
+            .ok()
+            .and_then(|id| self.fcalls.get(&id))
+            .ok_or_else(|| format!("no user fcall registered with id {id}"))?;
+        f(params)
+    }
+}
+
+impl fmt::Debug for UserFcalls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserFcalls").field("ids", &self.fcalls.keys().collect::<Vec<_>>()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum_fcalls() -> UserFcalls {
+        let mut user_fcalls = UserFcalls::new();
+        user_fcalls.register_typed(FCALL_USER_ID_FIRST, |(a, b): (u64, Vec<u64>)| {
+            a + b.iter().sum::<u64>()
+        });
+        user_fcalls
+    }
+
+    #[test]
+    fn test_register_typed() {
+        let user_fcalls = sum_fcalls();
+        assert_eq!(user_fcalls.ids().collect::<Vec<_>>(), [FCALL_USER_ID_FIRST]);
+        assert_eq!(user_fcalls.call(FCALL_USER_ID_FIRST as u64, &[1, 2, 10, 20]), Ok(vec![31]));
+        assert_eq!(user_fcalls.call(FCALL_USER_ID_FIRST as u64, &[1, 0]), Ok(vec![1]));
+
+        let e = user_fcalls.call(FCALL_USER_ID_FIRST as u64 + 1, &[1, 0]).unwrap_err();
+        assert!(e.contains("no user fcall registered"));
+    }
+
+    #[test]
+    fn test_register_typed_params_count() {
+        let user_fcalls = sum_fcalls();
+        let id = FCALL_USER_ID_FIRST as u64;
+
+        // The vector is shorter than its length
+        assert_eq!(user_fcalls.call(id, &[1, 2, 10]), Err("not enough parameters".to_string()));
+        assert_eq!(user_fcalls.call(id, &[1]), Err("not enough parameters".to_string()));
+        assert_eq!(user_fcalls.call(id, &[]), Err("not enough parameters".to_string()));
+
+        assert_eq!(user_fcalls.call(id, &[1, 1, 10, 20]), Err("too many parameters".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "user fcall id 511 is not in the range 512..=1023")]
+    fn test_register_typed_id_below_range() {
+        UserFcalls::new().register_typed(FCALL_USER_ID_FIRST - 1, |a: (u64,)| a.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "user fcall id 1024 is not in the range 512..=1023")]
+    fn test_register_typed_id_above_range() {
+        UserFcalls::new().register_typed(FCALL_USER_ID_LAST + 1, |a: (u64,)| a.0);
+    }
+}
@@ -0,0 +1,53 @@
+//! Guest exit status, as written by the program to the `GUEST_EXIT_ADDR` memory region.
+//!
+//! Programs using ziskos end with exit code 0 when `main()` returns, and with the exit code passed
+//! to `ziskos::exit()` otherwise.  When the program panics, the ziskos panic hook stores the panic
+//! message and ends with exit code 101, as a native Rust program would.  Programs that do not
+//! write this region, e.g. bare assembly programs, are considered successful.
+
+use std::fmt;
+
+use crate::{Mem, GUEST_EXIT_ADDR, GUEST_PANIC_MSG_MAX_SIZE};
+
+/// Exit status of the guest program
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GuestExitStatus {
+    /// Exit code, 0 meaning success
+    pub exit_code: u64,
+    /// Panic message, if the program panicked
+    pub panic_message: Option<String>,
+}
+
+impl GuestExitStatus {
+    /// Reads the exit status from the guest memory, once the emulation is done
+    pub fn from_mem(mem: &Mem) -> Self {
+        let exit_code = mem.read(GUEST_EXIT_ADDR, 8);
+        let panic_message_len = mem.read(GUEST_EXIT_ADDR + 8, 8).min(GUEST_PANIC_MSG_MAX_SIZE);
+        let panic_message = (panic_message_len > 0).then(|| {
+            let bytes = (0..panic_message_len)
+                .map(|i| mem.read(GUEST_EXIT_ADDR + 16 + i, 1) as u8)
+                .collect::<Vec<u8>>();
+            String::from_utf8_lossy(&bytes).into_owned()
+        });
+        Self { exit_code, panic_message }
+    }
+
+    /// Returns true if the program ended with exit code 0
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    /// Returns true if the program panicked
+    pub fn panicked(&self) -> bool {
+        self.panic_message.is_some()
+    }
+}
+
+impl fmt::Display for GuestExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.panic_message {
+            Some(message) => write!(f, "panicked with exit code {}: {}", self.exit_code, message),
+            None => write!(f, "exited with exit code {}", self.exit_code),
+        }
+    }
+}
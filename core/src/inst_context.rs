@@ -4,7 +4,9 @@
 //! * The state includes: memory, registers (a, b, c, flag, sp), program counter (pc), step and a
 //!   flag to mark the end of the program execution.
 
-use crate::{Mem, REGS_IN_MAIN_TOTAL_NUMBER, ROM_ENTRY};
+use std::sync::Arc;
+
+use crate::{Mem, UserFcalls, REGS_IN_MAIN_TOTAL_NUMBER, ROM_ENTRY};
 
 /// Zisk precompiled
 #[derive(Debug, Default)]
@@ -39,22 +41,23 @@ pub struct PrecompiledInstContext {
 /// Stores the fcall arguments data and the result data.
 #[derive(Debug, Default)]
 pub struct FcallInstContext {
-    /// Fcall parameters data
-    /// Maximum size is 32 u64's
-    pub parameters: [u64; 32],
+    /// Fcall parameters data, growing as parameters are added
+    pub parameters: Vec<u64>,
 
     /// Indicates how many parameters u64's contain valid data
     pub parameters_size: u64,
 
-    /// Fcall result data
-    /// Maximum size is 32 u64's
-    pub result: [u64; 32],
+    /// Fcall result data, of any size
+    pub result: Vec<u64>,
 
     /// Indicates how many result u64's contain valid data
     pub result_size: u64,
 
     /// Indicates how many result u64's have been read using fcall_get()
     pub result_got: u64,
+
+    /// Host functions serving the user-defined fcall ids, if any
+    pub user_fcalls: Option<Arc<UserFcalls>>,
 }
 
 #[derive(Debug)]
@@ -52,9 +52,13 @@
 //! The zisk_core crate contains basic structures and functionality used by several other modules:
 //! opcodes, instructions and transpilation
 pub mod elf2rom;
+pub mod elf_check;
 pub mod fcall;
+pub mod guest_exit_status;
 pub mod inst_context;
 pub mod mem;
+pub mod mem_console;
+pub mod resource_limits;
 pub mod riscv2zisk;
 pub mod riscv2zisk_context;
 mod utils;
@@ -66,11 +70,17 @@ pub mod zisk_registers;
 pub mod zisk_required_operation;
 pub mod zisk_rom;
 pub mod zisk_rom_2_asm;
+pub mod zisk_rom_dump;
+pub mod zisk_rom_optimizer;
 
 pub use elf2rom::*;
+pub use elf_check::*;
 pub use fcall::*;
+pub use guest_exit_status::*;
 pub use inst_context::*;
 pub use mem::*;
+pub use mem_console::*;
+pub use resource_limits::*;
 pub use riscv2zisk::*;
 pub use riscv2zisk_context::*;
 pub use utils::*;
@@ -81,3 +91,5 @@ pub use zisk_registers::*;
 pub use zisk_required_operation::*;
 pub use zisk_rom::*;
 pub use zisk_rom_2_asm::*;
+pub use zisk_rom_dump::*;
+pub use zisk_rom_optimizer::*;
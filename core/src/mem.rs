@@ -28,6 +28,8 @@
 //! `| Contains system address.`
 //! `| The first 256 bytes contain 32 8-byte registers`
 //! `| The address UART_ADDR is used as a standard output`
+//! `| The address UART_ERR_ADDR is used as a standard error`
+//! `| The address GUEST_EXIT_ADDR contains the exit status`
 //! `|`
 //! `|--------------- OUTPUT_ADDR                         (0xa0010000)`
 //! `|`
@@ -72,14 +74,17 @@
 //!   operation.
 //! * The lower addresses of this region is used to store 32 registers of 8 bytes each, i.e. 256
 //!   bytes in total.  These registers are the equivalent to the RISC-V registers.
-//! * Any data of exactly 1-byte length written to UART_ADDR will be sent to the standard output of
-//!   the system.
+//! * Any data of exactly 1-byte length written to UART_ADDR or UART_ERR_ADDR will be sent to the
+//!   guest standard output or standard error, respectively, which are printed to the host console
+//!   or captured, depending on the `MemConsole` configuration.
+//! * The program writes its exit code and, if it panicked, its panic message to `GUEST_EXIT_ADDR`
+//!   before ending, so that the host can report them.
 //! * The second RW memory region going from `OUTPUT_ADDR` to `AVAILABLE_MEM_ADDR` is reserved to
 //!   copy the output data during the program execution.
 //! * The third RW memory region going from `AVAILABLE_MEM_ADDR` onwards can be used during the
 //!   program execution a general purpose memory.
 
-use crate::{M16, M3, M32, M8, REG_FIRST, REG_LAST};
+use crate::{GuestStream, MemConsole, M16, M3, M32, M8, REG_FIRST, REG_LAST};
 use core::fmt;
 
 /// Fist input data memory address
@@ -116,6 +121,14 @@ pub const ROM_ADDR_MAX: u64 = (ROM_ADDR + 0x08000000) - 1; // 128M
 pub const ARCH_ID_ZISK: u64 = 0xFFFEEEE;
 /// UART memory address; single bytes written here will be copied to the standard output
 pub const UART_ADDR: u64 = SYS_ADDR + 512;
+/// UART memory address for the standard error; single bytes written here will be copied to the
+/// standard error
+pub const UART_ERR_ADDR: u64 = UART_ADDR + 8;
+/// Guest exit status memory address, containing the exit code (8 bytes), the panic message length
+/// (8 bytes) and the panic message data
+pub const GUEST_EXIT_ADDR: u64 = SYS_ADDR + 0x400;
+/// Maximum size of the guest panic message
+pub const GUEST_PANIC_MSG_MAX_SIZE: u64 = 0x400 - 16;
 
 /// Memory section data, including a buffer (a vector of bytes) and start and end program
 /// memory addresses.
@@ -165,13 +178,23 @@ pub struct Mem {
     pub read_sections: Vec<MemSection>,
     pub write_section: MemSection,
     pub free_input: u64,
+    pub console: MemConsole,
+    /// End of the highest write into the write section, i.e. the address right after the last
+    /// byte written, or 0 if nothing was written; used to measure the memory in use
+    pub write_end: u64,
 }
 
 impl Mem {
     /// Memory structue constructor
     pub fn new() -> Mem {
         //println!("Mem::new()");
-        Mem { read_sections: Vec::new(), write_section: MemSection::new(), free_input: 0 }
+        Mem {
+            read_sections: Vec::new(),
+            write_section: MemSection::new(),
+            free_input: 0,
+            console: MemConsole::default(),
+            write_end: 0,
+        }
     }
 
     /// Adds a read section to the memory structure
@@ -521,9 +544,13 @@ impl Mem {
         // Call write_silent to perform the real work
         self.write_silent(addr, val, width);
 
-        // Log to console bytes written to UART address
-        if (addr == UART_ADDR) && (width == 1) {
-            print!("{}", String::from(val as u8 as char));
+        // Send bytes written to UART addresses to the guest console
+        if width == 1 {
+            if addr == UART_ADDR {
+                self.console.write(GuestStream::Stdout, val as u8);
+            } else if addr == UART_ERR_ADDR {
+                self.console.write(GuestStream::Stderr, val as u8);
+            }
         }
     }
 
@@ -551,6 +578,7 @@ impl Mem {
                 "Mem::write_silent() section not found for addr={:x}={} with width: {}",
                 addr, addr, width
             );*/
+            self.write_end = self.write_end.max(addr + width);
             &mut self.write_section
         };
 
@@ -600,6 +628,7 @@ impl Mem {
                 "Mem::write_silent() section not found for addr={:x}={} with width: {}",
                 addr, addr, width
             );*/
+            self.write_end = self.write_end.max(addr + width);
             &mut self.write_section
         };
 
@@ -0,0 +1,208 @@
+//! Guest console, receiving the bytes written by the program to the UART addresses.
+//!
+//! * Bytes written to `UART_ADDR` belong to the guest standard output, and bytes written to
+//!   `UART_ERR_ADDR` belong to the guest standard error, as written by the ziskos `sys_write()`
+//!   for file descriptors 1 and 2.
+//! * By default, every byte is copied to the corresponding host stream as soon as it is written.
+//! * When a stream is captured, its bytes are appended to a buffer instead, so that the host can
+//!   retrieve the guest logs once the emulation is done, without mixing them with its own logs.
+//! * When a line callback is set, both streams are captured and every complete line is also
+//!   reported to the callback as soon as its end of line is written.
+
+use std::{
+    fmt,
+    io::{stderr, stdout, Write},
+};
+
+/// Guest output stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestStream {
+    Stdout,
+    Stderr,
+}
+
+impl fmt::Display for GuestStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuestStream::Stdout => write!(f, "stdout"),
+            GuestStream::Stderr => write!(f, "stderr"),
+        }
+    }
+}
+
+/// Callback called with every complete line written by the guest, without the end of line
+pub type GuestLineCallback = Box<dyn FnMut(GuestStream, &str) + Send + Sync>;
+
+/// Guest console state
+#[derive(Default)]
+pub struct MemConsole {
+    /// If true, the guest standard output is stored in `stdout` instead of being printed
+    pub capture_stdout: bool,
+    /// If true, the guest standard error is stored in `stderr` instead of being printed
+    pub capture_stderr: bool,
+    /// Captured guest standard output
+    pub stdout: Vec<u8>,
+    /// Captured guest standard error
+    pub stderr: Vec<u8>,
+    /// Optional callback to stream complete lines
+    line_callback: Option<GuestLineCallback>,
+    /// Position of the first byte not yet reported to the callback, per stream
+    line_start: [usize; 2],
+}
+
+impl fmt::Debug for MemConsole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemConsole")
+            .field("capture_stdout", &self.capture_stdout)
+            .field("capture_stderr", &self.capture_stderr)
+            .field("stdout", &self.stdout.len())
+            .field("stderr", &self.stderr.len())
+            .field("line_callback", &self.line_callback.is_some())
+            .finish()
+    }
+}
+
+impl MemConsole {
+    /// Creates a console that captures both guest streams
+    pub fn captured() -> Self {
+        Self { capture_stdout: true, capture_stderr: true, ..Default::default() }
+    }
+
+    /// Sets a callback to be called with every complete line written by the guest; this enables
+    /// the capture of both streams
+    pub fn set_line_callback(&mut self, callback: GuestLineCallback) {
+        self.capture_stdout = true;
+        self.capture_stderr = true;
+        self.line_callback = Some(callback);
+    }
+
+    /// Processes a byte written by the guest to a stream
+    #[inline(always)]
+    pub fn write(&mut self, stream: GuestStream, byte: u8) {
+        let (capture, buffer) = match stream {
+            GuestStream::Stdout => (self.capture_stdout, &mut self.stdout),
+            GuestStream::Stderr => (self.capture_stderr, &mut self.stderr),
+        };
+
+        if !capture {
+            // Errors writing to the host console are not relevant for the emulation
+            let _ = match stream {
+                GuestStream::Stdout => stdout().write_all(&[byte]),
+                GuestStream::Stderr => stderr().write_all(&[byte]),
+            };
+            return;
+        }
+
+        buffer.push(byte);
+        if byte == b'\n' {
+            self.report_line(stream);
+        }
+    }
+
+    /// Reports the last incomplete line of every stream to the callback, if any; to be called
+    /// once the emulation is done
+    pub fn flush(&mut self) {
+        for stream in [GuestStream::Stdout, GuestStream::Stderr] {
+            self.report_line(stream);
+        }
+    }
+
+    /// Reports the pending bytes of a stream to the callback, removing the end of line
+    fn report_line(&mut self, stream: GuestStream) {
+        let Some(callback) = self.line_callback.as_mut() else {
+            return;
+        };
+        let (buffer, start) = match stream {
+            GuestStream::Stdout => (&self.stdout, &mut self.line_start[0]),
+            GuestStream::Stderr => (&self.stderr, &mut self.line_start[1]),
+        };
+        if *start == buffer.len() {
+            return;
+        }
+        let line = &buffer[*start..];
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        callback(stream, &String::from_utf8_lossy(line));
+        *start = buffer.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mem, SYS_ADDR, SYS_SIZE, UART_ADDR, UART_ERR_ADDR};
+    use std::sync::{Arc, Mutex};
+
+    fn write_str(console: &mut MemConsole, stream: GuestStream, s: &str) {
+        for byte in s.bytes() {
+            console.write(stream, byte);
+        }
+    }
+
+    #[test]
+    fn test_captured_streams() {
+        let mut console = MemConsole::captured();
+        write_str(&mut console, GuestStream::Stdout, "out 1\n");
+        write_str(&mut console, GuestStream::Stderr, "err\n");
+        write_str(&mut console, GuestStream::Stdout, "out 2");
+        assert_eq!(console.stdout, b"out 1\nout 2");
+        assert_eq!(console.stderr, b"err\n");
+    }
+
+    #[test]
+    fn test_uncaptured_stream() {
+        let mut console = MemConsole { capture_stderr: true, ..Default::default() };
+        write_str(&mut console, GuestStream::Stdout, "out\n");
+        write_str(&mut console, GuestStream::Stderr, "err\n");
+        assert!(console.stdout.is_empty());
+        assert_eq!(console.stderr, b"err\n");
+    }
+
+    #[test]
+    fn test_line_callback() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let mut console = MemConsole::default();
+        {
+            let lines = lines.clone();
+            console.set_line_callback(Box::new(move |stream, line| {
+                lines.lock().unwrap().push((stream, line.to_string()))
+            }));
+        }
+        assert!(console.capture_stdout && console.capture_stderr);
+
+        write_str(&mut console, GuestStream::Stdout, "a\nb");
+        write_str(&mut console, GuestStream::Stderr, "c\n\n");
+        write_str(&mut console, GuestStream::Stdout, "c");
+        assert_eq!(
+            *lines.lock().unwrap(),
+            [
+                (GuestStream::Stdout, "a".to_string()),
+                (GuestStream::Stderr, "c".to_string()),
+                (GuestStream::Stderr, "".to_string()),
+            ]
+        );
+
+        // Only the incomplete stdout line is pending
+        console.flush();
+        console.flush();
+        assert_eq!(lines.lock().unwrap().len(), 4);
+        assert_eq!(lines.lock().unwrap()[3], (GuestStream::Stdout, "bc".to_string()));
+        assert_eq!(console.stdout, b"a\nbc");
+    }
+
+    #[test]
+    fn test_mem_uart_addresses() {
+        let mut mem = Mem::new();
+        mem.add_write_section(SYS_ADDR, SYS_SIZE);
+        mem.console = MemConsole::captured();
+
+        mem.write(UART_ADDR, b'o' as u64, 1);
+        mem.write(UART_ERR_ADDR, b'e' as u64, 1);
+
+        // Only single bytes are sent to the console
+        mem.write(UART_ADDR, b'x' as u64, 8);
+        mem.write(UART_ERR_ADDR + 1, b'x' as u64, 1);
+
+        assert_eq!(mem.console.stdout, b"o");
+        assert_eq!(mem.console.stderr, b"e");
+    }
+}
@@ -0,0 +1,215 @@
+//! Resource limits, used to run untrusted guest programs.
+//!
+//! * The number of steps is always limited, and reaching the limit before the program ends is
+//!   reported as a `ResourceLimit::Steps` error instead of an incomplete emulation.
+//! * The input data size is checked before the emulation starts.
+//! * The heap size and the output data size are checked periodically during the emulation, and
+//!   once it ends.  The heap size is measured by the emulator itself as the general purpose memory
+//!   in use, from `AVAILABLE_MEM_ADDR` up to the highest address written, so it also accounts for
+//!   the static data and the stack of the program.
+//! * The number of calls of every precompile with a configured limit is counted and checked at
+//!   every call.
+//! * The assembly emulator only enforces the steps limit while it runs, and reports the rest of
+//!   the usage once the emulation completes: the memory in use up to its highest non-zero word,
+//!   since it does not track its writes, the output data size and the calls of every precompile.
+//! * Unset limits default to the bounds of the memory map, e.g. `MAX_INPUT_SIZE` or
+//!   `OUTPUT_MAX_SIZE`.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    zisk_ops::{OpType, ZiskOp},
+    AVAILABLE_MEM_SIZE, DEFAULT_MAX_STEPS, MAX_INPUT_SIZE, OUTPUT_MAX_SIZE,
+};
+
+/// Period, in steps, of the memory usage checks done during the emulation
+pub const RESOURCE_LIMITS_CHECK_PERIOD: u64 = 1 << 16;
+
+/// Lowest op code of the precompiles; the assembly emulator counts their calls in an array indexed
+/// by `op code - PRECOMPILE_FIRST_OP`
+pub const PRECOMPILE_FIRST_OP: u8 = 0xf0;
+/// Number of op codes counted by the assembly emulator, starting at `PRECOMPILE_FIRST_OP`
+pub const PRECOMPILE_OPS: usize = 16;
+
+/// Resource limits of a guest program execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum number of steps
+    pub max_steps: u64,
+    /// Maximum heap size, in bytes
+    pub max_heap_size: Option<u64>,
+    /// Maximum input data size, in bytes
+    pub max_input_size: Option<u64>,
+    /// Maximum output data size, in bytes
+    pub max_output_size: Option<u64>,
+    /// Maximum number of calls per precompile
+    pub max_precompile_calls: BTreeMap<ZiskOp, u64>,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_steps: DEFAULT_MAX_STEPS,
+            max_heap_size: None,
+            max_input_size: None,
+            max_output_size: None,
+            max_precompile_calls: BTreeMap::new(),
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// Returns true if any limit has to be checked during the emulation, apart from the steps
+    pub fn check_during_emulation(&self) -> bool {
+        self.max_heap_size.is_some()
+            || self.max_output_size.is_some()
+            || !self.max_precompile_calls.is_empty()
+    }
+
+    /// Checks the number of executed steps of a program that did not end
+    pub fn check_steps(&self, steps: u64) -> Result<(), ResourceLimitExceeded> {
+        Self::check(ResourceLimit::Steps, steps, Some(self.max_steps), true)
+    }
+
+    /// Checks the input data size
+    pub fn check_input_size(&self, size: u64) -> Result<(), ResourceLimitExceeded> {
+        let max = self.max_input_size.unwrap_or(MAX_INPUT_SIZE - 16).min(MAX_INPUT_SIZE - 16);
+        Self::check(ResourceLimit::InputSize, size, Some(max), false)
+    }
+
+    /// Checks the heap size
+    pub fn check_heap_size(&self, size: u64) -> Result<(), ResourceLimitExceeded> {
+        let max = self.max_heap_size.unwrap_or(AVAILABLE_MEM_SIZE);
+        Self::check(ResourceLimit::HeapSize, size, Some(max), false)
+    }
+
+    /// Checks the output data size
+    pub fn check_output_size(&self, size: u64) -> Result<(), ResourceLimitExceeded> {
+        let max = self.max_output_size.unwrap_or(OUTPUT_MAX_SIZE);
+        Self::check(ResourceLimit::OutputSize, size, Some(max), false)
+    }
+
+    /// Checks the number of calls of a precompile, if it is limited
+    pub fn check_precompile_calls(
+        &self,
+        op: ZiskOp,
+        calls: u64,
+    ) -> Result<(), ResourceLimitExceeded> {
+        let max = self.max_precompile_calls.get(&op).copied();
+        Self::check(ResourceLimit::PrecompileCalls(op), calls, max, false)
+    }
+
+    /// Returns an error if `used` exceeds `max`, or if it reaches it when `inclusive` is true
+    fn check(
+        limit: ResourceLimit,
+        used: u64,
+        max: Option<u64>,
+        inclusive: bool,
+    ) -> Result<(), ResourceLimitExceeded> {
+        match max {
+            Some(max) if used > max || (inclusive && used == max) => {
+                Err(ResourceLimitExceeded { limit, used, max })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses a precompile limit in `name=calls` format, e.g. `keccak=1000`
+    pub fn parse_precompile_limit(s: &str) -> Result<(ZiskOp, u64), String> {
+        let (name, calls) =
+            s.split_once('=').ok_or_else(|| format!("expected <precompile>=<calls>, got {}", s))?;
+        let op = ZiskOp::try_from_name(name).map_err(|_| format!("unknown precompile {}", name))?;
+        if !Self::is_precompile(op) {
+            return Err(format!("{} is not a precompile", name));
+        }
+        let calls = calls.parse::<u64>().map_err(|e| format!("invalid calls {}: {}", calls, e))?;
+        Ok((op, calls))
+    }
+
+    /// Returns true if `op` is a precompile, whose number of calls can be limited
+    pub fn is_precompile(op: ZiskOp) -> bool {
+        !matches!(
+            op.op_type(),
+            OpType::Internal
+                | OpType::Arith
+                | OpType::ArithA32
+                | OpType::ArithAm32
+                | OpType::Binary
+                | OpType::BinaryE
+                | OpType::PubOut
+        )
+    }
+}
+
+/// Kind of resource limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    Steps,
+    HeapSize,
+    InputSize,
+    OutputSize,
+    PrecompileCalls(ZiskOp),
+}
+
+impl fmt::Display for ResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceLimit::Steps => write!(f, "steps"),
+            ResourceLimit::HeapSize => write!(f, "heap size"),
+            ResourceLimit::InputSize => write!(f, "input size"),
+            ResourceLimit::OutputSize => write!(f, "output size"),
+            ResourceLimit::PrecompileCalls(op) => write!(f, "{} calls", op.name()),
+        }
+    }
+}
+
+/// Error returned when a guest program exceeds one of its resource limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLimitExceeded {
+    /// Limit that was exceeded
+    pub limit: ResourceLimit,
+    /// Amount of the resource used when the limit was detected
+    pub used: u64,
+    /// Configured limit
+    pub max: u64,
+}
+
+impl fmt::Display for ResourceLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limit {
+            ResourceLimit::Steps => {
+                write!(f, "steps limit exceeded: the program did not end after {} steps", self.max)
+            }
+            _ => write!(f, "{} limit exceeded: {} > {}", self.limit, self.used, self.max),
+        }
+    }
+}
+
+impl std::error::Error for ResourceLimitExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precompile_op_codes() {
+        // The assembly emulator counts the calls of every precompile in a fixed size array
+        let precompiles: Vec<ZiskOp> = (0..=u8::MAX)
+            .filter_map(|code| ZiskOp::try_from_code(code).ok())
+            .filter(|op| ResourceLimits::is_precompile(*op))
+            .collect();
+        assert!(precompiles.contains(&ZiskOp::Keccak));
+        for op in precompiles {
+            let index = op.code().checked_sub(PRECOMPILE_FIRST_OP);
+            assert!(index.is_some_and(|index| (index as usize) < PRECOMPILE_OPS), "{}", op.name());
+        }
+        assert!(!ResourceLimits::is_precompile(ZiskOp::Add));
+    }
+
+    #[test]
+    fn test_parse_precompile_limit() {
+        assert_eq!(ResourceLimits::parse_precompile_limit("keccak=10"), Ok((ZiskOp::Keccak, 10)));
+        assert!(ResourceLimits::parse_precompile_limit("add=10").is_err());
+        assert!(ResourceLimits::parse_precompile_limit("keccak").is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! The input parameter is an ELF RISC-V file name, and the output parameter is a JSON Zisk ROM
 //! file.  Optionally, the Zisk ROM can also be saved in PIL-friendly format or in a binary format.
 
-use crate::{elf2rom, elf2romfile, ZiskRom};
+use crate::{elf2rom, elf2rom_optimized, elf2romfile, RomOptimizerStats, ZiskRom};
 use std::{error::Error, path::PathBuf};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -54,4 +54,11 @@ impl Riscv2zisk {
     pub fn run(&self) -> Result<ZiskRom, Box<dyn Error>> {
         elf2rom(&self.elf_file)
     }
+
+    /// Executes the file conversion process by calling elf2rom_optimized(), returning the
+    /// optimized ROM and the optimizer counters.  The optimized ROM is only meant to be emulated,
+    /// since the prover setup is built from the original ROM.
+    pub fn run_optimized(&self) -> Result<(ZiskRom, RomOptimizerStats), Box<dyn Error>> {
+        elf2rom_optimized(&self.elf_file)
+    }
 }
@@ -13,8 +13,20 @@ use std::collections::HashMap;
 // The CSR precompiled addresses are defined in the `ZiskOS` `ziskos/entrypoint/src` files
 // because legacy versions of Rust do not support constant parameters in `asm!` macros.
 
-const CSR_PRECOMPILED: [&str; 6] =
-    ["keccak", "arith256", "arith256_mod", "secp256k1_add", "secp256k1_dbl", "sha256"];
+const CSR_PRECOMPILED: [&str; 12] = [
+    "keccak",
+    "arith256",
+    "arith256_mod",
+    "secp256k1_add",
+    "secp256k1_dbl",
+    "sha256",
+    "curve25519_mod",
+    "ed25519_dbl",
+    "blake2br",
+    "ed25519_add",
+    "memcpy",
+    "memset",
+];
 const CSR_PRECOMPILED_ADDR_START: u32 = 0x800;
 const CSR_PRECOMPILED_ADDR_END: u32 = CSR_PRECOMPILED_ADDR_START + CSR_PRECOMPILED.len() as u32;
 const CSR_FCALL_ADDR_START: u32 = 0x8C0;
@@ -25,11 +37,36 @@ const CSR_FCALL_PARAM_ADDR_END: u32 = 0x8FF;
 const CSR_FCALL_PARAM_OFFSET_TO_WORDS: [u64; 16] =
     [1, 2, 4, 8, 12, 16, 20, 24, 28, 32, 48, 64, 80, 96, 128, 256];
 
+/// RISC-V machine architecture ID CSR, initialized by the BIOS with `ARCH_ID_ZISK`
+const CSR_MARCHID_ADDR: u32 = 0xF12;
+/// RISC-V machine trap vector CSR, initialized by the BIOS with the ecall handler address
+const CSR_MTVEC_ADDR: u32 = 0x305;
+
 const CAUSE_EXIT: u64 = 93;
 const CSR_ADDR: u64 = SYS_ADDR + 0x8000;
-const MTVEC: u64 = CSR_ADDR + 0x305;
+const MTVEC: u64 = CSR_ADDR + CSR_MTVEC_ADDR as u64;
 const M64: u64 = 0xFFFFFFFFFFFFFFFF;
 
+/// Returns the ZisK meaning of a CSR address, or None if the CSR is not handled by ZisK and is
+/// only emulated as a plain memory location
+pub fn zisk_csr_name(csr: u32) -> Option<&'static str> {
+    if (CSR_PRECOMPILED_ADDR_START..CSR_PRECOMPILED_ADDR_END).contains(&csr) {
+        Some(CSR_PRECOMPILED[(csr - CSR_PRECOMPILED_ADDR_START) as usize])
+    } else if (CSR_FCALL_ADDR_START..=CSR_FCALL_ADDR_END).contains(&csr) {
+        Some("fcall")
+    } else if (CSR_FCALL_PARAM_ADDR_START..=CSR_FCALL_PARAM_ADDR_END).contains(&csr) {
+        Some("fcall_param")
+    } else if csr == CSR_FCALL_GET_ADDR {
+        Some("fcall_get")
+    } else if csr == CSR_MARCHID_ADDR {
+        Some("marchid")
+    } else if csr == CSR_MTVEC_ADDR {
+        Some("mtvec")
+    } else {
+        None
+    }
+}
+
 /// Context to store the list of converted ZisK instructions, including their program address and a
 /// map to store the instructions
 pub struct Riscv2ZiskContext<'a> {
@@ -43,6 +80,23 @@ impl Riscv2ZiskContext<'_> {
     /// Converts an input RISCV instruction into a ZisK instruction and stores it into the internal
     /// map
     pub fn convert(&mut self, riscv_instruction: &RiscvInstruction) {
+        if !self.try_convert(riscv_instruction) {
+            panic!(
+                "Riscv2ZiskContext::convert() found invalid riscv_instruction.inst={}",
+                riscv_instruction.inst
+            );
+        }
+    }
+
+    /// Returns true if `convert` supports the mnemonic of a RISCV instruction found at `addr`
+    pub fn can_convert(riscv_instruction: &RiscvInstruction, addr: u64) -> bool {
+        let mut insts = HashMap::new();
+        Riscv2ZiskContext { s: addr, insts: &mut insts }.try_convert(riscv_instruction)
+    }
+
+    /// Converts an input RISCV instruction like `convert`, returning false if its mnemonic is not
+    /// supported
+    fn try_convert(&mut self, riscv_instruction: &RiscvInstruction) -> bool {
         match riscv_instruction.inst.as_str() {
             "lb" => self.load_op(riscv_instruction, "signextend_b", 1),
             "lbu" => self.load_op(riscv_instruction, "copyb", 1),
@@ -152,11 +206,9 @@ impl Riscv2ZiskContext<'_> {
             "csrrwi" => self.csrrwi(riscv_instruction),
             "csrrsi" => self.csrrsi(riscv_instruction),
             "csrrci" => self.csrrci(riscv_instruction),
-            _ => panic!(
-                "Riscv2ZiskContext::convert() found invalid riscv_instruction.inst={}",
-                riscv_instruction.inst
-            ),
+            _ => return false,
         }
+        true
     }
 
     /*amoadd.w rs1, rs2, rd
@@ -875,7 +927,7 @@ impl Riscv2ZiskContext<'_> {
             let mut zib = ZiskInstBuilder::new(self.s);
             zib.src_b("reg", i.rs1 as u64, false);
             zib.j(4, 4);
-            if (CSR_PRECOMPILED_ADDR_START..=CSR_PRECOMPILED_ADDR_END).contains(&i.csr) {
+            if (CSR_PRECOMPILED_ADDR_START..CSR_PRECOMPILED_ADDR_END).contains(&i.csr) {
                 zib.src_a("step", 0, false);
                 let precompiled = CSR_PRECOMPILED[(i.csr - CSR_PRECOMPILED_ADDR_START) as usize];
                 zib.op(precompiled).unwrap();
@@ -1522,7 +1574,7 @@ pub fn add_entry_exit_jmp(rom: &mut ZiskRom, addr: u64) {
     zib.src_a("imm", 0, false);
     zib.src_b("imm", ARCH_ID_ZISK, false);
     zib.op("copyb").unwrap();
-    zib.store("mem", CSR_ADDR as i64 + 0xF12, false, false);
+    zib.store("mem", CSR_ADDR as i64 + CSR_MARCHID_ADDR as i64, false, false);
     zib.j(4, 4);
     zib.verbose(&format!("Set marchid: {:x}", ARCH_ID_ZISK));
     zib.build();
@@ -1779,3 +1831,77 @@ pub fn add_end_jmp(rom: &mut ZiskRom) {
     rom.insts.insert(rom.next_init_inst_addr, zib);
     rom.next_init_inst_addr += 4;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ROM_ADDR;
+    use riscv::{Rvd, RvdOperation};
+
+    /// Converts `csrrs x0, csr, x10` and returns the operation of the generated instruction
+    fn convert_csrrs(csr: u32) -> &'static str {
+        let riscv_instruction =
+            RiscvInstruction { inst: "csrrs".to_string(), rs1: 10, csr, ..Default::default() };
+        let mut insts = HashMap::new();
+        let mut ctx = Riscv2ZiskContext { s: ROM_ADDR, insts: &mut insts };
+        ctx.convert(&riscv_instruction);
+        assert_eq!(insts.len(), 1);
+        insts[&ROM_ADDR].i.op_str
+    }
+
+    #[test]
+    fn test_can_convert_decoded_instructions() {
+        fn mnemonics(op: &RvdOperation, names: &mut Vec<String>) {
+            if !op.s.is_empty() {
+                names.push(op.s.clone());
+            }
+            for op in op.map.values() {
+                mnemonics(op, names);
+            }
+        }
+
+        // Every instruction the interpreter can decode must be transpiled
+        let mut rvd = Rvd::new();
+        rvd.init();
+        let mut names = Vec::new();
+        for info in rvd.opcodes.values() {
+            mnemonics(&info.op, &mut names);
+        }
+        assert!(!names.is_empty());
+        for inst in names {
+            let riscv_instruction = RiscvInstruction { inst, ..Default::default() };
+            assert!(
+                Riscv2ZiskContext::can_convert(&riscv_instruction, ROM_ADDR),
+                "{} is not transpiled",
+                riscv_instruction.inst
+            );
+        }
+
+        let riscv_instruction = RiscvInstruction { inst: "mret".to_string(), ..Default::default() };
+        assert!(!Riscv2ZiskContext::can_convert(&riscv_instruction, ROM_ADDR));
+    }
+
+    #[test]
+    fn test_precompiled_csr_range() {
+        assert_eq!(zisk_csr_name(CSR_PRECOMPILED_ADDR_START), Some(CSR_PRECOMPILED[0]));
+        assert_eq!(
+            zisk_csr_name(CSR_PRECOMPILED_ADDR_END - 1),
+            Some(CSR_PRECOMPILED[CSR_PRECOMPILED.len() - 1])
+        );
+        assert_eq!(zisk_csr_name(CSR_PRECOMPILED_ADDR_END), None);
+    }
+
+    #[test]
+    fn test_csrrs_precompiled() {
+        for (n, precompiled) in CSR_PRECOMPILED.iter().enumerate() {
+            assert_eq!(convert_csrrs(CSR_PRECOMPILED_ADDR_START + n as u32), *precompiled);
+        }
+    }
+
+    #[test]
+    fn test_csrrs_after_precompiled_range() {
+        // The first CSR after the precompiles is a plain memory location, not an out of bounds
+        // precompile
+        assert_eq!(convert_csrrs(CSR_PRECOMPILED_ADDR_END), "or");
+    }
+}
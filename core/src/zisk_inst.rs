@@ -85,6 +85,8 @@ pub enum ZiskOperationType {
     FcallParam,
     Fcall,
     FcallGet,
+    Blake2,
+    Dma,
 }
 
 pub const ZISK_OP_TYPE_COUNT: usize = 10;
@@ -10,7 +10,7 @@
 #![allow(unused)]
 
 use precompiles_helpers::sha256f;
-use ziskos::fcall_proxy;
+use ziskos::{fcall_proxy, is_user_fcall_id};
 
 use generic_array::{typenum::U64, GenericArray};
 use sha2::compress256;
@@ -23,15 +23,15 @@ use std::{
 use tiny_keccak::keccakf;
 
 use crate::{
-    EmulationMode, InstContext, Mem, ZiskOperationType, ZiskRequiredOperation, M64, REG_A0,
-    SYS_ADDR,
+    EmulationMode, InstContext, Mem, ZiskInst, ZiskOperationType, ZiskRequiredOperation, M64,
+    REG_A0, SYS_ADDR,
 };
 
 use lib_c::{inverse_fn_ec_c, inverse_fp_ec_c, sqrt_fp_ec_parity_c, Fcall, FcallContext};
 
 use crate::{
     FCALL_ID_INVERSE_FN_EC, FCALL_ID_INVERSE_FP_EC, FCALL_ID_SQRT_FP_EC_PARITY,
-    FCALL_PARAMS_MAX_SIZE, FCALL_RESULT_MAX_SIZE,
+    FCALL_RESULT_MIN_SIZE,
 };
 
 /// Determines the type of a [`ZiskOp`].
@@ -52,6 +52,8 @@ pub enum OpType {
     PubOut,
     ArithEq,
     Fcall,
+    Blake2,
+    Dma,
 }
 
 impl From<OpType> for ZiskOperationType {
@@ -66,6 +68,8 @@ impl From<OpType> for ZiskOperationType {
             OpType::PubOut => ZiskOperationType::PubOut,
             OpType::ArithEq => ZiskOperationType::ArithEq,
             OpType::Fcall => ZiskOperationType::Fcall,
+            OpType::Blake2 => ZiskOperationType::Blake2,
+            OpType::Dma => ZiskOperationType::Dma,
         }
     }
 }
@@ -84,6 +88,8 @@ impl Display for OpType {
             Self::PubOut => write!(f, "PubOut"),
             Self::ArithEq => write!(f, "Arith256"),
             Self::Fcall => write!(f, "Fcall"),
+            Self::Blake2 => write!(f, "Blake2"),
+            Self::Dma => write!(f, "Dma"),
         }
     }
 }
@@ -103,6 +109,8 @@ impl FromStr for OpType {
             "s" => Ok(Self::Sha256),
             "aeq" => Ok(Self::ArithEq),
             "fcall" => Ok(Self::Fcall),
+            "blake2" => Ok(Self::Blake2),
+            "dma" => Ok(Self::Dma),
             _ => Err(InvalidOpTypeError),
         }
     }
@@ -272,6 +280,8 @@ const ARITHA32_COST: u64 = 95;
 const ARITHAM32_COST: u64 = 95;
 const KECCAK_COST: u64 = 145000;
 const SHA256_COST: u64 = 0; // TODO: To be decide
+const BLAKE2_COST: u64 = 0; // TODO: To be decide
+const DMA_COST: u64 = 0; // TODO: To be decide
 const ARITH_EQ_COST: u64 = 1200;
 const FCALL_COST: u64 = INTERNAL_COST;
 
@@ -340,6 +350,12 @@ define_ops! {
     (Fcall, "fcall", Fcall, FCALL_COST, 0xf7, 0, opc_fcall, op_fcall),
     (FcallGet, "fcall_get", Fcall, FCALL_COST, 0xf8, 0, opc_fcall_get, op_fcall_get),
     (Sha256, "sha256", Sha256, SHA256_COST, 0xf9, 96, opc_sha256, op_sha256),
+    (Curve25519Mod, "curve25519_mod", ArithEq, ARITH_EQ_COST, 0xfa, 128, opc_curve25519_mod, op_curve25519_mod),
+    (Ed25519Dbl, "ed25519_dbl", ArithEq, ARITH_EQ_COST, 0xfb, 64, opc_ed25519_dbl, op_ed25519_dbl),
+    (Ed25519Add, "ed25519_add", ArithEq, ARITH_EQ_COST, 0xf0, 144, opc_ed25519_add, op_ed25519_add),
+    (Blake2br, "blake2br", Blake2, BLAKE2_COST, 0xfc, 256, opc_blake2br, op_blake2br),
+    (Memcpy, "memcpy", Dma, DMA_COST, 0xfd, 24, opc_memcpy, op_memcpy),
+    (Memset, "memset", Dma, DMA_COST, 0xfe, 24, opc_memset, op_memset),
 }
 
 /* INTERNAL operations */
@@ -1524,6 +1540,280 @@ pub fn op_secp256k1_dbl(_a: u64, _b: u64) -> (u64, bool) {
     unimplemented!("op_secp256k1_dbl() is not implemented");
 }
 
+#[inline(always)]
+pub fn opc_curve25519_mod(ctx: &mut InstContext) {
+    const WORDS: usize = 4 + 3 * 4;
+    let mut data = [0u64; WORDS];
+
+    precompiled_load_data(ctx, 4, 3, 4, &mut data, "curve25519_mod");
+
+    // ignore 4 indirections
+    let (_, rest) = data.split_at(4);
+    let (a, rest) = rest.split_at(4);
+    let (b, c) = rest.split_at(4);
+
+    let a: &[u64; 4] = a.try_into().expect("opc_curve25519_mod: a.len != 4");
+    let b: &[u64; 4] = b.try_into().expect("opc_curve25519_mod: b.len != 4");
+    let c: &[u64; 4] = c.try_into().expect("opc_curve25519_mod: c.len != 4");
+
+    let mut d = [0u64; 4];
+
+    precompiles_helpers::curve25519_mod(a, b, c, &mut d);
+
+    // [a,b,c,3:d]
+    for (i, d) in d.iter().enumerate() {
+        ctx.mem.write(data[3] + (8 * i as u64), *d, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Curve25519Mod can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_curve25519_mod(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_curve25519_mod() is not implemented");
+}
+
+#[inline(always)]
+pub fn opc_ed25519_dbl(ctx: &mut InstContext) {
+    const WORDS: usize = 8; // one input of 8 64-bit words
+    let mut data = [0u64; WORDS];
+
+    precompiled_load_data(ctx, 0, 1, 8, &mut data, "ed25519_dbl");
+
+    let p1: &[u64; 8] = &data;
+    let mut p3 = [0u64; 8];
+
+    precompiles_helpers::ed25519_dbl(p1, &mut p3);
+
+    for (i, d) in p3.iter().enumerate() {
+        ctx.mem.write(ctx.b + (8 * i as u64), *d, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Ed25519Dbl can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_ed25519_dbl(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_ed25519_dbl() is not implemented");
+}
+
+#[inline(always)]
+pub fn opc_ed25519_add(ctx: &mut InstContext) {
+    const WORDS: usize = 2 + 2 * 8;
+    let mut data = [0u64; WORDS];
+
+    precompiled_load_data(ctx, 2, 2, 8, &mut data, "ed25519_add");
+
+    // ignore 2 indirections
+    let (_, rest) = data.split_at(2);
+    let (p1, p2) = rest.split_at(8);
+
+    let p1: &[u64; 8] = p1.try_into().expect("opc_ed25519_add: p1.len != 8");
+    let p2: &[u64; 8] = p2.try_into().expect("opc_ed25519_add: p2.len != 8");
+    let mut p3 = [0u64; 8];
+
+    precompiles_helpers::ed25519_add(p1, p2, &mut p3);
+
+    // [0:p1,p2]
+    for (i, d) in p3.iter().enumerate() {
+        ctx.mem.write(data[0] + (8 * i as u64), *d, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Ed25519Add can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_ed25519_add(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_ed25519_add() is not implemented");
+}
+
+/// Performs a single BLAKE2b round over the working vector and the permuted message words found at
+/// address b, writing the updated working vector back in place
+#[inline(always)]
+pub fn opc_blake2br(ctx: &mut InstContext) {
+    const WORDS: usize = 32; // 16 64-bit words of working vector + 16 64-bit words of message
+    let mut data = [0u64; WORDS];
+
+    precompiled_load_data(ctx, 0, 1, WORDS, &mut data, "blake2br");
+
+    let (v, m) = data.split_at_mut(16);
+    let v: &mut [u64; 16] = v.try_into().unwrap();
+    let m: &[u64; 16] = (&*m).try_into().unwrap();
+
+    precompiles_helpers::blake2b_round(v, m);
+
+    for (i, d) in v.iter().enumerate() {
+        ctx.mem.write(ctx.b + (8 * i as u64), *d, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Blake2br can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_blake2br(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_blake2br() is not implemented");
+}
+
+/// Maximum number of 64-bit words written by a single dma operation (memcpy or memset)
+pub const DMA_MAX_WORDS: u64 = 64;
+
+/// Number of 64-bit parameters of a dma operation, stored at the address in b: destination
+/// address, source address (memcpy) or fill word (memset), and number of words
+pub const DMA_PARAMS: usize = 3;
+
+/// Returns the number of memory reads consumed by a precompiled operation, where `mem_reads` starts
+/// at its first precompiled read.  Every precompiled operation reads a fixed amount of data, except
+/// memcpy, which also reads as many source words as its count parameter.
+#[inline(always)]
+pub fn precompiled_mem_reads_count(inst: &ZiskInst, mem_reads: &[u64]) -> usize {
+    let count = ((inst.input_size + 7) >> 3) as usize;
+    if inst.op == ZiskOp::Memcpy.code() {
+        count + mem_reads[2] as usize
+    } else {
+        count
+    }
+}
+
+/// Loads the parameters of a dma operation, from the address in b or from the precompiled context,
+/// and checks that the addresses are aligned to 8 bytes and the number of words is in the range
+/// 1..=DMA_MAX_WORDS
+#[inline(always)]
+fn dma_load_params(ctx: &mut InstContext, is_memcpy: bool, title: &str) -> [u64; DMA_PARAMS] {
+    let address = ctx.b;
+    if address & 0x7 != 0 {
+        panic!("[{}] found params address=0x{:x} not aligned to 8 bytes", title, address);
+    }
+
+    let mut params = [0u64; DMA_PARAMS];
+    if let EmulationMode::ConsumeMemReads = ctx.emulation_mode {
+        if ctx.precompiled.input_data.len() < DMA_PARAMS {
+            panic!(
+                "[{}] ctx.precompiled.input_data.len={} < {}",
+                title,
+                ctx.precompiled.input_data.len(),
+                DMA_PARAMS
+            );
+        }
+        params.copy_from_slice(&ctx.precompiled.input_data[..DMA_PARAMS]);
+    } else {
+        for (i, param) in params.iter_mut().enumerate() {
+            *param = ctx.mem.read(address + (8 * i as u64), 8);
+        }
+    }
+
+    let [dst, src, count] = params;
+    if (dst & 0x7 != 0) || (is_memcpy && (src & 0x7 != 0)) {
+        panic!("[{}] found dst=0x{:x} src=0x{:x} not aligned to 8 bytes", title, dst, src);
+    }
+    if count == 0 || count > DMA_MAX_WORDS {
+        panic!("[{}] found count={} out of range 1..={}", title, count, DMA_MAX_WORDS);
+    }
+
+    params
+}
+
+/// Copies count words from the src address to the dst address.  The parameters [dst, src, count]
+/// are stored at the address in b, where dst and src must be aligned to 8 bytes and count must be in
+/// the range 1..=DMA_MAX_WORDS.  All the source words are read before writing any destination word,
+/// so overlapping areas are supported.
+#[inline(always)]
+pub fn opc_memcpy(ctx: &mut InstContext) {
+    let params = dma_load_params(ctx, true, "memcpy");
+    let [dst, src, count] = params;
+    let count = count as usize;
+
+    let mut data = [0u64; DMA_MAX_WORDS as usize];
+    if let EmulationMode::ConsumeMemReads = ctx.emulation_mode {
+        if ctx.precompiled.input_data.len() != DMA_PARAMS + count {
+            panic!(
+                "[memcpy] ctx.precompiled.input_data.len={} != {} [{}+{}]",
+                ctx.precompiled.input_data.len(),
+                DMA_PARAMS + count,
+                DMA_PARAMS,
+                count
+            );
+        }
+        data[..count].copy_from_slice(&ctx.precompiled.input_data[DMA_PARAMS..]);
+    } else {
+        for (i, d) in data.iter_mut().take(count).enumerate() {
+            *d = ctx.mem.read(src + (8 * i as u64), 8);
+        }
+    }
+    if let EmulationMode::GenerateMemReads = ctx.emulation_mode {
+        ctx.precompiled.input_data.clear();
+        ctx.precompiled.input_data.extend_from_slice(&params);
+        ctx.precompiled.input_data.extend_from_slice(&data[..count]);
+        ctx.precompiled.step = ctx.step;
+    }
+
+    for (i, d) in data.iter().take(count).enumerate() {
+        ctx.mem.write(dst + (8 * i as u64), *d, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Memcpy can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_memcpy(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_memcpy() is not implemented");
+}
+
+/// Fills count words at the dst address with the fill word.  The parameters [dst, fill, count] are
+/// stored at the address in b, where dst must be aligned to 8 bytes and count must be in the range
+/// 1..=DMA_MAX_WORDS.
+#[inline(always)]
+pub fn opc_memset(ctx: &mut InstContext) {
+    let params = dma_load_params(ctx, false, "memset");
+    let [dst, fill, count] = params;
+
+    match ctx.emulation_mode {
+        EmulationMode::GenerateMemReads => {
+            ctx.precompiled.input_data.clear();
+            ctx.precompiled.input_data.extend_from_slice(&params);
+            ctx.precompiled.step = ctx.step;
+        }
+        EmulationMode::ConsumeMemReads => {
+            if ctx.precompiled.input_data.len() != DMA_PARAMS {
+                panic!(
+                    "[memset] ctx.precompiled.input_data.len={} != {}",
+                    ctx.precompiled.input_data.len(),
+                    DMA_PARAMS
+                );
+            }
+        }
+        EmulationMode::Mem => {}
+    }
+
+    for i in 0..count {
+        ctx.mem.write(dst + 8 * i, fill, 8);
+    }
+
+    ctx.c = 0;
+    ctx.flag = false;
+}
+
+/// Unimplemented.  Memset can only be called from the system call context via InstContext.
+/// This is provided just for completeness.
+#[inline(always)]
+pub fn op_memset(_a: u64, _b: u64) -> (u64, bool) {
+    unimplemented!("op_memset() is not implemented");
+}
+
 impl From<ZiskRequiredOperation> for ZiskOp {
     fn from(value: ZiskRequiredOperation) -> Self {
         ZiskOp::try_from_code(value.opcode).unwrap()
@@ -1569,26 +1859,17 @@ pub fn opc_fcall_param(ctx: &mut InstContext) {
     // Get param chunk from b
     let param = ctx.b;
 
-    // Check for consistency
-    if (ctx.fcall.parameters_size + words) as usize >= FCALL_PARAMS_MAX_SIZE {
-        panic!(
-            "opc_fcall_param({0}) called with ctx.fcall.parameters_size({1}) + param({0})>={2}",
-            words, ctx.fcall.parameters_size, FCALL_PARAMS_MAX_SIZE
-        );
-    }
-
-    // Store param in context
+    // Store param in context; the parameters can have any size
     if words == 1 {
-        ctx.fcall.parameters[ctx.fcall.parameters_size as usize] = param;
-        ctx.fcall.parameters_size += 1;
+        ctx.fcall.parameters.push(param);
     } else {
         let addr = param;
         for i in 0..words {
             let value = ctx.mem.read(addr + i * 8, 8);
-            ctx.fcall.parameters[(ctx.fcall.parameters_size + i) as usize] = value;
+            ctx.fcall.parameters.push(value);
         }
-        ctx.fcall.parameters_size += words;
     }
+    ctx.fcall.parameters_size += words;
 }
 
 /// Implements fcall, free input data calls
@@ -1613,7 +1894,28 @@ pub fn opc_fcall(ctx: &mut InstContext) {
     // Get function id from a
     let function_id = ctx.a;
 
-    let iresult = fcall_proxy(function_id, &ctx.fcall.parameters, &mut ctx.fcall.result);
+    let iresult = if is_user_fcall_id(function_id) {
+        let user_fcalls = ctx.fcall.user_fcalls.as_ref().unwrap_or_else(|| {
+            panic!("opc_fcall() found user fcall function_id={function_id} but none is registered")
+        });
+        match user_fcalls.call(function_id, &ctx.fcall.parameters) {
+            Ok(result) => {
+                ctx.fcall.result = result;
+                ctx.fcall.result.len() as i64
+            }
+            Err(e) => {
+                panic!("opc_fcall() failed calling user fcall function_id={function_id}: {e}")
+            }
+        }
+    } else {
+        // Built-in fcalls never return more words than they get as parameters, except for the
+        // fixed-size results
+        ctx.fcall.result.clear();
+        ctx.fcall.result.resize(ctx.fcall.parameters.len().max(FCALL_RESULT_MIN_SIZE), 0);
+        let iresult = fcall_proxy(function_id, &ctx.fcall.parameters, &mut ctx.fcall.result);
+        ctx.fcall.result.truncate(iresult.max(0) as usize);
+        iresult
+    };
 
     if iresult < 0 {
         panic!(
@@ -1630,6 +1932,7 @@ pub fn opc_fcall(ctx: &mut InstContext) {
     }
     ctx.fcall.result_got = 1;
     ctx.fcall.result_size = iresult as u64;
+    ctx.fcall.parameters.clear();
     ctx.fcall.parameters_size = 0;
 }
 
@@ -1654,9 +1957,6 @@ pub fn opc_fcall_get(ctx: &mut InstContext) {
     if ctx.fcall.result_size == 0 {
         panic!("opc_fcall_get() called with ctx.fcall.result_size==0");
     }
-    if ctx.fcall.result_size as usize > FCALL_RESULT_MAX_SIZE {
-        panic!("opc_fcall_get() called with ctx.fcall.result_size=={}>32", ctx.fcall.result_size);
-    }
     if ctx.fcall.result_got > ctx.fcall.result_size {
         panic!(
             "opc_fcall_get() called with ctx.fcall.result_got({}) >= ctx.fcall.result_size {}",
@@ -1673,3 +1973,119 @@ pub fn opc_fcall_get(ctx: &mut InstContext) {
     ctx.fcall.result_got += 1;
     ctx.flag = false;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ZiskInstBuilder, RAM_ADDR};
+
+    // Above the registers, which are at the start of the RAM
+    const PARAMS_ADDR: u64 = RAM_ADDR + 0x10000;
+    const DATA_ADDR: u64 = PARAMS_ADDR + 0x100;
+
+    /// Creates a context whose memory has a write section with the dma parameters at
+    /// `PARAMS_ADDR` and the words `0x1000 + i` from `DATA_ADDR`
+    fn dma_context(params: [u64; DMA_PARAMS]) -> InstContext {
+        let mut ctx = InstContext::new();
+        ctx.mem.add_write_section(RAM_ADDR, 0x11000);
+        for (i, param) in params.iter().enumerate() {
+            ctx.mem.write(PARAMS_ADDR + 8 * i as u64, *param, 8);
+        }
+        for i in 0..2 * DMA_MAX_WORDS {
+            ctx.mem.write(DATA_ADDR + 8 * i, 0x1000 + i, 8);
+        }
+        ctx.b = PARAMS_ADDR;
+        ctx
+    }
+
+    fn read_words(ctx: &InstContext, addr: u64, count: u64) -> Vec<u64> {
+        (0..count).map(|i| ctx.mem.read(addr + 8 * i, 8)).collect()
+    }
+
+    #[test]
+    fn test_memcpy_overlapping_forward() {
+        let mut ctx = dma_context([DATA_ADDR + 16, DATA_ADDR, 4]);
+        opc_memcpy(&mut ctx);
+        assert_eq!(
+            read_words(&ctx, DATA_ADDR, 7),
+            [0x1000, 0x1001, 0x1000, 0x1001, 0x1002, 0x1003, 0x1006]
+        );
+    }
+
+    #[test]
+    fn test_memcpy_overlapping_backward() {
+        let mut ctx = dma_context([DATA_ADDR, DATA_ADDR + 16, 4]);
+        opc_memcpy(&mut ctx);
+        assert_eq!(
+            read_words(&ctx, DATA_ADDR, 7),
+            [0x1002, 0x1003, 0x1004, 0x1005, 0x1004, 0x1005, 0x1006]
+        );
+    }
+
+    #[test]
+    fn test_memcpy_generate_and_consume_mem_reads() {
+        let params = [DATA_ADDR + 8 * DMA_MAX_WORDS, DATA_ADDR, 3];
+        let mut ctx = dma_context(params);
+        ctx.emulation_mode = EmulationMode::GenerateMemReads;
+        ctx.step = 7;
+        opc_memcpy(&mut ctx);
+        assert_eq!(
+            ctx.precompiled.input_data,
+            [params.as_slice(), &[0x1000, 0x1001, 0x1002]].concat()
+        );
+        assert_eq!(ctx.precompiled.step, 7);
+
+        // Consuming the generated reads copies the words of the reads, not those in memory
+        let mut ctx = dma_context(params);
+        ctx.emulation_mode = EmulationMode::ConsumeMemReads;
+        ctx.precompiled.input_data = [params.as_slice(), &[1, 2, 3]].concat();
+        opc_memcpy(&mut ctx);
+        assert_eq!(read_words(&ctx, params[0], 3), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_memset() {
+        let fill = 0x0123_4567_89ab_cdef;
+        let mut ctx = dma_context([DATA_ADDR + 8, fill, DMA_MAX_WORDS]);
+        ctx.emulation_mode = EmulationMode::GenerateMemReads;
+        opc_memset(&mut ctx);
+        assert_eq!(ctx.precompiled.input_data, [DATA_ADDR + 8, fill, DMA_MAX_WORDS]);
+        assert_eq!(ctx.mem.read(DATA_ADDR, 8), 0x1000);
+        assert!(read_words(&ctx, DATA_ADDR + 8, DMA_MAX_WORDS).iter().all(|&word| word == fill));
+        assert_eq!(
+            ctx.mem.read(DATA_ADDR + 8 * (DMA_MAX_WORDS + 1), 8),
+            0x1000 + DMA_MAX_WORDS + 1
+        );
+    }
+
+    #[test]
+    fn test_precompiled_mem_reads_count() {
+        let inst = |op: &str| {
+            let mut builder = ZiskInstBuilder::new(0);
+            builder.op(op).unwrap();
+            builder.i
+        };
+
+        assert_eq!(precompiled_mem_reads_count(&inst("memcpy"), &[0, 0, 5, 9, 9]), DMA_PARAMS + 5);
+        assert_eq!(precompiled_mem_reads_count(&inst("memset"), &[0, 0, 5, 9, 9]), DMA_PARAMS);
+        assert_eq!(precompiled_mem_reads_count(&inst("blake2br"), &[0; 32]), 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "not aligned to 8 bytes")]
+    fn test_memcpy_unaligned_src() {
+        opc_memcpy(&mut dma_context([DATA_ADDR, DATA_ADDR + 4, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_memset_zero_count() {
+        opc_memset(&mut dma_context([DATA_ADDR, 0, 0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_memcpy_count_too_large() {
+        opc_memcpy(&mut dma_context([DATA_ADDR, DATA_ADDR, DMA_MAX_WORDS + 1]));
+    }
+}
@@ -4,9 +4,10 @@
 use std::path::Path;
 
 use crate::{
-    zisk_ops::ZiskOp, AsmGenerationMethod, ZiskInst, ZiskRom, FREE_INPUT_ADDR, M64, P2_32,
-    ROM_ADDR, ROM_ADDR_MAX, ROM_ENTRY, SRC_C, SRC_IMM, SRC_IND, SRC_MEM, SRC_REG, SRC_STEP,
-    STORE_IND, STORE_MEM, STORE_NONE, STORE_REG,
+    zisk_ops::ZiskOp, AsmGenerationMethod, ResourceLimits, ZiskInst, ZiskRom, FREE_INPUT_ADDR, M64,
+    P2_32, PRECOMPILE_FIRST_OP, ROM_ADDR, ROM_ADDR_MAX, ROM_ENTRY, SRC_C, SRC_IMM, SRC_IND,
+    SRC_MEM, SRC_REG, SRC_STEP, STORE_IND, STORE_MEM, STORE_NONE, STORE_REG, UART_ADDR,
+    UART_ERR_ADDR,
 };
 
 // Regs rax, rcx, rdx, rdi, rsi, rsp, and r8-r11 are caller-save, not saved across function calls.
@@ -50,15 +51,16 @@ const REG_ACTIVE_CHUNK: &str = "rbp";
 const TRACE_ADDR: &str = "0xc0000020";
 const TRACE_ADDR_NUMBER: u64 = 0xc0000020;
 
-// Fcall context offsets of the different fields
+// Fcall context offsets of the different fields; params and result are pointers to buffers grown
+// by the C code, which also manages the result capacity (offset 4)
 const FCALL_FUNCTION_ID: u64 = 0;
 const FCALL_PARAMS_CAPACITY: u64 = 1;
 const FCALL_PARAMS_SIZE: u64 = 2;
 const FCALL_PARAMS: u64 = 3;
-const FCALL_RESULT_CAPACITY: u64 = 35;
-const FCALL_RESULT_SIZE: u64 = 36;
-const FCALL_RESULT: u64 = 37;
-const FCALL_RESULT_GOT: u64 = 69;
+const FCALL_RESULT_SIZE: u64 = 5;
+const FCALL_RESULT: u64 = 6;
+const FCALL_RESULT_GOT: u64 = 7;
+const FCALL_CTX_SIZE: u64 = 8;
 
 const XMM_MAPPED_REGS: [u64; 16] = [1, 2, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18];
 
@@ -114,7 +116,7 @@ pub struct ZiskAsmContext {
 //   registers[35] -> RSP - 34*8
 //   trace_address -> RSP - (35-16 = 19)*8
 //   trace_size -> RSP - 20*8
-//   fcall_ctx[70] -> RSP - 90*8
+//   fcall_ctx[8] -> RSP - 90*8
 //   mem_step -> RSP - 91*8
 const RSP_REGS_OFFSET: u64 = 34 * 8;
 // const RSP_TRACE_ADDRESS_OFFSET: u64 = 19 * 8;
@@ -319,7 +321,7 @@ impl ZiskRom2Asm {
             || ctx.chunks()
             || ctx.zip()
         {
-            *code += ".comm fcall_ctx, 8*70, 8\n";
+            *code += &format!(".comm fcall_ctx, 8*{}, 8\n", FCALL_CTX_SIZE);
         }
 
         // for k in 0..keys.len() {
@@ -334,6 +336,7 @@ impl ZiskRom2Asm {
         }
         *code += ".extern print_abcflag\n";
         *code += ".extern print_char\n";
+        *code += ".extern print_char_err\n";
         *code += ".extern print_step\n";
         *code += ".extern opcode_keccak\n";
         *code += ".extern opcode_sha256\n";
@@ -341,10 +344,19 @@ impl ZiskRom2Asm {
         *code += ".extern opcode_arith256_mod\n";
         *code += ".extern opcode_secp256k1_add\n";
         *code += ".extern opcode_secp256k1_dbl\n";
+        *code += ".extern opcode_curve25519_mod\n";
+        *code += ".extern opcode_ed25519_dbl\n";
+        *code += ".extern opcode_ed25519_add\n";
+        *code += ".extern opcode_blake2br\n";
+        *code += ".extern opcode_memcpy\n";
+        *code += ".extern opcode_memset\n";
         *code += ".extern opcode_fcall\n";
+        *code += ".extern opcode_fcall_params_reserve\n";
         *code += ".extern chunk_done\n";
         *code += ".extern print_fcall_ctx\n";
-        *code += ".extern realloc_trace\n\n";
+        *code += ".extern realloc_trace\n";
+        *code += ".extern precompile_error\n";
+        *code += ".extern precompile_calls\n\n";
 
         if ctx.minimal_trace() || ctx.main_trace() || ctx.zip() {
             *code += ".extern chunk_size\n";
@@ -355,6 +367,10 @@ impl ZiskRom2Asm {
             *code += ".extern chunk_mask\n";
         }
 
+        if ctx.chunks() || ctx.minimal_trace() || ctx.main_trace() || ctx.zip() {
+            *code += ".extern max_steps\n\n";
+        }
+
         if ctx.chunks() || ctx.minimal_trace() || ctx.main_trace() || ctx.zip() {
             // Chunk start
             *code += "chunk_start:\n";
@@ -524,12 +540,9 @@ impl ZiskRom2Asm {
                 ctx.comment_str("address += fcall_ctx_offset")
             );
         }
-        for i in 0..70 {
-            if (i == FCALL_PARAMS_CAPACITY) || (i == FCALL_RESULT_CAPACITY) {
-                *code += &format!("\tmov qword {}[{} + {}*8], 32\n", ctx.ptr, REG_ADDRESS, i);
-            } else {
-                *code += &format!("\tmov qword {}[{} + {}*8], 0\n", ctx.ptr, REG_ADDRESS, i);
-            }
+        // The params and result buffers are allocated on demand, so all capacities start at zero
+        for i in 0..FCALL_CTX_SIZE {
+            *code += &format!("\tmov qword {}[{} + {}*8], 0\n", ctx.ptr, REG_ADDRESS, i);
         }
 
         // For all program addresses in the vector, create an assembly set of instructions with an
@@ -587,6 +600,17 @@ impl ZiskRom2Asm {
             // Set special storage destinations for a and b registers, based on operations, in order
             // to save instructions
             let zisk_op = ZiskOp::try_from_code(instruction.op).unwrap();
+
+            // Count the precompile calls, reported after the minimal traces to check their limits
+            if ctx.minimal_trace() && ResourceLimits::is_precompile(zisk_op) {
+                *code += &format!(
+                    "\tinc qword {}[precompile_calls + {}] {}\n",
+                    ctx.ptr,
+                    (instruction.op - PRECOMPILE_FIRST_OP) as u64 * 8,
+                    ctx.comment_str("precompile_calls[op]++")
+                );
+            }
+
             ctx.store_a_in_c = false;
             ctx.store_a_in_a = false;
             ctx.store_b_in_c = false;
@@ -1994,37 +2018,50 @@ impl ZiskRom2Asm {
                             }
                             if ctx.log_output {
                                 *code += &format!(
-                                    "\tmov {}, 0xa0000200 {}\n",
+                                    "\tmov {}, 0x{:x} {}\n",
                                     REG_FLAG,
+                                    UART_ADDR,
                                     ctx.comment_str("width=1: aux = UART")
                                 );
                                 *code += &format!(
                                     "\tcmp {}, {} {}\n",
                                     REG_ADDRESS,
                                     REG_FLAG,
-                                    ctx.comment_str("width=1: if address = USART then print char")
+                                    ctx.comment_str("width=1: if address = UART then print char")
+                                );
+                                *code += &format!(
+                                    "\tje pc_{:x}_store_c_uart {}\n",
+                                    ctx.pc,
+                                    ctx.comment_str("width=1: print char")
+                                );
+                                *code += &format!(
+                                    "\tmov {}, 0x{:x} {}\n",
+                                    REG_FLAG,
+                                    UART_ERR_ADDR,
+                                    ctx.comment_str("width=1: aux = UART_ERR")
+                                );
+                                *code += &format!(
+                                    "\tcmp {}, {} {}\n",
+                                    REG_ADDRESS,
+                                    REG_FLAG,
+                                    ctx.comment_str(
+                                        "width=1: if address = UART_ERR then print error char"
+                                    )
                                 );
                                 *code += &format!(
                                     "\tjne pc_{:x}_store_c_not_uart {}\n",
                                     ctx.pc,
                                     ctx.comment_str("width=1: continue")
                                 );
-                                if instruction.store_ra {
-                                    *code += &format!(
-                                        "\tmov dil, 0x{:x} {}\n",
-                                        (ctx.pc as i64 + instruction.jmp_offset2) as u64 as u8,
-                                        ctx.comment_str("width=1: rdi = value")
-                                    );
-                                } else {
-                                    *code += &format!(
-                                        "\tmov dil, {} {}\n",
-                                        REG_C_B,
-                                        ctx.comment_str("width=1: rdi = c")
-                                    );
-                                }
-                                Self::push_internal_registers(&mut ctx, code);
-                                *code += "\tcall _print_char\n";
-                                Self::pop_internal_registers(&mut ctx, code);
+                                Self::print_uart_char(
+                                    &mut ctx,
+                                    code,
+                                    instruction,
+                                    "_print_char_err",
+                                );
+                                *code += &format!("\tjmp pc_{:x}_store_c_not_uart\n", ctx.pc);
+                                *code += &format!("pc_{:x}_store_c_uart:\n", ctx.pc);
+                                Self::print_uart_char(&mut ctx, code, instruction, "_print_char");
                                 *code += &format!("pc_{:x}_store_c_not_uart:\n", ctx.pc);
                             }
                         }
@@ -2158,9 +2195,33 @@ impl ZiskRom2Asm {
                     unusual_code += &format!("pc_{:x}_step_zero:\n", ctx.pc);
                     Self::set_pc(&mut ctx, instruction, &mut unusual_code, "z");
                     if ctx.process() {
+                        // Stop the emulation if the chunk that just ended reached max_steps
+                        unusual_code += &format!(
+                            "\tmov {}, {} {}\n",
+                            REG_VALUE,
+                            ctx.mem_step,
+                            ctx.comment_str("value = step")
+                        );
+                        unusual_code += &format!(
+                            "\tadd {}, chunk_size {}\n",
+                            REG_VALUE,
+                            ctx.comment_str("value += chunk_size")
+                        );
+                        unusual_code += &format!(
+                            "\tcmp {}, qword {}[max_steps] {}\n",
+                            REG_VALUE,
+                            ctx.ptr,
+                            ctx.comment_str("value ?= max_steps")
+                        );
+                        unusual_code += &format!("\tjae pc_{:x}_max_steps\n", ctx.pc);
                         unusual_code += "\tcall chunk_end_and_start\n";
                     }
                     unusual_code += &format!("\tjmp pc_{:x}_step_done\n", ctx.pc);
+                    if ctx.process() {
+                        unusual_code += &format!("pc_{:x}_max_steps:\n", ctx.pc);
+                        unusual_code += "\tcall chunk_end\n";
+                        unusual_code += "\tjmp execute_end\n";
+                    }
                     Self::set_pc(&mut ctx, instruction, code, "nz");
                     *code += &format!("pc_{:x}_step_done:\n", ctx.pc);
                 }
@@ -4095,28 +4156,285 @@ impl ZiskRom2Asm {
                 ctx.c.is_saved = true;
                 ctx.flag_is_always_zero = true;
             }
+            ZiskOp::Curve25519Mod => {
+                *code += &ctx.full_line_comment("Curve25519Mod".to_string());
+
+                // Use the memory address as the first and unique parameter
+                *code += &format!(
+                    "\tmov rdi, {} {}\n",
+                    ctx.b.string_value,
+                    ctx.comment_str("rdi = b = address")
+                );
+
+                // Save data into mem_reads
+                if ctx.minimal_trace() || ctx.zip() {
+                    // If zip, check if chunk is active
+                    if ctx.zip() {
+                        *code += &format!(
+                            "\ttest {}, 1 {}\n",
+                            REG_ACTIVE_CHUNK,
+                            ctx.comment_str("active_chunk == 1 ?")
+                        );
+                        *code += &format!("\tjnz pc_{:x}_curve25519mod_active_chunk\n", ctx.pc);
+                        *code +=
+                            &format!("\tjmp pc_{:x}_curve25519mod_active_chunk_done\n", ctx.pc);
+                        *code += &format!("pc_{:x}_curve25519mod_active_chunk:\n", ctx.pc);
+                    }
+                    Self::precompiled_save_mem_reads(ctx, code, 4, 3, 4);
+                    if ctx.zip() {
+                        *code += &format!("pc_{:x}_curve25519mod_active_chunk_done:\n", ctx.pc);
+                    }
+                }
+
+                // Call the curve25519_mod function
+                Self::push_internal_registers(ctx, code);
+                *code += "\tcall _opcode_curve25519_mod\n";
+                Self::pop_internal_registers(ctx, code);
+
+                // Set result
+                *code += &format!("\txor {}, {} {}\n", REG_C, REG_C, ctx.comment_str("c = 0"));
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
+            ZiskOp::Ed25519Dbl => {
+                *code += &ctx.full_line_comment("Ed25519Dbl".to_string());
+
+                // Use the memory address as the first and unique parameter
+                *code += &format!(
+                    "\tmov rdi, {} {}\n",
+                    ctx.b.string_value,
+                    ctx.comment_str("rdi = b = address")
+                );
+
+                // Copy read data into mem_reads
+                if ctx.minimal_trace() || ctx.zip() {
+                    // If zip, check if chunk is active
+                    if ctx.zip() {
+                        *code += &format!(
+                            "\ttest {}, 1 {}\n",
+                            REG_ACTIVE_CHUNK,
+                            ctx.comment_str("active_chunk == 1 ?")
+                        );
+                        *code += &format!("\tjnz pc_{:x}_ed25519dbl_active_chunk\n", ctx.pc);
+                        *code += &format!("\tjmp pc_{:x}_ed25519dbl_active_chunk_done\n", ctx.pc);
+                        *code += &format!("pc_{:x}_ed25519dbl_active_chunk:\n", ctx.pc);
+                    }
+                    *code += &format!("\tmov {}, rdi\n", REG_ADDRESS);
+                    for k in 0..8 {
+                        *code += &format!(
+                            "\tmov {}, [{} + {}] {}\n",
+                            REG_VALUE,
+                            REG_ADDRESS,
+                            k * 8,
+                            ctx.comment(format!("value = mem[address[{}]]", k))
+                        );
+                        *code += &format!(
+                            "\tmov [{} + {}*8 + {}], {} {}\n",
+                            REG_MEM_READS_ADDRESS,
+                            REG_MEM_READS_SIZE,
+                            k * 8,
+                            REG_VALUE,
+                            ctx.comment(format!("mem_reads[{}] = value", k))
+                        );
+                    }
+
+                    // Increment chunk.steps.mem_reads_size in 8 units
+                    *code += &format!(
+                        "\tadd {}, 8 {}\n",
+                        REG_MEM_READS_SIZE,
+                        ctx.comment_str("mem_reads_size += 8")
+                    );
+                    if ctx.zip() {
+                        *code += &format!("pc_{:x}_ed25519dbl_active_chunk_done:\n", ctx.pc);
+                    }
+                }
+
+                // Call the ed25519_dbl function
+                Self::push_internal_registers(ctx, code);
+                *code += "\tcall _opcode_ed25519_dbl\n";
+                Self::pop_internal_registers(ctx, code);
+                Self::precompiled_check_error(ctx, code, unusual_code, "ed25519dbl");
+
+                // Set result
+                *code += &format!("\txor {}, {} {}\n", REG_C, REG_C, ctx.comment_str("c = 0"));
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
+            ZiskOp::Ed25519Add => {
+                *code += &ctx.full_line_comment("Ed25519Add".to_string());
+
+                // Use the memory address as the first and unique parameter
+                *code += &format!(
+                    "\tmov rdi, {} {}\n",
+                    ctx.b.string_value,
+                    ctx.comment_str("rdi = b = address")
+                );
+
+                // Save data into mem_reads
+                if ctx.minimal_trace() || ctx.zip() {
+                    // If zip, check if chunk is active
+                    if ctx.zip() {
+                        *code += &format!(
+                            "\ttest {}, 1 {}\n",
+                            REG_ACTIVE_CHUNK,
+                            ctx.comment_str("active_chunk == 1 ?")
+                        );
+                        *code += &format!("\tjnz pc_{:x}_ed25519add_active_chunk\n", ctx.pc);
+                        *code += &format!("\tjmp pc_{:x}_ed25519add_active_chunk_done\n", ctx.pc);
+                        *code += &format!("pc_{:x}_ed25519add_active_chunk:\n", ctx.pc);
+                    }
+                    Self::precompiled_save_mem_reads(ctx, code, 2, 2, 8);
+                    if ctx.zip() {
+                        *code += &format!("pc_{:x}_ed25519add_active_chunk_done:\n", ctx.pc);
+                    }
+                }
+
+                // Call the ed25519_add function
+                Self::push_internal_registers(ctx, code);
+                *code += "\tcall _opcode_ed25519_add\n";
+                Self::pop_internal_registers(ctx, code);
+                Self::precompiled_check_error(ctx, code, unusual_code, "ed25519add");
+
+                // Set result
+                *code += &format!("\txor {}, {} {}\n", REG_C, REG_C, ctx.comment_str("c = 0"));
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
+            ZiskOp::Blake2br => {
+                *code += &ctx.full_line_comment("Blake2br".to_string());
+
+                // Use the memory address as the first and unique parameter
+                *code += &format!(
+                    "\tmov rdi, {} {}\n",
+                    ctx.b.string_value,
+                    ctx.comment_str("rdi = b = address")
+                );
+
+                // Copy read data into mem_reads
+                if ctx.minimal_trace() || ctx.zip() {
+                    // If zip, check if chunk is active
+                    if ctx.zip() {
+                        *code += &format!(
+                            "\ttest {}, 1 {}\n",
+                            REG_ACTIVE_CHUNK,
+                            ctx.comment_str("active_chunk == 1 ?")
+                        );
+                        *code += &format!("\tjnz pc_{:x}_blake2br_active_chunk\n", ctx.pc);
+                        *code += &format!("\tjmp pc_{:x}_blake2br_active_chunk_done\n", ctx.pc);
+                        *code += &format!("pc_{:x}_blake2br_active_chunk:\n", ctx.pc);
+                    }
+                    *code += &format!("\tmov {}, rdi\n", REG_ADDRESS);
+                    for k in 0..32 {
+                        *code += &format!(
+                            "\tmov {}, [{} + {}] {}\n",
+                            REG_VALUE,
+                            REG_ADDRESS,
+                            k * 8,
+                            ctx.comment(format!("value = mem[address[{}]]", k))
+                        );
+                        *code += &format!(
+                            "\tmov [{} + {}*8 + {}], {} {}\n",
+                            REG_MEM_READS_ADDRESS,
+                            REG_MEM_READS_SIZE,
+                            k * 8,
+                            REG_VALUE,
+                            ctx.comment(format!("mem_reads[{}] = value", k))
+                        );
+                    }
+
+                    // Increment chunk.steps.mem_reads_size in 32 units
+                    *code += &format!(
+                        "\tadd {}, 32 {}\n",
+                        REG_MEM_READS_SIZE,
+                        ctx.comment_str("mem_reads_size += 32")
+                    );
+                    if ctx.zip() {
+                        *code += &format!("pc_{:x}_blake2br_active_chunk_done:\n", ctx.pc);
+                    }
+                }
+
+                // Call the blake2br function
+                Self::push_internal_registers(ctx, code);
+                *code += "\tcall _opcode_blake2br\n";
+                Self::pop_internal_registers(ctx, code);
+
+                // Set result
+                *code += &format!("\txor {}, {} {}\n", REG_C, REG_C, ctx.comment_str("c = 0"));
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
+            ZiskOp::Memcpy => {
+                *code += &ctx.full_line_comment("Memcpy".to_string());
+                Self::dma_call(ctx, code, unusual_code, "memcpy");
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
+            ZiskOp::Memset => {
+                *code += &ctx.full_line_comment("Memset".to_string());
+                Self::dma_call(ctx, code, unusual_code, "memset");
+                ctx.c.is_saved = true;
+                ctx.flag_is_always_zero = true;
+            }
             ZiskOp::FcallParam => {
                 assert!(ctx.store_b_in_c);
                 assert!(ctx.a.is_constant);
                 assert!(ctx.a.constant_value <= 32);
                 *code += &ctx.full_line_comment("FcallParam".to_string());
 
+                // Grow the params buffer if the new params do not fit
+                *code += &format!(
+                    "\tmov {}, qword {}[{} + {}*8] {}\n",
+                    REG_AUX,
+                    ctx.ptr,
+                    ctx.fcall_ctx,
+                    FCALL_PARAMS_SIZE,
+                    ctx.comment_str("aux = params size")
+                );
+                *code += &format!(
+                    "\tadd {}, {} {}\n",
+                    REG_AUX,
+                    ctx.a.constant_value,
+                    ctx.comment_str("aux += words")
+                );
+                *code += &format!(
+                    "\tcmp {}, qword {}[{} + {}*8] {}\n",
+                    REG_AUX,
+                    ctx.ptr,
+                    ctx.fcall_ctx,
+                    FCALL_PARAMS_CAPACITY,
+                    ctx.comment_str("aux <= params capacity?")
+                );
+                *code += &format!("\tjbe pc_{:x}_fcall_param_fits\n", ctx.pc);
+                Self::fcall_ctx_to_rdi(ctx, code);
+                *code += &format!("\tmov rsi, {} {}\n", REG_AUX, ctx.comment_str("rsi = aux"));
+                Self::push_internal_registers(ctx, code);
+                *code += "\tcall _opcode_fcall_params_reserve\n";
+                Self::pop_internal_registers(ctx, code);
+                *code += &format!("pc_{:x}_fcall_param_fits:\n", ctx.pc);
+
+                *code += &format!(
+                    "\tmov {}, qword {}[{} + {}*8] {}\n",
+                    REG_ADDRESS,
+                    ctx.ptr,
+                    ctx.fcall_ctx,
+                    FCALL_PARAMS,
+                    ctx.comment_str("address = ctx.params")
+                );
+                *code += &format!(
+                    "\tmov {}, qword {}[{} + {}*8] {}\n",
+                    REG_AUX,
+                    ctx.ptr,
+                    ctx.fcall_ctx,
+                    FCALL_PARAMS_SIZE,
+                    ctx.comment_str("aux = params size")
+                );
                 if ctx.a.constant_value == 1 {
                     // Store param in params
                     *code += &format!(
-                        "\tmov {}, qword {}[{} + {}*8] {}\n",
-                        REG_AUX,
-                        ctx.ptr,
-                        ctx.fcall_ctx,
-                        FCALL_PARAMS_SIZE,
-                        ctx.comment_str("aux = params size")
-                    );
-                    *code += &format!(
-                        "\tmov qword {}[{} + {}*8 + {}*8], {} {}\n",
+                        "\tmov qword {}[{} + {}*8], {} {}\n",
                         ctx.ptr,
-                        ctx.fcall_ctx,
+                        REG_ADDRESS,
                         REG_AUX,
-                        FCALL_PARAMS,
                         REG_C,
                         ctx.comment_str("ctx.params[size] = b")
                     );
@@ -4129,14 +4447,6 @@ impl ZiskRom2Asm {
                     );
                 } else {
                     // Store params in params
-                    *code += &format!(
-                        "\tmov {}, qword {}[{} + {}*8] {}\n",
-                        REG_AUX,
-                        ctx.ptr,
-                        ctx.fcall_ctx,
-                        FCALL_PARAMS_SIZE,
-                        ctx.comment_str("aux = params size")
-                    );
                     for i in 0..ctx.a.constant_value {
                         *code += &format!(
                             "\tmov {}, qword {}[{} + {}*8] {}\n",
@@ -4148,11 +4458,10 @@ impl ZiskRom2Asm {
                         );
 
                         *code += &format!(
-                            "\tmov qword {}[{} + {}*8 + {}*8], {} {}\n",
+                            "\tmov qword {}[{} + {}*8], {} {}\n",
                             ctx.ptr,
-                            ctx.fcall_ctx,
+                            REG_ADDRESS,
                             REG_AUX,
-                            FCALL_PARAMS,
                             REG_VALUE,
                             ctx.comment_str("params[aux] = param")
                         );
@@ -4188,26 +4497,7 @@ impl ZiskRom2Asm {
                 );
 
                 // Set the fcall context address as the first parameter
-                if ctx.fast()
-                    || ctx.minimal_trace()
-                    || ctx.rom_histogram()
-                    || ctx.main_trace()
-                    || ctx.chunks()
-                    || ctx.zip()
-                {
-                    *code += &format!(
-                        "\tlea rdi, {} {}\n",
-                        ctx.fcall_ctx,
-                        ctx.comment_str("rdi = fcall context")
-                    );
-                } else {
-                    *code += &format!("\tmov rdi, rsp {}\n", ctx.comment_str("rdi = rsp"));
-                    *code += &format!(
-                        "\tadd rdi, {} {}\n",
-                        RSP_FCALL_CTX_OFFSET,
-                        ctx.comment_str("rdi = fcall context")
-                    );
-                }
+                Self::fcall_ctx_to_rdi(ctx, code);
 
                 // Call the fcall function
                 Self::push_internal_registers(ctx, code);
@@ -4239,6 +4529,13 @@ impl ZiskRom2Asm {
                     ctx.ptr,
                     ctx.fcall_ctx,
                     FCALL_RESULT,
+                    ctx.comment_str("value = ctx.result")
+                );
+                *code += &format!(
+                    "\tmov {}, qword {}[{}] {}\n",
+                    REG_VALUE,
+                    ctx.ptr,
+                    REG_VALUE,
                     ctx.comment_str("value = ctx.result[0]")
                 );
                 *code += &format!(
@@ -4281,7 +4578,8 @@ impl ZiskRom2Asm {
 
                 assert!(ctx.store_b_in_c);
 
-                // Get value from fcall_ctx.result[got] and store it in free input address
+                // Get value from fcall_ctx.result[got], or 0 if all the result has been got, and
+                // store it in free input address
                 *code += &format!(
                     "\tmov {}, qword {}[{} + {}*8] {}\n",
                     REG_AUX,
@@ -4290,15 +4588,33 @@ impl ZiskRom2Asm {
                     FCALL_RESULT_GOT,
                     ctx.comment_str("aux = ctx.result_got")
                 );
+                *code += &format!("\tmov {}, 0 {}\n", REG_VALUE, ctx.comment_str("value = 0"));
                 *code += &format!(
-                    "\tmov {}, qword {}[{} + {}*8 + {}*8] {}\n",
+                    "\tcmp {}, qword {}[{} + {}*8] {}\n",
+                    REG_AUX,
+                    ctx.ptr,
+                    ctx.fcall_ctx,
+                    FCALL_RESULT_SIZE,
+                    ctx.comment_str("got < ctx.result_size?")
+                );
+                *code += &format!("\tjae pc_{:x}_fcall_get_done\n", ctx.pc);
+                *code += &format!(
+                    "\tmov {}, qword {}[{} + {}*8] {}\n",
                     REG_VALUE,
                     ctx.ptr,
                     ctx.fcall_ctx,
-                    REG_AUX,
                     FCALL_RESULT,
+                    ctx.comment_str("value = ctx.result")
+                );
+                *code += &format!(
+                    "\tmov {}, qword {}[{} + {}*8] {}\n",
+                    REG_VALUE,
+                    ctx.ptr,
+                    REG_VALUE,
+                    REG_AUX,
                     ctx.comment_str("value = ctx.result[got]")
                 );
+                *code += &format!("pc_{:x}_fcall_get_done:\n", ctx.pc);
                 *code += &format!(
                     "\tmov {}, {} {}\n",
                     REG_ADDRESS,
@@ -4326,6 +4642,24 @@ impl ZiskRom2Asm {
         }
     }
 
+    /// Sets rdi to the fcall context address, to be passed as the first parameter of a function
+    fn fcall_ctx_to_rdi(ctx: &mut ZiskAsmContext, code: &mut String) {
+        if ctx.process() {
+            *code += &format!(
+                "\tlea rdi, {} {}\n",
+                ctx.fcall_ctx,
+                ctx.comment_str("rdi = fcall context")
+            );
+        } else {
+            *code += &format!("\tmov rdi, rsp {}\n", ctx.comment_str("rdi = rsp"));
+            *code += &format!(
+                "\tadd rdi, {} {}\n",
+                RSP_FCALL_CTX_OFFSET,
+                ctx.comment_str("rdi = fcall context")
+            );
+        }
+    }
+
     fn set_pc(ctx: &mut ZiskAsmContext, instruction: &ZiskInst, code: &mut String, id: &str) {
         ctx.jump_to_dynamic_pc = false;
         ctx.jump_to_static_pc = String::new();
@@ -5046,6 +5380,27 @@ impl ZiskRom2Asm {
         //*code += "\tpop rsp\n";
     }
 
+    /// Generates the code to call a C function that prints the byte stored to a UART address
+    fn print_uart_char(
+        ctx: &mut ZiskAsmContext,
+        code: &mut String,
+        instruction: &ZiskInst,
+        function: &str,
+    ) {
+        if instruction.store_ra {
+            *code += &format!(
+                "\tmov dil, 0x{:x} {}\n",
+                (ctx.pc as i64 + instruction.jmp_offset2) as u64 as u8,
+                ctx.comment_str("width=1: rdi = value")
+            );
+        } else {
+            *code += &format!("\tmov dil, {} {}\n", REG_C_B, ctx.comment_str("width=1: rdi = c"));
+        }
+        Self::push_internal_registers(ctx, code);
+        *code += &format!("\tcall {}\n", function);
+        Self::pop_internal_registers(ctx, code);
+    }
+
     fn push_internal_registers(ctx: &mut ZiskAsmContext, code: &mut String) {
         *code += "\tpush rax\n";
         *code += "\tpush rcx\n";
@@ -5078,6 +5433,86 @@ impl ZiskRom2Asm {
         *code += "\tpop rax\n";
     }
 
+    /// Stops the emulation if the precompiled function that was just called failed, i.e. if it set
+    /// the precompile_error variable, so that the caller gets the error code in the output header
+    fn precompiled_check_error(
+        ctx: &mut ZiskAsmContext,
+        code: &mut String,
+        unusual_code: &mut String,
+        name: &str,
+    ) {
+        *code += &format!(
+            "\tcmp qword {}[precompile_error], 0 {}\n",
+            ctx.ptr,
+            ctx.comment_str("precompile_error ?= 0")
+        );
+        *code += &format!("\tjne pc_{:x}_{}_error\n", ctx.pc, name);
+        *unusual_code += &format!("pc_{:x}_{}_error:\n", ctx.pc, name);
+        if ctx.process() {
+            *unusual_code += "\tcall chunk_end\n";
+        }
+        *unusual_code += "\tjmp execute_end\n";
+    }
+
+    /// Calls a dma opcode function (memcpy, memset) with the params address and the current
+    /// mem_reads address, or zero if mem reads must not be saved.  The function returns the number
+    /// of saved mem reads, since it depends on the params.
+    fn dma_call(
+        ctx: &mut ZiskAsmContext,
+        code: &mut String,
+        unusual_code: &mut String,
+        name: &str,
+    ) {
+        // Use the params address as the first parameter
+        *code += &format!(
+            "\tmov rdi, {} {}\n",
+            ctx.b.string_value,
+            ctx.comment_str("rdi = b = params address")
+        );
+
+        // Use the mem reads address as the second parameter
+        let save_mem_reads = ctx.minimal_trace() || ctx.zip();
+        if save_mem_reads {
+            *code += &format!(
+                "\tlea rsi, [{} + {}*8] {}\n",
+                REG_MEM_READS_ADDRESS,
+                REG_MEM_READS_SIZE,
+                ctx.comment_str("rsi = mem_reads address")
+            );
+
+            // If zip, only save mem reads if chunk is active
+            if ctx.zip() {
+                *code += &format!(
+                    "\ttest {}, 1 {}\n",
+                    REG_ACTIVE_CHUNK,
+                    ctx.comment_str("active_chunk == 1 ?")
+                );
+                *code += &format!("\tjnz pc_{:x}_{}_active_chunk\n", ctx.pc, name);
+                *code += &format!("\txor rsi, rsi {}\n", ctx.comment_str("rsi = 0"));
+                *code += &format!("pc_{:x}_{}_active_chunk:\n", ctx.pc, name);
+            }
+        } else {
+            *code += &format!("\txor rsi, rsi {}\n", ctx.comment_str("rsi = 0"));
+        }
+
+        // Call the dma function
+        Self::push_internal_registers(ctx, code);
+        *code += &format!("\tcall _opcode_{}\n", name);
+        if save_mem_reads {
+            // Increment chunk.steps.mem_reads_size by the returned number of mem reads
+            *code += &format!(
+                "\tadd {}, rax {}\n",
+                REG_MEM_READS_SIZE,
+                ctx.comment_str("mem_reads_size += rax")
+            );
+        }
+        Self::pop_internal_registers(ctx, code);
+        Self::precompiled_check_error(ctx, code, unusual_code, name);
+
+        // Set result
+        *code += &format!("\txor {}, {} {}\n", REG_C, REG_C, ctx.comment_str("c = 0"));
+    }
+
     fn precompiled_save_mem_reads(
         ctx: &mut ZiskAsmContext,
         code: &mut String,
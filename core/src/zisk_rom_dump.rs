@@ -0,0 +1,482 @@
+//! Disassembles a ZisK ROM, printing every ZisK instruction next to the RISC-V instruction it was
+//! transpiled from.
+//!
+//! The BIOS instructions (address < `ROM_ADDR`) are generated by the transpiler and have no RISC-V
+//! origin.  Program instructions are matched with the RISC-V code sections of the ELF file: a
+//! RISC-V instruction at address `pc` is transpiled into ZisK instructions at `pc`, `pc + 1`,
+//! `pc + 2`, etc.
+
+use std::{collections::BTreeMap, error::Error, fmt::Write, path::Path};
+
+use elf::{
+    abi::{SHF_EXECINSTR, SHT_PROGBITS, STT_FUNC, STT_NOTYPE, STT_OBJECT},
+    endian::AnyEndian,
+    ElfBytes,
+};
+use riscv::{riscv_interpreter, RiscVRegisters, RiscvInstruction};
+use serde::Serialize;
+
+use crate::{
+    source_to_str, store_to_str, zisk_ops::ZiskOp, ZiskInst, ZiskRom, ROM_ADDR, SRC_IMM, SRC_IND,
+    SRC_MEM, SRC_REG, STORE_IND, STORE_MEM, STORE_NONE, STORE_REG,
+};
+
+/// A ZisK ROM instruction, annotated for display
+#[derive(Debug, Clone, Serialize)]
+pub struct RomDumpEntry {
+    /// ZisK instruction address
+    pub pc: u64,
+    /// `bios` for transpiler-generated instructions, `program` for ELF code
+    pub section: &'static str,
+    /// Closest preceding ELF symbol, as `name` or `name+0xoffset`
+    pub symbol: Option<String>,
+    /// Original 32-bit RISC-V instruction, only present on the first ZisK instruction it produced
+    pub riscv_word: Option<u32>,
+    /// RISC-V disassembly of `riscv_word`
+    pub riscv: Option<String>,
+    /// ZisK operation name
+    pub op: &'static str,
+    /// ZisK operation type, as displayed by `OpType`
+    pub op_type: String,
+    /// ZisK operation cost, in main steps
+    pub cost: u64,
+    /// ZisK instruction, with its a and b sources and c store
+    pub zisk: String,
+    /// Transpiler comment attached to the instruction
+    pub verbose: String,
+}
+
+/// Selects which instructions are dumped
+#[derive(Debug, Clone, Default)]
+pub struct RomDumpFilter {
+    /// First address to dump, inclusive
+    pub from: Option<u64>,
+    /// Last address to dump, exclusive
+    pub to: Option<u64>,
+    /// Only dump the instructions of the ELF symbol with this name
+    pub symbol: Option<String>,
+    /// Skip the BIOS instructions
+    pub skip_bios: bool,
+}
+
+/// ELF information required to annotate the ROM instructions
+#[derive(Debug, Default)]
+pub struct ElfDumpInfo {
+    /// Function and object symbols, as address -> (name, size)
+    pub symbols: BTreeMap<u64, (String, u64)>,
+    /// RISC-V code instructions, as address -> (word, disassembly)
+    pub code: BTreeMap<u64, (u32, String)>,
+}
+
+impl ElfDumpInfo {
+    /// Reads the symbols and the code sections of an ELF file
+    pub fn from_elf_file(elf_file: &Path) -> Result<Self, Box<dyn Error>> {
+        let file_data = std::fs::read(elf_file)?;
+        let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice())?;
+
+        let mut info = ElfDumpInfo { symbols: read_elf_symbols(&elf_bytes)?, ..Default::default() };
+
+        if let Some(section_headers) = elf_bytes.section_headers() {
+            for section_header in section_headers {
+                if section_header.sh_type != SHT_PROGBITS
+                    || section_header.sh_addr == 0
+                    || (section_header.sh_flags & SHF_EXECINSTR as u64) == 0
+                {
+                    continue;
+                }
+                let (data, _) = elf_bytes.section_data(&section_header)?;
+                let words: Vec<u32> = data
+                    .chunks_exact(4)
+                    .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+                    .collect();
+
+                // The interpreter skips zero words, so walk the non-zero ones in order to
+                // recover the address of every decoded instruction
+                let decoded = riscv_interpreter(&words);
+                let addresses = words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, w)| **w != 0)
+                    .map(|(i, _)| section_header.sh_addr + 4 * i as u64);
+                for (addr, inst) in addresses.zip(decoded) {
+                    info.code.insert(addr, (inst.rvinst, riscv_to_asm(&inst, addr)));
+                }
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Returns the closest symbol at or before `pc`, as `name` or `name+0xoffset`
+    pub fn symbol_at(&self, pc: u64) -> Option<String> {
+        let (addr, (name, size)) = self.symbols.range(..=pc).next_back()?;
+        let offset = pc - addr;
+        if *size != 0 && offset >= *size {
+            return None;
+        }
+        if offset == 0 {
+            Some(name.clone())
+        } else {
+            Some(format!("{}+0x{:x}", name, offset))
+        }
+    }
+
+    /// Returns the address range of the symbol named `name`
+    pub fn symbol_range(&self, name: &str) -> Option<(u64, u64)> {
+        self.symbols.iter().find(|(_, (n, _))| n == name).map(|(addr, (_, size))| {
+            // Unsized symbols extend up to the next symbol
+            let end = if *size != 0 {
+                addr + size
+            } else {
+                self.symbols.range(addr + 1..).next().map(|(a, _)| *a).unwrap_or(u64::MAX)
+            };
+            (*addr, end)
+        })
+    }
+}
+
+/// Reads the function and object symbols of an ELF file, as address -> (name, size)
+pub fn read_elf_symbols(
+    elf_bytes: &ElfBytes<AnyEndian>,
+) -> Result<BTreeMap<u64, (String, u64)>, Box<dyn Error>> {
+    let mut symbols = BTreeMap::new();
+    if let Some((symtab, strtab)) = elf_bytes.symbol_table()? {
+        for symbol in symtab.iter() {
+            let symtype = symbol.st_symtype();
+            if symbol.st_value == 0
+                || (symtype != STT_FUNC && symtype != STT_OBJECT && symtype != STT_NOTYPE)
+            {
+                continue;
+            }
+            let name = strtab.get(symbol.st_name as usize)?;
+            // Skip local labels and mapping symbols, e.g. `.L0 ` or `$x`
+            if name.is_empty() || name.starts_with(".L") || name.starts_with('$') {
+                continue;
+            }
+            // Keep the sized symbol when several share the same address
+            let entry = symbols.entry(symbol.st_value).or_insert((name.to_string(), 0));
+            if entry.1 == 0 && symbol.st_size != 0 {
+                *entry = (name.to_string(), symbol.st_size);
+            }
+        }
+    }
+
+    Ok(symbols)
+}
+
+/// Returns the address of the ELF symbol named `name`, if any.  Unlike `read_elf_symbols()`, this
+/// finds symbols that share their address with another one, e.g. riscof's `begin_signature`.
+pub fn find_elf_symbol(elf_file: &Path, name: &str) -> Result<Option<u64>, Box<dyn Error>> {
+    let file_data = std::fs::read(elf_file)?;
+    let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice())?;
+    if let Some((symtab, strtab)) = elf_bytes.symbol_table()? {
+        for symbol in symtab.iter() {
+            if strtab.get(symbol.st_name as usize)? == name {
+                return Ok(Some(symbol.st_value));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Dumps the instructions of `rom` that match `filter`, in address order
+pub fn rom_dump(
+    rom: &ZiskRom,
+    elf_info: &ElfDumpInfo,
+    filter: &RomDumpFilter,
+) -> Result<Vec<RomDumpEntry>, Box<dyn Error>> {
+    let (mut from, mut to) = (filter.from.unwrap_or(0), filter.to.unwrap_or(u64::MAX));
+    if let Some(symbol) = &filter.symbol {
+        let (start, end) = elf_info
+            .symbol_range(symbol)
+            .ok_or_else(|| format!("Symbol {} not found in the ELF file", symbol))?;
+        from = from.max(start);
+        to = to.min(end);
+    }
+
+    let entries = rom
+        .sorted_pc_list
+        .iter()
+        .copied()
+        .filter(|pc| *pc >= from && *pc < to && !(filter.skip_bios && *pc < ROM_ADDR))
+        .map(|pc| {
+            let inst = rom.get_instruction(pc);
+            let riscv = if pc >= ROM_ADDR { elf_info.code.get(&pc) } else { None };
+            let (op_type, cost) = match ZiskOp::try_from_code(inst.op) {
+                Ok(op) => (op.op_type().to_string(), op.steps()),
+                Err(_) => (String::new(), 0),
+            };
+            RomDumpEntry {
+                pc,
+                section: if pc < ROM_ADDR { "bios" } else { "program" },
+                symbol: elf_info.symbol_at(pc),
+                riscv_word: riscv.map(|(word, _)| *word),
+                riscv: riscv.map(|(_, asm)| asm.clone()),
+                op: inst.op_str,
+                op_type,
+                cost,
+                zisk: zisk_inst_to_asm(inst),
+                verbose: inst.verbose.clone(),
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Formats the dump as text, one instruction per line, with a label line for every symbol start
+pub fn rom_dump_to_text(entries: &[RomDumpEntry]) -> String {
+    let mut s = String::new();
+    let mut last_symbol: Option<&str> = None;
+    for entry in entries {
+        if let Some(symbol) = entry.symbol.as_deref() {
+            let name = symbol.split('+').next().unwrap();
+            if last_symbol != Some(name) {
+                let _ = writeln!(s, "\n<{}>:", name);
+                last_symbol = Some(name);
+            }
+        }
+        let word = entry.riscv_word.map(|w| format!("{:08x}", w)).unwrap_or_default();
+        let riscv = entry.riscv.as_deref().unwrap_or("");
+        let _ = writeln!(
+            s,
+            "{:>10x}:  {:8}  {:32}  {:<8} {:>6}  {}",
+            entry.pc, word, riscv, entry.op_type, entry.cost, entry.zisk
+        );
+    }
+    s
+}
+
+/// Returns the ABI name of a RISC-V register
+fn reg(index: u32) -> &'static str {
+    RiscVRegisters::name_from_usize(index as usize).unwrap_or("?")
+}
+
+/// Returns the disassembly of a decoded RISC-V instruction located at `pc`, in objdump syntax
+pub fn riscv_to_asm(i: &RiscvInstruction, pc: u64) -> String {
+    let target = |imm: i32| pc.wrapping_add(imm as i64 as u64);
+    match i.t.as_str() {
+        "R" => format!("{} {}, {}, {}", i.inst, reg(i.rd), reg(i.rs1), reg(i.rs2)),
+        "I" if i.inst == "jalr" || (i.inst.starts_with('l') && i.inst != "lui") => {
+            format!("{} {}, {}({})", i.inst, reg(i.rd), i.imm, reg(i.rs1))
+        }
+        "I" => format!("{} {}, {}, {}", i.inst, reg(i.rd), reg(i.rs1), i.imm),
+        "S" => format!("{} {}, {}({})", i.inst, reg(i.rs2), i.imm, reg(i.rs1)),
+        "B" => format!("{} {}, {}, 0x{:x}", i.inst, reg(i.rs1), reg(i.rs2), target(i.imm)),
+        "U" => format!("{} {}, 0x{:x}", i.inst, reg(i.rd), (i.imm as u32) >> 12),
+        "J" => format!("{} {}, 0x{:x}", i.inst, reg(i.rd), target(i.imm)),
+        "A" => {
+            let ordering = match (i.aq != 0, i.rl != 0) {
+                (true, true) => ".aqrl",
+                (true, false) => ".aq",
+                (false, true) => ".rl",
+                (false, false) => "",
+            };
+            if i.inst.starts_with("lr") {
+                format!("{}{} {}, ({})", i.inst, ordering, reg(i.rd), reg(i.rs1))
+            } else {
+                format!("{}{} {}, {}, ({})", i.inst, ordering, reg(i.rd), reg(i.rs2), reg(i.rs1))
+            }
+        }
+        "C" if i.inst == "ecall" || i.inst == "ebreak" => i.inst.clone(),
+        "C" if i.inst.ends_with('i') => {
+            format!("{} {}, 0x{:x}, {}", i.inst, reg(i.rd), i.csr, i.imme)
+        }
+        "C" => format!("{} {}, 0x{:x}, {}", i.inst, reg(i.rd), i.csr, reg(i.rs1)),
+        "F" if i.inst == "fence" => format!("fence {}, {}", fence_set(i.pred), fence_set(i.succ)),
+        _ => i.inst.clone(),
+    }
+}
+
+/// Returns the `iorw` ordering set of a fence instruction
+fn fence_set(bits: u32) -> String {
+    "iorw".chars().enumerate().filter(|(n, _)| bits & (8 >> n) != 0).map(|(_, c)| c).collect()
+}
+
+/// Describes an a or b source, e.g. `reg[3]`, `mem[0xa0000000]` or `imm(0x10)`
+fn source_to_asm(src: u64, imm1: u64, imm0: u64) -> String {
+    match src {
+        SRC_REG => format!("reg[{}]", imm0),
+        SRC_MEM => format!("mem[0x{:x}]", imm0),
+        SRC_IMM => format!("0x{:x}", (imm1 << 32) | imm0),
+        SRC_IND => format!("ind[a+0x{:x}]", imm0),
+        _ => source_to_str(src).to_lowercase(),
+    }
+}
+
+/// Returns a compact representation of a ZisK instruction: `c = op(a, b)`, its store and jumps
+pub fn zisk_inst_to_asm(inst: &ZiskInst) -> String {
+    let a = source_to_asm(inst.a_src, inst.a_use_sp_imm1, inst.a_offset_imm0);
+    let b = source_to_asm(inst.b_src, inst.b_use_sp_imm1, inst.b_offset_imm0);
+    let mut s = format!("{}({}, {})", inst.op_str, a, b);
+    if inst.ind_width != 0 && (inst.b_src == SRC_IND || inst.store == STORE_IND) {
+        let _ = write!(s, " w{}", inst.ind_width);
+    }
+    match inst.store {
+        STORE_NONE => {}
+        STORE_REG => {
+            let _ = write!(s, " -> reg[{}]", inst.store_offset);
+        }
+        STORE_MEM => {
+            let _ = write!(s, " -> mem[0x{:x}]", inst.store_offset as u64);
+        }
+        STORE_IND => {
+            let _ = write!(s, " -> ind[a+0x{:x}]", inst.store_offset as u64);
+        }
+        _ => {
+            let _ = write!(s, " -> {}", store_to_str(inst.store));
+        }
+    }
+    if inst.store_ra {
+        s += " ra";
+    }
+    if inst.set_pc {
+        s += " ; pc=c";
+    } else if inst.jmp_offset1 != inst.jmp_offset2 {
+        // Conditional jump: offset1 is taken when the flag is set, offset2 otherwise
+        let _ = write!(s, " ; jmp {:+}/{:+}", inst.jmp_offset1, inst.jmp_offset2);
+    } else if !(1..=4).contains(&inst.jmp_offset1) {
+        let _ = write!(s, " ; jmp {:+}", inst.jmp_offset1);
+    }
+    if inst.end {
+        s += " ; end";
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ZiskInstBuilder, ROM_ENTRY};
+
+    fn inst(pc: u64, op: &str) -> ZiskInst {
+        let mut zib = ZiskInstBuilder::new(pc);
+        zib.src_a("imm", 0, false);
+        zib.src_b("imm", 1, false);
+        zib.op(op).unwrap();
+        zib.store("reg", 1, false, false);
+        zib.j(4, 4);
+        zib.build();
+        zib.i
+    }
+
+    /// Returns a ROM with 2 BIOS instructions and 4 program instructions
+    fn test_rom() -> ZiskRom {
+        let bios = [ROM_ENTRY, ROM_ENTRY + 4];
+        let program = [ROM_ADDR, ROM_ADDR + 4, ROM_ADDR + 8, ROM_ADDR + 12];
+        ZiskRom {
+            rom_entry_instructions: bios.iter().map(|pc| inst(*pc, "copyb")).collect(),
+            rom_instructions: program.iter().map(|pc| inst(*pc, "add")).collect(),
+            sorted_pc_list: bios.iter().chain(&program).copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the ELF information of the test ROM: a sized `main` symbol covering the first two
+    /// program instructions, an unsized `helper` symbol after it and a sized `data` object
+    fn test_elf_info() -> ElfDumpInfo {
+        ElfDumpInfo {
+            symbols: BTreeMap::from([
+                (ROM_ADDR, ("main".to_string(), 8)),
+                (ROM_ADDR + 8, ("helper".to_string(), 0)),
+                (ROM_ADDR + 0x100, ("data".to_string(), 4)),
+            ]),
+            code: BTreeMap::from([(ROM_ADDR, (0x00000013, "addi zero, zero, 0".to_string()))]),
+        }
+    }
+
+    fn dump_pcs(filter: &RomDumpFilter) -> Vec<u64> {
+        let entries = rom_dump(&test_rom(), &test_elf_info(), filter).unwrap();
+        entries.iter().map(|entry| entry.pc).collect()
+    }
+
+    #[test]
+    fn test_dump_all() {
+        let entries = rom_dump(&test_rom(), &test_elf_info(), &RomDumpFilter::default()).unwrap();
+        let pcs: Vec<u64> = entries.iter().map(|entry| entry.pc).collect();
+        assert_eq!(pcs, test_rom().sorted_pc_list);
+
+        assert_eq!(entries[0].section, "bios");
+        assert_eq!(entries[0].op, "copyb");
+        assert_eq!(entries[0].symbol, None);
+        assert_eq!(entries[0].riscv, None);
+
+        assert_eq!(entries[2].section, "program");
+        assert_eq!(entries[2].op, "add");
+        assert_eq!(entries[2].symbol.as_deref(), Some("main"));
+        assert_eq!(entries[2].riscv_word, Some(0x00000013));
+        assert_eq!(entries[2].riscv.as_deref(), Some("addi zero, zero, 0"));
+
+        // The RISC-V instruction is only attached to the ZisK instruction at its address
+        assert_eq!(entries[3].symbol.as_deref(), Some("main+0x4"));
+        assert_eq!(entries[3].riscv_word, None);
+    }
+
+    #[test]
+    fn test_dump_skip_bios() {
+        let filter = RomDumpFilter { skip_bios: true, ..Default::default() };
+        assert_eq!(dump_pcs(&filter), [ROM_ADDR, ROM_ADDR + 4, ROM_ADDR + 8, ROM_ADDR + 12]);
+    }
+
+    #[test]
+    fn test_dump_address_range() {
+        // `from` is inclusive and `to` exclusive
+        let filter = RomDumpFilter {
+            from: Some(ROM_ADDR + 4),
+            to: Some(ROM_ADDR + 12),
+            ..Default::default()
+        };
+        assert_eq!(dump_pcs(&filter), [ROM_ADDR + 4, ROM_ADDR + 8]);
+
+        let filter = RomDumpFilter { to: Some(ROM_ADDR), ..Default::default() };
+        assert_eq!(dump_pcs(&filter), [ROM_ENTRY, ROM_ENTRY + 4]);
+    }
+
+    #[test]
+    fn test_dump_symbol() {
+        let filter = RomDumpFilter { symbol: Some("main".to_string()), ..Default::default() };
+        assert_eq!(dump_pcs(&filter), [ROM_ADDR, ROM_ADDR + 4]);
+
+        // Unsized symbols extend up to the next symbol
+        let filter = RomDumpFilter { symbol: Some("helper".to_string()), ..Default::default() };
+        assert_eq!(dump_pcs(&filter), [ROM_ADDR + 8, ROM_ADDR + 12]);
+
+        // The symbol range is intersected with the address range
+        let filter = RomDumpFilter {
+            symbol: Some("helper".to_string()),
+            to: Some(ROM_ADDR + 12),
+            ..Default::default()
+        };
+        assert_eq!(dump_pcs(&filter), [ROM_ADDR + 8]);
+
+        let filter = RomDumpFilter { symbol: Some("missing".to_string()), ..Default::default() };
+        assert!(rom_dump(&test_rom(), &test_elf_info(), &filter).is_err());
+    }
+
+    #[test]
+    fn test_symbol_at() {
+        let elf_info = test_elf_info();
+        assert_eq!(elf_info.symbol_at(ROM_ADDR - 4), None);
+        assert_eq!(elf_info.symbol_at(ROM_ADDR + 4).as_deref(), Some("main+0x4"));
+        assert_eq!(elf_info.symbol_at(ROM_ADDR + 0x20).as_deref(), Some("helper+0x18"));
+        assert_eq!(elf_info.symbol_at(ROM_ADDR + 0x100).as_deref(), Some("data"));
+        // Past the end of a sized symbol
+        assert_eq!(elf_info.symbol_at(ROM_ADDR + 0x104), None);
+    }
+
+    #[test]
+    fn test_dump_to_text() {
+        let filter = RomDumpFilter { skip_bios: true, ..Default::default() };
+        let entries = rom_dump(&test_rom(), &test_elf_info(), &filter).unwrap();
+        let text = rom_dump_to_text(&entries);
+
+        // One label line per symbol, followed by its instructions
+        assert_eq!(text.matches("<main>:").count(), 1);
+        assert_eq!(text.matches("<helper>:").count(), 1);
+        let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[1].trim_start().starts_with("80000000:  00000013  addi zero, zero, 0"));
+        assert!(lines[1].ends_with(&entries[0].zisk));
+    }
+}
@@ -0,0 +1,620 @@
+//! Peephole optimizer over the transpiled Zisk ROM
+//!
+//! The RISC-V to Zisk transpiler converts every RISC-V instruction on its own, so the resulting
+//! ROM contains instructions that do not need to be executed: nops (`fence`, `addi x0,x0,0`),
+//! copies of a register onto itself, operations over constant operands, and pairs of instructions
+//! of the same RISC-V instruction group that could be merged using the `c` register forwarding
+//! (`lastc` source).  Every instruction executed is a main state machine step, so removing them
+//! reduces the proving cost.
+//!
+//! The optimizer works over the `ZiskRom.insts` map, before the fetch vectors are built, in
+//! several passes:
+//! * Constant folding: operations with both sources as immediate values are replaced by a `copyb`
+//!   of the result, and conditional jumps are resolved
+//! * Redundant copy removal: instructions that store a register or memory value onto itself are
+//!   converted into nops
+//! * `c` forwarding fusion: pairs of instructions linked by the `lastc` source are merged
+//! * Jump threading: jumps that land on nops are retargeted to the instruction after the nop
+//! * Dead code removal: non-aligned instructions that are no longer reachable are removed
+//!
+//! Aligned addresses can be the target of any `jalr`, so they are never removed; non-aligned
+//! addresses are internal to a RISC-V instruction group and can only be reached through static
+//! jumps, so they can be removed when no instruction jumps to them.
+//!
+//! The optimizer is limited to emulation, i.e. `ziskemu --optimize` and `EmuBuilder::optimize()`.
+//! The ROM setup, its Merkle root, the assembly generator and the executor always transpile the
+//! original ROM, so an optimized ROM cannot be proven against that setup, and the emulator
+//! refuses to generate minimal traces from it.
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    zisk_ops::{OpType, ZiskOp},
+    ZiskInst, ZiskInstBuilder, SRC_C, SRC_IMM, SRC_MEM, SRC_REG, SRC_STEP, STORE_MEM, STORE_NONE,
+    STORE_REG,
+};
+
+/// Maximum number of nops that a jump can skip, to avoid looping over cycles of nops
+const MAX_THREADING_HOPS: usize = 16;
+
+/// Counters of the transformations applied by the ROM optimizer
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RomOptimizerStats {
+    /// Operations with constant operands replaced by a copy of the result
+    pub folded_constants: usize,
+    /// Copies of a value onto itself converted into nops
+    pub removed_copies: usize,
+    /// Pairs of instructions merged through the `c` register forwarding
+    pub fused_pairs: usize,
+    /// Jump offsets retargeted to skip nops
+    pub threaded_jumps: usize,
+    /// Unreachable instructions removed from the ROM
+    pub removed_instructions: usize,
+}
+
+impl fmt::Display for RomOptimizerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "folded_constants={} removed_copies={} fused_pairs={} threaded_jumps={} \
+             removed_instructions={}",
+            self.folded_constants,
+            self.removed_copies,
+            self.fused_pairs,
+            self.threaded_jumps,
+            self.removed_instructions
+        )
+    }
+}
+
+/// Optimizes the ROM instructions in place, returning the number of transformations applied
+pub fn optimize_rom_insts(insts: &mut HashMap<u64, ZiskInstBuilder>) -> RomOptimizerStats {
+    let mut stats = RomOptimizerStats::default();
+
+    // Process the instructions in address order, so that the result is deterministic
+    let mut pcs: Vec<u64> = insts.keys().copied().collect();
+    pcs.sort();
+
+    for pc in &pcs {
+        let zib = insts.get_mut(pc).unwrap();
+        if fold_constants(zib) {
+            stats.folded_constants += 1;
+        }
+        if remove_redundant_copy(zib) {
+            stats.removed_copies += 1;
+        }
+    }
+
+    stats.fused_pairs = fuse_pairs(insts, &pcs);
+    stats.threaded_jumps = thread_jumps(insts, &pcs);
+    stats.removed_instructions = remove_unreachable(insts, &pcs);
+
+    stats
+}
+
+/// Returns the operation of an instruction if it has no side effects other than its store, i.e.
+/// if it can be evaluated at transpilation time
+fn pure_op(inst: &ZiskInst) -> Option<ZiskOp> {
+    let op = ZiskOp::try_from_code(inst.op).ok()?;
+    match op.op_type() {
+        OpType::Internal
+        | OpType::Binary
+        | OpType::BinaryE
+        | OpType::Arith
+        | OpType::ArithA32
+        | OpType::ArithAm32 => Some(op),
+        _ => None,
+    }
+}
+
+/// Returns the 64-bits value of an immediate source
+fn imm_value(use_sp_imm1: u64, offset_imm0: u64) -> u64 {
+    offset_imm0 | (use_sp_imm1 << 32)
+}
+
+/// Returns true if the instruction reads the `c` register of the previous instruction
+fn reads_c(inst: &ZiskInst) -> bool {
+    inst.a_src == SRC_C || inst.b_src == SRC_C
+}
+
+/// Appends a note to the instruction verbose description
+fn annotate(zib: &mut ZiskInstBuilder, note: &str) {
+    zib.i.verbose = if zib.i.verbose.is_empty() {
+        note.to_string()
+    } else {
+        format!("{} => {}", zib.i.verbose, note)
+    };
+}
+
+/// Replaces an operation over two immediate values by a copy of its result, resolving the jump
+/// if the instruction is conditional
+fn fold_constants(zib: &mut ZiskInstBuilder) -> bool {
+    let inst = &zib.i;
+    if inst.a_src != SRC_IMM || inst.b_src != SRC_IMM {
+        return false;
+    }
+    let op = match pure_op(inst) {
+        Some(op) if op != ZiskOp::CopyB && op != ZiskOp::Flag => op,
+        _ => return false,
+    };
+    let (c, flag) = op.call_ab(
+        imm_value(inst.a_use_sp_imm1, inst.a_offset_imm0),
+        imm_value(inst.b_use_sp_imm1, inst.b_offset_imm0),
+    );
+
+    // A set pc instruction jumps to c + jmp_offset1 regardless of the flag, so only conditional
+    // jumps need to be resolved; jmp_offset2 is also the stored return address, so it can only
+    // be replaced when there is no store ra
+    if !inst.set_pc && inst.jmp_offset1 != inst.jmp_offset2 {
+        if flag {
+            if inst.store_ra {
+                return false;
+            }
+            zib.i.jmp_offset2 = zib.i.jmp_offset1;
+        } else {
+            zib.i.jmp_offset1 = zib.i.jmp_offset2;
+        }
+    }
+
+    // The a source is kept, since it could be used as the address of an indirect store
+    zib.op("copyb").unwrap();
+    zib.src_b("imm", c, false);
+    annotate(zib, &format!("copyb 0x{:x} (folded)", c));
+    true
+}
+
+/// Converts an instruction that stores onto a register or memory address the value it already
+/// contains into a nop
+fn remove_redundant_copy(zib: &mut ZiskInstBuilder) -> bool {
+    let inst = &zib.i;
+    if (inst.store != STORE_REG && inst.store != STORE_MEM) || inst.store_ra || inst.store_use_sp {
+        return false;
+    }
+
+    // Returns true if the source is the same location as the store
+    let same_location = |src: u64, use_sp_imm1: u64, offset_imm0: u64| {
+        ((src == SRC_REG && inst.store == STORE_REG) || (src == SRC_MEM && inst.store == STORE_MEM))
+            && use_sp_imm1 == 0
+            && offset_imm0 == inst.store_offset as u64
+    };
+    let b_imm = if inst.b_src == SRC_IMM {
+        Some(imm_value(inst.b_use_sp_imm1, inst.b_offset_imm0))
+    } else {
+        None
+    };
+
+    let redundant = match ZiskOp::try_from_code(inst.op) {
+        Ok(ZiskOp::CopyB) => same_location(inst.b_src, inst.b_use_sp_imm1, inst.b_offset_imm0),
+        Ok(ZiskOp::Add | ZiskOp::Sub | ZiskOp::Or | ZiskOp::Xor | ZiskOp::Sll | ZiskOp::Srl) => {
+            b_imm == Some(0) && same_location(inst.a_src, inst.a_use_sp_imm1, inst.a_offset_imm0)
+        }
+        Ok(ZiskOp::And) => {
+            b_imm == Some(u64::MAX)
+                && same_location(inst.a_src, inst.a_use_sp_imm1, inst.a_offset_imm0)
+        }
+        _ => false,
+    };
+    if !redundant {
+        return false;
+    }
+
+    zib.store("none", 0, false, false);
+    annotate(zib, "nop (redundant copy)");
+    true
+}
+
+/// Returns the static jump targets of an instruction.  Set pc instructions jump to a computed
+/// address, so they have no static targets.
+fn static_targets(pc: u64, inst: &ZiskInst) -> Vec<u64> {
+    if inst.set_pc {
+        Vec::new()
+    } else if inst.jmp_offset1 == inst.jmp_offset2 {
+        vec![pc.wrapping_add_signed(inst.jmp_offset1)]
+    } else {
+        vec![pc.wrapping_add_signed(inst.jmp_offset1), pc.wrapping_add_signed(inst.jmp_offset2)]
+    }
+}
+
+/// Counts the number of instructions that statically jump to every address
+fn count_predecessors(insts: &HashMap<u64, ZiskInstBuilder>) -> HashMap<u64, usize> {
+    let mut predecessors = HashMap::new();
+    for (pc, zib) in insts {
+        for target in static_targets(*pc, &zib.i) {
+            *predecessors.entry(target).or_insert(0) += 1;
+        }
+    }
+    predecessors
+}
+
+/// Returns the target of an unconditional instruction that can only be reached from it, i.e. a
+/// non-aligned address with a single predecessor
+fn exclusive_successor(
+    pc: u64,
+    inst: &ZiskInst,
+    predecessors: &HashMap<u64, usize>,
+) -> Option<u64> {
+    if inst.set_pc || inst.end || inst.store_ra || inst.jmp_offset1 != inst.jmp_offset2 {
+        return None;
+    }
+    let target = pc.wrapping_add_signed(inst.jmp_offset1);
+    if target & 0x03 != 0 && predecessors.get(&target) == Some(&1) {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+/// Merges pairs of instructions linked by the `c` register forwarding:
+/// * `op(a, b) -> none` followed by `copyb(_, lastc) -> dst` becomes `op(a, b) -> dst`
+/// * `copyb(_, x) -> none` followed by `op(lastc, lastc)` becomes `op(x, x)`, leaving the first
+///   instruction as a nop that jump threading can skip
+fn fuse_pairs(insts: &mut HashMap<u64, ZiskInstBuilder>, pcs: &[u64]) -> usize {
+    let predecessors = count_predecessors(insts);
+    let mut fused = 0;
+
+    for pc in pcs {
+        let Some(first) = insts.get(pc) else {
+            continue;
+        };
+        let first = &first.i;
+        if first.store != STORE_NONE {
+            continue;
+        }
+        let Some(op) = pure_op(first) else {
+            continue;
+        };
+        let Some(target) = exclusive_successor(*pc, first, &predecessors) else {
+            continue;
+        };
+        let Some(second) = insts.get(&target) else {
+            continue;
+        };
+        let second = &second.i;
+
+        // Store forwarding: the second instruction only stores the result of the first one
+        if second.op == ZiskOp::CopyB.code()
+            && second.b_src == SRC_C
+            && (second.store == STORE_REG || second.store == STORE_MEM)
+            && !second.set_pc
+            && !second.end
+            && !second.store_ra
+            && second.jmp_offset1 == second.jmp_offset2
+        {
+            let (store, store_offset, store_use_sp) =
+                (second.store, second.store_offset, second.store_use_sp);
+            let jmp_offset = first.jmp_offset1 + second.jmp_offset1;
+            let zib = insts.get_mut(pc).unwrap();
+            zib.i.store = store;
+            zib.i.store_offset = store_offset;
+            zib.i.store_use_sp = store_use_sp;
+            zib.i.jmp_offset1 = jmp_offset;
+            zib.i.jmp_offset2 = jmp_offset;
+            annotate(zib, "store fused");
+            fused += 1;
+            continue;
+        }
+
+        // Source forwarding: the second instruction reads the value copied by the first one,
+        // which can be read directly from its location
+        if op == ZiskOp::CopyB
+            && reads_c(second)
+            && matches!(first.b_src, SRC_IMM | SRC_REG | SRC_MEM)
+        {
+            let (src, use_sp_imm1, offset_imm0) =
+                (first.b_src, first.b_use_sp_imm1, first.b_offset_imm0);
+            let zib = insts.get_mut(&target).unwrap();
+            if zib.i.a_src == SRC_C {
+                zib.i.a_src = src;
+                zib.i.a_use_sp_imm1 = use_sp_imm1;
+                zib.i.a_offset_imm0 = offset_imm0;
+            }
+            if zib.i.b_src == SRC_C {
+                zib.i.b_src = src;
+                zib.i.b_use_sp_imm1 = use_sp_imm1;
+                zib.i.b_offset_imm0 = offset_imm0;
+            }
+            annotate(zib, "source fused");
+            fused += 1;
+        }
+    }
+
+    fused
+}
+
+/// If the instruction behaves as a nop, i.e. it has no store and no side effects, returns the
+/// offset it jumps to
+fn nop_jump_offset(inst: &ZiskInst) -> Option<i64> {
+    if inst.store != STORE_NONE || inst.set_pc || inst.end {
+        return None;
+    }
+    if !matches!(inst.a_src, SRC_IMM | SRC_REG | SRC_C | SRC_STEP)
+        || !matches!(inst.b_src, SRC_IMM | SRC_REG | SRC_C)
+    {
+        return None;
+    }
+    let op = pure_op(inst)?;
+    if inst.jmp_offset1 == inst.jmp_offset2 || op == ZiskOp::Flag {
+        // flag always sets the flag to true
+        Some(inst.jmp_offset1)
+    } else if op == ZiskOp::CopyB {
+        // copyb always sets the flag to false
+        Some(inst.jmp_offset2)
+    } else {
+        None
+    }
+}
+
+/// Returns the jump offset that skips the nops found starting at pc + offset
+fn thread_offset(insts: &HashMap<u64, ZiskInstBuilder>, pc: u64, offset: i64) -> i64 {
+    let mut offset = offset;
+    for _ in 0..MAX_THREADING_HOPS {
+        let target = pc.wrapping_add_signed(offset);
+        let Some(next) = insts.get(&target).and_then(|zib| nop_jump_offset(&zib.i)) else {
+            break;
+        };
+        if next == 0 {
+            break;
+        }
+
+        // Skipping the nop changes the value of c, so the landing instruction must not read it
+        let landing = target.wrapping_add_signed(next);
+        match insts.get(&landing) {
+            Some(zib) if !reads_c(&zib.i) => offset += next,
+            _ => break,
+        }
+    }
+    offset
+}
+
+/// Retargets jumps that land on nops to the instruction the nop jumps to
+fn thread_jumps(insts: &mut HashMap<u64, ZiskInstBuilder>, pcs: &[u64]) -> usize {
+    let mut threaded = 0;
+
+    for pc in pcs {
+        let Some(zib) = insts.get(pc) else {
+            continue;
+        };
+        let inst = &zib.i;
+        if inst.set_pc || inst.end {
+            continue;
+        }
+        let jmp_offset1 = thread_offset(insts, *pc, inst.jmp_offset1);
+        // jmp_offset2 is also the stored return address, so it cannot be changed with store ra
+        let jmp_offset2 = if inst.store_ra {
+            inst.jmp_offset2
+        } else {
+            thread_offset(insts, *pc, inst.jmp_offset2)
+        };
+        if jmp_offset1 == inst.jmp_offset1 && jmp_offset2 == inst.jmp_offset2 {
+            continue;
+        }
+
+        let zib = insts.get_mut(pc).unwrap();
+        threaded += (jmp_offset1 != zib.i.jmp_offset1) as usize;
+        threaded += (jmp_offset2 != zib.i.jmp_offset2) as usize;
+        zib.i.jmp_offset1 = jmp_offset1;
+        zib.i.jmp_offset2 = jmp_offset2;
+    }
+
+    threaded
+}
+
+/// Removes the non-aligned instructions that no instruction jumps to
+fn remove_unreachable(insts: &mut HashMap<u64, ZiskInstBuilder>, pcs: &[u64]) -> usize {
+    let mut removed = 0;
+
+    // Removing an instruction can leave its successors unreachable, so iterate until stable
+    loop {
+        let predecessors = count_predecessors(insts);
+        let unreachable: Vec<u64> = pcs
+            .iter()
+            .copied()
+            .filter(|pc| pc & 0x03 != 0 && insts.contains_key(pc))
+            .filter(|pc| !predecessors.contains_key(pc))
+            .collect();
+        if unreachable.is_empty() {
+            break;
+        }
+        for pc in &unreachable {
+            insts.remove(pc);
+        }
+        removed += unreachable.len();
+    }
+
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ROM_ADDR;
+
+    /// Builds an instruction `op(a, b) -> store` at `pc` jumping to `pc + j1` or `pc + j2`
+    fn inst(
+        pc: u64,
+        op: &str,
+        a: (&str, u64),
+        b: (&str, u64),
+        store: (&str, i64),
+        j: (i32, i32),
+    ) -> ZiskInstBuilder {
+        let mut zib = ZiskInstBuilder::new(pc);
+        zib.src_a(a.0, a.1, false);
+        zib.src_b(b.0, b.1, false);
+        zib.op(op).unwrap();
+        zib.store(store.0, store.1, false, false);
+        zib.j(j.0, j.1);
+        zib.build();
+        zib
+    }
+
+    fn rom(insts: Vec<ZiskInstBuilder>) -> (HashMap<u64, ZiskInstBuilder>, Vec<u64>) {
+        let mut pcs: Vec<u64> = insts.iter().map(|zib| zib.i.paddr).collect();
+        pcs.sort();
+        (insts.into_iter().map(|zib| (zib.i.paddr, zib)).collect(), pcs)
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        let mut zib = inst(ROM_ADDR, "add", ("imm", 2), ("imm", 3), ("reg", 1), (4, 4));
+        assert!(fold_constants(&mut zib));
+        assert_eq!(zib.i.op, ZiskOp::CopyB.code());
+        assert_eq!(
+            (zib.i.b_src, imm_value(zib.i.b_use_sp_imm1, zib.i.b_offset_imm0)),
+            (SRC_IMM, 5)
+        );
+
+        // Already folded, and operations over non constant operands
+        assert!(!fold_constants(&mut zib));
+        let mut zib = inst(ROM_ADDR, "add", ("reg", 2), ("imm", 3), ("reg", 1), (4, 4));
+        assert!(!fold_constants(&mut zib));
+    }
+
+    #[test]
+    fn test_fold_conditional_jump() {
+        // A taken conditional jump jumps to jmp_offset1, a not taken one to jmp_offset2
+        let mut zib = inst(ROM_ADDR, "eq", ("imm", 1), ("imm", 1), ("none", 0), (8, 4));
+        assert!(fold_constants(&mut zib));
+        assert_eq!((zib.i.jmp_offset1, zib.i.jmp_offset2), (8, 8));
+
+        let mut zib = inst(ROM_ADDR, "eq", ("imm", 1), ("imm", 2), ("none", 0), (8, 4));
+        assert!(fold_constants(&mut zib));
+        assert_eq!((zib.i.jmp_offset1, zib.i.jmp_offset2), (4, 4));
+    }
+
+    #[test]
+    fn test_fold_conditional_jump_with_store_ra() {
+        // jmp_offset2 is the stored return address, so a taken jump cannot be folded
+        let mut zib = inst(ROM_ADDR, "eq", ("imm", 1), ("imm", 1), ("none", 0), (8, 4));
+        zib.store_ra("reg", 1, false);
+        assert!(!fold_constants(&mut zib));
+        assert_eq!(zib.i.op, ZiskOp::Eq.code());
+        assert_eq!((zib.i.jmp_offset1, zib.i.jmp_offset2), (8, 4));
+
+        // A not taken jump keeps jmp_offset2, and so the return address
+        let mut zib = inst(ROM_ADDR, "eq", ("imm", 1), ("imm", 2), ("none", 0), (8, 4));
+        zib.store_ra("reg", 1, false);
+        assert!(fold_constants(&mut zib));
+        assert!(zib.i.store_ra);
+        assert_eq!((zib.i.jmp_offset1, zib.i.jmp_offset2), (4, 4));
+    }
+
+    #[test]
+    fn test_remove_redundant_copy() {
+        let mut zib = inst(ROM_ADDR, "copyb", ("imm", 0), ("reg", 5), ("reg", 5), (4, 4));
+        assert!(remove_redundant_copy(&mut zib));
+        assert_eq!(zib.i.store, STORE_NONE);
+
+        let mut zib = inst(ROM_ADDR, "and", ("reg", 5), ("imm", u64::MAX), ("reg", 5), (4, 4));
+        assert!(remove_redundant_copy(&mut zib));
+
+        // Different location, or an operand that changes the value
+        let mut zib = inst(ROM_ADDR, "copyb", ("imm", 0), ("reg", 5), ("reg", 6), (4, 4));
+        assert!(!remove_redundant_copy(&mut zib));
+        let mut zib = inst(ROM_ADDR, "add", ("reg", 5), ("imm", 1), ("reg", 5), (4, 4));
+        assert!(!remove_redundant_copy(&mut zib));
+    }
+
+    #[test]
+    fn test_fuse_store_into_mem() {
+        let addr = 0xa0000000;
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "add", ("reg", 1), ("reg", 2), ("none", 0), (1, 1)),
+            inst(ROM_ADDR + 1, "copyb", ("imm", 0), ("lastc", 0), ("mem", addr), (3, 3)),
+        ]);
+        assert_eq!(fuse_pairs(&mut insts, &pcs), 1);
+
+        let fused = &insts[&ROM_ADDR].i;
+        assert_eq!(fused.op, ZiskOp::Add.code());
+        assert_eq!((fused.store, fused.store_offset), (STORE_MEM, addr));
+        assert_eq!((fused.jmp_offset1, fused.jmp_offset2), (4, 4));
+
+        // The second instruction is no longer reachable
+        assert_eq!(remove_unreachable(&mut insts, &pcs), 1);
+        assert!(!insts.contains_key(&(ROM_ADDR + 1)));
+    }
+
+    #[test]
+    fn test_fuse_store_requires_exclusive_successor() {
+        // The second instruction is also reached from another instruction, which needs its store
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "add", ("reg", 1), ("reg", 2), ("none", 0), (1, 1)),
+            inst(ROM_ADDR + 1, "copyb", ("imm", 0), ("lastc", 0), ("reg", 3), (3, 3)),
+            inst(ROM_ADDR + 4, "sub", ("reg", 1), ("reg", 2), ("none", 0), (-3, -3)),
+        ]);
+        assert_eq!(fuse_pairs(&mut insts, &pcs), 0);
+    }
+
+    #[test]
+    fn test_fuse_source_reading_lastc() {
+        // copyb(reg 5) followed by add(lastc, lastc), followed by a copyb(lastc) read by a third
+        // instruction: every lastc source is replaced by reg 5
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "copyb", ("imm", 0), ("reg", 5), ("none", 0), (1, 1)),
+            inst(ROM_ADDR + 1, "copyb", ("imm", 0), ("lastc", 0), ("none", 0), (1, 1)),
+            inst(ROM_ADDR + 2, "add", ("lastc", 0), ("lastc", 0), ("reg", 1), (2, 2)),
+        ]);
+        assert_eq!(fuse_pairs(&mut insts, &pcs), 2);
+
+        let second = &insts[&(ROM_ADDR + 1)].i;
+        assert_eq!((second.b_src, second.b_offset_imm0), (SRC_REG, 5));
+        let third = &insts[&(ROM_ADDR + 2)].i;
+        assert_eq!((third.a_src, third.a_offset_imm0), (SRC_REG, 5));
+        assert_eq!((third.b_src, third.b_offset_imm0), (SRC_REG, 5));
+        assert!(!reads_c(third));
+    }
+
+    #[test]
+    fn test_thread_jump_over_nop() {
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "add", ("reg", 1), ("reg", 2), ("reg", 3), (4, 4)),
+            inst(ROM_ADDR + 4, "flag", ("imm", 0), ("imm", 0), ("none", 0), (4, 4)),
+            inst(ROM_ADDR + 8, "add", ("reg", 1), ("reg", 2), ("reg", 4), (4, 4)),
+        ]);
+        assert_eq!(thread_jumps(&mut insts, &pcs), 2);
+        assert_eq!(insts[&ROM_ADDR].i.jmp_offset1, 8);
+        assert_eq!(insts[&ROM_ADDR].i.jmp_offset2, 8);
+    }
+
+    #[test]
+    fn test_thread_jump_stops_on_lastc_reader() {
+        // Skipping the nop would change the value of c read by the landing instruction
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "add", ("reg", 1), ("reg", 2), ("reg", 3), (4, 4)),
+            inst(ROM_ADDR + 4, "copyb", ("imm", 0), ("imm", 7), ("none", 0), (4, 4)),
+            inst(ROM_ADDR + 8, "add", ("lastc", 0), ("reg", 2), ("reg", 4), (4, 4)),
+        ]);
+        assert_eq!(thread_jumps(&mut insts, &pcs), 0);
+        assert_eq!(insts[&ROM_ADDR].i.jmp_offset1, 4);
+    }
+
+    #[test]
+    fn test_thread_jump_keeps_return_address() {
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "eq", ("reg", 1), ("reg", 2), ("none", 0), (4, 8)),
+            inst(ROM_ADDR + 4, "flag", ("imm", 0), ("imm", 0), ("none", 0), (8, 8)),
+            inst(ROM_ADDR + 8, "flag", ("imm", 0), ("imm", 0), ("none", 0), (4, 4)),
+            inst(ROM_ADDR + 12, "add", ("reg", 1), ("reg", 2), ("reg", 4), (4, 4)),
+        ]);
+        insts.get_mut(&ROM_ADDR).unwrap().store_ra("reg", 1, false);
+        thread_jumps(&mut insts, &pcs);
+        assert_eq!(insts[&ROM_ADDR].i.jmp_offset1, 12);
+        assert_eq!(insts[&ROM_ADDR].i.jmp_offset2, 8);
+    }
+
+    #[test]
+    fn test_remove_unreachable_iterates() {
+        // Removing ROM_ADDR + 1 leaves ROM_ADDR + 2 without predecessors; aligned addresses are
+        // never removed
+        let (mut insts, pcs) = rom(vec![
+            inst(ROM_ADDR, "add", ("reg", 1), ("reg", 2), ("reg", 3), (4, 4)),
+            inst(ROM_ADDR + 1, "add", ("reg", 1), ("reg", 2), ("reg", 3), (1, 1)),
+            inst(ROM_ADDR + 2, "add", ("reg", 1), ("reg", 2), ("reg", 3), (2, 2)),
+            inst(ROM_ADDR + 4, "add", ("reg", 1), ("reg", 2), ("reg", 3), (4, 4)),
+        ]);
+        assert_eq!(remove_unreachable(&mut insts, &pcs), 2);
+        let mut remaining: Vec<u64> = insts.keys().copied().collect();
+        remaining.sort();
+        assert_eq!(remaining, [ROM_ADDR, ROM_ADDR + 4]);
+    }
+}
@@ -0,0 +1,192 @@
+//! Shared memory and named semaphore helpers used by the assembly emulator runners to talk to
+//! `ziskemuasm`.  Every failure is reported as an `AsmRunError`.
+
+use libc::{
+    close, ftruncate, mmap, munmap, shm_open, shm_unlink, MAP_SHARED, O_CREAT, PROT_READ,
+    PROT_WRITE, S_IRUSR, S_IWUSR, S_IXUSR,
+};
+
+use named_sem::NamedSemaphore;
+
+use std::ffi::{c_uint, CString};
+use std::process::Child;
+use std::time::Duration;
+use std::{io, ptr};
+
+use log::warn;
+
+use crate::{AsmInputC, AsmRunError};
+
+/// Interval used to check that the `ziskemuasm` process is still alive while waiting for it.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Returns the C string of a shared memory or semaphore name.
+pub(crate) fn c_name(name: &str) -> Result<CString, AsmRunError> {
+    CString::new(name).map_err(|e| AsmRunError::SharedMemory {
+        name: name.to_string(),
+        source: io::Error::new(io::ErrorKind::InvalidInput, e),
+    })
+}
+
+/// Returns the error of the last failed shared memory call on `name`.
+pub(crate) fn shm_error(name: &str) -> AsmRunError {
+    AsmRunError::SharedMemory { name: name.to_string(), source: io::Error::last_os_error() }
+}
+
+pub(crate) fn semaphore_error(name: &str, action: &str, e: impl std::fmt::Display) -> AsmRunError {
+    AsmRunError::Semaphore { name: name.to_string(), reason: format!("{} failed: {}", action, e) }
+}
+
+pub(crate) fn create_semaphore(name: &str) -> Result<NamedSemaphore, AsmRunError> {
+    NamedSemaphore::create(name, 0).map_err(|e| semaphore_error(name, "create", e))
+}
+
+pub(crate) fn post(sem: &mut NamedSemaphore, name: &str) -> Result<(), AsmRunError> {
+    sem.post().map_err(|e| semaphore_error(name, "post", e))
+}
+
+/// Waits on `sem`, checking periodically that the `ziskemuasm` process did not die meanwhile.
+pub(crate) fn wait(
+    child: &mut Child,
+    sem: &mut NamedSemaphore,
+    name: &str,
+) -> Result<(), AsmRunError> {
+    loop {
+        if sem.timed_wait(POLL_INTERVAL).is_ok() {
+            return Ok(());
+        }
+        match child.try_wait() {
+            Ok(None) => {}
+            Ok(Some(status)) => {
+                // The process may have posted right before exiting
+                if sem.try_wait().is_ok() {
+                    return Ok(());
+                }
+                warn!("ziskemuasm exited while waiting on {}", name);
+                return Err(AsmRunError::ProcessExited(Some(status)));
+            }
+            Err(_) => return Err(AsmRunError::ProcessExited(None)),
+        }
+    }
+}
+
+/// Kills a `ziskemuasm` process left blocked by a failed request and returns `err`.
+pub(crate) fn abort(child: &mut Child, err: AsmRunError) -> AsmRunError {
+    let _ = child.kill();
+    let _ = child.wait();
+    err
+}
+
+/// Unlinks shared memory regions and semaphores; names that no longer exist are ignored.
+pub(crate) fn unlink(shmem_names: &[&str], sem_names: &[String]) {
+    for name in shmem_names {
+        if let Ok(name) = CString::new(*name) {
+            unsafe { shm_unlink(name.as_ptr()) };
+        }
+    }
+    for name in sem_names {
+        if let Ok(name) = CString::new(name.as_str()) {
+            unsafe { libc::sem_unlink(name.as_ptr()) };
+        }
+    }
+}
+
+/// Writes `asm_input` followed by `inputs` to a fresh input shared memory called `name`.
+pub(crate) fn write_input(
+    name: &str,
+    asm_input: &AsmInputC,
+    inputs: &[u8],
+) -> Result<(), AsmRunError> {
+    let shmem_input_name = c_name(name)?;
+    let shmem_input_name_ptr = shmem_input_name.as_ptr();
+
+    // Shared memory size (aligned to 8 bytes)
+    let shmem_input_size = (inputs.len() + std::mem::size_of::<AsmInputC>() + 7) & !7;
+
+    let mut shmem_input_data = Vec::with_capacity(shmem_input_size);
+    shmem_input_data.extend_from_slice(&asm_input.to_bytes());
+    shmem_input_data.extend_from_slice(inputs);
+    shmem_input_data.resize(shmem_input_size, 0);
+
+    unsafe {
+        // Remove old shared memory if it exists
+        shm_unlink(shmem_input_name_ptr);
+
+        let shm_fd = shm_open(
+            shmem_input_name_ptr,
+            libc::O_RDWR | O_CREAT,
+            (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint,
+        );
+        if shm_fd == -1 {
+            return Err(shm_error(name));
+        }
+
+        if ftruncate(shm_fd, shmem_input_size as i64) < 0 {
+            let err = shm_error(name);
+            close(shm_fd);
+            return Err(err);
+        }
+
+        let mapped_ptr =
+            mmap(ptr::null_mut(), shmem_input_size, PROT_READ | PROT_WRITE, MAP_SHARED, shm_fd, 0);
+        if mapped_ptr == libc::MAP_FAILED {
+            let err = shm_error(name);
+            close(shm_fd);
+            return Err(err);
+        }
+
+        ptr::copy_nonoverlapping(
+            shmem_input_data.as_ptr(),
+            mapped_ptr as *mut u8,
+            shmem_input_size,
+        );
+
+        munmap(mapped_ptr, shmem_input_size);
+        close(shm_fd);
+    }
+
+    Ok(())
+}
+
+/// Opens the output shared memory called `name`, read-only.
+pub(crate) fn open_output(name: &str) -> Result<libc::c_int, AsmRunError> {
+    let shmem_output_name = c_name(name)?;
+    let shm_fd = unsafe {
+        shm_open(
+            shmem_output_name.as_ptr(),
+            libc::O_RDONLY,
+            (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint,
+        )
+    };
+    if shm_fd == -1 {
+        return Err(shm_error(name));
+    }
+    Ok(shm_fd)
+}
+
+/// Reads a `T` header from the start of the output shared memory opened as `shm_fd`.
+pub(crate) fn read_header<T>(shm_fd: libc::c_int, name: &str) -> Result<T, AsmRunError> {
+    let header_size = size_of::<T>();
+    unsafe {
+        let header_ptr = mmap(ptr::null_mut(), header_size, PROT_READ, MAP_SHARED, shm_fd, 0);
+        if header_ptr == libc::MAP_FAILED {
+            return Err(shm_error(name));
+        }
+        let header = ptr::read(header_ptr as *const T);
+        munmap(header_ptr, header_size);
+        Ok(header)
+    }
+}
+
+/// Maps the first `size` bytes of the output shared memory opened as `shm_fd`, read-only.
+pub(crate) fn map_output(
+    shm_fd: libc::c_int,
+    name: &str,
+    size: usize,
+) -> Result<*mut libc::c_void, AsmRunError> {
+    let mapped_ptr = unsafe { mmap(ptr::null_mut(), size, PROT_READ, MAP_SHARED, shm_fd, 0) };
+    if mapped_ptr == libc::MAP_FAILED {
+        return Err(shm_error(name));
+    }
+    Ok(mapped_ptr)
+}
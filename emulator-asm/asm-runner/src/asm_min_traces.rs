@@ -2,7 +2,12 @@ use std::ffi::c_void;
 use std::fmt::Debug;
 use zisk_common::EmuTrace;
 use zisk_common::EmuTraceStart;
-use zisk_core::{REGS_IN_MAIN_FROM, REGS_IN_MAIN_TO, REGS_IN_MAIN_TOTAL_NUMBER};
+use zisk_core::{
+    ResourceLimitExceeded, ResourceLimits, PRECOMPILE_FIRST_OP, PRECOMPILE_OPS, REGS_IN_MAIN_FROM,
+    REGS_IN_MAIN_TO, REGS_IN_MAIN_TOTAL_NUMBER,
+};
+
+use crate::ASM_EXIT_STATUS_MASK;
 
 #[repr(C)]
 #[derive(Debug)]
@@ -20,11 +25,56 @@ impl AsmMTHeader {
             output_header = std::ptr::read(mapped_ptr as *const AsmMTHeader);
         }
 
-        assert!(output_header.mt_allocated_size > 0);
-        assert!(output_header.mt_used_size > 0);
-
         output_header
     }
+
+    /// Returns true if the emulation completed, whatever the guest exit code
+    pub fn completed(&self) -> bool {
+        self.exit_code & ASM_EXIT_STATUS_MASK == 0
+    }
+
+    /// Returns true if the emulation is over, either because it completed or because it was
+    /// stopped, e.g. after reaching the maximum number of steps
+    pub fn finished(&self) -> bool {
+        self.exit_code & ASM_EXIT_STATUS_MASK != 1
+    }
+
+    /// Returns the exit code of the guest program, if the emulation completed
+    pub fn guest_exit_code(&self) -> u64 {
+        self.exit_code >> 8
+    }
+}
+
+/// Resource usage of the guest program, written by the assembly emulator right after the minimal
+/// traces once the emulation is over
+#[repr(C)]
+#[derive(Debug, Default, Clone)]
+pub struct AsmResourceUsage {
+    /// General purpose memory in use, up to its highest non-zero word, in bytes
+    pub heap_size: u64,
+    /// Output data size, in bytes
+    pub output_size: u64,
+    /// Number of calls of every precompile, indexed by `op code - PRECOMPILE_FIRST_OP`
+    pub precompile_calls: [u64; PRECOMPILE_OPS],
+}
+
+impl AsmResourceUsage {
+    /// Checks the heap size, output size and precompile calls limits
+    pub fn check(&self, limits: &ResourceLimits) -> Result<(), ResourceLimitExceeded> {
+        limits.check_heap_size(self.heap_size)?;
+        limits.check_output_size(self.output_size)?;
+        for &op in limits.max_precompile_calls.keys() {
+            // Only the precompiles are counted, which are the only limited operations
+            let calls = op
+                .code()
+                .checked_sub(PRECOMPILE_FIRST_OP)
+                .and_then(|index| self.precompile_calls.get(index as usize))
+                .copied()
+                .unwrap_or(0);
+            limits.check_precompile_calls(op, calls)?;
+        }
+        Ok(())
+    }
 }
 
 #[repr(C)]
@@ -100,3 +150,31 @@ impl AsmInputC {
         bytes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_core::{zisk_ops::ZiskOp, ResourceLimit};
+
+    #[test]
+    fn test_resource_usage_check() {
+        let mut usage = AsmResourceUsage { heap_size: 1024, output_size: 32, ..Default::default() };
+        usage.precompile_calls[(ZiskOp::Keccak.code() - PRECOMPILE_FIRST_OP) as usize] = 3;
+        assert!(usage.check(&ResourceLimits::default()).is_ok());
+
+        let limits = ResourceLimits { max_heap_size: Some(1000), ..Default::default() };
+        let e = usage.check(&limits).unwrap_err();
+        assert_eq!((e.limit, e.used, e.max), (ResourceLimit::HeapSize, 1024, 1000));
+
+        let limits = ResourceLimits { max_output_size: Some(16), ..Default::default() };
+        assert_eq!(usage.check(&limits).unwrap_err().limit, ResourceLimit::OutputSize);
+
+        let mut limits = ResourceLimits::default();
+        limits.max_precompile_calls.insert(ZiskOp::Keccak, 3);
+        limits.max_precompile_calls.insert(ZiskOp::Sha256, 0);
+        assert!(usage.check(&limits).is_ok());
+        limits.max_precompile_calls.insert(ZiskOp::Keccak, 2);
+        let e = usage.check(&limits).unwrap_err();
+        assert_eq!((e.limit, e.used), (ResourceLimit::PrecompileCalls(ZiskOp::Keccak), 3));
+    }
+}
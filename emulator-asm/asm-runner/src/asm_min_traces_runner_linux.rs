@@ -1,13 +1,9 @@
-use libc::{
-    close, ftruncate, mmap, munmap, shm_open, shm_unlink, MAP_SHARED, O_CREAT, PROT_READ,
-    PROT_WRITE, S_IRUSR, S_IWUSR, S_IXUSR,
-};
+use libc::{close, munmap, shm_unlink};
 
-use named_sem::NamedSemaphore;
 use rayon::ThreadPoolBuilder;
 use zisk_common::{ChunkId, EmuTrace};
 
-use std::ffi::{c_uint, c_void, CString};
+use std::ffi::{c_void, CString};
 use std::fmt::Debug;
 use std::path::Path;
 use std::process::{self, Command};
@@ -15,9 +11,17 @@ use std::sync::mpsc;
 use std::time::Duration;
 use std::{fs, ptr};
 
-use log::{error, info};
+use log::{debug, error, info};
+use zisk_core::ResourceLimits;
+
+use crate::asm_ipc_linux as ipc;
+use crate::{
+    AsmInputC, AsmMTChunk, AsmMTHeader, AsmResourceUsage, AsmRunError, AsmRunnerOptions,
+    AsmRunnerTraceLevel, AsmUserFcallServer, ASM_EXIT_STATUS_MASK,
+};
 
-use crate::{AsmInputC, AsmMTChunk, AsmMTHeader, AsmRunnerOptions, AsmRunnerTraceLevel};
+/// Time given to the assembly emulator to generate every chunk.
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub trait Task: Send + Sync + 'static {
     type Output: Send + 'static;
@@ -33,12 +37,25 @@ pub enum MinimalTraces {
     AsmEmuTrace(AsmRunnerMT),
 }
 
+/// Output of an assembly emulator run, as mapped by `AsmRunnerMT::map_output`.
+pub(crate) struct AsmMTOutput {
+    pub mapped_ptr: *mut c_void,
+    pub mapped_size: usize,
+    pub exit_code: u64,
+    pub vec_chunks: Vec<EmuTrace>,
+    pub resource_usage: AsmResourceUsage,
+}
+
 // This struct is used to run the assembly code in a separate process and generate minimal traces.
 #[derive(Debug)]
 pub struct AsmRunnerMT {
     shmem_output_name: String,
     mapped_ptr: *mut c_void,
+    /// Size of the mapping starting at `mapped_ptr`, or 0 when it has to be derived from chunks
+    mapped_size: usize,
     pub vec_chunks: Vec<EmuTrace>,
+    /// Exit code of the guest program, as reported by the assembly emulator
+    pub guest_exit_code: u64,
 }
 
 unsafe impl Send for AsmRunnerMT {}
@@ -53,13 +70,17 @@ impl Drop for AsmRunnerMT {
             }
 
             // Unmap shared memory
-            libc::munmap(self.mapped_ptr, self.total_size());
+            let size = if self.mapped_size > 0 { self.mapped_size } else { self.total_size() };
+            libc::munmap(self.mapped_ptr, size);
 
-            let shmem_output_name =
-                CString::new(self.shmem_output_name.clone()).expect("CString::new failed");
-            let shmem_output_name_ptr = shmem_output_name.as_ptr();
+            // Traces served by an `AsmServerMT` are unlinked by the server itself
+            if self.shmem_output_name.is_empty() {
+                return;
+            }
 
-            shm_unlink(shmem_output_name_ptr);
+            if let Ok(shmem_output_name) = CString::new(self.shmem_output_name.as_str()) {
+                shm_unlink(shmem_output_name.as_ptr());
+            }
         }
     }
 }
@@ -70,7 +91,30 @@ impl AsmRunnerMT {
         mapped_ptr: *mut c_void,
         vec_chunks: Vec<EmuTrace>,
     ) -> Self {
-        AsmRunnerMT { shmem_output_name, mapped_ptr, vec_chunks }
+        AsmRunnerMT {
+            shmem_output_name,
+            mapped_ptr,
+            mapped_size: 0,
+            vec_chunks,
+            guest_exit_code: 0,
+        }
+    }
+
+    /// Creates a runner result over an output mapping of `mapped_size` bytes whose shared memory
+    /// name is owned by someone else, so it is unmapped but not unlinked on drop.
+    pub(crate) fn from_mapping(
+        mapped_ptr: *mut c_void,
+        mapped_size: usize,
+        exit_code: u64,
+        vec_chunks: Vec<EmuTrace>,
+    ) -> Self {
+        AsmRunnerMT {
+            shmem_output_name: String::new(),
+            mapped_ptr,
+            mapped_size,
+            vec_chunks,
+            guest_exit_code: exit_code >> 8,
+        }
     }
 
     fn total_size(&self) -> usize {
@@ -78,13 +122,20 @@ impl AsmRunnerMT {
             + std::mem::size_of::<AsmMTHeader>()
     }
 
+    /// Runs the assembly emulator and returns its minimal traces.
+    ///
+    /// The assembly emulator only stops at the steps limit; the rest of the limits are checked
+    /// once the emulation completes.
     pub fn run(
         ziskemuasm_path: &Path,
         inputs_path: &Path,
-        shm_size: u64,
+        limits: &ResourceLimits,
         chunk_size: u64,
         options: AsmRunnerOptions,
-    ) -> AsmRunnerMT {
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        let inputs = fs::read(inputs_path).map_err(AsmRunError::Inputs)?;
+        limits.check_input_size(inputs.len() as u64)?;
+
         let pid = unsafe { libc::getpid() };
 
         let shmem_prefix = format!("ZISKMT{}", pid);
@@ -95,21 +146,10 @@ impl AsmRunnerMT {
         let sem_output_name = format!("/{}_semout", shmem_prefix);
         let sem_input_name = format!("/{}_semin", shmem_prefix);
 
-        let mut sem_in = NamedSemaphore::create(sem_input_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerMT::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_input_name, e
-            )
-        });
+        let mut sem_in = ipc::create_semaphore(&sem_input_name)?;
+        let mut sem_out = ipc::create_semaphore(&sem_output_name)?;
 
-        let mut sem_out = NamedSemaphore::create(sem_output_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerMT::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_output_name, e
-            )
-        });
-
-        Self::write_input(inputs_path, &shmem_input_name, shm_size, chunk_size);
+        Self::write_input(&inputs, &shmem_input_name, limits.max_steps, chunk_size)?;
 
         // Prepare command
         let mut command = Command::new(ziskemuasm_path);
@@ -140,49 +180,63 @@ impl AsmRunnerMT {
             command.arg("-k");
         }
 
+        // Serve the user fcalls until the emulation completes
+        let _user_fcall_server = AsmUserFcallServer::start_for_command(
+            &mut command,
+            &shmem_prefix,
+            options.user_fcalls.as_ref(),
+        )?;
+
         // Spawn child process
         let start = std::time::Instant::now();
-        if let Err(e) = command.arg(&shmem_prefix).spawn() {
-            error!("Child process failed: {:?}", e);
-        } else if options.verbose || options.log_output {
+        let mut child = command.arg(&shmem_prefix).spawn().map_err(AsmRunError::Spawn)?;
+        if options.verbose || options.log_output {
             info!("Child process launched successfully");
         }
 
         // Wait for the assembly emulator to complete writing the trace
-        if let Err(e) = sem_in.wait() {
-            panic!(
-                "AsmRunnerMT::run() failed calling semin.wait({}), error: {}",
-                sem_input_name, e
-            );
-        }
+        ipc::wait(&mut child, &mut sem_in, &sem_input_name)?;
 
         let stop = start.elapsed();
 
-        let (mapped_ptr, vec_chunks) = Self::map_output(shmem_output_name.clone());
+        let output = Self::map_output(&shmem_output_name);
+
+        // Tell the assembly that we are done reading the trace, even if it failed
+        ipc::post(&mut sem_out, &sem_output_name)?;
+
+        let AsmMTOutput { mapped_ptr, mapped_size, exit_code, vec_chunks, resource_usage } =
+            output?;
 
         let total_steps = vec_chunks.iter().map(|x| x.steps).sum::<u64>();
         let mhz = (total_steps as f64 / stop.as_secs_f64()) / 1_000_000.0;
         info!("AsmRnner: ··· Assembly execution speed: {:.2} MHz", mhz);
 
-        // Tell the assembly that we are done reading the trace
-        if let Err(e) = sem_out.post() {
-            panic!(
-                "AsmRunnerMT::run() failed calling semout.post({}), error: {}",
-                sem_output_name, e
-            );
-        }
-
-        AsmRunnerMT::new(shmem_output_name, mapped_ptr, vec_chunks)
+        let asm_runner_mt = AsmRunnerMT {
+            shmem_output_name,
+            mapped_ptr,
+            mapped_size,
+            vec_chunks,
+            guest_exit_code: exit_code >> 8,
+        };
+        Self::check_output(exit_code, &resource_usage, limits)?;
+        Ok(asm_runner_mt)
     }
 
+    /// Runs the assembly emulator, executing a task for every chunk as soon as it is generated.
+    ///
+    /// The assembly emulator only stops at the steps limit; the rest of the limits are checked
+    /// once the emulation completes.
     pub fn run_and_count<T: Task>(
         ziskemuasm_path: &Path,
         inputs_path: &Path,
-        shm_size: u64,
+        limits: &ResourceLimits,
         chunk_size: u64,
         options: AsmRunnerOptions,
         task_factory: TaskFactory<T>,
-    ) -> (AsmRunnerMT, Vec<T::Output>) {
+    ) -> Result<(AsmRunnerMT, Vec<T::Output>), AsmRunError> {
+        let inputs = fs::read(inputs_path).map_err(AsmRunError::Inputs)?;
+        limits.check_input_size(inputs.len() as u64)?;
+
         let pid = unsafe { libc::getpid() };
 
         let shmem_prefix = format!("ZISKMT{}", pid);
@@ -194,29 +248,11 @@ impl AsmRunnerMT {
         let sem_input_name = format!("/{}_semin", shmem_prefix);
         let sem_chunk_done_name = format!("/{}_semckd", shmem_prefix);
 
-        let mut sem_in = NamedSemaphore::create(sem_input_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerMT::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_input_name, e
-            )
-        });
-
-        let mut sem_out = NamedSemaphore::create(sem_output_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerMT::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_output_name, e
-            )
-        });
-
-        let mut sem_chunk_done = NamedSemaphore::create(sem_chunk_done_name.clone(), 0)
-            .unwrap_or_else(|e| {
-                panic!(
-                    "AsmRunnerMT::run() failed calling NamedSemaphore::create({}), error: {}",
-                    sem_chunk_done_name, e
-                )
-            });
+        let mut sem_in = ipc::create_semaphore(&sem_input_name)?;
+        let mut sem_out = ipc::create_semaphore(&sem_output_name)?;
+        let mut sem_chunk_done = ipc::create_semaphore(&sem_chunk_done_name)?;
 
-        Self::write_input(inputs_path, &shmem_input_name, shm_size, chunk_size);
+        Self::write_input(&inputs, &shmem_input_name, limits.max_steps, chunk_size)?;
 
         // Prepare command
         let mut command = Command::new(ziskemuasm_path);
@@ -245,291 +281,240 @@ impl AsmRunnerMT {
             command.arg("-k");
         }
 
+        // Serve the user fcalls until the emulation completes
+        let _user_fcall_server = AsmUserFcallServer::start_for_command(
+            &mut command,
+            &shmem_prefix,
+            options.user_fcalls.as_ref(),
+        )?;
+
+        let pool =
+            ThreadPoolBuilder::new().num_threads(16).build().map_err(AsmRunError::ThreadPool)?;
+
         let start = std::time::Instant::now();
-        if let Err(e) = command.arg(&shmem_prefix).spawn() {
-            error!("Child process failed: {:?}", e);
-        } else if options.verbose || options.log_output {
+        let mut child = command.arg(&shmem_prefix).spawn().map_err(AsmRunError::Spawn)?;
+        if options.verbose || options.log_output {
             info!("Child process launched successfully");
         }
 
-        let pool = ThreadPoolBuilder::new().num_threads(16).build().unwrap();
-
         let mut chunk_id = ChunkId(0);
-        let mut header_ptr: Option<*mut c_void> = None;
-        let mut data_ptr: Option<*mut c_void> = None;
-
-        let mut should_exit = false;
+        // Mapping of the whole output, created once the first chunk is available
+        let mut output: Option<(*mut c_void, usize)> = None;
+        let mut data_ptr: *mut c_void = ptr::null_mut();
 
         let (sender, receiver) = mpsc::channel();
 
         let exit_code = loop {
-            match sem_chunk_done.timed_wait(Duration::from_millis(10000)) {
+            match sem_chunk_done.timed_wait(CHUNK_TIMEOUT) {
                 Ok(()) => {
-                    // Read the header data
-                    if data_ptr.is_none() {
-                        header_ptr.get_or_insert_with(|| Self::get_output_ptr(&shmem_output_name));
-
-                        let output_header_size = std::mem::size_of::<AsmMTHeader>();
-
-                        data_ptr = Some(unsafe {
-                            (header_ptr.unwrap() as *mut u8).add(output_header_size + 8)
-                                as *mut c_void
-                        });
-                    }
-
-                    let emu_trace = AsmMTChunk::to_emu_trace(data_ptr.as_mut().unwrap());
+                    // Map the output and skip its header and the number of chunks
+                    let (header_ptr, _) = match output {
+                        Some(output) => output,
+                        None => {
+                            let mapping = Self::map_allocated_output(&shmem_output_name)
+                                .map_err(|e| ipc::abort(&mut child, e))?;
+                            data_ptr = unsafe {
+                                (mapping.0 as *mut u8).add(size_of::<AsmMTHeader>() + 8)
+                                    as *mut c_void
+                            };
+                            *output.insert(mapping)
+                        }
+                    };
+
+                    let emu_trace = AsmMTChunk::to_emu_trace(&mut data_ptr);
 
                     let task = task_factory(chunk_id, emu_trace);
-                    let sender = sender.clone();
+                    let task_sender = sender.clone();
                     pool.spawn(move || {
-                        sender.send(task.execute()).unwrap();
+                        // The receiver is only gone if the run already failed
+                        let _ = task_sender.send(task.execute());
                     });
 
                     chunk_id.0 += 1;
 
                     // Check exit_code after processing the chunk
-                    if !should_exit {
-                        let header = Self::read_output_header(&mut header_ptr, &shmem_output_name);
-                        should_exit = header.exit_code == 0;
+                    let header = Self::read_output_header(header_ptr);
+                    if header.finished() {
+                        while let Ok(()) = sem_chunk_done.try_wait() {
+                            let emu_trace = AsmMTChunk::to_emu_trace(&mut data_ptr);
+                            let task = task_factory(chunk_id, emu_trace);
+                            let task_sender = sender.clone();
+                            pool.spawn(move || {
+                                let _ = task_sender.send(task.execute());
+                            });
+
+                            chunk_id.0 += 1;
+                        }
+
+                        break header.exit_code & ASM_EXIT_STATUS_MASK;
                     }
                 }
                 Err(e) => {
                     error!("Semaphore sem_chunk_done error: {:?}", e);
 
-                    if chunk_id.0 == 0 {
-                        break 1;
+                    // Give up unless the emulation is over, since no chunk was generated in time
+                    let exit_code = match output {
+                        Some((header_ptr, _)) => {
+                            Self::read_output_header(header_ptr).exit_code & ASM_EXIT_STATUS_MASK
+                        }
+                        None => 1,
+                    };
+                    if exit_code == 1 {
+                        Self::unmap(output);
+                        let err = match child.try_wait() {
+                            Ok(Some(status)) => AsmRunError::ProcessExited(Some(status)),
+                            _ => AsmRunError::ChunkTimeout(CHUNK_TIMEOUT),
+                        };
+                        return Err(ipc::abort(&mut child, err));
                     }
-
-                    let output_header =
-                        Self::read_output_header(&mut header_ptr, &shmem_output_name);
-                    break output_header.exit_code;
-                }
-            }
-
-            if should_exit {
-                while let Ok(()) = sem_chunk_done.try_wait() {
-                    let emu_trace = AsmMTChunk::to_emu_trace(data_ptr.as_mut().unwrap());
-                    let task = task_factory(chunk_id, emu_trace);
-                    let sender = sender.clone();
-                    pool.spawn(move || {
-                        sender.send(task.execute()).unwrap();
-                    });
-
-                    chunk_id.0 += 1;
+                    break exit_code;
                 }
-
-                break 0;
             }
         };
-
-        if exit_code != 0 {
-            panic!("Child process terminated with error code: {}", exit_code,);
-        }
+        debug!("AsmRunnerMT: emulation finished with status {}", exit_code);
 
         // Collect results
         drop(sender);
         let tasks: Vec<T::Output> = receiver.iter().collect();
+        Self::unmap(output);
 
         // Wait for the assembly emulator to complete writing the trace
-        let result = sem_in.wait();
-        if result.is_err() {
-            panic!("AsmRunnerMT::run() failed calling semin.wait({})", sem_input_name);
-        }
+        ipc::wait(&mut child, &mut sem_in, &sem_input_name)?;
 
         let stop = start.elapsed();
 
-        let (mapped_ptr, vec_chunks) = Self::map_output(shmem_output_name.clone());
+        let output = Self::map_output(&shmem_output_name);
+
+        // Tell the assembly that we are done reading the trace, even if it failed
+        ipc::post(&mut sem_out, &sem_output_name)?;
+
+        let AsmMTOutput { mapped_ptr, mapped_size, exit_code, vec_chunks, resource_usage } =
+            output?;
 
         let total_steps = vec_chunks.iter().map(|x| x.steps).sum::<u64>();
         let mhz = (total_steps as f64 / stop.as_secs_f64()) / 1_000_000.0;
         info!("AsmRnner: ··· Assembly execution speed: {:.2} MHz", mhz);
 
-        // Tell the assembly that we are done reading the trace
-        let result = sem_out.post();
-        if result.is_err() {
-            panic!("AsmRunnerMT::run() failed calling semout.post({})", sem_output_name);
-        }
-
-        (AsmRunnerMT::new(shmem_output_name, mapped_ptr, vec_chunks), tasks)
+        let asm_runner_mt = AsmRunnerMT {
+            shmem_output_name,
+            mapped_ptr,
+            mapped_size,
+            vec_chunks,
+            guest_exit_code: exit_code >> 8,
+        };
+        Self::check_output(exit_code, &resource_usage, limits)?;
+        Ok((asm_runner_mt, tasks))
     }
 
-    fn write_input(inputs_path: &Path, shmem_input_name: &str, max_steps: u64, chunk_size: u64) {
-        let shmem_input_name = CString::new(shmem_input_name).expect("CString::new failed");
-        let shmem_input_name_ptr = shmem_input_name.as_ptr();
-
-        let inputs = fs::read(inputs_path).expect("Could not read inputs file");
-
+    fn write_input(
+        inputs: &[u8],
+        shmem_input_name: &str,
+        max_steps: u64,
+        chunk_size: u64,
+    ) -> Result<(), AsmRunError> {
         let asm_input = AsmInputC {
             chunk_size,
             max_steps,
             initial_trace_size: 1u64 << 32, // 4GB
             input_data_size: inputs.len() as u64,
         };
-
-        // Shared memory size (aligned to 8 bytes)
-        let shmem_input_size = (inputs.len() + std::mem::size_of::<AsmInputC>() + 7) & !7;
-
-        let mut shmem_input_data = Vec::with_capacity(shmem_input_size);
-        shmem_input_data.extend_from_slice(&asm_input.to_bytes());
-        shmem_input_data.extend_from_slice(&inputs);
-
-        // Remove old shared memory if it exists
-        unsafe { shm_unlink(shmem_input_name_ptr) };
-
-        let shm_fd = unsafe {
-            shm_open(
-                shmem_input_name_ptr,
-                libc::O_RDWR | O_CREAT,
-                (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint,
-            )
-        };
-        Self::check_shm_open(shm_fd, shmem_input_name_ptr);
-
-        if unsafe { ftruncate(shm_fd, shmem_input_size as i64) } < 0 {
-            panic!("ftruncate failed");
-        }
-
-        let mapped_ptr = unsafe {
-            mmap(
-                std::ptr::null_mut(),
-                shmem_input_size,
-                PROT_READ | PROT_WRITE,
-                MAP_SHARED,
-                shm_fd,
-                0,
-            )
-        };
-        Self::check_mmap(mapped_ptr, shmem_input_size, file!(), line!());
-
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                shmem_input_data.as_ptr(),
-                mapped_ptr as *mut u8,
-                shmem_input_size,
-            );
-
-            munmap(mapped_ptr, shmem_input_size);
-            close(shm_fd);
-        }
+        ipc::write_input(shmem_input_name, &asm_input, inputs)
     }
 
-    fn map_output(shmem_output_name: String) -> (*mut c_void, Vec<EmuTrace>) {
-        let shmem_output_name = CString::new(shmem_output_name).expect("CString::new failed");
-        let shmem_output_name_ptr = shmem_output_name.as_ptr();
-
-        let shm_fd = unsafe {
-            shm_open(shmem_output_name_ptr, libc::O_RDONLY, (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint)
-        };
-
-        Self::check_shm_open(shm_fd, shmem_output_name_ptr);
-
-        // Read Output Header
-        let output_header_size = size_of::<AsmMTHeader>();
-        let mapped_ptr =
-            unsafe { mmap(ptr::null_mut(), output_header_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-        Self::check_mmap(mapped_ptr, output_header_size, file!(), line!());
-
-        let output_header = AsmMTHeader::from_ptr(mapped_ptr);
-
-        // Read Output
-        let output_size = output_header_size + output_header.mt_used_size as usize;
-
-        let mut mapped_ptr =
-            unsafe { mmap(ptr::null_mut(), output_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-        Self::check_mmap(mapped_ptr, output_size, file!(), line!());
-
-        // println!("Output Header: {:?}", output_header);
+    /// Maps the output written by the assembly emulator, returning the mapping, its size, the
+    /// exit code of the emulation, the minimal traces chunks and the resource usage of the guest
+    /// program, which are only parsed if the emulation completed.
+    pub(crate) fn map_output(shmem_output_name: &str) -> Result<AsmMTOutput, AsmRunError> {
+        let shm_fd = ipc::open_output(shmem_output_name)?;
+
+        // Read the output header to know the used size, and map it with the used part of the
+        // trace and the resource usage that follows it, which outlive the file descriptor
+        let output =
+            ipc::read_header::<AsmMTHeader>(shm_fd, shmem_output_name).and_then(|header| {
+                let output_size = size_of::<AsmMTHeader>()
+                    + header.mt_used_size as usize
+                    + size_of::<AsmResourceUsage>();
+                ipc::map_output(shm_fd, shmem_output_name, output_size)
+                    .map(|mapped_ptr| (mapped_ptr, output_size, header))
+            });
+        unsafe { close(shm_fd) };
+        let (mapped_ptr, output_size, header) = output?;
+
+        let mut vec_chunks = Vec::new();
+        let mut resource_usage = AsmResourceUsage::default();
+        if header.completed() {
+            if header.mt_used_size < 8 {
+                unsafe { munmap(mapped_ptr, output_size) };
+                return Err(AsmRunError::InvalidOutput(format!(
+                    "{} bytes of minimal traces are too few to hold the number of chunks",
+                    header.mt_used_size
+                )));
+            }
+            unsafe {
+                let mut chunk_ptr =
+                    (mapped_ptr as *mut u8).add(size_of::<AsmMTHeader>()) as *mut c_void;
+                let num_chunks = ptr::read(chunk_ptr as *const u64);
+                chunk_ptr = (chunk_ptr as *mut u8).add(8) as *mut c_void;
+
+                vec_chunks.reserve(num_chunks as usize);
+                for _ in 0..num_chunks {
+                    vec_chunks.push(AsmMTChunk::to_emu_trace(&mut chunk_ptr));
+                }
 
-        let mut vec_chunks;
-        unsafe {
-            mapped_ptr = mapped_ptr.add(output_header_size);
-            let num_chunks = std::ptr::read(mapped_ptr as *const u64);
-            mapped_ptr = (mapped_ptr as *mut u8).add(8) as *mut c_void;
-
-            vec_chunks = Vec::with_capacity(num_chunks as usize);
-            for _ in 0..num_chunks {
-                let data = AsmMTChunk::to_emu_trace(&mut mapped_ptr);
-                vec_chunks.push(data);
+                let usage_ptr = (mapped_ptr as *mut u8)
+                    .add(size_of::<AsmMTHeader>() + header.mt_used_size as usize);
+                resource_usage = ptr::read_unaligned(usage_ptr as *const AsmResourceUsage);
             }
         }
 
-        (mapped_ptr, vec_chunks)
-    }
-
-    fn read_output_header(
-        header_ptr: &mut Option<*mut c_void>,
-        shmem_output_name: &str,
-    ) -> AsmMTHeader {
-        header_ptr.get_or_insert_with(|| {
-            let cstr = CString::new(shmem_output_name).expect("CString::new failed");
-            let ptr = cstr.as_ptr();
-
-            // Open shared memory read-only
-            let shm_fd = unsafe { shm_open(ptr, libc::O_RDONLY, S_IRUSR | S_IWUSR | S_IXUSR) };
-            Self::check_shm_open(shm_fd, ptr);
-
-            let header_size = size_of::<AsmMTHeader>();
-
-            // Map the header from the shared memory
-            let mapped =
-                unsafe { mmap(ptr::null_mut(), header_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-            Self::check_mmap(mapped, header_size, file!(), line!());
-
-            mapped
-        });
-        unsafe { std::ptr::read(header_ptr.unwrap() as *const AsmMTHeader) }
+        Ok(AsmMTOutput {
+            mapped_ptr,
+            mapped_size: output_size,
+            exit_code: header.exit_code,
+            vec_chunks,
+            resource_usage,
+        })
     }
 
-    fn get_output_ptr(shmem_output_name: &str) -> *mut c_void {
-        let cstr = CString::new(shmem_output_name).expect("CString::new failed");
-        let ptr = cstr.as_ptr();
-
-        // Open shared memory read-only
-        let shm_fd = unsafe { shm_open(ptr, libc::O_RDONLY, S_IRUSR | S_IWUSR | S_IXUSR) };
-        Self::check_shm_open(shm_fd, ptr);
-
-        let header_size = size_of::<AsmMTHeader>();
-
-        // Map the header from the shared memory
-        let mapped =
-            unsafe { mmap(ptr::null_mut(), header_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-        Self::check_mmap(mapped, header_size, file!(), line!());
-
-        // Read the header
-        let header = unsafe { ptr::read(mapped as *const AsmMTHeader) };
-
-        // Unmap the small mapping
-        unsafe {
-            munmap(mapped, header_size);
+    /// Checks the exit code and the resource usage of an emulation, once its output is mapped
+    pub(crate) fn check_output(
+        exit_code: u64,
+        resource_usage: &AsmResourceUsage,
+        limits: &ResourceLimits,
+    ) -> Result<(), AsmRunError> {
+        if let Some(e) = AsmRunError::from_exit_code(exit_code, limits.max_steps) {
+            return Err(e);
         }
+        resource_usage.check(limits).map_err(AsmRunError::from)
+    }
 
-        // Step 4: Map the full allocated size
-        let mapped = unsafe {
-            mmap(
-                ptr::null_mut(),
-                header.mt_allocated_size as usize,
-                PROT_READ,
-                MAP_SHARED,
-                shm_fd,
-                0,
-            )
-        };
-        Self::check_mmap(mapped, header.mt_allocated_size as usize, file!(), line!());
-
-        mapped
+    /// Maps the whole allocated output, whose chunks are read while the assembly emulator keeps
+    /// generating them
+    fn map_allocated_output(shmem_output_name: &str) -> Result<(*mut c_void, usize), AsmRunError> {
+        let shm_fd = ipc::open_output(shmem_output_name)?;
+        let output =
+            ipc::read_header::<AsmMTHeader>(shm_fd, shmem_output_name).and_then(|header| {
+                let size = header.mt_allocated_size as usize;
+                if size < size_of::<AsmMTHeader>() + 8 {
+                    return Err(AsmRunError::InvalidOutput(format!(
+                        "{} allocated bytes are too few to hold the minimal traces",
+                        size
+                    )));
+                }
+                ipc::map_output(shm_fd, shmem_output_name, size)
+                    .map(|mapped_ptr| (mapped_ptr, size))
+            });
+        unsafe { close(shm_fd) };
+        output
     }
 
-    fn check_shm_open(shm_fd: i32, name: *const i8) {
-        if shm_fd == -1 {
-            let err = std::io::Error::last_os_error();
-            panic!("shm_open({:?}) failed: {:?}", name, err);
-        }
+    fn read_output_header(header_ptr: *mut c_void) -> AsmMTHeader {
+        unsafe { ptr::read_volatile(header_ptr as *const AsmMTHeader) }
     }
 
-    fn check_mmap(ptr: *mut libc::c_void, size: usize, file: &str, line: u32) {
-        if ptr == libc::MAP_FAILED {
-            let err = std::io::Error::last_os_error();
-            panic!("mmap failed: {:?} (size: {} bytes) at {}:{}", err, size, file, line);
+    fn unmap(output: Option<(*mut c_void, usize)>) {
+        if let Some((mapped_ptr, size)) = output {
+            unsafe { munmap(mapped_ptr, size) };
         }
     }
 }
@@ -1,8 +1,9 @@
 use std::ffi::c_void;
 use std::path::Path;
 
-use crate::AsmRunnerOptions;
+use crate::{AsmRunError, AsmRunnerOptions};
 use zisk_common::{ChunkId, EmuTrace};
+use zisk_core::ResourceLimits;
 
 pub trait Task: Send + Sync + 'static {
     type Output: Send + 'static;
@@ -21,6 +22,7 @@ pub enum MinimalTraces {
 #[derive(Debug)]
 pub struct AsmRunnerMT {
     pub vec_chunks: Vec<EmuTrace>,
+    pub guest_exit_code: u64,
 }
 
 impl AsmRunnerMT {
@@ -37,26 +39,22 @@ impl AsmRunnerMT {
     pub fn run(
         _ziskemuasm_path: &Path,
         _inputs_path: &Path,
-        _shm_size: u64,
+        _limits: &ResourceLimits,
         _chunk_size: u64,
         _options: AsmRunnerOptions,
-    ) -> AsmRunnerMT {
-        panic!(
-            "AsmRunnerMT::run() is not supported on this platform. Only Linux x86_64 is supported."
-        );
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        Err(AsmRunError::Unsupported)
     }
 
     pub fn run_and_count<T: Task>(
         _ziskemuasm_path: &Path,
         _inputs_path: &Path,
-        _shm_size: u64,
+        _limits: &ResourceLimits,
         _chunk_size: u64,
         _options: AsmRunnerOptions,
         _task_factory: TaskFactory<T>,
-    ) -> (AsmRunnerMT, Vec<T::Output>) {
-        panic!(
-            "AsmRunnerMT::run_and_count() is not supported on this platform. Only Linux x86_64 is supported."
-        );
+    ) -> Result<(AsmRunnerMT, Vec<T::Output>), AsmRunError> {
+        Err(AsmRunError::Unsupported)
     }
 }
 
@@ -2,6 +2,8 @@ use std::ffi::c_void;
 
 use std::fmt::Debug;
 
+use crate::ASM_EXIT_STATUS_MASK;
+
 #[repr(C)]
 #[derive(Debug, Default)]
 pub struct AsmRHHeader {
@@ -18,11 +20,18 @@ impl AsmRHHeader {
             output_header = std::ptr::read(mapped_ptr as *const AsmRHHeader);
         }
 
-        assert!(output_header.mt_allocated_size > 0);
-        assert!(output_header.steps > 0);
-
         output_header
     }
+
+    /// Returns true if the emulation completed, whatever the guest exit code
+    pub fn completed(&self) -> bool {
+        self.exit_code & ASM_EXIT_STATUS_MASK == 0
+    }
+
+    /// Returns the exit code of the guest program, if the emulation completed
+    pub fn guest_exit_code(&self) -> u64 {
+        self.exit_code >> 8
+    }
 }
 
 #[repr(C)]
@@ -1,19 +1,17 @@
-use libc::{
-    close, ftruncate, mmap, munmap, shm_open, shm_unlink, MAP_SHARED, O_CREAT, PROT_READ,
-    PROT_WRITE, S_IRUSR, S_IWUSR, S_IXUSR,
-};
-
-use crate::AsmInputC;
-use named_sem::NamedSemaphore;
+use libc::{close, shm_unlink};
 
-use std::ffi::{c_uint, c_void, CString};
+use std::ffi::{c_void, CString};
+use std::fs;
 use std::path::Path;
 use std::process::{self, Command};
-use std::{fs, ptr};
 
-use log::{error, info};
+use log::info;
 
-use crate::{AsmRHData, AsmRHHeader, AsmRunnerOptions, AsmRunnerTraceLevel};
+use crate::asm_ipc_linux as ipc;
+use crate::{
+    AsmInputC, AsmRHData, AsmRHHeader, AsmRunError, AsmRunnerOptions, AsmRunnerTraceLevel,
+    AsmUserFcallServer,
+};
 
 // This struct is used to run the assembly code in a separate process and generate the ROM histogram.
 pub struct AsmRunnerRomH {
@@ -34,11 +32,9 @@ impl Drop for AsmRunnerRomH {
             // Unmap shared memory
             libc::munmap(self.mapped_ptr, self.total_size());
 
-            let shmem_output_name =
-                CString::new(self.shmem_output_name.clone()).expect("CString::new failed");
-            let shmem_output_name_ptr = shmem_output_name.as_ptr();
-
-            shm_unlink(shmem_output_name_ptr);
+            if let Ok(shmem_output_name) = CString::new(self.shmem_output_name.as_str()) {
+                shm_unlink(shmem_output_name.as_ptr());
+            }
         }
     }
 }
@@ -63,7 +59,12 @@ impl AsmRunnerRomH {
         inputs_path: Option<&Path>,
         shm_size: u64,
         options: AsmRunnerOptions,
-    ) -> AsmRunnerRomH {
+    ) -> Result<AsmRunnerRomH, AsmRunError> {
+        let inputs = match inputs_path {
+            Some(inputs_path) => fs::read(inputs_path).map_err(AsmRunError::Inputs)?,
+            None => vec![],
+        };
+
         let pid = unsafe { libc::getpid() };
 
         let shmem_prefix = format!("ZISKRH{}", pid);
@@ -73,20 +74,16 @@ impl AsmRunnerRomH {
         // Build semaphores names, and create them (if they don not already exist)
         let sem_output_name = format!("/{}_semout", shmem_prefix);
         let sem_input_name = format!("/{}_semin", shmem_prefix);
-        let mut semin = NamedSemaphore::create(sem_input_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerRomH::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_input_name, e
-            )
-        });
-        let mut semout = NamedSemaphore::create(sem_output_name.clone(), 0).unwrap_or_else(|e| {
-            panic!(
-                "AsmRunnerRomH::run() failed calling NamedSemaphore::create({}), error: {}",
-                sem_output_name, e
-            )
-        });
+        let mut semin = ipc::create_semaphore(&sem_input_name)?;
+        let mut semout = ipc::create_semaphore(&sem_output_name)?;
 
-        Self::write_input(inputs_path, &shmem_input_name, shm_size, 0);
+        let asm_input = AsmInputC {
+            chunk_size: 0,
+            max_steps: shm_size,
+            initial_trace_size: 1u64 << 32, // 4GB
+            input_data_size: inputs.len() as u64,
+        };
+        ipc::write_input(&shmem_input_name, &asm_input, &inputs)?;
 
         // Prepare command
         let mut command = Command::new(rom_asm_path);
@@ -117,148 +114,57 @@ impl AsmRunnerRomH {
             command.arg("-k");
         }
 
+        // Serve the user fcalls until the emulation completes
+        let _user_fcall_server = AsmUserFcallServer::start_for_command(
+            &mut command,
+            &shmem_prefix,
+            options.user_fcalls.as_ref(),
+        )?;
+
         // Spawn child process
-        if let Err(e) = command.arg(&shmem_prefix).spawn() {
-            error!("Child process failed: {:?}", e);
-        } else if options.verbose || options.log_output {
+        let mut child = command.arg(&shmem_prefix).spawn().map_err(AsmRunError::Spawn)?;
+        if options.verbose || options.log_output {
             info!("Child process launched successfully");
         }
 
         // Wait for the assembly emulator to complete writing the trace
-        if let Err(e) = semin.wait() {
-            panic!(
-                "AsmRunnerRomH::run() failed calling semin.wait({}), error: {}",
-                sem_input_name, e
-            );
-        }
-
-        let (mapped_ptr, asm_rowh_output) = Self::map_output(shmem_output_name.clone());
-
-        // Tell the assembly that we are done reading the trace
-        if let Err(e) = semout.post() {
-            panic!(
-                "AsmRunnerRomH::run() failed calling semout.post({}), error: {}",
-                sem_output_name, e
-            );
-        }
-
-        AsmRunnerRomH::new(shmem_output_name, mapped_ptr, asm_rowh_output)
-    }
-
-    fn write_input(
-        inputs_path: Option<&Path>,
-        shmem_input_name: &str,
-        max_steps: u64,
-        chunk_size: u64,
-    ) {
-        let shmem_input_name = CString::new(shmem_input_name).expect("CString::new failed");
-        let shmem_input_name_ptr = shmem_input_name.as_ptr();
-
-        let inputs = if let Some(inputs_path) = inputs_path {
-            fs::read(inputs_path).expect("Could not read inputs file")
-        } else {
-            vec![]
-        };
-
-        let asm_input = AsmInputC {
-            chunk_size,
-            max_steps,
-            initial_trace_size: 1u64 << 32, // 4GB
-            input_data_size: inputs.len() as u64,
-        };
-
-        // Shared memory size (aligned to 8 bytes)
-        let shmem_input_size = (inputs.len() + std::mem::size_of::<AsmInputC>() + 7) & !7;
-
-        let mut shmem_input_data = Vec::with_capacity(shmem_input_size);
-        shmem_input_data.extend_from_slice(&asm_input.to_bytes());
-        shmem_input_data.extend_from_slice(&inputs);
+        ipc::wait(&mut child, &mut semin, &sem_input_name)?;
 
-        // Remove old shared memory if it exists
-        unsafe { shm_unlink(shmem_input_name_ptr) };
+        let output = Self::map_output(&shmem_output_name);
 
-        let shm_fd = unsafe {
-            shm_open(
-                shmem_input_name_ptr,
-                libc::O_RDWR | O_CREAT,
-                (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint,
-            )
-        };
-        Self::check_shm_open(shm_fd, shmem_input_name_ptr);
-
-        if unsafe { ftruncate(shm_fd, shmem_input_size as i64) } < 0 {
-            panic!("ftruncate failed");
-        }
+        // Tell the assembly that we are done reading the trace, even if it failed
+        ipc::post(&mut semout, &sem_output_name)?;
 
-        let mapped_ptr = unsafe {
-            mmap(
-                std::ptr::null_mut(),
-                shmem_input_size,
-                PROT_READ | PROT_WRITE,
-                MAP_SHARED,
-                shm_fd,
-                0,
-            )
-        };
-        Self::check_mmap(mapped_ptr, shmem_input_size, file!(), line!());
-
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                shmem_input_data.as_ptr(),
-                mapped_ptr as *mut u8,
-                shmem_input_size,
-            );
-
-            munmap(mapped_ptr, shmem_input_size);
-            close(shm_fd);
-        }
+        let (mapped_ptr, asm_rowh_output) = output?;
+        Ok(AsmRunnerRomH::new(shmem_output_name, mapped_ptr, asm_rowh_output))
     }
 
-    fn map_output(shmem_output_name: String) -> (*mut c_void, AsmRHData) {
-        let shmem_output_name = CString::new(shmem_output_name).expect("CString::new failed");
-        let shmem_output_name_ptr = shmem_output_name.as_ptr();
+    fn map_output(shmem_output_name: &str) -> Result<(*mut c_void, AsmRHData), AsmRunError> {
+        let shm_fd = ipc::open_output(shmem_output_name)?;
 
-        let shm_fd = unsafe {
-            shm_open(shmem_output_name_ptr, libc::O_RDONLY, (S_IRUSR | S_IWUSR | S_IXUSR) as c_uint)
-        };
-
-        Self::check_shm_open(shm_fd, shmem_output_name_ptr);
-
-        // Read Output Header
+        // Read the output header to know the allocated size, and map it with the histogram,
+        // which outlives the file descriptor
         let output_header_size = size_of::<AsmRHHeader>();
-        let mapped_ptr =
-            unsafe { mmap(ptr::null_mut(), output_header_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-        Self::check_mmap(mapped_ptr, output_header_size, file!(), line!());
-
-        let output_header = AsmRHHeader::from_ptr(mapped_ptr);
-
-        // Read Output
-        let output_size = output_header_size + output_header.mt_allocated_size as usize;
-
-        let mut mapped_ptr =
-            unsafe { mmap(ptr::null_mut(), output_size, PROT_READ, MAP_SHARED, shm_fd, 0) };
-        Self::check_mmap(mapped_ptr, output_size, file!(), line!());
-
-        // println!("Output Header: {:?}", output_header);
+        let output =
+            ipc::read_header::<AsmRHHeader>(shm_fd, shmem_output_name).and_then(|header| {
+                // The histogram starts with the BIOS and program counters lengths
+                if header.mt_allocated_size < 16 {
+                    return Err(AsmRunError::InvalidOutput(format!(
+                        "{} allocated bytes are too few to hold the ROM histogram",
+                        header.mt_allocated_size
+                    )));
+                }
+                let output_size = output_header_size + header.mt_allocated_size as usize;
+                ipc::map_output(shm_fd, shmem_output_name, output_size)
+                    .map(|mapped_ptr| (mapped_ptr, header))
+            });
+        unsafe { close(shm_fd) };
+        let (mut mapped_ptr, output_header) = output?;
 
         unsafe {
             mapped_ptr = mapped_ptr.add(output_header_size);
 
-            (mapped_ptr, AsmRHData::from_ptr(&mut mapped_ptr, output_header))
-        }
-    }
-
-    fn check_shm_open(shm_fd: i32, name: *const i8) {
-        if shm_fd == -1 {
-            let err = std::io::Error::last_os_error();
-            panic!("shm_open({:?}) failed: {:?}", name, err);
-        }
-    }
-
-    fn check_mmap(ptr: *mut libc::c_void, size: usize, file: &str, line: u32) {
-        if ptr == libc::MAP_FAILED {
-            let err = std::io::Error::last_os_error();
-            panic!("mmap failed: {:?} (size: {} bytes) at {}:{}", err, size, file, line);
+            Ok((mapped_ptr, AsmRHData::from_ptr(&mut mapped_ptr, output_header)))
         }
     }
 }
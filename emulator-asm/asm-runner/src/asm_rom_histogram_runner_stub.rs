@@ -1,7 +1,7 @@
 use std::ffi::c_void;
 use std::path::Path;
 
-use crate::{AsmRHData, AsmRunnerOptions};
+use crate::{AsmRHData, AsmRunError, AsmRunnerOptions};
 
 #[derive(Debug)]
 pub struct AsmRunnerRomH {
@@ -27,9 +27,7 @@ impl AsmRunnerRomH {
         _inputs_path: Option<&Path>,
         _shm_size: u64,
         _options: AsmRunnerOptions,
-    ) -> AsmRunnerRomH {
-        panic!(
-            "AsmRunnerRomH::run() is not supported on this platform. Only Linux x86_64 is supported."
-        );
+    ) -> Result<AsmRunnerRomH, AsmRunError> {
+        Err(AsmRunError::Unsupported)
     }
 }
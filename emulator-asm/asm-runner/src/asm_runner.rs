@@ -1,4 +1,23 @@
-use std::process::Command;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus};
+use std::sync::Arc;
+use std::time::Duration;
+
+use zisk_core::{ResourceLimit, ResourceLimitExceeded, UserFcalls};
+
+/// Mask of the emulation status in the `exit_code` field of the output headers: 0 means that the
+/// emulation completed, 1 that it did not, `ASM_EXIT_STATUS_STEPS_LIMIT` that it was stopped after
+/// reaching the maximum number of steps and `ASM_EXIT_STATUS_PRECOMPILE_ERROR` that it was stopped
+/// after a failed precompiled call.  The upper bits contain the guest exit code.
+pub const ASM_EXIT_STATUS_MASK: u64 = 0xff;
+
+/// Emulation status of an emulation stopped after reaching the maximum number of steps
+pub const ASM_EXIT_STATUS_STEPS_LIMIT: u64 = 2;
+
+/// Emulation status of an emulation stopped after a precompiled call failed, e.g. because its
+/// input point had no result
+pub const ASM_EXIT_STATUS_PRECOMPILE_ERROR: u64 = 3;
 
 pub enum AsmRunnerTraceLevel {
     None,
@@ -12,6 +31,12 @@ pub struct AsmRunnerOptions {
     pub verbose: bool,
     pub trace_level: AsmRunnerTraceLevel,
     pub keccak_trace: bool,
+    /// File to redirect the guest standard output to, if any
+    pub guest_stdout: Option<PathBuf>,
+    /// File to redirect the guest standard error to, if any
+    pub guest_stderr: Option<PathBuf>,
+    /// Host functions serving the user-defined fcalls of the guest, if any
+    pub user_fcalls: Option<Arc<UserFcalls>>,
 }
 
 impl Default for AsmRunnerOptions {
@@ -22,6 +47,9 @@ impl Default for AsmRunnerOptions {
             verbose: false,
             trace_level: AsmRunnerTraceLevel::None,
             keccak_trace: false,
+            guest_stdout: None,
+            guest_stderr: None,
+            user_fcalls: None,
         }
     }
 }
@@ -53,6 +81,12 @@ impl AsmRunnerOptions {
         if self.keccak_trace {
             command.arg("-k");
         }
+        if let Some(guest_stdout) = &self.guest_stdout {
+            command.arg("--guest-stdout").arg(guest_stdout);
+        }
+        if let Some(guest_stderr) = &self.guest_stderr {
+            command.arg("--guest-stderr").arg(guest_stderr);
+        }
     }
 }
 
@@ -63,6 +97,9 @@ pub struct AsmRunnerOptionsBuilder {
     verbose: bool,
     trace_level: AsmRunnerTraceLevel,
     keccak_trace: bool,
+    guest_stdout: Option<PathBuf>,
+    guest_stderr: Option<PathBuf>,
+    user_fcalls: Option<Arc<UserFcalls>>,
 }
 
 impl Default for AsmRunnerOptionsBuilder {
@@ -80,6 +117,9 @@ impl AsmRunnerOptionsBuilder {
             verbose: false,
             trace_level: AsmRunnerTraceLevel::None,
             keccak_trace: false,
+            guest_stdout: None,
+            guest_stderr: None,
+            user_fcalls: None,
         }
     }
 
@@ -128,6 +168,24 @@ impl AsmRunnerOptionsBuilder {
         self
     }
 
+    /// Redirects the guest standard output to a file.
+    pub fn guest_stdout(mut self, path: impl Into<PathBuf>) -> Self {
+        self.guest_stdout = Some(path.into());
+        self
+    }
+
+    /// Redirects the guest standard error to a file.
+    pub fn guest_stderr(mut self, path: impl Into<PathBuf>) -> Self {
+        self.guest_stderr = Some(path.into());
+        self
+    }
+
+    /// Serves the user-defined fcalls of the guest with the registered host functions.
+    pub fn user_fcalls(mut self, user_fcalls: Arc<UserFcalls>) -> Self {
+        self.user_fcalls = Some(user_fcalls);
+        self
+    }
+
     /// Builds the configured `AsmRunnerOptions`.
     pub fn build(self) -> AsmRunnerOptions {
         AsmRunnerOptions {
@@ -136,6 +194,117 @@ impl AsmRunnerOptionsBuilder {
             verbose: self.verbose,
             trace_level: self.trace_level,
             keccak_trace: self.keccak_trace,
+            guest_stdout: self.guest_stdout,
+            guest_stderr: self.guest_stderr,
+            user_fcalls: self.user_fcalls,
+        }
+    }
+}
+
+/// Errors returned by the assembly emulator runners that propagate failures to the caller instead
+/// of panicking.
+#[derive(Debug)]
+pub enum AsmRunError {
+    /// The assembly emulator is not available on this platform.
+    Unsupported,
+    /// The `ziskemuasm` process could not be spawned.
+    Spawn(std::io::Error),
+    /// The inputs could not be read.
+    Inputs(std::io::Error),
+    /// A named semaphore operation failed.
+    Semaphore { name: String, reason: String },
+    /// A shared memory operation (`shm_open`, `ftruncate`, `mmap`) failed.
+    SharedMemory { name: String, source: std::io::Error },
+    /// The `ziskemuasm` process exited before completing the request.
+    ProcessExited(Option<ExitStatus>),
+    /// The `ziskemuasm` process did not generate a new chunk in time.
+    ChunkTimeout(Duration),
+    /// The output written by `ziskemuasm` is not consistent.
+    InvalidOutput(String),
+    /// A request asked for zero steps, which is reserved to shut an `AsmServerMT` down.
+    ZeroMaxSteps,
+    /// The thread pool running the chunk tasks could not be built.
+    ThreadPool(rayon::ThreadPoolBuildError),
+    /// The emulation completed with a non-zero exit code in the output header.
+    Emulation(u64),
+    /// The emulation was stopped because a precompiled call failed.
+    Precompile,
+    /// The guest program exceeded one of its resource limits.
+    ResourceLimitExceeded(ResourceLimitExceeded),
+}
+
+impl AsmRunError {
+    /// Returns the error reported by the `exit_code` field of an output header, if any, given the
+    /// maximum number of steps of the emulation
+    pub fn from_exit_code(exit_code: u64, max_steps: u64) -> Option<AsmRunError> {
+        match exit_code & ASM_EXIT_STATUS_MASK {
+            0 => None,
+            ASM_EXIT_STATUS_STEPS_LIMIT => {
+                Some(AsmRunError::ResourceLimitExceeded(ResourceLimitExceeded {
+                    limit: ResourceLimit::Steps,
+                    used: max_steps,
+                    max: max_steps,
+                }))
+            }
+            ASM_EXIT_STATUS_PRECOMPILE_ERROR => Some(AsmRunError::Precompile),
+            _ => Some(AsmRunError::Emulation(exit_code)),
         }
     }
 }
+
+impl fmt::Display for AsmRunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsmRunError::Unsupported => {
+                write!(f, "assembly emulator is only supported on Linux x86_64")
+            }
+            AsmRunError::Spawn(e) => write!(f, "failed to spawn ziskemuasm: {}", e),
+            AsmRunError::Inputs(e) => write!(f, "failed to read inputs: {}", e),
+            AsmRunError::Semaphore { name, reason } => {
+                write!(f, "semaphore {} failed: {}", name, reason)
+            }
+            AsmRunError::SharedMemory { name, source } => {
+                write!(f, "shared memory {} failed: {}", name, source)
+            }
+            AsmRunError::ProcessExited(Some(status)) => {
+                write!(f, "ziskemuasm exited before completing the request: {}", status)
+            }
+            AsmRunError::ProcessExited(None) => {
+                write!(f, "ziskemuasm exited before completing the request")
+            }
+            AsmRunError::ChunkTimeout(timeout) => {
+                write!(f, "ziskemuasm did not generate a chunk in {:?}", timeout)
+            }
+            AsmRunError::InvalidOutput(reason) => {
+                write!(f, "invalid ziskemuasm output: {}", reason)
+            }
+            AsmRunError::ZeroMaxSteps => write!(f, "max_steps must be greater than zero"),
+            AsmRunError::ThreadPool(e) => write!(f, "failed to build the chunk thread pool: {}", e),
+            AsmRunError::Emulation(exit_code) => {
+                write!(f, "assembly emulation failed with exit code {}", exit_code)
+            }
+            AsmRunError::Precompile => {
+                write!(f, "assembly emulation stopped after a failed precompiled call")
+            }
+            AsmRunError::ResourceLimitExceeded(e) => write!(f, "guest program {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AsmRunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AsmRunError::Spawn(e) | AsmRunError::Inputs(e) => Some(e),
+            AsmRunError::SharedMemory { source, .. } => Some(source),
+            AsmRunError::ResourceLimitExceeded(e) => Some(e),
+            AsmRunError::ThreadPool(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ResourceLimitExceeded> for AsmRunError {
+    fn from(e: ResourceLimitExceeded) -> Self {
+        AsmRunError::ResourceLimitExceeded(e)
+    }
+}
@@ -0,0 +1,253 @@
+use named_sem::NamedSemaphore;
+
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+use std::process::{self, Child, Command};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use zisk_core::ResourceLimits;
+
+use crate::asm_ipc_linux::{self as ipc, POLL_INTERVAL};
+use crate::{AsmInputC, AsmRunError, AsmRunnerMT, AsmRunnerOptions, AsmUserFcallServer};
+
+/// Time given to the server process to exit after a shutdown request before killing it.
+const SERVER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Initial size of the output shared memory, grown by the server when needed.
+const SERVER_INITIAL_TRACE_SIZE: u64 = 1 << 32; // 4GB
+
+// Distinguishes the shared memory names of several servers living in the same process.
+static SERVER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Long-lived assembly emulator that generates minimal traces for many inputs.
+///
+/// `AsmRunnerMT::run` spawns `ziskemuasm` for every execution, whereas `AsmServerMT` starts it
+/// once in `--server` mode, so the ROM stays loaded, and reuses the same shared memory regions
+/// and semaphores for every request:
+///
+/// 1. The caller writes the input shared memory and posts `_semreq`.
+/// 2. The server emulates the input, posting `_semckd` for every chunk, and posts `_semin`.
+/// 3. The caller maps the output and posts `_semout`; the server unlinks the output and waits
+///    for the next request.
+///
+/// A request with `max_steps = 0` shuts the server down.
+pub struct AsmServerMT {
+    child: Child,
+    shmem_input_name: String,
+    shmem_output_name: String,
+    sem_request: NamedSemaphore,
+    sem_in: NamedSemaphore,
+    sem_out: NamedSemaphore,
+    sem_chunk_done: NamedSemaphore,
+    sem_names: [String; 4],
+    /// Serves the user fcalls of every request; dropped after the server process exits
+    _user_fcall_server: Option<AsmUserFcallServer>,
+}
+
+// Requests are serialized by `&mut self`, so the server can be moved to and shared behind a lock
+// with the executor threads
+unsafe impl Send for AsmServerMT {}
+
+impl Debug for AsmServerMT {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsmServerMT")
+            .field("pid", &self.child.id())
+            .field("shmem_input_name", &self.shmem_input_name)
+            .field("shmem_output_name", &self.shmem_output_name)
+            .finish()
+    }
+}
+
+impl AsmServerMT {
+    /// Spawns `ziskemuasm` in server mode.
+    ///
+    /// # Arguments
+    /// * `ziskemuasm_path` - Path to the assembly emulator binary, generated for minimal traces.
+    /// * `options` - Runner options, applied to the server process for its whole lifetime.
+    pub fn start(ziskemuasm_path: &Path, options: AsmRunnerOptions) -> Result<Self, AsmRunError> {
+        let pid = unsafe { libc::getpid() };
+        let id = SERVER_ID.fetch_add(1, Ordering::Relaxed);
+
+        let shmem_prefix = format!("ZISKMT{}S{}", pid, id);
+        let shmem_input_name = format!("/{}_input", shmem_prefix);
+        let shmem_output_name = format!("/{}_output", shmem_prefix);
+
+        let sem_names = [
+            format!("/{}_semreq", shmem_prefix),
+            format!("/{}_semin", shmem_prefix),
+            format!("/{}_semout", shmem_prefix),
+            format!("/{}_semckd", shmem_prefix),
+        ];
+        let sem_request = ipc::create_semaphore(&sem_names[0])?;
+        let sem_in = ipc::create_semaphore(&sem_names[1])?;
+        let sem_out = ipc::create_semaphore(&sem_names[2])?;
+        let sem_chunk_done = ipc::create_semaphore(&sem_names[3])?;
+
+        let mut command = Command::new(ziskemuasm_path);
+        command.arg("--generate_minimal_trace").arg("--server");
+        options.apply_to_command(&mut command);
+        if !options.log_output {
+            command.stdout(process::Stdio::null());
+            command.stderr(process::Stdio::null());
+        }
+
+        let user_fcall_server = match AsmUserFcallServer::start_for_command(
+            &mut command,
+            &shmem_prefix,
+            options.user_fcalls.as_ref(),
+        ) {
+            Ok(user_fcall_server) => user_fcall_server,
+            Err(e) => {
+                ipc::unlink(&[&shmem_input_name, &shmem_output_name], &sem_names);
+                return Err(e);
+            }
+        };
+
+        let child = match command.arg(&shmem_prefix).spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                ipc::unlink(&[&shmem_input_name, &shmem_output_name], &sem_names);
+                return Err(AsmRunError::Spawn(e));
+            }
+        };
+        if options.verbose || options.log_output {
+            info!("AsmServerMT: server process {} launched successfully", child.id());
+        }
+
+        Ok(AsmServerMT {
+            child,
+            shmem_input_name,
+            shmem_output_name,
+            sem_request,
+            sem_in,
+            sem_out,
+            sem_chunk_done,
+            sem_names,
+            _user_fcall_server: user_fcall_server,
+        })
+    }
+
+    /// Runs the inputs stored in `inputs_path` and returns their minimal traces.
+    pub fn run(
+        &mut self,
+        inputs_path: &Path,
+        limits: &ResourceLimits,
+        chunk_size: u64,
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        let inputs = fs::read(inputs_path).map_err(AsmRunError::Inputs)?;
+        self.run_inputs(&inputs, limits, chunk_size)
+    }
+
+    /// Runs `inputs` and returns their minimal traces.
+    ///
+    /// The returned traces live in their own mapping, so they remain valid while the server
+    /// keeps processing further requests.  The server only stops at the steps limit; the rest of
+    /// the limits are checked once the emulation completes.
+    pub fn run_inputs(
+        &mut self,
+        inputs: &[u8],
+        limits: &ResourceLimits,
+        chunk_size: u64,
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        let max_steps = limits.max_steps;
+        if max_steps == 0 {
+            return Err(AsmRunError::ZeroMaxSteps);
+        }
+        limits.check_input_size(inputs.len() as u64)?;
+
+        self.write_input(inputs, max_steps, chunk_size)?;
+
+        let start = Instant::now();
+        ipc::post(&mut self.sem_request, &self.sem_names[0])?;
+        ipc::wait(&mut self.child, &mut self.sem_in, &self.sem_names[1])?;
+        let stop = start.elapsed();
+
+        // Chunks are not consumed while they are generated, so discard their notifications
+        // before the next request
+        while self.sem_chunk_done.try_wait().is_ok() {}
+
+        let output = AsmRunnerMT::map_output(&self.shmem_output_name);
+
+        // Let the server unlink the output and wait for the next request, even if it failed
+        ipc::post(&mut self.sem_out, &self.sem_names[2])?;
+
+        let output = output?;
+        let asm_runner_mt = AsmRunnerMT::from_mapping(
+            output.mapped_ptr,
+            output.mapped_size,
+            output.exit_code,
+            output.vec_chunks,
+        );
+        AsmRunnerMT::check_output(output.exit_code, &output.resource_usage, limits)?;
+
+        let total_steps = asm_runner_mt.vec_chunks.iter().map(|x| x.steps).sum::<u64>();
+        let mhz = (total_steps as f64 / stop.as_secs_f64()) / 1_000_000.0;
+        info!("AsmServerMT: ··· Assembly execution speed: {:.2} MHz", mhz);
+
+        Ok(asm_runner_mt)
+    }
+
+    /// Asks the server to exit and waits for it.
+    pub fn shutdown(mut self) -> Result<(), AsmRunError> {
+        self.request_shutdown()?;
+        self.child.wait().map(|_| ()).map_err(|_| AsmRunError::ProcessExited(None))
+    }
+
+    fn request_shutdown(&mut self) -> Result<(), AsmRunError> {
+        if let Ok(Some(status)) = self.child.try_wait() {
+            return Err(AsmRunError::ProcessExited(Some(status)));
+        }
+        self.write_input(&[], 0, 0)?;
+        ipc::post(&mut self.sem_request, &self.sem_names[0])
+    }
+
+    fn write_input(
+        &self,
+        inputs: &[u8],
+        max_steps: u64,
+        chunk_size: u64,
+    ) -> Result<(), AsmRunError> {
+        let asm_input = AsmInputC {
+            chunk_size,
+            max_steps,
+            initial_trace_size: SERVER_INITIAL_TRACE_SIZE,
+            input_data_size: inputs.len() as u64,
+        };
+        ipc::write_input(&self.shmem_input_name, &asm_input, inputs)
+    }
+
+    fn unlink(&self) {
+        ipc::unlink(&[&self.shmem_input_name, &self.shmem_output_name], &self.sem_names);
+    }
+}
+
+impl Drop for AsmServerMT {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            // Ask the server to exit, and kill it if it does not
+            let mut exited = false;
+            if self.request_shutdown().is_ok() {
+                let start = Instant::now();
+                while start.elapsed() < SERVER_SHUTDOWN_TIMEOUT {
+                    if let Ok(Some(_)) = self.child.try_wait() {
+                        exited = true;
+                        break;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+            if !exited {
+                warn!("AsmServerMT: killing server process {}", self.child.id());
+                let _ = self.child.kill();
+                let _ = self.child.wait();
+            }
+        }
+
+        // A server that crashed or was killed does not clean up its shared memory and
+        // semaphores; unlinking names that no longer exist is harmless
+        self.unlink();
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use zisk_core::ResourceLimits;
+
+use crate::{AsmRunError, AsmRunnerMT, AsmRunnerOptions};
+
+#[derive(Debug)]
+pub struct AsmServerMT;
+
+impl AsmServerMT {
+    pub fn start(_ziskemuasm_path: &Path, _options: AsmRunnerOptions) -> Result<Self, AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+
+    pub fn run(
+        &mut self,
+        _inputs_path: &Path,
+        _limits: &ResourceLimits,
+        _chunk_size: u64,
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+
+    pub fn run_inputs(
+        &mut self,
+        _inputs: &[u8],
+        _limits: &ResourceLimits,
+        _chunk_size: u64,
+    ) -> Result<AsmRunnerMT, AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+
+    pub fn shutdown(self) -> Result<(), AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+}
@@ -0,0 +1,392 @@
+use libc::{
+    c_int, close, ftruncate, mmap, munmap, shm_open, MAP_SHARED, O_CREAT, PROT_READ, PROT_WRITE,
+    S_IRUSR, S_IWUSR,
+};
+
+use std::ffi::{c_uint, c_void};
+use std::fmt::Debug;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::{io, ptr};
+
+use log::error;
+use zisk_core::{UserFcalls, FCALL_RESULT_MIN_SIZE};
+
+use crate::asm_ipc_linux as ipc;
+use crate::AsmRunError;
+
+/// Interval used to check whether the server has been stopped while waiting for a call.
+const FCALL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Result size written back to the assembly emulator when a user fcall fails.
+const FCALL_RESULT_SIZE_ERROR: u64 = u64::MAX;
+
+/// Header of the fcall shared memory, followed by `data_size` words holding the parameters of the
+/// call, written by the assembly emulator, or its result, written by the server.  Any of them can
+/// grow the shared memory when the data does not fit, updating `data_size`, and the other one maps
+/// it again.
+#[repr(C)]
+struct AsmFcallHeader {
+    function_id: u64,
+    params_size: u64,
+    result_size: u64,
+    data_size: u64,
+}
+
+/// Mapping of the fcall shared memory, only accessed by the server thread.
+struct FcallMapping {
+    name: String,
+    fd: c_int,
+    ptr: *mut AsmFcallHeader,
+    data_size: usize,
+}
+
+unsafe impl Send for FcallMapping {}
+
+impl FcallMapping {
+    fn create(name: &str) -> Result<Self, AsmRunError> {
+        let c_name = ipc::c_name(name)?;
+        let fd = unsafe {
+            shm_open(c_name.as_ptr(), libc::O_RDWR | O_CREAT, (S_IRUSR | S_IWUSR) as c_uint)
+        };
+        if fd == -1 {
+            return Err(Self::error(name));
+        }
+
+        let mut mapping =
+            FcallMapping { name: name.to_string(), fd, ptr: ptr::null_mut(), data_size: 0 };
+        mapping.grow(FCALL_RESULT_MIN_SIZE)?;
+        Ok(mapping)
+    }
+
+    fn header(&mut self) -> &mut AsmFcallHeader {
+        // SAFETY: the header is always mapped
+        unsafe { &mut *self.ptr }
+    }
+
+    fn data(&mut self) -> &mut [u64] {
+        // SAFETY: `data_size` words are mapped after the header
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.add(1) as *mut u64, self.data_size) }
+    }
+
+    /// Maps the data words written by the assembly emulator, which could have grown them
+    fn sync(&mut self) -> Result<(), AsmRunError> {
+        let data_size = self.header().data_size as usize;
+        if data_size > self.data_size {
+            self.map(data_size)?;
+        }
+        Ok(())
+    }
+
+    /// Grows the shared memory to hold at least `data_size` words
+    fn grow(&mut self, data_size: usize) -> Result<(), AsmRunError> {
+        let size = size_of::<AsmFcallHeader>() + data_size * size_of::<u64>();
+        if unsafe { ftruncate(self.fd, size as i64) } < 0 {
+            return Err(Self::error(&self.name));
+        }
+        self.map(data_size)?;
+        self.header().data_size = data_size as u64;
+        Ok(())
+    }
+
+    fn map(&mut self, data_size: usize) -> Result<(), AsmRunError> {
+        self.unmap();
+        let size = size_of::<AsmFcallHeader>() + data_size * size_of::<u64>();
+        let mapped_ptr =
+            unsafe { mmap(ptr::null_mut(), size, PROT_READ | PROT_WRITE, MAP_SHARED, self.fd, 0) };
+        if mapped_ptr == libc::MAP_FAILED {
+            return Err(Self::error(&self.name));
+        }
+        self.ptr = mapped_ptr as *mut AsmFcallHeader;
+        self.data_size = data_size;
+        Ok(())
+    }
+
+    fn unmap(&mut self) {
+        if !self.ptr.is_null() {
+            let size = size_of::<AsmFcallHeader>() + self.data_size * size_of::<u64>();
+            unsafe { munmap(self.ptr as *mut c_void, size) };
+            self.ptr = ptr::null_mut();
+        }
+    }
+
+    fn error(name: &str) -> AsmRunError {
+        AsmRunError::SharedMemory { name: name.to_string(), source: io::Error::last_os_error() }
+    }
+}
+
+impl Drop for FcallMapping {
+    fn drop(&mut self) {
+        self.unmap();
+        unsafe { close(self.fd) };
+    }
+}
+
+/// Serves the user-defined fcalls of an assembly emulator started with `--user-fcalls`.
+///
+/// For every user fcall, the assembly emulator copies its id and parameters to the `_fcall` shared
+/// memory and posts `_semfcr`; a server thread calls the registered function, writes its result
+/// back and posts `_semfcd`.  Parameters and results can have any size, since both sides grow the
+/// shared memory on demand.  A failed call is reported with an invalid result size, which makes
+/// the assembly emulator exit with an error.
+///
+/// The server must be started before spawning the assembly emulator, which maps the existing
+/// shared memory, and kept alive until the emulator exits.
+pub struct AsmUserFcallServer {
+    shmem_fcall_name: String,
+    sem_names: [String; 2],
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Debug for AsmUserFcallServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsmUserFcallServer")
+            .field("shmem_fcall_name", &self.shmem_fcall_name)
+            .finish()
+    }
+}
+
+impl AsmUserFcallServer {
+    /// Creates the fcall shared memory and semaphores of the assembly emulator using
+    /// `shmem_prefix`, and starts serving `user_fcalls`.
+    pub fn start(shmem_prefix: &str, user_fcalls: Arc<UserFcalls>) -> Result<Self, AsmRunError> {
+        let shmem_fcall_name = format!("/{}_fcall", shmem_prefix);
+        let sem_names = [format!("/{}_semfcr", shmem_prefix), format!("/{}_semfcd", shmem_prefix)];
+
+        // Semaphores left behind by a previous run could hold stale notifications
+        Self::unlink(&shmem_fcall_name, &sem_names);
+
+        let mapping = FcallMapping::create(&shmem_fcall_name);
+        let sem_request = ipc::create_semaphore(&sem_names[0]);
+        let sem_done = ipc::create_semaphore(&sem_names[1]);
+        let (mut mapping, mut sem_request, mut sem_done) = match (mapping, sem_request, sem_done) {
+            (Ok(mapping), Ok(sem_request), Ok(sem_done)) => (mapping, sem_request, sem_done),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                Self::unlink(&shmem_fcall_name, &sem_names);
+                return Err(e);
+            }
+        };
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    if sem_request.timed_wait(FCALL_POLL_INTERVAL).is_err() {
+                        continue;
+                    }
+                    // The emulator does not access the shared memory until `sem_done` is posted
+                    Self::serve(&mut mapping, &user_fcalls);
+                    if let Err(e) = sem_done.post() {
+                        error!("AsmUserFcallServer: failed to post the fcall result: {}", e);
+                    }
+                }
+            })
+        };
+
+        Ok(AsmUserFcallServer { shmem_fcall_name, sem_names, stop, thread: Some(thread) })
+    }
+
+    /// Starts serving `user_fcalls`, if any, and enables them in the assembly emulator command.
+    pub(crate) fn start_for_command(
+        command: &mut Command,
+        shmem_prefix: &str,
+        user_fcalls: Option<&Arc<UserFcalls>>,
+    ) -> Result<Option<Self>, AsmRunError> {
+        let Some(user_fcalls) = user_fcalls else {
+            return Ok(None);
+        };
+        command.arg("--user-fcalls");
+        Self::start(shmem_prefix, user_fcalls.clone()).map(Some)
+    }
+
+    fn serve(mapping: &mut FcallMapping, user_fcalls: &UserFcalls) {
+        let function_id = mapping.header().function_id;
+        let result = Self::call(mapping, function_id, user_fcalls).and_then(|result| {
+            if result.len() > mapping.data_size {
+                mapping.grow(result.len()).map_err(|e| e.to_string())?;
+            }
+            mapping.data()[..result.len()].copy_from_slice(&result);
+            Ok(result.len() as u64)
+        });
+        mapping.header().result_size = result.unwrap_or_else(|e| {
+            error!("AsmUserFcallServer: user fcall {} failed: {}", function_id, e);
+            FCALL_RESULT_SIZE_ERROR
+        });
+    }
+
+    fn call(
+        mapping: &mut FcallMapping,
+        function_id: u64,
+        user_fcalls: &UserFcalls,
+    ) -> Result<Vec<u64>, String> {
+        mapping.sync().map_err(|e| e.to_string())?;
+        let params_size = mapping.header().params_size as usize;
+        let params = mapping.data().get(..params_size).ok_or("invalid parameters size")?;
+        user_fcalls.call(function_id, params)
+    }
+
+    fn unlink(shmem_name: &str, sem_names: &[String]) {
+        ipc::unlink(&[shmem_name], sem_names);
+    }
+}
+
+impl Drop for AsmUserFcallServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        Self::unlink(&self.shmem_fcall_name, &self.sem_names);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_core::FCALL_USER_ID_FIRST;
+
+    /// Assembly emulator side of the fcall shared memory, growing and mapping it as `UserFcall()`
+    /// does in main.c
+    struct EmulatorMapping {
+        fd: c_int,
+        ptr: *mut AsmFcallHeader,
+        mapped_size: usize,
+    }
+
+    impl EmulatorMapping {
+        fn open(name: &str) -> Self {
+            let c_name = ipc::c_name(name).unwrap();
+            let fd = unsafe { shm_open(c_name.as_ptr(), libc::O_RDWR, 0) };
+            assert_ne!(fd, -1, "shm_open({}) failed", name);
+            let mut mapping = EmulatorMapping { fd, ptr: ptr::null_mut(), mapped_size: 0 };
+            mapping.map(0);
+            let data_size = mapping.header().data_size as usize;
+            mapping.map(data_size);
+            mapping
+        }
+
+        fn header(&mut self) -> &mut AsmFcallHeader {
+            unsafe { &mut *self.ptr }
+        }
+
+        fn data(&mut self, size: usize) -> &mut [u64] {
+            assert!(size <= self.mapped_size);
+            unsafe { std::slice::from_raw_parts_mut(self.ptr.add(1) as *mut u64, size) }
+        }
+
+        fn map(&mut self, data_size: usize) {
+            if !self.ptr.is_null() {
+                if data_size <= self.mapped_size {
+                    return;
+                }
+                self.unmap();
+            }
+            let size = size_of::<AsmFcallHeader>() + data_size * size_of::<u64>();
+            let mapped_ptr = unsafe {
+                mmap(ptr::null_mut(), size, PROT_READ | PROT_WRITE, MAP_SHARED, self.fd, 0)
+            };
+            assert_ne!(mapped_ptr, libc::MAP_FAILED);
+            self.ptr = mapped_ptr as *mut AsmFcallHeader;
+            self.mapped_size = data_size;
+        }
+
+        fn unmap(&mut self) {
+            let size = size_of::<AsmFcallHeader>() + self.mapped_size * size_of::<u64>();
+            unsafe { munmap(self.ptr as *mut c_void, size) };
+            self.ptr = ptr::null_mut();
+        }
+
+        /// Sends a call to the server mapping, serving it in place of the server thread, and
+        /// returns its result, or None if the call failed
+        fn call(
+            &mut self,
+            server: &mut FcallMapping,
+            user_fcalls: &UserFcalls,
+            function_id: u64,
+            params: &[u64],
+        ) -> Option<Vec<u64>> {
+            if params.len() as u64 > self.header().data_size {
+                let size = size_of::<AsmFcallHeader>() + params.len() * size_of::<u64>();
+                assert_eq!(unsafe { ftruncate(self.fd, size as i64) }, 0);
+                self.map(params.len());
+                self.header().data_size = params.len() as u64;
+            }
+            self.header().function_id = function_id;
+            self.header().params_size = params.len() as u64;
+            self.data(params.len()).copy_from_slice(params);
+
+            AsmUserFcallServer::serve(server, user_fcalls);
+
+            let result_size = self.header().result_size;
+            if result_size > self.header().data_size {
+                return None;
+            }
+            let data_size = self.header().data_size as usize;
+            self.map(data_size);
+            Some(self.data(result_size as usize).to_vec())
+        }
+    }
+
+    impl Drop for EmulatorMapping {
+        fn drop(&mut self) {
+            self.unmap();
+            unsafe { close(self.fd) };
+        }
+    }
+
+    #[test]
+    fn test_user_fcall_large_params_and_result() {
+        let name = format!("/zisk_test_{}_fcall", std::process::id());
+        let mut user_fcalls = UserFcalls::new();
+        // Returns `params[0]` words, each one the sum of the parameters plus its index
+        user_fcalls.register(FCALL_USER_ID_FIRST, |params| {
+            let sum = params.iter().sum::<u64>();
+            Ok((0..params[0]).map(|i| sum + i).collect())
+        });
+        let expected = |params: &[u64]| -> Vec<u64> {
+            let sum = params.iter().sum::<u64>();
+            (0..params[0]).map(|i| sum + i).collect()
+        };
+
+        let mut server = FcallMapping::create(&name).unwrap();
+        let mut emulator = EmulatorMapping::open(&name);
+        let id = FCALL_USER_ID_FIRST as u64;
+
+        // A small call fits in the initial shared memory
+        let params = [4, 1, 2];
+        assert_eq!(emulator.call(&mut server, &user_fcalls, id, &params), Some(expected(&params)));
+        assert_eq!(server.data_size, FCALL_RESULT_MIN_SIZE);
+
+        // A 4 KB result makes the server grow the shared memory, and the emulator remap it
+        let params = [512, 7];
+        let result = emulator.call(&mut server, &user_fcalls, id, &params).unwrap();
+        assert_eq!(result, expected(&params));
+        assert_eq!(server.data_size, 512);
+
+        // Parameters larger than the shared memory make the emulator grow it, and the server
+        // remap it, before a 64 KB result grows it again
+        let mut params = vec![8192];
+        params.extend(1..1000u64);
+        let result = emulator.call(&mut server, &user_fcalls, id, &params).unwrap();
+        assert_eq!(result.len(), 8192);
+        assert_eq!(result, expected(&params));
+        assert_eq!(server.data_size, 8192);
+
+        // A smaller call reuses the grown shared memory
+        let params = [3, 5];
+        assert_eq!(emulator.call(&mut server, &user_fcalls, id, &params), Some(expected(&params)));
+        assert_eq!(server.data_size, 8192);
+
+        // A failed call is reported with an invalid result size
+        assert_eq!(emulator.call(&mut server, &user_fcalls, id + 1, &params), None);
+
+        drop(emulator);
+        drop(server);
+        ipc::unlink(&[name.as_str()], &[]);
+    }
+}
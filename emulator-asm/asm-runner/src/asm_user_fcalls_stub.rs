@@ -0,0 +1,24 @@
+use std::process::Command;
+use std::sync::Arc;
+
+use zisk_core::UserFcalls;
+
+use crate::AsmRunError;
+
+#[derive(Debug)]
+pub struct AsmUserFcallServer;
+
+impl AsmUserFcallServer {
+    pub fn start(_shmem_prefix: &str, _user_fcalls: Arc<UserFcalls>) -> Result<Self, AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn start_for_command(
+        _command: &mut Command,
+        _shmem_prefix: &str,
+        _user_fcalls: Option<&Arc<UserFcalls>>,
+    ) -> Result<Option<Self>, AsmRunError> {
+        Err(AsmRunError::Unsupported)
+    }
+}
@@ -4,6 +4,9 @@ mod asm_min_traces;
 mod asm_rom_histogram;
 mod asm_runner;
 
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+mod asm_ipc_linux;
+
 #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
 mod asm_min_traces_runner_linux;
 #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
@@ -14,6 +17,16 @@ mod asm_rom_histogram_runner_linux;
 #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
 mod asm_rom_histogram_runner_stub;
 
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+mod asm_server_linux;
+#[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+mod asm_server_stub;
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+mod asm_user_fcalls_linux;
+#[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+mod asm_user_fcalls_stub;
+
 mod asm_min_traces_runner {
     #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
     pub use super::asm_min_traces_runner_linux::*;
@@ -28,8 +41,24 @@ mod asm_rom_histogram_runner {
     pub use super::asm_rom_histogram_runner_stub::*;
 }
 
+mod asm_server {
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    pub use super::asm_server_linux::*;
+    #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+    pub use super::asm_server_stub::*;
+}
+
+mod asm_user_fcalls {
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    pub use super::asm_user_fcalls_linux::*;
+    #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+    pub use super::asm_user_fcalls_stub::*;
+}
+
 pub use asm_min_traces::*;
 pub use asm_min_traces_runner::*;
 pub use asm_rom_histogram::*;
 pub use asm_rom_histogram_runner::*;
 pub use asm_runner::*;
+pub use asm_server::*;
+pub use asm_user_fcalls::*;
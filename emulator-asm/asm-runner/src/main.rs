@@ -1,8 +1,9 @@
 extern crate libc;
 
-use asm_runner::{AsmRunnerMT, AsmRunnerOptionsBuilder};
+use asm_runner::{AsmRunnerMT, AsmRunnerOptionsBuilder, AsmServerMT};
 use clap::Parser;
 use std::path::PathBuf;
+use zisk_core::ResourceLimits;
 
 #[derive(Parser)]
 #[command(version, about = "Zisk Asm Emulator Runner", long_about = None)]
@@ -12,6 +13,10 @@ struct Args {
 
     /// Path to the inputs file
     inputs_path: PathBuf,
+
+    /// Number of runs, served by a single long-lived assembly emulator when greater than 1
+    #[arg(long, default_value_t = 1)]
+    runs: usize,
 }
 
 fn main() {
@@ -19,13 +24,30 @@ fn main() {
 
     let runner_options = AsmRunnerOptionsBuilder::new().with_log_output().with_metrics().build();
 
-    let _ = AsmRunnerMT::run(
-        &args.asm_runner_path,
-        &args.inputs_path,
-        1 << 32,
-        1 << 15,
-        runner_options,
-    );
+    let limits = ResourceLimits { max_steps: 1 << 32, ..Default::default() };
+
+    if args.runs > 1 {
+        let mut server = AsmServerMT::start(&args.asm_runner_path, runner_options)
+            .unwrap_or_else(|e| panic!("Failed to start the assembly emulator server: {}", e));
+        for run in 0..args.runs {
+            if let Err(e) = server.run(&args.inputs_path, &limits, 1 << 15) {
+                panic!("Run {} failed: {}", run, e);
+            }
+        }
+        if let Err(e) = server.shutdown() {
+            panic!("Failed to shut down the assembly emulator server: {}", e);
+        }
+    } else {
+        if let Err(e) = AsmRunnerMT::run(
+            &args.asm_runner_path,
+            &args.inputs_path,
+            &limits,
+            1 << 15,
+            runner_options,
+        ) {
+            panic!("Run failed: {}", e);
+        }
+    }
 
     println!("Done!");
 }
@@ -0,0 +1,33 @@
+#include "blake2b_round.hpp"
+
+static inline uint64_t rotr64(uint64_t x, unsigned int n)
+{
+    return (x >> n) | (x << (64 - n));
+}
+
+static inline void blake2b_g(uint64_t v[16], int a, int b, int c, int d, uint64_t x, uint64_t y)
+{
+    v[a] = v[a] + v[b] + x;
+    v[d] = rotr64(v[d] ^ v[a], 32);
+    v[c] = v[c] + v[d];
+    v[b] = rotr64(v[b] ^ v[c], 24);
+    v[a] = v[a] + v[b] + y;
+    v[d] = rotr64(v[d] ^ v[a], 16);
+    v[c] = v[c] + v[d];
+    v[b] = rotr64(v[b] ^ v[c], 63);
+}
+
+void blake2b_round(uint64_t v[16], const uint64_t m[16])
+{
+    // Columns
+    blake2b_g(v, 0, 4,  8, 12, m[0],  m[1]);
+    blake2b_g(v, 1, 5,  9, 13, m[2],  m[3]);
+    blake2b_g(v, 2, 6, 10, 14, m[4],  m[5]);
+    blake2b_g(v, 3, 7, 11, 15, m[6],  m[7]);
+
+    // Diagonals
+    blake2b_g(v, 0, 5, 10, 15, m[8],  m[9]);
+    blake2b_g(v, 1, 6, 11, 12, m[10], m[11]);
+    blake2b_g(v, 2, 7,  8, 13, m[12], m[13]);
+    blake2b_g(v, 3, 4,  9, 14, m[14], m[15]);
+}
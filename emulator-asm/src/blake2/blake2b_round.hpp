@@ -0,0 +1,10 @@
+#ifndef BLAKE2B_ROUND_HPP
+#define BLAKE2B_ROUND_HPP
+
+#include <stdint.h>
+
+// Applies a single BLAKE2b round (RFC 7693) to the 16-word working vector v, using the 16 message
+// words m, which must be already permuted according to the round's message schedule
+void blake2b_round(uint64_t v[16], const uint64_t m[16]);
+
+#endif
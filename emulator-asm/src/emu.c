@@ -6,10 +6,13 @@
 #include <errno.h>
 #include <unistd.h>
 #include <stdlib.h>
+#include <string.h>
 #include "../../lib-c/c/src/ec/ec.hpp"
 #include "../../lib-c/c/src/fcall/fcall.hpp"
 #include "../../lib-c/c/src/arith256/arith256.hpp"
 #include "bcon/bcon_sha256.hpp"
+#include "blake2/blake2b_round.hpp"
+#include "emu.hpp"
 
 extern void keccakf1600_generic(uint64_t state[25]);
 
@@ -21,6 +24,12 @@ bool arith256_metrics = false;
 bool arith256_mod_metrics = false;
 bool secp256k1_add_metrics = false;
 bool secp256k1_dbl_metrics = false;
+bool curve25519_mod_metrics = false;
+bool ed25519_dbl_metrics = false;
+bool ed25519_add_metrics = false;
+bool blake2br_metrics = false;
+bool memcpy_metrics = false;
+bool memset_metrics = false;
 #endif
 
 struct timeval keccak_start, keccak_stop;
@@ -47,6 +56,38 @@ struct timeval secp256k1_dbl_start, secp256k1_dbl_stop;
 uint64_t secp256k1_dbl_counter = 0;
 uint64_t secp256k1_dbl_duration = 0;
 
+struct timeval curve25519_mod_start, curve25519_mod_stop;
+uint64_t curve25519_mod_counter = 0;
+uint64_t curve25519_mod_duration = 0;
+
+struct timeval ed25519_dbl_start, ed25519_dbl_stop;
+uint64_t ed25519_dbl_counter = 0;
+uint64_t ed25519_dbl_duration = 0;
+
+struct timeval ed25519_add_start, ed25519_add_stop;
+uint64_t ed25519_add_counter = 0;
+uint64_t ed25519_add_duration = 0;
+
+struct timeval blake2br_start, blake2br_stop;
+uint64_t blake2br_counter = 0;
+uint64_t blake2br_duration = 0;
+
+struct timeval memcpy_start, memcpy_stop;
+uint64_t memcpy_counter = 0;
+uint64_t memcpy_duration = 0;
+
+struct timeval memset_start, memset_stop;
+uint64_t memset_counter = 0;
+uint64_t memset_duration = 0;
+
+// Error code of the last failed precompiled call, or zero if none failed; the generated code
+// stops the emulation as soon as it is set
+uint64_t precompile_error = 0;
+
+// Number of calls of every precompile, indexed by its op code minus PRECOMPILE_FIRST_OP, counted
+// by the generated code when it generates minimal traces
+uint64_t precompile_calls[PRECOMPILE_OPS] = {0};
+
 uint64_t print_abcflag_counter = 0;
 
 extern int _print_abcflag(uint64_t a, uint64_t b, uint64_t c, uint64_t flag)
@@ -66,11 +107,23 @@ extern int _print_abcflag(uint64_t a, uint64_t b, uint64_t c, uint64_t flag)
 
 uint64_t printed_chars_counter = 0;
 
+// Guest standard output and standard error streams; NULL means the host stdout and stderr
+FILE * guest_stdout = NULL;
+FILE * guest_stderr = NULL;
+
 extern int _print_char(uint64_t param)
 {
     printed_chars_counter++;
     char c = param;
-    printf("%c", c);
+    fputc(c, guest_stdout != NULL ? guest_stdout : stdout);
+    return 0;
+}
+
+extern int _print_char_err(uint64_t param)
+{
+    printed_chars_counter++;
+    char c = param;
+    fputc(c, guest_stderr != NULL ? guest_stderr : stderr);
     return 0;
 }
 
@@ -333,6 +386,262 @@ extern int _opcode_secp256k1_dbl(uint64_t * address)
     return 0;
 }
 
+extern int _opcode_curve25519_mod(uint64_t * address)
+{
+#ifdef DEBUG
+    if (curve25519_mod_metrics || emu_verbose) gettimeofday(&curve25519_mod_start, NULL);
+#endif
+    uint64_t * a = (uint64_t *)address[0];
+    uint64_t * b = (uint64_t *)address[1];
+    uint64_t * c = (uint64_t *)address[2];
+    uint64_t * d = (uint64_t *)address[3];
+#ifdef DEBUG
+    if (emu_verbose)
+    {
+        printf("opcode_curve25519_mod() calling Curve25519Mod() counter=%lu address=%p\n", curve25519_mod_counter, address);
+        printf("a = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", a[3], a[2], a[1], a[0], a[3], a[2], a[1], a[0]);
+        printf("b = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", b[3], b[2], b[1], b[0], b[3], b[2], b[1], b[0]);
+        printf("c = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", c[3], c[2], c[1], c[0], c[3], c[2], c[1], c[0]);
+    }
+#endif
+
+    int result = Curve25519Mod (a, b, c, d);
+    if (result != 0)
+    {
+        printf("_opcode_curve25519_mod() failed callilng Curve25519Mod() result=%d;", result);
+        exit(-1);
+    }
+
+#ifdef DEBUG
+    if (emu_verbose)
+    {
+        printf("d = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", d[3], d[2], d[1], d[0], d[3], d[2], d[1], d[0]);
+    }
+    curve25519_mod_counter++;
+    if (curve25519_mod_metrics || emu_verbose)
+    {
+        gettimeofday(&curve25519_mod_stop, NULL);
+        curve25519_mod_duration += TimeDiff(curve25519_mod_start, curve25519_mod_stop);
+    }
+#endif
+    return 0;
+}
+
+extern int _opcode_ed25519_dbl(uint64_t * address)
+{
+#ifdef DEBUG
+    if (ed25519_dbl_metrics || emu_verbose) gettimeofday(&ed25519_dbl_start, NULL);
+#endif
+
+    uint64_t * p1 = address;
+
+#ifdef DEBUG
+    if (emu_verbose)
+    {
+        printf("opcode_ed25519_dbl() calling Ed25519Dbl() counter=%lu address=%p\n", ed25519_dbl_counter, address);
+        printf("p1.x = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[3], p1[2], p1[1], p1[0], p1[3], p1[2], p1[1], p1[0]);
+        printf("p1.y = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[7], p1[6], p1[5], p1[4], p1[7], p1[6], p1[5], p1[4]);
+    }
+#endif
+    int result = Ed25519Dbl (
+        p1, // p1 = [x1, y1] = 8x64bits
+        p1 // p3 = [x3, y3] = 8x64bits
+    );
+    if (result != 0)
+    {
+        printf("_opcode_ed25519_dbl() failed callilng Ed25519Dbl() result=%d\n", result);
+        precompile_error = (uint64_t)result;
+        return result;
+    }
+#ifdef DEBUG
+    ed25519_dbl_counter++;
+    if (ed25519_dbl_metrics || emu_verbose)
+    {
+        gettimeofday(&ed25519_dbl_stop, NULL);
+        ed25519_dbl_duration += TimeDiff(ed25519_dbl_start, ed25519_dbl_stop);
+    }
+    if (emu_verbose)
+    {
+        printf("p1.x = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[3], p1[2], p1[1], p1[0], p1[3], p1[2], p1[1], p1[0]);
+        printf("p1.y = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[7], p1[6], p1[5], p1[4], p1[7], p1[6], p1[5], p1[4]);
+    }
+#endif
+    return 0;
+}
+
+extern int _opcode_ed25519_add(uint64_t * address)
+{
+#ifdef DEBUG
+    if (ed25519_add_metrics || emu_verbose) gettimeofday(&ed25519_add_start, NULL);
+#endif
+    uint64_t * p1 = (uint64_t *)address[0];
+    uint64_t * p2 = (uint64_t *)address[1];
+#ifdef DEBUG
+    if (emu_verbose)
+    {
+        printf("opcode_ed25519_add() calling Ed25519Add() counter=%lu address=%p p1_address=%p p2_address=%p\n", ed25519_add_counter, address, p1, p2);
+        printf("p1.x = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[3], p1[2], p1[1], p1[0], p1[3], p1[2], p1[1], p1[0]);
+        printf("p1.y = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[7], p1[6], p1[5], p1[4], p1[7], p1[6], p1[5], p1[4]);
+        printf("p2.x = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p2[3], p2[2], p2[1], p2[0], p2[3], p2[2], p2[1], p2[0]);
+        printf("p2.y = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p2[7], p2[6], p2[5], p2[4], p2[7], p2[6], p2[5], p2[4]);
+    }
+#endif
+    int result = Ed25519Add (
+        p1, // p1 = [x1, y1] = 8x64bits
+        p2, // p2 = [x2, y2] = 8x64bits
+        p1 // p3 = [x3, y3] = 8x64bits
+    );
+    if (result != 0)
+    {
+        printf("_opcode_ed25519_add() failed callilng Ed25519Add() result=%d\n", result);
+        precompile_error = (uint64_t)result;
+        return result;
+    }
+#ifdef DEBUG
+    if (emu_verbose)
+    {
+        printf("p3 = %lu:%lu:%lu:%lu = %lx:%lx:%lx:%lx\n", p1[3], p1[2], p1[1], p1[0], p1[3], p1[2], p1[1], p1[0]);
+    }
+    ed25519_add_counter++;
+    if (ed25519_add_metrics || emu_verbose)
+    {
+        gettimeofday(&ed25519_add_stop, NULL);
+        ed25519_add_duration += TimeDiff(ed25519_add_start, ed25519_add_stop);
+    }
+#endif
+    return 0;
+}
+
+extern int _opcode_blake2br(uint64_t * address)
+{
+#ifdef DEBUG
+    if (blake2br_metrics || emu_verbose) gettimeofday(&blake2br_start, NULL);
+#endif
+
+    // address = [v, m] = 16x64bits + 16x64bits, v is updated in place
+    blake2b_round(address, address + 16);
+
+#ifdef DEBUG
+    blake2br_counter++;
+    if (blake2br_metrics || emu_verbose)
+    {
+        gettimeofday(&blake2br_stop, NULL);
+        blake2br_duration += TimeDiff(blake2br_start, blake2br_stop);
+    }
+#endif
+    return 0;
+}
+
+// Maximum number of 64-bit words written by a single dma operation (memcpy, memset)
+#define DMA_MAX_WORDS 64
+
+// Error code of an invalid dma operation
+#define DMA_ERROR_INVALID_PARAMS 1
+
+// Checks the dma params = [dst, src|fill, count], where dst (and src, if it is an address) must be
+// aligned to 8 bytes and count must be in the range 1..=DMA_MAX_WORDS
+static bool dma_check_params(const char * name, uint64_t * params, bool src_is_address)
+{
+    if (((uint64_t)params & 0x7) != 0 ||
+        (params[0] & 0x7) != 0 ||
+        (src_is_address && ((params[1] & 0x7) != 0)) ||
+        (params[2] == 0) ||
+        (params[2] > DMA_MAX_WORDS))
+    {
+        printf("_opcode_%s() found invalid params dst=0x%lx src=0x%lx count=%lu\n", name, params[0], params[1], params[2]);
+        precompile_error = DMA_ERROR_INVALID_PARAMS;
+        return false;
+    }
+    return true;
+}
+
+// Copies count words from src to dst, reading all of them before writing, so overlapping areas are
+// supported; if mem_reads is not NULL, the params and the source words are saved in it, and their
+// number is returned
+extern uint64_t _opcode_memcpy(uint64_t * params, uint64_t * mem_reads)
+{
+#ifdef DEBUG
+    if (memcpy_metrics || emu_verbose) gettimeofday(&memcpy_start, NULL);
+#endif
+
+    // params = [dst, src, count]
+    if (!dma_check_params("memcpy", params, true))
+    {
+        return 0;
+    }
+    uint64_t * dst = (uint64_t *)params[0];
+    uint64_t * src = (uint64_t *)params[1];
+    uint64_t count = params[2];
+
+    // Save the mem reads before writing, since source and destination can overlap
+    uint64_t mem_reads_size = 0;
+    if (mem_reads != NULL)
+    {
+        mem_reads[0] = params[0];
+        mem_reads[1] = params[1];
+        mem_reads[2] = count;
+        for (uint64_t i = 0; i < count; i++)
+        {
+            mem_reads[3 + i] = src[i];
+        }
+        mem_reads_size = 3 + count;
+    }
+
+    memmove(dst, src, count * 8);
+
+#ifdef DEBUG
+    memcpy_counter++;
+    if (memcpy_metrics || emu_verbose)
+    {
+        gettimeofday(&memcpy_stop, NULL);
+        memcpy_duration += TimeDiff(memcpy_start, memcpy_stop);
+    }
+#endif
+    return mem_reads_size;
+}
+
+// Fills count words at dst with the fill word; if mem_reads is not NULL, the params are saved in
+// it, and their number is returned
+extern uint64_t _opcode_memset(uint64_t * params, uint64_t * mem_reads)
+{
+#ifdef DEBUG
+    if (memset_metrics || emu_verbose) gettimeofday(&memset_start, NULL);
+#endif
+
+    // params = [dst, fill, count]
+    if (!dma_check_params("memset", params, false))
+    {
+        return 0;
+    }
+    uint64_t * dst = (uint64_t *)params[0];
+    uint64_t fill = params[1];
+    uint64_t count = params[2];
+
+    uint64_t mem_reads_size = 0;
+    if (mem_reads != NULL)
+    {
+        mem_reads[0] = params[0];
+        mem_reads[1] = fill;
+        mem_reads[2] = count;
+        mem_reads_size = 3;
+    }
+
+    for (uint64_t i = 0; i < count; i++)
+    {
+        dst[i] = fill;
+    }
+
+#ifdef DEBUG
+    memset_counter++;
+    if (memset_metrics || emu_verbose)
+    {
+        gettimeofday(&memset_stop, NULL);
+        memset_duration += TimeDiff(memset_start, memset_stop);
+    }
+#endif
+    return mem_reads_size;
+}
+
 uint64_t fcall_counter = 0;
 extern uint64_t MEM_TRACE_ADDRESS;
 extern uint64_t fcall_ctx;
@@ -345,24 +654,92 @@ extern int _print_fcall_ctx(void)
     printf("\tfunction_id=0x%lu\n", ctx->function_id);
     printf("\tparams_max_size=%lu=0x%lx\n", ctx->params_max_size, ctx->params_max_size);
     printf("\tparams_size=0x%lu\n", ctx->params_size);
-    for (int i=0; i<32; i++)
+    for (uint64_t i=0; i<ctx->params_size; i++)
     {
-        printf("\t\tparams[%d]=%lu=0x%lx\n", i, ctx->params[i], ctx->params[i]);
+        printf("\t\tparams[%lu]=%lu=0x%lx\n", i, ctx->params[i], ctx->params[i]);
     }
     printf("\tresult_max_size=0x%lu\n", ctx->result_max_size);
     printf("\tresult_size=0x%lu\n", ctx->result_size);
-    for (int i=0; i<32; i++)
+    for (uint64_t i=0; i<ctx->result_size; i++)
     {
-        printf("\t\tresult[%d]=%lu=0x%lx\n", i, ctx->result[i], ctx->result[i]);
+        printf("\t\tresult[%lu]=%lu=0x%lx\n", i, ctx->result[i], ctx->result[i]);
     }
     printf("\n");
     print_fcall_ctx_counter++;
 }
 
+// Fcall parameters and result buffers, grown on demand; every fcall context uses the same ones,
+// so that resetting a context does not leak them
+uint64_t * fcall_params_buffer = NULL;
+uint64_t fcall_params_buffer_size = 0;
+uint64_t * fcall_result_buffer = NULL;
+uint64_t fcall_result_buffer_size = 0;
+
+// Grows buffer to hold at least size u64's, keeping its content
+void fcall_buffer_reserve(uint64_t ** buffer, uint64_t * buffer_size, uint64_t size)
+{
+    if (size <= *buffer_size)
+    {
+        return;
+    }
+    uint64_t new_size = *buffer_size * 2;
+    if (new_size < FCALL_RESULT_MIN_SIZE)
+    {
+        new_size = FCALL_RESULT_MIN_SIZE;
+    }
+    if (new_size < size)
+    {
+        new_size = size;
+    }
+    uint64_t * new_buffer = (uint64_t *)realloc(*buffer, new_size * sizeof(uint64_t));
+    if (new_buffer == NULL)
+    {
+        printf("fcall_buffer_reserve() failed calling realloc(%lu) errno=%d=%s\n", new_size * sizeof(uint64_t), errno, strerror(errno));
+        exit(-1);
+    }
+    *buffer = new_buffer;
+    *buffer_size = new_size;
+}
+
+// Makes the fcall parameters array hold at least size u64's; called by FcallParam when the
+// parameters do not fit
+extern void _opcode_fcall_params_reserve(struct FcallContext * ctx, uint64_t size)
+{
+    fcall_buffer_reserve(&fcall_params_buffer, &fcall_params_buffer_size, size);
+    ctx->params = fcall_params_buffer;
+    ctx->params_max_size = fcall_params_buffer_size;
+}
+
+// Makes the fcall result array hold at least size u64's
+void FcallResultReserve(struct FcallContext * ctx, uint64_t size)
+{
+    fcall_buffer_reserve(&fcall_result_buffer, &fcall_result_buffer_size, size);
+    ctx->result = fcall_result_buffer;
+    ctx->result_max_size = fcall_result_buffer_size;
+}
+
+// Serves a user-defined fcall by forwarding it to the host; implemented in main.c
+int UserFcall(struct FcallContext * ctx);
+
 extern int _opcode_fcall(struct FcallContext * ctx)
 {
     fcall_counter++;
     //printf("_opcode_fcall() counter=%lu\n", fcall_counter);
+    if (ctx->function_id >= FCALL_USER_ID_FIRST)
+    {
+        int iresult = UserFcall(ctx);
+        if (iresult < 0)
+        {
+            printf("_opcode_fcall() failed calling UserFcall() function_id=%lu result=%d\n", ctx->function_id, iresult);
+            fflush(stdout);
+            exit(-1);
+        }
+        return iresult;
+    }
+
+    // Built-in fcalls never return more u64's than they get as parameters, except for the
+    // fixed-size results
+    FcallResultReserve(ctx, ctx->params_size > FCALL_RESULT_MIN_SIZE ? ctx->params_size : FCALL_RESULT_MIN_SIZE);
     int iresult = Fcall(ctx);
     if (iresult < 0)
     {
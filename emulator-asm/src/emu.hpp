@@ -1,6 +1,22 @@
 #ifndef EMU_ASM_HPP
 #define EMU_ASM_HPP
 
+#include <stdio.h>
+
+extern FILE * guest_stdout;
+extern FILE * guest_stderr;
+
+// Makes the fcall result array hold at least size u64's
+struct FcallContext;
+void FcallResultReserve(struct FcallContext * ctx, uint64_t size);
+
+// Error code of the last failed precompiled call, or zero if none failed
+extern uint64_t precompile_error;
+
+// Number of calls of every precompile, indexed by its op code minus PRECOMPILE_FIRST_OP (0xf0)
+#define PRECOMPILE_OPS 16
+extern uint64_t precompile_calls[PRECOMPILE_OPS];
+
 #ifdef DEBUG
 extern bool keccak_metrics;
 extern uint64_t keccak_counter;
@@ -10,6 +26,12 @@ extern bool arith256_metrics;
 extern bool arith256_mod_metrics;
 extern bool secp256k1_add_metrics;
 extern bool secp256k1_dbl_metrics;
+extern bool curve25519_mod_metrics;
+extern bool ed25519_dbl_metrics;
+extern bool ed25519_add_metrics;
+extern bool blake2br_metrics;
+extern bool memcpy_metrics;
+extern bool memset_metrics;
 #endif
 
 #endif
\ No newline at end of file
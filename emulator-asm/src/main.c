@@ -29,6 +29,8 @@ uint64_t get_gen_method(void);
 #define SYS_ADDR RAM_ADDR
 #define SYS_SIZE (uint64_t)0x10000
 #define OUTPUT_ADDR (SYS_ADDR + SYS_SIZE)
+#define AVAILABLE_MEM_ADDR (OUTPUT_ADDR + 0x10000)
+#define GUEST_EXIT_ADDR (SYS_ADDR + 0x400)
 
 #define ROM_ADDR (uint64_t)0x80000000
 #define ROM_SIZE (uint64_t)0x08000000 // 128MB
@@ -73,6 +75,8 @@ uint64_t TimeDiff(const struct timeval startTime, const struct timeval endTime);
 void log_minimal_trace(void);
 void log_histogram(void);
 void log_main_trace(void);
+void write_resource_usage(void);
+extern void _realloc_trace(void);
 
 // Configuration
 bool output = true;
@@ -85,6 +89,12 @@ bool verbose = false;
 char * input_parameter = NULL;
 bool is_file = false;
 bool generate_minimal_trace = false;
+bool server = false;
+
+// Signature: RAM region dumped to a file after the emulation, as in the riscof test suites
+FILE * signature_file = NULL;
+uint64_t signature_begin = 0;
+uint64_t signature_end = 0;
 
 // ROM histogram
 bool generate_rom_histogram = false;
@@ -136,6 +146,41 @@ char * sem_chunk_done_sufix = "_semckd";
 char sem_chunk_done_name[128];
 sem_t * sem_chunk_done = NULL;
 
+// Request semaphore: in server mode, lets the caller notify that a new input is ready
+char * sem_request_sufix = "_semreq";
+char sem_request_name[128];
+sem_t * sem_request = NULL;
+
+// User fcalls: the fcalls with id >= FCALL_USER_ID_FIRST are forwarded to the host through the
+// fcall shared memory; the request semaphore notifies the host that a new call is ready, and the
+// done semaphore notifies the emulator that the result is ready
+bool user_fcalls = false;
+char * shmem_fcall_sufix = "_fcall";
+char shmem_fcall_name[128];
+int shmem_fcall_fd = -1;
+
+// Fcall shared memory: a header followed by data_size u64's, containing the parameters of the
+// call (written by the emulator) or its result (written by the host); any of them can grow the
+// shared memory when the data does not fit, updating data_size, so the other one must map it again
+struct UserFcallShmem
+{
+    uint64_t function_id;
+    uint64_t params_size;
+    uint64_t result_size;
+    uint64_t data_size;
+    uint64_t data[];
+};
+struct UserFcallShmem * shmem_fcall_address = NULL;
+uint64_t shmem_fcall_mapped_size = 0; // data u64's currently mapped
+int user_fcall_shmem_map (uint64_t data_size);
+
+char * sem_fcall_request_sufix = "_semfcr";
+char sem_fcall_request_name[128];
+sem_t * sem_fcall_request = NULL;
+char * sem_fcall_done_sufix = "_semfcd";
+char sem_fcall_done_name[128];
+sem_t * sem_fcall_done = NULL;
+
 int process_id = 0;
 
 uint64_t input_size = 0;
@@ -217,533 +262,689 @@ int main(int argc, char *argv[])
             }
         }
 
+        if (server)
+        {
+            strcpy(sem_request_name, shmem_prefix);
+            strcat(sem_request_name, sem_request_sufix);
+            sem_request = sem_open(sem_request_name, O_CREAT, 0644, 0);
+            if (sem_request == SEM_FAILED)
+            {
+                printf("Failed calling sem_open(%s) errno=%d=%s\n", sem_request_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+        }
+
+        if (user_fcalls)
+        {
+            // Map the fcall shared memory, created by the caller before spawning this process
+            strcpy(shmem_fcall_name, shmem_prefix);
+            strcat(shmem_fcall_name, shmem_fcall_sufix);
+            shmem_fcall_fd = shm_open(shmem_fcall_name, O_RDWR, 0644);
+            if (shmem_fcall_fd < 0)
+            {
+                printf("Failed calling shm_open(%s) errno=%d=%s\n", shmem_fcall_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            if ((user_fcall_shmem_map(0) != 0) || (user_fcall_shmem_map(shmem_fcall_address->data_size) != 0))
+            {
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+
+            // Create (or open if existing) the fcall semaphores
+            strcpy(sem_fcall_request_name, shmem_prefix);
+            strcat(sem_fcall_request_name, sem_fcall_request_sufix);
+            sem_fcall_request = sem_open(sem_fcall_request_name, O_CREAT, 0644, 0);
+            if (sem_fcall_request == SEM_FAILED)
+            {
+                printf("Failed calling sem_open(%s) errno=%d=%s\n", sem_fcall_request_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            strcpy(sem_fcall_done_name, shmem_prefix);
+            strcat(sem_fcall_done_name, sem_fcall_done_sufix);
+            sem_fcall_done = sem_open(sem_fcall_done_name, O_CREAT, 0644, 0);
+            if (sem_fcall_done == SEM_FAILED)
+            {
+                printf("Failed calling sem_open(%s) errno=%d=%s\n", sem_fcall_done_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+        }
+
 #ifdef DEBUG
         if (verbose) printf("Emulator C start; input shared memory ID = %s\n", input_parameter);
 #endif
     }
     else
     {
-        // Mark this is an input file
-        is_file = true;
-        sprintf(shmem_output_name, "ZISK_%d_output", process_id);
-#ifdef DEBUG
-        if (verbose) printf("Emulator C start; input file = %s shmem=%s\n", input_parameter, shmem_output_name);
-#endif
-    }
-
-    /*********/
-    /* INPUT */
-    /*********/
-    // Allocate input memory region and initialize it with the data coming from the file
-    // of from the input shared memory region
-    if (is_file)
-    {
-        // Open input file
-        FILE * input_fp = fopen(input_parameter, "r");
-        if (input_fp == NULL)
+        // Server mode requires the caller to provide the inputs through shared memory
+        if (server)
         {
-            printf("Failed calling fopen(%s) errno=%d=%s; does it exist?\n", input_parameter, errno, strerror(errno));
+            printf("Server mode requires a shared memory ID as input parameter, but got %s\n", input_parameter);
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
 
-        // Get input file size
-        if (fseek(input_fp, 0, SEEK_END) == -1)
+        // User fcalls are served by the caller through shared memory
+        if (user_fcalls)
         {
-            printf("Failed calling fseek(%s) errno=%d=%s\n", input_parameter, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
-        long input_data_size = ftell(input_fp);
-        if (input_data_size == -1)
-        {
-            printf("Failed calling ftell(%s) errno=%d=%s\n", input_parameter, errno, strerror(errno));
+            printf("User fcalls require a shared memory ID as input parameter, but got %s\n", input_parameter);
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
 
-        // Go back to the first byte
-        if (fseek(input_fp, 0, SEEK_SET) == -1)
+        // Mark this is an input file
+        is_file = true;
+        sprintf(shmem_output_name, "ZISK_%d_output", process_id);
+#ifdef DEBUG
+        if (verbose) printf("Emulator C start; input file = %s shmem=%s\n", input_parameter, shmem_output_name);
+#endif
+    }
+
+    // In server mode the process stays alive with the ROM loaded, and serves one request per
+    // input shared memory write, until the caller sends a shutdown request
+    while (true)
+    {
+        if (server)
         {
-            printf("Failed calling fseek(%s, 0) errno=%d=%s\n", input_parameter, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
+            // Wait for the caller to notify that a new input is ready
+            result = sem_wait(sem_request);
+            if (result == -1)
+            {
+                printf("Failed calling sem_wait(%s) errno=%d=%s\n", sem_request_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            realloc_counter = 0;
         }
 
-        // Check the input data size is inside the proper range
-        if (input_data_size > (MAX_INPUT_SIZE - 8))
+        /*********/
+        /* INPUT */
+        /*********/
+        // Allocate input memory region and initialize it with the data coming from the file
+        // of from the input shared memory region
+        if (is_file)
         {
-            printf("Size of input file (%s) is too long (%lu)\n", input_parameter, input_data_size);
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // Open input file
+            FILE * input_fp = fopen(input_parameter, "r");
+            if (input_fp == NULL)
+            {
+                printf("Failed calling fopen(%s) errno=%d=%s; does it exist?\n", input_parameter, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Calculate input size = input file data size + 8B for size header + round up to higher 8B
-        // boundary
-        input_size = ((input_data_size + 16 + 7) >> 3) << 3;
+            // Get input file size
+            if (fseek(input_fp, 0, SEEK_END) == -1)
+            {
+                printf("Failed calling fseek(%s) errno=%d=%s\n", input_parameter, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            long input_data_size = ftell(input_fp);
+            if (input_data_size == -1)
+            {
+                printf("Failed calling ftell(%s) errno=%d=%s\n", input_parameter, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Map input address space
-        void * pInput = mmap((void *)INPUT_ADDR, input_size, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
-        if (pInput == NULL)
-        {
-            printf("Failed calling mmap(input) errno=%d=%s\n", errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
-        if ((uint64_t)pInput != INPUT_ADDR)
-        {
-            printf("Called mmap(pInput) but returned address = 0x%p != 0x%lx\n", pInput, INPUT_ADDR);
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
-    #ifdef DEBUG
-        if (verbose) printf("mmap(input) returned 0x%p\n", pInput);
-    #endif
+            // Go back to the first byte
+            if (fseek(input_fp, 0, SEEK_SET) == -1)
+            {
+                printf("Failed calling fseek(%s, 0) errno=%d=%s\n", input_parameter, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Write the input size in the first 64 bits
-        *(uint64_t *)INPUT_ADDR = (uint64_t)0; // free input
-        *(uint64_t *)(INPUT_ADDR + 8) = (uint64_t)input_data_size;
+            // Check the input data size is inside the proper range
+            if (input_data_size > (MAX_INPUT_SIZE - 8))
+            {
+                printf("Size of input file (%s) is too long (%lu)\n", input_parameter, input_data_size);
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Copy input data into input memory
-        size_t input_read = fread((void *)(INPUT_ADDR + 16), 1, input_data_size, input_fp);
-        if (input_read != input_data_size)
-        {
-            printf("Input read (%lu) != input file size (%lu)\n", input_read, input_data_size);
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // Calculate input size = input file data size + 8B for size header + round up to higher 8B
+            // boundary
+            input_size = ((input_data_size + 16 + 7) >> 3) << 3;
 
-        // Close the file pointer
-        fclose(input_fp);
-    }
-    else
-    {
-        // Open input shared memory
-        shmem_input_fd = shm_open(shmem_input_name, /*O_RDWR*/ O_RDONLY, 0666);
-        if (shmem_input_fd < 0)
-        {
-            printf("Failed calling shm_open(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // Map input address space
+            void * pInput = mmap((void *)INPUT_ADDR, input_size, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
+            if (pInput == NULL)
+            {
+                printf("Failed calling mmap(input) errno=%d=%s\n", errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            if ((uint64_t)pInput != INPUT_ADDR)
+            {
+                printf("Called mmap(pInput) but returned address = 0x%p != 0x%lx\n", pInput, INPUT_ADDR);
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+        #ifdef DEBUG
+            if (verbose) printf("mmap(input) returned 0x%p\n", pInput);
+        #endif
 
-        // Map the shared memory object into the process address space, but just the 32B header
-        shmem_input_address = mmap(NULL, 32, PROT_READ, MAP_SHARED, shmem_input_fd, 0);
-        if (shmem_input_address == MAP_FAILED)
-        {
-            printf("Failed calling mmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // Write the input size in the first 64 bits
+            *(uint64_t *)INPUT_ADDR = (uint64_t)0; // free input
+            *(uint64_t *)(INPUT_ADDR + 8) = (uint64_t)input_data_size;
+
+            // Copy input data into input memory
+            size_t input_read = fread((void *)(INPUT_ADDR + 16), 1, input_data_size, input_fp);
+            if (input_read != input_data_size)
+            {
+                printf("Input read (%lu) != input file size (%lu)\n", input_read, input_data_size);
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Read input header data
-        uint64_t * control = (uint64_t *)shmem_input_address;
-        if (generate_minimal_trace || generate_zip) {
-            chunk_size = control[0];
-            assert(chunk_size > 0);
-            chunk_size_mask = chunk_size - 1;
+            // Close the file pointer
+            fclose(input_fp);
         }
-        max_steps = control[1];
-        assert(max_steps > 0);
-        initial_trace_size = control[2]; // Initial trace size
-        assert(initial_trace_size > 0);
-        trace_size = initial_trace_size;
-        trace_address_threshold = TRACE_ADDR + initial_trace_size - MAX_CHUNK_TRACE_SIZE;
-        shmem_input_size = control[3];
-
-        // Unmap input header
-        result = munmap(shmem_input_address, 32);
-        if (result == -1)
+        else
         {
-            printf("Failed calling munmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            exit(-1);
-        }
+            // Open input shared memory
+            shmem_input_fd = shm_open(shmem_input_name, /*O_RDWR*/ O_RDONLY, 0666);
+            if (shmem_input_fd < 0)
+            {
+                printf("Failed calling shm_open(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Map the shared memory object into the process address space
-        shmem_input_address = mmap(NULL, shmem_input_size + 32, PROT_READ /*| PROT_WRITE*/, MAP_SHARED, shmem_input_fd, 0);
-        if (shmem_input_address == MAP_FAILED)
-        {
-            printf("Failed calling mmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // Map the shared memory object into the process address space, but just the 32B header
+            shmem_input_address = mmap(NULL, 32, PROT_READ, MAP_SHARED, shmem_input_fd, 0);
+            if (shmem_input_address == MAP_FAILED)
+            {
+                printf("Failed calling mmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Calculate input size
-        input_size = ((shmem_input_size + 16 + 7) >> 3) << 3;
+            // Read input header data
+            uint64_t * control = (uint64_t *)shmem_input_address;
 
-        // Map input address space
-        void * pInput = mmap((void *)INPUT_ADDR, input_size, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
-        if (pInput == NULL)
-        {
-            printf("Failed calling mmap(input) errno=%d=%s\n", errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
-        if ((uint64_t)pInput != INPUT_ADDR)
-        {
-            printf("Called mmap(pInput) but returned address = 0x%p != 0x%lx\n", pInput, INPUT_ADDR);
-            fflush(stdout);
-            fflush(stderr);
-            return -1;
-        }
+            // In server mode, a request with max_steps = 0 asks the server to shut down
+            if (server && (control[1] == 0))
+            {
+                munmap(shmem_input_address, 32);
+                shm_unlink(shmem_input_name);
+                close(shmem_input_fd);
+                shmem_input_fd = -1;
 #ifdef DEBUG
-        if (verbose) printf("mmap(input) returned 0x%p\n", pInput);
+                if (verbose) printf("Emulator C server shutdown requested\n");
 #endif
+                break;
+            }
 
-        // Write the input size in the first 64 bits
-        *(uint64_t *)INPUT_ADDR = (uint64_t)0; // free input
-        *(uint64_t *)(INPUT_ADDR + 8)= (uint64_t)shmem_input_size;
+            if (generate_minimal_trace || generate_zip) {
+                chunk_size = control[0];
+                assert(chunk_size > 0);
+                chunk_size_mask = chunk_size - 1;
+            }
+            max_steps = control[1];
+            assert(max_steps > 0);
+            initial_trace_size = control[2]; // Initial trace size
+            assert(initial_trace_size > 0);
+            trace_size = initial_trace_size;
+            trace_address_threshold = TRACE_ADDR + initial_trace_size - MAX_CHUNK_TRACE_SIZE;
+            shmem_input_size = control[3];
+
+            // Unmap input header
+            result = munmap(shmem_input_address, 32);
+            if (result == -1)
+            {
+                printf("Failed calling munmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                exit(-1);
+            }
 
-        // Copy the input data
-        memcpy((void *)(INPUT_ADDR + 16), shmem_input_address + 32, shmem_input_size);
+            // Map the shared memory object into the process address space
+            shmem_input_address = mmap(NULL, shmem_input_size + 32, PROT_READ /*| PROT_WRITE*/, MAP_SHARED, shmem_input_fd, 0);
+            if (shmem_input_address == MAP_FAILED)
+            {
+                printf("Failed calling mmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Unmap input
-        result = munmap(shmem_input_address, shmem_input_size + 32);
-        if (result == -1)
-        {
-            printf("Failed calling munmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            exit(-1);
+            // Calculate input size
+            input_size = ((shmem_input_size + 16 + 7) >> 3) << 3;
+
+            // Map input address space
+            void * pInput = mmap((void *)INPUT_ADDR, input_size, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
+            if (pInput == NULL)
+            {
+                printf("Failed calling mmap(input) errno=%d=%s\n", errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            if ((uint64_t)pInput != INPUT_ADDR)
+            {
+                printf("Called mmap(pInput) but returned address = 0x%p != 0x%lx\n", pInput, INPUT_ADDR);
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+#ifdef DEBUG
+            if (verbose) printf("mmap(input) returned 0x%p\n", pInput);
+#endif
+
+            // Write the input size in the first 64 bits
+            *(uint64_t *)INPUT_ADDR = (uint64_t)0; // free input
+            *(uint64_t *)(INPUT_ADDR + 8)= (uint64_t)shmem_input_size;
+
+            // Copy the input data
+            memcpy((void *)(INPUT_ADDR + 16), shmem_input_address + 32, shmem_input_size);
+
+            // Unmap input
+            result = munmap(shmem_input_address, shmem_input_size + 32);
+            if (result == -1)
+            {
+                printf("Failed calling munmap(%s) errno=%d=%s\n", shmem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                exit(-1);
+            }
+
+            // Unlink input
+            result = shm_unlink(shmem_input_name);
+            if (result == -1)
+            {
+                printf("Failed calling shm_unlink(%s) size=%lu errno=%d=%s\n", shmem_input_name, trace_size, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                exit(-1);
+            }
+
+            // Close input
+            close(shmem_input_fd);
+            shmem_input_fd = -1;
         }
 
-        // Unlink input
-        result = shm_unlink(shmem_input_name);
-        if (result == -1)
+        /*********/
+        /* TRACE */
+        /*********/
+
+        if (generate_rom_histogram)
         {
-            printf("Failed calling shm_unlink(%s) size=%lu errno=%d=%s\n", shmem_input_name, trace_size, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            exit(-1);
+            // Get max PC values for low and high addresses
+            uint64_t max_bios_pc = get_max_bios_pc();
+            uint64_t max_program_pc = get_max_program_pc();
+            assert(max_bios_pc >= 0x1000);
+            assert((max_bios_pc & 0x3) == 0);
+            assert(max_program_pc >= 0x80000000);
+
+            // Calculate sizes
+            bios_size = ((max_bios_pc - 0x1000) >> 2) + 1;
+            program_size = max_program_pc - 0x80000000 + 1;
+            histogram_size = (4 + 1 + bios_size + 1 + program_size)*8;
+#define TRACE_SIZE_GRANULARITY (1014*1014)
+            initial_trace_size = ((histogram_size/TRACE_SIZE_GRANULARITY) + 1) * TRACE_SIZE_GRANULARITY;
+            trace_size = initial_trace_size;
         }
-    }
 
-    /*********/
-    /* TRACE */
-    /*********/
+        if (generate_minimal_trace || generate_rom_histogram || generate_main_trace || generate_zip)
+        {
+            // Make sure the output shared memory is deleted
+            shm_unlink(shmem_output_name);
 
-    if (generate_rom_histogram)
-    {
-        // Get max PC values for low and high addresses
-        uint64_t max_bios_pc = get_max_bios_pc();
-        uint64_t max_program_pc = get_max_program_pc();
-        assert(max_bios_pc >= 0x1000);
-        assert((max_bios_pc & 0x3) == 0);
-        assert(max_program_pc >= 0x80000000);
-
-        // Calculate sizes
-        bios_size = ((max_bios_pc - 0x1000) >> 2) + 1;
-        program_size = max_program_pc - 0x80000000 + 1;
-        histogram_size = (4 + 1 + bios_size + 1 + program_size)*8;
-#define TRACE_SIZE_GRANULARITY (1014*1014)
-        initial_trace_size = ((histogram_size/TRACE_SIZE_GRANULARITY) + 1) * TRACE_SIZE_GRANULARITY;
-        trace_size = initial_trace_size;
-    }
+            // Create the output shared memory
+            shmem_output_fd = shm_open(shmem_output_name, O_RDWR | O_CREAT, 0644);
+            if (shmem_output_fd < 0)
+            {
+                printf("Failed calling shm_open(%s) errno=%d=%s\n", shmem_output_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-    if (generate_minimal_trace || generate_rom_histogram || generate_main_trace || generate_zip)
-    {
-        // Make sure the output shared memory is deleted
-        shm_unlink(shmem_output_name);
+            // Size it
+            result = ftruncate(shmem_output_fd, trace_size);
+            if (result != 0)
+            {
+                printf("Failed calling ftruncate(%s) errno=%d=%s\n", shmem_output_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
 
-        // Create the output shared memory
-        shmem_output_fd = shm_open(shmem_output_name, O_RDWR | O_CREAT, 0644);
-        if (shmem_output_fd < 0)
+            // Map it to the trace address
+            void * pTrace = mmap((void *)TRACE_ADDR, trace_size, PROT_READ | PROT_WRITE, MAP_SHARED|MAP_FIXED, shmem_output_fd, 0);
+            if (pTrace == NULL)
+            {
+                printf("Failed calling mmap(pTrace) errno=%d=%s\n", errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+            if ((uint64_t)pTrace != TRACE_ADDR)
+            {
+                printf("Called mmap(trace) but returned address = 0x%p != 0x%lx\n", pTrace, TRACE_ADDR);
+                fflush(stdout);
+                fflush(stderr);
+                return -1;
+            }
+        #ifdef DEBUG
+            if (verbose) printf("mmap(trace) returned 0x%p\n", pTrace);
+        #endif
+
+            // Init output header data
+            uint64_t * pOutput = (uint64_t *)TRACE_ADDR;
+            pOutput[0] = 0x000100; // Version, e.g. v1.0.0 [8]
+            pOutput[1] = 1; // Exit code: 0=successfully completed, 1=not completed (written at the beginning of the emulation), etc. [8]
+            pOutput[2] = trace_size;
+            // MT allocated size [8] -> to be updated after completion
+            // MT used size [8] -> to be updated after completion
+        }
+
+        /*******/
+        /* RAM */
+        /*******/
+        void * pRam = mmap((void *)RAM_ADDR, RAM_SIZE, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
+        if (pRam == NULL)
         {
-            printf("Failed calling shm_open(%s) errno=%d=%s\n", shmem_output_name, errno, strerror(errno));
+            printf("Failed calling mmap(ram) errno=%d=%s\n", errno, strerror(errno));
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
-
-        // Size it
-        result = ftruncate(shmem_output_fd, trace_size);
-        if (result != 0)
+        if ((uint64_t)pRam != RAM_ADDR)
         {
-            printf("Failed calling ftruncate(%s) errno=%d=%s\n", shmem_output_name, errno, strerror(errno));
+            printf("Called mmap(ram) but returned address = 0x%p != 0x%08lx\n", pRam, RAM_ADDR);
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
+#ifdef DEBUG
+        if (verbose) printf("mmap(ram) returned 0x%p\n", pRam);
+#endif
 
-        // Map it to the trace address
-        void * pTrace = mmap((void *)TRACE_ADDR, trace_size, PROT_READ | PROT_WRITE, MAP_SHARED|MAP_FIXED, shmem_output_fd, 0);
-        if (pTrace == NULL)
+        /*******/
+        /* ROM */
+        /*******/
+        void * pRom = mmap((void *)ROM_ADDR, ROM_SIZE, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
+        if (pRom == NULL)
         {
-            printf("Failed calling mmap(pTrace) errno=%d=%s\n", errno, strerror(errno));
+            printf("Failed calling mmap(rom) errno=%d=%s\n", errno, strerror(errno));
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
-        if ((uint64_t)pTrace != TRACE_ADDR)
+        if ((uint64_t)pRom != ROM_ADDR)
         {
-            printf("Called mmap(trace) but returned address = 0x%p != 0x%lx\n", pTrace, TRACE_ADDR);
+            printf("Called mmap(rom) but returned address = 0x%p != 0x%lx\n", pRom, ROM_ADDR);
             fflush(stdout);
             fflush(stderr);
             return -1;
         }
-    #ifdef DEBUG
-        if (verbose) printf("mmap(trace) returned 0x%p\n", pTrace);
-    #endif
-
-        // Init output header data
-        uint64_t * pOutput = (uint64_t *)TRACE_ADDR;
-        pOutput[0] = 0x000100; // Version, e.g. v1.0.0 [8]
-        pOutput[1] = 1; // Exit code: 0=successfully completed, 1=not completed (written at the beginning of the emulation), etc. [8]
-        pOutput[2] = trace_size;
-        // MT allocated size [8] -> to be updated after completion
-        // MT used size [8] -> to be updated after completion
-    }
-
-    /*******/
-    /* RAM */
-    /*******/
-    void * pRam = mmap((void *)RAM_ADDR, RAM_SIZE, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
-    if (pRam == NULL)
-    {
-        printf("Failed calling mmap(ram) errno=%d=%s\n", errno, strerror(errno));
-        fflush(stdout);
-        fflush(stderr);
-        return -1;
-    }
-    if ((uint64_t)pRam != RAM_ADDR)
-    {
-        printf("Called mmap(ram) but returned address = 0x%p != 0x%08lx\n", pRam, RAM_ADDR);
-        fflush(stdout);
-        fflush(stderr);
-        return -1;
-    }
 #ifdef DEBUG
-    if (verbose) printf("mmap(ram) returned 0x%p\n", pRam);
+        if (verbose) printf("mmap(rom) returned 0x%p\n", pRom);
 #endif
 
-    /*******/
-    /* ROM */
-    /*******/
-    void * pRom = mmap((void *)ROM_ADDR, ROM_SIZE, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS|MAP_FIXED, -1, 0);
-    if (pRom == NULL)
-    {
-        printf("Failed calling mmap(rom) errno=%d=%s\n", errno, strerror(errno));
-        fflush(stdout);
-        fflush(stderr);
-        return -1;
-    }
-    if ((uint64_t)pRom != ROM_ADDR)
-    {
-        printf("Called mmap(rom) but returned address = 0x%p != 0x%lx\n", pRom, ROM_ADDR);
-        fflush(stdout);
-        fflush(stderr);
-        return -1;
-    }
-#ifdef DEBUG
-    if (verbose) printf("mmap(rom) returned 0x%p\n", pRom);
-#endif
+        /*******/
+        /* ASM */
+        /*******/
+        // Call emulator assembly code
+        gettimeofday(&start_time,NULL);
+        precompile_error = 0;
+        memset(precompile_calls, 0, sizeof(precompile_calls));
+        emulator_start();
+        struct timeval stop_time;
+        gettimeofday(&stop_time,NULL);
 
-    /*******/
-    /* ASM */
-    /*******/
-    // Call emulator assembly code
-    gettimeofday(&start_time,NULL);
-    emulator_start();
-    struct timeval stop_time;
-    gettimeofday(&stop_time,NULL);
+        // Make the guest output of this execution available to the caller
+        if (guest_stdout != NULL) fflush(guest_stdout);
+        if (guest_stderr != NULL) fflush(guest_stderr);
 
-    uint64_t final_trace_size = MEM_CHUNK_ADDRESS - MEM_TRACE_ADDRESS;
+        uint64_t final_trace_size = MEM_CHUNK_ADDRESS - MEM_TRACE_ADDRESS;
 
-    if ( metrics
+        if ( metrics
 #ifdef DEBUG
-        || keccak_metrics
+            || keccak_metrics
 #endif
-        )
-    {
-        uint64_t duration = TimeDiff(start_time, stop_time);
-        uint64_t steps = MEM_STEP;
-        uint64_t end = MEM_END;
-        uint64_t step_duration_ns = steps == 0 ? 0 : (duration * 1000) / steps;
-        uint64_t step_tp_sec = duration == 0 ? 0 : steps * 1000000 / duration;
-        uint64_t final_trace_size_percentage = (final_trace_size * 100) / trace_size;
-#ifdef DEBUG
-        printf("Duration = %lu us, Keccak counter = %lu, realloc counter = %lu, steps = %lu, step duration = %lu ns, tp = %lu steps/s, trace size = 0x%lx - 0x%lx = %lu B(%lu%%), end=%lu\n",
-            duration,
-            keccak_counter,
-            realloc_counter,
-            steps,
-            step_duration_ns,
-            step_tp_sec,
-            MEM_CHUNK_ADDRESS,
-            MEM_TRACE_ADDRESS,
-            final_trace_size,
-            final_trace_size_percentage,
-            end);
-        if (keccak_metrics)
+            )
         {
-            uint64_t keccak_percentage = duration == 0 ? 0 : (keccak_duration * 100) / duration;
-            uint64_t single_keccak_duration_ns = keccak_counter == 0 ? 0 : (keccak_duration * 1000) / keccak_counter;
-            printf("Keccak counter = %lu, duration = %lu us, single keccak duration = %lu ns, percentage = %lu \n", keccak_counter, keccak_duration, single_keccak_duration_ns, keccak_percentage);
-        }
+            uint64_t duration = TimeDiff(start_time, stop_time);
+            uint64_t steps = MEM_STEP;
+            uint64_t end = MEM_END;
+            uint64_t step_duration_ns = steps == 0 ? 0 : (duration * 1000) / steps;
+            uint64_t step_tp_sec = duration == 0 ? 0 : steps * 1000000 / duration;
+            uint64_t final_trace_size_percentage = (final_trace_size * 100) / trace_size;
+#ifdef DEBUG
+            printf("Duration = %lu us, Keccak counter = %lu, realloc counter = %lu, steps = %lu, step duration = %lu ns, tp = %lu steps/s, trace size = 0x%lx - 0x%lx = %lu B(%lu%%), end=%lu\n",
+                duration,
+                keccak_counter,
+                realloc_counter,
+                steps,
+                step_duration_ns,
+                step_tp_sec,
+                MEM_CHUNK_ADDRESS,
+                MEM_TRACE_ADDRESS,
+                final_trace_size,
+                final_trace_size_percentage,
+                end);
+            if (keccak_metrics)
+            {
+                uint64_t keccak_percentage = duration == 0 ? 0 : (keccak_duration * 100) / duration;
+                uint64_t single_keccak_duration_ns = keccak_counter == 0 ? 0 : (keccak_duration * 1000) / keccak_counter;
+                printf("Keccak counter = %lu, duration = %lu us, single keccak duration = %lu ns, percentage = %lu \n", keccak_counter, keccak_duration, single_keccak_duration_ns, keccak_percentage);
+            }
 #else
-        printf("Duration = %lu us, realloc counter = %lu, steps = %lu, step duration = %lu ns, tp = %lu steps/s, trace size = 0x%lx - 0x%lx = %lu B(%lu%%), end=%lu\n",
-            duration,
-            realloc_counter,
-            steps,
-            step_duration_ns,
-            step_tp_sec,
-            MEM_CHUNK_ADDRESS,
-            MEM_TRACE_ADDRESS,
-            final_trace_size,
-            final_trace_size_percentage,
-            end);
+            printf("Duration = %lu us, realloc counter = %lu, steps = %lu, step duration = %lu ns, tp = %lu steps/s, trace size = 0x%lx - 0x%lx = %lu B(%lu%%), end=%lu\n",
+                duration,
+                realloc_counter,
+                steps,
+                step_duration_ns,
+                step_tp_sec,
+                MEM_CHUNK_ADDRESS,
+                MEM_TRACE_ADDRESS,
+                final_trace_size,
+                final_trace_size_percentage,
+                end);
 #endif
-        if (generate_rom_histogram)
-        {
-            printf("Rom histogram size=%lu\n", histogram_size);
+            if (generate_rom_histogram)
+            {
+                printf("Rom histogram size=%lu\n", histogram_size);
+            }
         }
-    }
 
-    // Log output
-    if (output)
-    {
-        unsigned int * pOutput = (unsigned int *)OUTPUT_ADDR;
-        unsigned int output_size = *pOutput;
+        // Log output
+        if (output)
+        {
+            unsigned int * pOutput = (unsigned int *)OUTPUT_ADDR;
+            unsigned int output_size = *pOutput;
 #ifdef DEBUG
-        if (verbose) printf("Output size=%d\n", output_size);
+            if (verbose) printf("Output size=%d\n", output_size);
 #endif
 
-        for (unsigned int i = 0; i < output_size; i++)
+            for (unsigned int i = 0; i < output_size; i++)
+            {
+                pOutput++;
+                printf("%08x\n", *pOutput);
+            }
+        }
+
+        // Write signature, one 32-bit word per line
+        if (signature_file != NULL)
         {
-            pOutput++;
-            printf("%08x\n", *pOutput);
+            if ((signature_begin < RAM_ADDR) || (signature_end > (RAM_ADDR + RAM_SIZE)) || (signature_begin > signature_end))
+            {
+                printf("Signature region 0x%lx - 0x%lx is out of RAM\n", signature_begin, signature_end);
+                fflush(stdout);
+                fflush(stderr);
+                exit(-1);
+            }
+            for (uint64_t address = signature_begin; address + 4 <= signature_end; address += 4)
+            {
+                fprintf(signature_file, "%08x\n", *(unsigned int *)address);
+            }
+            fclose(signature_file);
+            signature_file = NULL;
         }
-    }
 
-    // Complete output header data
-    if (generate_minimal_trace || generate_rom_histogram || generate_zip)
-    {
-        uint64_t * pOutput = (uint64_t *)TRACE_ADDR;
-        pOutput[0] = 0x000100; // Version, e.g. v1.0.0 [8]
-        pOutput[1] = 0; // Exit code: 0=successfully completed, 1=not completed (written at the beginning of the emulation), etc. [8]
-        pOutput[2] = trace_size; // MT allocated size [8]
-        //assert(final_trace_size > 32);
-        if (generate_minimal_trace || generate_zip)
+        // Report the resource usage of the guest program after the minimal traces
+        if (generate_minimal_trace)
         {
-            pOutput[3] = final_trace_size; // MT used size [8]
+            write_resource_usage();
         }
-        else
+
+        // Complete output header data
+        if (generate_minimal_trace || generate_rom_histogram || generate_zip)
         {
-            pOutput[3] = MEM_STEP;
-            pOutput[4] = bios_size;
-            pOutput[4 + bios_size + 1] = program_size;
+            uint64_t * pOutput = (uint64_t *)TRACE_ADDR;
+            pOutput[0] = 0x000100; // Version, e.g. v1.0.0 [8]
+            pOutput[1] = (*(uint64_t *)GUEST_EXIT_ADDR << 8) | (precompile_error ? 3 : MEM_END ? 0 : (MEM_STEP >= max_steps ? 2 : 1)); // Exit code: 0=successfully completed, 1=not completed (written at the beginning of the emulation), 2=stopped after max_steps, 3=stopped after a failed precompiled call, etc., plus the guest exit code in the upper bits [8]
+            pOutput[2] = trace_size; // MT allocated size [8]
+            //assert(final_trace_size > 32);
+            if (generate_minimal_trace || generate_zip)
+            {
+                pOutput[3] = final_trace_size; // MT used size [8]
+            }
+            else
+            {
+                pOutput[3] = MEM_STEP;
+                pOutput[4] = bios_size;
+                pOutput[4 + bios_size + 1] = program_size;
+            }
         }
-    }
 
-    // Notify the caller that the trace is ready to be consumed
-    if (!is_file)
-    {
-        result = sem_post(sem_input);
-        if (result == -1)
+        // Notify the caller that the trace is ready to be consumed
+        if (!is_file)
         {
-            printf("Failed calling sem_post(%s) errno=%d=%s\n", sem_input_name, errno, strerror(errno));
-            fflush(stdout);
-            fflush(stderr);
-            exit(-1);
+            result = sem_post(sem_input);
+            if (result == -1)
+            {
+                printf("Failed calling sem_post(%s) errno=%d=%s\n", sem_input_name, errno, strerror(errno));
+                fflush(stdout);
+                fflush(stderr);
+                exit(-1);
+            }
         }
-    }
 
-    // Log trace
-    if ((generate_minimal_trace || generate_zip) && trace)
-    {
-        log_minimal_trace();
-    }
-    if (generate_rom_histogram && trace)
-    {
-        log_histogram();
-    }
-    if (generate_main_trace && trace)
-    {
-        log_main_trace();
-    }
+        // Log trace
+        if ((generate_minimal_trace || generate_zip) && trace)
+        {
+            log_minimal_trace();
+        }
+        if (generate_rom_histogram && trace)
+        {
+            log_histogram();
+        }
+        if (generate_main_trace && trace)
+        {
+            log_main_trace();
+        }
 
 #ifdef DEBUG
-    if (verbose) printf("Emulator C end\n");
+        if (verbose) printf("Emulator C end\n");
 #endif
 
-    /************/
-    /* CLEAN UP */
-    /************/
+        /************/
+        /* CLEAN UP */
+        /************/
 
-    // Cleanup ROM
-    result = munmap((void *)ROM_ADDR, ROM_SIZE);
-    if (result == -1)
-    {
-        printf("Failed calling munmap(rom) errno=%d=%s\n", errno, strerror(errno));
-        fflush(stdout);
-        fflush(stderr);
-        exit(-1);
-    }
-
-    // Cleanup RAM
-    result = munmap((void *)RAM_ADDR, RAM_SIZE);
-    if (result == -1)
-    {
-        printf("Failed calling munmap(ram) errno=%d=%s\n", errno, strerror(errno));
-        fflush(stdout);
-        fflush(stderr);
-        exit(-1);
-    }
+        // Cleanup ROM
+        result = munmap((void *)ROM_ADDR, ROM_SIZE);
+        if (result == -1)
+        {
+            printf("Failed calling munmap(rom) errno=%d=%s\n", errno, strerror(errno));
+            fflush(stdout);
+            fflush(stderr);
+            exit(-1);
+        }
 
-    // Cleanup INPUT
-    result = munmap((void *)INPUT_ADDR, input_size);
-    if (result == -1)
-    {
-        printf("Failed calling munmap(input) errno=%d=%s\n", errno, strerror(errno));
-        fflush(stdout);
-        fflush(stderr);
-        exit(-1);
-    }
+        // Cleanup RAM
+        result = munmap((void *)RAM_ADDR, RAM_SIZE);
+        if (result == -1)
+        {
+            printf("Failed calling munmap(ram) errno=%d=%s\n", errno, strerror(errno));
+            fflush(stdout);
+            fflush(stderr);
+            exit(-1);
+        }
 
-    // Cleanup trace
-    if (generate_minimal_trace || generate_rom_histogram || generate_zip)
-    {
-        result = munmap((void *)TRACE_ADDR, trace_size);
+        // Cleanup INPUT
+        result = munmap((void *)INPUT_ADDR, input_size);
         if (result == -1)
         {
-            printf("Failed calling munmap(trace) for size=%lu errno=%d=%s\n", trace_size, errno, strerror(errno));
+            printf("Failed calling munmap(input) errno=%d=%s\n", errno, strerror(errno));
             fflush(stdout);
             fflush(stderr);
             exit(-1);
         }
 
-        // Wait for caller to notify when the trace has been totally consumed
-        if (!is_file)
+        // Cleanup trace
+        if (generate_minimal_trace || generate_rom_histogram || generate_zip)
         {
-            //printf("C sem_wait(%s)...\n", sem_output_name);
-            result = sem_wait(sem_output);
-            //printf("C sem_wait(%s) done\n", sem_output_name);
+            result = munmap((void *)TRACE_ADDR, trace_size);
             if (result == -1)
             {
-                printf("Failed calling sem_wait(%s) errno=%d=%s\n", sem_output_name, errno, strerror(errno));
+                printf("Failed calling munmap(trace) for size=%lu errno=%d=%s\n", trace_size, errno, strerror(errno));
                 fflush(stdout);
                 fflush(stderr);
                 exit(-1);
             }
+
+            // Wait for caller to notify when the trace has been totally consumed
+            if (!is_file)
+            {
+                //printf("C sem_wait(%s)...\n", sem_output_name);
+                result = sem_wait(sem_output);
+                //printf("C sem_wait(%s) done\n", sem_output_name);
+                if (result == -1)
+                {
+                    printf("Failed calling sem_wait(%s) errno=%d=%s\n", sem_output_name, errno, strerror(errno));
+                    fflush(stdout);
+                    fflush(stderr);
+                    exit(-1);
+                }
+            }
         }
-    }
 
-    // Make sure the output shared memory is deleted
-    shm_unlink(shmem_output_name);
+        // Make sure the output shared memory is deleted
+        shm_unlink(shmem_output_name);
+
+        // Close the output shared memory, since it is created again for every request
+        if (shmem_output_fd >= 0)
+        {
+            close(shmem_output_fd);
+            shmem_output_fd = -1;
+        }
+
+        if (!server)
+        {
+            break;
+        }
+        fflush(stdout);
+        fflush(stderr);
+    }
 
     // Cleanup semaphores
     if (!is_file)
@@ -781,7 +982,22 @@ int main(int argc, char *argv[])
                 printf("Failed calling sem_unlink(%s) errno=%d=%s\n", sem_chunk_done_name, errno, strerror(errno));
             }
         }
+        if (server)
+        {
+            result = sem_close(sem_request);
+            if (result == -1)
+            {
+                printf("Failed calling sem_close(%s) errno=%d=%s\n", sem_request_name, errno, strerror(errno));
+            }
+            result = sem_unlink(sem_request_name);
+            if (result == -1)
+            {
+                printf("Failed calling sem_unlink(%s) errno=%d=%s\n", sem_request_name, errno, strerror(errno));
+            }
+        }
     }
+    if (guest_stdout != NULL) fclose(guest_stdout);
+    if (guest_stderr != NULL) fclose(guest_stderr);
     fflush(stdout);
     fflush(stderr);
 }
@@ -880,6 +1096,37 @@ extern void _chunk_done()
     }
 }
 
+// Writes the resource usage of the guest program right after the minimal traces, growing the trace
+// if needed, so that the caller can check its limits: the general purpose memory in use, up to its
+// highest non-zero word [8], the output data size [8] and the number of calls of every precompile
+// [8 * PRECOMPILE_OPS]
+void write_resource_usage (void)
+{
+    uint64_t usage_size = (2 + PRECOMPILE_OPS) * 8;
+    while (MEM_CHUNK_ADDRESS + usage_size > TRACE_ADDR + trace_size)
+    {
+        _realloc_trace();
+    }
+
+    uint64_t heap_size = 0;
+    for (uint64_t address = RAM_ADDR + RAM_SIZE - 8; address >= AVAILABLE_MEM_ADDR; address -= 8)
+    {
+        if (*(uint64_t *)address != 0)
+        {
+            heap_size = address + 8 - AVAILABLE_MEM_ADDR;
+            break;
+        }
+    }
+
+    uint64_t * pUsage = (uint64_t *)MEM_CHUNK_ADDRESS;
+    pUsage[0] = heap_size;
+    pUsage[1] = (uint64_t)(*(uint32_t *)OUTPUT_ADDR) * 4;
+    for (uint64_t i = 0; i < PRECOMPILE_OPS; i++)
+    {
+        pUsage[2 + i] = precompile_calls[i];
+    }
+}
+
 extern void _realloc_trace (void)
 {
     realloc_counter++;
@@ -915,9 +1162,93 @@ extern void _realloc_trace (void)
     ((uint64_t *)new_address)[2] = trace_size;
 }
 
+// Maps the fcall shared memory with room for data_size u64's, unless it is already mapped
+int user_fcall_shmem_map (uint64_t data_size)
+{
+    if (shmem_fcall_address != NULL)
+    {
+        if (data_size <= shmem_fcall_mapped_size)
+        {
+            return 0;
+        }
+        munmap(shmem_fcall_address, sizeof(struct UserFcallShmem) + shmem_fcall_mapped_size * sizeof(uint64_t));
+        shmem_fcall_address = NULL;
+    }
+    void * address = mmap(NULL, sizeof(struct UserFcallShmem) + data_size * sizeof(uint64_t), PROT_READ | PROT_WRITE, MAP_SHARED, shmem_fcall_fd, 0);
+    if (address == MAP_FAILED)
+    {
+        printf("Failed calling mmap(%s) errno=%d=%s\n", shmem_fcall_name, errno, strerror(errno));
+        return -1;
+    }
+    shmem_fcall_address = (struct UserFcallShmem *)address;
+    shmem_fcall_mapped_size = data_size;
+    return 0;
+}
+
+int UserFcall(struct FcallContext * ctx)
+{
+    if (shmem_fcall_address == NULL)
+    {
+        printf("UserFcall() called with function_id=%lu but user fcalls are not enabled; use --user-fcalls\n", ctx->function_id);
+        return -1;
+    }
+
+    // Grow the shared memory if the parameters do not fit
+    if (ctx->params_size > shmem_fcall_address->data_size)
+    {
+        if (ftruncate(shmem_fcall_fd, sizeof(struct UserFcallShmem) + ctx->params_size * sizeof(uint64_t)) != 0)
+        {
+            printf("Failed calling ftruncate(%s) errno=%d=%s\n", shmem_fcall_name, errno, strerror(errno));
+            return -1;
+        }
+        if (user_fcall_shmem_map(ctx->params_size) != 0)
+        {
+            return -1;
+        }
+        shmem_fcall_address->data_size = ctx->params_size;
+    }
+
+    // Send the call to the caller and wait for its result
+    shmem_fcall_address->function_id = ctx->function_id;
+    shmem_fcall_address->params_size = ctx->params_size;
+    memcpy(shmem_fcall_address->data, ctx->params, ctx->params_size * sizeof(uint64_t));
+    if (sem_post(sem_fcall_request) == -1)
+    {
+        printf("Failed calling sem_post(%s) errno=%d=%s\n", sem_fcall_request_name, errno, strerror(errno));
+        return -1;
+    }
+    int result;
+    do
+    {
+        result = sem_wait(sem_fcall_done);
+    } while ((result == -1) && (errno == EINTR));
+    if (result == -1)
+    {
+        printf("Failed calling sem_wait(%s) errno=%d=%s\n", sem_fcall_done_name, errno, strerror(errno));
+        return -1;
+    }
+
+    // The caller sets an invalid result size when the call fails, and could have grown the shared
+    // memory to fit the result
+    uint64_t result_size = shmem_fcall_address->result_size;
+    if ((result_size > shmem_fcall_address->data_size) || (result_size > INT32_MAX))
+    {
+        printf("UserFcall() function_id=%lu failed in the caller\n", ctx->function_id);
+        return -1;
+    }
+    if (user_fcall_shmem_map(shmem_fcall_address->data_size) != 0)
+    {
+        return -1;
+    }
+    FcallResultReserve(ctx, result_size);
+    ctx->result_size = result_size;
+    memcpy(ctx->result, shmem_fcall_address->data, result_size * sizeof(uint64_t));
+    return (int)result_size;
+}
+
 void print_usage (void)
 {
-    char * usage = "Usage: ziskemuasm <input_file> [--gen=0|--generate_fast] [--gen=1|--generate_minimal_trace] [--gen=2|--generate_rom_histogram] [--gen=3|--generate_main_trace] [--gen=4|--generate_chunks] [--gen=6|--generate_zip] [-c <chunk_number>] [-o output off] [-m metrics on] [-t trace on] [-tt trace on] [-h/--help print this]";
+    char * usage = "Usage: ziskemuasm <input_file> [--gen=0|--generate_fast] [--gen=1|--generate_minimal_trace] [--gen=2|--generate_rom_histogram] [--gen=3|--generate_main_trace] [--gen=4|--generate_chunks] [--gen=6|--generate_zip] [-c <chunk_number>] [--server keep serving shared memory requests] [--user-fcalls forward user fcalls to the caller] [--guest-stdout <file>] [--guest-stderr <file>] [--signature <file> <begin_address> <end_address>] [-o output off] [-m metrics on] [-t trace on] [-tt trace on] [-h/--help print this]";
 #ifdef DEBUG
     printf("%s [-v verbose on] [-k keccak trace on]\n", usage);
 #else
@@ -981,6 +1312,16 @@ void parse_arguments(int argc, char *argv[])
                 number_of_selected_generation_methods++;
                 continue;
             }
+            if (strcmp(argv[i], "--server") == 0)
+            {
+                server = true;
+                continue;
+            }
+            if (strcmp(argv[i], "--user-fcalls") == 0)
+            {
+                user_fcalls = true;
+                continue;
+            }
             if (strcmp(argv[i], "-o") == 0)
             {
                 output = false;
@@ -1034,6 +1375,61 @@ void parse_arguments(int argc, char *argv[])
                 print_usage();
                 continue;
             }
+            if ( (strcmp(argv[i], "--guest-stdout") == 0) || (strcmp(argv[i], "--guest-stderr") == 0) )
+            {
+                bool is_stderr = (strcmp(argv[i], "--guest-stderr") == 0);
+                i++;
+                if (i >= argc)
+                {
+                    printf("Detected argument %s in the last position; please provide file name after it\n", argv[i-1]);
+                    print_usage();
+                    exit(-1);
+                }
+                FILE * file = fopen(argv[i], "wb");
+                if (file == NULL)
+                {
+                    printf("Failed calling fopen(%s) errno=%d=%s\n", argv[i], errno, strerror(errno));
+                    exit(-1);
+                }
+                // Flush every guest line, so that the file can be followed while the program runs
+                setvbuf(file, NULL, _IOLBF, 0);
+                if (is_stderr)
+                {
+                    guest_stderr = file;
+                }
+                else
+                {
+                    guest_stdout = file;
+                }
+                continue;
+            }
+            if (strcmp(argv[i], "--signature") == 0)
+            {
+                if (i + 3 >= argc)
+                {
+                    printf("Detected argument --signature without all its parameters; please provide file name, begin address and end address after it\n");
+                    print_usage();
+                    exit(-1);
+                }
+                signature_file = fopen(argv[i + 1], "w");
+                if (signature_file == NULL)
+                {
+                    printf("Failed calling fopen(%s) errno=%d=%s\n", argv[i + 1], errno, strerror(errno));
+                    exit(-1);
+                }
+                char *endptr_begin;
+                char *endptr_end;
+                signature_begin = strtoull(argv[i + 2], &endptr_begin, 0);
+                signature_end = strtoull(argv[i + 3], &endptr_end, 0);
+                if ((*endptr_begin != '\0') || (*endptr_end != '\0') || (endptr_begin == argv[i + 2]) || (endptr_end == argv[i + 3]))
+                {
+                    printf("Error: Invalid signature address range: %s %s\n", argv[i + 2], argv[i + 3]);
+                    print_usage();
+                    exit(-1);
+                }
+                i += 3;
+                continue;
+            }
             if (strcmp(argv[i], "-c") == 0)
             {
                 i++;
@@ -1,7 +1,7 @@
 use clap::Parser;
 use std::{fmt::Write, process};
 use zisk_common::EmuTrace;
-use ziskemu::{EmuOptions, Emulator, ZiskEmulator};
+use ziskemu::{EmuOptions, Emulator, ZiskEmulator, ZiskEmulatorErr};
 
 fn main() {
     // Create a emulator options instance based on arguments or default values
@@ -27,6 +27,22 @@ fn main() {
             });
             // print!("Result: 0x{}", hex_string);
         }
+        Err(ZiskEmulatorErr::GuestFailed(status)) => {
+            // Exit with the guest exit code, as a native program would, but never with a code that
+            // the shell would read as a success
+            if let Some(message) = &status.panic_message {
+                eprintln!("Guest program panicked: {}", message);
+            }
+            let exit_code = i32::try_from(status.exit_code).unwrap_or(1);
+            process::exit(if exit_code & 0xff == 0 { 1 } else { exit_code });
+        }
+        Err(
+            e @ (ZiskEmulatorErr::ResourceLimitExceeded(_)
+            | ZiskEmulatorErr::ConformanceFailed { .. }),
+        ) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
         Err(e) => {
             eprintln!("Error during emulation: {:?}", e);
             process::exit(1);
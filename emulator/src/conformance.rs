@@ -0,0 +1,540 @@
+//! Runs a riscof-style conformance suite: a directory of RISC-V test ELF files.
+//!
+//! Every test is emulated and its signature, i.e. the memory region between the `begin_signature`
+//! and `end_signature` symbols of the ELF file, is compared against the reference signature, one
+//! 32-bit hexadecimal word per line.  Riscof lays out every test as `<test>/dut/my.elf` with its
+//! reference in `<test>/ref/Reference-sail_c_simulator.signature`; a standalone `<name>.elf` can
+//! also provide its reference as `<name>.signature` next to it.
+//!
+//! Tests run with the Rust emulator, and optionally with the assembly emulator, which is rebuilt
+//! from `emulator-asm` for every test.
+
+use std::{
+    fmt::Write,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use zisk_common::EmuTrace;
+use zisk_core::{find_elf_symbol, AsmGenerationMethod, Riscv2zisk};
+
+use crate::{Emu, EmuOptions, ZiskEmulator};
+
+/// Emulator used to run the conformance tests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConformanceBackend {
+    /// The Rust emulator, `Emu`
+    Rust,
+    /// The assembly emulator, built with the makefile of this `emulator-asm` directory
+    Asm { emulator_asm_dir: PathBuf },
+}
+
+impl ConformanceBackend {
+    /// Returns the backend name, as shown in the results
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConformanceBackend::Rust => "rust",
+            ConformanceBackend::Asm { .. } => "asm",
+        }
+    }
+}
+
+/// A conformance test: an ELF file and its reference signature
+#[derive(Debug, Clone)]
+pub struct ConformanceTest {
+    /// Test name, relative to the suite directory
+    pub name: String,
+    pub elf: PathBuf,
+    /// Reference signature file, if found
+    pub reference: Option<PathBuf>,
+}
+
+/// Outcome of a conformance test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConformanceStatus {
+    Passed,
+    /// The signature does not match the reference one
+    Failed(String),
+    /// The test could not be run, e.g. the emulation did not complete
+    Error(String),
+}
+
+/// Result of running a conformance test with a backend
+#[derive(Debug, Clone)]
+pub struct ConformanceResult {
+    pub name: String,
+    pub backend: &'static str,
+    pub status: ConformanceStatus,
+    pub duration: Duration,
+}
+
+/// Results of a conformance suite run
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub results: Vec<ConformanceResult>,
+}
+
+impl ConformanceReport {
+    /// Returns the number of results with this status kind
+    fn count(&self, f: impl Fn(&ConformanceStatus) -> bool) -> usize {
+        self.results.iter().filter(|r| f(&r.status)).count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.count(|s| *s == ConformanceStatus::Passed)
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|s| matches!(s, ConformanceStatus::Failed(_)))
+    }
+
+    pub fn errors(&self) -> usize {
+        self.count(|s| matches!(s, ConformanceStatus::Error(_)))
+    }
+
+    /// Formats the results as a pass/fail table, followed by a summary line
+    pub fn to_table(&self) -> String {
+        let name_width = self.results.iter().map(|r| r.name.len()).max().unwrap_or(0).max(4);
+        let mut table = String::new();
+        writeln!(
+            table,
+            "{:<6} {:<7} {:>9}  {:<name_width$}  DETAILS",
+            "RESULT", "BACKEND", "TIME(ms)", "TEST"
+        )
+        .unwrap();
+        for result in &self.results {
+            let (label, details) = match &result.status {
+                ConformanceStatus::Passed => ("PASS", ""),
+                ConformanceStatus::Failed(details) => ("FAIL", details.as_str()),
+                ConformanceStatus::Error(details) => ("ERROR", details.as_str()),
+            };
+            let line = format!(
+                "{:<6} {:<7} {:>9}  {:<name_width$}  {}",
+                label,
+                result.backend,
+                result.duration.as_millis(),
+                result.name,
+                details
+            );
+            writeln!(table, "{}", line.trim_end()).unwrap();
+        }
+        write!(
+            table,
+            "{} passed, {} failed, {} errors, {} total",
+            self.passed(),
+            self.failed(),
+            self.errors(),
+            self.results.len()
+        )
+        .unwrap();
+        table
+    }
+
+    /// Formats the results as a JUnit XML report, with one test suite per backend
+    pub fn to_junit_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+        let mut backends: Vec<&str> = self.results.iter().map(|r| r.backend).collect();
+        backends.dedup();
+        for backend in backends {
+            let results: Vec<&ConformanceResult> =
+                self.results.iter().filter(|r| r.backend == backend).collect();
+            let failures =
+                results.iter().filter(|r| matches!(r.status, ConformanceStatus::Failed(_))).count();
+            let errors =
+                results.iter().filter(|r| matches!(r.status, ConformanceStatus::Error(_))).count();
+            let time: f64 = results.iter().map(|r| r.duration.as_secs_f64()).sum();
+            writeln!(
+                xml,
+                "  <testsuite name=\"ziskemu.{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+                backend,
+                results.len(),
+                failures,
+                errors,
+                time
+            )
+            .unwrap();
+            for result in results {
+                write!(
+                    xml,
+                    "    <testcase classname=\"ziskemu.{}\" name=\"{}\" time=\"{:.3}\"",
+                    backend,
+                    xml_escape(&result.name),
+                    result.duration.as_secs_f64()
+                )
+                .unwrap();
+                match &result.status {
+                    ConformanceStatus::Passed => xml.push_str("/>\n"),
+                    ConformanceStatus::Failed(details) => writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\"/>\n    </testcase>",
+                        xml_escape(details)
+                    )
+                    .unwrap(),
+                    ConformanceStatus::Error(details) => writeln!(
+                        xml,
+                        ">\n      <error message=\"{}\"/>\n    </testcase>",
+                        xml_escape(details)
+                    )
+                    .unwrap(),
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Escapes a string to be used as XML text or attribute value
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Finds the conformance tests of a directory: all its ELF files, except the riscof reference
+/// ones, sorted by name
+pub fn find_conformance_tests(directory: &Path) -> std::io::Result<Vec<ConformanceTest>> {
+    let mut tests = Vec::new();
+    for file in ZiskEmulator::list_files(&directory.display().to_string())? {
+        let elf = PathBuf::from(file);
+        if elf.extension().and_then(|ext| ext.to_str()) != Some("elf")
+            || elf.components().any(|component| component.as_os_str() == "ref")
+        {
+            continue;
+        }
+
+        // Riscof tests are named after the directory that contains `dut`
+        let parent = elf.parent().unwrap_or(Path::new(""));
+        let (test_path, reference) = if parent.file_name().is_some_and(|name| name == "dut") {
+            let test_dir = parent.parent().unwrap_or(Path::new(""));
+            (test_dir.to_path_buf(), find_reference(&test_dir.join("ref")))
+        } else {
+            let reference = elf.with_extension("signature");
+            (elf.with_extension(""), reference.is_file().then_some(reference))
+        };
+        let name = test_path.strip_prefix(directory).unwrap_or(&test_path).display().to_string();
+
+        tests.push(ConformanceTest { name, elf, reference });
+    }
+    tests.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tests)
+}
+
+/// Returns the first signature file of a riscof `ref` directory
+fn find_reference(ref_dir: &Path) -> Option<PathBuf> {
+    let mut signatures: Vec<PathBuf> = fs::read_dir(ref_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "signature"))
+        .collect();
+    signatures.sort();
+    signatures.into_iter().next()
+}
+
+/// Runs a conformance test with a backend, emulating at most `max_steps` steps
+pub fn run_conformance_test(
+    test: &ConformanceTest,
+    backend: &ConformanceBackend,
+    work_dir: &Path,
+    max_steps: u64,
+) -> ConformanceResult {
+    let start = Instant::now();
+    let status = match check_conformance_test(test, backend, work_dir, max_steps) {
+        Ok(None) => ConformanceStatus::Passed,
+        Ok(Some(mismatch)) => ConformanceStatus::Failed(mismatch),
+        Err(e) => ConformanceStatus::Error(e),
+    };
+    ConformanceResult {
+        name: test.name.clone(),
+        backend: backend.name(),
+        status,
+        duration: start.elapsed(),
+    }
+}
+
+/// Runs the conformance tests of `directory` with every backend, printing the result of every test
+/// as soon as it completes when `verbose`
+pub fn run_conformance_suite(
+    directory: &Path,
+    backends: &[ConformanceBackend],
+    max_steps: u64,
+    verbose: bool,
+) -> std::io::Result<ConformanceReport> {
+    let tests = find_conformance_tests(directory)?;
+    let work_dir = std::env::temp_dir().join(format!("ziskemu-conformance-{}", std::process::id()));
+    fs::create_dir_all(&work_dir)?;
+
+    let mut report = ConformanceReport::default();
+    for backend in backends {
+        for test in &tests {
+            let result = run_conformance_test(test, backend, &work_dir, max_steps);
+            if verbose {
+                println!("{} {}: {:?}", result.backend, result.name, result.status);
+            }
+            report.results.push(result);
+        }
+    }
+
+    let _ = fs::remove_dir_all(&work_dir);
+    Ok(report)
+}
+
+/// Runs a conformance test, returning the first signature mismatch, if any
+fn check_conformance_test(
+    test: &ConformanceTest,
+    backend: &ConformanceBackend,
+    work_dir: &Path,
+    max_steps: u64,
+) -> Result<Option<String>, String> {
+    let reference = test.reference.as_ref().ok_or("reference signature not found")?;
+    let reference = fs::read_to_string(reference)
+        .map_err(|e| format!("could not read {}: {}", reference.display(), e))?;
+
+    let symbol = |name: &str| {
+        find_elf_symbol(&test.elf, name)
+            .map_err(|e| format!("could not read ELF file: {}", e))?
+            .ok_or_else(|| format!("symbol {} not found", name))
+    };
+    let begin = symbol("begin_signature")?;
+    let end = symbol("end_signature")?;
+    if end < begin {
+        return Err(format!("invalid signature region {:#x} - {:#x}", begin, end));
+    }
+
+    let signature = match backend {
+        ConformanceBackend::Rust => rust_signature(&test.elf, begin, end, max_steps)?,
+        ConformanceBackend::Asm { emulator_asm_dir } => {
+            asm_signature(&test.elf, begin, end, emulator_asm_dir, work_dir)?
+        }
+    };
+
+    Ok(compare_signatures(&signature, &reference))
+}
+
+/// Compares a signature against a reference, returning the first mismatch, if any
+fn compare_signatures(signature: &[String], reference: &str) -> Option<String> {
+    let reference: Vec<String> = reference
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect();
+    if let Some(i) = (0..signature.len().min(reference.len()))
+        .find(|i| signature[*i].trim().to_lowercase() != reference[*i])
+    {
+        return Some(format!("word {}: expected {}, got {}", i, reference[i], signature[i].trim()));
+    }
+    (signature.len() != reference.len()).then(|| {
+        format!("{} signature words, but {} reference words", signature.len(), reference.len())
+    })
+}
+
+/// Emulates an ELF file with the Rust emulator and returns its signature words
+fn rust_signature(elf: &Path, begin: u64, end: u64, max_steps: u64) -> Result<Vec<String>, String> {
+    let elf = elf.display().to_string();
+    let run = || -> Result<Vec<String>, String> {
+        let rom = Riscv2zisk::new(elf).run().map_err(|e| e.to_string())?;
+        let mut emu = Emu::new(&rom);
+        let options = EmuOptions { max_steps, ..Default::default() };
+        emu.run(Vec::new(), &options, None::<Box<dyn Fn(EmuTrace)>>);
+        if !emu.terminated() {
+            return Err(format!("emulation not completed after {} steps", emu.number_of_steps()));
+        }
+
+        // The signature is written by the test, so it must be in RAM
+        let mem = &emu.ctx.inst_ctx.mem;
+        if begin < mem.write_section.start || end > mem.write_section.end {
+            return Err(format!("signature region {:#x} - {:#x} is out of RAM", begin, end));
+        }
+        Ok((begin..end).step_by(4).map(|addr| format!("{:08x}", mem.read(addr, 4))).collect())
+    };
+
+    // A test must not abort the whole suite, e.g. if it uses an unsupported instruction
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(format!("emulator panicked: {}", message))
+    })
+}
+
+/// Builds the assembly emulator of an ELF file, runs it and returns its signature words
+fn asm_signature(
+    elf: &Path,
+    begin: u64,
+    end: u64,
+    emulator_asm_dir: &Path,
+    work_dir: &Path,
+) -> Result<Vec<String>, String> {
+    let asm_file = work_dir.join("emu.asm");
+    let bin_file = work_dir.join("ziskemuasm");
+    let input_file = work_dir.join("empty_input.bin");
+    let signature_file = work_dir.join("signature");
+
+    Riscv2zisk::new(elf.display().to_string())
+        .runfile(&asm_file, AsmGenerationMethod::AsmFast, false)
+        .map_err(|e| e.to_string())?;
+
+    let make_clean = Command::new("make").arg("clean").current_dir(emulator_asm_dir).output();
+    let make = make_clean.and_then(|_| {
+        Command::new("make")
+            .arg(format!("EMU_PATH={}", asm_file.display()))
+            .arg(format!("OUT_PATH={}", bin_file.display()))
+            .current_dir(emulator_asm_dir)
+            .output()
+    });
+    match make {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            // Report the first error, rather than make's final summary
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let error =
+                stderr.lines().find(|line| line.contains("error")).or(stderr.lines().last());
+            return Err(format!("make failed: {}", error.unwrap_or("")));
+        }
+        Err(e) => return Err(format!("could not run make: {}", e)),
+    }
+
+    fs::write(&input_file, []).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&signature_file);
+    let output = Command::new(&bin_file)
+        .arg(&input_file)
+        .arg("--gen=0")
+        .arg("-o")
+        .arg("--signature")
+        .arg(&signature_file)
+        .arg(format!("{:#x}", begin))
+        .arg(format!("{:#x}", end))
+        .output()
+        .map_err(|e| format!("could not run {}: {}", bin_file.display(), e))?;
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(format!(
+            "assembly emulator failed with {}: {}",
+            output.status,
+            stdout.lines().last().unwrap_or("")
+        ));
+    }
+
+    let signature = fs::read_to_string(&signature_file)
+        .map_err(|e| format!("could not read signature: {}", e))?;
+    Ok(signature.lines().map(|line| line.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn result(name: &str, backend: &'static str, status: ConformanceStatus) -> ConformanceResult {
+        ConformanceResult { name: name.to_string(), backend, status, duration: Duration::ZERO }
+    }
+
+    #[test]
+    fn test_compare_signatures_match() {
+        // Case, surrounding blanks and empty reference lines are ignored
+        let reference = "DEADBEEF\n  00000001 \n\n";
+        assert_eq!(compare_signatures(&words(&["deadbeef", "00000001"]), reference), None);
+        assert_eq!(compare_signatures(&[], ""), None);
+    }
+
+    #[test]
+    fn test_compare_signatures_mismatch() {
+        let reference = "deadbeef\n00000001\n00000002\n";
+        assert_eq!(
+            compare_signatures(&words(&["deadbeef", "00000003", "00000002"]), reference),
+            Some("word 1: expected 00000001, got 00000003".to_string())
+        );
+
+        // A differing word is reported before a differing length
+        assert_eq!(
+            compare_signatures(&words(&["00000000"]), reference),
+            Some("word 0: expected deadbeef, got 00000000".to_string())
+        );
+        assert_eq!(
+            compare_signatures(&words(&["deadbeef", "00000001"]), reference),
+            Some("2 signature words, but 3 reference words".to_string())
+        );
+        assert_eq!(
+            compare_signatures(&words(&["deadbeef", "00000001", "00000002", "0"]), reference),
+            Some("4 signature words, but 3 reference words".to_string())
+        );
+    }
+
+    #[test]
+    fn test_report_counts_and_table() {
+        let report = ConformanceReport {
+            results: vec![
+                result("add", "rust", ConformanceStatus::Passed),
+                result("sub", "rust", ConformanceStatus::Failed("word 0".to_string())),
+                result("add", "asm", ConformanceStatus::Error("make failed".to_string())),
+            ],
+        };
+        assert_eq!((report.passed(), report.failed(), report.errors()), (1, 1, 1));
+
+        let table = report.to_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].starts_with("FAIL   rust") && lines[2].ends_with("sub   word 0"));
+        assert_eq!(lines[4], "1 passed, 1 failed, 1 errors, 3 total");
+    }
+
+    #[test]
+    fn test_report_to_junit_xml() {
+        let report = ConformanceReport {
+            results: vec![
+                result("rv64i/add", "rust", ConformanceStatus::Passed),
+                result("rv64i/sub", "rust", ConformanceStatus::Failed("a < b & \"c\"".to_string())),
+                result("rv64i/add", "asm", ConformanceStatus::Error("make failed".to_string())),
+            ],
+        };
+        let expected = concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<testsuites>\n",
+            "  <testsuite name=\"ziskemu.rust\" tests=\"2\" failures=\"1\" errors=\"0\" time=\"0.000\">\n",
+            "    <testcase classname=\"ziskemu.rust\" name=\"rv64i/add\" time=\"0.000\"/>\n",
+            "    <testcase classname=\"ziskemu.rust\" name=\"rv64i/sub\" time=\"0.000\">\n",
+            "      <failure message=\"a &lt; b &amp; &quot;c&quot;\"/>\n",
+            "    </testcase>\n",
+            "  </testsuite>\n",
+            "  <testsuite name=\"ziskemu.asm\" tests=\"1\" failures=\"0\" errors=\"1\" time=\"0.000\">\n",
+            "    <testcase classname=\"ziskemu.asm\" name=\"rv64i/add\" time=\"0.000\">\n",
+            "      <error message=\"make failed\"/>\n",
+            "    </testcase>\n",
+            "  </testsuite>\n",
+            "</testsuites>\n",
+        );
+        assert_eq!(report.to_junit_xml(), expected);
+    }
+
+    #[test]
+    fn test_report_to_junit_xml_empty() {
+        assert_eq!(
+            ConformanceReport::default().to_junit_xml(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n</testsuites>\n"
+        );
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("<a href='x'>&</a>"), "&lt;a href=&apos;x&apos;&gt;&amp;&lt;/a&gt;");
+        assert_eq!(xml_escape("plain text"), "plain text");
+    }
+}
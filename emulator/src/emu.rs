@@ -11,11 +11,14 @@ use zisk_common::{
 // use zisk_core::SRC_SP;
 use data_bus::DataBusTrait;
 use zisk_common::{EmuTrace, EmuTraceStart};
-use zisk_core::zisk_ops::ZiskOp;
+use zisk_core::zisk_ops::{precompiled_mem_reads_count, ZiskOp};
 use zisk_core::{
-    EmulationMode, InstContext, Mem, ZiskInst, ZiskRom, OUTPUT_ADDR, ROM_ENTRY, SRC_C, SRC_IMM,
-    SRC_IND, SRC_MEM, SRC_REG, SRC_STEP, STORE_IND, STORE_MEM, STORE_NONE, STORE_REG,
+    EmulationMode, GuestExitStatus, InstContext, Mem, ResourceLimitExceeded, ResourceLimits,
+    ZiskInst, ZiskOperationType, ZiskRom, AVAILABLE_MEM_ADDR, OUTPUT_ADDR, RESOURCE_LIMITS_CHECK_PERIOD, ROM_ENTRY,
+    SRC_C, SRC_IMM, SRC_IND, SRC_MEM, SRC_REG, SRC_STEP, STORE_IND, STORE_MEM, STORE_NONE,
+    STORE_REG,
 };
+use zisk_trace::{MemAccess, TraceStep};
 
 /// ZisK emulator structure, containing the ZisK rom, the list of ZisK operations, and the
 /// execution context
@@ -1218,8 +1221,24 @@ impl<'a> Emu<'a> {
         options: &EmuOptions,
         callback: Option<impl Fn(EmuTrace)>,
     ) {
-        // Context, where the state of the execution is stored and modified at every execution step
-        self.ctx = self.create_emu_context(inputs.clone());
+        // Context, where the state of the execution is stored and modified at every execution step,
+        // keeping the tracer installed before the run, if any
+        let tracer = self.ctx.tracer.take();
+        self.ctx = self.create_emu_context(inputs);
+        self.ctx.tracer = tracer;
+
+        // Capture the guest streams that must be written to a file
+        self.ctx.inst_ctx.mem.console.capture_stdout = options.guest_stdout.is_some();
+        self.ctx.inst_ctx.mem.console.capture_stderr = options.guest_stderr.is_some();
+
+        self.run_context(options, callback);
+    }
+
+    /// Run the whole program over the current emulation context, which must have been created
+    /// with `create_emu_context()`
+    pub fn run_context(&mut self, options: &EmuOptions, callback: Option<impl Fn(EmuTrace)>) {
+        // Install the host functions serving the user-defined fcalls
+        self.ctx.inst_ctx.fcall.user_fcalls = options.user_fcalls.clone();
 
         // Check that callback is provided if trace_steps is specified
         if options.trace_steps.is_some() {
@@ -1281,6 +1300,10 @@ impl<'a> Emu<'a> {
         // Store the stats option into the emulator context
         self.ctx.do_stats = options.stats;
 
+        // Get the resource limits to check at every step, if any
+        let limits = options.resource_limits();
+        let check_limits = limits.check_during_emulation();
+
         // While not done
         while !self.ctx.inst_ctx.end {
             if options.verbose {
@@ -1306,9 +1329,16 @@ impl<'a> Emu<'a> {
             if self.ctx.inst_ctx.step >= options.max_steps {
                 break;
             }
+            if check_limits && !self.check_step_resource_limits(&limits) {
+                break;
+            }
 
             // Execute the current step
+            let (pc, step) = (self.ctx.inst_ctx.pc, self.ctx.inst_ctx.step);
             self.step(options, &callback);
+            if self.ctx.tracer.is_some() {
+                self.trace_step(pc, step);
+            }
 
             // Only trace after finishing a riscV instruction
             if options.tracerv && (self.ctx.inst_ctx.pc & 0b11) == 0 {
@@ -1345,12 +1375,6 @@ impl<'a> Emu<'a> {
 
             // println!("Emu::run() done ctx.pc={}", self.ctx.pc); // 2147483828
         }
-
-        // Print stats report
-        if options.stats {
-            let report = self.ctx.stats.report();
-            println!("{}", report);
-        }
     }
 
     /// Run the whole program
@@ -1362,6 +1386,7 @@ impl<'a> Emu<'a> {
     ) -> Vec<EmuTrace> {
         // Context, where the state of the execution is stored and modified at every execution step
         self.ctx = self.create_emu_context(inputs);
+        self.ctx.inst_ctx.fcall.user_fcalls = options.user_fcalls.clone();
 
         // Init pc to the rom entry address
         self.ctx.trace.start_state.pc = ROM_ENTRY;
@@ -1374,7 +1399,19 @@ impl<'a> Emu<'a> {
 
         let mut emu_traces = Vec::new();
 
+        // Get the resource limits to check at every step, if any
+        let limits = options.resource_limits();
+        let check_limits = limits.check_during_emulation();
+
         while !self.ctx.inst_ctx.end {
+            // Stop the execution if the program exceeded its resource limits; the caller gets the
+            // error from `check_resource_limits()`
+            if self.ctx.inst_ctx.step >= options.max_steps
+                || (check_limits && !self.check_step_resource_limits(&limits))
+            {
+                break;
+            }
+
             let block_idx = self.ctx.inst_ctx.step / par_options.num_steps as u64;
             let is_my_block =
                 block_idx % par_options.num_threads as u64 == par_options.thread_id as u64;
@@ -1400,10 +1437,6 @@ impl<'a> Emu<'a> {
                 }
 
                 self.par_step_my_block(emu_traces.last_mut().unwrap());
-
-                if self.ctx.inst_ctx.step >= options.max_steps {
-                    panic!("Emu::par_run() reached max_steps");
-                }
             }
         }
 
@@ -1427,7 +1460,18 @@ impl<'a> Emu<'a> {
 
         let mut emu_traces = Vec::new();
 
+        // Get the resource limits to check at every step, if any
+        let limits = options.resource_limits();
+        let check_limits = limits.check_during_emulation();
+
         while !self.ctx.inst_ctx.end {
+            // Stop the execution if the program exceeded its resource limits
+            if self.ctx.inst_ctx.step >= options.max_steps
+                || (check_limits && !self.check_step_resource_limits(&limits))
+            {
+                break;
+            }
+
             // Check if is the first step of a new block
             if self.ctx.inst_ctx.step % par_options.num_steps as u64 == 0 {
                 emu_traces.push(EmuTrace {
@@ -1446,10 +1490,6 @@ impl<'a> Emu<'a> {
             }
 
             self.par_step_my_block(emu_traces.last_mut().unwrap());
-
-            if self.ctx.inst_ctx.step >= options.max_steps {
-                panic!("Emu::par_run() reached max_steps");
-            }
         }
 
         emu_traces
@@ -1478,6 +1518,14 @@ impl<'a> Emu<'a> {
         // Build the 'b' register value  based on the source specified by the current instruction
         self.source_b(instruction);
 
+        // Read the dma parameters before the operation, which can overwrite them
+        if self.ctx.do_stats && instruction.op_type == ZiskOperationType::Dma {
+            let params = std::array::from_fn(|i| {
+                self.ctx.inst_ctx.mem.read(self.ctx.inst_ctx.b + 8 * i as u64, 8)
+            });
+            self.ctx.stats.on_dma(instruction, &params);
+        }
+
         // Call the operation
         (instruction.func)(&mut self.ctx.inst_ctx);
 
@@ -1681,8 +1729,8 @@ impl<'a> Emu<'a> {
             self.ctx.inst_ctx.precompiled.input_data.clear();
             self.ctx.inst_ctx.precompiled.output_data.clear();
 
-            // round_up => (size + 7) >> 3
-            let number_of_mem_reads = (instruction.input_size + 7) >> 3;
+            let number_of_mem_reads =
+                precompiled_mem_reads_count(instruction, &mem_reads[*mem_reads_index..]);
             for _ in 0..number_of_mem_reads {
                 let mem_read = mem_reads[*mem_reads_index];
                 *mem_reads_index += 1;
@@ -1738,6 +1786,21 @@ impl<'a> Emu<'a> {
             ExtOperationData::OperationSecp256k1DblData(data) => {
                 data_bus.write_to_bus(OPERATION_BUS_ID, &data);
             }
+            ExtOperationData::OperationCurve25519ModData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationEd25519DblData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationEd25519AddData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationBlake2brData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationDmaData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
         }
 
         // #[cfg(feature = "sp")]
@@ -1818,7 +1881,8 @@ impl<'a> Emu<'a> {
         if instruction.input_size > 0 {
             self.ctx.inst_ctx.precompiled.input_data.clear();
             self.ctx.inst_ctx.precompiled.output_data.clear();
-            let number_of_mem_reads = (instruction.input_size + 7) >> 3;
+            let number_of_mem_reads =
+                precompiled_mem_reads_count(instruction, &mem_reads[*mem_reads_index..]);
             for _ in 0..number_of_mem_reads {
                 let mem_read = mem_reads[*mem_reads_index];
                 *mem_reads_index += 1;
@@ -1854,6 +1918,21 @@ impl<'a> Emu<'a> {
             ExtOperationData::OperationSecp256k1DblData(data) => {
                 data_bus.write_to_bus(OPERATION_BUS_ID, &data);
             }
+            ExtOperationData::OperationCurve25519ModData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationEd25519DblData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationEd25519AddData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationBlake2brData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
+            ExtOperationData::OperationDmaData(data) => {
+                data_bus.write_to_bus(OPERATION_BUS_ID, &data);
+            }
         }
 
         // Get rom bus data
@@ -1893,7 +1972,8 @@ impl<'a> Emu<'a> {
         if instruction.input_size > 0 {
             self.ctx.inst_ctx.precompiled.input_data.clear();
             self.ctx.inst_ctx.precompiled.output_data.clear();
-            let number_of_mem_reads = (instruction.input_size + 7) >> 3;
+            let number_of_mem_reads =
+                precompiled_mem_reads_count(instruction, &mem_reads[*mem_reads_index..]);
             for _ in 0..number_of_mem_reads {
                 let mem_read = mem_reads[*mem_reads_index];
                 *mem_reads_index += 1;
@@ -2100,6 +2180,142 @@ impl<'a> Emu<'a> {
         output
     }
 
+    /// Gets the exit status written by the guest program
+    pub fn get_exit_status(&self) -> GuestExitStatus {
+        GuestExitStatus::from_mem(&self.ctx.inst_ctx.mem)
+    }
+
+    /// Checks the resource limits that must be checked before executing the current step,
+    /// storing the exceeded limit in the context, if any.  Returns false if the execution must
+    /// stop.
+    #[inline(always)]
+    fn check_step_resource_limits(&mut self, limits: &ResourceLimits) -> bool {
+        let result = self.count_precompile_call(limits).and_then(|_| {
+            if self.ctx.inst_ctx.step % RESOURCE_LIMITS_CHECK_PERIOD == 0 {
+                self.check_memory_limits(limits)
+            } else {
+                Ok(())
+            }
+        });
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.ctx.limit_exceeded = Some(e);
+                false
+            }
+        }
+    }
+
+    /// Counts the call of the current instruction, if it is a precompile with a limited number of
+    /// calls, and checks its limit
+    #[inline(always)]
+    fn count_precompile_call(
+        &mut self,
+        limits: &ResourceLimits,
+    ) -> Result<(), ResourceLimitExceeded> {
+        if limits.max_precompile_calls.is_empty() {
+            return Ok(());
+        }
+        let instruction = self.rom.get_instruction(self.ctx.inst_ctx.pc);
+        let Ok(op) = ZiskOp::try_from_code(instruction.op) else {
+            return Ok(());
+        };
+        if !limits.max_precompile_calls.contains_key(&op) {
+            return Ok(());
+        }
+        let calls = self.ctx.precompile_calls.entry(op).or_insert(0);
+        *calls += 1;
+        limits.check_precompile_calls(op, *calls)
+    }
+
+    /// Checks the heap size and the output data size limits against the current memory contents.
+    /// The heap size is the general purpose memory in use, up to the highest address written.
+    pub fn check_memory_limits(
+        &self,
+        limits: &ResourceLimits,
+    ) -> Result<(), ResourceLimitExceeded> {
+        let heap_size = self.ctx.inst_ctx.mem.write_end.saturating_sub(AVAILABLE_MEM_ADDR);
+        limits.check_heap_size(heap_size)?;
+        limits.check_output_size(self.ctx.inst_ctx.mem.read(OUTPUT_ADDR, 4) * 4)
+    }
+
+    /// Checks that the emulation did not exceed any of the resource limits, once it is done
+    pub fn check_resource_limits(
+        &self,
+        limits: &ResourceLimits,
+    ) -> Result<(), ResourceLimitExceeded> {
+        if let Some(e) = &self.ctx.limit_exceeded {
+            return Err(e.clone());
+        }
+        if !self.terminated() {
+            limits.check_steps(self.number_of_steps())?;
+        }
+        self.check_memory_limits(limits)
+    }
+
+    /// Feeds the tracer with the step that has just been executed at `pc`, including the memory
+    /// accesses of its a and b sources and its c store
+    fn trace_step(&mut self, pc: u64, step: u64) {
+        let instruction = self.rom.get_instruction(pc);
+        let inst_ctx = &self.ctx.inst_ctx;
+
+        let mut mem = Vec::new();
+        if instruction.a_src == SRC_MEM {
+            let mut addr = instruction.a_offset_imm0;
+            if instruction.a_use_sp_imm1 != 0 {
+                addr += inst_ctx.sp;
+            }
+            mem.push(MemAccess::read(addr, 8, inst_ctx.a));
+        }
+        match instruction.b_src {
+            SRC_MEM => {
+                let mut addr = instruction.b_offset_imm0;
+                if instruction.b_use_sp_imm1 != 0 {
+                    addr += inst_ctx.sp;
+                }
+                mem.push(MemAccess::read(addr, 8, inst_ctx.b));
+            }
+            SRC_IND => {
+                let mut addr = (inst_ctx.a as i64 + instruction.b_offset_imm0 as i64) as u64;
+                if instruction.b_use_sp_imm1 != 0 {
+                    addr += inst_ctx.sp;
+                }
+                mem.push(MemAccess::read(addr, instruction.ind_width, inst_ctx.b));
+            }
+            _ => {}
+        }
+        if instruction.store == STORE_MEM || instruction.store == STORE_IND {
+            let value = if instruction.store_ra {
+                (pc as i64 + instruction.jmp_offset2) as u64
+            } else {
+                inst_ctx.c
+            };
+            let mut addr = instruction.store_offset;
+            if instruction.store_use_sp {
+                addr += inst_ctx.sp as i64;
+            }
+            let (addr, width) = if instruction.store == STORE_IND {
+                ((addr + inst_ctx.a as i64) as u64, instruction.ind_width)
+            } else {
+                (addr as u64, 8)
+            };
+            mem.push(MemAccess::write(addr, width, value));
+        }
+
+        let trace_step = TraceStep {
+            step,
+            pc,
+            op: instruction.op,
+            flag: inst_ctx.flag,
+            a: inst_ctx.a,
+            b: inst_ctx.b,
+            c: inst_ctx.c,
+            mem,
+        };
+        let regs = self.get_regs_array();
+        self.ctx.tracer.as_mut().unwrap().on_step(trace_step, &regs);
+    }
+
     /// Gets the log traces
     pub fn get_tracerv(&self) -> Vec<String> {
         self.ctx.tracerv.clone()
@@ -0,0 +1,339 @@
+//! Builder-style API to embed the emulator in other programs.
+//!
+//! Unlike `ZiskEmulator::emulate()`, which is driven by `EmuOptions` file paths and logs to the
+//! console, the builder takes the ELF file and the input data from memory and returns all the
+//! results in an `EmulationResult`, without touching the filesystem or the console.  The guest
+//! standard output and standard error are captured into the result, and can also be streamed line
+//! by line to a callback.
+//!
+//! ```ignore
+//! let result = ZiskEmulator::builder().elf_bytes(&elf).input(&input).max_steps(1 << 30).run()?;
+//! ```
+
+use crate::{
+    Emu, EmuOptions, ErrWrongArguments, StatsCostModel, StatsReport, ZiskEmulator, ZiskEmulatorErr,
+};
+use std::sync::Arc;
+use zisk_common::EmuTrace;
+use zisk_core::{
+    elf_bytes2rom, elf_bytes2rom_optimized, FcallDecode, FcallEncode, GuestExitStatus, GuestStream,
+    MemConsole, ResourceLimit, ResourceLimitExceeded, ResourceLimits, UserFcalls, ZiskRom,
+};
+
+/// Final status of an emulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmulationExitStatus {
+    /// The program reached the end instruction, with the provided guest exit status
+    Completed(GuestExitStatus),
+    /// The emulation was stopped after executing the maximum number of steps
+    MaxStepsReached,
+    /// The emulation was stopped because the program exceeded another resource limit
+    ResourceLimitExceeded(ResourceLimitExceeded),
+}
+
+impl EmulationExitStatus {
+    /// Returns true if the program reached the end instruction with exit code 0
+    pub fn success(&self) -> bool {
+        matches!(self, EmulationExitStatus::Completed(status) if status.success())
+    }
+}
+
+/// Results of an emulation
+#[derive(Debug, Clone)]
+pub struct EmulationResult {
+    /// Output data, as written by the program
+    pub output: Vec<u8>,
+    /// Public outputs, as 32-bits values
+    pub public_outputs: Vec<u32>,
+    /// Guest standard output
+    pub stdout: Vec<u8>,
+    /// Guest standard error
+    pub stderr: Vec<u8>,
+    /// Number of executed steps
+    pub steps: u64,
+    /// Statistics report, if requested
+    pub stats: Option<StatsReport>,
+    /// Final status of the emulation
+    pub exit_status: EmulationExitStatus,
+}
+
+/// Program to emulate, either an ELF file to transpile or an already transpiled ROM
+enum EmulationProgram<'a> {
+    Elf(&'a [u8]),
+    Rom(&'a ZiskRom),
+}
+
+/// Builds and runs an in-process emulation, see `ZiskEmulator::builder()`
+pub struct ZiskEmulatorBuilder<'a> {
+    program: Option<EmulationProgram<'a>>,
+    input: Vec<u8>,
+    limits: ResourceLimits,
+    stats: Option<StatsCostModel>,
+    optimize: bool,
+    console: MemConsole,
+    user_fcalls: UserFcalls,
+}
+
+impl ZiskEmulator {
+    /// Returns a builder to configure and run an emulation from in-memory data
+    pub fn builder<'a>() -> ZiskEmulatorBuilder<'a> {
+        ZiskEmulatorBuilder::default()
+    }
+}
+
+impl Default for ZiskEmulatorBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            program: None,
+            input: Vec::new(),
+            limits: ResourceLimits::default(),
+            stats: None,
+            optimize: false,
+            console: MemConsole::captured(),
+            user_fcalls: UserFcalls::default(),
+        }
+    }
+}
+
+impl<'a> ZiskEmulatorBuilder<'a> {
+    /// Sets the ELF file data of the program to emulate
+    pub fn elf_bytes(mut self, elf: &'a [u8]) -> Self {
+        self.program = Some(EmulationProgram::Elf(elf));
+        self
+    }
+
+    /// Sets an already transpiled ROM as the program to emulate
+    pub fn rom(mut self, rom: &'a ZiskRom) -> Self {
+        self.program = Some(EmulationProgram::Rom(rom));
+        self
+    }
+
+    /// Sets the input data of the program
+    pub fn input(mut self, input: &[u8]) -> Self {
+        self.input = input.to_vec();
+        self
+    }
+
+    /// Sets the maximum number of steps to execute
+    pub fn max_steps(mut self, max_steps: u64) -> Self {
+        self.limits.max_steps = max_steps;
+        self
+    }
+
+    /// Sets all the resource limits of the program, including the maximum number of steps
+    pub fn limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Collects statistics, calculating their costs with the provided cost model
+    pub fn stats(mut self, cost_model: StatsCostModel) -> Self {
+        self.stats = Some(cost_model);
+        self
+    }
+
+    /// Runs the peephole optimizer over the transpiled ELF file.  The prover always uses the
+    /// original ROM, so the optimizer only speeds up the emulation.
+    pub fn optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Calls `callback` with every complete line written by the guest to its standard output or
+    /// standard error, as soon as it is written
+    pub fn on_output_line(
+        mut self,
+        callback: impl FnMut(GuestStream, &str) + Send + Sync + 'static,
+    ) -> Self {
+        self.console.set_line_callback(Box::new(callback));
+        self
+    }
+
+    /// Serves the user-defined fcall `id` with `f`, which receives and returns raw `u64` words.
+    /// Panics if `id` is not in the user fcall range.
+    pub fn user_fcall(
+        mut self,
+        id: u16,
+        f: impl Fn(&[u64]) -> Result<Vec<u64>, String> + Send + Sync + 'static,
+    ) -> Self {
+        self.user_fcalls.register(id, f);
+        self
+    }
+
+    /// Serves the user-defined fcall `id` with `f`, which receives the parameters and returns the
+    /// result with the types declared in the guest `ziskos_user_fcall!`
+    pub fn user_fcall_typed<P: FcallDecode, R: FcallEncode>(
+        mut self,
+        id: u16,
+        f: impl Fn(P) -> R + Send + Sync + 'static,
+    ) -> Self {
+        self.user_fcalls.register_typed(id, f);
+        self
+    }
+
+    /// Serves the user-defined fcalls with the functions of an existing registry, replacing the
+    /// ones registered so far
+    pub fn user_fcalls(mut self, user_fcalls: UserFcalls) -> Self {
+        self.user_fcalls = user_fcalls;
+        self
+    }
+
+    /// Runs the emulation
+    pub fn run(self) -> Result<EmulationResult, ZiskEmulatorErr> {
+        let transpiled_rom;
+        let rom = match self.program {
+            Some(EmulationProgram::Rom(rom)) => rom,
+            Some(EmulationProgram::Elf(elf)) => {
+                let result = if self.optimize {
+                    elf_bytes2rom_optimized(elf).map(|(rom, _)| rom)
+                } else {
+                    elf_bytes2rom(elf)
+                };
+                transpiled_rom = result.map_err(|e| ZiskEmulatorErr::Unknown(e.to_string()))?;
+                &transpiled_rom
+            }
+            None => {
+                return Err(ZiskEmulatorErr::WrongArguments(ErrWrongArguments::new(
+                    "ELF file data or ROM must be provided",
+                )))
+            }
+        };
+
+        self.limits.check_input_size(self.input.len() as u64)?;
+
+        let mut options = EmuOptions { stats: self.stats.is_some(), ..Default::default() };
+        options.set_resource_limits(&self.limits);
+        if !self.user_fcalls.is_empty() {
+            options.user_fcalls = Some(Arc::new(self.user_fcalls));
+        }
+
+        let mut emu = Emu::new(rom);
+        emu.ctx = emu.create_emu_context(self.input);
+        emu.ctx.inst_ctx.mem.console = self.console;
+        emu.run_context(&options, None::<Box<dyn Fn(EmuTrace)>>);
+
+        let console = &mut emu.ctx.inst_ctx.mem.console;
+        console.flush();
+        let stdout = std::mem::take(&mut console.stdout);
+        let stderr = std::mem::take(&mut console.stderr);
+
+        let exit_status = match emu.check_resource_limits(&self.limits) {
+            Err(ResourceLimitExceeded { limit: ResourceLimit::Steps, .. }) => {
+                EmulationExitStatus::MaxStepsReached
+            }
+            Err(e) => EmulationExitStatus::ResourceLimitExceeded(e),
+            Ok(()) if emu.terminated() => EmulationExitStatus::Completed(emu.get_exit_status()),
+            Ok(()) => EmulationExitStatus::MaxStepsReached,
+        };
+
+        Ok(EmulationResult {
+            output: emu.get_output_8(),
+            public_outputs: emu.get_output_32(),
+            stdout,
+            stderr,
+            steps: emu.number_of_steps(),
+            stats: self.stats.map(|cost_model| emu.ctx.stats.summary(&cost_model)),
+            exit_status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_core::{ZiskInstBuilder, AVAILABLE_MEM_ADDR, GUEST_EXIT_ADDR, OUTPUT_ADDR, ROM_ENTRY};
+
+    /// Returns a BIOS-only ROM storing every `(value, address)` pair in memory and ending, or
+    /// looping forever if `end` is false
+    fn rom(stores: &[(u64, u64)], end: bool) -> ZiskRom {
+        let mut insts = Vec::new();
+        for (value, addr) in stores {
+            let mut zib = ZiskInstBuilder::new(ROM_ENTRY + 4 * insts.len() as u64);
+            zib.src_a("imm", 0, false);
+            zib.src_b("imm", *value, false);
+            zib.op("copyb").unwrap();
+            zib.store("mem", *addr as i64, false, false);
+            zib.j(4, 4);
+            zib.build();
+            insts.push(zib.i);
+        }
+        let mut zib = ZiskInstBuilder::new(ROM_ENTRY + 4 * insts.len() as u64);
+        zib.src_a("imm", 0, false);
+        zib.src_b("imm", 0, false);
+        zib.op("copyb").unwrap();
+        zib.j(0, 0);
+        if end {
+            zib.end();
+        }
+        zib.build();
+        insts.push(zib.i);
+        ZiskRom {
+            sorted_pc_list: insts.iter().map(|i| i.paddr).collect(),
+            rom_entry_instructions: insts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_run_completed() {
+        let rom = rom(&[], true);
+        let result = ZiskEmulator::builder().rom(&rom).run().unwrap();
+        assert_eq!(result.exit_status, EmulationExitStatus::Completed(GuestExitStatus::default()));
+        assert!(result.exit_status.success());
+        assert_eq!(result.steps, 1);
+    }
+
+    #[test]
+    fn test_run_exit_code() {
+        let rom = rom(&[(3, GUEST_EXIT_ADDR)], true);
+        let result = ZiskEmulator::builder().rom(&rom).run().unwrap();
+        let status = GuestExitStatus { exit_code: 3, panic_message: None };
+        assert_eq!(result.exit_status, EmulationExitStatus::Completed(status));
+        assert!(!result.exit_status.success());
+        assert_eq!(result.steps, 2);
+    }
+
+    #[test]
+    fn test_run_max_steps_reached() {
+        let rom = rom(&[], false);
+        let result = ZiskEmulator::builder().rom(&rom).max_steps(10).run().unwrap();
+        assert_eq!(result.exit_status, EmulationExitStatus::MaxStepsReached);
+        assert!(!result.exit_status.success());
+        assert_eq!(result.steps, 10);
+    }
+
+    #[test]
+    fn test_run_resource_limit_exceeded() {
+        // 10 output words
+        let rom = rom(&[(10, OUTPUT_ADDR)], true);
+        let limits = ResourceLimits { max_output_size: Some(8), ..Default::default() };
+        let result = ZiskEmulator::builder().rom(&rom).limits(limits).run().unwrap();
+        let exceeded = ResourceLimitExceeded { limit: ResourceLimit::OutputSize, used: 40, max: 8 };
+        assert_eq!(result.exit_status, EmulationExitStatus::ResourceLimitExceeded(exceeded));
+        assert!(!result.exit_status.success());
+    }
+
+    #[test]
+    fn test_run_heap_limit_exceeded() {
+        // The heap size is measured from the memory written by the program, whatever it reports
+        let rom = rom(&[(1, AVAILABLE_MEM_ADDR + 0x1000)], true);
+        let limits = ResourceLimits { max_heap_size: Some(0x1008), ..Default::default() };
+        let result = ZiskEmulator::builder().rom(&rom).limits(limits).run().unwrap();
+        assert!(result.exit_status.success());
+
+        let limits = ResourceLimits { max_heap_size: Some(0x1000), ..Default::default() };
+        let result = ZiskEmulator::builder().rom(&rom).limits(limits).run().unwrap();
+        let exceeded =
+            ResourceLimitExceeded { limit: ResourceLimit::HeapSize, used: 0x1008, max: 0x1000 };
+        assert_eq!(result.exit_status, EmulationExitStatus::ResourceLimitExceeded(exceeded));
+    }
+
+    #[test]
+    fn test_run_errors() {
+        assert!(matches!(ZiskEmulator::builder().run(), Err(ZiskEmulatorErr::WrongArguments(_))));
+
+        let rom = rom(&[], true);
+        let limits = ResourceLimits { max_input_size: Some(1), ..Default::default() };
+        assert!(ZiskEmulator::builder().rom(&rom).limits(limits).input(&[1, 2]).run().is_err());
+    }
+}
@@ -1,8 +1,11 @@
-use crate::Stats;
+use std::collections::BTreeMap;
+
+use crate::{EmuTracer, Stats};
 use zisk_common::EmuTrace;
 use zisk_core::{
-    EmulationMode, FcallInstContext, InstContext, Mem, PrecompiledInstContext, INPUT_ADDR,
-    MAX_INPUT_SIZE, RAM_ADDR, RAM_SIZE, REGS_IN_MAIN_TOTAL_NUMBER, ROM_ENTRY,
+    zisk_ops::ZiskOp, EmulationMode, FcallInstContext, InstContext, Mem, PrecompiledInstContext,
+    ResourceLimitExceeded, INPUT_ADDR, MAX_INPUT_SIZE, RAM_ADDR, RAM_SIZE,
+    REGS_IN_MAIN_TOTAL_NUMBER, ROM_ENTRY,
 };
 
 /// ZisK emulator context data container, storing the state of the emulation
@@ -19,6 +22,12 @@ pub struct EmuContext {
     pub trace: EmuTrace,
     pub do_stats: bool,
     pub stats: Stats,
+    /// Number of calls of the precompiles with a limited number of calls
+    pub precompile_calls: BTreeMap<ZiskOp, u64>,
+    /// Resource limit that stopped the emulation, if any
+    pub limit_exceeded: Option<ResourceLimitExceeded>,
+    /// Execution tracer, if a trace or a commit log was requested
+    pub tracer: Option<EmuTracer>,
 }
 
 /// RisK emulator context implementation
@@ -51,6 +60,9 @@ impl EmuContext {
             last_callback_step: 0,
             do_stats: false,
             stats: Stats::default(),
+            precompile_calls: BTreeMap::new(),
+            limit_exceeded: None,
+            tracer: None,
         };
 
         // Check the input data size is inside the proper range
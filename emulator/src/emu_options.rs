@@ -1,8 +1,8 @@
 //! Zisk emulator options
 
 use clap::Parser;
-use std::fmt;
-use zisk_core::DEFAULT_MAX_STEPS_STR;
+use std::{fmt, sync::Arc};
+use zisk_core::{zisk_ops::ZiskOp, ResourceLimits, UserFcalls, DEFAULT_MAX_STEPS_STR};
 
 pub const ZISK_VERSION_MESSAGE: &str = concat!(
     env!("CARGO_PKG_VERSION"),
@@ -37,7 +37,8 @@ pub struct EmuOptions {
     /// Sets the print step period in number of steps
     #[clap(short, long, value_name = "PRINT_STEP", default_value = "0")]
     pub print_step: Option<u64>,
-    /// Sets the trace output file
+    /// Writes a binary trace of every ZisK step to this file, in the format of the `zisk-trace`
+    /// crate
     #[clap(short, long, value_name = "TRACE_FILE")]
     pub trace: Option<String>,
     /// Sets the verbose mode
@@ -64,6 +65,56 @@ pub struct EmuOptions {
     /// Generates minimal traces.  Enabled with `-g`.
     #[clap(short = 'g', long, value_name = "MINIMAL_TRACES", default_value = "false")]
     pub generate_minimal_traces: bool,
+    /// Sets the statistics format: text, json or csv.  Requires `-x`.
+    #[clap(long, value_name = "STATS_FORMAT", requires = "stats", value_parser = ["text", "json", "csv"])]
+    pub stats_format: Option<String>,
+    /// Writes the statistics to this file instead of the console.  Requires `-x`.
+    #[clap(long, value_name = "STATS_FILE", requires = "stats")]
+    pub stats_file: Option<String>,
+    /// Loads the statistics cost model from this JSON file.  Requires `-x`.
+    #[clap(long, value_name = "COST_MODEL_FILE", requires = "stats")]
+    pub cost_model: Option<String>,
+    /// Runs the peephole optimizer over the transpiled ROM.  Enabled with `-O`.  The prover always
+    /// uses the original ROM, so it cannot be combined with `-g`.
+    #[clap(short = 'O', long, value_name = "OPTIMIZE", default_value = "false")]
+    pub optimize: bool,
+    /// Verifies the optimized ROM by co-running it with the original one before the emulation.
+    /// Implies `--optimize`.
+    #[clap(long, value_name = "VERIFY_OPTIMIZER", default_value = "false")]
+    pub verify_optimizer: bool,
+    /// Writes the guest standard output to this file instead of the console
+    #[clap(long, value_name = "GUEST_STDOUT_FILE")]
+    pub guest_stdout: Option<String>,
+    /// Writes the guest standard error to this file instead of the console
+    #[clap(long, value_name = "GUEST_STDERR_FILE")]
+    pub guest_stderr: Option<String>,
+    /// Sets the maximum heap size of the guest program, in bytes
+    #[clap(long, value_name = "MAX_HEAP_SIZE")]
+    pub max_heap_size: Option<u64>,
+    /// Sets the maximum input data size, in bytes
+    #[clap(long, value_name = "MAX_INPUT_SIZE")]
+    pub max_input_size: Option<u64>,
+    /// Sets the maximum output data size, in bytes
+    #[clap(long, value_name = "MAX_OUTPUT_SIZE")]
+    pub max_output_size: Option<u64>,
+    /// Sets the maximum number of calls of a precompile, e.g. `keccak=1000`.  Can be repeated.
+    #[clap(long, value_name = "PRECOMPILE=CALLS", value_parser = ResourceLimits::parse_precompile_limit)]
+    pub max_precompile_calls: Vec<(ZiskOp, u64)>,
+    /// Writes a spike-style commit log of every RISC-V instruction to this file
+    #[clap(long, value_name = "COMMIT_LOG_FILE")]
+    pub commit_log: Option<String>,
+    /// Writes the results of a conformance suite, run when the ELF path is a directory, to this
+    /// JUnit XML file
+    #[clap(long, value_name = "JUNIT_FILE")]
+    pub junit: Option<String>,
+    /// Also runs the conformance suite with the assembly emulator, built in this `emulator-asm`
+    /// directory
+    #[clap(long, value_name = "EMULATOR_ASM_DIR")]
+    pub asm_emulator: Option<String>,
+    /// Host functions serving the user-defined fcalls of the guest.  Only available through the
+    /// API, e.g. `ZiskEmulatorBuilder::user_fcall()`.
+    #[clap(skip)]
+    pub user_fcalls: Option<Arc<UserFcalls>>,
 }
 
 impl Default for EmuOptions {
@@ -85,6 +136,21 @@ impl Default for EmuOptions {
             tracerv: false,
             stats: false,
             generate_minimal_traces: false,
+            stats_format: None,
+            stats_file: None,
+            cost_model: None,
+            optimize: false,
+            verify_optimizer: false,
+            guest_stdout: None,
+            guest_stderr: None,
+            max_heap_size: None,
+            max_input_size: None,
+            max_output_size: None,
+            max_precompile_calls: Vec::new(),
+            commit_log: None,
+            junit: None,
+            asm_emulator: None,
+            user_fcalls: None,
         }
     }
 }
@@ -106,7 +172,22 @@ impl fmt::Display for EmuOptions {
         writeln!(f, "STATS: {:?}", self.stats)?;
         writeln!(f, "TRACERV: {:?}", self.tracerv)?;
         writeln!(f, "LOG_STEP: {:?}", self.log_step)?;
-        writeln!(f, "MINIMAL_TRACES: {:?}", self.generate_minimal_traces)
+        writeln!(f, "MINIMAL_TRACES: {:?}", self.generate_minimal_traces)?;
+        writeln!(f, "STATS_FORMAT: {:?}", self.stats_format)?;
+        writeln!(f, "STATS_FILE: {:?}", self.stats_file)?;
+        writeln!(f, "COST_MODEL: {:?}", self.cost_model)?;
+        writeln!(f, "OPTIMIZE: {:?}", self.optimize)?;
+        writeln!(f, "VERIFY_OPTIMIZER: {:?}", self.verify_optimizer)?;
+        writeln!(f, "GUEST_STDOUT: {:?}", self.guest_stdout)?;
+        writeln!(f, "GUEST_STDERR: {:?}", self.guest_stderr)?;
+        writeln!(f, "MAX_HEAP_SIZE: {:?}", self.max_heap_size)?;
+        writeln!(f, "MAX_INPUT_SIZE: {:?}", self.max_input_size)?;
+        writeln!(f, "MAX_OUTPUT_SIZE: {:?}", self.max_output_size)?;
+        writeln!(f, "MAX_PRECOMPILE_CALLS: {:?}", self.max_precompile_calls)?;
+        writeln!(f, "COMMIT_LOG: {:?}", self.commit_log)?;
+        writeln!(f, "JUNIT: {:?}", self.junit)?;
+        writeln!(f, "ASM_EMULATOR: {:?}", self.asm_emulator)?;
+        writeln!(f, "USER_FCALLS: {:?}", self.user_fcalls)
     }
 }
 
@@ -116,10 +197,33 @@ impl EmuOptions {
         self.trace_steps.is_none()
             && (self.print_step.is_none() || (self.print_step.unwrap() == 0))
             && self.trace.is_none()
+            && self.commit_log.is_none()
             && !self.log_step
             && !self.verbose
             && !self.tracerv
             && !self.stats
             && !self.generate_minimal_traces
+            && !self.resource_limits().check_during_emulation()
+    }
+
+    /// Returns the resource limits of the guest program
+    pub fn resource_limits(&self) -> ResourceLimits {
+        ResourceLimits {
+            max_steps: self.max_steps,
+            max_heap_size: self.max_heap_size,
+            max_input_size: self.max_input_size,
+            max_output_size: self.max_output_size,
+            max_precompile_calls: self.max_precompile_calls.iter().copied().collect(),
+        }
+    }
+
+    /// Sets the resource limits of the guest program
+    pub fn set_resource_limits(&mut self, limits: &ResourceLimits) {
+        self.max_steps = limits.max_steps;
+        self.max_heap_size = limits.max_heap_size;
+        self.max_input_size = limits.max_input_size;
+        self.max_output_size = limits.max_output_size;
+        self.max_precompile_calls =
+            limits.max_precompile_calls.iter().map(|(op, calls)| (*op, *calls)).collect();
     }
 }
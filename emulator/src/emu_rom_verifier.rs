@@ -0,0 +1,108 @@
+//! Verifies that an optimized ROM is semantically equivalent to the original one, for a given
+//! input, by co-running both of them in lockstep.
+//!
+//! The optimized ROM executes fewer steps, so both emulations can only be compared at the
+//! boundaries of the RISC-V instructions, i.e. when the pc is 4-bytes aligned.  Every time the
+//! optimized emulation reaches an aligned pc, the original emulation is advanced until it reaches
+//! the same pc, and then their registers are compared.  When both emulations end, their registers,
+//! public outputs and RAM contents are compared.
+
+use crate::{Emu, ZiskEmulatorErr};
+use zisk_core::ZiskRom;
+
+/// Maximum number of original ROM steps that can be executed to reach the pc of the optimized
+/// ROM, before considering that they diverged
+const MAX_CATCH_UP_STEPS: u64 = 1024;
+
+/// Result of a successful co-run verification
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RomVerifierReport {
+    /// Steps executed by the original ROM
+    pub original_steps: u64,
+    /// Steps executed by the optimized ROM
+    pub optimized_steps: u64,
+    /// Number of times the registers of both emulations were compared
+    pub sync_points: u64,
+}
+
+/// Co-runs the original and the optimized ROMs with the provided inputs, returning an error at
+/// the first divergence found
+pub fn verify_optimized_rom(
+    rom: &ZiskRom,
+    optimized_rom: &ZiskRom,
+    inputs: &[u8],
+    max_steps: u64,
+) -> Result<RomVerifierReport, ZiskEmulatorErr> {
+    let mut original = Emu::new(rom);
+    original.ctx = original.create_emu_context(inputs.to_vec());
+    let mut optimized = Emu::new(optimized_rom);
+    optimized.ctx = optimized.create_emu_context(inputs.to_vec());
+
+    let mut report = RomVerifierReport::default();
+
+    while !optimized.terminated() {
+        if optimized.number_of_steps() >= max_steps {
+            return Err(ZiskEmulatorErr::EmulationNoCompleted);
+        }
+        optimized.step_fast();
+
+        // Only aligned pcs have a counterpart in the original ROM
+        let pc = optimized.ctx.inst_ctx.pc;
+        if optimized.terminated() || (pc & 0x03) != 0 {
+            continue;
+        }
+
+        // Advance the original emulation until it reaches the same pc
+        let mut catch_up_steps = 0;
+        loop {
+            if original.terminated() || catch_up_steps == MAX_CATCH_UP_STEPS {
+                return Err(mismatch(&optimized, "original ROM did not reach the same pc"));
+            }
+            original.step_fast();
+            catch_up_steps += 1;
+            if original.ctx.inst_ctx.pc == pc {
+                break;
+            }
+        }
+
+        if original.ctx.inst_ctx.regs != optimized.ctx.inst_ctx.regs {
+            return Err(mismatch(&optimized, "registers differ"));
+        }
+        report.sync_points += 1;
+    }
+
+    // Complete the original emulation, which can still be executing the last instructions
+    let mut catch_up_steps = 0;
+    while !original.terminated() {
+        if catch_up_steps == MAX_CATCH_UP_STEPS {
+            return Err(mismatch(&optimized, "original ROM did not end"));
+        }
+        original.step_fast();
+        catch_up_steps += 1;
+    }
+
+    if original.ctx.inst_ctx.regs != optimized.ctx.inst_ctx.regs {
+        return Err(mismatch(&optimized, "final registers differ"));
+    }
+    if original.get_output_8() != optimized.get_output_8() {
+        return Err(mismatch(&optimized, "public outputs differ"));
+    }
+    if original.ctx.inst_ctx.mem.write_section.buffer
+        != optimized.ctx.inst_ctx.mem.write_section.buffer
+    {
+        return Err(mismatch(&optimized, "RAM contents differ"));
+    }
+
+    report.original_steps = original.number_of_steps();
+    report.optimized_steps = optimized.number_of_steps();
+    Ok(report)
+}
+
+/// Builds a mismatch error at the current step of the optimized emulation
+fn mismatch(optimized: &Emu, reason: &str) -> ZiskEmulatorErr {
+    ZiskEmulatorErr::OptimizerMismatch {
+        step: optimized.number_of_steps(),
+        pc: optimized.ctx.inst_ctx.pc,
+        reason: reason.to_string(),
+    }
+}
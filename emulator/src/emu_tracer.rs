@@ -0,0 +1,241 @@
+//! Writes the ZisK trace and the RISC-V commit log of an emulation, in the formats of the
+//! `zisk-trace` crate.
+//!
+//! A RISC-V instruction at address `pc` is transpiled into ZisK instructions at `pc`, `pc + 1`,
+//! `pc + 2`, etc., so the commit log line of a RISC-V instruction is built from the ZisK steps
+//! executed in that address range: its written registers are the ones whose value changed, and
+//! its memory accesses are the ones of those steps.
+
+use std::{collections::BTreeMap, fs::File, io::BufWriter, path::Path};
+
+use zisk_core::{zisk_ops::ZiskOp, ElfDumpInfo, ROM_ADDR};
+use zisk_trace::{CommitLogWriter, RiscvCommit, TraceStep, TraceWriter};
+
+use crate::{EmuOptions, ZiskEmulatorErr};
+
+/// Execution tracer, fed with every step executed by the emulator
+pub struct EmuTracer {
+    trace: Option<TraceWriter<BufWriter<File>>>,
+    commit_log: Option<CommitLogWriter<BufWriter<File>>>,
+    /// RISC-V instruction words, by address, if the ELF file is known
+    riscv_words: BTreeMap<u64, u32>,
+    /// RISC-V instruction being executed, and the registers before it
+    commit: Option<(RiscvCommit, [u64; 32])>,
+    /// Registers after the last executed step
+    regs: [u64; 32],
+    /// First error found while writing, reported by `finish()`
+    error: Option<String>,
+}
+
+impl EmuTracer {
+    /// Creates the tracer requested by the options, if any
+    pub fn from_options(options: &EmuOptions) -> Result<Option<Self>, ZiskEmulatorErr> {
+        if options.trace.is_none() && options.commit_log.is_none() {
+            return Ok(None);
+        }
+
+        let trace = match &options.trace {
+            Some(path) => {
+                let ops: Vec<(u8, &str)> = (0..=u8::MAX)
+                    .filter_map(|code| ZiskOp::try_from_code(code).ok())
+                    .map(|op| (op.code(), op.name()))
+                    .collect();
+                Some(TraceWriter::create(Path::new(path), &ops).map_err(|e| {
+                    ZiskEmulatorErr::Unknown(format!("Could not create trace file {}: {}", path, e))
+                })?)
+            }
+            None => None,
+        };
+
+        let mut riscv_words = BTreeMap::new();
+        let commit_log = match &options.commit_log {
+            Some(path) => {
+                if let Some(elf) = &options.elf {
+                    let elf_info = ElfDumpInfo::from_elf_file(Path::new(elf))
+                        .map_err(|e| ZiskEmulatorErr::Unknown(e.to_string()))?;
+                    riscv_words =
+                        elf_info.code.iter().map(|(addr, (word, _))| (*addr, *word)).collect();
+                }
+                Some(CommitLogWriter::create(Path::new(path)).map_err(|e| {
+                    ZiskEmulatorErr::Unknown(format!(
+                        "Could not create commit log file {}: {}",
+                        path, e
+                    ))
+                })?)
+            }
+            None => None,
+        };
+
+        Ok(Some(Self { trace, commit_log, riscv_words, commit: None, regs: [0; 32], error: None }))
+    }
+
+    /// Records an executed step, given the registers after it
+    pub fn on_step(&mut self, step: TraceStep, regs: &[u64; 32]) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(trace) = &mut self.trace {
+            if let Err(e) = trace.write_step(&step) {
+                self.error = Some(format!("Could not write trace: {}", e));
+                return;
+            }
+        }
+        if self.commit_log.is_some() {
+            self.on_commit_log_step(step);
+        }
+        self.regs = *regs;
+    }
+
+    fn on_commit_log_step(&mut self, step: TraceStep) {
+        // A RISC-V instruction starts at every word-aligned program address
+        let starts_instruction = if self.riscv_words.is_empty() {
+            step.pc >= ROM_ADDR && (step.pc & 0b11) == 0
+        } else {
+            self.riscv_words.contains_key(&step.pc)
+        };
+        if starts_instruction {
+            self.write_commit();
+            let insn = self.riscv_words.get(&step.pc).copied().unwrap_or(0);
+            self.commit =
+                Some((RiscvCommit { pc: step.pc, insn, ..Default::default() }, self.regs));
+        }
+
+        // Ignore the steps out of the current instruction, e.g. the BIOS ones
+        if let Some((commit, _)) = &mut self.commit {
+            if step.pc >= commit.pc && step.pc < commit.pc + 4 {
+                commit.mem.extend(step.mem);
+            }
+        }
+    }
+
+    /// Writes the commit log line of the current RISC-V instruction, if any
+    fn write_commit(&mut self) {
+        let (Some(commit_log), Some((mut commit, regs_before))) =
+            (&mut self.commit_log, self.commit.take())
+        else {
+            return;
+        };
+        commit.reg_writes = (1..32)
+            .filter(|i| self.regs[*i] != regs_before[*i])
+            .map(|i| (i as u8, self.regs[i]))
+            .collect();
+        if let Err(e) = commit_log.write_commit(&commit) {
+            self.error = Some(format!("Could not write commit log: {}", e));
+        }
+    }
+
+    /// Writes the pending data and flushes the files
+    pub fn finish(mut self) -> Result<(), ZiskEmulatorErr> {
+        if self.error.is_none() {
+            self.write_commit();
+        }
+        if let Some(e) = self.error {
+            return Err(ZiskEmulatorErr::Unknown(e));
+        }
+        if let Some(trace) = self.trace {
+            trace
+                .finish()
+                .map_err(|e| ZiskEmulatorErr::Unknown(format!("Could not write trace: {}", e)))?;
+        }
+        if let Some(commit_log) = self.commit_log {
+            commit_log.finish().map_err(|e| {
+                ZiskEmulatorErr::Unknown(format!("Could not write commit log: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_trace::MemAccess;
+
+    /// Feeds a commit log tracer with `steps`, given as (pc, memory accesses, registers after
+    /// the step), and returns the lines of the commit log
+    fn commit_log(
+        test: &str,
+        riscv_words: &[(u64, u32)],
+        steps: Vec<(u64, Vec<MemAccess>, [u64; 32])>,
+    ) -> Vec<String> {
+        let path = std::env::temp_dir().join(format!(
+            "ziskemu_commit_log_{}_{}",
+            test,
+            std::process::id()
+        ));
+        let mut tracer = EmuTracer {
+            trace: None,
+            commit_log: Some(CommitLogWriter::create(&path).unwrap()),
+            riscv_words: riscv_words.iter().copied().collect(),
+            commit: None,
+            regs: [0; 32],
+            error: None,
+        };
+        for (i, (pc, mem, regs)) in steps.into_iter().enumerate() {
+            tracer.on_step(TraceStep { step: i as u64, pc, mem, ..Default::default() }, &regs);
+        }
+        tracer.finish().unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        log.lines().map(str::to_string).collect()
+    }
+
+    fn regs(values: &[(usize, u64)]) -> [u64; 32] {
+        let mut regs = [0; 32];
+        for (i, value) in values {
+            regs[*i] = *value;
+        }
+        regs
+    }
+
+    #[test]
+    fn test_commit_log_groups_steps() {
+        let lines = commit_log(
+            "groups_steps",
+            &[],
+            vec![
+                // BIOS step, out of any RISC-V instruction
+                (0x1000, vec![MemAccess::read(0xa0000000, 8, 1)], regs(&[(1, 0x10)])),
+                // RISC-V instruction transpiled into two ZisK steps
+                (ROM_ADDR, vec![MemAccess::read(0xa0000000, 8, 7)], regs(&[(1, 0x10), (5, 7)])),
+                (
+                    ROM_ADDR + 1,
+                    vec![MemAccess::write(0xa0000008, 4, 0x1234)],
+                    regs(&[(1, 0x10), (5, 7), (6, 9)]),
+                ),
+                (ROM_ADDR + 4, vec![], regs(&[(1, 0x10), (5, 7), (6, 9), (7, 1)])),
+            ],
+        );
+        assert_eq!(
+            lines,
+            [
+                "core   0: 3 0x0000000080000000 (0x00000000) x5  0x0000000000000007 \
+                 x6  0x0000000000000009 mem 0x00000000a0000000 mem 0x00000000a0000008 0x00001234",
+                "core   0: 3 0x0000000080000004 (0x00000000) x7  0x0000000000000001",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commit_log_riscv_words() {
+        // With the ELF file, instructions start at the addresses of its code, e.g. compressed
+        // instructions 2 bytes apart, and get their instruction words
+        let lines = commit_log(
+            "riscv_words",
+            &[(ROM_ADDR, 0x00000297), (ROM_ADDR + 2, 0x4501)],
+            vec![
+                (ROM_ADDR, vec![], regs(&[(5, ROM_ADDR)])),
+                (ROM_ADDR + 1, vec![], regs(&[(5, ROM_ADDR)])),
+                (ROM_ADDR + 2, vec![], regs(&[(5, ROM_ADDR), (10, 0)])),
+            ],
+        );
+        assert_eq!(
+            lines,
+            [
+                "core   0: 3 0x0000000080000000 (0x00000297) x5  0x0000000080000000",
+                "core   0: 3 0x0000000080000002 (0x00004501)",
+            ]
+        );
+    }
+}
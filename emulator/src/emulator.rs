@@ -5,17 +5,20 @@
 //!  \
 //!   emulate()
 //!    \
-//!     process_directory() -> lists *dut*.elf files
+//!     process_directory() -> runs the conformance tests of a directory, see `conformance`
+//!     process_elf_file()
 //!      \
-//!       process_elf_file()
-//!        \
-//!         - Riscv2zisk::run()
-//!         - process_rom()
-//!            \
-//!             Emu::run()
+//!       - Riscv2zisk::run() or Riscv2zisk::run_optimized()
+//!       - verify_optimized_rom(), if requested
+//!       - process_rom()
+//!          \
+//!           Emu::run()
 //! ```
 
-use crate::{Emu, EmuOptions, ErrWrongArguments, ParEmuOptions, ZiskEmulatorErr};
+use crate::{
+    run_conformance_suite, verify_optimized_rom, ConformanceBackend, Emu, EmuOptions, EmuTracer,
+    ErrWrongArguments, ParEmuOptions, StatsCostModel, ZiskEmulatorErr,
+};
 
 use data_bus::DataBusTrait;
 use p3_field::PrimeField;
@@ -26,7 +29,7 @@ use std::{
 };
 use sysinfo::System;
 use zisk_common::EmuTrace;
-use zisk_core::{Riscv2zisk, ZiskRom};
+use zisk_core::{GuestExitStatus, Riscv2zisk, ZiskRom};
 
 pub trait Emulator {
     fn emulate(
@@ -40,26 +43,42 @@ use rayon::prelude::*;
 pub struct ZiskEmulator;
 
 impl ZiskEmulator {
-    /// Lists all device-under-test riscof files in a directory (*dut*.elf) and calls
-    /// process_elf_file with each of them
+    /// Runs all the riscof-style conformance tests found in a directory, comparing their
+    /// signatures against the reference ones, and prints the results
     fn process_directory(
         directory: String,
-        inputs: &[u8],
         options: &EmuOptions,
     ) -> Result<Vec<u8>, ZiskEmulatorErr> {
         if options.verbose {
             println!("process_directory() directory={}", directory);
         }
 
-        // List all files in the directory
-        let files = Self::list_files(&directory).unwrap();
+        // Run the tests with the Rust emulator and, if requested, with the assembly emulator
+        let mut backends = vec![ConformanceBackend::Rust];
+        if let Some(emulator_asm_dir) = &options.asm_emulator {
+            backends.push(ConformanceBackend::Asm { emulator_asm_dir: emulator_asm_dir.into() });
+        }
+        let report = run_conformance_suite(
+            Path::new(&directory),
+            &backends,
+            options.max_steps,
+            options.verbose,
+        )
+        .map_err(|e| ZiskEmulatorErr::Unknown(e.to_string()))?;
+
+        println!("{}", report.to_table());
+        if let Some(junit) = &options.junit {
+            fs::write(junit, report.to_junit_xml()).map_err(|e| {
+                ZiskEmulatorErr::Unknown(format!("Could not write JUnit file {}: {}", junit, e))
+            })?;
+        }
 
-        // For every file
-        for file in files {
-            // If file follows the riscof dut file name convention, then call process_elf_file()
-            if file.contains("dut") && file.ends_with(".elf") {
-                Self::process_elf_file(file, inputs, options, None::<Box<dyn Fn(EmuTrace)>>)?;
-            }
+        let total = report.results.len();
+        if report.passed() < total {
+            return Err(ZiskEmulatorErr::ConformanceFailed {
+                failed: total - report.passed(),
+                total,
+            });
         }
 
         Ok(Vec::new())
@@ -81,7 +100,37 @@ impl ZiskEmulator {
         let riscv2zisk = Riscv2zisk::new(elf_filename);
 
         // Convert the ELF file to ZisK ROM calling the transpiler run() method
-        let zisk_rom = riscv2zisk.run().map_err(|err| ZiskEmulatorErr::Unknown(err.to_string()))?;
+        if !options.optimize && !options.verify_optimizer {
+            let zisk_rom =
+                riscv2zisk.run().map_err(|err| ZiskEmulatorErr::Unknown(err.to_string()))?;
+            return Self::process_rom(&zisk_rom, inputs, options, callback);
+        }
+
+        // Minimal traces feed the prover, which uses the original ROM
+        if options.generate_minimal_traces || callback.is_some() {
+            return Err(ZiskEmulatorErr::WrongArguments(ErrWrongArguments::new(
+                "The optimized ROM cannot be used to generate minimal traces",
+            )));
+        }
+
+        // Convert the ELF file to an optimized ZisK ROM
+        let (zisk_rom, optimizer_stats) =
+            riscv2zisk.run_optimized().map_err(|err| ZiskEmulatorErr::Unknown(err.to_string()))?;
+        if options.verbose {
+            println!("process_elf_file() optimizer {}", optimizer_stats);
+        }
+
+        // Check the optimized ROM against the original one, co-running both of them
+        if options.verify_optimizer {
+            let original_rom =
+                riscv2zisk.run().map_err(|err| ZiskEmulatorErr::Unknown(err.to_string()))?;
+            let report = verify_optimized_rom(&original_rom, &zisk_rom, inputs, options.max_steps)?;
+            println!(
+                "process_elf_file() optimized ROM verified: steps={} optimized_steps={} \
+                 sync_points={}",
+                report.original_steps, report.optimized_steps, report.sync_points
+            );
+        }
 
         // Process the Zisk rom with the provided inputs, according to the configured options
         Self::process_rom(&zisk_rom, inputs, options, callback)
@@ -114,15 +163,43 @@ impl ZiskEmulator {
             println!("process_rom() rom size={} inputs size={}", rom.insts.len(), inputs.len());
         }
 
+        // Check the input data size before running anything
+        let limits = options.resource_limits();
+        limits.check_input_size(inputs.len() as u64)?;
+
         // Create a emulator instance with the Zisk rom
         let mut emu = Emu::new(rom);
 
         // Get the current time, to be used to calculate the metrics
         let start = Instant::now();
 
+        // Install the execution tracer, if requested
+        emu.ctx.tracer = EmuTracer::from_options(options)?;
+
         // Run the emulation, using the input and the options
         emu.run(inputs.to_owned(), options, callback);
 
+        // Complete the trace files, which are useful even if the emulation did not complete
+        if let Some(tracer) = emu.ctx.tracer.take() {
+            tracer.finish()?;
+        }
+
+        // Save the captured guest streams, even if the emulation did not complete, since they can
+        // explain why
+        let console = &emu.ctx.inst_ctx.mem.console;
+        if let Some(guest_stdout) = &options.guest_stdout {
+            fs::write(guest_stdout, &console.stdout)
+                .map_err(|e| ZiskEmulatorErr::Unknown(e.to_string()))?;
+        }
+        if let Some(guest_stderr) = &options.guest_stderr {
+            fs::write(guest_stderr, &console.stderr)
+                .map_err(|e| ZiskEmulatorErr::Unknown(e.to_string()))?;
+        }
+
+        // Check that the program did not exceed its resource limits, including the maximum number
+        // of steps
+        emu.check_resource_limits(&limits)?;
+
         // Check that the emulation completed, either successfully or not, but it must reach the end
         // of the program
         if !emu.terminated() {
@@ -149,6 +226,11 @@ impl ZiskEmulator {
             );
         }
 
+        // Report the statistics, if requested
+        if options.stats {
+            Self::report_stats(&emu, options)?;
+        }
+
         // Get the emulation output
         let output = emu.get_output_8();
 
@@ -170,37 +252,87 @@ impl ZiskEmulator {
             }
         }
 
+        // Fail if the guest program did not succeed, once its output has been saved
+        let exit_status = emu.get_exit_status();
+        if !exit_status.success() {
+            return Err(ZiskEmulatorErr::GuestFailed(exit_status));
+        }
+
         Ok(output)
     }
 
+    /// Writes the emulation statistics in the configured format, either to the configured file or
+    /// to the console
+    fn report_stats(emu: &Emu, options: &EmuOptions) -> Result<(), ZiskEmulatorErr> {
+        let cost_model = match &options.cost_model {
+            Some(path) => StatsCostModel::from_file(Path::new(path))?,
+            None => StatsCostModel::default(),
+        };
+        let report = emu.ctx.stats.summary(&cost_model);
+        let report = match options.stats_format.as_deref() {
+            Some("json") => report.to_json(),
+            Some("csv") => report.to_csv(),
+            _ => report.to_text(),
+        };
+
+        match &options.stats_file {
+            Some(path) => fs::write(path, report).map_err(|e| {
+                ZiskEmulatorErr::Unknown(format!("Could not write stats file {}: {}", path, e))
+            }),
+            None => {
+                println!("{}", report);
+                Ok(())
+            }
+        }
+    }
+
     /// EXECUTE phase
     /// First phase of the witness computation
     /// 8 threads in waterfall (# threads to be re-calibrated after memory reads refactor)
     /// Must be fast
+    /// Returns the minimal traces and the exit status of the guest program
     pub fn compute_minimal_traces(
         rom: &ZiskRom,
         inputs: &[u8],
         options: &EmuOptions,
         num_threads: usize,
-    ) -> Result<Vec<EmuTrace>, ZiskEmulatorErr> {
-        let mut minimal_traces = vec![Vec::new(); num_threads];
+    ) -> Result<(Vec<EmuTrace>, GuestExitStatus), ZiskEmulatorErr> {
+        // Check the input data size before running anything
+        let limits = options.resource_limits();
+        limits.check_input_size(inputs.len() as u64)?;
 
-        minimal_traces.par_iter_mut().enumerate().for_each(|(thread_id, emu_trace)| {
-            let par_emu_options =
-                ParEmuOptions::new(num_threads, thread_id, options.trace_steps.unwrap() as usize);
+        let mut minimal_traces = vec![Vec::new(); num_threads];
 
-            // Run the emulation
-            let mut emu = Emu::new(rom);
-            let result = emu.par_run(inputs.to_owned(), options, &par_emu_options);
+        let results: Vec<Result<GuestExitStatus, ZiskEmulatorErr>> = minimal_traces
+            .par_iter_mut()
+            .enumerate()
+            .map(|(thread_id, emu_trace)| {
+                let par_emu_options = ParEmuOptions::new(
+                    num_threads,
+                    thread_id,
+                    options.trace_steps.unwrap() as usize,
+                );
+
+                // Run the emulation
+                let mut emu = Emu::new(rom);
+                let result = emu.par_run(inputs.to_owned(), options, &par_emu_options);
+
+                // All threads run the whole program, so all of them detect the same limit
+                emu.check_resource_limits(&limits)?;
+                if !emu.terminated() {
+                    return Err(ZiskEmulatorErr::EmulationNoCompleted);
+                }
 
-            if !emu.terminated() {
-                panic!("Emulation did not complete");
-                // TODO!
-                // return Err(ZiskEmulatorErr::EmulationNoCompleted);
-            }
+                *emu_trace = result;
+                Ok(emu.get_exit_status())
+            })
+            .collect();
 
-            *emu_trace = result;
-        });
+        // All threads end with the same memory, so take the exit status of the first one
+        let exit_status = results
+            .into_iter()
+            .collect::<Result<Vec<GuestExitStatus>, ZiskEmulatorErr>>()?
+            .swap_remove(0);
 
         let capacity = minimal_traces.iter().map(|trace| trace.len()).sum::<usize>();
         let mut vec_traces = Vec::with_capacity(capacity);
@@ -211,7 +343,7 @@ impl ZiskEmulator {
             vec_traces.push(std::mem::take(&mut minimal_traces[x][y]));
         }
 
-        Ok(vec_traces)
+        Ok((vec_traces, exit_status))
     }
 
     /// COUNT phase
@@ -247,7 +379,7 @@ impl ZiskEmulator {
     }
 
     /// Finds all files in a directory and returns a vector with their full paths
-    fn list_files(directory: &str) -> std::io::Result<Vec<String>> {
+    pub(crate) fn list_files(directory: &str) -> std::io::Result<Vec<String>> {
         // Define an internal function to call it recursively
         fn _list_files(vec: &mut Vec<PathBuf>, path: &Path) -> std::io::Result<()> {
             // Only search if the path is a directory
@@ -342,7 +474,7 @@ impl Emulator for ZiskEmulator {
 
             // If it is a directory, call process_directory()
             if metadata.is_dir() {
-                Self::process_directory(elf_filename, &inputs, options)
+                Self::process_directory(elf_filename, options)
             }
             // If it is a file, call process_elf_file()
             else {
@@ -351,3 +483,71 @@ impl Emulator for ZiskEmulator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use zisk_core::{UserFcalls, ZiskInstBuilder, FCALL_USER_ID_FIRST, INPUT_ADDR, ROM_ENTRY};
+
+    /// Returns a BIOS-only ROM calling the first user fcall with `words` as its only parameter
+    /// and getting `words` result words into a register
+    fn fcall_rom(words: u64) -> ZiskRom {
+        let mut insts = Vec::new();
+        let mut add = |src_a: u64, src_b: (&str, u64), op: &str, end: bool| {
+            let mut zib = ZiskInstBuilder::new(ROM_ENTRY + 4 * insts.len() as u64);
+            zib.src_a("imm", src_a, false);
+            zib.src_b(src_b.0, src_b.1, false);
+            zib.op(op).unwrap();
+            if op == "fcall_get" {
+                zib.store("reg", 1, false, false);
+            }
+            if end {
+                zib.j(0, 0);
+                zib.end();
+            } else {
+                zib.j(4, 4);
+            }
+            zib.build();
+            insts.push(zib.i);
+        };
+        add(1, ("imm", words), "fcall_param", false);
+        add(FCALL_USER_ID_FIRST as u64, ("imm", 0), "fcall", false);
+        for _ in 0..words {
+            add(0, ("mem", INPUT_ADDR), "fcall_get", false);
+        }
+        add(0, ("imm", 0), "copyb", true);
+        ZiskRom {
+            sorted_pc_list: insts.iter().map(|i| i.paddr).collect(),
+            rom_entry_instructions: insts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_minimal_traces_large_fcall_result() {
+        // An 8 KB result, got over several chunks
+        const WORDS: u64 = 1024;
+        let result = |words: u64| (0..words).map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let mut user_fcalls = UserFcalls::new();
+        user_fcalls.register(FCALL_USER_ID_FIRST, move |params| Ok(result(params[0]).collect()));
+
+        let rom = fcall_rom(WORDS);
+        let options = EmuOptions {
+            trace_steps: Some(256),
+            user_fcalls: Some(Arc::new(user_fcalls)),
+            ..Default::default()
+        };
+        let (traces, exit_status) =
+            ZiskEmulator::compute_minimal_traces(&rom, &[], &options, 2).unwrap();
+        assert!(exit_status.success());
+        assert_eq!(traces.len(), 5);
+        assert_eq!(traces.iter().map(|trace| trace.steps).sum::<u64>(), WORDS + 3);
+
+        // Every fcall_get step is a free-input read, which the main state machine replays from
+        // the minimal traces, so they must hold every result word in order
+        let mem_reads: Vec<u64> =
+            traces.iter().flat_map(|trace| trace.mem_reads.iter().copied()).collect();
+        assert_eq!(mem_reads, result(WORDS).collect::<Vec<u64>>());
+    }
+}
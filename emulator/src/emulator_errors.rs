@@ -1,10 +1,26 @@
 use std::{error::Error, fmt};
+use zisk_core::{GuestExitStatus, ResourceLimitExceeded};
 
 #[derive(Debug)]
 pub enum ZiskEmulatorErr {
     WrongArguments(ErrWrongArguments),
     AddressOutOfRange(u64),
     EmulationNoCompleted,
+    /// The optimized ROM diverged from the original one, at the given optimized ROM step and pc
+    OptimizerMismatch {
+        step: u64,
+        pc: u64,
+        reason: String,
+    },
+    /// The emulation completed, but the guest program panicked or ended with a non-zero exit code
+    GuestFailed(GuestExitStatus),
+    /// The guest program exceeded one of its resource limits
+    ResourceLimitExceeded(ResourceLimitExceeded),
+    /// Some tests of a conformance suite failed or could not be run
+    ConformanceFailed {
+        failed: usize,
+        total: usize,
+    },
     Unknown(String),
 }
 
@@ -31,6 +47,14 @@ impl fmt::Display for ZiskEmulatorErr {
                 write!(f, "Address out of range: {:#x}", addr)
             }
             ZiskEmulatorErr::EmulationNoCompleted => write!(f, "Emulation not completed"),
+            ZiskEmulatorErr::OptimizerMismatch { step, pc, reason } => {
+                write!(f, "Optimized ROM mismatch at step={} pc={:#x}: {}", step, pc, reason)
+            }
+            ZiskEmulatorErr::GuestFailed(status) => write!(f, "Guest program {}", status),
+            ZiskEmulatorErr::ResourceLimitExceeded(e) => write!(f, "Guest program {}", e),
+            ZiskEmulatorErr::ConformanceFailed { failed, total } => {
+                write!(f, "{} of {} conformance tests did not pass", failed, total)
+            }
             ZiskEmulatorErr::Unknown(code) => write!(f, "Error code {}", code),
         }
     }
@@ -42,6 +66,10 @@ impl Error for ZiskEmulatorErr {
             ZiskEmulatorErr::WrongArguments(e) => Some(e),
             ZiskEmulatorErr::AddressOutOfRange(_) => None,
             ZiskEmulatorErr::EmulationNoCompleted => None,
+            ZiskEmulatorErr::OptimizerMismatch { .. } => None,
+            ZiskEmulatorErr::GuestFailed(_) => None,
+            ZiskEmulatorErr::ResourceLimitExceeded(e) => Some(e),
+            ZiskEmulatorErr::ConformanceFailed { .. } => None,
             ZiskEmulatorErr::Unknown(_) => None,
         }
     }
@@ -54,3 +82,9 @@ impl fmt::Display for ErrWrongArguments {
 }
 
 impl Error for ErrWrongArguments {}
+
+impl From<ResourceLimitExceeded> for ZiskEmulatorErr {
+    fn from(e: ResourceLimitExceeded) -> Self {
+        ZiskEmulatorErr::ResourceLimitExceeded(e)
+    }
+}
@@ -12,24 +12,32 @@
 //! User configuration -------> EmuOptions /
 //! ```
 
+mod conformance;
 mod emu;
+mod emu_builder;
 mod emu_context;
 mod emu_full_trace;
 pub mod emu_options;
 mod emu_par_options;
 mod emu_reg_trace;
+mod emu_rom_verifier;
 mod emu_segment;
+mod emu_tracer;
 mod emulator;
 mod emulator_errors;
 pub mod stats;
 
+pub use conformance::*;
 pub use emu::*;
+pub use emu_builder::*;
 pub use emu_context::*;
 pub use emu_full_trace::*;
 pub use emu_options::*;
 pub use emu_par_options::*;
 pub use emu_reg_trace::*;
+pub use emu_rom_verifier::*;
 pub use emu_segment::*;
+pub use emu_tracer::*;
 pub use emulator::*;
 pub use emulator_errors::*;
 pub use stats::*;
@@ -4,17 +4,153 @@
 //! * Memory read/write counters (aligned and not aligned)
 //! * Registers read/write counters (total and per register)
 //! * Operations counters (total and per opcode)
+//!
+//! The counters are summarized into a serializable `StatsReport`, using a configurable
+//! `StatsCostModel`, that can be written as human-readable text, JSON or CSV.
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use zisk_core::{
+    zisk_ops::{OpType, ZiskOp, DMA_PARAMS},
+    ZiskInst, M3, REGS_IN_MAIN_TOTAL_NUMBER,
+};
+
+use crate::{ErrWrongArguments, ZiskEmulatorErr};
+
+/// Cost of every proven element, in area units; the cost in seconds is the area divided by
+/// `area_per_sec`.  Missing fields in a cost model file take their default value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsCostModel {
+    /// Area units proven per second
+    pub area_per_sec: f64,
+    /// Area of a memory access
+    pub mem: f64,
+    /// Area of a non-aligned memory read within one 8-bytes chunk
+    pub mema_r1: f64,
+    /// Area of a non-aligned memory read across two 8-bytes chunks
+    pub mema_r2: f64,
+    /// Area of a non-aligned memory write within one 8-bytes chunk
+    pub mema_w1: f64,
+    /// Area of a non-aligned memory write across two 8-bytes chunks
+    pub mema_w2: f64,
+    /// Area of an operation with both operands lower than 256
+    pub usual: f64,
+    /// Area of a main state machine step
+    pub step: f64,
+    /// Area per operation by opcode name, overriding the opcode cost of the ZisK ops table
+    pub ops: BTreeMap<String, f64>,
+}
+
+impl Default for StatsCostModel {
+    fn default() -> Self {
+        Self {
+            area_per_sec: 1000000_f64,
+            mem: 10_f64,
+            mema_r1: 20_f64,
+            mema_r2: 40_f64,
+            mema_w1: 40_f64,
+            mema_w2: 80_f64,
+            usual: 8_f64,
+            step: 50_f64,
+            ops: BTreeMap::new(),
+        }
+    }
+}
+
+impl StatsCostModel {
+    /// Loads a cost model from a JSON file
+    pub fn from_file(path: &Path) -> Result<Self, ZiskEmulatorErr> {
+        let data = fs::read_to_string(path).map_err(|e| {
+            ZiskEmulatorErr::WrongArguments(ErrWrongArguments::new(format!(
+                "Could not read cost model file {}: {}",
+                path.display(),
+                e
+            )))
+        })?;
+        serde_json::from_str(&data).map_err(|e| {
+            ZiskEmulatorErr::WrongArguments(ErrWrongArguments::new(format!(
+                "Invalid cost model file {}: {}",
+                path.display(),
+                e
+            )))
+        })
+    }
+
+    /// Returns the area of an operation
+    fn op_area(&self, op: ZiskOp) -> f64 {
+        self.ops.get(op.name()).copied().unwrap_or(op.steps() as f64)
+    }
+}
+
+/// Costs in seconds, according to a cost model
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatsCosts {
+    pub total: f64,
+    pub main: f64,
+    pub mem: f64,
+    pub mem_align: f64,
+    pub opcodes: f64,
+    pub usual: f64,
+}
+
+/// Memory operation counters, including registers mapped to memory
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatsMemory {
+    pub aligned_reads: u64,
+    pub aligned_writes: u64,
+    pub non_aligned_1_reads: u64,
+    pub non_aligned_2_reads: u64,
+    pub non_aligned_1_writes: u64,
+    pub non_aligned_2_writes: u64,
+    /// Memory align state machine steps required by the non-aligned accesses
+    pub mem_align_steps: u64,
+}
+
+/// Counters of an opcode
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatsOpcode {
+    pub name: String,
+    pub op_type: String,
+    /// Operations executed, including the usual ones
+    pub count: u64,
+    /// Operations executed with both operands lower than 256
+    pub usual: u64,
+    /// Area of one non-usual operation
+    pub area_per_op: f64,
+    /// Cost in seconds of the non-usual operations
+    pub cost: f64,
+}
 
-use zisk_core::{zisk_ops::ZiskOp, ZiskInst, M3, REGS_IN_MAIN_TOTAL_NUMBER};
+/// Totals of an operation type
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatsOpType {
+    pub op_type: String,
+    pub count: u64,
+    pub cost: f64,
+}
 
-const AREA_PER_SEC: f64 = 1000000_f64;
-const COST_MEM: f64 = 10_f64 / AREA_PER_SEC;
-const COST_MEMA_R1: f64 = 20_f64 / AREA_PER_SEC;
-const COST_MEMA_R2: f64 = 40_f64 / AREA_PER_SEC;
-const COST_MEMA_W1: f64 = 40_f64 / AREA_PER_SEC;
-const COST_MEMA_W2: f64 = 80_f64 / AREA_PER_SEC;
-const COST_USUAL: f64 = 8_f64 / AREA_PER_SEC;
-const COST_STEP: f64 = 50_f64 / AREA_PER_SEC;
+/// Serializable summary of the emulator statistics
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatsReport {
+    pub steps: u64,
+    pub cost_model: StatsCostModel,
+    pub costs: StatsCosts,
+    pub memory: StatsMemory,
+    /// Operations executed with both operands lower than 256, proven as usual operations
+    pub usual_ops: u64,
+    /// Non-usual operations executed
+    pub total_ops: u64,
+    /// Per opcode counters, for the executed opcodes
+    pub opcodes: Vec<StatsOpcode>,
+    /// Per operation type totals, for the executed operation types
+    pub op_types: Vec<StatsOpType>,
+    /// Calls per precompile and fcall opcode
+    pub precompiles: BTreeMap<String, u64>,
+    /// Accesses per register
+    pub registers: Vec<u64>,
+}
 
 /// Keeps counters for every type of memory operation (including registers).
 ///
@@ -47,6 +183,8 @@ pub struct Stats {
     steps: u64,
     /// Counters of operations, one per possible u8 opcode (many remain unused)
     ops: [u64; 256],
+    /// Counters of usual operations, one per possible u8 opcode
+    usual_ops: [u64; 256],
     /// Counters of register accesses, one per register
     regs: [u64; REGS_IN_MAIN_TOTAL_NUMBER],
 }
@@ -59,6 +197,7 @@ impl Default for Stats {
             usual: 0,
             steps: 0,
             ops: [0; 256],
+            usual_ops: [0; 256],
             regs: [0; REGS_IN_MAIN_TOTAL_NUMBER],
         }
     }
@@ -128,6 +267,7 @@ impl Stats {
         // If the operation is a usual operation, then increase the usual counter
         if self.is_usual(instruction, a, b) {
             self.usual += 1;
+            self.usual_ops[instruction.op as usize] += 1;
         }
         // Otherwise, increase the counter corresponding to this opcode
         else {
@@ -135,6 +275,19 @@ impl Stats {
         }
     }
 
+    /// Called every time a dma operation (memcpy, memset) is executed, if statistics are enabled,
+    /// with its parameters [dst, src|fill, count] read before the operation.  Its parameters,
+    /// source words and destination words are all aligned memory accesses.
+    pub fn on_dma(&mut self, instruction: &ZiskInst, params: &[u64; DMA_PARAMS]) {
+        let count = params[2];
+        let mut reads = DMA_PARAMS as u64;
+        if instruction.op == ZiskOp::Memcpy.code() {
+            reads += count;
+        }
+        self.mops.mread_a += reads;
+        self.mops.mwrite_a += count;
+    }
+
     /// Returns true if the provided operation is a usual operation
     fn is_usual(&self, instruction: &ZiskInst, a: u64, b: u64) -> bool {
         // ecall/system call functions are not candidates to be usual
@@ -146,99 +299,153 @@ impl Stats {
         (a < 256) && (b < 256)
     }
 
-    /// Returns a string containing a human-readable text showing all caunters
-    pub fn report(&self) -> String {
-        const AREA_PER_SEC: f64 = 1000000_f64;
+    /// Summarizes the counters, calculating their costs with the provided cost model
+    pub fn summary(&self, cost_model: &StatsCostModel) -> StatsReport {
+        let area_per_sec = cost_model.area_per_sec;
+        let mops = &self.mops;
 
-        // The result of his function is accumulated in this string
-        let mut output = String::new();
+        let memory = StatsMemory {
+            aligned_reads: mops.mread_a,
+            aligned_writes: mops.mwrite_a,
+            non_aligned_1_reads: mops.mread_na1,
+            non_aligned_2_reads: mops.mread_na2,
+            non_aligned_1_writes: mops.mwrite_na1,
+            non_aligned_2_writes: mops.mwrite_na2,
+            mem_align_steps: mops.mread_na1
+                + mops.mread_na2 * 2
+                + mops.mwrite_na1 * 2
+                + mops.mwrite_na2 * 4,
+        };
+        let total_mem_ops = mops.mread_na1
+            + mops.mread_na2
+            + mops.mread_a
+            + mops.mwrite_na1
+            + mops.mwrite_na2
+            + mops.mwrite_a;
 
-        // First, log the cost constants
-        output += "Cost definitions:\n";
-        output += &format!("    AREA_PER_SEC: {} steps\n", AREA_PER_SEC);
-        output += &format!("    COST_MEMA_R1: {:02} sec\n", COST_MEMA_R1);
-        output += &format!("    COST_MEMA_R2: {:02} sec\n", COST_MEMA_R2);
-        output += &format!("    COST_MEMA_W1: {:02} sec\n", COST_MEMA_W1);
-        output += &format!("    COST_MEMA_W2: {:02} sec\n", COST_MEMA_W2);
-        output += &format!("    COST_USUAL: {:02} sec\n", COST_USUAL);
-        output += &format!("    COST_STEP: {:02} sec\n", COST_STEP);
-
-        // Calculate some aggregated counters to be used in the logs
-        let total_mem_ops = self.mops.mread_na1
-            + self.mops.mread_na2
-            + self.mops.mread_a
-            + self.mops.mwrite_na1
-            + self.mops.mwrite_na2
-            + self.mops.mwrite_a;
-        let total_mem_align_steps = self.mops.mread_na1
-            + self.mops.mread_na2 * 2
-            + self.mops.mwrite_na1 * 2
-            + self.mops.mwrite_na2 * 4;
-
-        let cost_mem = total_mem_ops as f64 * COST_MEM;
-        let cost_mem_align = self.mops.mread_na1 as f64 * COST_MEMA_R1
-            + self.mops.mread_na2 as f64 * COST_MEMA_R2
-            + self.mops.mwrite_na1 as f64 * COST_MEMA_W1
-            + self.mops.mwrite_na2 as f64 * COST_MEMA_W2;
-
-        // Declare some total counters for the opcodes
-        let mut total_opcodes: u64 = 0;
-        let mut opcode_steps: [u64; 256] = [0; 256];
-        let mut total_opcode_steps: u64 = 0;
-        let mut opcode_cost: [f64; 256] = [0_f64; 256];
-        let mut total_opcode_cost: f64 = 0_f64;
-
-        // For every possible opcode value
+        let mut report = StatsReport {
+            steps: self.steps,
+            cost_model: cost_model.clone(),
+            memory,
+            usual_ops: self.usual,
+            registers: self.regs.to_vec(),
+            ..Default::default()
+        };
+
+        // For every executed opcode, in opcode order
+        let mut op_types: BTreeMap<String, StatsOpType> = BTreeMap::new();
         for opcode in 0..256 {
-            // Skip opcode counters that are zero
-            if self.ops[opcode] == 0 {
+            let count = self.ops[opcode] + self.usual_ops[opcode];
+            if count == 0 {
                 continue;
             }
 
-            // Increase total opcodes counter
-            total_opcodes += self.ops[opcode];
+            // If the counter has been increased, then the opcode must be a valid one
+            let op = ZiskOp::try_from_code(opcode as u8).unwrap();
+            let area_per_op = cost_model.op_area(op);
+            let cost = self.ops[opcode] as f64 * area_per_op / area_per_sec;
+            report.total_ops += self.ops[opcode];
+            report.costs.opcodes += cost;
 
-            // Get the Zisk instruction corresponding to this opcode; if the counter has been
-            // increased, then the opcode must be a valid one
-            let inst = ZiskOp::try_from_code(opcode as u8).unwrap();
+            let op_type = format!("{:?}", op.op_type());
+            let totals = op_types
+                .entry(op_type.clone())
+                .or_insert_with(|| StatsOpType { op_type: op_type.clone(), ..Default::default() });
+            totals.count += count;
+            totals.cost += cost;
 
-            // Increase steps, both per opcode and total
-            opcode_steps[opcode] += inst.steps();
-            total_opcode_steps += inst.steps();
+            if matches!(
+                op.op_type(),
+                OpType::Keccak
+                    | OpType::Sha256
+                    | OpType::ArithEq
+                    | OpType::Blake2
+                    | OpType::Dma
+                    | OpType::Fcall
+            ) {
+                report.precompiles.insert(op.name().to_string(), count);
+            }
 
-            // Increse cost, both per opcode and total
-            let value = self.ops[opcode] as f64;
-            opcode_cost[opcode] += value * inst.steps() as f64 / AREA_PER_SEC;
-            total_opcode_cost += value * inst.steps() as f64 / AREA_PER_SEC;
+            report.opcodes.push(StatsOpcode {
+                name: op.name().to_string(),
+                op_type,
+                count,
+                usual: self.usual_ops[opcode],
+                area_per_op,
+                cost,
+            });
         }
+        report.op_types = op_types.into_values().collect();
+
+        report.costs.main = self.steps as f64 * cost_model.step / area_per_sec;
+        report.costs.mem = total_mem_ops as f64 * cost_model.mem / area_per_sec;
+        report.costs.mem_align = (mops.mread_na1 as f64 * cost_model.mema_r1
+            + mops.mread_na2 as f64 * cost_model.mema_r2
+            + mops.mwrite_na1 as f64 * cost_model.mema_w1
+            + mops.mwrite_na2 as f64 * cost_model.mema_w2)
+            / area_per_sec;
+        report.costs.usual = self.usual as f64 * cost_model.usual / area_per_sec;
+        report.costs.total = report.costs.main
+            + report.costs.mem
+            + report.costs.mem_align
+            + report.costs.opcodes
+            + report.costs.usual;
+
+        report
+    }
+
+    /// Returns a string containing a human-readable text showing all counters, using the default
+    /// cost model
+    pub fn report(&self) -> String {
+        self.summary(&StatsCostModel::default()).to_text()
+    }
+}
+
+impl StatsReport {
+    /// Returns a human-readable text showing all counters
+    pub fn to_text(&self) -> String {
+        let cm = &self.cost_model;
+
+        // The result of his function is accumulated in this string
+        let mut output = String::new();
 
-        // Calculate some costs
-        let cost_usual = self.usual as f64 * COST_USUAL;
-        let cost_main = self.steps as f64 * COST_STEP;
-        let total_cost = cost_main + cost_mem + cost_mem_align + total_opcode_cost + cost_usual;
+        // First, log the cost constants
+        output += "Cost definitions:\n";
+        output += &format!("    AREA_PER_SEC: {} steps\n", cm.area_per_sec);
+        output += &format!("    COST_MEMA_R1: {:02} sec\n", cm.mema_r1 / cm.area_per_sec);
+        output += &format!("    COST_MEMA_R2: {:02} sec\n", cm.mema_r2 / cm.area_per_sec);
+        output += &format!("    COST_MEMA_W1: {:02} sec\n", cm.mema_w1 / cm.area_per_sec);
+        output += &format!("    COST_MEMA_W2: {:02} sec\n", cm.mema_w2 / cm.area_per_sec);
+        output += &format!("    COST_USUAL: {:02} sec\n", cm.usual / cm.area_per_sec);
+        output += &format!("    COST_STEP: {:02} sec\n", cm.step / cm.area_per_sec);
 
         // Build the memory usage counters and cost values
-        output += &format!("\nTotal Cost: {:.2} sec\n", total_cost);
-        output += &format!("    Main Cost: {:.2} sec {} steps\n", cost_main, self.steps);
-        output += &format!("    Mem Cost: {:.2} sec {} steps\n", cost_mem, total_mem_ops);
-        output +=
-            &format!("    Mem Align: {:.2} sec {} steps\n", cost_mem_align, total_mem_align_steps);
+        let m = &self.memory;
+        let memory_reads = m.aligned_reads + m.non_aligned_1_reads + m.non_aligned_2_reads;
+        let memory_writes = m.aligned_writes + m.non_aligned_1_writes + m.non_aligned_2_writes;
+        let memory_total = memory_reads + memory_writes;
+        let opcode_steps: u64 =
+            self.opcodes.iter().filter(|o| o.count > o.usual).map(|o| o.area_per_op as u64).sum();
+        output += &format!("\nTotal Cost: {:.2} sec\n", self.costs.total);
+        output += &format!("    Main Cost: {:.2} sec {} steps\n", self.costs.main, self.steps);
+        output += &format!("    Mem Cost: {:.2} sec {} steps\n", self.costs.mem, memory_total);
+        output += &format!(
+            "    Mem Align: {:.2} sec {} steps\n",
+            self.costs.mem_align, m.mem_align_steps
+        );
         output += &format!(
             "    Opcodes: {:.2} sec {} steps ({} ops)\n",
-            total_opcode_cost, total_opcode_steps, total_opcodes
+            self.costs.opcodes, opcode_steps, self.total_ops
         );
-        output += &format!("    Usual: {:.2} sec {} steps\n", cost_usual, self.usual);
-        let memory_reads = self.mops.mread_a + self.mops.mread_na1 + self.mops.mread_na2;
-        let memory_writes = self.mops.mwrite_a + self.mops.mwrite_na1 + self.mops.mwrite_na2;
-        let memory_total = memory_reads + memory_writes;
+        output += &format!("    Usual: {:.2} sec {} steps\n", self.costs.usual, self.usual_ops);
         output += &format!(
             "    Memory: {} a reads + {} na1 reads + {} na2 reads + {} a writes + {} na1 writes + {} na2 writes = {} reads + {} writes = {} r/w\n",
-            self.mops.mread_a,
-            self.mops.mread_na1,
-            self.mops.mread_na2,
-            self.mops.mwrite_a,
-            self.mops.mwrite_na1,
-            self.mops.mwrite_na2,
+            m.aligned_reads,
+            m.non_aligned_1_reads,
+            m.non_aligned_2_reads,
+            m.aligned_writes,
+            m.non_aligned_1_writes,
+            m.non_aligned_2_writes,
             memory_reads,
             memory_writes,
             memory_total
@@ -246,44 +453,189 @@ impl Stats {
 
         // Build the operations usage counters and cost values
         output += "\nOpcodes:\n";
-        for opcode in 0..256 {
-            // Skip zero counters
-            if self.ops[opcode] == 0 {
-                continue;
-            }
-
-            // Get the Zisk instruction corresponding to this opcode
-            let inst = ZiskOp::try_from_code(opcode as u8).unwrap();
-
-            // Log opcode cost
+        for o in self.opcodes.iter().filter(|o| o.count > o.usual) {
             output += &format!(
                 "    {}: {:.2} sec ({} steps/op) ({} ops)\n",
-                inst.name(),
-                opcode_cost[opcode],
-                opcode_steps[opcode],
-                self.ops[opcode]
+                o.name,
+                o.cost,
+                o.area_per_op,
+                o.count - o.usual
             );
         }
 
         // Build the register counters
         output += "\nRegisters:\n";
-        let mut total_regs = 0u64;
-        for reg in self.regs.iter() {
-            total_regs += reg;
-        }
-        if total_regs == 0 {
-            total_regs = 1;
-        }
+        let total_regs = self.registers.iter().sum::<u64>().max(1);
         output += &format!("total regs = {}\n", total_regs);
         output += &format!("total steps = {}\n", self.steps);
-        let regs_per_step = total_regs * 1000 / if self.steps == 0 { 1 } else { self.steps };
+        let regs_per_step = total_regs * 1000 / self.steps.max(1);
         output += &format!("total regs / steps = {} %o\n", regs_per_step);
 
-        for (i, reg) in self.regs.iter().enumerate() {
+        for (i, reg) in self.registers.iter().enumerate() {
             let per_thousand = reg * 1000 / total_regs;
             output += &format!("reg[{}] = {} ({}%o)\n", i, reg, per_thousand);
         }
 
         output
     }
+
+    /// Returns the report as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Returns the report as CSV, one `section,name,count,cost` row per counter
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from("section,name,count,cost\n");
+        let mut row = |section: &str, name: &str, count: u64, cost: f64| {
+            writeln!(output, "{},{},{},{}", section, name, count, cost).unwrap();
+        };
+
+        row("summary", "steps", self.steps, self.costs.main);
+        row("summary", "total", self.steps, self.costs.total);
+        row("summary", "usual", self.usual_ops, self.costs.usual);
+        row("summary", "opcodes", self.total_ops, self.costs.opcodes);
+        let m = &self.memory;
+        row("memory", "aligned_reads", m.aligned_reads, 0.0);
+        row("memory", "aligned_writes", m.aligned_writes, 0.0);
+        row("memory", "non_aligned_1_reads", m.non_aligned_1_reads, 0.0);
+        row("memory", "non_aligned_2_reads", m.non_aligned_2_reads, 0.0);
+        row("memory", "non_aligned_1_writes", m.non_aligned_1_writes, 0.0);
+        row("memory", "non_aligned_2_writes", m.non_aligned_2_writes, 0.0);
+        row("memory", "mem", 0, self.costs.mem);
+        row("memory", "mem_align", m.mem_align_steps, self.costs.mem_align);
+        for o in &self.opcodes {
+            row("opcode", &o.name, o.count, o.cost);
+        }
+        for t in &self.op_types {
+            row("op_type", &t.op_type, t.count, t.cost);
+        }
+        for (name, count) in &self.precompiles {
+            row("precompile", name, *count, 0.0);
+        }
+        for (i, count) in self.registers.iter().enumerate() {
+            row("register", &format!("reg{}", i), *count, 0.0);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zisk_core::ZiskInstBuilder;
+
+    fn inst(op: &str) -> ZiskInst {
+        let mut zib = ZiskInstBuilder::new(0);
+        zib.op(op).unwrap();
+        zib.build();
+        zib.i
+    }
+
+    /// Returns statistics of 10 steps with one memory access of every kind, 3 additions (one of
+    /// them usual), a keccak, a copyb and 2 accesses to register 1
+    fn stats() -> Stats {
+        let mut stats = Stats::default();
+        stats.on_memory_read(0x1000, 8);
+        stats.on_memory_read(0x1001, 4);
+        stats.on_memory_read(0x1006, 4);
+        stats.on_memory_write(0x1000, 8);
+        stats.on_memory_write(0x1000, 1);
+        stats.on_memory_write(0x1007, 2);
+        stats.on_op(&inst("add"), 1, 2);
+        stats.on_op(&inst("add"), 1000, 2);
+        stats.on_op(&inst("add"), 1, 1000);
+        stats.on_op(&inst("keccak"), 1, 2);
+        stats.on_op(&inst("copyb"), 1, 2);
+        stats.on_register_read(1);
+        stats.on_register_write(1);
+        stats.on_steps(10);
+        stats
+    }
+
+    #[test]
+    fn test_summary() {
+        let cost_model = StatsCostModel {
+            area_per_sec: 100.0,
+            ops: BTreeMap::from([("add".to_string(), 5.0)]),
+            ..Default::default()
+        };
+        let report = stats().summary(&cost_model);
+
+        assert_eq!(report.steps, 10);
+        assert_eq!(report.usual_ops, 1);
+        assert_eq!(report.total_ops, 4);
+
+        let m = &report.memory;
+        assert_eq!((m.aligned_reads, m.non_aligned_1_reads, m.non_aligned_2_reads), (1, 1, 1));
+        assert_eq!((m.aligned_writes, m.non_aligned_1_writes, m.non_aligned_2_writes), (1, 1, 1));
+        assert_eq!(m.mem_align_steps, 1 + 2 + 2 + 4);
+
+        // Opcodes in opcode order, with the usual operations counted but not costed
+        let names: Vec<&str> = report.opcodes.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["copyb", "add", "keccak"]);
+        let add = &report.opcodes[1];
+        assert_eq!((add.count, add.usual, add.area_per_op), (3, 1, 5.0));
+        assert_eq!(add.cost, 2.0 * 5.0 / 100.0);
+        let keccak = &report.opcodes[2];
+        assert_eq!(keccak.area_per_op, ZiskOp::Keccak.steps() as f64);
+
+        let op_types: Vec<(&str, u64)> =
+            report.op_types.iter().map(|t| (t.op_type.as_str(), t.count)).collect();
+        assert_eq!(op_types, [("Binary", 3), ("Internal", 1), ("Keccak", 1)]);
+        assert_eq!(report.precompiles, BTreeMap::from([("keccak".to_string(), 1)]));
+        assert_eq!(report.registers[1], 2);
+        assert_eq!(report.registers.iter().sum::<u64>(), 2);
+
+        let costs = &report.costs;
+        assert_eq!(costs.main, 10.0 * cost_model.step / 100.0);
+        assert_eq!(costs.mem, 6.0 * cost_model.mem / 100.0);
+        assert_eq!(
+            costs.mem_align,
+            (cost_model.mema_r1 + cost_model.mema_r2 + cost_model.mema_w1 + cost_model.mema_w2)
+                / 100.0
+        );
+        assert_eq!(costs.usual, cost_model.usual / 100.0);
+        assert_eq!(costs.opcodes, report.opcodes.iter().map(|o| o.cost).sum::<f64>());
+        assert_eq!(
+            costs.total,
+            costs.main + costs.mem + costs.mem_align + costs.opcodes + costs.usual
+        );
+    }
+
+    #[test]
+    fn test_cost_model_defaults() {
+        let cost_model: StatsCostModel =
+            serde_json::from_str(r#"{ "step": 1.5, "ops": { "keccak": 7 } }"#).unwrap();
+        assert_eq!(cost_model.step, 1.5);
+        assert_eq!(cost_model.mem, StatsCostModel::default().mem);
+        assert_eq!(cost_model.op_area(ZiskOp::Keccak), 7.0);
+        assert_eq!(cost_model.op_area(ZiskOp::Add), ZiskOp::Add.steps() as f64);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let report = stats().summary(&StatsCostModel::default());
+        let csv = report.to_csv();
+        let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+
+        assert_eq!(rows[0], ["section", "name", "count", "cost"]);
+        assert!(rows.iter().all(|row| row.len() == 4));
+        assert_eq!(rows[1], ["summary", "steps", "10", &report.costs.main.to_string()]);
+
+        let find = |section: &str, name: &str| {
+            rows.iter().find(|row| row[0] == section && row[1] == name).map(|row| row[2])
+        };
+        assert_eq!(find("memory", "mem_align"), Some("9"));
+        assert_eq!(find("opcode", "add"), Some("3"));
+        assert_eq!(find("op_type", "Binary"), Some("3"));
+        assert_eq!(find("precompile", "keccak"), Some("1"));
+        assert_eq!(find("register", "reg1"), Some("2"));
+        assert_eq!(find("opcode", "sub"), None);
+
+        // Summary and memory rows, then one row per opcode, operation type, precompile and
+        // register
+        assert_eq!(rows.len(), 1 + 4 + 8 + 3 + 3 + 1 + REGS_IN_MAIN_TOTAL_NUMBER);
+    }
 }
@@ -19,7 +19,9 @@
 //! By structuring these phases, the `ZiskExecutor` ensures high-performance execution while
 //! maintaining clarity and modularity in the computation process.
 
-use asm_runner::{AsmRunnerMT, MinimalTraces, Task, TaskFactory};
+use asm_runner::{
+    AsmRunError, AsmRunnerMT, AsmRunnerOptions, AsmServerMT, MinimalTraces, Task, TaskFactory,
+};
 use p3_field::PrimeField64;
 use pil_std_lib::Std;
 use proofman_common::{ProofCtx, SetupCtx};
@@ -46,8 +48,8 @@ use std::{
     sync::{Arc, Mutex, RwLock},
 };
 use zisk_common::EmuTrace;
-use zisk_core::ZiskRom;
-use ziskemu::{EmuOptions, ZiskEmulator};
+use zisk_core::{GuestExitStatus, ResourceLimitExceeded, ResourceLimits, UserFcalls, ZiskRom};
+use ziskemu::{EmuOptions, ZiskEmulator, ZiskEmulatorErr};
 
 use crate::SMBundle;
 
@@ -55,16 +57,41 @@ type DeviceMetricsByChunk = (ChunkId, Box<dyn BusDeviceMetrics>); // (chunk_id,
 type DeviceMetricsList = Vec<DeviceMetricsByChunk>;
 pub type NestedDeviceMetricsList = Vec<DeviceMetricsList>;
 
+/// Path to the input data file of the next execution, shared between the prover and a witness
+/// library that is kept loaded to prove many inputs of the same program.
+pub type SharedInputPath = Arc<RwLock<Option<PathBuf>>>;
+
 #[derive(Debug, Default, Clone)]
 pub struct ZiskExecutionResult {
     pub executed_steps: u64,
+
+    /// Exit status of the guest program.  The assembly emulator only reports the exit code, so
+    /// the panic message is only available when the minimal traces are computed by the emulator.
+    pub exit_status: GuestExitStatus,
 }
 
+/// Execution planned as the prover plans it, but without a proof context.
+#[derive(Debug, Default, Clone)]
+pub struct ZiskExecutionPlan {
+    pub result: ZiskExecutionResult,
+
+    /// Public values set by the program, as `(index, value)` pairs.
+    pub public_values: Vec<(u64, u32)>,
+
+    /// Number of planned instances of every AIR, as `(airgroup_id, air_id, instances)` tuples
+    /// sorted by AIR.
+    pub instances: Vec<(usize, usize, usize)>,
+}
+
+/// Minimal traces, main and secondary plans and public values of a planned execution.
+type Planning = (MinimalTraces, Vec<Plan>, Vec<Vec<Plan>>, Vec<(u64, u32)>);
+
 #[allow(dead_code)]
 enum MinimalTraceExecutionMode {
     Emulator,
     Asm,
     AsmWithCounter,
+    AsmServer,
 }
 
 /// The `ZiskExecutor` struct orchestrates the execution of the ZisK ROM program, managing state
@@ -88,10 +115,24 @@ pub struct ZiskExecutor<F: PrimeField64, BD: SMBundle<F>> {
 
     pub main_instances: RwLock<HashMap<usize, MainInstance>>,
     pub secn_instances: RwLock<HashMap<usize, Box<dyn Instance<F>>>>,
-    std: Arc<Std<F>>,
+
+    /// PIL2 standard library, only missing in executors that just plan executions.
+    std: Option<Arc<Std<F>>>,
 
     execution_result: Mutex<ZiskExecutionResult>,
 
+    /// Resource limits of the guest program execution.
+    resource_limits: ResourceLimits,
+
+    /// Host functions serving the user-defined fcalls of the guest program, if any.
+    user_fcalls: Option<Arc<UserFcalls>>,
+
+    /// Resident assembly emulator, shared by the executors of consecutive proofs, if any.
+    asm_server: Option<Arc<Mutex<AsmServerMT>>>,
+
+    /// Whether to go on with an execution whose guest program failed.
+    allow_panic: bool,
+
     main_count: Mutex<Option<DeviceMetricsList>>,
     secn_count: Mutex<Option<NestedDeviceMetricsList>>,
     sm_bundle: BD,
@@ -119,6 +160,44 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
         zisk_rom: Arc<ZiskRom>,
         std: Arc<Std<F>>,
         sm_bundle: BD,
+    ) -> Self {
+        Self::with_std(
+            rom_path,
+            asm_path,
+            asm_rom_path,
+            input_data_path,
+            zisk_rom,
+            Some(std),
+            sm_bundle,
+        )
+    }
+
+    /// Creates an executor that only plans executions with `execute_plan`, which does not need
+    /// the PIL2 standard library, and hence the proving key.  The bundle only has to count and
+    /// plan.
+    ///
+    /// # Arguments
+    /// * `input_data_path` - Path to the input data file.
+    /// * `zisk_rom` - An `Arc`-wrapped ZisK ROM instance.
+    pub fn new_planner(
+        rom_path: PathBuf,
+        asm_path: Option<PathBuf>,
+        input_data_path: Option<PathBuf>,
+        zisk_rom: Arc<ZiskRom>,
+        sm_bundle: BD,
+    ) -> Self {
+        Self::with_std(rom_path, asm_path, None, input_data_path, zisk_rom, None, sm_bundle)
+            .with_allow_panic(true)
+    }
+
+    fn with_std(
+        rom_path: PathBuf,
+        asm_path: Option<PathBuf>,
+        asm_rom_path: Option<PathBuf>,
+        input_data_path: Option<PathBuf>,
+        zisk_rom: Arc<ZiskRom>,
+        std: Option<Arc<Std<F>>>,
+        sm_bundle: BD,
     ) -> Self {
         Self {
             input_data_path,
@@ -133,12 +212,118 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
             secn_instances: RwLock::new(HashMap::new()),
             std,
             execution_result: Mutex::new(ZiskExecutionResult::default()),
+            resource_limits: ResourceLimits {
+                max_steps: Self::MAX_NUM_STEPS,
+                ..ResourceLimits::default()
+            },
+            user_fcalls: None,
+            asm_server: None,
+            allow_panic: false,
             main_count: Mutex::new(None),
             secn_count: Mutex::new(None),
             sm_bundle,
         }
     }
 
+    /// Sets the resource limits of the guest program execution, enforced by both the emulator and
+    /// the assembly emulator when they compute the minimal traces.
+    pub fn with_resource_limits(mut self, resource_limits: ResourceLimits) -> Self {
+        self.resource_limits = resource_limits;
+        self
+    }
+
+    /// Goes on with the execution even if the guest program panics or ends with a non-zero exit
+    /// code.  Otherwise, which is the default except for executors that only plan, the executor
+    /// panics once the minimal traces are computed, so that no time is spent proving a failed
+    /// execution.
+    pub fn with_allow_panic(mut self, allow_panic: bool) -> Self {
+        self.allow_panic = allow_panic;
+        self
+    }
+
+    /// Serves the user-defined fcalls of the guest program with the registered host functions,
+    /// both in the emulator and in the assembly emulator.  The ROM histogram assembly emulator is
+    /// run by `RomSM`, which gets the same functions in its constructor.
+    ///
+    /// The functions are called once per execution of the guest program, e.g. once per thread
+    /// when the minimal traces are computed by the emulator, so they must be deterministic.
+    pub fn with_user_fcalls(mut self, user_fcalls: UserFcalls) -> Self {
+        self.user_fcalls = Some(Arc::new(user_fcalls));
+        self
+    }
+
+    /// Computes the minimal traces with a resident assembly emulator instead of spawning one for
+    /// every execution, so that the ROM stays loaded when many inputs are proven in a row.
+    ///
+    /// The server must have been started with the same assembly emulator and user fcalls as
+    /// this executor.  Since the server does not count, the metrics are counted afterwards from
+    /// the minimal traces.
+    pub fn with_asm_server(mut self, asm_server: Arc<Mutex<AsmServerMT>>) -> Self {
+        self.asm_server = Some(asm_server);
+        self
+    }
+
+    /// Executes the program and plans its instances exactly as `execute` does for a proof, but
+    /// without assigning them to a proof context.
+    pub fn execute_plan(&self) -> Result<ZiskExecutionPlan, ResourceLimitExceeded> {
+        let (_, main_planning, secn_planning, public_values) = self.plan_execution()?;
+
+        let mut instances = HashMap::new();
+        for plan in main_planning.iter().chain(secn_planning.iter().flatten()) {
+            *instances.entry((plan.airgroup_id, plan.air_id)).or_insert(0) += 1;
+        }
+        let mut instances: Vec<(usize, usize, usize)> = instances
+            .into_iter()
+            .map(|((airgroup_id, air_id), count)| (airgroup_id, air_id, count))
+            .collect();
+        instances.sort();
+
+        Ok(ZiskExecutionPlan { result: self.get_execution_result(), public_values, instances })
+    }
+
+    /// Computes the minimal traces, counts their metrics and plans the main and secondary
+    /// instances.
+    fn plan_execution(&self) -> Result<Planning, ResourceLimitExceeded> {
+        // Process the ROM to collect the Minimal Traces
+        timer_start_info!(COMPUTE_MINIMAL_TRACE);
+        let min_traces_execution_mode = if self.asm_runner_path.is_none() {
+            MinimalTraceExecutionMode::Emulator
+        } else if self.asm_server.is_some() {
+            MinimalTraceExecutionMode::AsmServer
+        } else {
+            MinimalTraceExecutionMode::AsmWithCounter
+        };
+        let min_traces = self.compute_minimal_traces(min_traces_execution_mode)?;
+        timer_stop_and_log_info!(COMPUTE_MINIMAL_TRACE);
+
+        timer_start_info!(COUNT);
+        // Count the metrics for the Secondary SM instances
+        let (main_count, secn_count) = if self.main_count.lock().unwrap().is_none() {
+            self.count(&min_traces)
+        } else {
+            let main_count = self.main_count.lock().unwrap().take().unwrap();
+            let secn_count = self.secn_count.lock().unwrap().take().unwrap();
+
+            (main_count, secn_count)
+        };
+        timer_stop_and_log_info!(COUNT);
+
+        // Plan the main and secondary instances using the counted metrics
+        timer_start_info!(PLAN);
+        let (main_planning, public_values) =
+            MainPlanner::plan::<F>(&min_traces, main_count, Self::MIN_TRACE_SIZE);
+
+        let secn_planning = self.sm_bundle.plan_sec(secn_count);
+        timer_stop_and_log_info!(PLAN);
+
+        Ok((min_traces, main_planning, secn_planning, public_values))
+    }
+
+    /// Returns the options of the assembly emulator runs.
+    fn asm_runner_options(&self) -> AsmRunnerOptions {
+        AsmRunnerOptions { user_fcalls: self.user_fcalls.clone(), ..AsmRunnerOptions::default() }
+    }
+
     pub fn get_execution_result(&self) -> ZiskExecutionResult {
         self.execution_result.lock().unwrap().clone()
     }
@@ -150,13 +335,18 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
     /// * `num_threads` - Number of threads to use for parallel execution.
     ///
     /// # Returns
-    /// A vector of `EmuTrace` instances representing minimal traces.
-    fn compute_minimal_traces(&self, mode: MinimalTraceExecutionMode) -> MinimalTraces {
-        let min_traces = match mode {
+    /// A vector of `EmuTrace` instances representing minimal traces, or the resource limit
+    /// exceeded by the guest program.  Any other execution error panics.
+    fn compute_minimal_traces(
+        &self,
+        mode: MinimalTraceExecutionMode,
+    ) -> Result<MinimalTraces, ResourceLimitExceeded> {
+        let (min_traces, exit_status) = match mode {
             MinimalTraceExecutionMode::Emulator => self.run_emulator(Self::NUM_THREADS),
             MinimalTraceExecutionMode::Asm => self.run_assembly(),
             MinimalTraceExecutionMode::AsmWithCounter => self.run_and_count_assembly(),
-        };
+            MinimalTraceExecutionMode::AsmServer => self.run_assembly_server(),
+        }?;
 
         // Store execute steps
         let steps = match &min_traces {
@@ -171,22 +361,62 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
             }
         };
 
-        self.execution_result.lock().unwrap().executed_steps = steps;
+        *self.execution_result.lock().unwrap() =
+            ZiskExecutionResult { executed_steps: steps, exit_status: exit_status.clone() };
 
-        min_traces
+        if !self.allow_panic && !exit_status.success() {
+            panic!("Guest program {}, refusing to go on with a failed execution", exit_status);
+        }
+
+        Ok(min_traces)
+    }
+
+    /// Returns the guest exit status reported by the assembly emulator.
+    fn asm_exit_status(asm_runner_mt: &AsmRunnerMT) -> GuestExitStatus {
+        GuestExitStatus { exit_code: asm_runner_mt.guest_exit_code, panic_message: None }
     }
 
-    fn run_assembly(&self) -> MinimalTraces {
-        MinimalTraces::AsmEmuTrace(AsmRunnerMT::run(
+    /// Returns the result of an assembly emulator run, or the resource limit exceeded by the
+    /// guest program.  Any other error panics.
+    fn asm_result<T>(result: Result<T, AsmRunError>) -> Result<T, ResourceLimitExceeded> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(AsmRunError::ResourceLimitExceeded(e)) => Err(e),
+            Err(e) => panic!("Error during assembly execution: {}", e),
+        }
+    }
+
+    fn run_assembly(&self) -> Result<(MinimalTraces, GuestExitStatus), ResourceLimitExceeded> {
+        let asm_runner_mt = Self::asm_result(AsmRunnerMT::run(
             self.asm_runner_path.as_ref().unwrap(),
             self.input_data_path.as_ref().unwrap(),
-            Self::MAX_NUM_STEPS,
+            &self.resource_limits,
             Self::MIN_TRACE_SIZE,
-            asm_runner::AsmRunnerOptions::default(),
-        ))
+            self.asm_runner_options(),
+        ))?;
+
+        let exit_status = Self::asm_exit_status(&asm_runner_mt);
+        Ok((MinimalTraces::AsmEmuTrace(asm_runner_mt), exit_status))
     }
 
-    fn run_and_count_assembly(&self) -> MinimalTraces {
+    fn run_assembly_server(
+        &self,
+    ) -> Result<(MinimalTraces, GuestExitStatus), ResourceLimitExceeded> {
+        // The lock is released before panicking, so that the server can serve the next proof
+        let result = self.asm_server.as_ref().unwrap().lock().unwrap().run(
+            self.input_data_path.as_ref().unwrap(),
+            &self.resource_limits,
+            Self::MIN_TRACE_SIZE,
+        );
+        let asm_runner_mt = Self::asm_result(result)?;
+
+        let exit_status = Self::asm_exit_status(&asm_runner_mt);
+        Ok((MinimalTraces::AsmEmuTrace(asm_runner_mt), exit_status))
+    }
+
+    fn run_and_count_assembly(
+        &self,
+    ) -> Result<(MinimalTraces, GuestExitStatus), ResourceLimitExceeded> {
         struct CounterTask<F, DB>
         where
             DB: DataBusTrait<PayloadType, Box<dyn BusDeviceMetrics>>,
@@ -241,14 +471,14 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
             }
         });
 
-        let (asm_runner_mt, mut data_buses) = AsmRunnerMT::run_and_count(
+        let (asm_runner_mt, mut data_buses) = Self::asm_result(AsmRunnerMT::run_and_count(
             self.asm_runner_path.as_ref().unwrap(),
             self.input_data_path.as_ref().unwrap(),
-            Self::MAX_NUM_STEPS,
+            &self.resource_limits,
             Self::MIN_TRACE_SIZE,
-            asm_runner::AsmRunnerOptions::default(),
+            self.asm_runner_options(),
             task_factory,
-        );
+        ))?;
 
         data_buses.sort_by_key(|(chunk_id, _)| chunk_id.0);
 
@@ -287,10 +517,14 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
         self.main_count.lock().unwrap().replace(main_count);
         self.secn_count.lock().unwrap().replace(secn_vec_counters);
 
-        MinimalTraces::AsmEmuTrace(asm_runner_mt)
+        let exit_status = Self::asm_exit_status(&asm_runner_mt);
+        Ok((MinimalTraces::AsmEmuTrace(asm_runner_mt), exit_status))
     }
 
-    fn run_emulator(&self, num_threads: usize) -> MinimalTraces {
+    fn run_emulator(
+        &self,
+        num_threads: usize,
+    ) -> Result<(MinimalTraces, GuestExitStatus), ResourceLimitExceeded> {
         assert!(Self::MIN_TRACE_SIZE.is_power_of_two());
 
         // Call emulate with these options
@@ -303,21 +537,23 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
         };
 
         // Settings for the emulator
-        let emu_options = EmuOptions {
-            trace_steps: Some(Self::MIN_TRACE_SIZE),
-            max_steps: Self::MAX_NUM_STEPS,
-            ..EmuOptions::default()
-        };
+        let mut emu_options =
+            EmuOptions { trace_steps: Some(Self::MIN_TRACE_SIZE), ..EmuOptions::default() };
+        emu_options.set_resource_limits(&self.resource_limits);
+        emu_options.user_fcalls = self.user_fcalls.clone();
 
-        let min_traces = ZiskEmulator::compute_minimal_traces(
+        let (min_traces, exit_status) = match ZiskEmulator::compute_minimal_traces(
             &self.zisk_rom,
             &input_data,
             &emu_options,
             num_threads,
-        )
-        .expect("Error during emulator execution");
+        ) {
+            Ok(result) => result,
+            Err(ZiskEmulatorErr::ResourceLimitExceeded(e)) => return Err(e),
+            Err(e) => panic!("Error during emulator execution: {}", e),
+        };
 
-        MinimalTraces::EmuTrace(min_traces)
+        Ok((MinimalTraces::EmuTrace(min_traces), exit_status))
     }
 
     /// Adds main state machine instances to the proof context and assigns global IDs.
@@ -492,7 +728,7 @@ impl<F: PrimeField64, BD: SMBundle<F>> ZiskExecutor<F, BD> {
             min_traces,
             Self::MIN_TRACE_SIZE,
             main_instance,
-            self.std.clone(),
+            self.std.clone().expect("Executor created without the PIL2 standard library"),
         );
 
         pctx.add_air_instance(air_instance, main_instance.ictx.global_id);
@@ -640,35 +876,10 @@ impl<F: PrimeField64, BD: SMBundle<F>> WitnessComponent<F> for ZiskExecutor<F, B
     /// # Returns
     /// A vector of global IDs for the instances to compute witness for.
     fn execute(&self, pctx: Arc<ProofCtx<F>>) -> Vec<usize> {
-        // Process the ROM to collect the Minimal Traces
-        timer_start_info!(COMPUTE_MINIMAL_TRACE);
-        let min_traces_execution_mode = if self.asm_runner_path.is_none() {
-            MinimalTraceExecutionMode::Emulator
-        } else {
-            MinimalTraceExecutionMode::AsmWithCounter
-        };
-        let min_traces = self.compute_minimal_traces(min_traces_execution_mode);
-        timer_stop_and_log_info!(COMPUTE_MINIMAL_TRACE);
-
-        timer_start_info!(COUNT);
-        // Count the metrics for the Secondary SM instances
-        let (main_count, secn_count) = if self.main_count.lock().unwrap().is_none() {
-            self.count(&min_traces)
-        } else {
-            let main_count = self.main_count.lock().unwrap().take().unwrap();
-            let secn_count = self.secn_count.lock().unwrap().take().unwrap();
-
-            (main_count, secn_count)
-        };
-        timer_stop_and_log_info!(COUNT);
-
-        // Plan the main and secondary instances using the counted metrics
-        timer_start_info!(PLAN);
-        let (mut main_planning, public_values) =
-            MainPlanner::plan::<F>(&min_traces, main_count, Self::MIN_TRACE_SIZE);
-
-        let mut secn_planning = self.sm_bundle.plan_sec(secn_count);
-        timer_stop_and_log_info!(PLAN);
+        // Witness components cannot return errors, so a guest program exceeding its limits is
+        // reported as a panic
+        let (min_traces, mut main_planning, mut secn_planning, public_values) =
+            self.plan_execution().unwrap_or_else(|e| panic!("Guest program {}", e));
 
         // Configure the instances
         self.sm_bundle.configure_instances(&pctx, &secn_planning);
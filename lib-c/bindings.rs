@@ -55,10 +55,10 @@ pub struct FcallContext {
     pub function_id: ::std::os::raw::c_ulong,
     pub params_max_size: ::std::os::raw::c_ulong,
     pub params_size: ::std::os::raw::c_ulong,
-    pub params: [::std::os::raw::c_ulong; 32usize],
+    pub params: *mut ::std::os::raw::c_ulong,
     pub result_max_size: ::std::os::raw::c_ulong,
     pub result_size: ::std::os::raw::c_ulong,
-    pub result: [::std::os::raw::c_ulong; 32usize],
+    pub result: *mut ::std::os::raw::c_ulong,
 }
 
 extern "C" {
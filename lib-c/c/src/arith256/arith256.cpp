@@ -54,4 +54,110 @@ int Arith256Mod (
     scalar2array(d, _d);
 
     return 0;
-}
\ No newline at end of file
+}
+mpz_class Ed25519Prime("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed", 16);
+
+int Curve25519Mod (
+    const uint64_t * _a, // 4 x 64 bits
+    const uint64_t * _b, // 4 x 64 bits
+    const uint64_t * _c, // 4 x 64 bits
+          uint64_t * _d  // 4 x 64 bits
+)
+{
+    // Convert input parameters to scalars
+    mpz_class a, b, c;
+    array2scalar(_a, a);
+    array2scalar(_b, b);
+    array2scalar(_c, c);
+
+    // Calculate the result as a scalar
+    mpz_class d;
+    d = ((a * b) + c) % Ed25519Prime;
+
+    // Convert scalar to output parameter
+    scalar2array(d, _d);
+
+    return 0;
+}
+
+int Ed25519Dbl (
+    const uint64_t * _p1, // 8 x 64 bits
+          uint64_t * _p3  // 8 x 64 bits
+)
+{
+    // Convert input parameters to scalars
+    mpz_class x1, y1;
+    array2scalar(_p1, x1);
+    array2scalar(_p1 + 4, y1);
+
+    // s = y1^2 - x1^2, x3 = 2*x1*y1 / s, y3 = (y1^2 + x1^2) / (2 - s)
+    mpz_class x1_sq = x1 * x1;
+    mpz_class y1_sq = y1 * y1;
+    mpz_class s = y1_sq - x1_sq;
+    mpz_mod(s.get_mpz_t(), s.get_mpz_t(), Ed25519Prime.get_mpz_t());
+
+    mpz_class s_inv, t, t_inv;
+    if (mpz_invert(s_inv.get_mpz_t(), s.get_mpz_t(), Ed25519Prime.get_mpz_t()) == 0)
+    {
+        return -1;
+    }
+    t = 2 - s;
+    mpz_mod(t.get_mpz_t(), t.get_mpz_t(), Ed25519Prime.get_mpz_t());
+    if (mpz_invert(t_inv.get_mpz_t(), t.get_mpz_t(), Ed25519Prime.get_mpz_t()) == 0)
+    {
+        return -1;
+    }
+
+    mpz_class x3, y3;
+    x3 = (2 * x1 * y1 * s_inv) % Ed25519Prime;
+    y3 = ((y1_sq + x1_sq) * t_inv) % Ed25519Prime;
+
+    // Convert scalars to output parameters
+    scalar2array(x3, _p3);
+    scalar2array(y3, _p3 + 4);
+
+    return 0;
+}
+
+mpz_class Ed25519D("52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3", 16);
+
+int Ed25519Add (
+    const uint64_t * _p1, // 8 x 64 bits
+    const uint64_t * _p2, // 8 x 64 bits
+          uint64_t * _p3  // 8 x 64 bits
+)
+{
+    // Convert input parameters to scalars
+    mpz_class x1, y1, x2, y2;
+    array2scalar(_p1, x1);
+    array2scalar(_p1 + 4, y1);
+    array2scalar(_p2, x2);
+    array2scalar(_p2 + 4, y2);
+
+    // u = d*x1*y1*x2*y2, x3 = (x1*y2 + y1*x2) / (1 + u), y3 = (y1*y2 + x1*x2) / (1 - u)
+    mpz_class u = (Ed25519D * ((x1 * y1) % Ed25519Prime) * ((x2 * y2) % Ed25519Prime)) % Ed25519Prime;
+
+    mpz_class den_x = (1 + u) % Ed25519Prime;
+    mpz_class den_y = 1 - u;
+    mpz_mod(den_y.get_mpz_t(), den_y.get_mpz_t(), Ed25519Prime.get_mpz_t());
+
+    mpz_class den_x_inv, den_y_inv;
+    if (mpz_invert(den_x_inv.get_mpz_t(), den_x.get_mpz_t(), Ed25519Prime.get_mpz_t()) == 0)
+    {
+        return -1;
+    }
+    if (mpz_invert(den_y_inv.get_mpz_t(), den_y.get_mpz_t(), Ed25519Prime.get_mpz_t()) == 0)
+    {
+        return -1;
+    }
+
+    mpz_class x3, y3;
+    x3 = ((x1 * y2 + y1 * x2) * den_x_inv) % Ed25519Prime;
+    y3 = ((y1 * y2 + x1 * x2) * den_y_inv) % Ed25519Prime;
+
+    // Convert scalars to output parameters
+    scalar2array(x3, _p3);
+    scalar2array(y3, _p3 + 4);
+
+    return 0;
+}
@@ -23,6 +23,27 @@ int Arith256Mod (
     unsigned long * d // 4 x 64 bits
 );
 
+// Computes d = ((a * b) + c) % p, being p the curve25519 base field prime 2^255 - 19
+int Curve25519Mod (
+    const unsigned long * a,  // 4 x 64 bits
+    const unsigned long * b,  // 4 x 64 bits
+    const unsigned long * c,  // 4 x 64 bits
+    unsigned long * d // 4 x 64 bits
+);
+
+// Computes p3 = 2 * p1 over the ed25519 twisted Edwards curve
+int Ed25519Dbl (
+    const unsigned long * p1, // 8 x 64 bits
+    unsigned long * p3 // 8 x 64 bits
+);
+
+// Computes p3 = p1 + p2 over the ed25519 twisted Edwards curve
+int Ed25519Add (
+    const unsigned long * p1, // 8 x 64 bits
+    const unsigned long * p2, // 8 x 64 bits
+    unsigned long * p3 // 8 x 64 bits
+);
+
 #ifdef __cplusplus
 } // extern "C"
 #endif
@@ -1,6 +1,7 @@
 #include "fcall.hpp"
 #include "../common/utils.hpp"
 #include <stdint.h>
+#include <string.h>
 
 int Fcall (
     struct FcallContext * ctx  // fcall context
@@ -30,6 +31,16 @@ int Fcall (
             iresult = MsbPos256Ctx(ctx);
             break;
         }
+        case FCALL_ID_BIGINT_DIV:
+        {
+            iresult = BigIntDivCtx(ctx);
+            break;
+        }
+        case FCALL_ID_BIGINT_MODINV:
+        {
+            iresult = BigIntModInvCtx(ctx);
+            break;
+        }
         default:
         {
             printf("Fcall() found unsupported function_id=%llu\n", ctx->function_id);
@@ -259,3 +270,96 @@ int MsbPos256Ctx (
     }
     return iresult;
 }
+
+/**********/
+/* BIGINT */
+/**********/
+
+inline void limbs2scalar (const uint64_t * a, uint64_t len, mpz_class &s)
+{
+    mpz_import(s.get_mpz_t(), len, -1, 8, 0, 0, a);
+}
+
+inline bool scalar2limbs (mpz_class &s, uint64_t * a, uint64_t len)
+{
+    if (mpz_sizeinbase(s.get_mpz_t(), 2) > len * 64)
+    {
+        return false;
+    }
+    memset(a, 0, len * 8);
+    mpz_export(a, NULL, -1, 8, 0, 0, s.get_mpz_t());
+    return true;
+}
+
+// Parameters: [len_a, len_m, a[len_a], m[len_m]]
+// Results: [q[len_a], r[len_m]]
+int BigIntDivCtx (
+    struct FcallContext * ctx  // fcall context
+)
+{
+    uint64_t len_a = ctx->params[0];
+    uint64_t len_m = ctx->params[1];
+    if ((2 + len_a + len_m > ctx->params_size) || (len_a + len_m > ctx->result_max_size))
+    {
+        printf("BigIntDivCtx() invalid sizes len_a=%lu len_m=%lu\n", len_a, len_m);
+        return -1;
+    }
+
+    mpz_class a, m, q, r;
+    limbs2scalar(&ctx->params[2], len_a, a);
+    limbs2scalar(&ctx->params[2 + len_a], len_m, m);
+    if (m == 0)
+    {
+        printf("BigIntDivCtx() Division by zero\n");
+        return -1;
+    }
+    mpz_fdiv_qr(q.get_mpz_t(), r.get_mpz_t(), a.get_mpz_t(), m.get_mpz_t());
+
+    if (!scalar2limbs(q, &ctx->result[0], len_a) || !scalar2limbs(r, &ctx->result[len_a], len_m))
+    {
+        printf("BigIntDivCtx() Result does not fit in len_a=%lu len_m=%lu\n", len_a, len_m);
+        return -1;
+    }
+    ctx->result_size = len_a + len_m;
+    return ctx->result_size;
+}
+
+// Parameters: [len, a[len], m[len]]
+// Results: [1, inv[len]] if the inverse exists, [0, gcd(a, m)[len]] otherwise
+int BigIntModInvCtx (
+    struct FcallContext * ctx  // fcall context
+)
+{
+    uint64_t len = ctx->params[0];
+    if ((1 + 2 * len > ctx->params_size) || (1 + len > ctx->result_max_size))
+    {
+        printf("BigIntModInvCtx() invalid size len=%lu\n", len);
+        return -1;
+    }
+
+    mpz_class a, m, r;
+    limbs2scalar(&ctx->params[1], len, a);
+    limbs2scalar(&ctx->params[1 + len], len, m);
+    if (m == 0)
+    {
+        printf("BigIntModInvCtx() Modulus is zero\n");
+        return -1;
+    }
+
+    if (mpz_invert(r.get_mpz_t(), a.get_mpz_t(), m.get_mpz_t()) != 0)
+    {
+        ctx->result[0] = 1;
+    }
+    else
+    {
+        mpz_gcd(r.get_mpz_t(), a.get_mpz_t(), m.get_mpz_t());
+        ctx->result[0] = 0;
+    }
+    if (!scalar2limbs(r, &ctx->result[1], len))
+    {
+        printf("BigIntModInvCtx() Result does not fit in len=%lu\n", len);
+        return -1;
+    }
+    ctx->result_size = 1 + len;
+    return ctx->result_size;
+}
@@ -12,17 +12,25 @@ extern "C" {
 #define FCALL_ID_INVERSE_FN_EC 2
 #define FCALL_ID_SQRT_FP_EC_PARITY 3
 #define FCALL_ID_MSB_POS_256 4
+#define FCALL_ID_BIGINT_DIV 6
+#define FCALL_ID_BIGINT_MODINV 7
 
-// Fcall context
+// Identifiers from this one on are reserved to user-defined fcalls, served by the host
+#define FCALL_USER_ID_FIRST 512
+
+// Minimum length of the fcall result array, enough for any fixed-size result
+#define FCALL_RESULT_MIN_SIZE 32
+
+// Fcall context; the parameters and result arrays are owned by the caller, which can grow them
 struct FcallContext
 {
     uint64_t function_id; // identifies what function to call
     uint64_t params_max_size; // max length of input parameters array
     uint64_t params_size; // input parameters array valid data size
-    uint64_t params[32]; // input parameters array
+    uint64_t * params; // input parameters array
     uint64_t result_max_size; // max length of output result array
     uint64_t result_size; // output result array valid data size (written by fcall)
-    uint64_t result[32]; // output result array (written by fcall)
+    uint64_t * result; // output result array (written by fcall)
 };
 
 // Fcall function; calls the corresponding function based on function identifier
@@ -43,6 +51,12 @@ int SqrtFpEcParityCtx (
 int MsbPos256Ctx (
     struct FcallContext * ctx  // fcall context
 );
+int BigIntDivCtx (
+    struct FcallContext * ctx  // fcall context
+);
+int BigIntModInvCtx (
+    struct FcallContext * ctx  // fcall context
+);
 
 // Functions supported by fcall, in u64 array format
 int InverseFpEc (
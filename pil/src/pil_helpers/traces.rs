@@ -66,7 +66,13 @@ pub const SHA_256_F_AIR_IDS: &[usize] = &[19];
 
 pub const SHA_256_F_TABLE_AIR_IDS: &[usize] = &[20];
 
-pub const SPECIFIED_RANGES_AIR_IDS: &[usize] = &[21];
+pub const BLAKE_2_BR_AIR_IDS: &[usize] = &[21];
+
+pub const BLAKE_2_BR_TABLE_AIR_IDS: &[usize] = &[22];
+
+pub const DMA_AIR_IDS: &[usize] = &[23];
+
+pub const SPECIFIED_RANGES_AIR_IDS: &[usize] = &[24];
 
 //PUBLICS
 use serde::Deserialize;
@@ -188,7 +194,7 @@ trace!(ArithEqFixed<F> {
 },  0, 10, 2097152 );
 
 trace!(ArithEqTrace<F> {
- x1: F, y1: F, x2: F, y2: F, x3: F, y3: F, q0: F, q1: F, q2: F, s: F, sel_op: [F; 4], sel_op_clk0: [F; 4], x_delta_chunk_inv: F, x_are_different: F, x3_lt: F, y3_lt: F, carry: [[F; 2]; 3], step_addr: F,
+ x1: F, y1: F, x2: F, y2: F, x3: F, y3: F, q0: F, q1: F, q2: F, q3: F, q4: F, s: F, t: F, u: F, sel_op: [F; 7], sel_op_clk0: [F; 7], x_delta_chunk_inv: F, x_are_different: F, x3_lt: F, y3_lt: F, carry: [[F; 2]; 5], step_addr: F,
 },  0, 10, 2097152 );
 
 trace!(ArithEqLtTableFixed<F> {
@@ -271,13 +277,37 @@ trace!(Sha256fTableTrace<F> {
  multiplicity: [F; 1],
 },  0, 20, 8388608 );
 
+trace!(Blake2brFixed<F> {
+ L1: F, GATE_OP: F, CONN_A: F, CONN_B: F, CONN_C: F, ID: F, latch_num_blake2br: F, factor_num_blake2br: F, latch_in_out: F, addr_inc: F, latch_in: F, latch_out: F, __L1__: F,
+},  0, 21, 4194304 );
+
+trace!(Blake2brTrace<F> {
+ free_in_a: [F; 3], free_in_b: [F; 3], free_in_c: [F; 3], step: F, addr: F, multiplicity: F, bit: [F; 2], val: [F; 2], is_val: F,
+},  0, 21, 4194304 );
+
+trace!(Blake2brTableFixed<F> {
+ A: [F; 1], B: F, GATE_OP: F, C: [F; 1], __L1__: F,
+},  0, 22, 2048 );
+
+trace!(Blake2brTableTrace<F> {
+ multiplicity: [F; 1],
+},  0, 22, 2048 );
+
+trace!(DmaFixed<F> {
+ __L1__: F,
+},  0, 23, 2097152 );
+
+trace!(DmaTrace<F> {
+ step: F, addr: F, dst: F, src: F, count: F, value: [F; 2], sel: F, is_first: F, is_last: F, is_set: F,
+},  0, 23, 2097152 );
+
 trace!(SpecifiedRangesFixed<F> {
  RANGE: [F; 16], __L1__: F,
-},  0, 21, 2097152 );
+},  0, 24, 2097152 );
 
 trace!(SpecifiedRangesTrace<F> {
  mul: [F; 16],
-},  0, 21, 2097152 );
+},  0, 24, 2097152 );
 
 trace!(RomRomTrace<F> {
  line: F, a_offset_imm0: F, a_imm1: F, b_offset_imm0: F, b_imm1: F, ind_width: F, op: F, store_offset: F, jmp_offset1: F, jmp_offset2: F, flags: F,
@@ -383,6 +413,18 @@ values!(Sha256fTableAirGroupValues<F> {
  gsum_result: FieldExtension<F>,
 });
 
+values!(Blake2brAirGroupValues<F> {
+ gsum_result: FieldExtension<F>,
+});
+
+values!(Blake2brTableAirGroupValues<F> {
+ gsum_result: FieldExtension<F>,
+});
+
+values!(DmaAirGroupValues<F> {
+ gsum_result: FieldExtension<F>,
+});
+
 values!(SpecifiedRangesAirGroupValues<F> {
  gsum_result: FieldExtension<F>,
 });
@@ -10,8 +10,10 @@ use zisk_pil::ArithEqTrace;
 
 use crate::{
     arith_eq_constants::*, executors, Arith256Input, Arith256ModInput, ArithEqInput,
-    ArithEqLtTableSM, Secp256k1AddInput, Secp256k1DblInput, SECP256K1_PRIME_CHUNKS,
-    SEL_OP_ARITH256, SEL_OP_ARITH256_MOD, SEL_OP_SECP256K1_ADD, SEL_OP_SECP256K1_DBL,
+    ArithEqLtTableSM, Curve25519ModInput, Ed25519AddInput, Ed25519DblInput, Secp256k1AddInput,
+    Secp256k1DblInput, ED25519_PRIME_CHUNKS, SECP256K1_PRIME_CHUNKS, SEL_OP_ARITH256,
+    SEL_OP_ARITH256_MOD, SEL_OP_CURVE25519_MOD, SEL_OP_ED25519_ADD, SEL_OP_ED25519_DBL,
+    SEL_OP_SECP256K1_ADD, SEL_OP_SECP256K1_DBL,
 };
 
 /// The `ArithEqSM` struct encapsulates the logic of the ArithEq State Machine.
@@ -44,13 +46,16 @@ struct ArithEqStepAddr {
 impl<F: PrimeField64> ArithEqSM<F> {
     const MY_NAME: &'static str = "ArithEq  ";
 
+    /// Number of available arith256s in the trace.
+    pub const NUM_AVAILABLE_OPS: usize = ArithEqTrace::<usize>::NUM_ROWS / ARITH_EQ_ROWS_BY_OP;
+
     /// Creates a new ArithEq State Machine instance.
     ///
     /// # Returns
     /// A new `ArithEqSM` instance.
     pub fn new(std: Arc<Std<F>>, arith_eq_lt_table_sm: Arc<ArithEqLtTableSM>) -> Arc<Self> {
         // Compute some useful values
-        let num_available_ops = ArithEqTrace::<usize>::NUM_ROWS / ARITH_EQ_ROWS_BY_OP;
+        let num_available_ops = Self::NUM_AVAILABLE_OPS;
         let p2_22 = 1 << 22;
         let q_hsc_range_id = std.get_range(0, p2_22 - 1, None);
         let chunk_range_id = std.get_range(0, 0xFFFF, None);
@@ -186,6 +191,78 @@ impl<F: PrimeField64> ArithEqSM<F> {
             row_offset,
         );
     }
+    fn process_curve25519_mod(
+        &self,
+        input: &Curve25519ModInput,
+        trace: &mut ArithEqTrace<F>,
+        row_offset: usize,
+    ) {
+        let data = executors::Curve25519Mod::execute(&input.a, &input.b, &input.c);
+        self.expand_data_on_trace(&data, row_offset, trace, SEL_OP_CURVE25519_MOD);
+        Self::expand_addr_step_on_trace(
+            &ArithEqStepAddr {
+                main_step: input.step,
+                addr_op: input.addr,
+                addr_x1: input.a_addr,
+                addr_y1: input.b_addr,
+                addr_x2: input.c_addr,
+                addr_y2: 0,
+                addr_x3: input.d_addr,
+                addr_y3: 0,
+                addr_ind: [input.a_addr, input.b_addr, input.c_addr, input.d_addr, 0],
+            },
+            trace,
+            row_offset,
+        );
+    }
+    fn process_ed25519_dbl(
+        &self,
+        input: &Ed25519DblInput,
+        trace: &mut ArithEqTrace<F>,
+        row_offset: usize,
+    ) {
+        let data = executors::Ed25519::execute_dbl(&input.p1);
+        self.expand_data_on_trace(&data, row_offset, trace, SEL_OP_ED25519_DBL);
+        Self::expand_addr_step_on_trace(
+            &ArithEqStepAddr {
+                main_step: input.step,
+                addr_op: input.addr,
+                addr_x1: input.addr,
+                addr_y1: input.addr + 32,
+                addr_x2: 0,
+                addr_y2: 0,
+                addr_x3: input.addr,
+                addr_y3: input.addr + 32,
+                addr_ind: [0, 0, 0, 0, 0],
+            },
+            trace,
+            row_offset,
+        );
+    }
+    fn process_ed25519_add(
+        &self,
+        input: &Ed25519AddInput,
+        trace: &mut ArithEqTrace<F>,
+        row_offset: usize,
+    ) {
+        let data = executors::Ed25519::execute_add(&input.p1, &input.p2);
+        self.expand_data_on_trace(&data, row_offset, trace, SEL_OP_ED25519_ADD);
+        Self::expand_addr_step_on_trace(
+            &ArithEqStepAddr {
+                main_step: input.step,
+                addr_op: input.addr,
+                addr_x1: input.p1_addr,
+                addr_y1: input.p1_addr + 32,
+                addr_x2: input.p2_addr,
+                addr_y2: input.p2_addr + 32,
+                addr_x3: input.p1_addr,
+                addr_y3: input.p1_addr + 32,
+                addr_ind: [input.p1_addr, input.p2_addr, 0, 0, 0],
+            },
+            trace,
+            row_offset,
+        );
+    }
 
     #[inline(always)]
     fn to_ranged_field(&self, value: i64, range_id: usize) -> F {
@@ -206,7 +283,7 @@ impl<F: PrimeField64> ArithEqSM<F> {
         #[allow(clippy::needless_range_loop)]
         for i in 0..ARITH_EQ_ROWS_BY_OP {
             let irow = row_offset + i;
-            for j in 0..3 {
+            for j in 0..ARITH_EQ_MAX_CEQS {
                 // first position without carry
                 let carry_0 = if i == 0 { 0 } else { data.cout[i * 2 - 1][j] };
                 trace[irow].carry[j][0] = self.to_ranged_field(carry_0, self.carry_range_id);
@@ -227,10 +304,14 @@ impl<F: PrimeField64> ArithEqSM<F> {
             trace[irow].q0 = self.to_ranged_field(data.q0[i], q_range_id);
             trace[irow].q1 = self.to_ranged_field(data.q1[i], q_range_id);
             trace[irow].q2 = self.to_ranged_field(data.q2[i], q_range_id);
+            trace[irow].q3 = self.to_ranged_field(data.q3[i], q_range_id);
+            trace[irow].q4 = self.to_ranged_field(data.q4[i], q_range_id);
             trace[irow].s = self.to_ranged_field(data.s[i], self.chunk_range_id);
+            trace[irow].t = self.to_ranged_field(data.t[i], self.chunk_range_id);
+            trace[irow].u = self.to_ranged_field(data.u[i], self.chunk_range_id);
 
             // TODO Range check
-            for j in 0..4 {
+            for j in 0..ARITH_EQ_OPS {
                 let selected = j == sel_op;
                 trace[irow].sel_op[j] = F::from_bool(selected);
                 if i == 0 {
@@ -252,26 +333,41 @@ impl<F: PrimeField64> ArithEqSM<F> {
 
                     trace[irow].y3_lt = F::ZERO;
                 }
-                SEL_OP_SECP256K1_ADD | SEL_OP_SECP256K1_DBL => {
-                    let x3_lt = data.x3[i] < SECP256K1_PRIME_CHUNKS[i]
-                        || (data.x3[i] == SECP256K1_PRIME_CHUNKS[i] && prev_x3_lt);
+                SEL_OP_SECP256K1_ADD
+                | SEL_OP_SECP256K1_DBL
+                | SEL_OP_CURVE25519_MOD
+                | SEL_OP_ED25519_DBL
+                | SEL_OP_ED25519_ADD => {
+                    let prime_chunks =
+                        if sel_op == SEL_OP_SECP256K1_ADD || sel_op == SEL_OP_SECP256K1_DBL {
+                            &SECP256K1_PRIME_CHUNKS
+                        } else {
+                            &ED25519_PRIME_CHUNKS
+                        };
+                    let x3_lt = data.x3[i] < prime_chunks[i]
+                        || (data.x3[i] == prime_chunks[i] && prev_x3_lt);
                     trace[irow].x3_lt = F::from_bool(x3_lt);
                     self.arith_eq_lt_table_sm.update_input(
                         prev_x3_lt,
                         x3_lt,
-                        data.x3[i] - SECP256K1_PRIME_CHUNKS[i],
+                        data.x3[i] - prime_chunks[i],
                     );
                     prev_x3_lt = x3_lt;
 
-                    let y3_lt = data.y3[i] < SECP256K1_PRIME_CHUNKS[i]
-                        || (data.y3[i] == SECP256K1_PRIME_CHUNKS[i] && prev_y3_lt);
-                    trace[irow].y3_lt = F::from_bool(y3_lt);
-                    self.arith_eq_lt_table_sm.update_input(
-                        prev_y3_lt,
-                        y3_lt,
-                        data.y3[i] - SECP256K1_PRIME_CHUNKS[i],
-                    );
-                    prev_y3_lt = y3_lt;
+                    if sel_op == SEL_OP_CURVE25519_MOD {
+                        // only x3 is written, y3 is not used
+                        trace[irow].y3_lt = F::ZERO;
+                    } else {
+                        let y3_lt = data.y3[i] < prime_chunks[i]
+                            || (data.y3[i] == prime_chunks[i] && prev_y3_lt);
+                        trace[irow].y3_lt = F::from_bool(y3_lt);
+                        self.arith_eq_lt_table_sm.update_input(
+                            prev_y3_lt,
+                            y3_lt,
+                            data.y3[i] - prime_chunks[i],
+                        );
+                        prev_y3_lt = y3_lt;
+                    }
                 }
                 _ => {
                     trace[irow].x3_lt = F::ZERO;
@@ -345,14 +441,23 @@ impl<F: PrimeField64> ArithEqSM<F> {
                     ArithEqInput::Secp256k1Dbl(idata) => {
                         self.process_secp256k1_dbl(idata, &mut trace, row_offset)
                     }
+                    ArithEqInput::Curve25519Mod(idata) => {
+                        self.process_curve25519_mod(idata, &mut trace, row_offset)
+                    }
+                    ArithEqInput::Ed25519Dbl(idata) => {
+                        self.process_ed25519_dbl(idata, &mut trace, row_offset)
+                    }
+                    ArithEqInput::Ed25519Add(idata) => {
+                        self.process_ed25519_add(idata, &mut trace, row_offset)
+                    }
                 }
                 index += 1;
             }
         }
         let padding_ops = (self.num_available_ops - index) as u64;
-        self.std.range_check(0, 3 * padding_ops, self.q_hsc_range_id);
-        self.std.range_check(0, 157 * padding_ops, self.chunk_range_id);
-        self.std.range_check(0, 96 * padding_ops, self.carry_range_id);
+        self.std.range_check(0, 5 * padding_ops, self.q_hsc_range_id);
+        self.std.range_check(0, 219 * padding_ops, self.chunk_range_id);
+        self.std.range_check(0, 160 * padding_ops, self.carry_range_id);
 
         timer_stop_and_log_trace!(ARITH_EQ_TRACE);
 
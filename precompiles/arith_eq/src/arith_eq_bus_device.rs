@@ -5,17 +5,25 @@
 use std::ops::Add;
 
 use zisk_common::{
-    BusDevice, BusDeviceMode, BusId, Counter, Metrics, A, B, OPERATION_BUS_ARITH_256_DATA_SIZE,
-    OPERATION_BUS_ARITH_256_MOD_DATA_SIZE, OPERATION_BUS_ID, OPERATION_BUS_SECP256K1_ADD_DATA_SIZE,
-    OPERATION_BUS_SECP256K1_DBL_DATA_SIZE, OP_TYPE,
+    BusDevice, BusDeviceMode, BusId, Counter, Metrics, A, B, OP, OPERATION_BUS_ID, OP_TYPE,
 };
-use zisk_core::ZiskOperationType;
+use zisk_core::{zisk_ops::ZiskOp, ZiskOperationType};
 
 use crate::mem_inputs::{
     generate_arith256_mem_inputs, generate_arith256_mod_mem_inputs,
-    generate_secp256k1_add_mem_inputs, generate_secp256k1_dbl_mem_inputs,
+    generate_curve25519_mod_mem_inputs, generate_ed25519_add_mem_inputs,
+    generate_ed25519_dbl_mem_inputs, generate_secp256k1_add_mem_inputs,
+    generate_secp256k1_dbl_mem_inputs,
 };
 
+const ARITH256_OP: u8 = ZiskOp::Arith256.code();
+const ARITH256_MOD_OP: u8 = ZiskOp::Arith256Mod.code();
+const SECP256K1_ADD_OP: u8 = ZiskOp::Secp256k1Add.code();
+const SECP256K1_DBL_OP: u8 = ZiskOp::Secp256k1Dbl.code();
+const CURVE25519_MOD_OP: u8 = ZiskOp::Curve25519Mod.code();
+const ED25519_DBL_OP: u8 = ZiskOp::Ed25519Dbl.code();
+const ED25519_ADD_OP: u8 = ZiskOp::Ed25519Add.code();
+
 /// The `ArithEqCounter` struct represents a counter that monitors and measures
 /// arith_eq-related operations on the data bus.
 ///
@@ -120,19 +128,28 @@ impl BusDevice<u64> for ArithEqCounterInputGen {
             self.measure(data);
         }
 
-        match data.len() {
-            OPERATION_BUS_ARITH_256_DATA_SIZE => {
-                generate_arith256_mem_inputs(addr_main, step_main, data, only_counters)
-            }
-            OPERATION_BUS_ARITH_256_MOD_DATA_SIZE => {
+        // Payload sizes are not unique (secp256k1_dbl and ed25519_dbl share the same layout),
+        // so the operation code is used to select the memory inputs generator.
+        match data[OP] as u8 {
+            ARITH256_OP => generate_arith256_mem_inputs(addr_main, step_main, data, only_counters),
+            ARITH256_MOD_OP => {
                 generate_arith256_mod_mem_inputs(addr_main, step_main, data, only_counters)
             }
-            OPERATION_BUS_SECP256K1_ADD_DATA_SIZE => {
+            SECP256K1_ADD_OP => {
                 generate_secp256k1_add_mem_inputs(addr_main, step_main, data, only_counters)
             }
-            OPERATION_BUS_SECP256K1_DBL_DATA_SIZE => {
+            SECP256K1_DBL_OP => {
                 generate_secp256k1_dbl_mem_inputs(addr_main, step_main, data, only_counters)
             }
+            CURVE25519_MOD_OP => {
+                generate_curve25519_mod_mem_inputs(addr_main, step_main, data, only_counters)
+            }
+            ED25519_DBL_OP => {
+                generate_ed25519_dbl_mem_inputs(addr_main, step_main, data, only_counters)
+            }
+            ED25519_ADD_OP => {
+                generate_ed25519_add_mem_inputs(addr_main, step_main, data, only_counters)
+            }
 
             _ => None,
         }
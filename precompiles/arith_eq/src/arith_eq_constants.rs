@@ -7,13 +7,51 @@ pub const ARITH_EQ_CHUNKS: usize = 16;
 pub const ARITH_EQ_CHUNK_BITS: usize = 16;
 pub const ARITH_EQ_CHUNK_SIZE: usize = 1 << ARITH_EQ_CHUNK_BITS;
 pub const ARITH_EQ_CHUNK_BASE_MAX: usize = ARITH_EQ_CHUNK_SIZE - 1;
+pub const ARITH_EQ_MAX_CEQS: usize = 5;
 
 pub const SEL_OP_ARITH256: usize = 0;
 pub const SEL_OP_ARITH256_MOD: usize = 1;
 pub const SEL_OP_SECP256K1_ADD: usize = 2;
 pub const SEL_OP_SECP256K1_DBL: usize = 3;
+pub const SEL_OP_CURVE25519_MOD: usize = 4;
+pub const SEL_OP_ED25519_DBL: usize = 5;
+pub const SEL_OP_ED25519_ADD: usize = 6;
+pub const ARITH_EQ_OPS: usize = 7;
 
 pub const SECP256K1_PRIME_CHUNKS: [i64; 16] = [
     0xFC2F, 0xFFFF, 0xFFFE, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF,
     0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF,
 ];
+
+pub const ED25519_PRIME_CHUNKS: [i64; 16] = [
+    0xFFED, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF,
+    0xFFFF, 0xFFFF, 0xFFFF, 0x7FFF,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_goldilocks::Goldilocks;
+    use zisk_pil::ArithEqTraceRow;
+
+    const ARITH_EQ_PIL: &str = include_str!("../pil/arith_eq.pil");
+
+    fn pil_const(name: &str) -> usize {
+        let prefix = format!("const int {name} = ");
+        let line = ARITH_EQ_PIL.lines().map(str::trim).find(|l| l.starts_with(&prefix)).unwrap();
+        line[prefix.len()..].split(';').next().unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn test_layout_matches_pil() {
+        assert_eq!(pil_const("OPS"), ARITH_EQ_OPS);
+        assert_eq!(pil_const("MAX_CEQS"), ARITH_EQ_MAX_CEQS);
+        assert_eq!(pil_const("CLOCKS"), ARITH_EQ_ROWS_BY_OP);
+
+        // The trace columns must have been regenerated from the same pil
+        let row = ArithEqTraceRow::<Goldilocks>::default();
+        assert_eq!(row.sel_op.len(), ARITH_EQ_OPS);
+        assert_eq!(row.sel_op_clk0.len(), ARITH_EQ_OPS);
+        assert_eq!(row.carry.len(), ARITH_EQ_MAX_CEQS);
+    }
+}
@@ -116,4 +116,165 @@ fn main() {
 
     let pil_file = pil_code_path.join("secp256k1_y3.pil");
     eq.generate_pil_code_to_file("eq_secp256k1_y3", pil_file.to_str().unwrap());
+
+    // CURVE25519
+
+    // x3 = (x1*y1 + x2) mod p
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "x1*y1+x2-x3-p*q0",
+        &[("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed")],
+    );
+
+    let rust_file = rust_code_path.join("curve25519_mod.rs");
+    eq.generate_rust_code_to_file("Curve25519Mod", "x1,y1,x2,x3,q0", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("curve25519_mod.pil");
+    eq.generate_pil_code_to_file("eq_curve25519_mod", pil_file.to_str().unwrap());
+
+    // ED25519 (twisted Edwards, a = -1)
+
+    // s = y1^2 - x1^2 - doubling denominator
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "y1*y1-x1*x1-s+p*q0-p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("offset", "0x40000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_dbl.rs");
+    eq.generate_rust_code_to_file("Ed25519Dbl", "x1,y1,s,q0", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_dbl.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_dbl", pil_file.to_str().unwrap());
+
+    // x3 = 2*x1*y1 / s
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "x3*s-2*x1*y1-p*q1+p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("offset", "0x80000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_dbl_x3.rs");
+    eq.generate_rust_code_to_file("Ed25519DblX3", "x1,y1,x3,s,q1", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_dbl_x3.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_dbl_x3", pil_file.to_str().unwrap());
+
+    // y3 = (y1^2 + x1^2) / (2 - s)
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "2*y3-y3*s-y1*y1-x1*x1+p*q2-p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("offset", "0x20000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_dbl_y3.rs");
+    eq.generate_rust_code_to_file("Ed25519DblY3", "x1,y1,y3,s,q2", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_dbl_y3.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_dbl_y3", pil_file.to_str().unwrap());
+
+    // ed25519 addition, with the unified formulas x3 = (x1·y2 + y1·x2) / (1 + d·x1·y1·x2·y2) and
+    // y3 = (y1·y2 + x1·x2) / (1 - d·x1·y1·x2·y2), whose denominators are never zero for points
+    // of the curve, because d is not a square
+
+    // s = x1*y1
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "x1*y1-s-p*q0",
+        &[("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed")],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_add.rs");
+    eq.generate_rust_code_to_file("Ed25519Add", "x1,y1,s,q0", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_add.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_add", pil_file.to_str().unwrap());
+
+    // t = x2*y2
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "x2*y2-t-p*q1",
+        &[("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed")],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_add_t.rs");
+    eq.generate_rust_code_to_file("Ed25519AddT", "x2,y2,t,q1", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_add_t.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_add_t", pil_file.to_str().unwrap());
+
+    // u = d*s*t, as u/d = s*t
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "dinv*u-s*t+p*q2-p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("dinv", "0x40907ed214d5ce432b162114cdb9cf660b5dd6984279542e25e0f276cdc9f843"),
+            ("offset", "0x10000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_add_u.rs");
+    eq.generate_rust_code_to_file("Ed25519AddU", "s,t,u,q2", rust_file.to_str().unwrap());
+
+    let pil_file = pil_code_path.join("ed25519_add_u.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_add_u", pil_file.to_str().unwrap());
+
+    // x3 = (x1*y2 + y1*x2) / (1 + u)
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "x3+x3*u-x1*y2-y1*x2+p*q3-p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("offset", "0x80000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_add_x3.rs");
+    eq.generate_rust_code_to_file(
+        "Ed25519AddX3",
+        "x1,y1,x2,y2,x3,u,q3",
+        rust_file.to_str().unwrap(),
+    );
+
+    let pil_file = pil_code_path.join("ed25519_add_x3.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_add_x3", pil_file.to_str().unwrap());
+
+    // y3 = (y1*y2 + x1*x2) / (1 - u)
+
+    let mut eq = Equation::new(&config);
+    eq.parse(
+        "y3-y3*u-y1*y2-x1*x2+p*q4-p*offset",
+        &[
+            ("p", "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+            ("offset", "0x80000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    let rust_file = rust_code_path.join("ed25519_add_y3.rs");
+    eq.generate_rust_code_to_file(
+        "Ed25519AddY3",
+        "x1,y1,x2,y2,y3,u,q4",
+        rust_file.to_str().unwrap(),
+    );
+
+    let pil_file = pil_code_path.join("ed25519_add_y3.pil");
+    eq.generate_pil_code_to_file("eq_ed25519_add_y3", pil_file.to_str().unwrap());
 }
@@ -1,5 +1,6 @@
 use zisk_common::{
-    OperationArith256Data, OperationArith256ModData, OperationSecp256k1AddData,
+    OperationArith256Data, OperationArith256ModData, OperationCurve25519ModData,
+    OperationEd25519AddData, OperationEd25519DblData, OperationSecp256k1AddData,
     OperationSecp256k1DblData,
 };
 
@@ -9,6 +10,9 @@ pub enum ArithEqInput {
     Arith256Mod(Arith256ModInput),
     Secp256k1Add(Secp256k1AddInput),
     Secp256k1Dbl(Secp256k1DblInput),
+    Curve25519Mod(Curve25519ModInput),
+    Ed25519Dbl(Ed25519DblInput),
+    Ed25519Add(Ed25519AddInput),
 }
 
 #[derive(Debug)]
@@ -110,3 +114,68 @@ impl Secp256k1DblInput {
         Self { addr: values[3] as u32, step: values[2], p1: values[4..12].try_into().unwrap() }
     }
 }
+
+#[derive(Debug)]
+pub struct Curve25519ModInput {
+    pub addr: u32,
+    pub a_addr: u32,
+    pub b_addr: u32,
+    pub c_addr: u32,
+    pub d_addr: u32,
+    pub step: u64,
+    pub a: [u64; 4],
+    pub b: [u64; 4],
+    pub c: [u64; 4],
+}
+
+impl Curve25519ModInput {
+    pub fn from(values: &OperationCurve25519ModData<u64>) -> Self {
+        Self {
+            addr: values[3] as u32,
+            a_addr: values[4] as u32,
+            b_addr: values[5] as u32,
+            c_addr: values[6] as u32,
+            d_addr: values[7] as u32,
+            step: values[2],
+            a: values[8..12].try_into().unwrap(),
+            b: values[12..16].try_into().unwrap(),
+            c: values[16..20].try_into().unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Ed25519DblInput {
+    pub addr: u32,
+    pub step: u64,
+    pub p1: [u64; 8],
+}
+
+impl Ed25519DblInput {
+    pub fn from(values: &OperationEd25519DblData<u64>) -> Self {
+        Self { addr: values[3] as u32, step: values[2], p1: values[4..12].try_into().unwrap() }
+    }
+}
+
+#[derive(Debug)]
+pub struct Ed25519AddInput {
+    pub addr: u32,
+    pub p1_addr: u32,
+    pub p2_addr: u32,
+    pub step: u64,
+    pub p1: [u64; 8],
+    pub p2: [u64; 8],
+}
+
+impl Ed25519AddInput {
+    pub fn from(values: &OperationEd25519AddData<u64>) -> Self {
+        Self {
+            addr: values[3] as u32,
+            p1_addr: values[4] as u32,
+            p2_addr: values[5] as u32,
+            step: values[2],
+            p1: values[6..14].try_into().unwrap(),
+            p2: values[14..22].try_into().unwrap(),
+        }
+    }
+}
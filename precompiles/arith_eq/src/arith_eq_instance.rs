@@ -5,7 +5,8 @@
 //! execution plans.
 
 use crate::{
-    Arith256Input, Arith256ModInput, ArithEqInput, ArithEqSM, Secp256k1AddInput, Secp256k1DblInput,
+    Arith256Input, Arith256ModInput, ArithEqInput, ArithEqSM, Curve25519ModInput, Ed25519AddInput,
+    Ed25519DblInput, Secp256k1AddInput, Secp256k1DblInput,
 };
 use p3_field::PrimeField64;
 use proofman_common::{AirInstance, ProofCtx, SetupCtx};
@@ -175,6 +176,15 @@ impl BusDevice<PayloadType> for ArithEqCollector {
             ExtOperationData::OperationSecp256k1DblData(bus_data) => {
                 ArithEqInput::Secp256k1Dbl(Secp256k1DblInput::from(&bus_data))
             }
+            ExtOperationData::OperationCurve25519ModData(bus_data) => {
+                ArithEqInput::Curve25519Mod(Curve25519ModInput::from(&bus_data))
+            }
+            ExtOperationData::OperationEd25519DblData(bus_data) => {
+                ArithEqInput::Ed25519Dbl(Ed25519DblInput::from(&bus_data))
+            }
+            ExtOperationData::OperationEd25519AddData(bus_data) => {
+                ArithEqInput::Ed25519Add(Ed25519AddInput::from(&bus_data))
+            }
             // Add here new operations
             _ => panic!("Expected ExtOperationData::OperationData"),
         });
@@ -37,6 +37,24 @@ impl<F: PrimeField64> ArithEqManager<F> {
     pub fn build_arith_eq_counter(&self) -> ArithEqCounterInputGen {
         ArithEqCounterInputGen::new(BusDeviceMode::Counter)
     }
+
+    /// Builds the planner of the arith_eq instances, which does not depend on the state machine,
+    /// so executions can be planned without it.
+    pub fn build_arith_eq_planner() -> Box<dyn Planner> {
+        Box::new(
+            ArithEqPlanner::new()
+                .add_instance(InstanceInfo::new(
+                    ArithEqTrace::<usize>::AIRGROUP_ID,
+                    ArithEqTrace::<usize>::AIR_ID,
+                    ArithEqSM::<F>::NUM_AVAILABLE_OPS,
+                    ZiskOperationType::ArithEq,
+                ))
+                .add_table_instance(TableInfo::new(
+                    ArithEqLtTableTrace::<usize>::AIRGROUP_ID,
+                    ArithEqLtTableTrace::<usize>::AIR_ID,
+                )),
+        )
+    }
 }
 
 impl<F: PrimeField64> ComponentBuilder<F> for ArithEqManager<F> {
@@ -53,22 +71,7 @@ impl<F: PrimeField64> ComponentBuilder<F> for ArithEqManager<F> {
     /// # Returns
     /// A boxed implementation of `RegularPlanner`.
     fn build_planner(&self) -> Box<dyn Planner> {
-        // Get the number of arith256s that a single arith256 instance can handle
-        let num_available_ops = self.arith_eq_sm.num_available_ops;
-
-        Box::new(
-            ArithEqPlanner::new()
-                .add_instance(InstanceInfo::new(
-                    ArithEqTrace::<usize>::AIRGROUP_ID,
-                    ArithEqTrace::<usize>::AIR_ID,
-                    num_available_ops,
-                    ZiskOperationType::ArithEq,
-                ))
-                .add_table_instance(TableInfo::new(
-                    ArithEqLtTableTrace::<usize>::AIRGROUP_ID,
-                    ArithEqLtTableTrace::<usize>::AIR_ID,
-                )),
-        )
+        Self::build_arith_eq_planner()
     }
 
     /// Builds an inputs data collector for arith_eq operations.
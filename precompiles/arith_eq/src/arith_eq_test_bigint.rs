@@ -4,6 +4,7 @@ use test_data::{
     get_secp256k1_dbl_test_data,
 };
 
+mod arith_eq_constants;
 mod equations;
 mod executors;
 use executors::arith256::Arith256;
@@ -1,6 +1,7 @@
 mod test_data;
 use test_data::{get_secp256k1_add_test_data, get_secp256k1_dbl_test_data};
 
+mod arith_eq_constants;
 mod equations;
 mod executors;
 use lib_c::add_point_ec_p_c;
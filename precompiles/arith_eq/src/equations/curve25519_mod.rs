@@ -0,0 +1,601 @@
+// code generated
+//
+// equation: x1*y1+x2-x3-p*q0
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Curve25519Mod {}
+
+impl Curve25519Mod {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        x2: &[i64; 16],
+        x3: &[i64; 16],
+        q0: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => x1[0] * y1[0] + x2[0] - x3[0] - 0xFFED * q0[0],
+            1 => x1[1] * y1[0] + x1[0] * y1[1] + x2[1] - x3[1] - 0xFFFF * q0[0] - 0xFFED * q0[1],
+            2 => {
+                x1[2] * y1[0] + x1[1] * y1[1] + x1[0] * y1[2] + x2[2]
+                    - x3[2]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFED * q0[2]
+            }
+            3 => {
+                x1[3] * y1[0] + x1[2] * y1[1] + x1[1] * y1[2] + x1[0] * y1[3] + x2[3]
+                    - x3[3]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFED * q0[3]
+            }
+            4 => {
+                x1[4] * y1[0]
+                    + x1[3] * y1[1]
+                    + x1[2] * y1[2]
+                    + x1[1] * y1[3]
+                    + x1[0] * y1[4]
+                    + x2[4]
+                    - x3[4]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFED * q0[4]
+            }
+            5 => {
+                x1[5] * y1[0]
+                    + x1[4] * y1[1]
+                    + x1[3] * y1[2]
+                    + x1[2] * y1[3]
+                    + x1[1] * y1[4]
+                    + x1[0] * y1[5]
+                    + x2[5]
+                    - x3[5]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFED * q0[5]
+            }
+            6 => {
+                x1[6] * y1[0]
+                    + x1[5] * y1[1]
+                    + x1[4] * y1[2]
+                    + x1[3] * y1[3]
+                    + x1[2] * y1[4]
+                    + x1[1] * y1[5]
+                    + x1[0] * y1[6]
+                    + x2[6]
+                    - x3[6]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFED * q0[6]
+            }
+            7 => {
+                x1[7] * y1[0]
+                    + x1[6] * y1[1]
+                    + x1[5] * y1[2]
+                    + x1[4] * y1[3]
+                    + x1[3] * y1[4]
+                    + x1[2] * y1[5]
+                    + x1[1] * y1[6]
+                    + x1[0] * y1[7]
+                    + x2[7]
+                    - x3[7]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFED * q0[7]
+            }
+            8 => {
+                x1[8] * y1[0]
+                    + x1[7] * y1[1]
+                    + x1[6] * y1[2]
+                    + x1[5] * y1[3]
+                    + x1[4] * y1[4]
+                    + x1[3] * y1[5]
+                    + x1[2] * y1[6]
+                    + x1[1] * y1[7]
+                    + x1[0] * y1[8]
+                    + x2[8]
+                    - x3[8]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFED * q0[8]
+            }
+            9 => {
+                x1[9] * y1[0]
+                    + x1[8] * y1[1]
+                    + x1[7] * y1[2]
+                    + x1[6] * y1[3]
+                    + x1[5] * y1[4]
+                    + x1[4] * y1[5]
+                    + x1[3] * y1[6]
+                    + x1[2] * y1[7]
+                    + x1[1] * y1[8]
+                    + x1[0] * y1[9]
+                    + x2[9]
+                    - x3[9]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFED * q0[9]
+            }
+            10 => {
+                x1[10] * y1[0]
+                    + x1[9] * y1[1]
+                    + x1[8] * y1[2]
+                    + x1[7] * y1[3]
+                    + x1[6] * y1[4]
+                    + x1[5] * y1[5]
+                    + x1[4] * y1[6]
+                    + x1[3] * y1[7]
+                    + x1[2] * y1[8]
+                    + x1[1] * y1[9]
+                    + x1[0] * y1[10]
+                    + x2[10]
+                    - x3[10]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFED * q0[10]
+            }
+            11 => {
+                x1[11] * y1[0]
+                    + x1[10] * y1[1]
+                    + x1[9] * y1[2]
+                    + x1[8] * y1[3]
+                    + x1[7] * y1[4]
+                    + x1[6] * y1[5]
+                    + x1[5] * y1[6]
+                    + x1[4] * y1[7]
+                    + x1[3] * y1[8]
+                    + x1[2] * y1[9]
+                    + x1[1] * y1[10]
+                    + x1[0] * y1[11]
+                    + x2[11]
+                    - x3[11]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFED * q0[11]
+            }
+            12 => {
+                x1[12] * y1[0]
+                    + x1[11] * y1[1]
+                    + x1[10] * y1[2]
+                    + x1[9] * y1[3]
+                    + x1[8] * y1[4]
+                    + x1[7] * y1[5]
+                    + x1[6] * y1[6]
+                    + x1[5] * y1[7]
+                    + x1[4] * y1[8]
+                    + x1[3] * y1[9]
+                    + x1[2] * y1[10]
+                    + x1[1] * y1[11]
+                    + x1[0] * y1[12]
+                    + x2[12]
+                    - x3[12]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFED * q0[12]
+            }
+            13 => {
+                x1[13] * y1[0]
+                    + x1[12] * y1[1]
+                    + x1[11] * y1[2]
+                    + x1[10] * y1[3]
+                    + x1[9] * y1[4]
+                    + x1[8] * y1[5]
+                    + x1[7] * y1[6]
+                    + x1[6] * y1[7]
+                    + x1[5] * y1[8]
+                    + x1[4] * y1[9]
+                    + x1[3] * y1[10]
+                    + x1[2] * y1[11]
+                    + x1[1] * y1[12]
+                    + x1[0] * y1[13]
+                    + x2[13]
+                    - x3[13]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFED * q0[13]
+            }
+            14 => {
+                x1[14] * y1[0]
+                    + x1[13] * y1[1]
+                    + x1[12] * y1[2]
+                    + x1[11] * y1[3]
+                    + x1[10] * y1[4]
+                    + x1[9] * y1[5]
+                    + x1[8] * y1[6]
+                    + x1[7] * y1[7]
+                    + x1[6] * y1[8]
+                    + x1[5] * y1[9]
+                    + x1[4] * y1[10]
+                    + x1[3] * y1[11]
+                    + x1[2] * y1[12]
+                    + x1[1] * y1[13]
+                    + x1[0] * y1[14]
+                    + x2[14]
+                    - x3[14]
+                    - 0xFFFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFED * q0[14]
+            }
+            15 => {
+                x1[15] * y1[0]
+                    + x1[14] * y1[1]
+                    + x1[13] * y1[2]
+                    + x1[12] * y1[3]
+                    + x1[11] * y1[4]
+                    + x1[10] * y1[5]
+                    + x1[9] * y1[6]
+                    + x1[8] * y1[7]
+                    + x1[7] * y1[8]
+                    + x1[6] * y1[9]
+                    + x1[5] * y1[10]
+                    + x1[4] * y1[11]
+                    + x1[3] * y1[12]
+                    + x1[2] * y1[13]
+                    + x1[1] * y1[14]
+                    + x1[0] * y1[15]
+                    + x2[15]
+                    - x3[15]
+                    - 0x7FFF * q0[0]
+                    - 0xFFFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFED * q0[15]
+            }
+            16 => {
+                x1[15] * y1[1]
+                    + x1[14] * y1[2]
+                    + x1[13] * y1[3]
+                    + x1[12] * y1[4]
+                    + x1[11] * y1[5]
+                    + x1[10] * y1[6]
+                    + x1[9] * y1[7]
+                    + x1[8] * y1[8]
+                    + x1[7] * y1[9]
+                    + x1[6] * y1[10]
+                    + x1[5] * y1[11]
+                    + x1[4] * y1[12]
+                    + x1[3] * y1[13]
+                    + x1[2] * y1[14]
+                    + x1[1] * y1[15]
+                    - 0x7FFF * q0[1]
+                    - 0xFFFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            17 => {
+                x1[15] * y1[2]
+                    + x1[14] * y1[3]
+                    + x1[13] * y1[4]
+                    + x1[12] * y1[5]
+                    + x1[11] * y1[6]
+                    + x1[10] * y1[7]
+                    + x1[9] * y1[8]
+                    + x1[8] * y1[9]
+                    + x1[7] * y1[10]
+                    + x1[6] * y1[11]
+                    + x1[5] * y1[12]
+                    + x1[4] * y1[13]
+                    + x1[3] * y1[14]
+                    + x1[2] * y1[15]
+                    - 0x7FFF * q0[2]
+                    - 0xFFFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            18 => {
+                x1[15] * y1[3]
+                    + x1[14] * y1[4]
+                    + x1[13] * y1[5]
+                    + x1[12] * y1[6]
+                    + x1[11] * y1[7]
+                    + x1[10] * y1[8]
+                    + x1[9] * y1[9]
+                    + x1[8] * y1[10]
+                    + x1[7] * y1[11]
+                    + x1[6] * y1[12]
+                    + x1[5] * y1[13]
+                    + x1[4] * y1[14]
+                    + x1[3] * y1[15]
+                    - 0x7FFF * q0[3]
+                    - 0xFFFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            19 => {
+                x1[15] * y1[4]
+                    + x1[14] * y1[5]
+                    + x1[13] * y1[6]
+                    + x1[12] * y1[7]
+                    + x1[11] * y1[8]
+                    + x1[10] * y1[9]
+                    + x1[9] * y1[10]
+                    + x1[8] * y1[11]
+                    + x1[7] * y1[12]
+                    + x1[6] * y1[13]
+                    + x1[5] * y1[14]
+                    + x1[4] * y1[15]
+                    - 0x7FFF * q0[4]
+                    - 0xFFFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            20 => {
+                x1[15] * y1[5]
+                    + x1[14] * y1[6]
+                    + x1[13] * y1[7]
+                    + x1[12] * y1[8]
+                    + x1[11] * y1[9]
+                    + x1[10] * y1[10]
+                    + x1[9] * y1[11]
+                    + x1[8] * y1[12]
+                    + x1[7] * y1[13]
+                    + x1[6] * y1[14]
+                    + x1[5] * y1[15]
+                    - 0x7FFF * q0[5]
+                    - 0xFFFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            21 => {
+                x1[15] * y1[6]
+                    + x1[14] * y1[7]
+                    + x1[13] * y1[8]
+                    + x1[12] * y1[9]
+                    + x1[11] * y1[10]
+                    + x1[10] * y1[11]
+                    + x1[9] * y1[12]
+                    + x1[8] * y1[13]
+                    + x1[7] * y1[14]
+                    + x1[6] * y1[15]
+                    - 0x7FFF * q0[6]
+                    - 0xFFFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            22 => {
+                x1[15] * y1[7]
+                    + x1[14] * y1[8]
+                    + x1[13] * y1[9]
+                    + x1[12] * y1[10]
+                    + x1[11] * y1[11]
+                    + x1[10] * y1[12]
+                    + x1[9] * y1[13]
+                    + x1[8] * y1[14]
+                    + x1[7] * y1[15]
+                    - 0x7FFF * q0[7]
+                    - 0xFFFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            23 => {
+                x1[15] * y1[8]
+                    + x1[14] * y1[9]
+                    + x1[13] * y1[10]
+                    + x1[12] * y1[11]
+                    + x1[11] * y1[12]
+                    + x1[10] * y1[13]
+                    + x1[9] * y1[14]
+                    + x1[8] * y1[15]
+                    - 0x7FFF * q0[8]
+                    - 0xFFFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            24 => {
+                x1[15] * y1[9]
+                    + x1[14] * y1[10]
+                    + x1[13] * y1[11]
+                    + x1[12] * y1[12]
+                    + x1[11] * y1[13]
+                    + x1[10] * y1[14]
+                    + x1[9] * y1[15]
+                    - 0x7FFF * q0[9]
+                    - 0xFFFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            25 => {
+                x1[15] * y1[10]
+                    + x1[14] * y1[11]
+                    + x1[13] * y1[12]
+                    + x1[12] * y1[13]
+                    + x1[11] * y1[14]
+                    + x1[10] * y1[15]
+                    - 0x7FFF * q0[10]
+                    - 0xFFFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            26 => {
+                x1[15] * y1[11]
+                    + x1[14] * y1[12]
+                    + x1[13] * y1[13]
+                    + x1[12] * y1[14]
+                    + x1[11] * y1[15]
+                    - 0x7FFF * q0[11]
+                    - 0xFFFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            27 => {
+                x1[15] * y1[12] + x1[14] * y1[13] + x1[13] * y1[14] + x1[12] * y1[15]
+                    - 0x7FFF * q0[12]
+                    - 0xFFFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            28 => {
+                x1[15] * y1[13] + x1[14] * y1[14] + x1[13] * y1[15]
+                    - 0x7FFF * q0[13]
+                    - 0xFFFF * q0[14]
+                    - 0xFFFF * q0[15]
+            }
+            29 => x1[15] * y1[14] + x1[14] * y1[15] - 0x7FFF * q0[14] - 0xFFFF * q0[15],
+            30 => x1[15] * y1[15] - 0x7FFF * q0[15],
+            _ => 0,
+        }
+    }
+}
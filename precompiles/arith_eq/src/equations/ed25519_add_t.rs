@@ -0,0 +1,584 @@
+// code generated
+//
+// equation: x2*y2-t-p*q1
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519AddT {}
+
+impl Ed25519AddT {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x2: &[i64; 16],
+        y2: &[i64; 16],
+        t: &[i64; 16],
+        q1: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => x2[0] * y2[0] - t[0] - 0xFFED * q1[0],
+            1 => x2[1] * y2[0] + x2[0] * y2[1] - t[1] - 0xFFFF * q1[0] - 0xFFED * q1[1],
+            2 => {
+                x2[2] * y2[0] + x2[1] * y2[1] + x2[0] * y2[2]
+                    - t[2]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFED * q1[2]
+            }
+            3 => {
+                x2[3] * y2[0] + x2[2] * y2[1] + x2[1] * y2[2] + x2[0] * y2[3]
+                    - t[3]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFED * q1[3]
+            }
+            4 => {
+                x2[4] * y2[0] + x2[3] * y2[1] + x2[2] * y2[2] + x2[1] * y2[3] + x2[0] * y2[4]
+                    - t[4]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFED * q1[4]
+            }
+            5 => {
+                x2[5] * y2[0]
+                    + x2[4] * y2[1]
+                    + x2[3] * y2[2]
+                    + x2[2] * y2[3]
+                    + x2[1] * y2[4]
+                    + x2[0] * y2[5]
+                    - t[5]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFED * q1[5]
+            }
+            6 => {
+                x2[6] * y2[0]
+                    + x2[5] * y2[1]
+                    + x2[4] * y2[2]
+                    + x2[3] * y2[3]
+                    + x2[2] * y2[4]
+                    + x2[1] * y2[5]
+                    + x2[0] * y2[6]
+                    - t[6]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFED * q1[6]
+            }
+            7 => {
+                x2[7] * y2[0]
+                    + x2[6] * y2[1]
+                    + x2[5] * y2[2]
+                    + x2[4] * y2[3]
+                    + x2[3] * y2[4]
+                    + x2[2] * y2[5]
+                    + x2[1] * y2[6]
+                    + x2[0] * y2[7]
+                    - t[7]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFED * q1[7]
+            }
+            8 => {
+                x2[8] * y2[0]
+                    + x2[7] * y2[1]
+                    + x2[6] * y2[2]
+                    + x2[5] * y2[3]
+                    + x2[4] * y2[4]
+                    + x2[3] * y2[5]
+                    + x2[2] * y2[6]
+                    + x2[1] * y2[7]
+                    + x2[0] * y2[8]
+                    - t[8]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFED * q1[8]
+            }
+            9 => {
+                x2[9] * y2[0]
+                    + x2[8] * y2[1]
+                    + x2[7] * y2[2]
+                    + x2[6] * y2[3]
+                    + x2[5] * y2[4]
+                    + x2[4] * y2[5]
+                    + x2[3] * y2[6]
+                    + x2[2] * y2[7]
+                    + x2[1] * y2[8]
+                    + x2[0] * y2[9]
+                    - t[9]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFED * q1[9]
+            }
+            10 => {
+                x2[10] * y2[0]
+                    + x2[9] * y2[1]
+                    + x2[8] * y2[2]
+                    + x2[7] * y2[3]
+                    + x2[6] * y2[4]
+                    + x2[5] * y2[5]
+                    + x2[4] * y2[6]
+                    + x2[3] * y2[7]
+                    + x2[2] * y2[8]
+                    + x2[1] * y2[9]
+                    + x2[0] * y2[10]
+                    - t[10]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFED * q1[10]
+            }
+            11 => {
+                x2[11] * y2[0]
+                    + x2[10] * y2[1]
+                    + x2[9] * y2[2]
+                    + x2[8] * y2[3]
+                    + x2[7] * y2[4]
+                    + x2[6] * y2[5]
+                    + x2[5] * y2[6]
+                    + x2[4] * y2[7]
+                    + x2[3] * y2[8]
+                    + x2[2] * y2[9]
+                    + x2[1] * y2[10]
+                    + x2[0] * y2[11]
+                    - t[11]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFED * q1[11]
+            }
+            12 => {
+                x2[12] * y2[0]
+                    + x2[11] * y2[1]
+                    + x2[10] * y2[2]
+                    + x2[9] * y2[3]
+                    + x2[8] * y2[4]
+                    + x2[7] * y2[5]
+                    + x2[6] * y2[6]
+                    + x2[5] * y2[7]
+                    + x2[4] * y2[8]
+                    + x2[3] * y2[9]
+                    + x2[2] * y2[10]
+                    + x2[1] * y2[11]
+                    + x2[0] * y2[12]
+                    - t[12]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFED * q1[12]
+            }
+            13 => {
+                x2[13] * y2[0]
+                    + x2[12] * y2[1]
+                    + x2[11] * y2[2]
+                    + x2[10] * y2[3]
+                    + x2[9] * y2[4]
+                    + x2[8] * y2[5]
+                    + x2[7] * y2[6]
+                    + x2[6] * y2[7]
+                    + x2[5] * y2[8]
+                    + x2[4] * y2[9]
+                    + x2[3] * y2[10]
+                    + x2[2] * y2[11]
+                    + x2[1] * y2[12]
+                    + x2[0] * y2[13]
+                    - t[13]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFED * q1[13]
+            }
+            14 => {
+                x2[14] * y2[0]
+                    + x2[13] * y2[1]
+                    + x2[12] * y2[2]
+                    + x2[11] * y2[3]
+                    + x2[10] * y2[4]
+                    + x2[9] * y2[5]
+                    + x2[8] * y2[6]
+                    + x2[7] * y2[7]
+                    + x2[6] * y2[8]
+                    + x2[5] * y2[9]
+                    + x2[4] * y2[10]
+                    + x2[3] * y2[11]
+                    + x2[2] * y2[12]
+                    + x2[1] * y2[13]
+                    + x2[0] * y2[14]
+                    - t[14]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFED * q1[14]
+            }
+            15 => {
+                x2[15] * y2[0]
+                    + x2[14] * y2[1]
+                    + x2[13] * y2[2]
+                    + x2[12] * y2[3]
+                    + x2[11] * y2[4]
+                    + x2[10] * y2[5]
+                    + x2[9] * y2[6]
+                    + x2[8] * y2[7]
+                    + x2[7] * y2[8]
+                    + x2[6] * y2[9]
+                    + x2[5] * y2[10]
+                    + x2[4] * y2[11]
+                    + x2[3] * y2[12]
+                    + x2[2] * y2[13]
+                    + x2[1] * y2[14]
+                    + x2[0] * y2[15]
+                    - t[15]
+                    - 0x7FFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFED * q1[15]
+            }
+            16 => {
+                x2[15] * y2[1]
+                    + x2[14] * y2[2]
+                    + x2[13] * y2[3]
+                    + x2[12] * y2[4]
+                    + x2[11] * y2[5]
+                    + x2[10] * y2[6]
+                    + x2[9] * y2[7]
+                    + x2[8] * y2[8]
+                    + x2[7] * y2[9]
+                    + x2[6] * y2[10]
+                    + x2[5] * y2[11]
+                    + x2[4] * y2[12]
+                    + x2[3] * y2[13]
+                    + x2[2] * y2[14]
+                    + x2[1] * y2[15]
+                    - 0x7FFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            17 => {
+                x2[15] * y2[2]
+                    + x2[14] * y2[3]
+                    + x2[13] * y2[4]
+                    + x2[12] * y2[5]
+                    + x2[11] * y2[6]
+                    + x2[10] * y2[7]
+                    + x2[9] * y2[8]
+                    + x2[8] * y2[9]
+                    + x2[7] * y2[10]
+                    + x2[6] * y2[11]
+                    + x2[5] * y2[12]
+                    + x2[4] * y2[13]
+                    + x2[3] * y2[14]
+                    + x2[2] * y2[15]
+                    - 0x7FFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            18 => {
+                x2[15] * y2[3]
+                    + x2[14] * y2[4]
+                    + x2[13] * y2[5]
+                    + x2[12] * y2[6]
+                    + x2[11] * y2[7]
+                    + x2[10] * y2[8]
+                    + x2[9] * y2[9]
+                    + x2[8] * y2[10]
+                    + x2[7] * y2[11]
+                    + x2[6] * y2[12]
+                    + x2[5] * y2[13]
+                    + x2[4] * y2[14]
+                    + x2[3] * y2[15]
+                    - 0x7FFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            19 => {
+                x2[15] * y2[4]
+                    + x2[14] * y2[5]
+                    + x2[13] * y2[6]
+                    + x2[12] * y2[7]
+                    + x2[11] * y2[8]
+                    + x2[10] * y2[9]
+                    + x2[9] * y2[10]
+                    + x2[8] * y2[11]
+                    + x2[7] * y2[12]
+                    + x2[6] * y2[13]
+                    + x2[5] * y2[14]
+                    + x2[4] * y2[15]
+                    - 0x7FFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            20 => {
+                x2[15] * y2[5]
+                    + x2[14] * y2[6]
+                    + x2[13] * y2[7]
+                    + x2[12] * y2[8]
+                    + x2[11] * y2[9]
+                    + x2[10] * y2[10]
+                    + x2[9] * y2[11]
+                    + x2[8] * y2[12]
+                    + x2[7] * y2[13]
+                    + x2[6] * y2[14]
+                    + x2[5] * y2[15]
+                    - 0x7FFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            21 => {
+                x2[15] * y2[6]
+                    + x2[14] * y2[7]
+                    + x2[13] * y2[8]
+                    + x2[12] * y2[9]
+                    + x2[11] * y2[10]
+                    + x2[10] * y2[11]
+                    + x2[9] * y2[12]
+                    + x2[8] * y2[13]
+                    + x2[7] * y2[14]
+                    + x2[6] * y2[15]
+                    - 0x7FFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            22 => {
+                x2[15] * y2[7]
+                    + x2[14] * y2[8]
+                    + x2[13] * y2[9]
+                    + x2[12] * y2[10]
+                    + x2[11] * y2[11]
+                    + x2[10] * y2[12]
+                    + x2[9] * y2[13]
+                    + x2[8] * y2[14]
+                    + x2[7] * y2[15]
+                    - 0x7FFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            23 => {
+                x2[15] * y2[8]
+                    + x2[14] * y2[9]
+                    + x2[13] * y2[10]
+                    + x2[12] * y2[11]
+                    + x2[11] * y2[12]
+                    + x2[10] * y2[13]
+                    + x2[9] * y2[14]
+                    + x2[8] * y2[15]
+                    - 0x7FFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            24 => {
+                x2[15] * y2[9]
+                    + x2[14] * y2[10]
+                    + x2[13] * y2[11]
+                    + x2[12] * y2[12]
+                    + x2[11] * y2[13]
+                    + x2[10] * y2[14]
+                    + x2[9] * y2[15]
+                    - 0x7FFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            25 => {
+                x2[15] * y2[10]
+                    + x2[14] * y2[11]
+                    + x2[13] * y2[12]
+                    + x2[12] * y2[13]
+                    + x2[11] * y2[14]
+                    + x2[10] * y2[15]
+                    - 0x7FFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            26 => {
+                x2[15] * y2[11]
+                    + x2[14] * y2[12]
+                    + x2[13] * y2[13]
+                    + x2[12] * y2[14]
+                    + x2[11] * y2[15]
+                    - 0x7FFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            27 => {
+                x2[15] * y2[12] + x2[14] * y2[13] + x2[13] * y2[14] + x2[12] * y2[15]
+                    - 0x7FFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            28 => {
+                x2[15] * y2[13] + x2[14] * y2[14] + x2[13] * y2[15]
+                    - 0x7FFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+            }
+            29 => x2[15] * y2[14] + x2[14] * y2[15] - 0x7FFF * q1[14] - 0xFFFF * q1[15],
+            30 => x2[15] * y2[15] - 0x7FFF * q1[15],
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,836 @@
+// code generated
+//
+// equation: dinv*u-s*t+p*q2-p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// dinv: 0x40907ED214D5CE432B162114CDB9CF660B5DD6984279542E25E0F276CDC9F843
+// offset: 0x10000000000000000000000000000000000000000000000000000000000000000
+// (p*offset): 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED0000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519AddU {}
+
+impl Ed25519AddU {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(icol: u8, s: &[i64; 16], t: &[i64; 16], u: &[i64; 16], q2: &[i64; 16]) -> i64 {
+        match icol {
+            0 => 0xF843 * u[0] - s[0] * t[0] + 0xFFED * q2[0],
+            1 => {
+                0xCDC9 * u[0] + 0xF843 * u[1] - s[1] * t[0] - s[0] * t[1]
+                    + 0xFFFF * q2[0]
+                    + 0xFFED * q2[1]
+            }
+            2 => {
+                0xF276 * u[0] + 0xCDC9 * u[1] + 0xF843 * u[2]
+                    - s[2] * t[0]
+                    - s[1] * t[1]
+                    - s[0] * t[2]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFED * q2[2]
+            }
+            3 => {
+                0x25E0 * u[0] + 0xF276 * u[1] + 0xCDC9 * u[2] + 0xF843 * u[3]
+                    - s[3] * t[0]
+                    - s[2] * t[1]
+                    - s[1] * t[2]
+                    - s[0] * t[3]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFED * q2[3]
+            }
+            4 => {
+                0x542E * u[0] + 0x25E0 * u[1] + 0xF276 * u[2] + 0xCDC9 * u[3] + 0xF843 * u[4]
+                    - s[4] * t[0]
+                    - s[3] * t[1]
+                    - s[2] * t[2]
+                    - s[1] * t[3]
+                    - s[0] * t[4]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFED * q2[4]
+            }
+            5 => {
+                0x4279 * u[0]
+                    + 0x542E * u[1]
+                    + 0x25E0 * u[2]
+                    + 0xF276 * u[3]
+                    + 0xCDC9 * u[4]
+                    + 0xF843 * u[5]
+                    - s[5] * t[0]
+                    - s[4] * t[1]
+                    - s[3] * t[2]
+                    - s[2] * t[3]
+                    - s[1] * t[4]
+                    - s[0] * t[5]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFED * q2[5]
+            }
+            6 => {
+                0xD698 * u[0]
+                    + 0x4279 * u[1]
+                    + 0x542E * u[2]
+                    + 0x25E0 * u[3]
+                    + 0xF276 * u[4]
+                    + 0xCDC9 * u[5]
+                    + 0xF843 * u[6]
+                    - s[6] * t[0]
+                    - s[5] * t[1]
+                    - s[4] * t[2]
+                    - s[3] * t[3]
+                    - s[2] * t[4]
+                    - s[1] * t[5]
+                    - s[0] * t[6]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFED * q2[6]
+            }
+            7 => {
+                0xB5D * u[0]
+                    + 0xD698 * u[1]
+                    + 0x4279 * u[2]
+                    + 0x542E * u[3]
+                    + 0x25E0 * u[4]
+                    + 0xF276 * u[5]
+                    + 0xCDC9 * u[6]
+                    + 0xF843 * u[7]
+                    - s[7] * t[0]
+                    - s[6] * t[1]
+                    - s[5] * t[2]
+                    - s[4] * t[3]
+                    - s[3] * t[4]
+                    - s[2] * t[5]
+                    - s[1] * t[6]
+                    - s[0] * t[7]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFED * q2[7]
+            }
+            8 => {
+                0xCF66 * u[0]
+                    + 0xB5D * u[1]
+                    + 0xD698 * u[2]
+                    + 0x4279 * u[3]
+                    + 0x542E * u[4]
+                    + 0x25E0 * u[5]
+                    + 0xF276 * u[6]
+                    + 0xCDC9 * u[7]
+                    + 0xF843 * u[8]
+                    - s[8] * t[0]
+                    - s[7] * t[1]
+                    - s[6] * t[2]
+                    - s[5] * t[3]
+                    - s[4] * t[4]
+                    - s[3] * t[5]
+                    - s[2] * t[6]
+                    - s[1] * t[7]
+                    - s[0] * t[8]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFED * q2[8]
+            }
+            9 => {
+                0xCDB9 * u[0]
+                    + 0xCF66 * u[1]
+                    + 0xB5D * u[2]
+                    + 0xD698 * u[3]
+                    + 0x4279 * u[4]
+                    + 0x542E * u[5]
+                    + 0x25E0 * u[6]
+                    + 0xF276 * u[7]
+                    + 0xCDC9 * u[8]
+                    + 0xF843 * u[9]
+                    - s[9] * t[0]
+                    - s[8] * t[1]
+                    - s[7] * t[2]
+                    - s[6] * t[3]
+                    - s[5] * t[4]
+                    - s[4] * t[5]
+                    - s[3] * t[6]
+                    - s[2] * t[7]
+                    - s[1] * t[8]
+                    - s[0] * t[9]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFED * q2[9]
+            }
+            10 => {
+                0x2114 * u[0]
+                    + 0xCDB9 * u[1]
+                    + 0xCF66 * u[2]
+                    + 0xB5D * u[3]
+                    + 0xD698 * u[4]
+                    + 0x4279 * u[5]
+                    + 0x542E * u[6]
+                    + 0x25E0 * u[7]
+                    + 0xF276 * u[8]
+                    + 0xCDC9 * u[9]
+                    + 0xF843 * u[10]
+                    - s[10] * t[0]
+                    - s[9] * t[1]
+                    - s[8] * t[2]
+                    - s[7] * t[3]
+                    - s[6] * t[4]
+                    - s[5] * t[5]
+                    - s[4] * t[6]
+                    - s[3] * t[7]
+                    - s[2] * t[8]
+                    - s[1] * t[9]
+                    - s[0] * t[10]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFED * q2[10]
+            }
+            11 => {
+                0x2B16 * u[0]
+                    + 0x2114 * u[1]
+                    + 0xCDB9 * u[2]
+                    + 0xCF66 * u[3]
+                    + 0xB5D * u[4]
+                    + 0xD698 * u[5]
+                    + 0x4279 * u[6]
+                    + 0x542E * u[7]
+                    + 0x25E0 * u[8]
+                    + 0xF276 * u[9]
+                    + 0xCDC9 * u[10]
+                    + 0xF843 * u[11]
+                    - s[11] * t[0]
+                    - s[10] * t[1]
+                    - s[9] * t[2]
+                    - s[8] * t[3]
+                    - s[7] * t[4]
+                    - s[6] * t[5]
+                    - s[5] * t[6]
+                    - s[4] * t[7]
+                    - s[3] * t[8]
+                    - s[2] * t[9]
+                    - s[1] * t[10]
+                    - s[0] * t[11]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFED * q2[11]
+            }
+            12 => {
+                0xCE43 * u[0]
+                    + 0x2B16 * u[1]
+                    + 0x2114 * u[2]
+                    + 0xCDB9 * u[3]
+                    + 0xCF66 * u[4]
+                    + 0xB5D * u[5]
+                    + 0xD698 * u[6]
+                    + 0x4279 * u[7]
+                    + 0x542E * u[8]
+                    + 0x25E0 * u[9]
+                    + 0xF276 * u[10]
+                    + 0xCDC9 * u[11]
+                    + 0xF843 * u[12]
+                    - s[12] * t[0]
+                    - s[11] * t[1]
+                    - s[10] * t[2]
+                    - s[9] * t[3]
+                    - s[8] * t[4]
+                    - s[7] * t[5]
+                    - s[6] * t[6]
+                    - s[5] * t[7]
+                    - s[4] * t[8]
+                    - s[3] * t[9]
+                    - s[2] * t[10]
+                    - s[1] * t[11]
+                    - s[0] * t[12]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFED * q2[12]
+            }
+            13 => {
+                0x14D5 * u[0]
+                    + 0xCE43 * u[1]
+                    + 0x2B16 * u[2]
+                    + 0x2114 * u[3]
+                    + 0xCDB9 * u[4]
+                    + 0xCF66 * u[5]
+                    + 0xB5D * u[6]
+                    + 0xD698 * u[7]
+                    + 0x4279 * u[8]
+                    + 0x542E * u[9]
+                    + 0x25E0 * u[10]
+                    + 0xF276 * u[11]
+                    + 0xCDC9 * u[12]
+                    + 0xF843 * u[13]
+                    - s[13] * t[0]
+                    - s[12] * t[1]
+                    - s[11] * t[2]
+                    - s[10] * t[3]
+                    - s[9] * t[4]
+                    - s[8] * t[5]
+                    - s[7] * t[6]
+                    - s[6] * t[7]
+                    - s[5] * t[8]
+                    - s[4] * t[9]
+                    - s[3] * t[10]
+                    - s[2] * t[11]
+                    - s[1] * t[12]
+                    - s[0] * t[13]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFED * q2[13]
+            }
+            14 => {
+                0x7ED2 * u[0]
+                    + 0x14D5 * u[1]
+                    + 0xCE43 * u[2]
+                    + 0x2B16 * u[3]
+                    + 0x2114 * u[4]
+                    + 0xCDB9 * u[5]
+                    + 0xCF66 * u[6]
+                    + 0xB5D * u[7]
+                    + 0xD698 * u[8]
+                    + 0x4279 * u[9]
+                    + 0x542E * u[10]
+                    + 0x25E0 * u[11]
+                    + 0xF276 * u[12]
+                    + 0xCDC9 * u[13]
+                    + 0xF843 * u[14]
+                    - s[14] * t[0]
+                    - s[13] * t[1]
+                    - s[12] * t[2]
+                    - s[11] * t[3]
+                    - s[10] * t[4]
+                    - s[9] * t[5]
+                    - s[8] * t[6]
+                    - s[7] * t[7]
+                    - s[6] * t[8]
+                    - s[5] * t[9]
+                    - s[4] * t[10]
+                    - s[3] * t[11]
+                    - s[2] * t[12]
+                    - s[1] * t[13]
+                    - s[0] * t[14]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFED * q2[14]
+            }
+            15 => {
+                0x4090 * u[0]
+                    + 0x7ED2 * u[1]
+                    + 0x14D5 * u[2]
+                    + 0xCE43 * u[3]
+                    + 0x2B16 * u[4]
+                    + 0x2114 * u[5]
+                    + 0xCDB9 * u[6]
+                    + 0xCF66 * u[7]
+                    + 0xB5D * u[8]
+                    + 0xD698 * u[9]
+                    + 0x4279 * u[10]
+                    + 0x542E * u[11]
+                    + 0x25E0 * u[12]
+                    + 0xF276 * u[13]
+                    + 0xCDC9 * u[14]
+                    + 0xF843 * u[15]
+                    - s[15] * t[0]
+                    - s[14] * t[1]
+                    - s[13] * t[2]
+                    - s[12] * t[3]
+                    - s[11] * t[4]
+                    - s[10] * t[5]
+                    - s[9] * t[6]
+                    - s[8] * t[7]
+                    - s[7] * t[8]
+                    - s[6] * t[9]
+                    - s[5] * t[10]
+                    - s[4] * t[11]
+                    - s[3] * t[12]
+                    - s[2] * t[13]
+                    - s[1] * t[14]
+                    - s[0] * t[15]
+                    + 0x7FFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFED * q2[15]
+            }
+            16 => {
+                0x4090 * u[1]
+                    + 0x7ED2 * u[2]
+                    + 0x14D5 * u[3]
+                    + 0xCE43 * u[4]
+                    + 0x2B16 * u[5]
+                    + 0x2114 * u[6]
+                    + 0xCDB9 * u[7]
+                    + 0xCF66 * u[8]
+                    + 0xB5D * u[9]
+                    + 0xD698 * u[10]
+                    + 0x4279 * u[11]
+                    + 0x542E * u[12]
+                    + 0x25E0 * u[13]
+                    + 0xF276 * u[14]
+                    + 0xCDC9 * u[15]
+                    - s[15] * t[1]
+                    - s[14] * t[2]
+                    - s[13] * t[3]
+                    - s[12] * t[4]
+                    - s[11] * t[5]
+                    - s[10] * t[6]
+                    - s[9] * t[7]
+                    - s[8] * t[8]
+                    - s[7] * t[9]
+                    - s[6] * t[10]
+                    - s[5] * t[11]
+                    - s[4] * t[12]
+                    - s[3] * t[13]
+                    - s[2] * t[14]
+                    - s[1] * t[15]
+                    + 0x7FFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFED
+            }
+            17 => {
+                0x4090 * u[2]
+                    + 0x7ED2 * u[3]
+                    + 0x14D5 * u[4]
+                    + 0xCE43 * u[5]
+                    + 0x2B16 * u[6]
+                    + 0x2114 * u[7]
+                    + 0xCDB9 * u[8]
+                    + 0xCF66 * u[9]
+                    + 0xB5D * u[10]
+                    + 0xD698 * u[11]
+                    + 0x4279 * u[12]
+                    + 0x542E * u[13]
+                    + 0x25E0 * u[14]
+                    + 0xF276 * u[15]
+                    - s[15] * t[2]
+                    - s[14] * t[3]
+                    - s[13] * t[4]
+                    - s[12] * t[5]
+                    - s[11] * t[6]
+                    - s[10] * t[7]
+                    - s[9] * t[8]
+                    - s[8] * t[9]
+                    - s[7] * t[10]
+                    - s[6] * t[11]
+                    - s[5] * t[12]
+                    - s[4] * t[13]
+                    - s[3] * t[14]
+                    - s[2] * t[15]
+                    + 0x7FFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            18 => {
+                0x4090 * u[3]
+                    + 0x7ED2 * u[4]
+                    + 0x14D5 * u[5]
+                    + 0xCE43 * u[6]
+                    + 0x2B16 * u[7]
+                    + 0x2114 * u[8]
+                    + 0xCDB9 * u[9]
+                    + 0xCF66 * u[10]
+                    + 0xB5D * u[11]
+                    + 0xD698 * u[12]
+                    + 0x4279 * u[13]
+                    + 0x542E * u[14]
+                    + 0x25E0 * u[15]
+                    - s[15] * t[3]
+                    - s[14] * t[4]
+                    - s[13] * t[5]
+                    - s[12] * t[6]
+                    - s[11] * t[7]
+                    - s[10] * t[8]
+                    - s[9] * t[9]
+                    - s[8] * t[10]
+                    - s[7] * t[11]
+                    - s[6] * t[12]
+                    - s[5] * t[13]
+                    - s[4] * t[14]
+                    - s[3] * t[15]
+                    + 0x7FFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            19 => {
+                0x4090 * u[4]
+                    + 0x7ED2 * u[5]
+                    + 0x14D5 * u[6]
+                    + 0xCE43 * u[7]
+                    + 0x2B16 * u[8]
+                    + 0x2114 * u[9]
+                    + 0xCDB9 * u[10]
+                    + 0xCF66 * u[11]
+                    + 0xB5D * u[12]
+                    + 0xD698 * u[13]
+                    + 0x4279 * u[14]
+                    + 0x542E * u[15]
+                    - s[15] * t[4]
+                    - s[14] * t[5]
+                    - s[13] * t[6]
+                    - s[12] * t[7]
+                    - s[11] * t[8]
+                    - s[10] * t[9]
+                    - s[9] * t[10]
+                    - s[8] * t[11]
+                    - s[7] * t[12]
+                    - s[6] * t[13]
+                    - s[5] * t[14]
+                    - s[4] * t[15]
+                    + 0x7FFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            20 => {
+                0x4090 * u[5]
+                    + 0x7ED2 * u[6]
+                    + 0x14D5 * u[7]
+                    + 0xCE43 * u[8]
+                    + 0x2B16 * u[9]
+                    + 0x2114 * u[10]
+                    + 0xCDB9 * u[11]
+                    + 0xCF66 * u[12]
+                    + 0xB5D * u[13]
+                    + 0xD698 * u[14]
+                    + 0x4279 * u[15]
+                    - s[15] * t[5]
+                    - s[14] * t[6]
+                    - s[13] * t[7]
+                    - s[12] * t[8]
+                    - s[11] * t[9]
+                    - s[10] * t[10]
+                    - s[9] * t[11]
+                    - s[8] * t[12]
+                    - s[7] * t[13]
+                    - s[6] * t[14]
+                    - s[5] * t[15]
+                    + 0x7FFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            21 => {
+                0x4090 * u[6]
+                    + 0x7ED2 * u[7]
+                    + 0x14D5 * u[8]
+                    + 0xCE43 * u[9]
+                    + 0x2B16 * u[10]
+                    + 0x2114 * u[11]
+                    + 0xCDB9 * u[12]
+                    + 0xCF66 * u[13]
+                    + 0xB5D * u[14]
+                    + 0xD698 * u[15]
+                    - s[15] * t[6]
+                    - s[14] * t[7]
+                    - s[13] * t[8]
+                    - s[12] * t[9]
+                    - s[11] * t[10]
+                    - s[10] * t[11]
+                    - s[9] * t[12]
+                    - s[8] * t[13]
+                    - s[7] * t[14]
+                    - s[6] * t[15]
+                    + 0x7FFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            22 => {
+                0x4090 * u[7]
+                    + 0x7ED2 * u[8]
+                    + 0x14D5 * u[9]
+                    + 0xCE43 * u[10]
+                    + 0x2B16 * u[11]
+                    + 0x2114 * u[12]
+                    + 0xCDB9 * u[13]
+                    + 0xCF66 * u[14]
+                    + 0xB5D * u[15]
+                    - s[15] * t[7]
+                    - s[14] * t[8]
+                    - s[13] * t[9]
+                    - s[12] * t[10]
+                    - s[11] * t[11]
+                    - s[10] * t[12]
+                    - s[9] * t[13]
+                    - s[8] * t[14]
+                    - s[7] * t[15]
+                    + 0x7FFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            23 => {
+                0x4090 * u[8]
+                    + 0x7ED2 * u[9]
+                    + 0x14D5 * u[10]
+                    + 0xCE43 * u[11]
+                    + 0x2B16 * u[12]
+                    + 0x2114 * u[13]
+                    + 0xCDB9 * u[14]
+                    + 0xCF66 * u[15]
+                    - s[15] * t[8]
+                    - s[14] * t[9]
+                    - s[13] * t[10]
+                    - s[12] * t[11]
+                    - s[11] * t[12]
+                    - s[10] * t[13]
+                    - s[9] * t[14]
+                    - s[8] * t[15]
+                    + 0x7FFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            24 => {
+                0x4090 * u[9]
+                    + 0x7ED2 * u[10]
+                    + 0x14D5 * u[11]
+                    + 0xCE43 * u[12]
+                    + 0x2B16 * u[13]
+                    + 0x2114 * u[14]
+                    + 0xCDB9 * u[15]
+                    - s[15] * t[9]
+                    - s[14] * t[10]
+                    - s[13] * t[11]
+                    - s[12] * t[12]
+                    - s[11] * t[13]
+                    - s[10] * t[14]
+                    - s[9] * t[15]
+                    + 0x7FFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            25 => {
+                0x4090 * u[10]
+                    + 0x7ED2 * u[11]
+                    + 0x14D5 * u[12]
+                    + 0xCE43 * u[13]
+                    + 0x2B16 * u[14]
+                    + 0x2114 * u[15]
+                    - s[15] * t[10]
+                    - s[14] * t[11]
+                    - s[13] * t[12]
+                    - s[12] * t[13]
+                    - s[11] * t[14]
+                    - s[10] * t[15]
+                    + 0x7FFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            26 => {
+                0x4090 * u[11] + 0x7ED2 * u[12] + 0x14D5 * u[13] + 0xCE43 * u[14] + 0x2B16 * u[15]
+                    - s[15] * t[11]
+                    - s[14] * t[12]
+                    - s[13] * t[13]
+                    - s[12] * t[14]
+                    - s[11] * t[15]
+                    + 0x7FFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            27 => {
+                0x4090 * u[12] + 0x7ED2 * u[13] + 0x14D5 * u[14] + 0xCE43 * u[15]
+                    - s[15] * t[12]
+                    - s[14] * t[13]
+                    - s[13] * t[14]
+                    - s[12] * t[15]
+                    + 0x7FFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            28 => {
+                0x4090 * u[13] + 0x7ED2 * u[14] + 0x14D5 * u[15]
+                    - s[15] * t[13]
+                    - s[14] * t[14]
+                    - s[13] * t[15]
+                    + 0x7FFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            29 => {
+                0x4090 * u[14] + 0x7ED2 * u[15] - s[15] * t[14] - s[14] * t[15]
+                    + 0x7FFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            30 => 0x4090 * u[15] - s[15] * t[15] + 0x7FFF * q2[15] - 0xFFFF,
+            31 => -0x7FFF,
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,1113 @@
+// code generated
+//
+// equation: x3+x3*u-x1*y2-y1*x2+p*q3-p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// offset: 0x80000000000000000000000000000000000000000000000000000000000000000
+// (p*offset): 0x3FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF680000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519AddX3 {}
+
+impl Ed25519AddX3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        x2: &[i64; 16],
+        y2: &[i64; 16],
+        x3: &[i64; 16],
+        u: &[i64; 16],
+        q3: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => x3[0] + x3[0] * u[0] - x1[0] * y2[0] - y1[0] * x2[0] + 0xFFED * q3[0],
+            1 => {
+                x3[1] + x3[1] * u[0] + x3[0] * u[1]
+                    - x1[1] * y2[0]
+                    - x1[0] * y2[1]
+                    - y1[1] * x2[0]
+                    - y1[0] * x2[1]
+                    + 0xFFFF * q3[0]
+                    + 0xFFED * q3[1]
+            }
+            2 => {
+                x3[2] + x3[2] * u[0] + x3[1] * u[1] + x3[0] * u[2]
+                    - x1[2] * y2[0]
+                    - x1[1] * y2[1]
+                    - x1[0] * y2[2]
+                    - y1[2] * x2[0]
+                    - y1[1] * x2[1]
+                    - y1[0] * x2[2]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFED * q3[2]
+            }
+            3 => {
+                x3[3] + x3[3] * u[0] + x3[2] * u[1] + x3[1] * u[2] + x3[0] * u[3]
+                    - x1[3] * y2[0]
+                    - x1[2] * y2[1]
+                    - x1[1] * y2[2]
+                    - x1[0] * y2[3]
+                    - y1[3] * x2[0]
+                    - y1[2] * x2[1]
+                    - y1[1] * x2[2]
+                    - y1[0] * x2[3]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFED * q3[3]
+            }
+            4 => {
+                x3[4] + x3[4] * u[0] + x3[3] * u[1] + x3[2] * u[2] + x3[1] * u[3] + x3[0] * u[4]
+                    - x1[4] * y2[0]
+                    - x1[3] * y2[1]
+                    - x1[2] * y2[2]
+                    - x1[1] * y2[3]
+                    - x1[0] * y2[4]
+                    - y1[4] * x2[0]
+                    - y1[3] * x2[1]
+                    - y1[2] * x2[2]
+                    - y1[1] * x2[3]
+                    - y1[0] * x2[4]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFED * q3[4]
+            }
+            5 => {
+                x3[5]
+                    + x3[5] * u[0]
+                    + x3[4] * u[1]
+                    + x3[3] * u[2]
+                    + x3[2] * u[3]
+                    + x3[1] * u[4]
+                    + x3[0] * u[5]
+                    - x1[5] * y2[0]
+                    - x1[4] * y2[1]
+                    - x1[3] * y2[2]
+                    - x1[2] * y2[3]
+                    - x1[1] * y2[4]
+                    - x1[0] * y2[5]
+                    - y1[5] * x2[0]
+                    - y1[4] * x2[1]
+                    - y1[3] * x2[2]
+                    - y1[2] * x2[3]
+                    - y1[1] * x2[4]
+                    - y1[0] * x2[5]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFED * q3[5]
+            }
+            6 => {
+                x3[6]
+                    + x3[6] * u[0]
+                    + x3[5] * u[1]
+                    + x3[4] * u[2]
+                    + x3[3] * u[3]
+                    + x3[2] * u[4]
+                    + x3[1] * u[5]
+                    + x3[0] * u[6]
+                    - x1[6] * y2[0]
+                    - x1[5] * y2[1]
+                    - x1[4] * y2[2]
+                    - x1[3] * y2[3]
+                    - x1[2] * y2[4]
+                    - x1[1] * y2[5]
+                    - x1[0] * y2[6]
+                    - y1[6] * x2[0]
+                    - y1[5] * x2[1]
+                    - y1[4] * x2[2]
+                    - y1[3] * x2[3]
+                    - y1[2] * x2[4]
+                    - y1[1] * x2[5]
+                    - y1[0] * x2[6]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFED * q3[6]
+            }
+            7 => {
+                x3[7]
+                    + x3[7] * u[0]
+                    + x3[6] * u[1]
+                    + x3[5] * u[2]
+                    + x3[4] * u[3]
+                    + x3[3] * u[4]
+                    + x3[2] * u[5]
+                    + x3[1] * u[6]
+                    + x3[0] * u[7]
+                    - x1[7] * y2[0]
+                    - x1[6] * y2[1]
+                    - x1[5] * y2[2]
+                    - x1[4] * y2[3]
+                    - x1[3] * y2[4]
+                    - x1[2] * y2[5]
+                    - x1[1] * y2[6]
+                    - x1[0] * y2[7]
+                    - y1[7] * x2[0]
+                    - y1[6] * x2[1]
+                    - y1[5] * x2[2]
+                    - y1[4] * x2[3]
+                    - y1[3] * x2[4]
+                    - y1[2] * x2[5]
+                    - y1[1] * x2[6]
+                    - y1[0] * x2[7]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFED * q3[7]
+            }
+            8 => {
+                x3[8]
+                    + x3[8] * u[0]
+                    + x3[7] * u[1]
+                    + x3[6] * u[2]
+                    + x3[5] * u[3]
+                    + x3[4] * u[4]
+                    + x3[3] * u[5]
+                    + x3[2] * u[6]
+                    + x3[1] * u[7]
+                    + x3[0] * u[8]
+                    - x1[8] * y2[0]
+                    - x1[7] * y2[1]
+                    - x1[6] * y2[2]
+                    - x1[5] * y2[3]
+                    - x1[4] * y2[4]
+                    - x1[3] * y2[5]
+                    - x1[2] * y2[6]
+                    - x1[1] * y2[7]
+                    - x1[0] * y2[8]
+                    - y1[8] * x2[0]
+                    - y1[7] * x2[1]
+                    - y1[6] * x2[2]
+                    - y1[5] * x2[3]
+                    - y1[4] * x2[4]
+                    - y1[3] * x2[5]
+                    - y1[2] * x2[6]
+                    - y1[1] * x2[7]
+                    - y1[0] * x2[8]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFED * q3[8]
+            }
+            9 => {
+                x3[9]
+                    + x3[9] * u[0]
+                    + x3[8] * u[1]
+                    + x3[7] * u[2]
+                    + x3[6] * u[3]
+                    + x3[5] * u[4]
+                    + x3[4] * u[5]
+                    + x3[3] * u[6]
+                    + x3[2] * u[7]
+                    + x3[1] * u[8]
+                    + x3[0] * u[9]
+                    - x1[9] * y2[0]
+                    - x1[8] * y2[1]
+                    - x1[7] * y2[2]
+                    - x1[6] * y2[3]
+                    - x1[5] * y2[4]
+                    - x1[4] * y2[5]
+                    - x1[3] * y2[6]
+                    - x1[2] * y2[7]
+                    - x1[1] * y2[8]
+                    - x1[0] * y2[9]
+                    - y1[9] * x2[0]
+                    - y1[8] * x2[1]
+                    - y1[7] * x2[2]
+                    - y1[6] * x2[3]
+                    - y1[5] * x2[4]
+                    - y1[4] * x2[5]
+                    - y1[3] * x2[6]
+                    - y1[2] * x2[7]
+                    - y1[1] * x2[8]
+                    - y1[0] * x2[9]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFED * q3[9]
+            }
+            10 => {
+                x3[10]
+                    + x3[10] * u[0]
+                    + x3[9] * u[1]
+                    + x3[8] * u[2]
+                    + x3[7] * u[3]
+                    + x3[6] * u[4]
+                    + x3[5] * u[5]
+                    + x3[4] * u[6]
+                    + x3[3] * u[7]
+                    + x3[2] * u[8]
+                    + x3[1] * u[9]
+                    + x3[0] * u[10]
+                    - x1[10] * y2[0]
+                    - x1[9] * y2[1]
+                    - x1[8] * y2[2]
+                    - x1[7] * y2[3]
+                    - x1[6] * y2[4]
+                    - x1[5] * y2[5]
+                    - x1[4] * y2[6]
+                    - x1[3] * y2[7]
+                    - x1[2] * y2[8]
+                    - x1[1] * y2[9]
+                    - x1[0] * y2[10]
+                    - y1[10] * x2[0]
+                    - y1[9] * x2[1]
+                    - y1[8] * x2[2]
+                    - y1[7] * x2[3]
+                    - y1[6] * x2[4]
+                    - y1[5] * x2[5]
+                    - y1[4] * x2[6]
+                    - y1[3] * x2[7]
+                    - y1[2] * x2[8]
+                    - y1[1] * x2[9]
+                    - y1[0] * x2[10]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFED * q3[10]
+            }
+            11 => {
+                x3[11]
+                    + x3[11] * u[0]
+                    + x3[10] * u[1]
+                    + x3[9] * u[2]
+                    + x3[8] * u[3]
+                    + x3[7] * u[4]
+                    + x3[6] * u[5]
+                    + x3[5] * u[6]
+                    + x3[4] * u[7]
+                    + x3[3] * u[8]
+                    + x3[2] * u[9]
+                    + x3[1] * u[10]
+                    + x3[0] * u[11]
+                    - x1[11] * y2[0]
+                    - x1[10] * y2[1]
+                    - x1[9] * y2[2]
+                    - x1[8] * y2[3]
+                    - x1[7] * y2[4]
+                    - x1[6] * y2[5]
+                    - x1[5] * y2[6]
+                    - x1[4] * y2[7]
+                    - x1[3] * y2[8]
+                    - x1[2] * y2[9]
+                    - x1[1] * y2[10]
+                    - x1[0] * y2[11]
+                    - y1[11] * x2[0]
+                    - y1[10] * x2[1]
+                    - y1[9] * x2[2]
+                    - y1[8] * x2[3]
+                    - y1[7] * x2[4]
+                    - y1[6] * x2[5]
+                    - y1[5] * x2[6]
+                    - y1[4] * x2[7]
+                    - y1[3] * x2[8]
+                    - y1[2] * x2[9]
+                    - y1[1] * x2[10]
+                    - y1[0] * x2[11]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFED * q3[11]
+            }
+            12 => {
+                x3[12]
+                    + x3[12] * u[0]
+                    + x3[11] * u[1]
+                    + x3[10] * u[2]
+                    + x3[9] * u[3]
+                    + x3[8] * u[4]
+                    + x3[7] * u[5]
+                    + x3[6] * u[6]
+                    + x3[5] * u[7]
+                    + x3[4] * u[8]
+                    + x3[3] * u[9]
+                    + x3[2] * u[10]
+                    + x3[1] * u[11]
+                    + x3[0] * u[12]
+                    - x1[12] * y2[0]
+                    - x1[11] * y2[1]
+                    - x1[10] * y2[2]
+                    - x1[9] * y2[3]
+                    - x1[8] * y2[4]
+                    - x1[7] * y2[5]
+                    - x1[6] * y2[6]
+                    - x1[5] * y2[7]
+                    - x1[4] * y2[8]
+                    - x1[3] * y2[9]
+                    - x1[2] * y2[10]
+                    - x1[1] * y2[11]
+                    - x1[0] * y2[12]
+                    - y1[12] * x2[0]
+                    - y1[11] * x2[1]
+                    - y1[10] * x2[2]
+                    - y1[9] * x2[3]
+                    - y1[8] * x2[4]
+                    - y1[7] * x2[5]
+                    - y1[6] * x2[6]
+                    - y1[5] * x2[7]
+                    - y1[4] * x2[8]
+                    - y1[3] * x2[9]
+                    - y1[2] * x2[10]
+                    - y1[1] * x2[11]
+                    - y1[0] * x2[12]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFED * q3[12]
+            }
+            13 => {
+                x3[13]
+                    + x3[13] * u[0]
+                    + x3[12] * u[1]
+                    + x3[11] * u[2]
+                    + x3[10] * u[3]
+                    + x3[9] * u[4]
+                    + x3[8] * u[5]
+                    + x3[7] * u[6]
+                    + x3[6] * u[7]
+                    + x3[5] * u[8]
+                    + x3[4] * u[9]
+                    + x3[3] * u[10]
+                    + x3[2] * u[11]
+                    + x3[1] * u[12]
+                    + x3[0] * u[13]
+                    - x1[13] * y2[0]
+                    - x1[12] * y2[1]
+                    - x1[11] * y2[2]
+                    - x1[10] * y2[3]
+                    - x1[9] * y2[4]
+                    - x1[8] * y2[5]
+                    - x1[7] * y2[6]
+                    - x1[6] * y2[7]
+                    - x1[5] * y2[8]
+                    - x1[4] * y2[9]
+                    - x1[3] * y2[10]
+                    - x1[2] * y2[11]
+                    - x1[1] * y2[12]
+                    - x1[0] * y2[13]
+                    - y1[13] * x2[0]
+                    - y1[12] * x2[1]
+                    - y1[11] * x2[2]
+                    - y1[10] * x2[3]
+                    - y1[9] * x2[4]
+                    - y1[8] * x2[5]
+                    - y1[7] * x2[6]
+                    - y1[6] * x2[7]
+                    - y1[5] * x2[8]
+                    - y1[4] * x2[9]
+                    - y1[3] * x2[10]
+                    - y1[2] * x2[11]
+                    - y1[1] * x2[12]
+                    - y1[0] * x2[13]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFED * q3[13]
+            }
+            14 => {
+                x3[14]
+                    + x3[14] * u[0]
+                    + x3[13] * u[1]
+                    + x3[12] * u[2]
+                    + x3[11] * u[3]
+                    + x3[10] * u[4]
+                    + x3[9] * u[5]
+                    + x3[8] * u[6]
+                    + x3[7] * u[7]
+                    + x3[6] * u[8]
+                    + x3[5] * u[9]
+                    + x3[4] * u[10]
+                    + x3[3] * u[11]
+                    + x3[2] * u[12]
+                    + x3[1] * u[13]
+                    + x3[0] * u[14]
+                    - x1[14] * y2[0]
+                    - x1[13] * y2[1]
+                    - x1[12] * y2[2]
+                    - x1[11] * y2[3]
+                    - x1[10] * y2[4]
+                    - x1[9] * y2[5]
+                    - x1[8] * y2[6]
+                    - x1[7] * y2[7]
+                    - x1[6] * y2[8]
+                    - x1[5] * y2[9]
+                    - x1[4] * y2[10]
+                    - x1[3] * y2[11]
+                    - x1[2] * y2[12]
+                    - x1[1] * y2[13]
+                    - x1[0] * y2[14]
+                    - y1[14] * x2[0]
+                    - y1[13] * x2[1]
+                    - y1[12] * x2[2]
+                    - y1[11] * x2[3]
+                    - y1[10] * x2[4]
+                    - y1[9] * x2[5]
+                    - y1[8] * x2[6]
+                    - y1[7] * x2[7]
+                    - y1[6] * x2[8]
+                    - y1[5] * x2[9]
+                    - y1[4] * x2[10]
+                    - y1[3] * x2[11]
+                    - y1[2] * x2[12]
+                    - y1[1] * x2[13]
+                    - y1[0] * x2[14]
+                    + 0xFFFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFED * q3[14]
+            }
+            15 => {
+                x3[15]
+                    + x3[15] * u[0]
+                    + x3[14] * u[1]
+                    + x3[13] * u[2]
+                    + x3[12] * u[3]
+                    + x3[11] * u[4]
+                    + x3[10] * u[5]
+                    + x3[9] * u[6]
+                    + x3[8] * u[7]
+                    + x3[7] * u[8]
+                    + x3[6] * u[9]
+                    + x3[5] * u[10]
+                    + x3[4] * u[11]
+                    + x3[3] * u[12]
+                    + x3[2] * u[13]
+                    + x3[1] * u[14]
+                    + x3[0] * u[15]
+                    - x1[15] * y2[0]
+                    - x1[14] * y2[1]
+                    - x1[13] * y2[2]
+                    - x1[12] * y2[3]
+                    - x1[11] * y2[4]
+                    - x1[10] * y2[5]
+                    - x1[9] * y2[6]
+                    - x1[8] * y2[7]
+                    - x1[7] * y2[8]
+                    - x1[6] * y2[9]
+                    - x1[5] * y2[10]
+                    - x1[4] * y2[11]
+                    - x1[3] * y2[12]
+                    - x1[2] * y2[13]
+                    - x1[1] * y2[14]
+                    - x1[0] * y2[15]
+                    - y1[15] * x2[0]
+                    - y1[14] * x2[1]
+                    - y1[13] * x2[2]
+                    - y1[12] * x2[3]
+                    - y1[11] * x2[4]
+                    - y1[10] * x2[5]
+                    - y1[9] * x2[6]
+                    - y1[8] * x2[7]
+                    - y1[7] * x2[8]
+                    - y1[6] * x2[9]
+                    - y1[5] * x2[10]
+                    - y1[4] * x2[11]
+                    - y1[3] * x2[12]
+                    - y1[2] * x2[13]
+                    - y1[1] * x2[14]
+                    - y1[0] * x2[15]
+                    + 0x7FFF * q3[0]
+                    + 0xFFFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFED * q3[15]
+            }
+            16 => {
+                x3[15] * u[1]
+                    + x3[14] * u[2]
+                    + x3[13] * u[3]
+                    + x3[12] * u[4]
+                    + x3[11] * u[5]
+                    + x3[10] * u[6]
+                    + x3[9] * u[7]
+                    + x3[8] * u[8]
+                    + x3[7] * u[9]
+                    + x3[6] * u[10]
+                    + x3[5] * u[11]
+                    + x3[4] * u[12]
+                    + x3[3] * u[13]
+                    + x3[2] * u[14]
+                    + x3[1] * u[15]
+                    - x1[15] * y2[1]
+                    - x1[14] * y2[2]
+                    - x1[13] * y2[3]
+                    - x1[12] * y2[4]
+                    - x1[11] * y2[5]
+                    - x1[10] * y2[6]
+                    - x1[9] * y2[7]
+                    - x1[8] * y2[8]
+                    - x1[7] * y2[9]
+                    - x1[6] * y2[10]
+                    - x1[5] * y2[11]
+                    - x1[4] * y2[12]
+                    - x1[3] * y2[13]
+                    - x1[2] * y2[14]
+                    - x1[1] * y2[15]
+                    - y1[15] * x2[1]
+                    - y1[14] * x2[2]
+                    - y1[13] * x2[3]
+                    - y1[12] * x2[4]
+                    - y1[11] * x2[5]
+                    - y1[10] * x2[6]
+                    - y1[9] * x2[7]
+                    - y1[8] * x2[8]
+                    - y1[7] * x2[9]
+                    - y1[6] * x2[10]
+                    - y1[5] * x2[11]
+                    - y1[4] * x2[12]
+                    - y1[3] * x2[13]
+                    - y1[2] * x2[14]
+                    - y1[1] * x2[15]
+                    + 0x7FFF * q3[1]
+                    + 0xFFFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFF68
+            }
+            17 => {
+                x3[15] * u[2]
+                    + x3[14] * u[3]
+                    + x3[13] * u[4]
+                    + x3[12] * u[5]
+                    + x3[11] * u[6]
+                    + x3[10] * u[7]
+                    + x3[9] * u[8]
+                    + x3[8] * u[9]
+                    + x3[7] * u[10]
+                    + x3[6] * u[11]
+                    + x3[5] * u[12]
+                    + x3[4] * u[13]
+                    + x3[3] * u[14]
+                    + x3[2] * u[15]
+                    - x1[15] * y2[2]
+                    - x1[14] * y2[3]
+                    - x1[13] * y2[4]
+                    - x1[12] * y2[5]
+                    - x1[11] * y2[6]
+                    - x1[10] * y2[7]
+                    - x1[9] * y2[8]
+                    - x1[8] * y2[9]
+                    - x1[7] * y2[10]
+                    - x1[6] * y2[11]
+                    - x1[5] * y2[12]
+                    - x1[4] * y2[13]
+                    - x1[3] * y2[14]
+                    - x1[2] * y2[15]
+                    - y1[15] * x2[2]
+                    - y1[14] * x2[3]
+                    - y1[13] * x2[4]
+                    - y1[12] * x2[5]
+                    - y1[11] * x2[6]
+                    - y1[10] * x2[7]
+                    - y1[9] * x2[8]
+                    - y1[8] * x2[9]
+                    - y1[7] * x2[10]
+                    - y1[6] * x2[11]
+                    - y1[5] * x2[12]
+                    - y1[4] * x2[13]
+                    - y1[3] * x2[14]
+                    - y1[2] * x2[15]
+                    + 0x7FFF * q3[2]
+                    + 0xFFFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            18 => {
+                x3[15] * u[3]
+                    + x3[14] * u[4]
+                    + x3[13] * u[5]
+                    + x3[12] * u[6]
+                    + x3[11] * u[7]
+                    + x3[10] * u[8]
+                    + x3[9] * u[9]
+                    + x3[8] * u[10]
+                    + x3[7] * u[11]
+                    + x3[6] * u[12]
+                    + x3[5] * u[13]
+                    + x3[4] * u[14]
+                    + x3[3] * u[15]
+                    - x1[15] * y2[3]
+                    - x1[14] * y2[4]
+                    - x1[13] * y2[5]
+                    - x1[12] * y2[6]
+                    - x1[11] * y2[7]
+                    - x1[10] * y2[8]
+                    - x1[9] * y2[9]
+                    - x1[8] * y2[10]
+                    - x1[7] * y2[11]
+                    - x1[6] * y2[12]
+                    - x1[5] * y2[13]
+                    - x1[4] * y2[14]
+                    - x1[3] * y2[15]
+                    - y1[15] * x2[3]
+                    - y1[14] * x2[4]
+                    - y1[13] * x2[5]
+                    - y1[12] * x2[6]
+                    - y1[11] * x2[7]
+                    - y1[10] * x2[8]
+                    - y1[9] * x2[9]
+                    - y1[8] * x2[10]
+                    - y1[7] * x2[11]
+                    - y1[6] * x2[12]
+                    - y1[5] * x2[13]
+                    - y1[4] * x2[14]
+                    - y1[3] * x2[15]
+                    + 0x7FFF * q3[3]
+                    + 0xFFFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            19 => {
+                x3[15] * u[4]
+                    + x3[14] * u[5]
+                    + x3[13] * u[6]
+                    + x3[12] * u[7]
+                    + x3[11] * u[8]
+                    + x3[10] * u[9]
+                    + x3[9] * u[10]
+                    + x3[8] * u[11]
+                    + x3[7] * u[12]
+                    + x3[6] * u[13]
+                    + x3[5] * u[14]
+                    + x3[4] * u[15]
+                    - x1[15] * y2[4]
+                    - x1[14] * y2[5]
+                    - x1[13] * y2[6]
+                    - x1[12] * y2[7]
+                    - x1[11] * y2[8]
+                    - x1[10] * y2[9]
+                    - x1[9] * y2[10]
+                    - x1[8] * y2[11]
+                    - x1[7] * y2[12]
+                    - x1[6] * y2[13]
+                    - x1[5] * y2[14]
+                    - x1[4] * y2[15]
+                    - y1[15] * x2[4]
+                    - y1[14] * x2[5]
+                    - y1[13] * x2[6]
+                    - y1[12] * x2[7]
+                    - y1[11] * x2[8]
+                    - y1[10] * x2[9]
+                    - y1[9] * x2[10]
+                    - y1[8] * x2[11]
+                    - y1[7] * x2[12]
+                    - y1[6] * x2[13]
+                    - y1[5] * x2[14]
+                    - y1[4] * x2[15]
+                    + 0x7FFF * q3[4]
+                    + 0xFFFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            20 => {
+                x3[15] * u[5]
+                    + x3[14] * u[6]
+                    + x3[13] * u[7]
+                    + x3[12] * u[8]
+                    + x3[11] * u[9]
+                    + x3[10] * u[10]
+                    + x3[9] * u[11]
+                    + x3[8] * u[12]
+                    + x3[7] * u[13]
+                    + x3[6] * u[14]
+                    + x3[5] * u[15]
+                    - x1[15] * y2[5]
+                    - x1[14] * y2[6]
+                    - x1[13] * y2[7]
+                    - x1[12] * y2[8]
+                    - x1[11] * y2[9]
+                    - x1[10] * y2[10]
+                    - x1[9] * y2[11]
+                    - x1[8] * y2[12]
+                    - x1[7] * y2[13]
+                    - x1[6] * y2[14]
+                    - x1[5] * y2[15]
+                    - y1[15] * x2[5]
+                    - y1[14] * x2[6]
+                    - y1[13] * x2[7]
+                    - y1[12] * x2[8]
+                    - y1[11] * x2[9]
+                    - y1[10] * x2[10]
+                    - y1[9] * x2[11]
+                    - y1[8] * x2[12]
+                    - y1[7] * x2[13]
+                    - y1[6] * x2[14]
+                    - y1[5] * x2[15]
+                    + 0x7FFF * q3[5]
+                    + 0xFFFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            21 => {
+                x3[15] * u[6]
+                    + x3[14] * u[7]
+                    + x3[13] * u[8]
+                    + x3[12] * u[9]
+                    + x3[11] * u[10]
+                    + x3[10] * u[11]
+                    + x3[9] * u[12]
+                    + x3[8] * u[13]
+                    + x3[7] * u[14]
+                    + x3[6] * u[15]
+                    - x1[15] * y2[6]
+                    - x1[14] * y2[7]
+                    - x1[13] * y2[8]
+                    - x1[12] * y2[9]
+                    - x1[11] * y2[10]
+                    - x1[10] * y2[11]
+                    - x1[9] * y2[12]
+                    - x1[8] * y2[13]
+                    - x1[7] * y2[14]
+                    - x1[6] * y2[15]
+                    - y1[15] * x2[6]
+                    - y1[14] * x2[7]
+                    - y1[13] * x2[8]
+                    - y1[12] * x2[9]
+                    - y1[11] * x2[10]
+                    - y1[10] * x2[11]
+                    - y1[9] * x2[12]
+                    - y1[8] * x2[13]
+                    - y1[7] * x2[14]
+                    - y1[6] * x2[15]
+                    + 0x7FFF * q3[6]
+                    + 0xFFFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            22 => {
+                x3[15] * u[7]
+                    + x3[14] * u[8]
+                    + x3[13] * u[9]
+                    + x3[12] * u[10]
+                    + x3[11] * u[11]
+                    + x3[10] * u[12]
+                    + x3[9] * u[13]
+                    + x3[8] * u[14]
+                    + x3[7] * u[15]
+                    - x1[15] * y2[7]
+                    - x1[14] * y2[8]
+                    - x1[13] * y2[9]
+                    - x1[12] * y2[10]
+                    - x1[11] * y2[11]
+                    - x1[10] * y2[12]
+                    - x1[9] * y2[13]
+                    - x1[8] * y2[14]
+                    - x1[7] * y2[15]
+                    - y1[15] * x2[7]
+                    - y1[14] * x2[8]
+                    - y1[13] * x2[9]
+                    - y1[12] * x2[10]
+                    - y1[11] * x2[11]
+                    - y1[10] * x2[12]
+                    - y1[9] * x2[13]
+                    - y1[8] * x2[14]
+                    - y1[7] * x2[15]
+                    + 0x7FFF * q3[7]
+                    + 0xFFFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            23 => {
+                x3[15] * u[8]
+                    + x3[14] * u[9]
+                    + x3[13] * u[10]
+                    + x3[12] * u[11]
+                    + x3[11] * u[12]
+                    + x3[10] * u[13]
+                    + x3[9] * u[14]
+                    + x3[8] * u[15]
+                    - x1[15] * y2[8]
+                    - x1[14] * y2[9]
+                    - x1[13] * y2[10]
+                    - x1[12] * y2[11]
+                    - x1[11] * y2[12]
+                    - x1[10] * y2[13]
+                    - x1[9] * y2[14]
+                    - x1[8] * y2[15]
+                    - y1[15] * x2[8]
+                    - y1[14] * x2[9]
+                    - y1[13] * x2[10]
+                    - y1[12] * x2[11]
+                    - y1[11] * x2[12]
+                    - y1[10] * x2[13]
+                    - y1[9] * x2[14]
+                    - y1[8] * x2[15]
+                    + 0x7FFF * q3[8]
+                    + 0xFFFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            24 => {
+                x3[15] * u[9]
+                    + x3[14] * u[10]
+                    + x3[13] * u[11]
+                    + x3[12] * u[12]
+                    + x3[11] * u[13]
+                    + x3[10] * u[14]
+                    + x3[9] * u[15]
+                    - x1[15] * y2[9]
+                    - x1[14] * y2[10]
+                    - x1[13] * y2[11]
+                    - x1[12] * y2[12]
+                    - x1[11] * y2[13]
+                    - x1[10] * y2[14]
+                    - x1[9] * y2[15]
+                    - y1[15] * x2[9]
+                    - y1[14] * x2[10]
+                    - y1[13] * x2[11]
+                    - y1[12] * x2[12]
+                    - y1[11] * x2[13]
+                    - y1[10] * x2[14]
+                    - y1[9] * x2[15]
+                    + 0x7FFF * q3[9]
+                    + 0xFFFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            25 => {
+                x3[15] * u[10]
+                    + x3[14] * u[11]
+                    + x3[13] * u[12]
+                    + x3[12] * u[13]
+                    + x3[11] * u[14]
+                    + x3[10] * u[15]
+                    - x1[15] * y2[10]
+                    - x1[14] * y2[11]
+                    - x1[13] * y2[12]
+                    - x1[12] * y2[13]
+                    - x1[11] * y2[14]
+                    - x1[10] * y2[15]
+                    - y1[15] * x2[10]
+                    - y1[14] * x2[11]
+                    - y1[13] * x2[12]
+                    - y1[12] * x2[13]
+                    - y1[11] * x2[14]
+                    - y1[10] * x2[15]
+                    + 0x7FFF * q3[10]
+                    + 0xFFFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            26 => {
+                x3[15] * u[11] + x3[14] * u[12] + x3[13] * u[13] + x3[12] * u[14] + x3[11] * u[15]
+                    - x1[15] * y2[11]
+                    - x1[14] * y2[12]
+                    - x1[13] * y2[13]
+                    - x1[12] * y2[14]
+                    - x1[11] * y2[15]
+                    - y1[15] * x2[11]
+                    - y1[14] * x2[12]
+                    - y1[13] * x2[13]
+                    - y1[12] * x2[14]
+                    - y1[11] * x2[15]
+                    + 0x7FFF * q3[11]
+                    + 0xFFFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            27 => {
+                x3[15] * u[12] + x3[14] * u[13] + x3[13] * u[14] + x3[12] * u[15]
+                    - x1[15] * y2[12]
+                    - x1[14] * y2[13]
+                    - x1[13] * y2[14]
+                    - x1[12] * y2[15]
+                    - y1[15] * x2[12]
+                    - y1[14] * x2[13]
+                    - y1[13] * x2[14]
+                    - y1[12] * x2[15]
+                    + 0x7FFF * q3[12]
+                    + 0xFFFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            28 => {
+                x3[15] * u[13] + x3[14] * u[14] + x3[13] * u[15]
+                    - x1[15] * y2[13]
+                    - x1[14] * y2[14]
+                    - x1[13] * y2[15]
+                    - y1[15] * x2[13]
+                    - y1[14] * x2[14]
+                    - y1[13] * x2[15]
+                    + 0x7FFF * q3[13]
+                    + 0xFFFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            29 => {
+                x3[15] * u[14] + x3[14] * u[15]
+                    - x1[15] * y2[14]
+                    - x1[14] * y2[15]
+                    - y1[15] * x2[14]
+                    - y1[14] * x2[15]
+                    + 0x7FFF * q3[14]
+                    + 0xFFFF * q3[15]
+                    - 0xFFFF
+            }
+            30 => x3[15] * u[15] - x1[15] * y2[15] - y1[15] * x2[15] + 0x7FFF * q3[15] - 0xFFFF,
+            31 => -0x3FFFF,
+            _ => 0,
+        }
+    }
+}
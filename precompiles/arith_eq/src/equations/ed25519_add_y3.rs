@@ -0,0 +1,1137 @@
+// code generated
+//
+// equation: y3-y3*u-y1*y2-x1*x2+p*q4-p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// offset: 0x80000000000000000000000000000000000000000000000000000000000000000
+// (p*offset): 0x3FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF680000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519AddY3 {}
+
+impl Ed25519AddY3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        x2: &[i64; 16],
+        y2: &[i64; 16],
+        y3: &[i64; 16],
+        u: &[i64; 16],
+        q4: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => y3[0] - y3[0] * u[0] - y1[0] * y2[0] - x1[0] * x2[0] + 0xFFED * q4[0],
+            1 => {
+                y3[1]
+                    - y3[1] * u[0]
+                    - y3[0] * u[1]
+                    - y1[1] * y2[0]
+                    - y1[0] * y2[1]
+                    - x1[1] * x2[0]
+                    - x1[0] * x2[1]
+                    + 0xFFFF * q4[0]
+                    + 0xFFED * q4[1]
+            }
+            2 => {
+                y3[2]
+                    - y3[2] * u[0]
+                    - y3[1] * u[1]
+                    - y3[0] * u[2]
+                    - y1[2] * y2[0]
+                    - y1[1] * y2[1]
+                    - y1[0] * y2[2]
+                    - x1[2] * x2[0]
+                    - x1[1] * x2[1]
+                    - x1[0] * x2[2]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFED * q4[2]
+            }
+            3 => {
+                y3[3]
+                    - y3[3] * u[0]
+                    - y3[2] * u[1]
+                    - y3[1] * u[2]
+                    - y3[0] * u[3]
+                    - y1[3] * y2[0]
+                    - y1[2] * y2[1]
+                    - y1[1] * y2[2]
+                    - y1[0] * y2[3]
+                    - x1[3] * x2[0]
+                    - x1[2] * x2[1]
+                    - x1[1] * x2[2]
+                    - x1[0] * x2[3]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFED * q4[3]
+            }
+            4 => {
+                y3[4]
+                    - y3[4] * u[0]
+                    - y3[3] * u[1]
+                    - y3[2] * u[2]
+                    - y3[1] * u[3]
+                    - y3[0] * u[4]
+                    - y1[4] * y2[0]
+                    - y1[3] * y2[1]
+                    - y1[2] * y2[2]
+                    - y1[1] * y2[3]
+                    - y1[0] * y2[4]
+                    - x1[4] * x2[0]
+                    - x1[3] * x2[1]
+                    - x1[2] * x2[2]
+                    - x1[1] * x2[3]
+                    - x1[0] * x2[4]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFED * q4[4]
+            }
+            5 => {
+                y3[5]
+                    - y3[5] * u[0]
+                    - y3[4] * u[1]
+                    - y3[3] * u[2]
+                    - y3[2] * u[3]
+                    - y3[1] * u[4]
+                    - y3[0] * u[5]
+                    - y1[5] * y2[0]
+                    - y1[4] * y2[1]
+                    - y1[3] * y2[2]
+                    - y1[2] * y2[3]
+                    - y1[1] * y2[4]
+                    - y1[0] * y2[5]
+                    - x1[5] * x2[0]
+                    - x1[4] * x2[1]
+                    - x1[3] * x2[2]
+                    - x1[2] * x2[3]
+                    - x1[1] * x2[4]
+                    - x1[0] * x2[5]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFED * q4[5]
+            }
+            6 => {
+                y3[6]
+                    - y3[6] * u[0]
+                    - y3[5] * u[1]
+                    - y3[4] * u[2]
+                    - y3[3] * u[3]
+                    - y3[2] * u[4]
+                    - y3[1] * u[5]
+                    - y3[0] * u[6]
+                    - y1[6] * y2[0]
+                    - y1[5] * y2[1]
+                    - y1[4] * y2[2]
+                    - y1[3] * y2[3]
+                    - y1[2] * y2[4]
+                    - y1[1] * y2[5]
+                    - y1[0] * y2[6]
+                    - x1[6] * x2[0]
+                    - x1[5] * x2[1]
+                    - x1[4] * x2[2]
+                    - x1[3] * x2[3]
+                    - x1[2] * x2[4]
+                    - x1[1] * x2[5]
+                    - x1[0] * x2[6]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFED * q4[6]
+            }
+            7 => {
+                y3[7]
+                    - y3[7] * u[0]
+                    - y3[6] * u[1]
+                    - y3[5] * u[2]
+                    - y3[4] * u[3]
+                    - y3[3] * u[4]
+                    - y3[2] * u[5]
+                    - y3[1] * u[6]
+                    - y3[0] * u[7]
+                    - y1[7] * y2[0]
+                    - y1[6] * y2[1]
+                    - y1[5] * y2[2]
+                    - y1[4] * y2[3]
+                    - y1[3] * y2[4]
+                    - y1[2] * y2[5]
+                    - y1[1] * y2[6]
+                    - y1[0] * y2[7]
+                    - x1[7] * x2[0]
+                    - x1[6] * x2[1]
+                    - x1[5] * x2[2]
+                    - x1[4] * x2[3]
+                    - x1[3] * x2[4]
+                    - x1[2] * x2[5]
+                    - x1[1] * x2[6]
+                    - x1[0] * x2[7]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFED * q4[7]
+            }
+            8 => {
+                y3[8]
+                    - y3[8] * u[0]
+                    - y3[7] * u[1]
+                    - y3[6] * u[2]
+                    - y3[5] * u[3]
+                    - y3[4] * u[4]
+                    - y3[3] * u[5]
+                    - y3[2] * u[6]
+                    - y3[1] * u[7]
+                    - y3[0] * u[8]
+                    - y1[8] * y2[0]
+                    - y1[7] * y2[1]
+                    - y1[6] * y2[2]
+                    - y1[5] * y2[3]
+                    - y1[4] * y2[4]
+                    - y1[3] * y2[5]
+                    - y1[2] * y2[6]
+                    - y1[1] * y2[7]
+                    - y1[0] * y2[8]
+                    - x1[8] * x2[0]
+                    - x1[7] * x2[1]
+                    - x1[6] * x2[2]
+                    - x1[5] * x2[3]
+                    - x1[4] * x2[4]
+                    - x1[3] * x2[5]
+                    - x1[2] * x2[6]
+                    - x1[1] * x2[7]
+                    - x1[0] * x2[8]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFED * q4[8]
+            }
+            9 => {
+                y3[9]
+                    - y3[9] * u[0]
+                    - y3[8] * u[1]
+                    - y3[7] * u[2]
+                    - y3[6] * u[3]
+                    - y3[5] * u[4]
+                    - y3[4] * u[5]
+                    - y3[3] * u[6]
+                    - y3[2] * u[7]
+                    - y3[1] * u[8]
+                    - y3[0] * u[9]
+                    - y1[9] * y2[0]
+                    - y1[8] * y2[1]
+                    - y1[7] * y2[2]
+                    - y1[6] * y2[3]
+                    - y1[5] * y2[4]
+                    - y1[4] * y2[5]
+                    - y1[3] * y2[6]
+                    - y1[2] * y2[7]
+                    - y1[1] * y2[8]
+                    - y1[0] * y2[9]
+                    - x1[9] * x2[0]
+                    - x1[8] * x2[1]
+                    - x1[7] * x2[2]
+                    - x1[6] * x2[3]
+                    - x1[5] * x2[4]
+                    - x1[4] * x2[5]
+                    - x1[3] * x2[6]
+                    - x1[2] * x2[7]
+                    - x1[1] * x2[8]
+                    - x1[0] * x2[9]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFED * q4[9]
+            }
+            10 => {
+                y3[10]
+                    - y3[10] * u[0]
+                    - y3[9] * u[1]
+                    - y3[8] * u[2]
+                    - y3[7] * u[3]
+                    - y3[6] * u[4]
+                    - y3[5] * u[5]
+                    - y3[4] * u[6]
+                    - y3[3] * u[7]
+                    - y3[2] * u[8]
+                    - y3[1] * u[9]
+                    - y3[0] * u[10]
+                    - y1[10] * y2[0]
+                    - y1[9] * y2[1]
+                    - y1[8] * y2[2]
+                    - y1[7] * y2[3]
+                    - y1[6] * y2[4]
+                    - y1[5] * y2[5]
+                    - y1[4] * y2[6]
+                    - y1[3] * y2[7]
+                    - y1[2] * y2[8]
+                    - y1[1] * y2[9]
+                    - y1[0] * y2[10]
+                    - x1[10] * x2[0]
+                    - x1[9] * x2[1]
+                    - x1[8] * x2[2]
+                    - x1[7] * x2[3]
+                    - x1[6] * x2[4]
+                    - x1[5] * x2[5]
+                    - x1[4] * x2[6]
+                    - x1[3] * x2[7]
+                    - x1[2] * x2[8]
+                    - x1[1] * x2[9]
+                    - x1[0] * x2[10]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFED * q4[10]
+            }
+            11 => {
+                y3[11]
+                    - y3[11] * u[0]
+                    - y3[10] * u[1]
+                    - y3[9] * u[2]
+                    - y3[8] * u[3]
+                    - y3[7] * u[4]
+                    - y3[6] * u[5]
+                    - y3[5] * u[6]
+                    - y3[4] * u[7]
+                    - y3[3] * u[8]
+                    - y3[2] * u[9]
+                    - y3[1] * u[10]
+                    - y3[0] * u[11]
+                    - y1[11] * y2[0]
+                    - y1[10] * y2[1]
+                    - y1[9] * y2[2]
+                    - y1[8] * y2[3]
+                    - y1[7] * y2[4]
+                    - y1[6] * y2[5]
+                    - y1[5] * y2[6]
+                    - y1[4] * y2[7]
+                    - y1[3] * y2[8]
+                    - y1[2] * y2[9]
+                    - y1[1] * y2[10]
+                    - y1[0] * y2[11]
+                    - x1[11] * x2[0]
+                    - x1[10] * x2[1]
+                    - x1[9] * x2[2]
+                    - x1[8] * x2[3]
+                    - x1[7] * x2[4]
+                    - x1[6] * x2[5]
+                    - x1[5] * x2[6]
+                    - x1[4] * x2[7]
+                    - x1[3] * x2[8]
+                    - x1[2] * x2[9]
+                    - x1[1] * x2[10]
+                    - x1[0] * x2[11]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFED * q4[11]
+            }
+            12 => {
+                y3[12]
+                    - y3[12] * u[0]
+                    - y3[11] * u[1]
+                    - y3[10] * u[2]
+                    - y3[9] * u[3]
+                    - y3[8] * u[4]
+                    - y3[7] * u[5]
+                    - y3[6] * u[6]
+                    - y3[5] * u[7]
+                    - y3[4] * u[8]
+                    - y3[3] * u[9]
+                    - y3[2] * u[10]
+                    - y3[1] * u[11]
+                    - y3[0] * u[12]
+                    - y1[12] * y2[0]
+                    - y1[11] * y2[1]
+                    - y1[10] * y2[2]
+                    - y1[9] * y2[3]
+                    - y1[8] * y2[4]
+                    - y1[7] * y2[5]
+                    - y1[6] * y2[6]
+                    - y1[5] * y2[7]
+                    - y1[4] * y2[8]
+                    - y1[3] * y2[9]
+                    - y1[2] * y2[10]
+                    - y1[1] * y2[11]
+                    - y1[0] * y2[12]
+                    - x1[12] * x2[0]
+                    - x1[11] * x2[1]
+                    - x1[10] * x2[2]
+                    - x1[9] * x2[3]
+                    - x1[8] * x2[4]
+                    - x1[7] * x2[5]
+                    - x1[6] * x2[6]
+                    - x1[5] * x2[7]
+                    - x1[4] * x2[8]
+                    - x1[3] * x2[9]
+                    - x1[2] * x2[10]
+                    - x1[1] * x2[11]
+                    - x1[0] * x2[12]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFED * q4[12]
+            }
+            13 => {
+                y3[13]
+                    - y3[13] * u[0]
+                    - y3[12] * u[1]
+                    - y3[11] * u[2]
+                    - y3[10] * u[3]
+                    - y3[9] * u[4]
+                    - y3[8] * u[5]
+                    - y3[7] * u[6]
+                    - y3[6] * u[7]
+                    - y3[5] * u[8]
+                    - y3[4] * u[9]
+                    - y3[3] * u[10]
+                    - y3[2] * u[11]
+                    - y3[1] * u[12]
+                    - y3[0] * u[13]
+                    - y1[13] * y2[0]
+                    - y1[12] * y2[1]
+                    - y1[11] * y2[2]
+                    - y1[10] * y2[3]
+                    - y1[9] * y2[4]
+                    - y1[8] * y2[5]
+                    - y1[7] * y2[6]
+                    - y1[6] * y2[7]
+                    - y1[5] * y2[8]
+                    - y1[4] * y2[9]
+                    - y1[3] * y2[10]
+                    - y1[2] * y2[11]
+                    - y1[1] * y2[12]
+                    - y1[0] * y2[13]
+                    - x1[13] * x2[0]
+                    - x1[12] * x2[1]
+                    - x1[11] * x2[2]
+                    - x1[10] * x2[3]
+                    - x1[9] * x2[4]
+                    - x1[8] * x2[5]
+                    - x1[7] * x2[6]
+                    - x1[6] * x2[7]
+                    - x1[5] * x2[8]
+                    - x1[4] * x2[9]
+                    - x1[3] * x2[10]
+                    - x1[2] * x2[11]
+                    - x1[1] * x2[12]
+                    - x1[0] * x2[13]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFED * q4[13]
+            }
+            14 => {
+                y3[14]
+                    - y3[14] * u[0]
+                    - y3[13] * u[1]
+                    - y3[12] * u[2]
+                    - y3[11] * u[3]
+                    - y3[10] * u[4]
+                    - y3[9] * u[5]
+                    - y3[8] * u[6]
+                    - y3[7] * u[7]
+                    - y3[6] * u[8]
+                    - y3[5] * u[9]
+                    - y3[4] * u[10]
+                    - y3[3] * u[11]
+                    - y3[2] * u[12]
+                    - y3[1] * u[13]
+                    - y3[0] * u[14]
+                    - y1[14] * y2[0]
+                    - y1[13] * y2[1]
+                    - y1[12] * y2[2]
+                    - y1[11] * y2[3]
+                    - y1[10] * y2[4]
+                    - y1[9] * y2[5]
+                    - y1[8] * y2[6]
+                    - y1[7] * y2[7]
+                    - y1[6] * y2[8]
+                    - y1[5] * y2[9]
+                    - y1[4] * y2[10]
+                    - y1[3] * y2[11]
+                    - y1[2] * y2[12]
+                    - y1[1] * y2[13]
+                    - y1[0] * y2[14]
+                    - x1[14] * x2[0]
+                    - x1[13] * x2[1]
+                    - x1[12] * x2[2]
+                    - x1[11] * x2[3]
+                    - x1[10] * x2[4]
+                    - x1[9] * x2[5]
+                    - x1[8] * x2[6]
+                    - x1[7] * x2[7]
+                    - x1[6] * x2[8]
+                    - x1[5] * x2[9]
+                    - x1[4] * x2[10]
+                    - x1[3] * x2[11]
+                    - x1[2] * x2[12]
+                    - x1[1] * x2[13]
+                    - x1[0] * x2[14]
+                    + 0xFFFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFED * q4[14]
+            }
+            15 => {
+                y3[15]
+                    - y3[15] * u[0]
+                    - y3[14] * u[1]
+                    - y3[13] * u[2]
+                    - y3[12] * u[3]
+                    - y3[11] * u[4]
+                    - y3[10] * u[5]
+                    - y3[9] * u[6]
+                    - y3[8] * u[7]
+                    - y3[7] * u[8]
+                    - y3[6] * u[9]
+                    - y3[5] * u[10]
+                    - y3[4] * u[11]
+                    - y3[3] * u[12]
+                    - y3[2] * u[13]
+                    - y3[1] * u[14]
+                    - y3[0] * u[15]
+                    - y1[15] * y2[0]
+                    - y1[14] * y2[1]
+                    - y1[13] * y2[2]
+                    - y1[12] * y2[3]
+                    - y1[11] * y2[4]
+                    - y1[10] * y2[5]
+                    - y1[9] * y2[6]
+                    - y1[8] * y2[7]
+                    - y1[7] * y2[8]
+                    - y1[6] * y2[9]
+                    - y1[5] * y2[10]
+                    - y1[4] * y2[11]
+                    - y1[3] * y2[12]
+                    - y1[2] * y2[13]
+                    - y1[1] * y2[14]
+                    - y1[0] * y2[15]
+                    - x1[15] * x2[0]
+                    - x1[14] * x2[1]
+                    - x1[13] * x2[2]
+                    - x1[12] * x2[3]
+                    - x1[11] * x2[4]
+                    - x1[10] * x2[5]
+                    - x1[9] * x2[6]
+                    - x1[8] * x2[7]
+                    - x1[7] * x2[8]
+                    - x1[6] * x2[9]
+                    - x1[5] * x2[10]
+                    - x1[4] * x2[11]
+                    - x1[3] * x2[12]
+                    - x1[2] * x2[13]
+                    - x1[1] * x2[14]
+                    - x1[0] * x2[15]
+                    + 0x7FFF * q4[0]
+                    + 0xFFFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFED * q4[15]
+            }
+            16 => {
+                -y3[15] * u[1]
+                    - y3[14] * u[2]
+                    - y3[13] * u[3]
+                    - y3[12] * u[4]
+                    - y3[11] * u[5]
+                    - y3[10] * u[6]
+                    - y3[9] * u[7]
+                    - y3[8] * u[8]
+                    - y3[7] * u[9]
+                    - y3[6] * u[10]
+                    - y3[5] * u[11]
+                    - y3[4] * u[12]
+                    - y3[3] * u[13]
+                    - y3[2] * u[14]
+                    - y3[1] * u[15]
+                    - y1[15] * y2[1]
+                    - y1[14] * y2[2]
+                    - y1[13] * y2[3]
+                    - y1[12] * y2[4]
+                    - y1[11] * y2[5]
+                    - y1[10] * y2[6]
+                    - y1[9] * y2[7]
+                    - y1[8] * y2[8]
+                    - y1[7] * y2[9]
+                    - y1[6] * y2[10]
+                    - y1[5] * y2[11]
+                    - y1[4] * y2[12]
+                    - y1[3] * y2[13]
+                    - y1[2] * y2[14]
+                    - y1[1] * y2[15]
+                    - x1[15] * x2[1]
+                    - x1[14] * x2[2]
+                    - x1[13] * x2[3]
+                    - x1[12] * x2[4]
+                    - x1[11] * x2[5]
+                    - x1[10] * x2[6]
+                    - x1[9] * x2[7]
+                    - x1[8] * x2[8]
+                    - x1[7] * x2[9]
+                    - x1[6] * x2[10]
+                    - x1[5] * x2[11]
+                    - x1[4] * x2[12]
+                    - x1[3] * x2[13]
+                    - x1[2] * x2[14]
+                    - x1[1] * x2[15]
+                    + 0x7FFF * q4[1]
+                    + 0xFFFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFF68
+            }
+            17 => {
+                -y3[15] * u[2]
+                    - y3[14] * u[3]
+                    - y3[13] * u[4]
+                    - y3[12] * u[5]
+                    - y3[11] * u[6]
+                    - y3[10] * u[7]
+                    - y3[9] * u[8]
+                    - y3[8] * u[9]
+                    - y3[7] * u[10]
+                    - y3[6] * u[11]
+                    - y3[5] * u[12]
+                    - y3[4] * u[13]
+                    - y3[3] * u[14]
+                    - y3[2] * u[15]
+                    - y1[15] * y2[2]
+                    - y1[14] * y2[3]
+                    - y1[13] * y2[4]
+                    - y1[12] * y2[5]
+                    - y1[11] * y2[6]
+                    - y1[10] * y2[7]
+                    - y1[9] * y2[8]
+                    - y1[8] * y2[9]
+                    - y1[7] * y2[10]
+                    - y1[6] * y2[11]
+                    - y1[5] * y2[12]
+                    - y1[4] * y2[13]
+                    - y1[3] * y2[14]
+                    - y1[2] * y2[15]
+                    - x1[15] * x2[2]
+                    - x1[14] * x2[3]
+                    - x1[13] * x2[4]
+                    - x1[12] * x2[5]
+                    - x1[11] * x2[6]
+                    - x1[10] * x2[7]
+                    - x1[9] * x2[8]
+                    - x1[8] * x2[9]
+                    - x1[7] * x2[10]
+                    - x1[6] * x2[11]
+                    - x1[5] * x2[12]
+                    - x1[4] * x2[13]
+                    - x1[3] * x2[14]
+                    - x1[2] * x2[15]
+                    + 0x7FFF * q4[2]
+                    + 0xFFFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            18 => {
+                -y3[15] * u[3]
+                    - y3[14] * u[4]
+                    - y3[13] * u[5]
+                    - y3[12] * u[6]
+                    - y3[11] * u[7]
+                    - y3[10] * u[8]
+                    - y3[9] * u[9]
+                    - y3[8] * u[10]
+                    - y3[7] * u[11]
+                    - y3[6] * u[12]
+                    - y3[5] * u[13]
+                    - y3[4] * u[14]
+                    - y3[3] * u[15]
+                    - y1[15] * y2[3]
+                    - y1[14] * y2[4]
+                    - y1[13] * y2[5]
+                    - y1[12] * y2[6]
+                    - y1[11] * y2[7]
+                    - y1[10] * y2[8]
+                    - y1[9] * y2[9]
+                    - y1[8] * y2[10]
+                    - y1[7] * y2[11]
+                    - y1[6] * y2[12]
+                    - y1[5] * y2[13]
+                    - y1[4] * y2[14]
+                    - y1[3] * y2[15]
+                    - x1[15] * x2[3]
+                    - x1[14] * x2[4]
+                    - x1[13] * x2[5]
+                    - x1[12] * x2[6]
+                    - x1[11] * x2[7]
+                    - x1[10] * x2[8]
+                    - x1[9] * x2[9]
+                    - x1[8] * x2[10]
+                    - x1[7] * x2[11]
+                    - x1[6] * x2[12]
+                    - x1[5] * x2[13]
+                    - x1[4] * x2[14]
+                    - x1[3] * x2[15]
+                    + 0x7FFF * q4[3]
+                    + 0xFFFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            19 => {
+                -y3[15] * u[4]
+                    - y3[14] * u[5]
+                    - y3[13] * u[6]
+                    - y3[12] * u[7]
+                    - y3[11] * u[8]
+                    - y3[10] * u[9]
+                    - y3[9] * u[10]
+                    - y3[8] * u[11]
+                    - y3[7] * u[12]
+                    - y3[6] * u[13]
+                    - y3[5] * u[14]
+                    - y3[4] * u[15]
+                    - y1[15] * y2[4]
+                    - y1[14] * y2[5]
+                    - y1[13] * y2[6]
+                    - y1[12] * y2[7]
+                    - y1[11] * y2[8]
+                    - y1[10] * y2[9]
+                    - y1[9] * y2[10]
+                    - y1[8] * y2[11]
+                    - y1[7] * y2[12]
+                    - y1[6] * y2[13]
+                    - y1[5] * y2[14]
+                    - y1[4] * y2[15]
+                    - x1[15] * x2[4]
+                    - x1[14] * x2[5]
+                    - x1[13] * x2[6]
+                    - x1[12] * x2[7]
+                    - x1[11] * x2[8]
+                    - x1[10] * x2[9]
+                    - x1[9] * x2[10]
+                    - x1[8] * x2[11]
+                    - x1[7] * x2[12]
+                    - x1[6] * x2[13]
+                    - x1[5] * x2[14]
+                    - x1[4] * x2[15]
+                    + 0x7FFF * q4[4]
+                    + 0xFFFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            20 => {
+                -y3[15] * u[5]
+                    - y3[14] * u[6]
+                    - y3[13] * u[7]
+                    - y3[12] * u[8]
+                    - y3[11] * u[9]
+                    - y3[10] * u[10]
+                    - y3[9] * u[11]
+                    - y3[8] * u[12]
+                    - y3[7] * u[13]
+                    - y3[6] * u[14]
+                    - y3[5] * u[15]
+                    - y1[15] * y2[5]
+                    - y1[14] * y2[6]
+                    - y1[13] * y2[7]
+                    - y1[12] * y2[8]
+                    - y1[11] * y2[9]
+                    - y1[10] * y2[10]
+                    - y1[9] * y2[11]
+                    - y1[8] * y2[12]
+                    - y1[7] * y2[13]
+                    - y1[6] * y2[14]
+                    - y1[5] * y2[15]
+                    - x1[15] * x2[5]
+                    - x1[14] * x2[6]
+                    - x1[13] * x2[7]
+                    - x1[12] * x2[8]
+                    - x1[11] * x2[9]
+                    - x1[10] * x2[10]
+                    - x1[9] * x2[11]
+                    - x1[8] * x2[12]
+                    - x1[7] * x2[13]
+                    - x1[6] * x2[14]
+                    - x1[5] * x2[15]
+                    + 0x7FFF * q4[5]
+                    + 0xFFFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            21 => {
+                -y3[15] * u[6]
+                    - y3[14] * u[7]
+                    - y3[13] * u[8]
+                    - y3[12] * u[9]
+                    - y3[11] * u[10]
+                    - y3[10] * u[11]
+                    - y3[9] * u[12]
+                    - y3[8] * u[13]
+                    - y3[7] * u[14]
+                    - y3[6] * u[15]
+                    - y1[15] * y2[6]
+                    - y1[14] * y2[7]
+                    - y1[13] * y2[8]
+                    - y1[12] * y2[9]
+                    - y1[11] * y2[10]
+                    - y1[10] * y2[11]
+                    - y1[9] * y2[12]
+                    - y1[8] * y2[13]
+                    - y1[7] * y2[14]
+                    - y1[6] * y2[15]
+                    - x1[15] * x2[6]
+                    - x1[14] * x2[7]
+                    - x1[13] * x2[8]
+                    - x1[12] * x2[9]
+                    - x1[11] * x2[10]
+                    - x1[10] * x2[11]
+                    - x1[9] * x2[12]
+                    - x1[8] * x2[13]
+                    - x1[7] * x2[14]
+                    - x1[6] * x2[15]
+                    + 0x7FFF * q4[6]
+                    + 0xFFFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            22 => {
+                -y3[15] * u[7]
+                    - y3[14] * u[8]
+                    - y3[13] * u[9]
+                    - y3[12] * u[10]
+                    - y3[11] * u[11]
+                    - y3[10] * u[12]
+                    - y3[9] * u[13]
+                    - y3[8] * u[14]
+                    - y3[7] * u[15]
+                    - y1[15] * y2[7]
+                    - y1[14] * y2[8]
+                    - y1[13] * y2[9]
+                    - y1[12] * y2[10]
+                    - y1[11] * y2[11]
+                    - y1[10] * y2[12]
+                    - y1[9] * y2[13]
+                    - y1[8] * y2[14]
+                    - y1[7] * y2[15]
+                    - x1[15] * x2[7]
+                    - x1[14] * x2[8]
+                    - x1[13] * x2[9]
+                    - x1[12] * x2[10]
+                    - x1[11] * x2[11]
+                    - x1[10] * x2[12]
+                    - x1[9] * x2[13]
+                    - x1[8] * x2[14]
+                    - x1[7] * x2[15]
+                    + 0x7FFF * q4[7]
+                    + 0xFFFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            23 => {
+                -y3[15] * u[8]
+                    - y3[14] * u[9]
+                    - y3[13] * u[10]
+                    - y3[12] * u[11]
+                    - y3[11] * u[12]
+                    - y3[10] * u[13]
+                    - y3[9] * u[14]
+                    - y3[8] * u[15]
+                    - y1[15] * y2[8]
+                    - y1[14] * y2[9]
+                    - y1[13] * y2[10]
+                    - y1[12] * y2[11]
+                    - y1[11] * y2[12]
+                    - y1[10] * y2[13]
+                    - y1[9] * y2[14]
+                    - y1[8] * y2[15]
+                    - x1[15] * x2[8]
+                    - x1[14] * x2[9]
+                    - x1[13] * x2[10]
+                    - x1[12] * x2[11]
+                    - x1[11] * x2[12]
+                    - x1[10] * x2[13]
+                    - x1[9] * x2[14]
+                    - x1[8] * x2[15]
+                    + 0x7FFF * q4[8]
+                    + 0xFFFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            24 => {
+                -y3[15] * u[9]
+                    - y3[14] * u[10]
+                    - y3[13] * u[11]
+                    - y3[12] * u[12]
+                    - y3[11] * u[13]
+                    - y3[10] * u[14]
+                    - y3[9] * u[15]
+                    - y1[15] * y2[9]
+                    - y1[14] * y2[10]
+                    - y1[13] * y2[11]
+                    - y1[12] * y2[12]
+                    - y1[11] * y2[13]
+                    - y1[10] * y2[14]
+                    - y1[9] * y2[15]
+                    - x1[15] * x2[9]
+                    - x1[14] * x2[10]
+                    - x1[13] * x2[11]
+                    - x1[12] * x2[12]
+                    - x1[11] * x2[13]
+                    - x1[10] * x2[14]
+                    - x1[9] * x2[15]
+                    + 0x7FFF * q4[9]
+                    + 0xFFFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            25 => {
+                -y3[15] * u[10]
+                    - y3[14] * u[11]
+                    - y3[13] * u[12]
+                    - y3[12] * u[13]
+                    - y3[11] * u[14]
+                    - y3[10] * u[15]
+                    - y1[15] * y2[10]
+                    - y1[14] * y2[11]
+                    - y1[13] * y2[12]
+                    - y1[12] * y2[13]
+                    - y1[11] * y2[14]
+                    - y1[10] * y2[15]
+                    - x1[15] * x2[10]
+                    - x1[14] * x2[11]
+                    - x1[13] * x2[12]
+                    - x1[12] * x2[13]
+                    - x1[11] * x2[14]
+                    - x1[10] * x2[15]
+                    + 0x7FFF * q4[10]
+                    + 0xFFFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            26 => {
+                -y3[15] * u[11]
+                    - y3[14] * u[12]
+                    - y3[13] * u[13]
+                    - y3[12] * u[14]
+                    - y3[11] * u[15]
+                    - y1[15] * y2[11]
+                    - y1[14] * y2[12]
+                    - y1[13] * y2[13]
+                    - y1[12] * y2[14]
+                    - y1[11] * y2[15]
+                    - x1[15] * x2[11]
+                    - x1[14] * x2[12]
+                    - x1[13] * x2[13]
+                    - x1[12] * x2[14]
+                    - x1[11] * x2[15]
+                    + 0x7FFF * q4[11]
+                    + 0xFFFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            27 => {
+                -y3[15] * u[12]
+                    - y3[14] * u[13]
+                    - y3[13] * u[14]
+                    - y3[12] * u[15]
+                    - y1[15] * y2[12]
+                    - y1[14] * y2[13]
+                    - y1[13] * y2[14]
+                    - y1[12] * y2[15]
+                    - x1[15] * x2[12]
+                    - x1[14] * x2[13]
+                    - x1[13] * x2[14]
+                    - x1[12] * x2[15]
+                    + 0x7FFF * q4[12]
+                    + 0xFFFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            28 => {
+                -y3[15] * u[13]
+                    - y3[14] * u[14]
+                    - y3[13] * u[15]
+                    - y1[15] * y2[13]
+                    - y1[14] * y2[14]
+                    - y1[13] * y2[15]
+                    - x1[15] * x2[13]
+                    - x1[14] * x2[14]
+                    - x1[13] * x2[15]
+                    + 0x7FFF * q4[13]
+                    + 0xFFFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            29 => {
+                -y3[15] * u[14]
+                    - y3[14] * u[15]
+                    - y1[15] * y2[14]
+                    - y1[14] * y2[15]
+                    - x1[15] * x2[14]
+                    - x1[14] * x2[15]
+                    + 0x7FFF * q4[14]
+                    + 0xFFFF * q4[15]
+                    - 0xFFFF
+            }
+            30 => -y3[15] * u[15] - y1[15] * y2[15] - x1[15] * x2[15] + 0x7FFF * q4[15] - 0xFFFF,
+            31 => -0x3FFFF,
+            _ => 0,
+        }
+    }
+}
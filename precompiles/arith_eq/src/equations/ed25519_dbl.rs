@@ -0,0 +1,859 @@
+// code generated
+//
+// equation: y1*y1-x1*x1-s+p*q0-p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// offset: 0x40000000000000000000000000000000000000000000000000000000000000000
+// (p*offset): 0x1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFB40000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519Dbl {}
+
+impl Ed25519Dbl {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        s: &[i64; 16],
+        q0: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => y1[0] * y1[0] - x1[0] * x1[0] - s[0] + 0xFFED * q0[0],
+            1 => {
+                y1[1] * y1[0] + y1[0] * y1[1] - x1[1] * x1[0] - x1[0] * x1[1] - s[1]
+                    + 0xFFFF * q0[0]
+                    + 0xFFED * q0[1]
+            }
+            2 => {
+                y1[2] * y1[0] + y1[1] * y1[1] + y1[0] * y1[2]
+                    - x1[2] * x1[0]
+                    - x1[1] * x1[1]
+                    - x1[0] * x1[2]
+                    - s[2]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFED * q0[2]
+            }
+            3 => {
+                y1[3] * y1[0] + y1[2] * y1[1] + y1[1] * y1[2] + y1[0] * y1[3]
+                    - x1[3] * x1[0]
+                    - x1[2] * x1[1]
+                    - x1[1] * x1[2]
+                    - x1[0] * x1[3]
+                    - s[3]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFED * q0[3]
+            }
+            4 => {
+                y1[4] * y1[0] + y1[3] * y1[1] + y1[2] * y1[2] + y1[1] * y1[3] + y1[0] * y1[4]
+                    - x1[4] * x1[0]
+                    - x1[3] * x1[1]
+                    - x1[2] * x1[2]
+                    - x1[1] * x1[3]
+                    - x1[0] * x1[4]
+                    - s[4]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFED * q0[4]
+            }
+            5 => {
+                y1[5] * y1[0]
+                    + y1[4] * y1[1]
+                    + y1[3] * y1[2]
+                    + y1[2] * y1[3]
+                    + y1[1] * y1[4]
+                    + y1[0] * y1[5]
+                    - x1[5] * x1[0]
+                    - x1[4] * x1[1]
+                    - x1[3] * x1[2]
+                    - x1[2] * x1[3]
+                    - x1[1] * x1[4]
+                    - x1[0] * x1[5]
+                    - s[5]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFED * q0[5]
+            }
+            6 => {
+                y1[6] * y1[0]
+                    + y1[5] * y1[1]
+                    + y1[4] * y1[2]
+                    + y1[3] * y1[3]
+                    + y1[2] * y1[4]
+                    + y1[1] * y1[5]
+                    + y1[0] * y1[6]
+                    - x1[6] * x1[0]
+                    - x1[5] * x1[1]
+                    - x1[4] * x1[2]
+                    - x1[3] * x1[3]
+                    - x1[2] * x1[4]
+                    - x1[1] * x1[5]
+                    - x1[0] * x1[6]
+                    - s[6]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFED * q0[6]
+            }
+            7 => {
+                y1[7] * y1[0]
+                    + y1[6] * y1[1]
+                    + y1[5] * y1[2]
+                    + y1[4] * y1[3]
+                    + y1[3] * y1[4]
+                    + y1[2] * y1[5]
+                    + y1[1] * y1[6]
+                    + y1[0] * y1[7]
+                    - x1[7] * x1[0]
+                    - x1[6] * x1[1]
+                    - x1[5] * x1[2]
+                    - x1[4] * x1[3]
+                    - x1[3] * x1[4]
+                    - x1[2] * x1[5]
+                    - x1[1] * x1[6]
+                    - x1[0] * x1[7]
+                    - s[7]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFED * q0[7]
+            }
+            8 => {
+                y1[8] * y1[0]
+                    + y1[7] * y1[1]
+                    + y1[6] * y1[2]
+                    + y1[5] * y1[3]
+                    + y1[4] * y1[4]
+                    + y1[3] * y1[5]
+                    + y1[2] * y1[6]
+                    + y1[1] * y1[7]
+                    + y1[0] * y1[8]
+                    - x1[8] * x1[0]
+                    - x1[7] * x1[1]
+                    - x1[6] * x1[2]
+                    - x1[5] * x1[3]
+                    - x1[4] * x1[4]
+                    - x1[3] * x1[5]
+                    - x1[2] * x1[6]
+                    - x1[1] * x1[7]
+                    - x1[0] * x1[8]
+                    - s[8]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFED * q0[8]
+            }
+            9 => {
+                y1[9] * y1[0]
+                    + y1[8] * y1[1]
+                    + y1[7] * y1[2]
+                    + y1[6] * y1[3]
+                    + y1[5] * y1[4]
+                    + y1[4] * y1[5]
+                    + y1[3] * y1[6]
+                    + y1[2] * y1[7]
+                    + y1[1] * y1[8]
+                    + y1[0] * y1[9]
+                    - x1[9] * x1[0]
+                    - x1[8] * x1[1]
+                    - x1[7] * x1[2]
+                    - x1[6] * x1[3]
+                    - x1[5] * x1[4]
+                    - x1[4] * x1[5]
+                    - x1[3] * x1[6]
+                    - x1[2] * x1[7]
+                    - x1[1] * x1[8]
+                    - x1[0] * x1[9]
+                    - s[9]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFED * q0[9]
+            }
+            10 => {
+                y1[10] * y1[0]
+                    + y1[9] * y1[1]
+                    + y1[8] * y1[2]
+                    + y1[7] * y1[3]
+                    + y1[6] * y1[4]
+                    + y1[5] * y1[5]
+                    + y1[4] * y1[6]
+                    + y1[3] * y1[7]
+                    + y1[2] * y1[8]
+                    + y1[1] * y1[9]
+                    + y1[0] * y1[10]
+                    - x1[10] * x1[0]
+                    - x1[9] * x1[1]
+                    - x1[8] * x1[2]
+                    - x1[7] * x1[3]
+                    - x1[6] * x1[4]
+                    - x1[5] * x1[5]
+                    - x1[4] * x1[6]
+                    - x1[3] * x1[7]
+                    - x1[2] * x1[8]
+                    - x1[1] * x1[9]
+                    - x1[0] * x1[10]
+                    - s[10]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFED * q0[10]
+            }
+            11 => {
+                y1[11] * y1[0]
+                    + y1[10] * y1[1]
+                    + y1[9] * y1[2]
+                    + y1[8] * y1[3]
+                    + y1[7] * y1[4]
+                    + y1[6] * y1[5]
+                    + y1[5] * y1[6]
+                    + y1[4] * y1[7]
+                    + y1[3] * y1[8]
+                    + y1[2] * y1[9]
+                    + y1[1] * y1[10]
+                    + y1[0] * y1[11]
+                    - x1[11] * x1[0]
+                    - x1[10] * x1[1]
+                    - x1[9] * x1[2]
+                    - x1[8] * x1[3]
+                    - x1[7] * x1[4]
+                    - x1[6] * x1[5]
+                    - x1[5] * x1[6]
+                    - x1[4] * x1[7]
+                    - x1[3] * x1[8]
+                    - x1[2] * x1[9]
+                    - x1[1] * x1[10]
+                    - x1[0] * x1[11]
+                    - s[11]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFED * q0[11]
+            }
+            12 => {
+                y1[12] * y1[0]
+                    + y1[11] * y1[1]
+                    + y1[10] * y1[2]
+                    + y1[9] * y1[3]
+                    + y1[8] * y1[4]
+                    + y1[7] * y1[5]
+                    + y1[6] * y1[6]
+                    + y1[5] * y1[7]
+                    + y1[4] * y1[8]
+                    + y1[3] * y1[9]
+                    + y1[2] * y1[10]
+                    + y1[1] * y1[11]
+                    + y1[0] * y1[12]
+                    - x1[12] * x1[0]
+                    - x1[11] * x1[1]
+                    - x1[10] * x1[2]
+                    - x1[9] * x1[3]
+                    - x1[8] * x1[4]
+                    - x1[7] * x1[5]
+                    - x1[6] * x1[6]
+                    - x1[5] * x1[7]
+                    - x1[4] * x1[8]
+                    - x1[3] * x1[9]
+                    - x1[2] * x1[10]
+                    - x1[1] * x1[11]
+                    - x1[0] * x1[12]
+                    - s[12]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFED * q0[12]
+            }
+            13 => {
+                y1[13] * y1[0]
+                    + y1[12] * y1[1]
+                    + y1[11] * y1[2]
+                    + y1[10] * y1[3]
+                    + y1[9] * y1[4]
+                    + y1[8] * y1[5]
+                    + y1[7] * y1[6]
+                    + y1[6] * y1[7]
+                    + y1[5] * y1[8]
+                    + y1[4] * y1[9]
+                    + y1[3] * y1[10]
+                    + y1[2] * y1[11]
+                    + y1[1] * y1[12]
+                    + y1[0] * y1[13]
+                    - x1[13] * x1[0]
+                    - x1[12] * x1[1]
+                    - x1[11] * x1[2]
+                    - x1[10] * x1[3]
+                    - x1[9] * x1[4]
+                    - x1[8] * x1[5]
+                    - x1[7] * x1[6]
+                    - x1[6] * x1[7]
+                    - x1[5] * x1[8]
+                    - x1[4] * x1[9]
+                    - x1[3] * x1[10]
+                    - x1[2] * x1[11]
+                    - x1[1] * x1[12]
+                    - x1[0] * x1[13]
+                    - s[13]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFED * q0[13]
+            }
+            14 => {
+                y1[14] * y1[0]
+                    + y1[13] * y1[1]
+                    + y1[12] * y1[2]
+                    + y1[11] * y1[3]
+                    + y1[10] * y1[4]
+                    + y1[9] * y1[5]
+                    + y1[8] * y1[6]
+                    + y1[7] * y1[7]
+                    + y1[6] * y1[8]
+                    + y1[5] * y1[9]
+                    + y1[4] * y1[10]
+                    + y1[3] * y1[11]
+                    + y1[2] * y1[12]
+                    + y1[1] * y1[13]
+                    + y1[0] * y1[14]
+                    - x1[14] * x1[0]
+                    - x1[13] * x1[1]
+                    - x1[12] * x1[2]
+                    - x1[11] * x1[3]
+                    - x1[10] * x1[4]
+                    - x1[9] * x1[5]
+                    - x1[8] * x1[6]
+                    - x1[7] * x1[7]
+                    - x1[6] * x1[8]
+                    - x1[5] * x1[9]
+                    - x1[4] * x1[10]
+                    - x1[3] * x1[11]
+                    - x1[2] * x1[12]
+                    - x1[1] * x1[13]
+                    - x1[0] * x1[14]
+                    - s[14]
+                    + 0xFFFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFED * q0[14]
+            }
+            15 => {
+                y1[15] * y1[0]
+                    + y1[14] * y1[1]
+                    + y1[13] * y1[2]
+                    + y1[12] * y1[3]
+                    + y1[11] * y1[4]
+                    + y1[10] * y1[5]
+                    + y1[9] * y1[6]
+                    + y1[8] * y1[7]
+                    + y1[7] * y1[8]
+                    + y1[6] * y1[9]
+                    + y1[5] * y1[10]
+                    + y1[4] * y1[11]
+                    + y1[3] * y1[12]
+                    + y1[2] * y1[13]
+                    + y1[1] * y1[14]
+                    + y1[0] * y1[15]
+                    - x1[15] * x1[0]
+                    - x1[14] * x1[1]
+                    - x1[13] * x1[2]
+                    - x1[12] * x1[3]
+                    - x1[11] * x1[4]
+                    - x1[10] * x1[5]
+                    - x1[9] * x1[6]
+                    - x1[8] * x1[7]
+                    - x1[7] * x1[8]
+                    - x1[6] * x1[9]
+                    - x1[5] * x1[10]
+                    - x1[4] * x1[11]
+                    - x1[3] * x1[12]
+                    - x1[2] * x1[13]
+                    - x1[1] * x1[14]
+                    - x1[0] * x1[15]
+                    - s[15]
+                    + 0x7FFF * q0[0]
+                    + 0xFFFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFED * q0[15]
+            }
+            16 => {
+                y1[15] * y1[1]
+                    + y1[14] * y1[2]
+                    + y1[13] * y1[3]
+                    + y1[12] * y1[4]
+                    + y1[11] * y1[5]
+                    + y1[10] * y1[6]
+                    + y1[9] * y1[7]
+                    + y1[8] * y1[8]
+                    + y1[7] * y1[9]
+                    + y1[6] * y1[10]
+                    + y1[5] * y1[11]
+                    + y1[4] * y1[12]
+                    + y1[3] * y1[13]
+                    + y1[2] * y1[14]
+                    + y1[1] * y1[15]
+                    - x1[15] * x1[1]
+                    - x1[14] * x1[2]
+                    - x1[13] * x1[3]
+                    - x1[12] * x1[4]
+                    - x1[11] * x1[5]
+                    - x1[10] * x1[6]
+                    - x1[9] * x1[7]
+                    - x1[8] * x1[8]
+                    - x1[7] * x1[9]
+                    - x1[6] * x1[10]
+                    - x1[5] * x1[11]
+                    - x1[4] * x1[12]
+                    - x1[3] * x1[13]
+                    - x1[2] * x1[14]
+                    - x1[1] * x1[15]
+                    + 0x7FFF * q0[1]
+                    + 0xFFFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFB4
+            }
+            17 => {
+                y1[15] * y1[2]
+                    + y1[14] * y1[3]
+                    + y1[13] * y1[4]
+                    + y1[12] * y1[5]
+                    + y1[11] * y1[6]
+                    + y1[10] * y1[7]
+                    + y1[9] * y1[8]
+                    + y1[8] * y1[9]
+                    + y1[7] * y1[10]
+                    + y1[6] * y1[11]
+                    + y1[5] * y1[12]
+                    + y1[4] * y1[13]
+                    + y1[3] * y1[14]
+                    + y1[2] * y1[15]
+                    - x1[15] * x1[2]
+                    - x1[14] * x1[3]
+                    - x1[13] * x1[4]
+                    - x1[12] * x1[5]
+                    - x1[11] * x1[6]
+                    - x1[10] * x1[7]
+                    - x1[9] * x1[8]
+                    - x1[8] * x1[9]
+                    - x1[7] * x1[10]
+                    - x1[6] * x1[11]
+                    - x1[5] * x1[12]
+                    - x1[4] * x1[13]
+                    - x1[3] * x1[14]
+                    - x1[2] * x1[15]
+                    + 0x7FFF * q0[2]
+                    + 0xFFFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            18 => {
+                y1[15] * y1[3]
+                    + y1[14] * y1[4]
+                    + y1[13] * y1[5]
+                    + y1[12] * y1[6]
+                    + y1[11] * y1[7]
+                    + y1[10] * y1[8]
+                    + y1[9] * y1[9]
+                    + y1[8] * y1[10]
+                    + y1[7] * y1[11]
+                    + y1[6] * y1[12]
+                    + y1[5] * y1[13]
+                    + y1[4] * y1[14]
+                    + y1[3] * y1[15]
+                    - x1[15] * x1[3]
+                    - x1[14] * x1[4]
+                    - x1[13] * x1[5]
+                    - x1[12] * x1[6]
+                    - x1[11] * x1[7]
+                    - x1[10] * x1[8]
+                    - x1[9] * x1[9]
+                    - x1[8] * x1[10]
+                    - x1[7] * x1[11]
+                    - x1[6] * x1[12]
+                    - x1[5] * x1[13]
+                    - x1[4] * x1[14]
+                    - x1[3] * x1[15]
+                    + 0x7FFF * q0[3]
+                    + 0xFFFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            19 => {
+                y1[15] * y1[4]
+                    + y1[14] * y1[5]
+                    + y1[13] * y1[6]
+                    + y1[12] * y1[7]
+                    + y1[11] * y1[8]
+                    + y1[10] * y1[9]
+                    + y1[9] * y1[10]
+                    + y1[8] * y1[11]
+                    + y1[7] * y1[12]
+                    + y1[6] * y1[13]
+                    + y1[5] * y1[14]
+                    + y1[4] * y1[15]
+                    - x1[15] * x1[4]
+                    - x1[14] * x1[5]
+                    - x1[13] * x1[6]
+                    - x1[12] * x1[7]
+                    - x1[11] * x1[8]
+                    - x1[10] * x1[9]
+                    - x1[9] * x1[10]
+                    - x1[8] * x1[11]
+                    - x1[7] * x1[12]
+                    - x1[6] * x1[13]
+                    - x1[5] * x1[14]
+                    - x1[4] * x1[15]
+                    + 0x7FFF * q0[4]
+                    + 0xFFFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            20 => {
+                y1[15] * y1[5]
+                    + y1[14] * y1[6]
+                    + y1[13] * y1[7]
+                    + y1[12] * y1[8]
+                    + y1[11] * y1[9]
+                    + y1[10] * y1[10]
+                    + y1[9] * y1[11]
+                    + y1[8] * y1[12]
+                    + y1[7] * y1[13]
+                    + y1[6] * y1[14]
+                    + y1[5] * y1[15]
+                    - x1[15] * x1[5]
+                    - x1[14] * x1[6]
+                    - x1[13] * x1[7]
+                    - x1[12] * x1[8]
+                    - x1[11] * x1[9]
+                    - x1[10] * x1[10]
+                    - x1[9] * x1[11]
+                    - x1[8] * x1[12]
+                    - x1[7] * x1[13]
+                    - x1[6] * x1[14]
+                    - x1[5] * x1[15]
+                    + 0x7FFF * q0[5]
+                    + 0xFFFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            21 => {
+                y1[15] * y1[6]
+                    + y1[14] * y1[7]
+                    + y1[13] * y1[8]
+                    + y1[12] * y1[9]
+                    + y1[11] * y1[10]
+                    + y1[10] * y1[11]
+                    + y1[9] * y1[12]
+                    + y1[8] * y1[13]
+                    + y1[7] * y1[14]
+                    + y1[6] * y1[15]
+                    - x1[15] * x1[6]
+                    - x1[14] * x1[7]
+                    - x1[13] * x1[8]
+                    - x1[12] * x1[9]
+                    - x1[11] * x1[10]
+                    - x1[10] * x1[11]
+                    - x1[9] * x1[12]
+                    - x1[8] * x1[13]
+                    - x1[7] * x1[14]
+                    - x1[6] * x1[15]
+                    + 0x7FFF * q0[6]
+                    + 0xFFFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            22 => {
+                y1[15] * y1[7]
+                    + y1[14] * y1[8]
+                    + y1[13] * y1[9]
+                    + y1[12] * y1[10]
+                    + y1[11] * y1[11]
+                    + y1[10] * y1[12]
+                    + y1[9] * y1[13]
+                    + y1[8] * y1[14]
+                    + y1[7] * y1[15]
+                    - x1[15] * x1[7]
+                    - x1[14] * x1[8]
+                    - x1[13] * x1[9]
+                    - x1[12] * x1[10]
+                    - x1[11] * x1[11]
+                    - x1[10] * x1[12]
+                    - x1[9] * x1[13]
+                    - x1[8] * x1[14]
+                    - x1[7] * x1[15]
+                    + 0x7FFF * q0[7]
+                    + 0xFFFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            23 => {
+                y1[15] * y1[8]
+                    + y1[14] * y1[9]
+                    + y1[13] * y1[10]
+                    + y1[12] * y1[11]
+                    + y1[11] * y1[12]
+                    + y1[10] * y1[13]
+                    + y1[9] * y1[14]
+                    + y1[8] * y1[15]
+                    - x1[15] * x1[8]
+                    - x1[14] * x1[9]
+                    - x1[13] * x1[10]
+                    - x1[12] * x1[11]
+                    - x1[11] * x1[12]
+                    - x1[10] * x1[13]
+                    - x1[9] * x1[14]
+                    - x1[8] * x1[15]
+                    + 0x7FFF * q0[8]
+                    + 0xFFFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            24 => {
+                y1[15] * y1[9]
+                    + y1[14] * y1[10]
+                    + y1[13] * y1[11]
+                    + y1[12] * y1[12]
+                    + y1[11] * y1[13]
+                    + y1[10] * y1[14]
+                    + y1[9] * y1[15]
+                    - x1[15] * x1[9]
+                    - x1[14] * x1[10]
+                    - x1[13] * x1[11]
+                    - x1[12] * x1[12]
+                    - x1[11] * x1[13]
+                    - x1[10] * x1[14]
+                    - x1[9] * x1[15]
+                    + 0x7FFF * q0[9]
+                    + 0xFFFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            25 => {
+                y1[15] * y1[10]
+                    + y1[14] * y1[11]
+                    + y1[13] * y1[12]
+                    + y1[12] * y1[13]
+                    + y1[11] * y1[14]
+                    + y1[10] * y1[15]
+                    - x1[15] * x1[10]
+                    - x1[14] * x1[11]
+                    - x1[13] * x1[12]
+                    - x1[12] * x1[13]
+                    - x1[11] * x1[14]
+                    - x1[10] * x1[15]
+                    + 0x7FFF * q0[10]
+                    + 0xFFFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            26 => {
+                y1[15] * y1[11]
+                    + y1[14] * y1[12]
+                    + y1[13] * y1[13]
+                    + y1[12] * y1[14]
+                    + y1[11] * y1[15]
+                    - x1[15] * x1[11]
+                    - x1[14] * x1[12]
+                    - x1[13] * x1[13]
+                    - x1[12] * x1[14]
+                    - x1[11] * x1[15]
+                    + 0x7FFF * q0[11]
+                    + 0xFFFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            27 => {
+                y1[15] * y1[12] + y1[14] * y1[13] + y1[13] * y1[14] + y1[12] * y1[15]
+                    - x1[15] * x1[12]
+                    - x1[14] * x1[13]
+                    - x1[13] * x1[14]
+                    - x1[12] * x1[15]
+                    + 0x7FFF * q0[12]
+                    + 0xFFFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            28 => {
+                y1[15] * y1[13] + y1[14] * y1[14] + y1[13] * y1[15]
+                    - x1[15] * x1[13]
+                    - x1[14] * x1[14]
+                    - x1[13] * x1[15]
+                    + 0x7FFF * q0[13]
+                    + 0xFFFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            29 => {
+                y1[15] * y1[14] + y1[14] * y1[15] - x1[15] * x1[14] - x1[14] * x1[15]
+                    + 0x7FFF * q0[14]
+                    + 0xFFFF * q0[15]
+                    - 0xFFFF
+            }
+            30 => y1[15] * y1[15] - x1[15] * x1[15] + 0x7FFF * q0[15] - 0xFFFF,
+            31 => -0x1FFFF,
+            _ => 0,
+        }
+    }
+}
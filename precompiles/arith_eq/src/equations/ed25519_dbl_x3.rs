@@ -0,0 +1,847 @@
+// code generated
+//
+// equation: x3*s-2*x1*y1-p*q1+p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// offset: 0x80000000000000000000000000000000000000000000000000000000000000000
+// 2: 2
+// (p*offset): 0x3FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF680000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519DblX3 {}
+
+impl Ed25519DblX3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        x3: &[i64; 16],
+        s: &[i64; 16],
+        q1: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => x3[0] * s[0] - 2 * x1[0] * y1[0] - 0xFFED * q1[0],
+            1 => {
+                x3[1] * s[0] + x3[0] * s[1]
+                    - 2 * x1[1] * y1[0]
+                    - 2 * x1[0] * y1[1]
+                    - 0xFFFF * q1[0]
+                    - 0xFFED * q1[1]
+            }
+            2 => {
+                x3[2] * s[0] + x3[1] * s[1] + x3[0] * s[2]
+                    - 2 * x1[2] * y1[0]
+                    - 2 * x1[1] * y1[1]
+                    - 2 * x1[0] * y1[2]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFED * q1[2]
+            }
+            3 => {
+                x3[3] * s[0] + x3[2] * s[1] + x3[1] * s[2] + x3[0] * s[3]
+                    - 2 * x1[3] * y1[0]
+                    - 2 * x1[2] * y1[1]
+                    - 2 * x1[1] * y1[2]
+                    - 2 * x1[0] * y1[3]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFED * q1[3]
+            }
+            4 => {
+                x3[4] * s[0] + x3[3] * s[1] + x3[2] * s[2] + x3[1] * s[3] + x3[0] * s[4]
+                    - 2 * x1[4] * y1[0]
+                    - 2 * x1[3] * y1[1]
+                    - 2 * x1[2] * y1[2]
+                    - 2 * x1[1] * y1[3]
+                    - 2 * x1[0] * y1[4]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFED * q1[4]
+            }
+            5 => {
+                x3[5] * s[0]
+                    + x3[4] * s[1]
+                    + x3[3] * s[2]
+                    + x3[2] * s[3]
+                    + x3[1] * s[4]
+                    + x3[0] * s[5]
+                    - 2 * x1[5] * y1[0]
+                    - 2 * x1[4] * y1[1]
+                    - 2 * x1[3] * y1[2]
+                    - 2 * x1[2] * y1[3]
+                    - 2 * x1[1] * y1[4]
+                    - 2 * x1[0] * y1[5]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFED * q1[5]
+            }
+            6 => {
+                x3[6] * s[0]
+                    + x3[5] * s[1]
+                    + x3[4] * s[2]
+                    + x3[3] * s[3]
+                    + x3[2] * s[4]
+                    + x3[1] * s[5]
+                    + x3[0] * s[6]
+                    - 2 * x1[6] * y1[0]
+                    - 2 * x1[5] * y1[1]
+                    - 2 * x1[4] * y1[2]
+                    - 2 * x1[3] * y1[3]
+                    - 2 * x1[2] * y1[4]
+                    - 2 * x1[1] * y1[5]
+                    - 2 * x1[0] * y1[6]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFED * q1[6]
+            }
+            7 => {
+                x3[7] * s[0]
+                    + x3[6] * s[1]
+                    + x3[5] * s[2]
+                    + x3[4] * s[3]
+                    + x3[3] * s[4]
+                    + x3[2] * s[5]
+                    + x3[1] * s[6]
+                    + x3[0] * s[7]
+                    - 2 * x1[7] * y1[0]
+                    - 2 * x1[6] * y1[1]
+                    - 2 * x1[5] * y1[2]
+                    - 2 * x1[4] * y1[3]
+                    - 2 * x1[3] * y1[4]
+                    - 2 * x1[2] * y1[5]
+                    - 2 * x1[1] * y1[6]
+                    - 2 * x1[0] * y1[7]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFED * q1[7]
+            }
+            8 => {
+                x3[8] * s[0]
+                    + x3[7] * s[1]
+                    + x3[6] * s[2]
+                    + x3[5] * s[3]
+                    + x3[4] * s[4]
+                    + x3[3] * s[5]
+                    + x3[2] * s[6]
+                    + x3[1] * s[7]
+                    + x3[0] * s[8]
+                    - 2 * x1[8] * y1[0]
+                    - 2 * x1[7] * y1[1]
+                    - 2 * x1[6] * y1[2]
+                    - 2 * x1[5] * y1[3]
+                    - 2 * x1[4] * y1[4]
+                    - 2 * x1[3] * y1[5]
+                    - 2 * x1[2] * y1[6]
+                    - 2 * x1[1] * y1[7]
+                    - 2 * x1[0] * y1[8]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFED * q1[8]
+            }
+            9 => {
+                x3[9] * s[0]
+                    + x3[8] * s[1]
+                    + x3[7] * s[2]
+                    + x3[6] * s[3]
+                    + x3[5] * s[4]
+                    + x3[4] * s[5]
+                    + x3[3] * s[6]
+                    + x3[2] * s[7]
+                    + x3[1] * s[8]
+                    + x3[0] * s[9]
+                    - 2 * x1[9] * y1[0]
+                    - 2 * x1[8] * y1[1]
+                    - 2 * x1[7] * y1[2]
+                    - 2 * x1[6] * y1[3]
+                    - 2 * x1[5] * y1[4]
+                    - 2 * x1[4] * y1[5]
+                    - 2 * x1[3] * y1[6]
+                    - 2 * x1[2] * y1[7]
+                    - 2 * x1[1] * y1[8]
+                    - 2 * x1[0] * y1[9]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFED * q1[9]
+            }
+            10 => {
+                x3[10] * s[0]
+                    + x3[9] * s[1]
+                    + x3[8] * s[2]
+                    + x3[7] * s[3]
+                    + x3[6] * s[4]
+                    + x3[5] * s[5]
+                    + x3[4] * s[6]
+                    + x3[3] * s[7]
+                    + x3[2] * s[8]
+                    + x3[1] * s[9]
+                    + x3[0] * s[10]
+                    - 2 * x1[10] * y1[0]
+                    - 2 * x1[9] * y1[1]
+                    - 2 * x1[8] * y1[2]
+                    - 2 * x1[7] * y1[3]
+                    - 2 * x1[6] * y1[4]
+                    - 2 * x1[5] * y1[5]
+                    - 2 * x1[4] * y1[6]
+                    - 2 * x1[3] * y1[7]
+                    - 2 * x1[2] * y1[8]
+                    - 2 * x1[1] * y1[9]
+                    - 2 * x1[0] * y1[10]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFED * q1[10]
+            }
+            11 => {
+                x3[11] * s[0]
+                    + x3[10] * s[1]
+                    + x3[9] * s[2]
+                    + x3[8] * s[3]
+                    + x3[7] * s[4]
+                    + x3[6] * s[5]
+                    + x3[5] * s[6]
+                    + x3[4] * s[7]
+                    + x3[3] * s[8]
+                    + x3[2] * s[9]
+                    + x3[1] * s[10]
+                    + x3[0] * s[11]
+                    - 2 * x1[11] * y1[0]
+                    - 2 * x1[10] * y1[1]
+                    - 2 * x1[9] * y1[2]
+                    - 2 * x1[8] * y1[3]
+                    - 2 * x1[7] * y1[4]
+                    - 2 * x1[6] * y1[5]
+                    - 2 * x1[5] * y1[6]
+                    - 2 * x1[4] * y1[7]
+                    - 2 * x1[3] * y1[8]
+                    - 2 * x1[2] * y1[9]
+                    - 2 * x1[1] * y1[10]
+                    - 2 * x1[0] * y1[11]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFED * q1[11]
+            }
+            12 => {
+                x3[12] * s[0]
+                    + x3[11] * s[1]
+                    + x3[10] * s[2]
+                    + x3[9] * s[3]
+                    + x3[8] * s[4]
+                    + x3[7] * s[5]
+                    + x3[6] * s[6]
+                    + x3[5] * s[7]
+                    + x3[4] * s[8]
+                    + x3[3] * s[9]
+                    + x3[2] * s[10]
+                    + x3[1] * s[11]
+                    + x3[0] * s[12]
+                    - 2 * x1[12] * y1[0]
+                    - 2 * x1[11] * y1[1]
+                    - 2 * x1[10] * y1[2]
+                    - 2 * x1[9] * y1[3]
+                    - 2 * x1[8] * y1[4]
+                    - 2 * x1[7] * y1[5]
+                    - 2 * x1[6] * y1[6]
+                    - 2 * x1[5] * y1[7]
+                    - 2 * x1[4] * y1[8]
+                    - 2 * x1[3] * y1[9]
+                    - 2 * x1[2] * y1[10]
+                    - 2 * x1[1] * y1[11]
+                    - 2 * x1[0] * y1[12]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFED * q1[12]
+            }
+            13 => {
+                x3[13] * s[0]
+                    + x3[12] * s[1]
+                    + x3[11] * s[2]
+                    + x3[10] * s[3]
+                    + x3[9] * s[4]
+                    + x3[8] * s[5]
+                    + x3[7] * s[6]
+                    + x3[6] * s[7]
+                    + x3[5] * s[8]
+                    + x3[4] * s[9]
+                    + x3[3] * s[10]
+                    + x3[2] * s[11]
+                    + x3[1] * s[12]
+                    + x3[0] * s[13]
+                    - 2 * x1[13] * y1[0]
+                    - 2 * x1[12] * y1[1]
+                    - 2 * x1[11] * y1[2]
+                    - 2 * x1[10] * y1[3]
+                    - 2 * x1[9] * y1[4]
+                    - 2 * x1[8] * y1[5]
+                    - 2 * x1[7] * y1[6]
+                    - 2 * x1[6] * y1[7]
+                    - 2 * x1[5] * y1[8]
+                    - 2 * x1[4] * y1[9]
+                    - 2 * x1[3] * y1[10]
+                    - 2 * x1[2] * y1[11]
+                    - 2 * x1[1] * y1[12]
+                    - 2 * x1[0] * y1[13]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFED * q1[13]
+            }
+            14 => {
+                x3[14] * s[0]
+                    + x3[13] * s[1]
+                    + x3[12] * s[2]
+                    + x3[11] * s[3]
+                    + x3[10] * s[4]
+                    + x3[9] * s[5]
+                    + x3[8] * s[6]
+                    + x3[7] * s[7]
+                    + x3[6] * s[8]
+                    + x3[5] * s[9]
+                    + x3[4] * s[10]
+                    + x3[3] * s[11]
+                    + x3[2] * s[12]
+                    + x3[1] * s[13]
+                    + x3[0] * s[14]
+                    - 2 * x1[14] * y1[0]
+                    - 2 * x1[13] * y1[1]
+                    - 2 * x1[12] * y1[2]
+                    - 2 * x1[11] * y1[3]
+                    - 2 * x1[10] * y1[4]
+                    - 2 * x1[9] * y1[5]
+                    - 2 * x1[8] * y1[6]
+                    - 2 * x1[7] * y1[7]
+                    - 2 * x1[6] * y1[8]
+                    - 2 * x1[5] * y1[9]
+                    - 2 * x1[4] * y1[10]
+                    - 2 * x1[3] * y1[11]
+                    - 2 * x1[2] * y1[12]
+                    - 2 * x1[1] * y1[13]
+                    - 2 * x1[0] * y1[14]
+                    - 0xFFFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFED * q1[14]
+            }
+            15 => {
+                x3[15] * s[0]
+                    + x3[14] * s[1]
+                    + x3[13] * s[2]
+                    + x3[12] * s[3]
+                    + x3[11] * s[4]
+                    + x3[10] * s[5]
+                    + x3[9] * s[6]
+                    + x3[8] * s[7]
+                    + x3[7] * s[8]
+                    + x3[6] * s[9]
+                    + x3[5] * s[10]
+                    + x3[4] * s[11]
+                    + x3[3] * s[12]
+                    + x3[2] * s[13]
+                    + x3[1] * s[14]
+                    + x3[0] * s[15]
+                    - 2 * x1[15] * y1[0]
+                    - 2 * x1[14] * y1[1]
+                    - 2 * x1[13] * y1[2]
+                    - 2 * x1[12] * y1[3]
+                    - 2 * x1[11] * y1[4]
+                    - 2 * x1[10] * y1[5]
+                    - 2 * x1[9] * y1[6]
+                    - 2 * x1[8] * y1[7]
+                    - 2 * x1[7] * y1[8]
+                    - 2 * x1[6] * y1[9]
+                    - 2 * x1[5] * y1[10]
+                    - 2 * x1[4] * y1[11]
+                    - 2 * x1[3] * y1[12]
+                    - 2 * x1[2] * y1[13]
+                    - 2 * x1[1] * y1[14]
+                    - 2 * x1[0] * y1[15]
+                    - 0x7FFF * q1[0]
+                    - 0xFFFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFED * q1[15]
+            }
+            16 => {
+                x3[15] * s[1]
+                    + x3[14] * s[2]
+                    + x3[13] * s[3]
+                    + x3[12] * s[4]
+                    + x3[11] * s[5]
+                    + x3[10] * s[6]
+                    + x3[9] * s[7]
+                    + x3[8] * s[8]
+                    + x3[7] * s[9]
+                    + x3[6] * s[10]
+                    + x3[5] * s[11]
+                    + x3[4] * s[12]
+                    + x3[3] * s[13]
+                    + x3[2] * s[14]
+                    + x3[1] * s[15]
+                    - 2 * x1[15] * y1[1]
+                    - 2 * x1[14] * y1[2]
+                    - 2 * x1[13] * y1[3]
+                    - 2 * x1[12] * y1[4]
+                    - 2 * x1[11] * y1[5]
+                    - 2 * x1[10] * y1[6]
+                    - 2 * x1[9] * y1[7]
+                    - 2 * x1[8] * y1[8]
+                    - 2 * x1[7] * y1[9]
+                    - 2 * x1[6] * y1[10]
+                    - 2 * x1[5] * y1[11]
+                    - 2 * x1[4] * y1[12]
+                    - 2 * x1[3] * y1[13]
+                    - 2 * x1[2] * y1[14]
+                    - 2 * x1[1] * y1[15]
+                    - 0x7FFF * q1[1]
+                    - 0xFFFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFF68
+            }
+            17 => {
+                x3[15] * s[2]
+                    + x3[14] * s[3]
+                    + x3[13] * s[4]
+                    + x3[12] * s[5]
+                    + x3[11] * s[6]
+                    + x3[10] * s[7]
+                    + x3[9] * s[8]
+                    + x3[8] * s[9]
+                    + x3[7] * s[10]
+                    + x3[6] * s[11]
+                    + x3[5] * s[12]
+                    + x3[4] * s[13]
+                    + x3[3] * s[14]
+                    + x3[2] * s[15]
+                    - 2 * x1[15] * y1[2]
+                    - 2 * x1[14] * y1[3]
+                    - 2 * x1[13] * y1[4]
+                    - 2 * x1[12] * y1[5]
+                    - 2 * x1[11] * y1[6]
+                    - 2 * x1[10] * y1[7]
+                    - 2 * x1[9] * y1[8]
+                    - 2 * x1[8] * y1[9]
+                    - 2 * x1[7] * y1[10]
+                    - 2 * x1[6] * y1[11]
+                    - 2 * x1[5] * y1[12]
+                    - 2 * x1[4] * y1[13]
+                    - 2 * x1[3] * y1[14]
+                    - 2 * x1[2] * y1[15]
+                    - 0x7FFF * q1[2]
+                    - 0xFFFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            18 => {
+                x3[15] * s[3]
+                    + x3[14] * s[4]
+                    + x3[13] * s[5]
+                    + x3[12] * s[6]
+                    + x3[11] * s[7]
+                    + x3[10] * s[8]
+                    + x3[9] * s[9]
+                    + x3[8] * s[10]
+                    + x3[7] * s[11]
+                    + x3[6] * s[12]
+                    + x3[5] * s[13]
+                    + x3[4] * s[14]
+                    + x3[3] * s[15]
+                    - 2 * x1[15] * y1[3]
+                    - 2 * x1[14] * y1[4]
+                    - 2 * x1[13] * y1[5]
+                    - 2 * x1[12] * y1[6]
+                    - 2 * x1[11] * y1[7]
+                    - 2 * x1[10] * y1[8]
+                    - 2 * x1[9] * y1[9]
+                    - 2 * x1[8] * y1[10]
+                    - 2 * x1[7] * y1[11]
+                    - 2 * x1[6] * y1[12]
+                    - 2 * x1[5] * y1[13]
+                    - 2 * x1[4] * y1[14]
+                    - 2 * x1[3] * y1[15]
+                    - 0x7FFF * q1[3]
+                    - 0xFFFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            19 => {
+                x3[15] * s[4]
+                    + x3[14] * s[5]
+                    + x3[13] * s[6]
+                    + x3[12] * s[7]
+                    + x3[11] * s[8]
+                    + x3[10] * s[9]
+                    + x3[9] * s[10]
+                    + x3[8] * s[11]
+                    + x3[7] * s[12]
+                    + x3[6] * s[13]
+                    + x3[5] * s[14]
+                    + x3[4] * s[15]
+                    - 2 * x1[15] * y1[4]
+                    - 2 * x1[14] * y1[5]
+                    - 2 * x1[13] * y1[6]
+                    - 2 * x1[12] * y1[7]
+                    - 2 * x1[11] * y1[8]
+                    - 2 * x1[10] * y1[9]
+                    - 2 * x1[9] * y1[10]
+                    - 2 * x1[8] * y1[11]
+                    - 2 * x1[7] * y1[12]
+                    - 2 * x1[6] * y1[13]
+                    - 2 * x1[5] * y1[14]
+                    - 2 * x1[4] * y1[15]
+                    - 0x7FFF * q1[4]
+                    - 0xFFFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            20 => {
+                x3[15] * s[5]
+                    + x3[14] * s[6]
+                    + x3[13] * s[7]
+                    + x3[12] * s[8]
+                    + x3[11] * s[9]
+                    + x3[10] * s[10]
+                    + x3[9] * s[11]
+                    + x3[8] * s[12]
+                    + x3[7] * s[13]
+                    + x3[6] * s[14]
+                    + x3[5] * s[15]
+                    - 2 * x1[15] * y1[5]
+                    - 2 * x1[14] * y1[6]
+                    - 2 * x1[13] * y1[7]
+                    - 2 * x1[12] * y1[8]
+                    - 2 * x1[11] * y1[9]
+                    - 2 * x1[10] * y1[10]
+                    - 2 * x1[9] * y1[11]
+                    - 2 * x1[8] * y1[12]
+                    - 2 * x1[7] * y1[13]
+                    - 2 * x1[6] * y1[14]
+                    - 2 * x1[5] * y1[15]
+                    - 0x7FFF * q1[5]
+                    - 0xFFFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            21 => {
+                x3[15] * s[6]
+                    + x3[14] * s[7]
+                    + x3[13] * s[8]
+                    + x3[12] * s[9]
+                    + x3[11] * s[10]
+                    + x3[10] * s[11]
+                    + x3[9] * s[12]
+                    + x3[8] * s[13]
+                    + x3[7] * s[14]
+                    + x3[6] * s[15]
+                    - 2 * x1[15] * y1[6]
+                    - 2 * x1[14] * y1[7]
+                    - 2 * x1[13] * y1[8]
+                    - 2 * x1[12] * y1[9]
+                    - 2 * x1[11] * y1[10]
+                    - 2 * x1[10] * y1[11]
+                    - 2 * x1[9] * y1[12]
+                    - 2 * x1[8] * y1[13]
+                    - 2 * x1[7] * y1[14]
+                    - 2 * x1[6] * y1[15]
+                    - 0x7FFF * q1[6]
+                    - 0xFFFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            22 => {
+                x3[15] * s[7]
+                    + x3[14] * s[8]
+                    + x3[13] * s[9]
+                    + x3[12] * s[10]
+                    + x3[11] * s[11]
+                    + x3[10] * s[12]
+                    + x3[9] * s[13]
+                    + x3[8] * s[14]
+                    + x3[7] * s[15]
+                    - 2 * x1[15] * y1[7]
+                    - 2 * x1[14] * y1[8]
+                    - 2 * x1[13] * y1[9]
+                    - 2 * x1[12] * y1[10]
+                    - 2 * x1[11] * y1[11]
+                    - 2 * x1[10] * y1[12]
+                    - 2 * x1[9] * y1[13]
+                    - 2 * x1[8] * y1[14]
+                    - 2 * x1[7] * y1[15]
+                    - 0x7FFF * q1[7]
+                    - 0xFFFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            23 => {
+                x3[15] * s[8]
+                    + x3[14] * s[9]
+                    + x3[13] * s[10]
+                    + x3[12] * s[11]
+                    + x3[11] * s[12]
+                    + x3[10] * s[13]
+                    + x3[9] * s[14]
+                    + x3[8] * s[15]
+                    - 2 * x1[15] * y1[8]
+                    - 2 * x1[14] * y1[9]
+                    - 2 * x1[13] * y1[10]
+                    - 2 * x1[12] * y1[11]
+                    - 2 * x1[11] * y1[12]
+                    - 2 * x1[10] * y1[13]
+                    - 2 * x1[9] * y1[14]
+                    - 2 * x1[8] * y1[15]
+                    - 0x7FFF * q1[8]
+                    - 0xFFFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            24 => {
+                x3[15] * s[9]
+                    + x3[14] * s[10]
+                    + x3[13] * s[11]
+                    + x3[12] * s[12]
+                    + x3[11] * s[13]
+                    + x3[10] * s[14]
+                    + x3[9] * s[15]
+                    - 2 * x1[15] * y1[9]
+                    - 2 * x1[14] * y1[10]
+                    - 2 * x1[13] * y1[11]
+                    - 2 * x1[12] * y1[12]
+                    - 2 * x1[11] * y1[13]
+                    - 2 * x1[10] * y1[14]
+                    - 2 * x1[9] * y1[15]
+                    - 0x7FFF * q1[9]
+                    - 0xFFFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            25 => {
+                x3[15] * s[10]
+                    + x3[14] * s[11]
+                    + x3[13] * s[12]
+                    + x3[12] * s[13]
+                    + x3[11] * s[14]
+                    + x3[10] * s[15]
+                    - 2 * x1[15] * y1[10]
+                    - 2 * x1[14] * y1[11]
+                    - 2 * x1[13] * y1[12]
+                    - 2 * x1[12] * y1[13]
+                    - 2 * x1[11] * y1[14]
+                    - 2 * x1[10] * y1[15]
+                    - 0x7FFF * q1[10]
+                    - 0xFFFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            26 => {
+                x3[15] * s[11] + x3[14] * s[12] + x3[13] * s[13] + x3[12] * s[14] + x3[11] * s[15]
+                    - 2 * x1[15] * y1[11]
+                    - 2 * x1[14] * y1[12]
+                    - 2 * x1[13] * y1[13]
+                    - 2 * x1[12] * y1[14]
+                    - 2 * x1[11] * y1[15]
+                    - 0x7FFF * q1[11]
+                    - 0xFFFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            27 => {
+                x3[15] * s[12] + x3[14] * s[13] + x3[13] * s[14] + x3[12] * s[15]
+                    - 2 * x1[15] * y1[12]
+                    - 2 * x1[14] * y1[13]
+                    - 2 * x1[13] * y1[14]
+                    - 2 * x1[12] * y1[15]
+                    - 0x7FFF * q1[12]
+                    - 0xFFFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            28 => {
+                x3[15] * s[13] + x3[14] * s[14] + x3[13] * s[15]
+                    - 2 * x1[15] * y1[13]
+                    - 2 * x1[14] * y1[14]
+                    - 2 * x1[13] * y1[15]
+                    - 0x7FFF * q1[13]
+                    - 0xFFFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            29 => {
+                x3[15] * s[14] + x3[14] * s[15]
+                    - 2 * x1[15] * y1[14]
+                    - 2 * x1[14] * y1[15]
+                    - 0x7FFF * q1[14]
+                    - 0xFFFF * q1[15]
+                    + 0xFFFF
+            }
+            30 => x3[15] * s[15] - 2 * x1[15] * y1[15] - 0x7FFF * q1[15] + 0xFFFF,
+            31 => 0x3FFFF,
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,1136 @@
+// code generated
+//
+// equation: 2*y3-y3*s-y1*y1-x1*x1+p*q2-p*offset
+//
+// p: 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED
+// offset: 0x20000000000000000000000000000000000000000000000000000000000000000
+// 2: 2
+// (p*offset): 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFDA0000000000000000000000000000000000000000000000000000000000000000
+//
+// chunks:16
+// chunk_bits:16
+// terms_by_clock: 2
+
+pub struct Ed25519DblY3 {}
+
+impl Ed25519DblY3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        icol: u8,
+        x1: &[i64; 16],
+        y1: &[i64; 16],
+        y3: &[i64; 16],
+        s: &[i64; 16],
+        q2: &[i64; 16],
+    ) -> i64 {
+        match icol {
+            0 => 2 * y3[0] - y3[0] * s[0] - y1[0] * y1[0] - x1[0] * x1[0] + 0xFFED * q2[0],
+            1 => {
+                2 * y3[1]
+                    - y3[1] * s[0]
+                    - y3[0] * s[1]
+                    - y1[1] * y1[0]
+                    - y1[0] * y1[1]
+                    - x1[1] * x1[0]
+                    - x1[0] * x1[1]
+                    + 0xFFFF * q2[0]
+                    + 0xFFED * q2[1]
+            }
+            2 => {
+                2 * y3[2]
+                    - y3[2] * s[0]
+                    - y3[1] * s[1]
+                    - y3[0] * s[2]
+                    - y1[2] * y1[0]
+                    - y1[1] * y1[1]
+                    - y1[0] * y1[2]
+                    - x1[2] * x1[0]
+                    - x1[1] * x1[1]
+                    - x1[0] * x1[2]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFED * q2[2]
+            }
+            3 => {
+                2 * y3[3]
+                    - y3[3] * s[0]
+                    - y3[2] * s[1]
+                    - y3[1] * s[2]
+                    - y3[0] * s[3]
+                    - y1[3] * y1[0]
+                    - y1[2] * y1[1]
+                    - y1[1] * y1[2]
+                    - y1[0] * y1[3]
+                    - x1[3] * x1[0]
+                    - x1[2] * x1[1]
+                    - x1[1] * x1[2]
+                    - x1[0] * x1[3]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFED * q2[3]
+            }
+            4 => {
+                2 * y3[4]
+                    - y3[4] * s[0]
+                    - y3[3] * s[1]
+                    - y3[2] * s[2]
+                    - y3[1] * s[3]
+                    - y3[0] * s[4]
+                    - y1[4] * y1[0]
+                    - y1[3] * y1[1]
+                    - y1[2] * y1[2]
+                    - y1[1] * y1[3]
+                    - y1[0] * y1[4]
+                    - x1[4] * x1[0]
+                    - x1[3] * x1[1]
+                    - x1[2] * x1[2]
+                    - x1[1] * x1[3]
+                    - x1[0] * x1[4]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFED * q2[4]
+            }
+            5 => {
+                2 * y3[5]
+                    - y3[5] * s[0]
+                    - y3[4] * s[1]
+                    - y3[3] * s[2]
+                    - y3[2] * s[3]
+                    - y3[1] * s[4]
+                    - y3[0] * s[5]
+                    - y1[5] * y1[0]
+                    - y1[4] * y1[1]
+                    - y1[3] * y1[2]
+                    - y1[2] * y1[3]
+                    - y1[1] * y1[4]
+                    - y1[0] * y1[5]
+                    - x1[5] * x1[0]
+                    - x1[4] * x1[1]
+                    - x1[3] * x1[2]
+                    - x1[2] * x1[3]
+                    - x1[1] * x1[4]
+                    - x1[0] * x1[5]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFED * q2[5]
+            }
+            6 => {
+                2 * y3[6]
+                    - y3[6] * s[0]
+                    - y3[5] * s[1]
+                    - y3[4] * s[2]
+                    - y3[3] * s[3]
+                    - y3[2] * s[4]
+                    - y3[1] * s[5]
+                    - y3[0] * s[6]
+                    - y1[6] * y1[0]
+                    - y1[5] * y1[1]
+                    - y1[4] * y1[2]
+                    - y1[3] * y1[3]
+                    - y1[2] * y1[4]
+                    - y1[1] * y1[5]
+                    - y1[0] * y1[6]
+                    - x1[6] * x1[0]
+                    - x1[5] * x1[1]
+                    - x1[4] * x1[2]
+                    - x1[3] * x1[3]
+                    - x1[2] * x1[4]
+                    - x1[1] * x1[5]
+                    - x1[0] * x1[6]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFED * q2[6]
+            }
+            7 => {
+                2 * y3[7]
+                    - y3[7] * s[0]
+                    - y3[6] * s[1]
+                    - y3[5] * s[2]
+                    - y3[4] * s[3]
+                    - y3[3] * s[4]
+                    - y3[2] * s[5]
+                    - y3[1] * s[6]
+                    - y3[0] * s[7]
+                    - y1[7] * y1[0]
+                    - y1[6] * y1[1]
+                    - y1[5] * y1[2]
+                    - y1[4] * y1[3]
+                    - y1[3] * y1[4]
+                    - y1[2] * y1[5]
+                    - y1[1] * y1[6]
+                    - y1[0] * y1[7]
+                    - x1[7] * x1[0]
+                    - x1[6] * x1[1]
+                    - x1[5] * x1[2]
+                    - x1[4] * x1[3]
+                    - x1[3] * x1[4]
+                    - x1[2] * x1[5]
+                    - x1[1] * x1[6]
+                    - x1[0] * x1[7]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFED * q2[7]
+            }
+            8 => {
+                2 * y3[8]
+                    - y3[8] * s[0]
+                    - y3[7] * s[1]
+                    - y3[6] * s[2]
+                    - y3[5] * s[3]
+                    - y3[4] * s[4]
+                    - y3[3] * s[5]
+                    - y3[2] * s[6]
+                    - y3[1] * s[7]
+                    - y3[0] * s[8]
+                    - y1[8] * y1[0]
+                    - y1[7] * y1[1]
+                    - y1[6] * y1[2]
+                    - y1[5] * y1[3]
+                    - y1[4] * y1[4]
+                    - y1[3] * y1[5]
+                    - y1[2] * y1[6]
+                    - y1[1] * y1[7]
+                    - y1[0] * y1[8]
+                    - x1[8] * x1[0]
+                    - x1[7] * x1[1]
+                    - x1[6] * x1[2]
+                    - x1[5] * x1[3]
+                    - x1[4] * x1[4]
+                    - x1[3] * x1[5]
+                    - x1[2] * x1[6]
+                    - x1[1] * x1[7]
+                    - x1[0] * x1[8]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFED * q2[8]
+            }
+            9 => {
+                2 * y3[9]
+                    - y3[9] * s[0]
+                    - y3[8] * s[1]
+                    - y3[7] * s[2]
+                    - y3[6] * s[3]
+                    - y3[5] * s[4]
+                    - y3[4] * s[5]
+                    - y3[3] * s[6]
+                    - y3[2] * s[7]
+                    - y3[1] * s[8]
+                    - y3[0] * s[9]
+                    - y1[9] * y1[0]
+                    - y1[8] * y1[1]
+                    - y1[7] * y1[2]
+                    - y1[6] * y1[3]
+                    - y1[5] * y1[4]
+                    - y1[4] * y1[5]
+                    - y1[3] * y1[6]
+                    - y1[2] * y1[7]
+                    - y1[1] * y1[8]
+                    - y1[0] * y1[9]
+                    - x1[9] * x1[0]
+                    - x1[8] * x1[1]
+                    - x1[7] * x1[2]
+                    - x1[6] * x1[3]
+                    - x1[5] * x1[4]
+                    - x1[4] * x1[5]
+                    - x1[3] * x1[6]
+                    - x1[2] * x1[7]
+                    - x1[1] * x1[8]
+                    - x1[0] * x1[9]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFED * q2[9]
+            }
+            10 => {
+                2 * y3[10]
+                    - y3[10] * s[0]
+                    - y3[9] * s[1]
+                    - y3[8] * s[2]
+                    - y3[7] * s[3]
+                    - y3[6] * s[4]
+                    - y3[5] * s[5]
+                    - y3[4] * s[6]
+                    - y3[3] * s[7]
+                    - y3[2] * s[8]
+                    - y3[1] * s[9]
+                    - y3[0] * s[10]
+                    - y1[10] * y1[0]
+                    - y1[9] * y1[1]
+                    - y1[8] * y1[2]
+                    - y1[7] * y1[3]
+                    - y1[6] * y1[4]
+                    - y1[5] * y1[5]
+                    - y1[4] * y1[6]
+                    - y1[3] * y1[7]
+                    - y1[2] * y1[8]
+                    - y1[1] * y1[9]
+                    - y1[0] * y1[10]
+                    - x1[10] * x1[0]
+                    - x1[9] * x1[1]
+                    - x1[8] * x1[2]
+                    - x1[7] * x1[3]
+                    - x1[6] * x1[4]
+                    - x1[5] * x1[5]
+                    - x1[4] * x1[6]
+                    - x1[3] * x1[7]
+                    - x1[2] * x1[8]
+                    - x1[1] * x1[9]
+                    - x1[0] * x1[10]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFED * q2[10]
+            }
+            11 => {
+                2 * y3[11]
+                    - y3[11] * s[0]
+                    - y3[10] * s[1]
+                    - y3[9] * s[2]
+                    - y3[8] * s[3]
+                    - y3[7] * s[4]
+                    - y3[6] * s[5]
+                    - y3[5] * s[6]
+                    - y3[4] * s[7]
+                    - y3[3] * s[8]
+                    - y3[2] * s[9]
+                    - y3[1] * s[10]
+                    - y3[0] * s[11]
+                    - y1[11] * y1[0]
+                    - y1[10] * y1[1]
+                    - y1[9] * y1[2]
+                    - y1[8] * y1[3]
+                    - y1[7] * y1[4]
+                    - y1[6] * y1[5]
+                    - y1[5] * y1[6]
+                    - y1[4] * y1[7]
+                    - y1[3] * y1[8]
+                    - y1[2] * y1[9]
+                    - y1[1] * y1[10]
+                    - y1[0] * y1[11]
+                    - x1[11] * x1[0]
+                    - x1[10] * x1[1]
+                    - x1[9] * x1[2]
+                    - x1[8] * x1[3]
+                    - x1[7] * x1[4]
+                    - x1[6] * x1[5]
+                    - x1[5] * x1[6]
+                    - x1[4] * x1[7]
+                    - x1[3] * x1[8]
+                    - x1[2] * x1[9]
+                    - x1[1] * x1[10]
+                    - x1[0] * x1[11]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFED * q2[11]
+            }
+            12 => {
+                2 * y3[12]
+                    - y3[12] * s[0]
+                    - y3[11] * s[1]
+                    - y3[10] * s[2]
+                    - y3[9] * s[3]
+                    - y3[8] * s[4]
+                    - y3[7] * s[5]
+                    - y3[6] * s[6]
+                    - y3[5] * s[7]
+                    - y3[4] * s[8]
+                    - y3[3] * s[9]
+                    - y3[2] * s[10]
+                    - y3[1] * s[11]
+                    - y3[0] * s[12]
+                    - y1[12] * y1[0]
+                    - y1[11] * y1[1]
+                    - y1[10] * y1[2]
+                    - y1[9] * y1[3]
+                    - y1[8] * y1[4]
+                    - y1[7] * y1[5]
+                    - y1[6] * y1[6]
+                    - y1[5] * y1[7]
+                    - y1[4] * y1[8]
+                    - y1[3] * y1[9]
+                    - y1[2] * y1[10]
+                    - y1[1] * y1[11]
+                    - y1[0] * y1[12]
+                    - x1[12] * x1[0]
+                    - x1[11] * x1[1]
+                    - x1[10] * x1[2]
+                    - x1[9] * x1[3]
+                    - x1[8] * x1[4]
+                    - x1[7] * x1[5]
+                    - x1[6] * x1[6]
+                    - x1[5] * x1[7]
+                    - x1[4] * x1[8]
+                    - x1[3] * x1[9]
+                    - x1[2] * x1[10]
+                    - x1[1] * x1[11]
+                    - x1[0] * x1[12]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFED * q2[12]
+            }
+            13 => {
+                2 * y3[13]
+                    - y3[13] * s[0]
+                    - y3[12] * s[1]
+                    - y3[11] * s[2]
+                    - y3[10] * s[3]
+                    - y3[9] * s[4]
+                    - y3[8] * s[5]
+                    - y3[7] * s[6]
+                    - y3[6] * s[7]
+                    - y3[5] * s[8]
+                    - y3[4] * s[9]
+                    - y3[3] * s[10]
+                    - y3[2] * s[11]
+                    - y3[1] * s[12]
+                    - y3[0] * s[13]
+                    - y1[13] * y1[0]
+                    - y1[12] * y1[1]
+                    - y1[11] * y1[2]
+                    - y1[10] * y1[3]
+                    - y1[9] * y1[4]
+                    - y1[8] * y1[5]
+                    - y1[7] * y1[6]
+                    - y1[6] * y1[7]
+                    - y1[5] * y1[8]
+                    - y1[4] * y1[9]
+                    - y1[3] * y1[10]
+                    - y1[2] * y1[11]
+                    - y1[1] * y1[12]
+                    - y1[0] * y1[13]
+                    - x1[13] * x1[0]
+                    - x1[12] * x1[1]
+                    - x1[11] * x1[2]
+                    - x1[10] * x1[3]
+                    - x1[9] * x1[4]
+                    - x1[8] * x1[5]
+                    - x1[7] * x1[6]
+                    - x1[6] * x1[7]
+                    - x1[5] * x1[8]
+                    - x1[4] * x1[9]
+                    - x1[3] * x1[10]
+                    - x1[2] * x1[11]
+                    - x1[1] * x1[12]
+                    - x1[0] * x1[13]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFED * q2[13]
+            }
+            14 => {
+                2 * y3[14]
+                    - y3[14] * s[0]
+                    - y3[13] * s[1]
+                    - y3[12] * s[2]
+                    - y3[11] * s[3]
+                    - y3[10] * s[4]
+                    - y3[9] * s[5]
+                    - y3[8] * s[6]
+                    - y3[7] * s[7]
+                    - y3[6] * s[8]
+                    - y3[5] * s[9]
+                    - y3[4] * s[10]
+                    - y3[3] * s[11]
+                    - y3[2] * s[12]
+                    - y3[1] * s[13]
+                    - y3[0] * s[14]
+                    - y1[14] * y1[0]
+                    - y1[13] * y1[1]
+                    - y1[12] * y1[2]
+                    - y1[11] * y1[3]
+                    - y1[10] * y1[4]
+                    - y1[9] * y1[5]
+                    - y1[8] * y1[6]
+                    - y1[7] * y1[7]
+                    - y1[6] * y1[8]
+                    - y1[5] * y1[9]
+                    - y1[4] * y1[10]
+                    - y1[3] * y1[11]
+                    - y1[2] * y1[12]
+                    - y1[1] * y1[13]
+                    - y1[0] * y1[14]
+                    - x1[14] * x1[0]
+                    - x1[13] * x1[1]
+                    - x1[12] * x1[2]
+                    - x1[11] * x1[3]
+                    - x1[10] * x1[4]
+                    - x1[9] * x1[5]
+                    - x1[8] * x1[6]
+                    - x1[7] * x1[7]
+                    - x1[6] * x1[8]
+                    - x1[5] * x1[9]
+                    - x1[4] * x1[10]
+                    - x1[3] * x1[11]
+                    - x1[2] * x1[12]
+                    - x1[1] * x1[13]
+                    - x1[0] * x1[14]
+                    + 0xFFFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFED * q2[14]
+            }
+            15 => {
+                2 * y3[15]
+                    - y3[15] * s[0]
+                    - y3[14] * s[1]
+                    - y3[13] * s[2]
+                    - y3[12] * s[3]
+                    - y3[11] * s[4]
+                    - y3[10] * s[5]
+                    - y3[9] * s[6]
+                    - y3[8] * s[7]
+                    - y3[7] * s[8]
+                    - y3[6] * s[9]
+                    - y3[5] * s[10]
+                    - y3[4] * s[11]
+                    - y3[3] * s[12]
+                    - y3[2] * s[13]
+                    - y3[1] * s[14]
+                    - y3[0] * s[15]
+                    - y1[15] * y1[0]
+                    - y1[14] * y1[1]
+                    - y1[13] * y1[2]
+                    - y1[12] * y1[3]
+                    - y1[11] * y1[4]
+                    - y1[10] * y1[5]
+                    - y1[9] * y1[6]
+                    - y1[8] * y1[7]
+                    - y1[7] * y1[8]
+                    - y1[6] * y1[9]
+                    - y1[5] * y1[10]
+                    - y1[4] * y1[11]
+                    - y1[3] * y1[12]
+                    - y1[2] * y1[13]
+                    - y1[1] * y1[14]
+                    - y1[0] * y1[15]
+                    - x1[15] * x1[0]
+                    - x1[14] * x1[1]
+                    - x1[13] * x1[2]
+                    - x1[12] * x1[3]
+                    - x1[11] * x1[4]
+                    - x1[10] * x1[5]
+                    - x1[9] * x1[6]
+                    - x1[8] * x1[7]
+                    - x1[7] * x1[8]
+                    - x1[6] * x1[9]
+                    - x1[5] * x1[10]
+                    - x1[4] * x1[11]
+                    - x1[3] * x1[12]
+                    - x1[2] * x1[13]
+                    - x1[1] * x1[14]
+                    - x1[0] * x1[15]
+                    + 0x7FFF * q2[0]
+                    + 0xFFFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFED * q2[15]
+            }
+            16 => {
+                -y3[15] * s[1]
+                    - y3[14] * s[2]
+                    - y3[13] * s[3]
+                    - y3[12] * s[4]
+                    - y3[11] * s[5]
+                    - y3[10] * s[6]
+                    - y3[9] * s[7]
+                    - y3[8] * s[8]
+                    - y3[7] * s[9]
+                    - y3[6] * s[10]
+                    - y3[5] * s[11]
+                    - y3[4] * s[12]
+                    - y3[3] * s[13]
+                    - y3[2] * s[14]
+                    - y3[1] * s[15]
+                    - y1[15] * y1[1]
+                    - y1[14] * y1[2]
+                    - y1[13] * y1[3]
+                    - y1[12] * y1[4]
+                    - y1[11] * y1[5]
+                    - y1[10] * y1[6]
+                    - y1[9] * y1[7]
+                    - y1[8] * y1[8]
+                    - y1[7] * y1[9]
+                    - y1[6] * y1[10]
+                    - y1[5] * y1[11]
+                    - y1[4] * y1[12]
+                    - y1[3] * y1[13]
+                    - y1[2] * y1[14]
+                    - y1[1] * y1[15]
+                    - x1[15] * x1[1]
+                    - x1[14] * x1[2]
+                    - x1[13] * x1[3]
+                    - x1[12] * x1[4]
+                    - x1[11] * x1[5]
+                    - x1[10] * x1[6]
+                    - x1[9] * x1[7]
+                    - x1[8] * x1[8]
+                    - x1[7] * x1[9]
+                    - x1[6] * x1[10]
+                    - x1[5] * x1[11]
+                    - x1[4] * x1[12]
+                    - x1[3] * x1[13]
+                    - x1[2] * x1[14]
+                    - x1[1] * x1[15]
+                    + 0x7FFF * q2[1]
+                    + 0xFFFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFDA
+            }
+            17 => {
+                -y3[15] * s[2]
+                    - y3[14] * s[3]
+                    - y3[13] * s[4]
+                    - y3[12] * s[5]
+                    - y3[11] * s[6]
+                    - y3[10] * s[7]
+                    - y3[9] * s[8]
+                    - y3[8] * s[9]
+                    - y3[7] * s[10]
+                    - y3[6] * s[11]
+                    - y3[5] * s[12]
+                    - y3[4] * s[13]
+                    - y3[3] * s[14]
+                    - y3[2] * s[15]
+                    - y1[15] * y1[2]
+                    - y1[14] * y1[3]
+                    - y1[13] * y1[4]
+                    - y1[12] * y1[5]
+                    - y1[11] * y1[6]
+                    - y1[10] * y1[7]
+                    - y1[9] * y1[8]
+                    - y1[8] * y1[9]
+                    - y1[7] * y1[10]
+                    - y1[6] * y1[11]
+                    - y1[5] * y1[12]
+                    - y1[4] * y1[13]
+                    - y1[3] * y1[14]
+                    - y1[2] * y1[15]
+                    - x1[15] * x1[2]
+                    - x1[14] * x1[3]
+                    - x1[13] * x1[4]
+                    - x1[12] * x1[5]
+                    - x1[11] * x1[6]
+                    - x1[10] * x1[7]
+                    - x1[9] * x1[8]
+                    - x1[8] * x1[9]
+                    - x1[7] * x1[10]
+                    - x1[6] * x1[11]
+                    - x1[5] * x1[12]
+                    - x1[4] * x1[13]
+                    - x1[3] * x1[14]
+                    - x1[2] * x1[15]
+                    + 0x7FFF * q2[2]
+                    + 0xFFFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            18 => {
+                -y3[15] * s[3]
+                    - y3[14] * s[4]
+                    - y3[13] * s[5]
+                    - y3[12] * s[6]
+                    - y3[11] * s[7]
+                    - y3[10] * s[8]
+                    - y3[9] * s[9]
+                    - y3[8] * s[10]
+                    - y3[7] * s[11]
+                    - y3[6] * s[12]
+                    - y3[5] * s[13]
+                    - y3[4] * s[14]
+                    - y3[3] * s[15]
+                    - y1[15] * y1[3]
+                    - y1[14] * y1[4]
+                    - y1[13] * y1[5]
+                    - y1[12] * y1[6]
+                    - y1[11] * y1[7]
+                    - y1[10] * y1[8]
+                    - y1[9] * y1[9]
+                    - y1[8] * y1[10]
+                    - y1[7] * y1[11]
+                    - y1[6] * y1[12]
+                    - y1[5] * y1[13]
+                    - y1[4] * y1[14]
+                    - y1[3] * y1[15]
+                    - x1[15] * x1[3]
+                    - x1[14] * x1[4]
+                    - x1[13] * x1[5]
+                    - x1[12] * x1[6]
+                    - x1[11] * x1[7]
+                    - x1[10] * x1[8]
+                    - x1[9] * x1[9]
+                    - x1[8] * x1[10]
+                    - x1[7] * x1[11]
+                    - x1[6] * x1[12]
+                    - x1[5] * x1[13]
+                    - x1[4] * x1[14]
+                    - x1[3] * x1[15]
+                    + 0x7FFF * q2[3]
+                    + 0xFFFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            19 => {
+                -y3[15] * s[4]
+                    - y3[14] * s[5]
+                    - y3[13] * s[6]
+                    - y3[12] * s[7]
+                    - y3[11] * s[8]
+                    - y3[10] * s[9]
+                    - y3[9] * s[10]
+                    - y3[8] * s[11]
+                    - y3[7] * s[12]
+                    - y3[6] * s[13]
+                    - y3[5] * s[14]
+                    - y3[4] * s[15]
+                    - y1[15] * y1[4]
+                    - y1[14] * y1[5]
+                    - y1[13] * y1[6]
+                    - y1[12] * y1[7]
+                    - y1[11] * y1[8]
+                    - y1[10] * y1[9]
+                    - y1[9] * y1[10]
+                    - y1[8] * y1[11]
+                    - y1[7] * y1[12]
+                    - y1[6] * y1[13]
+                    - y1[5] * y1[14]
+                    - y1[4] * y1[15]
+                    - x1[15] * x1[4]
+                    - x1[14] * x1[5]
+                    - x1[13] * x1[6]
+                    - x1[12] * x1[7]
+                    - x1[11] * x1[8]
+                    - x1[10] * x1[9]
+                    - x1[9] * x1[10]
+                    - x1[8] * x1[11]
+                    - x1[7] * x1[12]
+                    - x1[6] * x1[13]
+                    - x1[5] * x1[14]
+                    - x1[4] * x1[15]
+                    + 0x7FFF * q2[4]
+                    + 0xFFFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            20 => {
+                -y3[15] * s[5]
+                    - y3[14] * s[6]
+                    - y3[13] * s[7]
+                    - y3[12] * s[8]
+                    - y3[11] * s[9]
+                    - y3[10] * s[10]
+                    - y3[9] * s[11]
+                    - y3[8] * s[12]
+                    - y3[7] * s[13]
+                    - y3[6] * s[14]
+                    - y3[5] * s[15]
+                    - y1[15] * y1[5]
+                    - y1[14] * y1[6]
+                    - y1[13] * y1[7]
+                    - y1[12] * y1[8]
+                    - y1[11] * y1[9]
+                    - y1[10] * y1[10]
+                    - y1[9] * y1[11]
+                    - y1[8] * y1[12]
+                    - y1[7] * y1[13]
+                    - y1[6] * y1[14]
+                    - y1[5] * y1[15]
+                    - x1[15] * x1[5]
+                    - x1[14] * x1[6]
+                    - x1[13] * x1[7]
+                    - x1[12] * x1[8]
+                    - x1[11] * x1[9]
+                    - x1[10] * x1[10]
+                    - x1[9] * x1[11]
+                    - x1[8] * x1[12]
+                    - x1[7] * x1[13]
+                    - x1[6] * x1[14]
+                    - x1[5] * x1[15]
+                    + 0x7FFF * q2[5]
+                    + 0xFFFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            21 => {
+                -y3[15] * s[6]
+                    - y3[14] * s[7]
+                    - y3[13] * s[8]
+                    - y3[12] * s[9]
+                    - y3[11] * s[10]
+                    - y3[10] * s[11]
+                    - y3[9] * s[12]
+                    - y3[8] * s[13]
+                    - y3[7] * s[14]
+                    - y3[6] * s[15]
+                    - y1[15] * y1[6]
+                    - y1[14] * y1[7]
+                    - y1[13] * y1[8]
+                    - y1[12] * y1[9]
+                    - y1[11] * y1[10]
+                    - y1[10] * y1[11]
+                    - y1[9] * y1[12]
+                    - y1[8] * y1[13]
+                    - y1[7] * y1[14]
+                    - y1[6] * y1[15]
+                    - x1[15] * x1[6]
+                    - x1[14] * x1[7]
+                    - x1[13] * x1[8]
+                    - x1[12] * x1[9]
+                    - x1[11] * x1[10]
+                    - x1[10] * x1[11]
+                    - x1[9] * x1[12]
+                    - x1[8] * x1[13]
+                    - x1[7] * x1[14]
+                    - x1[6] * x1[15]
+                    + 0x7FFF * q2[6]
+                    + 0xFFFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            22 => {
+                -y3[15] * s[7]
+                    - y3[14] * s[8]
+                    - y3[13] * s[9]
+                    - y3[12] * s[10]
+                    - y3[11] * s[11]
+                    - y3[10] * s[12]
+                    - y3[9] * s[13]
+                    - y3[8] * s[14]
+                    - y3[7] * s[15]
+                    - y1[15] * y1[7]
+                    - y1[14] * y1[8]
+                    - y1[13] * y1[9]
+                    - y1[12] * y1[10]
+                    - y1[11] * y1[11]
+                    - y1[10] * y1[12]
+                    - y1[9] * y1[13]
+                    - y1[8] * y1[14]
+                    - y1[7] * y1[15]
+                    - x1[15] * x1[7]
+                    - x1[14] * x1[8]
+                    - x1[13] * x1[9]
+                    - x1[12] * x1[10]
+                    - x1[11] * x1[11]
+                    - x1[10] * x1[12]
+                    - x1[9] * x1[13]
+                    - x1[8] * x1[14]
+                    - x1[7] * x1[15]
+                    + 0x7FFF * q2[7]
+                    + 0xFFFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            23 => {
+                -y3[15] * s[8]
+                    - y3[14] * s[9]
+                    - y3[13] * s[10]
+                    - y3[12] * s[11]
+                    - y3[11] * s[12]
+                    - y3[10] * s[13]
+                    - y3[9] * s[14]
+                    - y3[8] * s[15]
+                    - y1[15] * y1[8]
+                    - y1[14] * y1[9]
+                    - y1[13] * y1[10]
+                    - y1[12] * y1[11]
+                    - y1[11] * y1[12]
+                    - y1[10] * y1[13]
+                    - y1[9] * y1[14]
+                    - y1[8] * y1[15]
+                    - x1[15] * x1[8]
+                    - x1[14] * x1[9]
+                    - x1[13] * x1[10]
+                    - x1[12] * x1[11]
+                    - x1[11] * x1[12]
+                    - x1[10] * x1[13]
+                    - x1[9] * x1[14]
+                    - x1[8] * x1[15]
+                    + 0x7FFF * q2[8]
+                    + 0xFFFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            24 => {
+                -y3[15] * s[9]
+                    - y3[14] * s[10]
+                    - y3[13] * s[11]
+                    - y3[12] * s[12]
+                    - y3[11] * s[13]
+                    - y3[10] * s[14]
+                    - y3[9] * s[15]
+                    - y1[15] * y1[9]
+                    - y1[14] * y1[10]
+                    - y1[13] * y1[11]
+                    - y1[12] * y1[12]
+                    - y1[11] * y1[13]
+                    - y1[10] * y1[14]
+                    - y1[9] * y1[15]
+                    - x1[15] * x1[9]
+                    - x1[14] * x1[10]
+                    - x1[13] * x1[11]
+                    - x1[12] * x1[12]
+                    - x1[11] * x1[13]
+                    - x1[10] * x1[14]
+                    - x1[9] * x1[15]
+                    + 0x7FFF * q2[9]
+                    + 0xFFFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            25 => {
+                -y3[15] * s[10]
+                    - y3[14] * s[11]
+                    - y3[13] * s[12]
+                    - y3[12] * s[13]
+                    - y3[11] * s[14]
+                    - y3[10] * s[15]
+                    - y1[15] * y1[10]
+                    - y1[14] * y1[11]
+                    - y1[13] * y1[12]
+                    - y1[12] * y1[13]
+                    - y1[11] * y1[14]
+                    - y1[10] * y1[15]
+                    - x1[15] * x1[10]
+                    - x1[14] * x1[11]
+                    - x1[13] * x1[12]
+                    - x1[12] * x1[13]
+                    - x1[11] * x1[14]
+                    - x1[10] * x1[15]
+                    + 0x7FFF * q2[10]
+                    + 0xFFFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            26 => {
+                -y3[15] * s[11]
+                    - y3[14] * s[12]
+                    - y3[13] * s[13]
+                    - y3[12] * s[14]
+                    - y3[11] * s[15]
+                    - y1[15] * y1[11]
+                    - y1[14] * y1[12]
+                    - y1[13] * y1[13]
+                    - y1[12] * y1[14]
+                    - y1[11] * y1[15]
+                    - x1[15] * x1[11]
+                    - x1[14] * x1[12]
+                    - x1[13] * x1[13]
+                    - x1[12] * x1[14]
+                    - x1[11] * x1[15]
+                    + 0x7FFF * q2[11]
+                    + 0xFFFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            27 => {
+                -y3[15] * s[12]
+                    - y3[14] * s[13]
+                    - y3[13] * s[14]
+                    - y3[12] * s[15]
+                    - y1[15] * y1[12]
+                    - y1[14] * y1[13]
+                    - y1[13] * y1[14]
+                    - y1[12] * y1[15]
+                    - x1[15] * x1[12]
+                    - x1[14] * x1[13]
+                    - x1[13] * x1[14]
+                    - x1[12] * x1[15]
+                    + 0x7FFF * q2[12]
+                    + 0xFFFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            28 => {
+                -y3[15] * s[13]
+                    - y3[14] * s[14]
+                    - y3[13] * s[15]
+                    - y1[15] * y1[13]
+                    - y1[14] * y1[14]
+                    - y1[13] * y1[15]
+                    - x1[15] * x1[13]
+                    - x1[14] * x1[14]
+                    - x1[13] * x1[15]
+                    + 0x7FFF * q2[13]
+                    + 0xFFFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            29 => {
+                -y3[15] * s[14]
+                    - y3[14] * s[15]
+                    - y1[15] * y1[14]
+                    - y1[14] * y1[15]
+                    - x1[15] * x1[14]
+                    - x1[14] * x1[15]
+                    + 0x7FFF * q2[14]
+                    + 0xFFFF * q2[15]
+                    - 0xFFFF
+            }
+            30 => -y3[15] * s[15] - y1[15] * y1[15] - x1[15] * x1[15] + 0x7FFF * q2[15] - 0xFFFF,
+            31 => -0xFFFF,
+            _ => 0,
+        }
+    }
+}
@@ -1,5 +1,14 @@
 mod arith256;
 mod arith256_mod;
+mod curve25519_mod;
+mod ed25519_add;
+mod ed25519_add_t;
+mod ed25519_add_u;
+mod ed25519_add_x3;
+mod ed25519_add_y3;
+mod ed25519_dbl;
+mod ed25519_dbl_x3;
+mod ed25519_dbl_y3;
 mod secp256k1_add;
 mod secp256k1_dbl;
 mod secp256k1_x3;
@@ -7,6 +16,15 @@ mod secp256k1_y3;
 
 pub use arith256::*;
 pub use arith256_mod::*;
+pub use curve25519_mod::*;
+pub use ed25519_add::*;
+pub use ed25519_add_t::*;
+pub use ed25519_add_u::*;
+pub use ed25519_add_x3::*;
+pub use ed25519_add_y3::*;
+pub use ed25519_dbl::*;
+pub use ed25519_dbl_x3::*;
+pub use ed25519_dbl_y3::*;
 pub use secp256k1_add::*;
 pub use secp256k1_dbl::*;
 pub use secp256k1_x3::*;
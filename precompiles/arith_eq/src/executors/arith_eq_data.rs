@@ -1,3 +1,5 @@
+use crate::arith_eq_constants::ARITH_EQ_MAX_CEQS;
+
 #[derive(Debug, Default)]
 pub struct ArithEqData {
     pub x1: [i64; 16],
@@ -7,11 +9,15 @@ pub struct ArithEqData {
     pub x3: [i64; 16],
     pub y3: [i64; 16],
     pub s: [i64; 16],
+    pub t: [i64; 16],
+    pub u: [i64; 16],
     pub q0: [i64; 16],
     pub q1: [i64; 16],
     pub q2: [i64; 16],
-    pub eq: [[i64; 3]; 32],
-    pub cout: [[i64; 3]; 31],
+    pub q3: [i64; 16],
+    pub q4: [i64; 16],
+    pub eq: [[i64; ARITH_EQ_MAX_CEQS]; 32],
+    pub cout: [[i64; ARITH_EQ_MAX_CEQS]; 31],
 }
 
 impl ArithEqData {
@@ -32,10 +38,14 @@ impl ArithEqData {
             assert!(self.x1[i] >= 0 && self.x1[i] <= MAX_16_BITS);
             assert!(self.x1[i] >= 0 && self.x1[i] <= MAX_16_BITS);
             assert!(self.s[i] >= 0 && self.s[i] <= max_range);
+            assert!(self.t[i] >= 0 && self.t[i] <= MAX_16_BITS);
+            assert!(self.u[i] >= 0 && self.u[i] <= MAX_16_BITS);
             assert!(self.q0[i] >= 0 && self.q0[i] <= max_range);
             assert!(self.q1[i] >= 0 && self.q1[i] <= max_range);
             assert!(self.q2[i] >= 0 && self.q2[i] <= max_range);
-            for j in 0..2 {
+            assert!(self.q3[i] >= 0 && self.q3[i] <= max_range);
+            assert!(self.q4[i] >= 0 && self.q4[i] <= max_range);
+            for j in 0..ARITH_EQ_MAX_CEQS {
                 assert!(
                     self.cout[i][j] >= MIN_CARRY && self.cout[i][j] <= MAX_CARRY,
                     "cout[{}][{}]:{} not in [{},{}]",
@@ -0,0 +1,378 @@
+use super::ArithEqData;
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_traits::Zero;
+use precompiles_helpers::{
+    bigint2_to_8_u64, bigint_from_u64s, bigint_to_16_chunks, bigint_to_4_u64, ed25519_d,
+    ed25519_inv, ed25519_prime,
+};
+
+use crate::{arith_eq_constants::ARITH_EQ_MAX_CEQS, equations};
+
+const COLS: u8 = 32;
+
+lazy_static! {
+    pub static ref ED25519_PRIME: BigInt = ed25519_prime();
+    pub static ref ED25519_DBL_Q0_OFFSET: BigInt = BigInt::from(1) << 258;
+    pub static ref ED25519_Q1_OFFSET: BigInt = BigInt::from(1) << 259;
+    pub static ref ED25519_Q2_OFFSET: BigInt = BigInt::from(1) << 257;
+    pub static ref ED25519_D_INV: BigInt = ed25519_inv(&ed25519_d());
+    pub static ref ED25519_ADD_Q2_OFFSET: BigInt = BigInt::from(1) << 256;
+    pub static ref ED25519_ADD_Q3_OFFSET: BigInt = BigInt::from(1) << 259;
+    pub static ref ED25519_ADD_Q4_OFFSET: BigInt = BigInt::from(1) << 259;
+}
+
+pub struct Curve25519Mod {}
+
+impl Curve25519Mod {
+    #[allow(dead_code)]
+    pub fn calculate(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4], d: &mut [u64; 4]) {
+        Self::prepare(a, b, c, Some(d));
+    }
+    fn prepare(
+        a: &[u64; 4],
+        b: &[u64; 4],
+        c: &[u64; 4],
+        d: Option<&mut [u64; 4]>,
+    ) -> Option<ArithEqData> {
+        let a = bigint_from_u64s(a);
+        let b = bigint_from_u64s(b);
+        let c = bigint_from_u64s(c);
+
+        // a (0..2^256-1) * b (0..2^256-1) + c (0..2^256-1) = res (0..2^512-2^256), so the
+        // quotient is always positive and lower than 2^258
+        let res = &a * &b + &c;
+        let q0 = &res / &*ED25519_PRIME;
+        let res = &res % &*ED25519_PRIME;
+
+        if let Some(d) = d {
+            bigint_to_4_u64(&res, d);
+            return None;
+        }
+        // x3 = mod(x1*y1+x2, p)
+        // a:x1 b:y1 c:x2 d:x3
+        let mut data = ArithEqData::default();
+        bigint_to_16_chunks(&a, &mut data.x1);
+        bigint_to_16_chunks(&b, &mut data.y1);
+        bigint_to_16_chunks(&c, &mut data.x2);
+        bigint_to_16_chunks(&res, &mut data.x3);
+        bigint_to_16_chunks(&q0, &mut data.q0);
+        Some(data)
+    }
+    pub fn execute(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4]) -> ArithEqData {
+        let mut data = Self::prepare(a, b, c, None).unwrap();
+        for icol in 0..COLS {
+            let index = icol as usize;
+            data.eq[index][0] = equations::Curve25519Mod::calculate(
+                icol, &data.x1, &data.y1, &data.x2, &data.x3, &data.q0,
+            );
+
+            let cin = if index > 0 { data.cout[index - 1][0] } else { 0 };
+            let value = data.eq[index][0] + cin;
+            if icol != COLS - 1 {
+                data.cout[index][0] = value / 0x10000;
+            }
+            debug_assert!(
+                0 == if icol == COLS - 1 { value } else { value % 0x10000 },
+                "Curve25519Mod residue ({}) #:{} cin:{}",
+                index,
+                value,
+                cin
+            );
+        }
+        data
+    }
+    #[cfg(feature = "test_data")]
+    #[allow(dead_code)]
+    pub fn verify(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4], d: &[u64; 4]) {
+        let data = Self::execute(a, b, c);
+        data.check_ranges();
+        for (i, chunk_d) in d.iter().enumerate() {
+            let offset = (i + 1) * 4 - 1;
+            let mut x3 = data.x3[offset] as u64;
+            for j in 1..4 {
+                x3 <<= 16;
+                x3 += data.x3[offset - j] as u64;
+            }
+            assert!(*chunk_d == x3, "Curve25519Mod d[{}]:{} not match with x3:{}", i, chunk_d, x3);
+        }
+    }
+}
+
+pub struct Ed25519 {}
+
+impl Ed25519 {
+    #[allow(dead_code)]
+    pub fn calculate_dbl(p1: &[u64; 8], p3: &mut [u64; 8]) {
+        Self::prepare_dbl(p1, Some(p3));
+    }
+
+    fn prepare_dbl(p1: &[u64; 8], p3: Option<&mut [u64; 8]>) -> Option<ArithEqData> {
+        let p = &*ED25519_PRIME;
+        let x1 = bigint_from_u64s(&p1[0..4]);
+        let y1 = bigint_from_u64s(&p1[4..8]);
+
+        // s = y1^2 - x1^2, x3 = 2·x1·y1 / s, y3 = (y1^2 + x1^2) / (2 - s)
+        let s = (((&y1 * &y1 - &x1 * &x1) % p) + p) % p;
+        let x3 = (2 * &x1 * &y1 * ed25519_inv(&s)) % p;
+        let y3 = ((&y1 * &y1 + &x1 * &x1) * ed25519_inv(&(2 - &s))) % p;
+
+        let _q0: BigInt = &y1 * &y1 - &x1 * &x1 - &s;
+        assert!((&_q0 % p).is_zero());
+        let q0 = &*ED25519_DBL_Q0_OFFSET - (&_q0 / p);
+
+        let _q1: BigInt = &x3 * &s - 2 * &x1 * &y1;
+        assert!((&_q1 % p).is_zero());
+        let q1 = (&_q1 / p) + &*ED25519_Q1_OFFSET;
+
+        let _q2: BigInt = 2 * &y3 - &y3 * &s - &y1 * &y1 - &x1 * &x1;
+        assert!((&_q2 % p).is_zero());
+        let q2 = &*ED25519_Q2_OFFSET - (&_q2 / p);
+
+        if let Some(p3) = p3 {
+            bigint2_to_8_u64(&x3, &y3, p3);
+            return None;
+        }
+
+        let mut data = ArithEqData::default();
+        bigint_to_16_chunks(&q0, &mut data.q0);
+        bigint_to_16_chunks(&q1, &mut data.q1);
+        bigint_to_16_chunks(&q2, &mut data.q2);
+        bigint_to_16_chunks(&s, &mut data.s);
+        bigint_to_16_chunks(&x1, &mut data.x1);
+        bigint_to_16_chunks(&y1, &mut data.y1);
+        bigint_to_16_chunks(&x3, &mut data.x3);
+        bigint_to_16_chunks(&y3, &mut data.y3);
+        Some(data)
+    }
+
+    pub fn execute_dbl(p1: &[u64; 8]) -> ArithEqData {
+        let mut data = Self::prepare_dbl(p1, None).unwrap();
+        for icol in 0..COLS {
+            let index = icol as usize;
+            data.eq[index][..3].copy_from_slice(&[
+                equations::Ed25519Dbl::calculate(icol, &data.x1, &data.y1, &data.s, &data.q0),
+                equations::Ed25519DblX3::calculate(
+                    icol, &data.x1, &data.y1, &data.x3, &data.s, &data.q1,
+                ),
+                equations::Ed25519DblY3::calculate(
+                    icol, &data.x1, &data.y1, &data.y3, &data.s, &data.q2,
+                ),
+            ]);
+            for ieq in 0..3 {
+                let cin = if index > 0 { data.cout[index - 1][ieq] } else { 0 };
+                let value = data.eq[index][ieq] + cin;
+                if icol != COLS - 1 {
+                    data.cout[index][ieq] = value / 0x10000;
+                }
+                debug_assert!(
+                    0 == if icol == COLS - 1 { value } else { value % 0x10000 },
+                    "EqEd25519 residue eq{} ({}) #:{} cin:{}",
+                    ieq,
+                    index,
+                    value,
+                    cin
+                );
+            }
+        }
+        data
+    }
+
+    #[allow(dead_code)]
+    pub fn calculate_add(p1: &[u64; 8], p2: &[u64; 8], p3: &mut [u64; 8]) {
+        Self::prepare_add(p1, p2, Some(p3));
+    }
+
+    fn prepare_add(p1: &[u64; 8], p2: &[u64; 8], p3: Option<&mut [u64; 8]>) -> Option<ArithEqData> {
+        let p = &*ED25519_PRIME;
+        let x1 = bigint_from_u64s(&p1[0..4]);
+        let y1 = bigint_from_u64s(&p1[4..8]);
+        let x2 = bigint_from_u64s(&p2[0..4]);
+        let y2 = bigint_from_u64s(&p2[4..8]);
+
+        // s = x1·y1, t = x2·y2, u = d·s·t, x3 = (x1·y2 + y1·x2) / (1 + u),
+        // y3 = (y1·y2 + x1·x2) / (1 - u)
+        let s = (&x1 * &y1) % p;
+        let t = (&x2 * &y2) % p;
+        let u = (ed25519_d() * &s * &t) % p;
+        let x3 = ((&x1 * &y2 + &y1 * &x2) * ed25519_inv(&(1 + &u))) % p;
+        let y3 = ((&y1 * &y2 + &x1 * &x2) * ed25519_inv(&(1 - &u))) % p;
+
+        let _q0: BigInt = &x1 * &y1 - &s;
+        assert!((&_q0 % p).is_zero());
+        let q0 = &_q0 / p;
+
+        let _q1: BigInt = &x2 * &y2 - &t;
+        assert!((&_q1 % p).is_zero());
+        let q1 = &_q1 / p;
+
+        let _q2: BigInt = &*ED25519_D_INV * &u - &s * &t;
+        assert!((&_q2 % p).is_zero());
+        let q2 = &*ED25519_ADD_Q2_OFFSET - (&_q2 / p);
+
+        let _q3: BigInt = &x3 + &x3 * &u - &x1 * &y2 - &y1 * &x2;
+        assert!((&_q3 % p).is_zero());
+        let q3 = &*ED25519_ADD_Q3_OFFSET - (&_q3 / p);
+
+        let _q4: BigInt = &y3 - &y3 * &u - &y1 * &y2 - &x1 * &x2;
+        assert!((&_q4 % p).is_zero());
+        let q4 = &*ED25519_ADD_Q4_OFFSET - (&_q4 / p);
+
+        if let Some(p3) = p3 {
+            bigint2_to_8_u64(&x3, &y3, p3);
+            return None;
+        }
+
+        let mut data = ArithEqData::default();
+        bigint_to_16_chunks(&q0, &mut data.q0);
+        bigint_to_16_chunks(&q1, &mut data.q1);
+        bigint_to_16_chunks(&q2, &mut data.q2);
+        bigint_to_16_chunks(&q3, &mut data.q3);
+        bigint_to_16_chunks(&q4, &mut data.q4);
+        bigint_to_16_chunks(&s, &mut data.s);
+        bigint_to_16_chunks(&t, &mut data.t);
+        bigint_to_16_chunks(&u, &mut data.u);
+        bigint_to_16_chunks(&x1, &mut data.x1);
+        bigint_to_16_chunks(&y1, &mut data.y1);
+        bigint_to_16_chunks(&x2, &mut data.x2);
+        bigint_to_16_chunks(&y2, &mut data.y2);
+        bigint_to_16_chunks(&x3, &mut data.x3);
+        bigint_to_16_chunks(&y3, &mut data.y3);
+        Some(data)
+    }
+
+    pub fn execute_add(p1: &[u64; 8], p2: &[u64; 8]) -> ArithEqData {
+        let mut data = Self::prepare_add(p1, p2, None).unwrap();
+        for icol in 0..COLS {
+            let index = icol as usize;
+            data.eq[index] = [
+                equations::Ed25519Add::calculate(icol, &data.x1, &data.y1, &data.s, &data.q0),
+                equations::Ed25519AddT::calculate(icol, &data.x2, &data.y2, &data.t, &data.q1),
+                equations::Ed25519AddU::calculate(icol, &data.s, &data.t, &data.u, &data.q2),
+                equations::Ed25519AddX3::calculate(
+                    icol, &data.x1, &data.y1, &data.x2, &data.y2, &data.x3, &data.u, &data.q3,
+                ),
+                equations::Ed25519AddY3::calculate(
+                    icol, &data.x1, &data.y1, &data.x2, &data.y2, &data.y3, &data.u, &data.q4,
+                ),
+            ];
+            for ieq in 0..ARITH_EQ_MAX_CEQS {
+                let cin = if index > 0 { data.cout[index - 1][ieq] } else { 0 };
+                let value = data.eq[index][ieq] + cin;
+                if icol != COLS - 1 {
+                    data.cout[index][ieq] = value / 0x10000;
+                }
+                debug_assert!(
+                    0 == if icol == COLS - 1 { value } else { value % 0x10000 },
+                    "EqEd25519Add residue eq{} ({}) #:{} cin:{}",
+                    ieq,
+                    index,
+                    value,
+                    cin
+                );
+            }
+        }
+        data
+    }
+
+    #[cfg(feature = "test_data")]
+    #[allow(dead_code)]
+    pub fn verify_dbl(p1: &[u64; 8], p: &[u64; 8]) {
+        let data = Self::execute_dbl(p1);
+        data.check_ranges();
+        for i in 0..4 {
+            let offset = (i + 1) * 4 - 1;
+            let mut x3 = data.x3[offset] as u64;
+            let mut y3 = data.y3[offset] as u64;
+            for j in 1..4 {
+                x3 <<= 16;
+                y3 <<= 16;
+                x3 += data.x3[offset - j] as u64;
+                y3 += data.y3[offset - j] as u64;
+            }
+            assert!(p[i] == x3, "Ed25519Dbl p[{}]:{} not match with x3:{}", i, p[i], x3);
+            assert!(
+                p[i + 4] == y3,
+                "Ed25519Dbl p[{}]:{} not match with y3:{}",
+                i + 4,
+                p[i + 4],
+                y3
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const B: [u64; 8] = [
+        0xc9562d608f25d51a,
+        0x692cc7609525a7b2,
+        0xc0a4e231fdd6dc5c,
+        0x216936d3cd6e53fe,
+        0x6666666666666658,
+        0x6666666666666666,
+        0x6666666666666666,
+        0x6666666666666666,
+    ];
+    const IDENTITY: [u64; 8] = [0, 0, 0, 0, 1, 0, 0, 0];
+
+    /// Checks that the quotients and carries of `data` fit the trace columns
+    fn assert_in_range(data: &ArithEqData) {
+        for i in 0..16 {
+            let q_max = if i == 15 { 0xF_FFFF } else { 0xFFFF };
+            for q in [&data.q0, &data.q1, &data.q2, &data.q3, &data.q4] {
+                assert!(q[i] >= 0 && q[i] <= q_max, "q[{i}] = {} out of range", q[i]);
+            }
+        }
+        for cout in data.cout.iter().flatten() {
+            assert!((-0x3F_FFFF..=0x40_0000).contains(cout), "carry {cout} out of range");
+        }
+    }
+
+    #[test]
+    fn test_execute_add() {
+        // Multiples of the generator, plus the neutral element
+        let mut points = vec![IDENTITY, B];
+        for _ in 0..20 {
+            let mut p = [0u64; 8];
+            Ed25519::calculate_add(points.last().unwrap(), &B, &mut p);
+            points.push(p);
+        }
+
+        for p1 in points.iter() {
+            for p2 in points.iter() {
+                let mut expected = [0u64; 8];
+                precompiles_helpers::ed25519_add(p1, p2, &mut expected);
+                let mut p3 = [0u64; 8];
+                Ed25519::calculate_add(p1, p2, &mut p3);
+                assert_eq!(p3, expected);
+
+                assert_in_range(&Ed25519::execute_add(p1, p2));
+            }
+        }
+
+        // Adding a point to itself is a doubling
+        let mut dbl = [0u64; 8];
+        Ed25519::calculate_dbl(&B, &mut dbl);
+        assert_eq!(dbl, points[2]);
+    }
+
+    #[test]
+    fn test_execute_add_unreduced() {
+        // Coordinates close to 2^256 give the largest quotients and carries
+        let big = [
+            u64::MAX - 5,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX - 7,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+        ];
+        assert_in_range(&Ed25519::execute_add(&big, &big));
+        assert_in_range(&Ed25519::execute_add(&big, &B));
+    }
+}
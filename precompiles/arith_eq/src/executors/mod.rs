@@ -1,6 +1,7 @@
 pub(crate) mod arith256;
 pub(crate) mod arith256_mod;
 pub(crate) mod arith_eq_data;
+pub(crate) mod ed25519;
 pub(crate) mod secp256k1;
 
 #[allow(unused_imports)]
@@ -8,6 +9,8 @@ pub use arith256::*;
 #[allow(unused_imports)]
 pub use arith256_mod::*;
 #[allow(unused_imports)]
+pub use ed25519::*;
+#[allow(unused_imports)]
 pub use secp256k1::*;
 
 pub use arith_eq_data::*;
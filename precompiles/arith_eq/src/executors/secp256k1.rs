@@ -115,7 +115,7 @@ impl Secp256k1 {
         let mut data = Self::prepare(is_dbl, p1, p2, None).unwrap();
         for icol in 0..COLS {
             let index = icol as usize;
-            data.eq[index] = [
+            data.eq[index][..3].copy_from_slice(&[
                 if is_dbl {
                     equations::Secp256k1Dbl::calculate(icol, &data.x1, &data.y1, &data.s, &data.q0)
                 } else {
@@ -129,7 +129,7 @@ impl Secp256k1 {
                 equations::Secp256k1Y3::calculate(
                     icol, &data.x1, &data.y1, &data.x3, &data.y3, &data.s, &data.q2,
                 ),
-            ];
+            ]);
             for ieq in 0..3 {
                 let cin = if index > 0 { data.cout[index - 1][ieq] } else { 0 };
                 let value = data.eq[index][ieq] + cin;
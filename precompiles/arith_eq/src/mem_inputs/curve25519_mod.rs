@@ -0,0 +1,33 @@
+use super::ArithEqMemInputConfig;
+use crate::executors::Curve25519Mod;
+use zisk_common::BusId;
+
+pub const CURVE25519_MOD_MEM_CONFIG: ArithEqMemInputConfig = ArithEqMemInputConfig {
+    indirect_params: 4,
+    rewrite_params: false,
+    read_params: 3,
+    write_params: 1,
+    chunks_per_param: 4,
+};
+pub fn generate_curve25519_mod_mem_inputs(
+    addr_main: u32,
+    step_main: u64,
+    data: &[u64],
+    only_counters: bool,
+) -> Option<Vec<(BusId, Vec<u64>)>> {
+    // op,op_type,a,b,addr[4],...
+    let a: &[u64; 4] = &data[8..12].try_into().unwrap();
+    let b: &[u64; 4] = &data[12..16].try_into().unwrap();
+    let c: &[u64; 4] = &data[16..20].try_into().unwrap();
+    let mut d: [u64; 4] = [0u64; 4];
+
+    Curve25519Mod::calculate(a, b, c, &mut d);
+    Some(super::generate_mem_inputs(
+        addr_main,
+        step_main,
+        data,
+        Some(&d),
+        only_counters,
+        &CURVE25519_MOD_MEM_CONFIG,
+    ))
+}
@@ -0,0 +1,33 @@
+use super::ArithEqMemInputConfig;
+use crate::executors::Ed25519;
+use zisk_common::BusId;
+
+pub const ED25519_ADD_MEM_CONFIG: ArithEqMemInputConfig = ArithEqMemInputConfig {
+    indirect_params: 2,
+    rewrite_params: true,
+    read_params: 2,
+    write_params: 1,
+    chunks_per_param: 8,
+};
+
+pub fn generate_ed25519_add_mem_inputs(
+    addr_main: u32,
+    step_main: u64,
+    data: &[u64],
+    only_counters: bool,
+) -> Option<Vec<(BusId, Vec<u64>)>> {
+    // op,op_type,a,b,addr[2],...
+    let p1: &[u64; 8] = &data[6..14].try_into().unwrap();
+    let p2: &[u64; 8] = &data[14..22].try_into().unwrap();
+    let mut p3 = [0u64; 8];
+
+    Ed25519::calculate_add(p1, p2, &mut p3);
+    Some(super::generate_mem_inputs(
+        addr_main,
+        step_main,
+        data,
+        Some(&p3),
+        only_counters,
+        &ED25519_ADD_MEM_CONFIG,
+    ))
+}
@@ -0,0 +1,32 @@
+use super::ArithEqMemInputConfig;
+use crate::executors::Ed25519;
+use zisk_common::BusId;
+
+pub const ED25519_DBL_MEM_CONFIG: ArithEqMemInputConfig = ArithEqMemInputConfig {
+    indirect_params: 0,
+    rewrite_params: true,
+    read_params: 1,
+    write_params: 1,
+    chunks_per_param: 8,
+};
+
+pub fn generate_ed25519_dbl_mem_inputs(
+    addr_main: u32,
+    step_main: u64,
+    data: &[u64],
+    only_counters: bool,
+) -> Option<Vec<(BusId, Vec<u64>)>> {
+    // op,op_type,a,b,...
+    let p1: &[u64; 8] = &data[4..12].try_into().unwrap();
+    let mut p3 = [0u64; 8];
+
+    Ed25519::calculate_dbl(p1, &mut p3);
+    Some(super::generate_mem_inputs(
+        addr_main,
+        step_main,
+        data,
+        Some(&p3),
+        only_counters,
+        &ED25519_DBL_MEM_CONFIG,
+    ))
+}
@@ -1,11 +1,17 @@
 mod arith256;
 mod arith256_mod;
+mod curve25519_mod;
+mod ed25519_add;
+mod ed25519_dbl;
 mod generate_mem_inputs;
 mod secp256k1_add;
 mod secp256k1_dbl;
 
 pub use arith256::*;
 pub use arith256_mod::*;
+pub use curve25519_mod::*;
+pub use ed25519_add::*;
+pub use ed25519_dbl::*;
 pub use generate_mem_inputs::*;
 pub use secp256k1_add::*;
 pub use secp256k1_dbl::*;
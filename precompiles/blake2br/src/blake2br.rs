@@ -0,0 +1,619 @@
+use core::panic;
+use std::sync::Arc;
+
+use log::info;
+use p3_field::PrimeField64;
+
+use circuit::{Gate, GateOperation, PinId};
+use precompiles_common::MemBusHelpers;
+use precompiles_helpers::{blake2b_round, blake2br_topology};
+use proofman_common::{AirInstance, FromTrace, SetupCtx};
+use proofman_util::{timer_start_trace, timer_stop_and_log_trace};
+use zisk_common::{ExtOperationData, OperationBlake2brData, OperationBusData, PayloadType};
+use zisk_pil::{Blake2brFixed, Blake2brTrace, Blake2brTraceRow};
+
+use super::{blake2br_constants::*, Blake2brTableGateOp, Blake2brTableSM};
+
+use rayon::prelude::*;
+
+/// The `Blake2brSM` struct encapsulates the logic of the Blake2br State Machine.
+pub struct Blake2brSM {
+    /// Reference to the Blake2br Table State Machine.
+    blake2br_table_sm: Arc<Blake2brTableSM>,
+
+    /// The circuit description of the Blake2br
+    program: Vec<u64>,
+    gates: Vec<Gate>,
+
+    /// Size of a slot in the trace. It corresponds to the number of gates in the circuit.
+    slot_size: usize,
+
+    /// Number of available slots in the trace.
+    num_available_slots: usize,
+
+    /// Number of available blake2brs in the trace.
+    pub num_available_blake2brs: usize,
+}
+
+type Blake2brInput = [u64; INPUT_DATA_SIZE_BITS];
+
+impl Blake2brSM {
+    const MY_NAME: &'static str = "Blake2br ";
+
+    pub const NUM_BLAKE2BR_PER_SLOT: usize = CHUNKS_BLAKE2BR * BITS_BLAKE2BR;
+
+    const RB_SIZE: usize = Self::NUM_BLAKE2BR_PER_SLOT * RB;
+    const IN_SIZE: usize = Self::NUM_BLAKE2BR_PER_SLOT * INPUT_DATA_SIZE_BITS / BITS_IN_PARALLEL;
+
+    /// Creates a new Blake2br State Machine instance.
+    ///
+    /// # Arguments
+    /// * `blake2br_table_sm` - An `Arc`-wrapped reference to the Blake2br Table State Machine.
+    ///
+    /// # Returns
+    /// A new `Blake2brSM` instance.
+    pub fn new(blake2br_table_sm: Arc<Blake2brTableSM>) -> Arc<Self> {
+        // Get the slot size
+        let blake2br_top = blake2br_topology();
+        let blake2br_program = blake2br_top.program;
+        let blake2br_gates = blake2br_top.gates;
+        let slot_size = blake2br_program.len();
+
+        // Compute some useful values
+        let num_available_slots = (Blake2brTrace::<usize>::NUM_ROWS - 1) / slot_size;
+        let num_available_blake2brs = Self::NUM_BLAKE2BR_PER_SLOT * num_available_slots;
+
+        Arc::new(Self {
+            blake2br_table_sm,
+            program: blake2br_program,
+            gates: blake2br_gates,
+            slot_size,
+            num_available_slots,
+            num_available_blake2brs,
+        })
+    }
+
+    /// Processes a slice of operation data, updating the trace and multiplicities.
+    ///
+    /// # Arguments
+    /// * `trace` - A mutable reference to the Blake2br trace.
+    /// * `num_slots` - The number of slots to process.
+    /// * `input` - The operation data to process.
+    /// * `multiplicity` - A mutable slice to update with multiplicities for the operation.
+    #[inline(always)]
+    pub fn process_slice<F: PrimeField64>(
+        &self,
+        fixed: &Blake2brFixed<F>,
+        trace: &mut Blake2brTrace<F>,
+        num_rows_constants: usize,
+        inputs: &[OperationBlake2brData<u64>],
+    ) {
+        let num_inputs = inputs.len();
+        let mut inputs_bits: Vec<Blake2brInput> =
+            vec![[0u64; INPUT_DATA_SIZE_BITS]; self.num_available_slots];
+
+        // Process the inputs
+        let initial_offset = num_rows_constants;
+        let input_offset = Self::IN_SIZE; // Length of the input data
+        inputs.iter().enumerate().for_each(|(i, input)| {
+            let input_data = ExtOperationData::OperationBlake2brData(*input);
+
+            // Get the basic data from the input
+            let step_received = OperationBusData::get_a(&input_data);
+            let addr_received = OperationBusData::get_b(&input_data);
+
+            // Get the raw blake2br input as 32 u64 values: the working vector followed by the
+            // message words already permuted for the round
+            let blake2br_input: [u64; INPUT_DATA_SIZE_U64] =
+                OperationBusData::get_extra_data(&input_data).try_into().unwrap();
+
+            let slot = i / Self::NUM_BLAKE2BR_PER_SLOT;
+            let slot_pos = i % Self::NUM_BLAKE2BR_PER_SLOT;
+            let slot_offset = slot * self.slot_size;
+
+            // Update the multiplicity for the input
+            let initial_pos = initial_offset + slot_offset + slot_pos;
+            trace[initial_pos].multiplicity = F::ONE; // The pair (step_received, addr_received) is unique each time, so its multiplicity is 1
+
+            // Process the blake2br input
+            blake2br_input.iter().enumerate().for_each(|(j, &value)| {
+                let chunk_offset = j * Self::RB_SIZE;
+                let pos = initial_pos + chunk_offset;
+
+                // At the beginning of each 64-bit chunk, we set the step and address
+                trace[pos].step = F::from_u64(step_received);
+                trace[pos].addr = F::from_u64(addr_received + 8 * j as u64);
+                trace[pos].is_val = F::ONE;
+
+                // Process the 64-bit chunk
+                for k in 0..64 {
+                    // Divide the value in bits:
+                    //    (slot i) [0b1011,  0b0011,  0b1000,  0b0010]
+                    //    (slot i) [1,1,0,1, 1,1,0,0, 0,0,0,1, 0,0,1,0]
+                    let bit_pos = k + 64 * j;
+                    let old_value = inputs_bits[slot][bit_pos];
+                    let new_bit = (value >> k) & 1;
+                    inputs_bits[slot][bit_pos] = (new_bit << slot_pos) | old_value;
+
+                    // We update bit[i] and val[i]
+                    let bit_pos = k % BITS_IN_PARALLEL;
+                    let bit_offset = (k - bit_pos) * Self::NUM_BLAKE2BR_PER_SLOT / BITS_IN_PARALLEL;
+                    update_bit_val(fixed, trace, pos + bit_offset, new_bit, slot_pos, bit_pos);
+                }
+            });
+
+            // Apply the blake2b round and get the output
+            let blake2br_output = apply_round(&blake2br_input);
+
+            // Process the output
+            blake2br_output.iter().enumerate().for_each(|(j, &value)| {
+                let chunk_offset = j * Self::RB_SIZE;
+                let pos = initial_pos + input_offset + chunk_offset;
+
+                // At the beginning of each 64-bit chunk, we set the step and address
+                trace[pos].step = F::from_u64(step_received);
+                trace[pos].addr = F::from_u64(addr_received + 8 * j as u64);
+                trace[pos].is_val = F::ONE;
+
+                // Process the 64-bit chunk
+                for k in 0..64 {
+                    // We update bit[i] and val[i]
+                    let new_bit = (value >> k) & 1;
+                    let bit_pos = k % BITS_IN_PARALLEL;
+                    let bit_offset = (k - bit_pos) * Self::NUM_BLAKE2BR_PER_SLOT / BITS_IN_PARALLEL;
+                    update_bit_val(fixed, trace, pos + bit_offset, new_bit, slot_pos, bit_pos);
+                }
+            });
+
+            // At the end of the outputs, we set the next step and address for the constraints to be satisfied
+            let final_pos =
+                initial_pos + input_offset + (blake2br_output.len() - 1) * Self::RB_SIZE;
+            trace[final_pos + Self::RB_SIZE].step = trace[final_pos].step;
+            trace[final_pos + Self::RB_SIZE].addr = trace[final_pos].addr
+        });
+
+        // It the number of inputs is less than the available blake2brs, we need to fill the remaining inputs
+        if num_inputs < self.num_available_blake2brs {
+            // Compute the round of zero
+            let zero_output = apply_round(&[0u64; INPUT_DATA_SIZE_U64]);
+
+            // If the number of inputs is not a multiple of NUM_BLAKE2BR_PER_SLOT,
+            // we fill the last processed slot
+            let rem_inputs = num_inputs % Self::NUM_BLAKE2BR_PER_SLOT;
+            if rem_inputs != 0 {
+                let last_slot = (num_inputs - 1) / Self::NUM_BLAKE2BR_PER_SLOT;
+                let slot_offset = last_slot * self.slot_size;
+                // Since no more bits are being introduced as input, we let 0 be the
+                // new bits and therefore we repeat the last values
+                for j in 0..INPUT_DATA_SIZE_BITS / BITS_IN_PARALLEL {
+                    let block_offset = j * Self::NUM_BLAKE2BR_PER_SLOT;
+                    for k in rem_inputs..Self::NUM_BLAKE2BR_PER_SLOT {
+                        let pos = initial_offset + slot_offset + block_offset + k;
+                        for l in 0..BITS_IN_PARALLEL {
+                            // trace[pos+1].bit[l] = F::ZERO;
+                            trace[pos + 1].val[l] = trace[pos].val[l];
+                        }
+                    }
+                }
+
+                let initial_pos = initial_offset + slot_offset;
+                // Since the new bits are all zero, we have to set the round of 0 as the respective output
+                zero_output.iter().enumerate().for_each(|(j, &value)| {
+                    let chunk_offset = j * Self::RB_SIZE;
+                    let pos = initial_pos + input_offset + chunk_offset;
+                    for k in 0..64 {
+                        let new_bit = (value >> k) & 1;
+                        let bit_pos = k % BITS_IN_PARALLEL;
+                        let bit_offset =
+                            (k - bit_pos) * Self::NUM_BLAKE2BR_PER_SLOT / BITS_IN_PARALLEL;
+                        for w in rem_inputs..Self::NUM_BLAKE2BR_PER_SLOT {
+                            update_bit_val(fixed, trace, pos + bit_offset + w, new_bit, w, bit_pos);
+                        }
+                    }
+                });
+            }
+
+            // Fill the remaining slots with the round of 0
+            let next_slot = num_inputs.div_ceil(Self::NUM_BLAKE2BR_PER_SLOT);
+            zero_output.iter().enumerate().for_each(|(j, &value)| {
+                for s in next_slot..self.num_available_slots {
+                    let slot_offset = s * self.slot_size;
+                    let chunk_offset = j * Self::RB_SIZE;
+                    for k in 0..64 {
+                        let new_bit = (value >> k) & 1;
+                        let bit_pos = k % BITS_IN_PARALLEL;
+                        let bit_offset =
+                            (k - bit_pos) * Self::NUM_BLAKE2BR_PER_SLOT / BITS_IN_PARALLEL;
+                        let pos =
+                            initial_offset + slot_offset + input_offset + chunk_offset + bit_offset;
+                        for w in 0..Self::NUM_BLAKE2BR_PER_SLOT {
+                            update_bit_val(fixed, trace, pos + w, new_bit, w, bit_pos);
+                        }
+                    }
+                }
+            });
+        }
+
+        // Set the values of free_in_a, free_in_b, free_in_c
+        let program = &self.program;
+        let gates = &self.gates;
+
+        let row0 = trace.buffer[0];
+
+        let mut trace_slice = &mut trace.buffer[1..];
+        let mut par_traces = Vec::new();
+
+        for _ in 0..inputs_bits.len() {
+            // while !par_traces.is_empty() {
+            let take = self.slot_size.min(trace_slice.len());
+            let (head, tail) = trace_slice.split_at_mut(take);
+            par_traces.push(head);
+            trace_slice = tail;
+        }
+
+        par_traces.into_par_iter().enumerate().for_each(|(i, par_trace)| {
+            for &line in program.iter() {
+                let line = line as usize;
+                let row = line - 1;
+                let gate = &gates[line];
+
+                // Set the value of free_in_a
+                let a = &gate.pins[0];
+                let ref_a = a.wired_ref as usize;
+                let row_a = ref_a - 1;
+                let wired_a = a.wired_pin_id;
+                let value_a;
+                // If the reference is in the range of the inputs
+                // and the wired pin is A (inputs are located at pin A),
+                // we can get the value directly from the inputs
+                if (STATE_IN_REF_0
+                    ..=STATE_IN_REF_0
+                        + (STATE_IN_NUMBER - STATE_IN_GROUP_BY) * STATE_IN_REF_DISTANCE
+                            / STATE_IN_GROUP_BY
+                        + (STATE_IN_GROUP_BY - 1))
+                    .contains(&ref_a)
+                    && ((ref_a - STATE_IN_REF_0) % STATE_IN_REF_DISTANCE < STATE_IN_GROUP_BY)
+                    && matches!(wired_a, PinId::A)
+                {
+                    let s = ref_a - STATE_IN_REF_0;
+                    let bit_a =
+                        s / STATE_IN_REF_DISTANCE * STATE_IN_GROUP_BY + s % STATE_IN_GROUP_BY;
+                    value_a = inputs_bits[i][bit_a];
+                } else
+                // Otherwise, we get one of the already computed values
+                {
+                    match wired_a {
+                        PinId::A => {
+                            value_a = if ref_a > 0 {
+                                get_col(par_trace, |row| &row.free_in_a, row_a)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_a)
+                            };
+                        }
+                        PinId::B => {
+                            value_a = if ref_a > 0 {
+                                get_col(par_trace, |row| &row.free_in_b, row_a)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_b)
+                            };
+                        }
+                        PinId::C => panic!("Input pin C is not used by the Blake2br circuit"),
+                        PinId::D => {
+                            value_a = if ref_a > 0 {
+                                get_col(par_trace, |row| &row.free_in_c, row_a)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_c)
+                            };
+                        }
+                    }
+                }
+                set_col(par_trace, |row| &mut row.free_in_a, row, value_a);
+
+                // Set the value of free_in_b
+                let b = &gate.pins[1];
+                let ref_b = b.wired_ref as usize;
+                let row_b = ref_b - 1;
+                let wired_b = b.wired_pin_id;
+                let value_b;
+                // If the reference is in the range of the inputs
+                // and the wired pin is A (inputs are located at pin A),
+                // we can get the value directly from the inputs
+                if (STATE_IN_REF_0
+                    ..=STATE_IN_REF_0
+                        + (STATE_IN_NUMBER - STATE_IN_GROUP_BY) * STATE_IN_REF_DISTANCE
+                            / STATE_IN_GROUP_BY
+                        + (STATE_IN_GROUP_BY - 1))
+                    .contains(&ref_b)
+                    && ((ref_b - STATE_IN_REF_0) % STATE_IN_REF_DISTANCE < STATE_IN_GROUP_BY)
+                    && matches!(wired_b, PinId::A)
+                {
+                    let s = ref_b - STATE_IN_REF_0;
+                    let bit_b =
+                        s / STATE_IN_REF_DISTANCE * STATE_IN_GROUP_BY + s % STATE_IN_GROUP_BY;
+                    value_b = inputs_bits[i][bit_b];
+                } else
+                // Otherwise, we get one of the already computed values
+                {
+                    match wired_b {
+                        PinId::A => {
+                            value_b = if ref_b > 0 {
+                                get_col(par_trace, |row| &row.free_in_a, row_b)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_a)
+                            };
+                        }
+                        PinId::B => {
+                            value_b = if ref_b > 0 {
+                                get_col(par_trace, |row| &row.free_in_b, row_b)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_b)
+                            };
+                        }
+                        PinId::C => panic!("Input pin C is not used by the Blake2br circuit"),
+                        PinId::D => {
+                            value_b = if ref_b > 0 {
+                                get_col(par_trace, |row| &row.free_in_c, row_b)
+                            } else {
+                                get_col_row(&row0, |row| &row.free_in_c)
+                            };
+                        }
+                    }
+                }
+                set_col(par_trace, |row| &mut row.free_in_b, row, value_b);
+
+                // Set the value of free_in_c as value_a OP value_b
+                let op = gate.op;
+                let c_val = match op {
+                    GateOperation::Xor => value_a ^ value_b,
+                    GateOperation::Andp => (value_a ^ MASK_CHUNK_BITS_BLAKE2BR) & value_b,
+                    _ => panic!("Invalid operation"),
+                };
+                set_col(par_trace, |row| &mut row.free_in_c, row, c_val);
+            }
+
+            // Update the multiplicity table for the slot
+            for k in 0..self.slot_size {
+                let a = par_trace[k].free_in_a;
+                let b = par_trace[k].free_in_b;
+                let gate_op = fixed[k + 1 + i * self.slot_size].GATE_OP;
+                let gate_op_val = match F::as_canonical_u64(&gate_op) {
+                    0u64 => Blake2brTableGateOp::Xor,
+                    1u64 => Blake2brTableGateOp::Andp,
+                    _ => panic!("Invalid gate operation"),
+                };
+                for j in 0..CHUNKS_BLAKE2BR {
+                    let a_val = F::as_canonical_u64(&a[j]);
+                    let b_val = F::as_canonical_u64(&b[j]);
+                    let table_row =
+                        Blake2brTableSM::calculate_table_row(&gate_op_val, a_val, b_val);
+                    self.blake2br_table_sm.update_input(table_row, 1);
+                }
+            }
+        });
+
+        fn update_bit_val<F: PrimeField64>(
+            fixed: &Blake2brFixed<F>,
+            trace: &mut Blake2brTrace<F>,
+            pos: usize,
+            new_bit: u64,
+            slot_pos: usize,
+            bit_pos: usize,
+        ) {
+            trace[pos].bit[bit_pos] = F::from_u64(new_bit);
+            trace[pos + 1].val[bit_pos] = if fixed[pos].latch_num_blake2br == F::ZERO {
+                trace[pos].val[bit_pos] + F::from_u64(new_bit << slot_pos)
+            } else {
+                F::from_u64(new_bit << slot_pos)
+            };
+        }
+
+        fn set_col<F: PrimeField64>(
+            trace: &mut [Blake2brTraceRow<F>],
+            cols: impl Fn(&mut Blake2brTraceRow<F>) -> &mut [F; CHUNKS_BLAKE2BR],
+            index: usize,
+            value: u64,
+        ) {
+            let mut _value = value;
+            let row = &mut trace[index];
+            let cols = cols(row);
+            for col in cols.iter_mut() {
+                *col = F::from_u64(_value & MASK_BITS_BLAKE2BR);
+                _value >>= BITS_BLAKE2BR;
+            }
+        }
+
+        fn get_col<F: PrimeField64>(
+            trace: &[Blake2brTraceRow<F>],
+            cols: impl Fn(&Blake2brTraceRow<F>) -> &[F; CHUNKS_BLAKE2BR],
+            index: usize,
+        ) -> u64 {
+            let mut value = 0;
+            let row = &trace[index];
+            let cols = cols(row);
+            for (i, col) in cols.iter().enumerate() {
+                let col_i_val = F::as_canonical_u64(col);
+                value += col_i_val << ((i * BITS_BLAKE2BR) as u64);
+            }
+            value
+        }
+
+        fn get_col_row<F: PrimeField64>(
+            trace_row: &Blake2brTraceRow<F>,
+            cols: impl Fn(&Blake2brTraceRow<F>) -> &[F; CHUNKS_BLAKE2BR],
+        ) -> u64 {
+            let mut value = 0;
+            let row = trace_row;
+            let cols = cols(row);
+            for (i, col) in cols.iter().enumerate() {
+                let col_i_val = F::as_canonical_u64(col);
+                value += col_i_val << ((i * BITS_BLAKE2BR) as u64);
+            }
+            value
+        }
+    }
+
+    /// Computes the witness for a series of inputs and produces an `AirInstance`.
+    ///
+    /// # Arguments
+    /// * `inputs` - A slice of operations to process.
+    ///
+    /// # Returns
+    /// An `AirInstance` containing the computed witness data.
+    pub fn compute_witness<F: PrimeField64>(
+        &self,
+        sctx: &SetupCtx<F>,
+        inputs: &[Vec<OperationBlake2brData<u64>>],
+    ) -> AirInstance<F> {
+        // Get the fixed cols
+        let airgroup_id = Blake2brTrace::<usize>::AIRGROUP_ID;
+        let air_id = Blake2brTrace::<usize>::AIR_ID;
+        let fixed_pols = sctx.get_fixed(airgroup_id, air_id);
+        let fixed = Blake2brFixed::from_vec(fixed_pols);
+
+        timer_start_trace!(BLAKE2BR_TRACE);
+        let mut blake2br_trace = Blake2brTrace::new();
+        let num_rows = blake2br_trace.num_rows();
+
+        // Flatten the inputs
+        let inputs: Vec<OperationBlake2brData<u64>> = inputs.iter().flatten().cloned().collect();
+
+        // Check that we can fit all the blake2brs in the trace
+        let num_inputs: usize = inputs.len();
+        let num_slots_needed = num_inputs.div_ceil(Self::NUM_BLAKE2BR_PER_SLOT);
+        let num_rows_constants = 1; // Number of rows used for the constants
+        let num_rows_needed = num_rows_constants + num_slots_needed * self.slot_size;
+
+        // Sanity checks
+        debug_assert!(
+            num_inputs <= self.num_available_blake2brs,
+            "Exceeded available Blake2brs inputs: requested {}, but only {} are available.",
+            num_inputs,
+            self.num_available_blake2brs
+        );
+        debug_assert!(num_slots_needed <= self.num_available_slots);
+        debug_assert!(num_rows_needed <= num_rows);
+
+        info!(
+            "{}: ··· Creating Blake2br instance [{} / {} rows filled {:.2}%]",
+            Self::MY_NAME,
+            num_rows_needed,
+            num_rows,
+            num_rows_needed as f64 / num_rows as f64 * 100.0
+        );
+
+        // Set a = 0b00..00 and b = 0b11..11 at the first row
+        // Set, e.g., the operation to be an XOR and set c = 0b11..11 = b = a ^ b
+        let mut row: Blake2brTraceRow<F> = Default::default();
+        let zeros = 0u64;
+        let ones = MASK_BITS_BLAKE2BR;
+        let gate_op = fixed[0].GATE_OP.as_canonical_u64();
+        // Sanity check
+        debug_assert_eq!(
+            gate_op,
+            Blake2brTableGateOp::Xor as u64,
+            "Invalid initial dummy gate operation"
+        );
+        for i in 0..CHUNKS_BLAKE2BR {
+            row.free_in_a[i] = F::from_u64(zeros);
+            row.free_in_b[i] = F::from_u64(ones);
+            row.free_in_c[i] = F::from_u64(ones);
+        }
+        // Update the multiplicity table
+        let table_row =
+            Blake2brTableSM::calculate_table_row(&Blake2brTableGateOp::Xor, zeros, ones);
+        self.blake2br_table_sm.update_input(table_row, CHUNKS_BLAKE2BR as u64);
+
+        // Assign the single constant row
+        blake2br_trace[0] = row;
+
+        // Fill the rest of the trace
+        self.process_slice(&fixed, &mut blake2br_trace, num_rows_constants, &inputs);
+        timer_stop_and_log_trace!(BLAKE2BR_TRACE);
+
+        timer_start_trace!(BLAKE2BR_PADDING);
+        // A row with all zeros satisfies the constraints (since both XOR(0,0) and ANDP(0,0) are 0)
+        let padding_row: Blake2brTraceRow<F> = Default::default();
+        for i in (num_rows_constants + self.slot_size * self.num_available_slots)..num_rows {
+            let gate_op = fixed[i].GATE_OP.as_canonical_u64();
+            // Sanity check
+            debug_assert_eq!(
+                gate_op,
+                Blake2brTableGateOp::Xor as u64,
+                "Invalid padding dummy gate operation"
+            );
+
+            let table_row = Blake2brTableSM::calculate_table_row(&Blake2brTableGateOp::Xor, 0, 0);
+            self.blake2br_table_sm.update_input(table_row, CHUNKS_BLAKE2BR as u64);
+
+            blake2br_trace[i] = padding_row;
+        }
+        timer_stop_and_log_trace!(BLAKE2BR_PADDING);
+
+        AirInstance::new_from_trace(FromTrace::new(&mut blake2br_trace))
+    }
+
+    /// Generates memory inputs.
+    pub fn generate_inputs(
+        input: &OperationBlake2brData<u64>,
+        counters_mode: bool,
+    ) -> Vec<Vec<PayloadType>> {
+        // Get the basic data from the input
+        let input_data = ExtOperationData::OperationBlake2brData(*input);
+
+        let step_main = OperationBusData::get_a(&input_data);
+        let addr = OperationBusData::get_b(&input_data) as u32;
+
+        let mut mem_data = vec![];
+        if counters_mode {
+            // On counter phase we don't need final values, we only need the
+            // address and step
+            // Compute the reads
+            for i in 0..INPUT_DATA_SIZE_U64 {
+                let new_addr = addr + 8 * i as u32;
+                let read = MemBusHelpers::mem_aligned_load(new_addr, step_main, 0);
+                mem_data.push(read.to_vec());
+            }
+
+            // Compute the writes
+            for i in 0..OUTPUT_SIZE_U64 {
+                let new_addr = addr + 8 * i as u32;
+                let write = MemBusHelpers::mem_aligned_write(new_addr, step_main, 0);
+                mem_data.push(write.to_vec());
+            }
+
+            return mem_data;
+        }
+        // Get the raw blake2br input as 32 u64 values
+        let blake2br_input: [u64; INPUT_DATA_SIZE_U64] =
+            OperationBusData::get_extra_data(&input_data).try_into().unwrap();
+
+        // Apply the blake2b round and get the output
+        let blake2br_output = apply_round(&blake2br_input);
+
+        // Compute the reads
+        for (i, &input) in blake2br_input.iter().enumerate() {
+            let new_addr = addr + 8 * i as u32;
+            let read = MemBusHelpers::mem_aligned_load(new_addr, step_main, input);
+            mem_data.push(read.to_vec());
+        }
+
+        // Compute the writes
+        for (i, &output) in blake2br_output.iter().enumerate() {
+            let new_addr = addr + 8 * i as u32;
+            let write = MemBusHelpers::mem_aligned_write(new_addr, step_main, output);
+            mem_data.push(write.to_vec());
+        }
+
+        mem_data
+    }
+}
+
+/// Applies a single BLAKE2b round to the 32 u64 input (working vector and permuted message words)
+/// and returns the resulting working vector.
+fn apply_round(input: &[u64; INPUT_DATA_SIZE_U64]) -> [u64; OUTPUT_SIZE_U64] {
+    let mut state: [u64; OUTPUT_SIZE_U64] = input[..OUTPUT_SIZE_U64].try_into().unwrap();
+    let message: [u64; INPUT_DATA_SIZE_U64 - OUTPUT_SIZE_U64] =
+        input[OUTPUT_SIZE_U64..].try_into().unwrap();
+    blake2b_round(&mut state, &message);
+    state
+}
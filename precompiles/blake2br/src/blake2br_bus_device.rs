@@ -0,0 +1,137 @@
+//! The `Blake2brCounter` module defines a counter for tracking blake2br-related operations
+//! sent over the data bus. It connects to the bus and gathers metrics for specific
+//! `ZiskOperationType::Blake2` instructions.
+
+use std::ops::Add;
+
+use zisk_common::{
+    BusDevice, BusDeviceMode, BusId, Counter, ExtOperationData, Metrics, MEM_BUS_ID,
+    OPERATION_BUS_ID, OP_TYPE,
+};
+use zisk_core::ZiskOperationType;
+
+use crate::Blake2brSM;
+
+/// The `Blake2brCounter` struct represents a counter that monitors and measures
+/// blake2br-related operations on the data bus.
+///
+/// It tracks specific operation types (`ZiskOperationType`) and updates counters for each
+/// accepted operation type whenever data is processed on the bus.
+pub struct Blake2brCounterInputGen {
+    /// Blake2br counter.
+    counter: Counter,
+
+    /// Bus device mode (counter or input generator).
+    mode: BusDeviceMode,
+}
+
+impl Blake2brCounterInputGen {
+    /// Creates a new instance of `Blake2brCounter`.
+    ///
+    /// # Arguments
+    /// * `bus_id` - The ID of the bus to which this counter is connected.
+    /// * `op_type` - A vector of `ZiskOperationType` instructions to monitor.
+    ///
+    /// # Returns
+    /// A new `Blake2brCounter` instance.
+    pub fn new(mode: BusDeviceMode) -> Self {
+        Self { counter: Counter::default(), mode }
+    }
+
+    /// Retrieves the count of instructions for a specific `ZiskOperationType`.
+    ///
+    /// # Arguments
+    /// * `op_type` - The operation type to retrieve the count for.
+    ///
+    /// # Returns
+    /// Returns the count of instructions for the specified operation type.
+    pub fn inst_count(&self, op_type: ZiskOperationType) -> Option<u64> {
+        (op_type == ZiskOperationType::Blake2).then_some(self.counter.inst_count)
+    }
+}
+
+impl Metrics for Blake2brCounterInputGen {
+    /// Tracks activity on the connected bus and updates counters for recognized operations.
+    ///
+    /// # Arguments
+    /// * `_bus_id` - The ID of the bus (unused in this implementation).
+    /// * `_data` - The data received from the bus.
+    ///
+    /// # Returns
+    /// An empty vector, as this implementation does not produce any derived inputs for the bus.
+    #[inline(always)]
+    fn measure(&mut self, _data: &[u64]) {
+        self.counter.update(1);
+    }
+
+    /// Provides a dynamic reference for downcasting purposes.
+    ///
+    /// # Returns
+    /// A reference to `self` as `dyn std::any::Any`.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Add for Blake2brCounterInputGen {
+    type Output = Blake2brCounterInputGen;
+
+    /// Combines two `Blake2brCounter` instances by summing their counters.
+    ///
+    /// # Arguments
+    /// * `self` - The first `Blake2brCounter` instance.
+    /// * `other` - The second `Blake2brCounter` instance.
+    ///
+    /// # Returns
+    /// A new `Blake2brCounter` with combined counters.
+    fn add(self, other: Self) -> Blake2brCounterInputGen {
+        Blake2brCounterInputGen { counter: &self.counter + &other.counter, mode: self.mode }
+    }
+}
+
+impl BusDevice<u64> for Blake2brCounterInputGen {
+    /// Processes data received on the bus, updating counters and generating inputs when applicable.
+    ///
+    /// # Arguments
+    /// * `bus_id` - The ID of the bus sending the data.
+    /// * `data` - The data received from the bus.
+    ///
+    /// # Returns
+    /// A vector of derived inputs to be sent back to the bus.
+    #[inline(always)]
+    fn process_data(&mut self, bus_id: &BusId, data: &[u64]) -> Option<Vec<(BusId, Vec<u64>)>> {
+        debug_assert!(*bus_id == OPERATION_BUS_ID);
+
+        if data[OP_TYPE] as u32 != ZiskOperationType::Blake2 as u32 {
+            return None;
+        }
+
+        let data: ExtOperationData<u64> = data.try_into().ok()?;
+
+        match data {
+            ExtOperationData::OperationBlake2brData(data) => {
+                if self.mode == BusDeviceMode::Counter {
+                    self.measure(&data);
+                }
+
+                let mem_inputs =
+                    Blake2brSM::generate_inputs(&data, self.mode == BusDeviceMode::Counter);
+                Some(mem_inputs.into_iter().map(|x| (MEM_BUS_ID, x)).collect())
+            }
+            _ => panic!("Expected ExtOperationData::OperationData"),
+        }
+    }
+
+    /// Returns the bus IDs associated with this counter.
+    ///
+    /// # Returns
+    /// A vector containing the connected bus ID.
+    fn bus_id(&self) -> Vec<BusId> {
+        vec![OPERATION_BUS_ID]
+    }
+
+    /// Provides a dynamic reference for downcasting purposes.
+    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
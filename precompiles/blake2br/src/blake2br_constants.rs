@@ -0,0 +1,42 @@
+//! This module defines constants for the Blake2br precompile.
+
+/// Generic Parameters
+pub const STATE_SIZE_BITS: usize = 1024;
+pub const INPUT_SIZE_BITS: usize = 1024;
+pub const INPUT_DATA_SIZE_BITS: usize = STATE_SIZE_BITS + INPUT_SIZE_BITS; // 2048
+pub const INPUT_DATA_SIZE_U64: usize = INPUT_DATA_SIZE_BITS / 64; // 32
+pub const OUTPUT_SIZE_BITS: usize = STATE_SIZE_BITS;
+pub const OUTPUT_SIZE_U64: usize = OUTPUT_SIZE_BITS / 64; // 16
+pub const RB: usize = 32;
+pub const BITS_IN_PARALLEL: usize = 2;
+
+/// Blake2br Parameters
+pub const CHUNKS_BLAKE2BR: usize = 3;
+pub const BITS_BLAKE2BR: usize = 5;
+pub const P2_BITS_BLAKE2BR: u64 = 1 << BITS_BLAKE2BR;
+pub const P2_CHUNK_BITS_BLAKE2BR: u64 = 1 << (BITS_BLAKE2BR * CHUNKS_BLAKE2BR);
+pub const MASK_BITS_BLAKE2BR: u64 = P2_BITS_BLAKE2BR - 1;
+pub const MASK_CHUNK_BITS_BLAKE2BR: u64 = P2_CHUNK_BITS_BLAKE2BR - 1;
+
+/// Blake2br Table Parameters
+pub const CHUNKS_BLAKE2BR_TABLE: usize = 1;
+pub const BITS_BLAKE2BR_TABLE: usize = BITS_BLAKE2BR;
+pub const BITS_A: usize = BITS_BLAKE2BR_TABLE - CHUNKS_BLAKE2BR_TABLE + 1;
+pub const BITS_B: usize = BITS_BLAKE2BR_TABLE;
+pub const P2_BITS_A: u64 = 1 << BITS_A;
+pub const P2_BITS_B: u64 = 1 << BITS_B;
+pub const P2_BITS_AB: u64 = P2_BITS_A * P2_BITS_B;
+pub const MASK_BITS_A: u64 = P2_BITS_A - 1;
+pub const MASK_BITS_B: u64 = P2_BITS_B - 1;
+
+/// Circuit parameters
+pub const STATE_IN_REF_0: usize = 16;
+pub const STATE_IN_GROUP_BY: usize = 2;
+pub const STATE_IN_NUMBER: usize = 2048;
+pub const STATE_IN_REF_DISTANCE: usize = 15;
+pub const STATE_OUT_REF_0: usize = 15_376; // 16 + 2048 * 15 / 2;
+pub const STATE_OUT_GROUP_BY: usize = 2;
+pub const STATE_OUT_NUMBER: usize = 1024;
+pub const STATE_OUT_REF_DISTANCE: usize = 15;
+pub const XOR_GATE_OP: u8 = 0x00;
+pub const ANDP_GATE_OP: u8 = 0x01;
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use p3_field::PrimeField64;
+use zisk_common::{BusDevice, PayloadType, OPERATION_BUS_ID};
+
+use zisk_common::{
+    table_instance_array, BusDeviceMetrics, BusDeviceMode, ComponentBuilder, Instance, InstanceCtx,
+    InstanceInfo, Planner, TableInfo,
+};
+use zisk_core::ZiskOperationType;
+use zisk_pil::{Blake2brTableTrace, Blake2brTrace};
+
+use crate::{
+    Blake2brCounterInputGen, Blake2brInstance, Blake2brPlanner, Blake2brSM, Blake2brTableSM,
+};
+
+/// The `Blake2brManager` struct represents the Blake2br manager,
+/// which is responsible for managing the Blake2br state machine and its table state machine.
+#[allow(dead_code)]
+pub struct Blake2brManager {
+    /// Blake2br state machine
+    blake2br_sm: Arc<Blake2brSM>,
+
+    /// Blake2br table state machine
+    blake2br_table_sm: Arc<Blake2brTableSM>,
+}
+
+impl Blake2brManager {
+    /// Creates a new instance of `Blake2brManager`.
+    ///
+    /// # Returns
+    /// An `Arc`-wrapped instance of `Blake2brManager`.
+    pub fn new<F: PrimeField64>() -> Arc<Self> {
+        let blake2br_table_sm = Blake2brTableSM::new::<F>();
+        let blake2br_sm = Blake2brSM::new(blake2br_table_sm.clone());
+
+        Arc::new(Self { blake2br_sm, blake2br_table_sm })
+    }
+
+    pub fn build_blake2br_counter(&self) -> Blake2brCounterInputGen {
+        Blake2brCounterInputGen::new(BusDeviceMode::Counter)
+    }
+}
+
+impl<F: PrimeField64> ComponentBuilder<F> for Blake2brManager {
+    /// Builds and returns a new counter for monitoring blake2br operations.
+    ///
+    /// # Returns
+    /// A boxed implementation of `RegularCounters` configured for blake2br operations.
+    fn build_counter(&self) -> Option<Box<dyn BusDeviceMetrics>> {
+        Some(Box::new(Blake2brCounterInputGen::new(BusDeviceMode::Counter)))
+    }
+
+    /// Builds a planner to plan blake2br-related instances.
+    ///
+    /// # Returns
+    /// A boxed implementation of `RegularPlanner`.
+    fn build_planner(&self) -> Box<dyn Planner> {
+        // Get the number of blake2brs that a single blake2br instance can handle
+        let num_available_blake2brs = self.blake2br_sm.num_available_blake2brs;
+
+        Box::new(
+            Blake2brPlanner::new()
+                .add_instance(InstanceInfo::new(
+                    Blake2brTrace::<usize>::AIRGROUP_ID,
+                    Blake2brTrace::<usize>::AIR_ID,
+                    num_available_blake2brs,
+                    ZiskOperationType::Blake2,
+                ))
+                .add_table_instance(TableInfo::new(
+                    Blake2brTableTrace::<usize>::AIRGROUP_ID,
+                    Blake2brTableTrace::<usize>::AIR_ID,
+                )),
+        )
+    }
+
+    /// Builds an inputs data collector for blake2br operations.
+    ///
+    /// # Arguments
+    /// * `ictx` - The context of the instance, containing the plan and its associated
+    ///   configurations.
+    ///
+    /// # Returns
+    /// A boxed implementation of `BusDeviceInstance` specific to the requested `air_id` instance.
+    ///
+    /// # Panics
+    /// Panics if the provided `air_id` is not supported.
+    fn build_instance(&self, ictx: InstanceCtx) -> Box<dyn Instance<F>> {
+        match ictx.plan.air_id {
+            id if id == Blake2brTrace::<usize>::AIR_ID => {
+                Box::new(Blake2brInstance::new(self.blake2br_sm.clone(), ictx))
+            }
+            id if id == Blake2brTableTrace::<usize>::AIR_ID => {
+                table_instance_array!(Blake2brTableInstance, Blake2brTableSM, Blake2brTableTrace);
+                Box::new(Blake2brTableInstance::new(
+                    self.blake2br_table_sm.clone(),
+                    ictx,
+                    OPERATION_BUS_ID,
+                ))
+            }
+            _ => {
+                panic!("Blake2brBuilder::get_instance() Unsupported air_id: {:?}", ictx.plan.air_id)
+            }
+        }
+    }
+
+    fn build_inputs_generator(&self) -> Option<Box<dyn BusDevice<PayloadType>>> {
+        Some(Box::new(Blake2brCounterInputGen::new(BusDeviceMode::InputGenerator)))
+    }
+}
@@ -0,0 +1,101 @@
+//! The `Blake2brTableSM` module defines the Blake2br Table State Machine.
+//!
+//! This state machine is responsible for handling Blake2br operations, calculating table rows,
+//! and managing multiplicity tables for Blake2br table traces.
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+
+use p3_field::Field;
+use zisk_common::create_atomic_vec;
+use zisk_pil::Blake2brTableTrace;
+
+use crate::blake2br_constants::*;
+
+/// Represents operations supported by the Blake2br Table.
+#[repr(u8)]
+pub enum Blake2brTableGateOp {
+    /// XOR gate
+    Xor = XOR_GATE_OP,
+
+    /// ANDP gate
+    Andp = ANDP_GATE_OP,
+}
+
+/// The `Blake2brTableSM` struct represents the Blake2br Table State Machine.
+///
+/// It manages a multiplicity table and provides functionality to process slices and calculate table
+/// rows.
+pub struct Blake2brTableSM {
+    /// The multiplicity table, shared across threads.
+    multiplicities: Vec<Vec<AtomicU64>>,
+    calculated: AtomicBool,
+}
+
+impl Blake2brTableSM {
+    /// Creates a new `Blake2brTableSM` instance.
+    ///
+    /// # Returns
+    /// An `Arc`-wrapped instance of `Blake2brTableSM`.
+    pub fn new<F: Field>() -> Arc<Self> {
+        let mut multiplicities = Vec::new();
+        for _ in 0..Blake2brTableTrace::<usize>::ROW_SIZE {
+            multiplicities.push(create_atomic_vec(Blake2brTableTrace::<usize>::NUM_ROWS));
+        }
+        Arc::new(Self { multiplicities, calculated: AtomicBool::new(false) })
+    }
+
+    /// Processes a slice of input data and updates the multiplicity table.
+    ///
+    /// # Arguments
+    /// * `input` - A slice of `u64` values representing the input data.
+    pub fn update_input(&self, index: usize, value: u64) {
+        if self.calculated.load(Ordering::Relaxed) {
+            return;
+        }
+        self.multiplicities[0][index].fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Detaches and returns the current multiplicity table.
+    ///
+    /// # Returns
+    /// A vector containing the multiplicity table.
+    pub fn detach_multiplicities(&self) -> &[Vec<AtomicU64>] {
+        &self.multiplicities
+    }
+
+    pub fn set_calculated(&self) {
+        self.calculated.store(true, Ordering::Relaxed);
+    }
+
+    /// Calculates the table row offset based on the provided parameters.
+    ///
+    /// # Arguments
+    /// * `gate_opcode` - The operation code (`Blake2brTableGateOp`).
+    /// * `a` - The first operand a.
+    /// * `b` - The second operand b.
+    ///
+    /// # Returns
+    /// The calculated table row offset.
+    pub fn calculate_table_row(gate_opcode: &Blake2brTableGateOp, a: u64, b: u64) -> usize {
+        debug_assert!(a <= MASK_BITS_A);
+        debug_assert!(b <= MASK_BITS_B);
+
+        // Calculate the different row offset contributors, according to the PIL
+        let offset_a: u64 = a;
+        let offset_b: u64 = b * P2_BITS_A;
+        let offset_opcode: u64 = Self::offset_opcode(gate_opcode);
+
+        (offset_a + offset_b + offset_opcode).try_into().expect("Invalid table row offset")
+    }
+
+    /// Computes the opcode offset for the given operation.
+    fn offset_opcode(gate_opcode: &Blake2brTableGateOp) -> u64 {
+        match gate_opcode {
+            Blake2brTableGateOp::Xor => 0,
+            Blake2brTableGateOp::Andp => P2_BITS_AB,
+        }
+    }
+}
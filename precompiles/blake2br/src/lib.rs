@@ -0,0 +1,15 @@
+mod blake2br;
+mod blake2br_bus_device;
+mod blake2br_constants;
+mod blake2br_instance;
+mod blake2br_manager;
+mod blake2br_planner;
+mod blake2br_table;
+
+pub use blake2br::*;
+pub use blake2br_bus_device::*;
+pub use blake2br_constants::*;
+pub use blake2br_instance::*;
+pub use blake2br_manager::*;
+pub use blake2br_planner::*;
+pub use blake2br_table::*;
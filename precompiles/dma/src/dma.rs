@@ -0,0 +1,264 @@
+use std::sync::Arc;
+
+use log::info;
+use p3_field::PrimeField64;
+
+use precompiles_common::MemBusHelpers;
+use proofman_common::{AirInstance, FromTrace, SetupCtx};
+use proofman_util::{timer_start_trace, timer_stop_and_log_trace};
+use zisk_common::{OperationDmaData, PayloadType, OPERATION_BUS_DMA_DATA_SIZE};
+use zisk_core::zisk_ops::{ZiskOp, DMA_PARAMS};
+use zisk_pil::{DmaTrace, DmaTraceRow};
+
+use rayon::prelude::*;
+
+// Positions of the fields in an `OperationDmaData`: [op, op_type, a = step, b = addr, step, dst,
+// src|fill, count, words...]
+const OP: usize = 0;
+const STEP_MAIN: usize = 2;
+const ADDR: usize = 3;
+const DST: usize = OPERATION_BUS_DMA_DATA_SIZE;
+const SRC: usize = OPERATION_BUS_DMA_DATA_SIZE + 1;
+const COUNT: usize = OPERATION_BUS_DMA_DATA_SIZE + 2;
+const WORDS: usize = OPERATION_BUS_DMA_DATA_SIZE + DMA_PARAMS;
+
+/// The `DmaSM` struct encapsulates the logic of the Dma State Machine, which proves the
+/// memcpy and memset precompiles one written word per row.
+pub struct DmaSM {
+    /// Number of available rows in the trace.
+    pub num_available_rows: usize,
+}
+
+impl DmaSM {
+    const MY_NAME: &'static str = "Dma     ";
+
+    /// Creates a new Dma State Machine instance.
+    ///
+    /// # Returns
+    /// A new `DmaSM` instance.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self { num_available_rows: DmaTrace::<usize>::NUM_ROWS })
+    }
+
+    /// Returns the number of rows used by an operation, that is, the number of words it writes.
+    ///
+    /// # Arguments
+    /// * `input` - The operation data.
+    pub fn num_rows(input: &[u64]) -> usize {
+        input[COUNT] as usize
+    }
+
+    /// Fills the rows of a single operation.
+    ///
+    /// # Arguments
+    /// * `rows` - The rows of the trace assigned to the operation, one per written word.
+    /// * `input` - The operation data.
+    #[inline(always)]
+    fn process_input<F: PrimeField64>(rows: &mut [DmaTraceRow<F>], input: &OperationDmaData<u64>) {
+        let step = input[STEP_MAIN];
+        let addr = input[ADDR];
+        let is_set = input[OP] as u8 == ZiskOp::Memset.code();
+        let (dst, src, count) = (input[DST], input[SRC], input[COUNT]);
+        let words = &input[WORDS..];
+
+        let num_rows = rows.len();
+        for (i, row) in rows.iter_mut().enumerate() {
+            let value = if is_set { src } else { words[i] };
+            let offset = 8 * i as u64;
+
+            row.step = F::from_u64(step);
+            row.addr = F::from_u64(addr);
+            row.dst = F::from_u64(dst + offset);
+            // memset reads no source words, but its src column still has to increase by one word
+            row.src = F::from_u64(if is_set { offset } else { src + offset });
+            row.count = F::from_u64(count - i as u64);
+            row.value = [F::from_u32(value as u32), F::from_u32((value >> 32) as u32)];
+            row.sel = F::ONE;
+            row.is_first = F::from_bool(i == 0);
+            row.is_last = F::from_bool(i == num_rows - 1);
+            row.is_set = F::from_bool(is_set);
+        }
+    }
+
+    /// Computes the witness for a series of inputs and produces an `AirInstance`.
+    ///
+    /// # Arguments
+    /// * `inputs` - A slice of operations to process.
+    ///
+    /// # Returns
+    /// An `AirInstance` containing the computed witness data.
+    pub fn compute_witness<F: PrimeField64>(
+        &self,
+        _sctx: &SetupCtx<F>,
+        inputs: &[Vec<OperationDmaData<u64>>],
+    ) -> AirInstance<F> {
+        timer_start_trace!(DMA_TRACE);
+        let mut dma_trace = DmaTrace::new();
+        let num_rows = dma_trace.num_rows();
+
+        let num_rows_needed: usize =
+            inputs.iter().flatten().map(|input| Self::num_rows(input)).sum();
+
+        // Sanity check
+        debug_assert!(
+            num_rows_needed <= self.num_available_rows,
+            "Exceeded available Dma rows: requested {}, but only {} are available.",
+            num_rows_needed,
+            self.num_available_rows
+        );
+
+        info!(
+            "{}: ··· Creating Dma instance [{} / {} rows filled {:.2}%]",
+            Self::MY_NAME,
+            num_rows_needed,
+            num_rows,
+            num_rows_needed as f64 / num_rows as f64 * 100.0
+        );
+
+        // Split the trace in the rows of every operation and fill them in parallel
+        let mut trace_rows = &mut dma_trace.buffer[..];
+        let mut par_traces = Vec::new();
+        for input in inputs.iter().flatten() {
+            let (head, tail) = trace_rows.split_at_mut(Self::num_rows(input));
+            par_traces.push((head, input));
+            trace_rows = tail;
+        }
+
+        par_traces.into_par_iter().for_each(|(rows, input)| Self::process_input(rows, input));
+        timer_stop_and_log_trace!(DMA_TRACE);
+
+        timer_start_trace!(DMA_PADDING);
+        // A row with all zeros satisfies the constraints, since it belongs to no operation
+        let padding_row: DmaTraceRow<F> = Default::default();
+        trace_rows.par_iter_mut().for_each(|row| *row = padding_row);
+        timer_stop_and_log_trace!(DMA_PADDING);
+
+        AirInstance::new_from_trace(FromTrace::new(&mut dma_trace))
+    }
+
+    /// Generates memory inputs.
+    pub fn generate_inputs(
+        input: &OperationDmaData<u64>,
+        counters_mode: bool,
+    ) -> Vec<Vec<PayloadType>> {
+        let step_main = input[STEP_MAIN];
+        let addr = input[ADDR] as u32;
+        let is_set = input[OP] as u8 == ZiskOp::Memset.code();
+        let params = &input[DST..WORDS];
+        let words = &input[WORDS..];
+        let (dst, src, count) = (input[DST] as u32, input[SRC] as u32, input[COUNT] as usize);
+
+        // On counter phase we don't need final values, we only need the address and step
+        let mem_value = |value: u64| if counters_mode { 0 } else { value };
+
+        let mut mem_data = Vec::with_capacity(DMA_PARAMS + 2 * count);
+
+        // Compute the reads of the parameters
+        for (i, &param) in params.iter().enumerate() {
+            let read =
+                MemBusHelpers::mem_aligned_load(addr + 8 * i as u32, step_main, mem_value(param));
+            mem_data.push(read.to_vec());
+        }
+
+        // Compute the reads of the source words (memcpy)
+        if !is_set {
+            for (i, &word) in words.iter().enumerate() {
+                let read =
+                    MemBusHelpers::mem_aligned_load(src + 8 * i as u32, step_main, mem_value(word));
+                mem_data.push(read.to_vec());
+            }
+        }
+
+        // Compute the writes
+        let written = (0..count).map(|i| if is_set { input[SRC] } else { words[i] });
+        for (i, word) in written.enumerate() {
+            let write =
+                MemBusHelpers::mem_aligned_write(dst + 8 * i as u32, step_main, mem_value(word));
+            mem_data.push(write.to_vec());
+        }
+
+        mem_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_field::PrimeCharacteristicRing;
+    use p3_goldilocks::Goldilocks;
+    use zisk_core::ZiskOperationType;
+
+    const STEP: u64 = 10;
+    const ADDR: u64 = 0xa0010000;
+    const DST: u64 = 0xa0020000;
+    const SRC: u64 = 0xa0030000;
+
+    fn memcpy_input(words: &[u64]) -> OperationDmaData<u64> {
+        let op = [ZiskOp::Memcpy.code() as u64, ZiskOperationType::Dma as u64, STEP, ADDR, STEP];
+        [op.as_slice(), &[DST, SRC, words.len() as u64], words].concat()
+    }
+
+    fn memset_input(fill: u64, count: u64) -> OperationDmaData<u64> {
+        let op = [ZiskOp::Memset.code() as u64, ZiskOperationType::Dma as u64, STEP, ADDR, STEP];
+        [op.as_slice(), &[DST, fill, count]].concat()
+    }
+
+    #[test]
+    fn test_generate_inputs_memcpy() {
+        let input = memcpy_input(&[7, 8]);
+        let mem_inputs = DmaSM::generate_inputs(&input, false);
+
+        let expected = [
+            MemBusHelpers::mem_aligned_load(ADDR as u32, STEP, DST),
+            MemBusHelpers::mem_aligned_load(ADDR as u32 + 8, STEP, SRC),
+            MemBusHelpers::mem_aligned_load(ADDR as u32 + 16, STEP, 2),
+            MemBusHelpers::mem_aligned_load(SRC as u32, STEP, 7),
+            MemBusHelpers::mem_aligned_load(SRC as u32 + 8, STEP, 8),
+            MemBusHelpers::mem_aligned_write(DST as u32, STEP, 7),
+            MemBusHelpers::mem_aligned_write(DST as u32 + 8, STEP, 8),
+        ];
+        assert_eq!(mem_inputs, expected.map(|x| x.to_vec()));
+
+        // The counters only need the addresses and steps
+        let mem_inputs = DmaSM::generate_inputs(&input, true);
+        assert_eq!(mem_inputs.len(), expected.len());
+        assert!(mem_inputs.iter().zip(expected).all(|(x, y)| x[..4] == y[..4] && x[4..] == [0; 3]));
+    }
+
+    #[test]
+    fn test_generate_inputs_memset() {
+        let mem_inputs = DmaSM::generate_inputs(&memset_input(0xff, 2), false);
+
+        let expected = [
+            MemBusHelpers::mem_aligned_load(ADDR as u32, STEP, DST),
+            MemBusHelpers::mem_aligned_load(ADDR as u32 + 8, STEP, 0xff),
+            MemBusHelpers::mem_aligned_load(ADDR as u32 + 16, STEP, 2),
+            MemBusHelpers::mem_aligned_write(DST as u32, STEP, 0xff),
+            MemBusHelpers::mem_aligned_write(DST as u32 + 8, STEP, 0xff),
+        ];
+        assert_eq!(mem_inputs, expected.map(|x| x.to_vec()));
+    }
+
+    #[test]
+    fn test_process_input() {
+        let f = Goldilocks::from_u64;
+        let input = memcpy_input(&[1, 2, 0x1_0000_0003]);
+        let mut rows = [DmaTraceRow::<Goldilocks>::default(); 3];
+        DmaSM::process_input(&mut rows, &input);
+
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!((row.step, row.addr), (f(STEP), f(ADDR)));
+            assert_eq!((row.dst, row.src), (f(DST + 8 * i as u64), f(SRC + 8 * i as u64)));
+            assert_eq!(row.count, f(3 - i as u64));
+            assert_eq!((row.sel, row.is_set), (f(1), f(0)));
+            assert_eq!((row.is_first, row.is_last), (f((i == 0) as u64), f((i == 2) as u64)));
+        }
+        assert_eq!(rows[2].value, [f(3), f(1)]);
+
+        // memset rows keep the fill word, and their src column still increases by one word
+        let mut rows = [DmaTraceRow::<Goldilocks>::default(); 2];
+        DmaSM::process_input(&mut rows, &memset_input(0x5_0000_0006, 2));
+        assert!(rows.iter().all(|row| row.value == [f(6), f(5)] && row.is_set == f(1)));
+        assert_eq!((rows[0].src, rows[1].src), (f(0), f(8)));
+    }
+}
@@ -0,0 +1,147 @@
+//! The `DmaCounterInputGen` module defines a counter for tracking memcpy and memset operations
+//! sent over the data bus. It connects to the bus and gathers metrics for specific
+//! `ZiskOperationType::Dma` instructions.
+
+use std::ops::Add;
+
+use zisk_common::{
+    BusDevice, BusDeviceMode, BusId, Counter, ExtOperationData, Metrics, MEM_BUS_ID,
+    OPERATION_BUS_ID, OP_TYPE,
+};
+use zisk_core::ZiskOperationType;
+
+use crate::DmaSM;
+
+/// The `DmaCounterInputGen` struct represents a counter that monitors and measures
+/// dma-related operations on the data bus.
+///
+/// Since every operation uses as many rows as words it writes, besides the number of operations
+/// it keeps the number of rows of each of them, so that the planner never splits an operation
+/// between two instances.
+pub struct DmaCounterInputGen {
+    /// Dma counter.
+    counter: Counter,
+
+    /// Number of rows of each counted operation, in execution order.
+    ops_rows: Vec<u8>,
+
+    /// Bus device mode (counter or input generator).
+    mode: BusDeviceMode,
+}
+
+impl DmaCounterInputGen {
+    /// Creates a new instance of `DmaCounterInputGen`.
+    ///
+    /// # Arguments
+    /// * `mode` - The mode of the bus device.
+    ///
+    /// # Returns
+    /// A new `DmaCounterInputGen` instance.
+    pub fn new(mode: BusDeviceMode) -> Self {
+        Self { counter: Counter::default(), ops_rows: Vec::new(), mode }
+    }
+
+    /// Retrieves the count of instructions for a specific `ZiskOperationType`.
+    ///
+    /// # Arguments
+    /// * `op_type` - The operation type to retrieve the count for.
+    ///
+    /// # Returns
+    /// Returns the count of instructions for the specified operation type.
+    pub fn inst_count(&self, op_type: ZiskOperationType) -> Option<u64> {
+        (op_type == ZiskOperationType::Dma).then_some(self.counter.inst_count)
+    }
+
+    /// Retrieves the number of rows of each counted operation, in execution order.
+    pub fn ops_rows(&self) -> &[u8] {
+        &self.ops_rows
+    }
+}
+
+impl Metrics for DmaCounterInputGen {
+    /// Tracks activity on the connected bus and updates counters for recognized operations.
+    ///
+    /// # Arguments
+    /// * `data` - The data received from the bus.
+    #[inline(always)]
+    fn measure(&mut self, data: &[u64]) {
+        self.counter.update(1);
+        self.ops_rows.push(DmaSM::num_rows(data) as u8);
+    }
+
+    /// Provides a dynamic reference for downcasting purposes.
+    ///
+    /// # Returns
+    /// A reference to `self` as `dyn std::any::Any`.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Add for DmaCounterInputGen {
+    type Output = DmaCounterInputGen;
+
+    /// Combines two `DmaCounterInputGen` instances by summing their counters and appending the
+    /// operations of `other` after those of `self`.
+    ///
+    /// # Arguments
+    /// * `self` - The first `DmaCounterInputGen` instance.
+    /// * `other` - The second `DmaCounterInputGen` instance.
+    ///
+    /// # Returns
+    /// A new `DmaCounterInputGen` with combined counters.
+    fn add(mut self, other: Self) -> DmaCounterInputGen {
+        self.ops_rows.extend_from_slice(&other.ops_rows);
+        DmaCounterInputGen {
+            counter: &self.counter + &other.counter,
+            ops_rows: self.ops_rows,
+            mode: self.mode,
+        }
+    }
+}
+
+impl BusDevice<u64> for DmaCounterInputGen {
+    /// Processes data received on the bus, updating counters and generating inputs when applicable.
+    ///
+    /// # Arguments
+    /// * `bus_id` - The ID of the bus sending the data.
+    /// * `data` - The data received from the bus.
+    ///
+    /// # Returns
+    /// A vector of derived inputs to be sent back to the bus.
+    #[inline(always)]
+    fn process_data(&mut self, bus_id: &BusId, data: &[u64]) -> Option<Vec<(BusId, Vec<u64>)>> {
+        debug_assert!(*bus_id == OPERATION_BUS_ID);
+
+        if data[OP_TYPE] as u32 != ZiskOperationType::Dma as u32 {
+            return None;
+        }
+
+        let data: ExtOperationData<u64> = data.try_into().ok()?;
+
+        match data {
+            ExtOperationData::OperationDmaData(data) => {
+                if self.mode == BusDeviceMode::Counter {
+                    self.measure(&data);
+                }
+
+                let mem_inputs = DmaSM::generate_inputs(&data, self.mode == BusDeviceMode::Counter);
+                Some(mem_inputs.into_iter().map(|x| (MEM_BUS_ID, x)).collect())
+            }
+            _ => panic!("Expected ExtOperationData::OperationDmaData"),
+        }
+    }
+
+    /// Returns the bus IDs associated with this counter.
+    ///
+    /// # Returns
+    /// A vector containing the connected bus ID.
+    fn bus_id(&self) -> Vec<BusId> {
+        vec![OPERATION_BUS_ID]
+    }
+
+    /// Provides a dynamic reference for downcasting purposes.
+    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
@@ -0,0 +1,180 @@
+//! The `DmaInstance` module defines an instance to perform the witness computation
+//! for the Dma State Machine.
+//!
+//! It manages collected inputs and interacts with the `DmaSM` to compute witnesses for
+//! execution plans.
+
+use crate::DmaSM;
+use p3_field::PrimeField64;
+use proofman_common::{AirInstance, ProofCtx, SetupCtx};
+use std::{any::Any, collections::HashMap, sync::Arc};
+use zisk_common::{
+    BusDevice, BusId, CheckPoint, ChunkId, CollectSkipper, ExtOperationData, Instance, InstanceCtx,
+    InstanceType, OperationDmaData, PayloadType, OPERATION_BUS_ID, OP_TYPE,
+};
+use zisk_core::ZiskOperationType;
+use zisk_pil::DmaTrace;
+
+/// The `DmaInstance` struct represents an instance for the Dma State Machine.
+///
+/// It encapsulates the `DmaSM` and its associated context, and it processes input data
+/// to compute witnesses for the Dma State Machine.
+pub struct DmaInstance {
+    /// Dma state machine.
+    dma_sm: Arc<DmaSM>,
+
+    /// Instance context.
+    ictx: InstanceCtx,
+}
+
+impl DmaInstance {
+    /// Creates a new `DmaInstance`.
+    ///
+    /// # Arguments
+    /// * `dma_sm` - An `Arc`-wrapped reference to the Dma State Machine.
+    /// * `ictx` - The `InstanceCtx` associated with this instance, containing the execution plan.
+    /// * `bus_id` - The bus ID associated with this instance.
+    ///
+    /// # Returns
+    /// A new `DmaInstance` instance initialized with the provided state machine and
+    /// context.
+    pub fn new(dma_sm: Arc<DmaSM>, ictx: InstanceCtx) -> Self {
+        Self { dma_sm, ictx }
+    }
+}
+
+impl<F: PrimeField64> Instance<F> for DmaInstance {
+    /// Computes the witness for the dma execution plan.
+    ///
+    /// This method leverages the `DmaSM` to generate an `AirInstance` using the collected
+    /// inputs.
+    ///
+    /// # Arguments
+    /// * `_pctx` - The proof context, unused in this implementation.
+    ///
+    /// # Returns
+    /// An `Option` containing the computed `AirInstance`.
+    fn compute_witness(
+        &mut self,
+        _pctx: &ProofCtx<F>,
+        sctx: &SetupCtx<F>,
+        collectors: Vec<(usize, Box<dyn BusDevice<PayloadType>>)>,
+    ) -> Option<AirInstance<F>> {
+        let inputs: Vec<_> = collectors
+            .into_iter()
+            .map(|(_, collector)| collector.as_any().downcast::<DmaCollector>().unwrap().inputs)
+            .collect();
+
+        Some(self.dma_sm.compute_witness(sctx, &inputs))
+    }
+
+    /// Retrieves the checkpoint associated with this instance.
+    ///
+    /// # Returns
+    /// A `CheckPoint` object representing the checkpoint of the execution plan.
+    fn check_point(&self) -> CheckPoint {
+        self.ictx.plan.check_point.clone()
+    }
+
+    /// Retrieves the type of this instance.
+    ///
+    /// # Returns
+    /// An `InstanceType` representing the type of this instance (`InstanceType::Instance`).
+    fn instance_type(&self) -> InstanceType {
+        InstanceType::Instance
+    }
+
+    fn build_inputs_collector(&self, chunk_id: ChunkId) -> Option<Box<dyn BusDevice<PayloadType>>> {
+        assert_eq!(
+            self.ictx.plan.air_id,
+            DmaTrace::<F>::AIR_ID,
+            "DmaInstance: Unsupported air_id: {:?}",
+            self.ictx.plan.air_id
+        );
+
+        let meta = self.ictx.plan.meta.as_ref().unwrap();
+        let collect_info = meta.downcast_ref::<HashMap<ChunkId, (u64, CollectSkipper)>>().unwrap();
+        let (num_ops, collect_skipper) = collect_info[&chunk_id];
+        Some(Box::new(DmaCollector::new(num_ops, collect_skipper)))
+    }
+}
+
+pub struct DmaCollector {
+    /// Collected inputs for witness computation.
+    inputs: Vec<OperationDmaData<u64>>,
+
+    /// The number of operations to collect.
+    num_operations: u64,
+
+    /// Helper to skip instructions based on the plan's configuration.
+    collect_skipper: CollectSkipper,
+}
+
+impl DmaCollector {
+    /// Creates a new `DmaCollector`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus_id` - The connected bus ID.
+    /// * `num_operations` - The number of operations to collect.
+    /// * `collect_skipper` - The helper to skip instructions based on the plan's configuration.
+    ///
+    /// # Returns
+    /// A new `DmaCollector` instance initialized with the provided parameters.
+    pub fn new(num_operations: u64, collect_skipper: CollectSkipper) -> Self {
+        Self { inputs: Vec::new(), num_operations, collect_skipper }
+    }
+}
+
+impl BusDevice<PayloadType> for DmaCollector {
+    /// Processes data received on the bus, collecting the inputs necessary for witness computation.
+    ///
+    /// # Arguments
+    /// * `_bus_id` - The ID of the bus (unused in this implementation).
+    /// * `data` - The data received from the bus.
+    ///
+    /// # Returns
+    /// A tuple where:
+    /// - The first element indicates whether further processing should continue.
+    /// - The second element contains derived inputs to be sent back to the bus (always empty).
+    fn process_data(
+        &mut self,
+        bus_id: &BusId,
+        data: &[PayloadType],
+    ) -> Option<Vec<(BusId, Vec<PayloadType>)>> {
+        debug_assert!(*bus_id == OPERATION_BUS_ID);
+
+        if self.inputs.len() == self.num_operations as usize {
+            return None;
+        }
+
+        if data[OP_TYPE] as u32 != ZiskOperationType::Dma as u32 {
+            return None;
+        }
+
+        if self.collect_skipper.should_skip() {
+            return None;
+        }
+
+        let data: ExtOperationData<u64> =
+            data.try_into().expect("Dma Collector: Failed to convert data");
+        if let ExtOperationData::OperationDmaData(data) = data {
+            self.inputs.push(data);
+            None
+        } else {
+            panic!("Expected ExtOperationData::OperationDmaData");
+        }
+    }
+
+    /// Returns the bus IDs associated with this instance.
+    ///
+    /// # Returns
+    /// A vector containing the connected bus ID.
+    fn bus_id(&self) -> Vec<BusId> {
+        vec![OPERATION_BUS_ID]
+    }
+
+    fn as_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use p3_field::PrimeField64;
+use zisk_common::{BusDevice, PayloadType};
+
+use zisk_common::{
+    BusDeviceMetrics, BusDeviceMode, ComponentBuilder, Instance, InstanceCtx, InstanceInfo, Planner,
+};
+use zisk_core::ZiskOperationType;
+use zisk_pil::DmaTrace;
+
+use crate::{DmaCounterInputGen, DmaInstance, DmaPlanner, DmaSM};
+
+/// The `DmaManager` struct represents the Dma manager,
+/// which is responsible for managing the Dma state machine.
+pub struct DmaManager {
+    /// Dma state machine
+    dma_sm: Arc<DmaSM>,
+}
+
+impl DmaManager {
+    /// Creates a new instance of `DmaManager`.
+    ///
+    /// # Returns
+    /// An `Arc`-wrapped instance of `DmaManager`.
+    pub fn new<F: PrimeField64>() -> Arc<Self> {
+        let dma_sm = DmaSM::new();
+
+        Arc::new(Self { dma_sm })
+    }
+
+    pub fn build_dma_counter(&self) -> DmaCounterInputGen {
+        DmaCounterInputGen::new(BusDeviceMode::Counter)
+    }
+}
+
+impl<F: PrimeField64> ComponentBuilder<F> for DmaManager {
+    /// Builds and returns a new counter for monitoring dma operations.
+    ///
+    /// # Returns
+    /// A boxed implementation of `DmaCounterInputGen` configured for dma operations.
+    fn build_counter(&self) -> Option<Box<dyn BusDeviceMetrics>> {
+        Some(Box::new(DmaCounterInputGen::new(BusDeviceMode::Counter)))
+    }
+
+    /// Builds a planner to plan dma-related instances.
+    ///
+    /// # Returns
+    /// A boxed implementation of `DmaPlanner`.
+    fn build_planner(&self) -> Box<dyn Planner> {
+        // Get the number of rows that a single dma instance can fill
+        let num_available_rows = self.dma_sm.num_available_rows;
+
+        Box::new(DmaPlanner::new(InstanceInfo::new(
+            DmaTrace::<usize>::AIRGROUP_ID,
+            DmaTrace::<usize>::AIR_ID,
+            num_available_rows,
+            ZiskOperationType::Dma,
+        )))
+    }
+
+    /// Builds an inputs data collector for dma operations.
+    ///
+    /// # Arguments
+    /// * `ictx` - The context of the instance, containing the plan and its associated
+    ///   configurations.
+    ///
+    /// # Returns
+    /// A boxed implementation of `DmaInstance`.
+    ///
+    /// # Panics
+    /// Panics if the provided `air_id` is not supported.
+    fn build_instance(&self, ictx: InstanceCtx) -> Box<dyn Instance<F>> {
+        match ictx.plan.air_id {
+            id if id == DmaTrace::<usize>::AIR_ID => {
+                Box::new(DmaInstance::new(self.dma_sm.clone(), ictx))
+            }
+            _ => {
+                panic!("DmaBuilder::get_instance() Unsupported air_id: {:?}", ictx.plan.air_id)
+            }
+        }
+    }
+
+    fn build_inputs_generator(&self) -> Option<Box<dyn BusDevice<PayloadType>>> {
+        Some(Box::new(DmaCounterInputGen::new(BusDeviceMode::InputGenerator)))
+    }
+}
@@ -0,0 +1,157 @@
+//! The `DmaPlanner` module defines a planner for generating execution plans specific to
+//! dma operations.
+//!
+//! Unlike the other precompiles, every dma operation uses a variable number of rows, so the
+//! operations are packed by rows instead of by count, never splitting one of them between two
+//! instances.
+
+use std::{any::Any, collections::HashMap};
+
+use crate::DmaCounterInputGen;
+
+use zisk_common::{
+    BusDeviceMetrics, CheckPoint, ChunkId, CollectSkipper, InstanceInfo, InstanceType, Metrics,
+    Plan, Planner,
+};
+
+/// The `DmaPlanner` struct organizes execution plans for dma instances.
+pub struct DmaPlanner {
+    /// Dma instance info to be planned, where `num_ops` is the number of rows of an instance.
+    instance_info: InstanceInfo,
+}
+
+impl DmaPlanner {
+    /// Creates a new `DmaPlanner`.
+    ///
+    /// # Arguments
+    /// * `instance_info` - The `InstanceInfo` describing the dma instance to be planned.
+    ///
+    /// # Returns
+    /// A new `DmaPlanner` instance.
+    pub fn new(instance_info: InstanceInfo) -> Self {
+        Self { instance_info }
+    }
+
+    /// Packs the operations of every chunk into instances of `num_rows` rows.
+    ///
+    /// # Arguments
+    /// * `ops_rows` - The chunk id and the number of rows of each operation of every chunk, in
+    ///   execution order.
+    /// * `num_rows` - The number of rows of an instance.
+    ///
+    /// # Returns
+    /// The checkpoint of every instance, together with the number of operations to collect and
+    /// the number of operations to skip on each of its chunks.
+    #[allow(clippy::type_complexity)]
+    pub fn plan_rows(
+        ops_rows: &[(ChunkId, &[u8])],
+        num_rows: usize,
+    ) -> Vec<(CheckPoint, HashMap<ChunkId, (u64, CollectSkipper)>)> {
+        let mut checkpoints = Vec::new();
+        let mut current_scope: HashMap<ChunkId, (u64, CollectSkipper)> = HashMap::new();
+        let mut used_rows = 0;
+
+        for (chunk_id, rows) in ops_rows {
+            for (op_idx, &op_rows) in rows.iter().enumerate() {
+                let op_rows = op_rows as usize;
+                if used_rows + op_rows > num_rows {
+                    let keys = current_scope.keys().cloned().collect::<Vec<_>>();
+                    checkpoints
+                        .push((CheckPoint::Multiple(keys), std::mem::take(&mut current_scope)));
+                    used_rows = 0;
+                }
+
+                current_scope
+                    .entry(*chunk_id)
+                    .or_insert_with(|| (0, CollectSkipper::new(op_idx as u64)))
+                    .0 += 1;
+                used_rows += op_rows;
+            }
+        }
+
+        if !current_scope.is_empty() {
+            let keys = current_scope.keys().cloned().collect::<Vec<_>>();
+            checkpoints.push((CheckPoint::Multiple(keys), current_scope));
+        }
+
+        checkpoints
+    }
+}
+
+impl Planner for DmaPlanner {
+    /// Generates execution plans for dma instances.
+    ///
+    /// # Arguments
+    /// * `counters` - A vector of counters, each associated with a `ChunkId` and
+    ///   `DmaCounterInputGen` metrics data.
+    ///
+    /// # Returns
+    /// A vector of `Plan` instances representing execution configurations for the instances.
+    ///
+    /// # Panics
+    /// Panics if any counter cannot be downcasted to a `DmaCounterInputGen`.
+    fn plan(&self, counters: Vec<(ChunkId, Box<dyn BusDeviceMetrics>)>) -> Vec<Plan> {
+        let ops_rows: Vec<_> = counters
+            .iter()
+            .map(|(chunk_id, counter)| {
+                let dma_counter =
+                    Metrics::as_any(&**counter).downcast_ref::<DmaCounterInputGen>().unwrap();
+                (*chunk_id, dma_counter.ops_rows())
+            })
+            .collect();
+
+        Self::plan_rows(&ops_rows, self.instance_info.num_ops)
+            .into_iter()
+            .map(|(check_point, collect_info)| {
+                let converted: Box<dyn Any> = Box::new(collect_info);
+                Plan::new(
+                    self.instance_info.airgroup_id,
+                    self.instance_info.air_id,
+                    None,
+                    InstanceType::Instance,
+                    check_point,
+                    Some(converted),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_info(plan: &HashMap<ChunkId, (u64, CollectSkipper)>, chunk_id: usize) -> (u64, u64) {
+        let (num_ops, skipper) = plan[&ChunkId(chunk_id)];
+        (num_ops, skipper.skip)
+    }
+
+    #[test]
+    fn test_plan_rows_empty() {
+        assert!(DmaPlanner::plan_rows(&[], 8).is_empty());
+        assert!(DmaPlanner::plan_rows(&[(ChunkId(0), &[])], 8).is_empty());
+    }
+
+    #[test]
+    fn test_plan_rows_does_not_split_operations() {
+        let plans = DmaPlanner::plan_rows(&[(ChunkId(0), &[3, 4, 2])], 8);
+        assert_eq!(plans.len(), 2);
+        assert_eq!(collect_info(&plans[0].1, 0), (2, 0));
+        assert_eq!(collect_info(&plans[1].1, 0), (1, 2));
+    }
+
+    #[test]
+    fn test_plan_rows_across_chunks() {
+        let plans = DmaPlanner::plan_rows(&[(ChunkId(2), &[5, 2]), (ChunkId(7), &[1, 8])], 8);
+        assert_eq!(plans.len(), 2);
+
+        let CheckPoint::Multiple(mut keys) = plans[0].0.clone() else { panic!() };
+        keys.sort_by_key(|chunk_id| chunk_id.0);
+        assert_eq!(keys, vec![ChunkId(2), ChunkId(7)]);
+        assert_eq!(collect_info(&plans[0].1, 2), (2, 0));
+        assert_eq!(collect_info(&plans[0].1, 7), (1, 0));
+
+        assert_eq!(plans[1].0, CheckPoint::Multiple(vec![ChunkId(7)]));
+        assert_eq!(collect_info(&plans[1].1, 7), (1, 1));
+    }
+}
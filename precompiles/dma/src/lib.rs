@@ -0,0 +1,11 @@
+mod dma;
+mod dma_bus_device;
+mod dma_instance;
+mod dma_manager;
+mod dma_planner;
+
+pub use dma::*;
+pub use dma_bus_device::*;
+pub use dma_instance::*;
+pub use dma_manager::*;
+pub use dma_planner::*;
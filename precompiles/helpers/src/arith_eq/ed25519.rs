@@ -0,0 +1,171 @@
+use num_bigint::BigInt;
+
+use super::{bigint2_to_8_u64, bigint_from_u64s, bigint_to_4_u64};
+
+/// Curve25519 base field prime 2^255 - 19, as little-endian u64 limbs
+pub const ED25519_PRIME_U64: [u64; 4] =
+    [0xffffffffffffffed, 0xffffffffffffffff, 0xffffffffffffffff, 0x7fffffffffffffff];
+
+/// Twisted Edwards constant d = -121665/121666 of ed25519, as little-endian u64 limbs
+pub const ED25519_D_U64: [u64; 4] =
+    [0x75eb4dca135978a3, 0x00700a4d4141d8ab, 0x8cc740797779e898, 0x52036cee2b6ffe73];
+
+pub fn ed25519_prime() -> BigInt {
+    bigint_from_u64s(&ED25519_PRIME_U64)
+}
+
+pub fn ed25519_d() -> BigInt {
+    bigint_from_u64s(&ED25519_D_U64)
+}
+
+/// Given a value `value`, returns its inverse modulo the curve25519 base field prime
+pub fn ed25519_inv(value: &BigInt) -> BigInt {
+    let p = ed25519_prime();
+    let value = ((value % &p) + &p) % &p;
+    value.modpow(&(&p - 2), &p)
+}
+
+pub fn curve25519_mod(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4], d: &mut [u64; 4]) {
+    let a = bigint_from_u64s(a);
+    let b = bigint_from_u64s(b);
+    let c = bigint_from_u64s(c);
+
+    let res = (&a * &b + &c) % ed25519_prime();
+    bigint_to_4_u64(&res, d);
+}
+
+/// Doubles a point of the twisted Edwards curve ed25519 (a = -1), using the d-free formulas
+/// x3 = 2·x1·y1 / (y1² - x1²) and y3 = (y1² + x1²) / (2 - (y1² - x1²))
+pub fn ed25519_dbl(p1: &[u64; 8], p: &mut [u64; 8]) {
+    let prime = &ed25519_prime();
+    let x1 = bigint_from_u64s(&p1[0..4]);
+    let y1 = bigint_from_u64s(&p1[4..8]);
+
+    let x1_sq = &x1 * &x1;
+    let y1_sq = &y1 * &y1;
+    let s = (((&y1_sq - &x1_sq) % prime) + prime) % prime;
+    let x3 = (2 * &x1 * &y1 * ed25519_inv(&s)) % prime;
+    let y3 = ((&y1_sq + &x1_sq) * ed25519_inv(&(2 - &s))) % prime;
+
+    bigint2_to_8_u64(&x3, &y3, p);
+}
+
+/// Adds two points of the twisted Edwards curve ed25519 (a = -1), using the unified formulas
+/// x3 = (x1·y2 + y1·x2) / (1 + d·x1·x2·y1·y2) and y3 = (y1·y2 + x1·x2) / (1 - d·x1·x2·y1·y2),
+/// which also double a point when both are equal
+pub fn ed25519_add(p1: &[u64; 8], p2: &[u64; 8], p: &mut [u64; 8]) {
+    let prime = &ed25519_prime();
+    let x1 = bigint_from_u64s(&p1[0..4]);
+    let y1 = bigint_from_u64s(&p1[4..8]);
+    let x2 = bigint_from_u64s(&p2[0..4]);
+    let y2 = bigint_from_u64s(&p2[4..8]);
+
+    let u = (ed25519_d() * &x1 * &y1 * &x2 * &y2) % prime;
+    let x3 = ((&x1 * &y2 + &y1 * &x2) * ed25519_inv(&(1 + &u))) % prime;
+    let y3 = ((&y1 * &y2 + &x1 * &x2) * ed25519_inv(&(1 - &u))) % prime;
+
+    bigint2_to_8_u64(&x3, &y3, p);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const B: [u64; 8] = [
+        0xc9562d608f25d51a,
+        0x692cc7609525a7b2,
+        0xc0a4e231fdd6dc5c,
+        0x216936d3cd6e53fe,
+        0x6666666666666658,
+        0x6666666666666666,
+        0x6666666666666666,
+        0x6666666666666666,
+    ];
+    const B2: [u64; 8] = [
+        0x83c5a14e2843ce0e,
+        0x080d8e4515d7a45f,
+        0x3d043b7d1833e7ac,
+        0x36ab384c9f5a046c,
+        0x0e5f46ae6af8a3c9,
+        0x97390f5164385156,
+        0x1da25ee8c9a21f56,
+        0x2260cdf3092329c2,
+    ];
+    const B3: [u64; 8] = [
+        0xac62485fd3f8e25c,
+        0x6343981981624886,
+        0x1ff4ae743edac83a,
+        0x67ae9c4a22928f49,
+        0x02c3684878f5b4d4,
+        0x9f16ec1767240304,
+        0xa126a18e60269ef7,
+        0x1267b1d177ee69ab,
+    ];
+    const IDENTITY: [u64; 8] = [0, 0, 0, 0, 1, 0, 0, 0];
+
+    #[test]
+    fn test_d() {
+        // d·121666 = -121665 (mod p)
+        let p = ed25519_prime();
+        assert_eq!((ed25519_d() * 121666 + 121665) % &p, BigInt::from(0));
+    }
+
+    #[test]
+    fn test_inv() {
+        let p = ed25519_prime();
+        for value in [BigInt::from(1), BigInt::from(2), BigInt::from(-5), &p - 1u32, ed25519_d()] {
+            let value_inv = ed25519_inv(&value);
+            assert_eq!(((value * value_inv) % &p + &p) % &p, BigInt::from(1));
+        }
+    }
+
+    #[test]
+    fn test_curve25519_mod() {
+        let a = [u64::MAX; 4];
+        let mut d = [0u64; 4];
+        curve25519_mod(&a, &a, &a, &mut d);
+        let a = bigint_from_u64s(&a);
+        let expected = (&a * &a + &a) % ed25519_prime();
+        assert_eq!(bigint_from_u64s(&d), expected);
+
+        // p·1 + 0 is zero and (p-1)·1 + 1 wraps around to zero
+        let mut d = [1u64; 4];
+        curve25519_mod(&ED25519_PRIME_U64, &[1, 0, 0, 0], &[0; 4], &mut d);
+        assert_eq!(d, [0; 4]);
+        let mut p_minus_1 = ED25519_PRIME_U64;
+        p_minus_1[0] -= 1;
+        curve25519_mod(&p_minus_1, &[1, 0, 0, 0], &[1, 0, 0, 0], &mut d);
+        assert_eq!(d, [0; 4]);
+    }
+
+    #[test]
+    fn test_dbl() {
+        let mut p = [0u64; 8];
+        ed25519_dbl(&B, &mut p);
+        assert_eq!(p, B2);
+        ed25519_dbl(&IDENTITY, &mut p);
+        assert_eq!(p, IDENTITY);
+    }
+
+    #[test]
+    fn test_add() {
+        let mut p = [0u64; 8];
+        ed25519_add(&B2, &B, &mut p);
+        assert_eq!(p, B3);
+        ed25519_add(&B, &B2, &mut p);
+        assert_eq!(p, B3);
+
+        // The unified formulas double equal points and the identity is the neutral element
+        ed25519_add(&B, &B, &mut p);
+        assert_eq!(p, B2);
+        ed25519_add(&B, &IDENTITY, &mut p);
+        assert_eq!(p, B);
+
+        // P + (-P) is the identity, with -(x, y) = (-x, y)
+        let mut neg_b = B;
+        let neg_x = ed25519_prime() - bigint_from_u64s(&B[0..4]);
+        bigint_to_4_u64(&neg_x, (&mut neg_b[0..4]).try_into().unwrap());
+        ed25519_add(&B, &neg_b, &mut p);
+        assert_eq!(p, IDENTITY);
+    }
+}
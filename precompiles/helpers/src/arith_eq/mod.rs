@@ -1,7 +1,9 @@
 mod arith256;
 mod common;
+mod ed25519;
 mod secp256k1;
 
 pub use arith256::*;
 pub use common::*;
+pub use ed25519::*;
 pub use secp256k1::*;
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+
+use circuit::{gate_u64_add, gate_u64_xor, GateState, GateU64, PinId};
+
+use super::{BLAKE2B_G_INDEXES, BLAKE2B_STATE_SIZE_BITS};
+
+/// Builds the circuit of a single BLAKE2b round over the working vector `v` and the message
+/// words `m`, both located at the state input references, in this order
+pub fn blake2br_internal(gate_state: &RefCell<GateState>) {
+    #[cfg(debug_assertions)]
+    gate_state.borrow().print_refs(&gate_state.borrow().sin_refs, "Before round");
+
+    // The input references are also gates of the circuit: pass each input bit through
+    // a XOR with zero so that every row of the circuit is used
+    let zero_ref = gate_state.borrow().gate_config.zero_ref.unwrap();
+    let sin_refs = gate_state.borrow().sin_refs.clone();
+    for &sin_ref in sin_refs.iter() {
+        gate_state.borrow_mut().xor(sin_ref, PinId::A, zero_ref, PinId::A, sin_ref);
+    }
+
+    // Map the working vector and the message words to the input references
+    let mut v = [GateU64::new(gate_state); 16];
+    let mut m = [GateU64::new(gate_state); 16];
+    for i in 0..16 {
+        for j in 0..64 {
+            v[i].bits[j].ref_ = sin_refs[i * 64 + j];
+            v[i].bits[j].pin_id = PinId::D;
+            m[i].bits[j].ref_ = sin_refs[BLAKE2B_STATE_SIZE_BITS + i * 64 + j];
+            m[i].bits[j].pin_id = PinId::D;
+        }
+    }
+
+    // Apply the mixing function to the columns and then to the diagonals
+    for (i, &[a, b, c, d]) in BLAKE2B_G_INDEXES.iter().enumerate() {
+        let x = m[2 * i];
+        let y = m[2 * i + 1];
+        blake2b_g(gate_state, &mut v, [a, b, c, d], &x, &y);
+    }
+
+    // Add 1024 more gates to make sure that the working vector output is located in the expected gates
+    for i in 0..16 {
+        for j in 0..64 {
+            let group = (i * 64 + j) as u64 / gate_state.borrow().gate_config.sout_ref_group_by;
+            let group_pos = (i * 64 + j) as u64 % gate_state.borrow().gate_config.sout_ref_group_by;
+            let ref_idx = gate_state.borrow().gate_config.sout_first_ref
+                + group * gate_state.borrow().gate_config.sout_ref_distance
+                + group_pos;
+            gate_state.borrow_mut().xor(
+                v[i].bits[j].ref_,
+                v[i].bits[j].pin_id,
+                zero_ref,
+                PinId::A,
+                ref_idx,
+            );
+            gate_state.borrow_mut().sout_refs[i * 64 + j] = ref_idx;
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    gate_state.borrow().print_refs(&gate_state.borrow().sout_refs, "After round");
+}
+
+/// BLAKE2b mixing function G
+fn blake2b_g<'a>(
+    gate_state: &'a RefCell<GateState>,
+    v: &mut [GateU64<'a>; 16],
+    [a, b, c, d]: [usize; 4],
+    x: &GateU64<'a>,
+    y: &GateU64<'a>,
+) {
+    for (z, rot_d, rot_b) in [(x, 32, 24), (y, 16, 63)] {
+        // a = a + b + z
+        let mut tmp = GateU64::new(gate_state);
+        gate_u64_add(&mut gate_state.borrow_mut(), &v[a], &v[b], &mut tmp);
+        gate_u64_add(&mut gate_state.borrow_mut(), &tmp, z, &mut v[a]);
+
+        // d = (d ^ a) >>> rot_d
+        let mut tmp = GateU64::new(gate_state);
+        gate_u64_xor(&mut gate_state.borrow_mut(), &v[d], &v[a], &mut tmp);
+        tmp.rotate_right(rot_d);
+        v[d] = tmp;
+
+        // c = c + d
+        let mut tmp = GateU64::new(gate_state);
+        gate_u64_add(&mut gate_state.borrow_mut(), &v[c], &v[d], &mut tmp);
+        v[c] = tmp;
+
+        // b = (b ^ c) >>> rot_b
+        let mut tmp = GateU64::new(gate_state);
+        gate_u64_xor(&mut gate_state.borrow_mut(), &v[b], &v[c], &mut tmp);
+        tmp.rotate_right(rot_b);
+        v[b] = tmp;
+    }
+}
@@ -0,0 +1,211 @@
+#![allow(clippy::needless_range_loop)]
+
+use std::cell::RefCell;
+
+use circuit::{GateConfig, GateState, PinId};
+
+mod blake2br;
+
+use blake2br::blake2br_internal;
+
+pub const BLAKE2B_STATE_SIZE_BITS: usize = 1024; // 16 x 64 bits (working vector)
+
+/// BLAKE2b initialization vector
+pub const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// BLAKE2b message schedule. Round `r` uses the permutation `BLAKE2B_SIGMA[r % 10]`
+pub const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Indexes of the working vector mixed by each application of G within a round:
+/// first the four columns, then the four diagonals
+const BLAKE2B_G_INDEXES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+// Blake2br Configuration
+#[rustfmt::skip]
+pub static BLAKE2BR_GATE_CONFIG: GateConfig = GateConfig::with_values(
+    23216,
+    25000,
+    Some(0),
+    16,
+    2,
+    2048, // 1024 (working vector bits) + 1024 (message bits)
+    15,
+    16 + 2048 * 15 / 2,
+    2,
+    1024, // 1024 (working vector bits)
+    15,
+);
+
+/// Applies a single BLAKE2b round to the working vector `v`, where `m` holds the message words
+/// already permuted by the round's message schedule, i.e., `m[i] = block[BLAKE2B_SIGMA[r][i]]`
+pub fn blake2b_round(v: &mut [u64; 16], m: &[u64; 16]) {
+    for (i, &[a, b, c, d]) in BLAKE2B_G_INDEXES.iter().enumerate() {
+        let (x, y) = (m[2 * i], m[2 * i + 1]);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+}
+
+/// BLAKE2b compression function F as specified in EIP-152, with a configurable number of rounds
+pub fn blake2b_compress(rounds: u32, h: &mut [u64; 8], m: &[u64; 16], t: [u64; 2], f: bool) {
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= t[0];
+    v[13] ^= t[1];
+    if f {
+        v[14] = !v[14];
+    }
+
+    for r in 0..rounds as usize {
+        let s = &BLAKE2B_SIGMA[r % 10];
+        let m_r: [u64; 16] = std::array::from_fn(|i| m[s[i]]);
+        blake2b_round(&mut v, &m_r);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Applies a single BLAKE2b round to `state` through its circuit representation
+pub fn blake2br(
+    state: &mut [u64; 16],
+    input: &[u64; 16],
+    get_circuit_topology: bool,
+) -> Option<GateState> {
+    // Initialize the gate state
+    let gate_state = RefCell::new(GateState::new(BLAKE2BR_GATE_CONFIG.clone()));
+
+    // Copy the working vector and the message bits into the state
+    for (offset, words) in [(0, state as &[u64; 16]), (BLAKE2B_STATE_SIZE_BITS, input)] {
+        for i in 0..16 {
+            for j in 0..64 {
+                let bit = ((words[i] >> j) & 1) as u8;
+                let group = (offset + i * 64 + j) as u64 / BLAKE2BR_GATE_CONFIG.sin_ref_group_by;
+                let group_pos =
+                    (offset + i * 64 + j) as u64 % BLAKE2BR_GATE_CONFIG.sin_ref_group_by;
+                let ref_idx = BLAKE2BR_GATE_CONFIG.sin_first_ref
+                    + group * BLAKE2BR_GATE_CONFIG.sin_ref_distance
+                    + group_pos;
+                gate_state.borrow_mut().gates[ref_idx as usize].pins[PinId::A].bit = bit;
+            }
+        }
+    }
+
+    // Execute the round
+    blake2br_internal(&gate_state);
+
+    #[cfg(debug_assertions)]
+    gate_state.borrow().print_circuit_topology();
+
+    if get_circuit_topology {
+        // The blake2br circuit topology is completely known after a single execution
+        return Some(gate_state.into_inner());
+    }
+
+    // Get the output working vector
+    let gate_state = gate_state.borrow();
+    for i in 0..16 {
+        let mut word = 0u64;
+        for j in 0..64 {
+            let ref_idx = gate_state.sout_refs[i * 64 + j] as usize;
+            word |= (gate_state.gates[ref_idx].pins[PinId::D].bit as u64) << j;
+        }
+        state[i] = word;
+    }
+
+    None
+}
+
+// Get the circuit topology of a BLAKE2b round
+pub fn blake2br_topology() -> GateState {
+    // Apply the round to any input and get the circuit topology
+    let mut state = [0u64; 16];
+    let input = [0u64; 16];
+    blake2br(&mut state, &input, true).expect("Failed to get circuit topology")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blake2b_compress, blake2b_round, blake2br};
+
+    #[test]
+    fn test_round_circuit() {
+        let mut v: [u64; 16] =
+            std::array::from_fn(|i| 0x0123456789abcdefu64.rotate_left(i as u32 * 5) ^ i as u64);
+        let m: [u64; 16] = std::array::from_fn(|i| {
+            0xfedcba9876543210u64.rotate_right(i as u32 * 3) ^ (i as u64) << 7
+        });
+
+        let mut expected = v;
+        blake2b_round(&mut expected, &m);
+        blake2br(&mut v, &m, false);
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn test_eip152_vector() {
+        // Test vector 5 of EIP-152: 12 rounds over the message "abc"
+        let mut h: [u64; 8] = [
+            0x6a09e667f2bdc948,
+            0xbb67ae8584caa73b,
+            0x3c6ef372fe94f82b,
+            0xa54ff53a5f1d36f1,
+            0x510e527fade682d1,
+            0x9b05688c2b3e6c1f,
+            0x1f83d9abfb41bd6b,
+            0x5be0cd19137e2179,
+        ];
+        let mut m = [0u64; 16];
+        m[0] = 0x636261;
+        blake2b_compress(12, &mut h, &m, [3, 0], true);
+
+        let expected: [u64; 8] = [
+            0x0d4d1c983fa580ba,
+            0xe9f6129fb697276a,
+            0xb7c45a68142f214c,
+            0xd1a2ffdb6fbb124b,
+            0x2d79ab2a39c5877d,
+            0x95cc3345ded552c2,
+            0x5a92f1dba88ad318,
+            0x239900d4ed8623b9,
+        ];
+        assert_eq!(h, expected);
+    }
+}
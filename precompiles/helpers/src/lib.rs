@@ -1,7 +1,11 @@
 mod arith_eq;
+mod blake2;
 mod keccak;
 mod sha256;
 
 pub use arith_eq::*;
+pub use blake2::{
+    blake2b_compress, blake2b_round, blake2br, blake2br_topology, BLAKE2B_IV, BLAKE2B_SIGMA,
+};
 pub use keccak::{keccak, keccakf_topology};
 pub use sha256::{sha256, sha256f, sha256f_topology};
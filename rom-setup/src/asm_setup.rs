@@ -12,7 +12,7 @@ pub fn generate_assembly(
     zisk_path: &Path,
     output_path: &Path,
     verbose: bool,
-) -> Result<(), anyhow::Error> {
+) -> Result<Vec<PathBuf>, anyhow::Error> {
     // Read the ELF file and check if it is a valid ELF file
     let elf_file_path = PathBuf::from(elf);
     let file_data = std::fs::read(&elf_file_path)?;
@@ -32,12 +32,11 @@ pub fn generate_assembly(
     let bin_rom_file = format!("{}-rom.bin", file_stem);
     let bin_rom_file = base_path.with_file_name(bin_rom_file);
 
-    [
+    let targets = [
         (bin_mt_file, AsmGenerationMethod::AsmMinimalTraces),
         (bin_rom_file, AsmGenerationMethod::AsmRomHistogram),
-    ]
-    .iter()
-    .for_each(|(file, gen_method)| {
+    ];
+    targets.iter().for_each(|(file, gen_method)| {
         let asm_file = file.with_extension("asm");
         // Convert the ELF file to Zisk format and generates an assembly file
         let rv2zk = Riscv2zisk::new(elf_file_path.to_str().unwrap().to_string());
@@ -77,5 +76,6 @@ pub fn generate_assembly(
         }
     });
 
-    Ok(())
+    // Return the generated files, i.e. the assembly sources and the emulator binaries
+    Ok(targets.into_iter().flat_map(|(file, _)| [file.with_extension("asm"), file]).collect())
 }
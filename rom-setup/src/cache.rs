@@ -0,0 +1,397 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use zisk_pil::{RomRomTrace, PILOUT_HASH};
+
+use crate::DEFAULT_CACHE_PATH;
+
+/// Extension of the sidecar manifests describing the artifacts of a ROM setup
+pub const CACHE_MANIFEST_EXTENSION: &str = "manifest.json";
+
+/// A file generated by the ROM setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheArtifact {
+    /// File name, relative to the cache directory
+    pub file: String,
+    pub size: u64,
+    /// Blake3 hash of the file contents when it was generated
+    pub hash: String,
+}
+
+impl CacheArtifact {
+    /// Returns the path of the artifact in `cache_path`, failing if its name is not a plain file
+    /// name, e.g. `../file` or an absolute path read from a crafted manifest, which would refer to
+    /// a file outside the cache directory
+    pub fn path(&self, cache_path: &Path) -> Result<PathBuf> {
+        if !is_file_name(Path::new(&self.file)) {
+            anyhow::bail!("Invalid cache artifact name {:?}", self.file);
+        }
+        Ok(cache_path.join(&self.file))
+    }
+}
+
+/// Integrity of a cached artifact, compared with its manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheArtifactStatus {
+    Ok,
+    Missing,
+    Modified,
+}
+
+/// Sidecar manifest of a ROM setup, stored in the cache directory next to its artifacts as
+/// `{elf_hash}_{pilout_hash}_{rows}_{blowup}.manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheManifest {
+    /// ELF file the setup was generated from
+    pub elf: PathBuf,
+    pub elf_hash: String,
+    pub pilout_hash: String,
+    pub rows: u64,
+    pub blowup_factor: u64,
+    /// Creation time, in seconds since the Unix epoch
+    pub created: u64,
+    pub artifacts: Vec<CacheArtifact>,
+}
+
+impl CacheManifest {
+    /// Creates the manifest of the setup of `elf`, whose artifacts are `files`, all of them in
+    /// `cache_path`
+    pub fn new(
+        elf: &Path,
+        elf_hash: &str,
+        blowup_factor: u64,
+        cache_path: &Path,
+        files: &[PathBuf],
+    ) -> Result<Self> {
+        let artifacts = files
+            .iter()
+            .map(|file| {
+                let name = file
+                    .strip_prefix(cache_path)
+                    .ok()
+                    .filter(|name| is_file_name(name))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Artifact {:?} is not in the cache {:?}", file, cache_path)
+                    })?
+                    .to_string_lossy()
+                    .to_string();
+                let size = fs::metadata(file)
+                    .with_context(|| format!("Error reading artifact: {:?}", file))?
+                    .len();
+                Ok(CacheArtifact { file: name, size, hash: hash_file(file)? })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            elf: fs::canonicalize(elf).unwrap_or_else(|_| elf.to_path_buf()),
+            elf_hash: elf_hash.to_string(),
+            pilout_hash: PILOUT_HASH.to_string(),
+            rows: RomRomTrace::<usize>::NUM_ROWS as u64,
+            blowup_factor,
+            created: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            artifacts,
+        })
+    }
+
+    /// Returns the path of the manifest in `cache_path`, named as the ROM custom commit file
+    pub fn path(&self, cache_path: &Path) -> PathBuf {
+        cache_path.join(format!(
+            "{}_{}_{}_{}.{}",
+            self.elf_hash,
+            self.pilout_hash,
+            self.rows,
+            self.blowup_factor,
+            CACHE_MANIFEST_EXTENSION
+        ))
+    }
+
+    pub fn save(&self, cache_path: &Path) -> Result<PathBuf> {
+        let path = self.path(cache_path);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Error writing cache manifest: {:?}", path))?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Error reading cache manifest: {:?}", path))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Error parsing cache manifest: {:?}", path))
+    }
+
+    /// Total size of the artifacts, in bytes
+    pub fn size(&self) -> u64 {
+        self.artifacts.iter().map(|artifact| artifact.size).sum()
+    }
+
+    /// Returns true if the setup was generated for a different PIL than the current one
+    pub fn is_outdated(&self) -> bool {
+        self.pilout_hash != PILOUT_HASH
+    }
+
+    /// Re-checks the size and hash of every artifact
+    pub fn verify(&self, cache_path: &Path) -> Vec<(&CacheArtifact, CacheArtifactStatus)> {
+        self.artifacts
+            .iter()
+            .map(|artifact| {
+                let Ok(file) = artifact.path(cache_path) else {
+                    return (artifact, CacheArtifactStatus::Missing);
+                };
+                let status = match fs::metadata(&file) {
+                    Err(_) => CacheArtifactStatus::Missing,
+                    Ok(metadata) if metadata.len() != artifact.size => {
+                        CacheArtifactStatus::Modified
+                    }
+                    Ok(_) => match hash_file(&file) {
+                        Ok(hash) if hash == artifact.hash => CacheArtifactStatus::Ok,
+                        Ok(_) => CacheArtifactStatus::Modified,
+                        Err(_) => CacheArtifactStatus::Missing,
+                    },
+                };
+                (artifact, status)
+            })
+            .collect()
+    }
+}
+
+/// A ROM setup found in the cache directory, with the path of its manifest
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub manifest_path: PathBuf,
+    pub manifest: CacheManifest,
+}
+
+/// Cache directory of the ROM setup artifacts
+#[derive(Debug, Clone)]
+pub struct RomCache {
+    path: PathBuf,
+}
+
+impl RomCache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the default cache directory, `$HOME/.zisk/cache`
+    pub fn default_path() -> PathBuf {
+        std::env::var("HOME")
+            .map(PathBuf::from)
+            .map(|home| home.join(DEFAULT_CACHE_PATH))
+            .unwrap_or_else(|_| panic!("$HOME environment variable is not set"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the setups of the cache, oldest first
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        for file in self.files()? {
+            if file.to_string_lossy().ends_with(CACHE_MANIFEST_EXTENSION) {
+                let manifest = CacheManifest::load(&file)?;
+                entries.push(CacheEntry { manifest_path: file, manifest });
+            }
+        }
+        entries.sort_by_key(|entry| entry.manifest.created);
+        Ok(entries)
+    }
+
+    /// Returns the files of the cache that do not belong to any setup, e.g. generated before
+    /// manifests existed
+    pub fn unmanaged_files(&self, entries: &[CacheEntry]) -> Result<Vec<PathBuf>> {
+        let managed: HashSet<PathBuf> = entries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .manifest
+                    .artifacts
+                    .iter()
+                    .filter_map(|artifact| artifact.path(&self.path).ok())
+                    .chain(std::iter::once(entry.manifest_path.clone()))
+            })
+            .collect();
+        Ok(self.files()?.into_iter().filter(|file| !managed.contains(file)).collect())
+    }
+
+    /// Removes the setups in `remove`, keeping the artifacts shared with the setups in `keep`, and
+    /// returns the number of bytes freed.  Nothing is removed if any artifact name is not a plain
+    /// file name.
+    pub fn remove(&self, remove: &[CacheEntry], keep: &[CacheEntry]) -> Result<u64> {
+        for entry in remove {
+            for artifact in &entry.manifest.artifacts {
+                artifact.path(&self.path).with_context(|| {
+                    format!("Refusing to remove the setup of {:?}", entry.manifest_path)
+                })?;
+            }
+        }
+
+        let kept: HashSet<&str> = keep
+            .iter()
+            .flat_map(|entry| entry.manifest.artifacts.iter().map(|a| a.file.as_str()))
+            .collect();
+
+        let mut freed = 0;
+        for entry in remove {
+            for artifact in &entry.manifest.artifacts {
+                if kept.contains(artifact.file.as_str()) {
+                    continue;
+                }
+                let file = artifact.path(&self.path)?;
+                match fs::remove_file(&file) {
+                    Ok(()) => freed += artifact.size,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        return Err(e).with_context(|| format!("Failed to remove {:?}", file))
+                    }
+                }
+            }
+            fs::remove_file(&entry.manifest_path)
+                .with_context(|| format!("Failed to remove {:?}", entry.manifest_path))?;
+        }
+        Ok(freed)
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.path)
+            .with_context(|| format!("Error reading cache directory: {:?}", self.path))?
+        {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Returns true if `name` is a plain file name, so that it can only refer to a file directly inside
+/// a directory
+fn is_file_name(name: &Path) -> bool {
+    let mut components = name.components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(_)), None))
+}
+
+/// Returns the blake3 hash of a file, reading it in chunks
+fn hash_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path).with_context(|| format!("Error reading file: {:?}", path))?;
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a fresh temporary directory of this test, with a `cache` subdirectory
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zisk_cache_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("cache")).unwrap();
+        dir
+    }
+
+    fn entry(cache: &RomCache, elf_hash: &str, files: &[&str]) -> CacheEntry {
+        let artifacts = files
+            .iter()
+            .map(|file| CacheArtifact { file: file.to_string(), size: 4, hash: String::new() })
+            .collect();
+        let manifest = CacheManifest {
+            elf: PathBuf::from("guest"),
+            elf_hash: elf_hash.to_string(),
+            pilout_hash: PILOUT_HASH.to_string(),
+            rows: 1,
+            blowup_factor: 2,
+            created: 0,
+            artifacts,
+        };
+        let manifest_path = manifest.save(cache.path()).unwrap();
+        CacheEntry { manifest_path, manifest }
+    }
+
+    #[test]
+    fn test_artifact_path() {
+        let cache_path = Path::new("/cache");
+        let artifact =
+            |file: &str| CacheArtifact { file: file.to_string(), size: 0, hash: "".into() };
+        assert_eq!(artifact("rom.bin").path(cache_path).unwrap(), cache_path.join("rom.bin"));
+        for file in ["", ".", "..", "../rom.bin", "dir/rom.bin", "/etc/passwd"] {
+            assert!(artifact(file).path(cache_path).is_err(), "{:?}", file);
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_files_outside_the_cache() {
+        let dir = temp_dir("new");
+        let cache_path = dir.join("cache");
+        fs::write(cache_path.join("rom.bin"), b"rom").unwrap();
+        fs::write(dir.join("outside.bin"), b"outside").unwrap();
+
+        let manifest = CacheManifest::new(
+            Path::new("guest"),
+            "elf",
+            2,
+            &cache_path,
+            &[cache_path.join("rom.bin")],
+        )
+        .unwrap();
+        assert_eq!(manifest.artifacts[0].file, "rom.bin");
+        assert_eq!(manifest.size(), 3);
+
+        let files = [cache_path.join("rom.bin"), dir.join("outside.bin")];
+        assert!(CacheManifest::new(Path::new("guest"), "elf", 2, &cache_path, &files).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_remove_keeps_shared_artifacts() {
+        let dir = temp_dir("remove");
+        let cache = RomCache::new(dir.join("cache"));
+        for file in ["a.bin", "b.bin", "shared.bin"] {
+            fs::write(cache.path().join(file), b"data").unwrap();
+        }
+        let old = entry(&cache, "old", &["a.bin", "shared.bin"]);
+        let new = entry(&cache, "new", &["b.bin", "shared.bin"]);
+
+        assert_eq!(
+            cache.remove(std::slice::from_ref(&old), std::slice::from_ref(&new)).unwrap(),
+            4
+        );
+        assert!(!cache.path().join("a.bin").exists());
+        assert!(!old.manifest_path.exists());
+        assert!(cache.path().join("shared.bin").exists());
+        assert_eq!(cache.entries().unwrap().len(), 1);
+        assert!(cache.unmanaged_files(&[new]).unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_remove_rejects_files_outside_the_cache() {
+        let dir = temp_dir("remove_outside");
+        let cache = RomCache::new(dir.join("cache"));
+        let outside = dir.join("outside.bin");
+        fs::write(cache.path().join("a.bin"), b"data").unwrap();
+        fs::write(&outside, b"data").unwrap();
+
+        for file in ["../outside.bin", outside.to_str().unwrap()] {
+            let crafted = entry(&cache, "crafted", &["a.bin", file]);
+            assert!(cache.remove(std::slice::from_ref(&crafted), &[]).is_err());
+            assert_eq!(crafted.manifest.verify(cache.path())[1].1, CacheArtifactStatus::Missing);
+            assert!(outside.exists());
+            assert!(cache.path().join("a.bin").exists());
+            assert!(crafted.manifest_path.exists());
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
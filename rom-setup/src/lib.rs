@@ -1,10 +1,12 @@
 mod asm_setup;
+mod cache;
 mod rom_full_setup;
 mod rom_merkle;
 mod rom_vkey;
 mod utils;
 
 pub use asm_setup::*;
+pub use cache::*;
 pub use rom_full_setup::*;
 pub use rom_merkle::*;
 pub use rom_vkey::*;
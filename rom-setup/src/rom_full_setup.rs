@@ -6,7 +6,10 @@ use std::{
 use colored::Colorize;
 use log::info;
 
-use crate::{get_elf_data_hash, DEFAULT_CACHE_PATH};
+use crate::{
+    get_elf_bin_file_path_with_hash, get_elf_data_hash, get_rom_blowup_factor, CacheManifest,
+    DEFAULT_CACHE_PATH,
+};
 
 pub fn rom_full_setup(
     elf: &PathBuf,
@@ -39,14 +42,24 @@ pub fn rom_full_setup(
     let elf_hash = get_elf_data_hash(elf)?;
 
     info!("Computing assembly setup");
-    crate::generate_assembly(elf, &elf_hash, zisk_path, output_path.as_path(), verbose)?;
+    let mut artifacts =
+        crate::generate_assembly(elf, &elf_hash, zisk_path, output_path.as_path(), verbose)?;
 
     info!("Computing merkle root");
-    crate::rom_merkle_setup(elf, &elf_hash, output_path.as_path(), proving_key, false)?;
+    let rom_root =
+        crate::rom_merkle_setup(elf, &elf_hash, output_path.as_path(), proving_key, false)?;
 
     info!("Computing Verification key");
+    let vkey_path = crate::rom_vkey(elf, &elf_hash, &rom_root, output_path.as_path(), proving_key)?;
+    info!("Program verification key written to {}", vkey_path.display());
 
-    crate::rom_vkey()?;
+    info!("Writing cache manifest");
+    let blowup_factor = get_rom_blowup_factor(proving_key);
+    artifacts.push(get_elf_bin_file_path_with_hash(elf, &elf_hash, &output_path, blowup_factor)?);
+    artifacts.push(vkey_path);
+    let manifest =
+        CacheManifest::new(elf, &elf_hash, blowup_factor, output_path.as_path(), &artifacts)?;
+    manifest.save(output_path.as_path())?;
 
     println!();
     info!(
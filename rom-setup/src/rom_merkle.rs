@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use log::info;
+use p3_goldilocks::Goldilocks;
 
 use crate::{gen_elf_hash, get_elf_bin_file_path_with_hash, get_rom_blowup_factor};
 
@@ -10,7 +11,7 @@ pub fn rom_merkle_setup(
     output_path: &Path,
     proving_key: &Path,
     mut check: bool,
-) -> Result<(), anyhow::Error> {
+) -> Result<Vec<Goldilocks>, anyhow::Error> {
     // Check if the path is a file and not a directory
     if !elf.is_file() {
         log::error!("Error: The specified ROM path is not a file: {}", elf.display());
@@ -29,5 +30,5 @@ pub fn rom_merkle_setup(
 
     info!("Root hash: {:?}", root);
 
-    Ok(())
+    Ok(root)
 }
@@ -1,31 +1,217 @@
-// use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-// use log::info;
+use anyhow::{Context, Result};
+use log::info;
+use p3_field::PrimeField64;
+use p3_goldilocks::Goldilocks;
+use serde::{Deserialize, Serialize};
+use zisk_pil::PILOUT_HASH;
 
-// use crate::{gen_elf_hash, get_elf_bin_file_path, get_rom_blowup_factor};
+use crate::{get_elf_bin_file_path_with_hash, get_rom_blowup_factor};
 
-pub fn rom_vkey(// elf: &Path,
-    // output_path: &Path,
-    // proving_key: &Path,
-    // mut check: bool,
-) -> Result<(), anyhow::Error> {
-    // // Check if the path is a file and not a directory
-    // if !elf.is_file() {
-    //     log::error!("Error: The specified ROM path is not a file: {}", elf.display());
-    //     std::process::exit(1);
-    // }
+/// Number of public values holding the ROM Merkle root; they are the first publics of a proof
+pub const ROM_ROOT_PUBLICS: usize = 4;
 
-    // let blowup_factor = get_rom_blowup_factor(proving_key);
+/// Program verification key: identifies the program (ELF) a proof corresponds to, for a given
+/// proving key.
+///
+/// The ROM Merkle root is exposed by every proof as its `rom_root` public values, so checking them
+/// against the key proves that the proof was generated for this ELF; the PIL and proving key
+/// identities make sure that the root was computed with the same setup the proof was verified with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramVkey {
+    /// Blake3 hash of the ELF file
+    pub elf_hash: String,
+    /// Merkle root of the ROM custom commit
+    pub rom_root: [u64; ROM_ROOT_PUBLICS],
+    /// Hash of the PIL the proving key was generated from
+    pub pilout_hash: String,
+    /// Blake3 hash of the final verification key of the proving key
+    pub verkey_hash: String,
+}
+
+impl ProgramVkey {
+    pub fn new(elf_hash: &str, rom_root: &[Goldilocks], verkey_hash: &str) -> Result<Self> {
+        let rom_root: Vec<u64> = rom_root.iter().map(|value| value.as_canonical_u64()).collect();
+        let rom_root = rom_root.try_into().map_err(|root: Vec<u64>| {
+            anyhow::anyhow!("Invalid ROM root length {}, expected {}", root.len(), ROM_ROOT_PUBLICS)
+        })?;
+
+        Ok(Self {
+            elf_hash: elf_hash.to_string(),
+            rom_root,
+            pilout_hash: PILOUT_HASH.to_string(),
+            verkey_hash: verkey_hash.to_string(),
+        })
+    }
+
+    /// Returns a digest of the whole key, to identify it at a glance
+    pub fn digest(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for value in self.rom_root {
+            hasher.update(&value.to_le_bytes());
+        }
+        hasher.update(self.pilout_hash.as_bytes());
+        hasher.update(self.verkey_hash.as_bytes());
+        hasher.finalize().to_hex().to_string()
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Error reading program verification key: {:?}", path))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Error parsing program verification key: {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Error writing program verification key: {:?}", path))
+    }
+
+    /// Checks that the key was generated for the final verification key file `verkey`
+    pub fn check_verkey(&self, verkey: &Path) -> Result<()> {
+        if self.pilout_hash != PILOUT_HASH {
+            anyhow::bail!(
+                "Program verification key was generated for PIL {}, but this binary uses {}",
+                self.pilout_hash,
+                PILOUT_HASH
+            );
+        }
+        if self.verkey_hash != get_file_hash(verkey)? {
+            anyhow::bail!(
+                "Program verification key was generated for a different proving key than {}",
+                verkey.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the public values of a proof contain the ROM root of the key
+    pub fn check_publics(&self, publics: &[u64]) -> Result<()> {
+        if publics.get(..ROM_ROOT_PUBLICS) != Some(&self.rom_root[..]) {
+            anyhow::bail!(
+                "Proof does not correspond to the program with ELF hash {}",
+                self.elf_hash
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the final verification key file of a proving key
+pub fn get_proving_key_verkey_path(proving_key: &Path) -> PathBuf {
+    proving_key.join("zisk/vadcop_final/vadcop_final.verkey.json")
+}
+
+/// Returns the blake3 hash of a file
+pub fn get_file_hash(path: &Path) -> Result<String> {
+    let data = fs::read(path).with_context(|| format!("Error reading file: {:?}", path))?;
+    Ok(blake3::hash(&data).to_hex().to_string())
+}
+
+/// Generates the program verification key of `elf` from its ROM Merkle root, and writes it next to
+/// the ROM custom commit file in `output_path`, returning its path
+pub fn rom_vkey(
+    elf: &Path,
+    elf_hash: &str,
+    rom_root: &[Goldilocks],
+    output_path: &Path,
+    proving_key: &Path,
+) -> Result<PathBuf> {
+    let blowup_factor = get_rom_blowup_factor(proving_key);
+
+    let elf_bin_path = get_elf_bin_file_path_with_hash(elf, elf_hash, output_path, blowup_factor)?;
+    let vkey_path = elf_bin_path.with_extension("vkey.json");
+
+    let verkey_hash = get_file_hash(&get_proving_key_verkey_path(proving_key))?;
+    let vkey = ProgramVkey::new(elf_hash, rom_root, &verkey_hash)?;
+    vkey.save(&vkey_path)?;
+
+    info!("Program verification key: {}", vkey.digest());
+
+    Ok(vkey_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_field::PrimeCharacteristicRing;
+
+    fn vkey(verkey_hash: &str) -> ProgramVkey {
+        let rom_root = [1u64, 2, 3, 4].map(Goldilocks::from_u64);
+        ProgramVkey::new("elf-hash", &rom_root, verkey_hash).unwrap()
+    }
+
+    /// Returns a path in a fresh temporary directory of this test
+    fn temp_path(test: &str, name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zisk_vkey_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn test_new() {
+        let vkey = vkey("verkey-hash");
+        assert_eq!(vkey.rom_root, [1, 2, 3, 4]);
+        assert_eq!(vkey.pilout_hash, PILOUT_HASH);
+
+        let rom_root = [Goldilocks::from_u64(1); 3];
+        assert!(ProgramVkey::new("elf-hash", &rom_root, "verkey-hash").is_err());
+    }
+
+    #[test]
+    fn test_check_publics() {
+        let vkey = vkey("verkey-hash");
+        assert!(vkey.check_publics(&[1, 2, 3, 4]).is_ok());
+        assert!(vkey.check_publics(&[1, 2, 3, 4, 5, 6]).is_ok());
+
+        let e = vkey.check_publics(&[1, 2, 3, 5]).unwrap_err();
+        assert!(e.to_string().contains("elf-hash"));
+        assert!(vkey.check_publics(&[5, 1, 2, 3, 4]).is_err());
+        assert!(vkey.check_publics(&[1, 2, 3]).is_err());
+        assert!(vkey.check_publics(&[]).is_err());
+    }
+
+    #[test]
+    fn test_check_verkey() {
+        let verkey = temp_path("check_verkey", "vadcop_final.verkey.json");
+        fs::write(&verkey, "[1, 2, 3]").unwrap();
+        let mut vkey = vkey(&get_file_hash(&verkey).unwrap());
+        assert!(vkey.check_verkey(&verkey).is_ok());
 
-    // let elf_bin_path = get_elf_bin_file_path(elf, output_path, blowup_factor)?;
+        // Another proving key
+        fs::write(&verkey, "[1, 2, 4]").unwrap();
+        assert!(vkey.check_verkey(&verkey).is_err());
+        fs::write(&verkey, "[1, 2, 3]").unwrap();
 
-    // if !elf_bin_path.exists() {
-    //     check = false;
-    // }
+        // Another PIL
+        vkey.pilout_hash = "other".to_string();
+        let e = vkey.check_verkey(&verkey).unwrap_err();
+        assert!(e.to_string().contains("PIL other"));
 
-    // let root = gen_elf_hash(elf, elf_bin_path.as_path(), blowup_factor, check)?;
+        // A missing verification key
+        vkey.pilout_hash = PILOUT_HASH.to_string();
+        fs::remove_file(&verkey).unwrap();
+        assert!(vkey.check_verkey(&verkey).is_err());
+    }
 
-    // info!("Root hash: {:?}", root);
+    #[test]
+    fn test_save_and_digest() {
+        let path = temp_path("save", "program.vkey.json");
+        let vkey = vkey("verkey-hash");
+        vkey.save(&path).unwrap();
+        let loaded = ProgramVkey::from_file(&path).unwrap();
+        assert_eq!(loaded, vkey);
+        assert_eq!(loaded.digest(), vkey.digest());
 
-    Ok(())
+        // The digest identifies the ROM root and the setup, but not the ELF file name or hash
+        let mut other = vkey.clone();
+        other.elf_hash = "other".to_string();
+        assert_eq!(other.digest(), vkey.digest());
+        other.rom_root[3] = 5;
+        assert_ne!(other.digest(), vkey.digest());
+    }
 }
@@ -74,6 +74,12 @@ impl<F: PrimeField64> BinarySM<F> {
     pub fn build_binary_counter(&self) -> BinaryCounter {
         BinaryCounter::new(BusDeviceMode::Counter)
     }
+
+    /// Builds the planner of the binary instances, which does not depend on the state machines,
+    /// so executions can be planned without them.
+    pub fn build_binary_planner() -> Box<dyn Planner> {
+        Box::new(BinaryPlanner::new())
+    }
 }
 
 impl<F: PrimeField64> ComponentBuilder<F> for BinarySM<F> {
@@ -91,7 +97,7 @@ impl<F: PrimeField64> ComponentBuilder<F> for BinarySM<F> {
     /// # Returns
     /// A boxed implementation of `RegularPlanner`.
     fn build_planner(&self) -> Box<dyn Planner> {
-        Box::new(BinaryPlanner::new())
+        Self::build_binary_planner()
     }
 
     /// Builds an instance for binary operations.
@@ -38,6 +38,12 @@ impl<F: PrimeField64> Mem<F> {
     pub fn build_mem_counter(&self) -> MemCounters {
         MemCounters::new()
     }
+
+    /// Builds the planner of the memory instances, which does not depend on the state machines,
+    /// so executions can be planned without them.
+    pub fn build_mem_planner() -> Box<dyn Planner> {
+        Box::new(MemPlanner::new())
+    }
 }
 
 impl<F: PrimeField64> ComponentBuilder<F> for Mem<F> {
@@ -46,7 +52,7 @@ impl<F: PrimeField64> ComponentBuilder<F> for Mem<F> {
     }
 
     fn build_planner(&self) -> Box<dyn Planner> {
-        Box::new(MemPlanner::new())
+        Self::build_mem_planner()
     }
 
     fn configure_instances(&self, pctx: &ProofCtx<F>, plannings: &[Plan]) {
@@ -24,7 +24,8 @@ use zisk_common::{
     Planner,
 };
 use zisk_core::{
-    zisk_ops::ZiskOp, Riscv2zisk, ZiskRom, ROM_ADDR, ROM_ADDR_MAX, ROM_ENTRY, ROM_EXIT, SRC_IMM,
+    zisk_ops::ZiskOp, Riscv2zisk, UserFcalls, ZiskRom, ROM_ADDR, ROM_ADDR_MAX, ROM_ENTRY, ROM_EXIT,
+    SRC_IMM,
 };
 use zisk_pil::{MainTrace, RomRomTrace, RomRomTraceRow, RomTrace};
 
@@ -50,6 +51,7 @@ impl RomSM {
     ///
     /// # Arguments
     /// * `zisk_rom` - The Zisk ROM representation.
+    /// * `user_fcalls` - Host functions serving the user-defined fcalls of the guest, if any.
     ///
     /// # Returns
     /// An `Arc`-wrapped instance of `RomSM`.
@@ -57,10 +59,11 @@ impl RomSM {
         zisk_rom: Arc<ZiskRom>,
         asm_rom_path: Option<PathBuf>,
         input_data_path: Option<PathBuf>,
+        user_fcalls: Option<Arc<UserFcalls>>,
     ) -> Arc<Self> {
         let rom_asm_worker = asm_rom_path.map(|asm_rom_path| {
             let mut worker = RomAsmWorker::new();
-            worker.launch_task(asm_rom_path, input_data_path);
+            worker.launch_task(asm_rom_path, input_data_path, user_fcalls);
             worker
         });
 
@@ -1,12 +1,14 @@
 use std::{
     path::PathBuf,
+    sync::Arc,
     thread::{self, JoinHandle},
 };
 
-use asm_runner::AsmRunnerRomH;
+use asm_runner::{AsmRunError, AsmRunnerOptions, AsmRunnerRomH};
+use zisk_core::UserFcalls;
 
 pub struct RomAsmWorker {
-    handle: Option<JoinHandle<AsmRunnerRomH>>,
+    handle: Option<JoinHandle<Result<AsmRunnerRomH, AsmRunError>>>,
 }
 
 impl RomAsmWorker {
@@ -16,19 +18,24 @@ impl RomAsmWorker {
         Self { handle: None }
     }
 
-    pub fn launch_task(&mut self, asm_path: PathBuf, input_data_path: Option<PathBuf>) {
+    pub fn launch_task(
+        &mut self,
+        asm_path: PathBuf,
+        input_data_path: Option<PathBuf>,
+        user_fcalls: Option<Arc<UserFcalls>>,
+    ) {
         let handle = thread::spawn(move || {
             AsmRunnerRomH::run(
                 &asm_path,
                 input_data_path.as_deref(),
                 Self::SHM_DEFAULT_SIZE,
-                asm_runner::AsmRunnerOptions::default(),
+                AsmRunnerOptions { user_fcalls, ..AsmRunnerOptions::default() },
             )
         });
         self.handle = Some(handle);
     }
 
-    pub fn wait_for_task(&mut self) -> AsmRunnerRomH {
+    pub fn wait_for_task(&mut self) -> Result<AsmRunnerRomH, AsmRunError> {
         if let Some(handle) = self.handle.take() {
             handle.join().unwrap()
         } else {
@@ -92,7 +92,11 @@ impl<F: PrimeField> Instance<F> for RomInstance {
         if self.is_asm_execution() {
             // Case 1: Use ROM assembly output
             let mut worker = self.rom_asm_worker.take().unwrap();
-            let asm_runner_romh = worker.wait_for_task();
+            // The witness computation has no error path, and the execution that produced the
+            // minimal traces already succeeded
+            let asm_runner_romh = worker
+                .wait_for_task()
+                .unwrap_or_else(|e| panic!("ROM histogram assembly emulation failed: {}", e));
 
             self.bios_inst_count =
                 Arc::new(create_atomic_vec(asm_runner_romh.asm_rowh_output.bios_inst_count.len()));
@@ -0,0 +1,125 @@
+use core::panic;
+use std::cell::RefCell;
+
+use crate::{bits_to_u64, GateBit};
+
+use super::{GateState, PinId};
+
+#[derive(Debug, Clone, Copy)]
+pub struct GateU64<'a> {
+    pub state: &'a RefCell<GateState>,
+    pub bits: [GateBit; 64],
+}
+
+impl<'a> GateU64<'a> {
+    pub fn new(state: &'a RefCell<GateState>) -> Self {
+        let default_ref = state.borrow().gate_config.zero_ref.unwrap();
+        let mut gate = Self { state, bits: [GateBit::new(default_ref); 64] };
+        gate.from_u64(0);
+        gate
+    }
+
+    pub fn from_u64(&mut self, value: u64) {
+        for (i, bit) in self.bits.iter_mut().enumerate() {
+            bit.pin_id = match (value >> i) & 1 {
+                0 => PinId::A,
+                1 => PinId::B,
+                _ => panic!("Invalid bit value in from_u64"),
+            };
+        }
+    }
+
+    pub fn to_u64(&self) -> u64 {
+        let mut bits = [0u8; 64];
+        let state = self.state.borrow();
+        for (i, bit) in self.bits.iter().enumerate() {
+            let ref_ = bit.ref_ as usize;
+            let pin_id = bit.pin_id;
+            bits[i] = state.gates[ref_].pins[pin_id].bit;
+        }
+
+        bits_to_u64(&bits)
+    }
+
+    pub fn rotate_right(&mut self, pos: usize) {
+        let mut rotated = [GateBit::new(self.state.borrow().gate_config.zero_ref.unwrap()); 64];
+        for (i, rotated_bit) in rotated.iter_mut().enumerate() {
+            *rotated_bit = self.bits[(i + pos) % 64];
+        }
+        self.bits = rotated;
+    }
+}
+
+/// XOR 2 numbers of 64 bits
+pub fn gate_u64_xor(gate_state: &mut GateState, a: &GateU64, b: &GateU64, r: &mut GateU64) {
+    for i in 0..64 {
+        let out_ref = gate_state.get_free_ref();
+        gate_state.xor(a.bits[i].ref_, a.bits[i].pin_id, b.bits[i].ref_, b.bits[i].pin_id, out_ref);
+        r.bits[i].ref_ = out_ref;
+        r.bits[i].pin_id = PinId::D;
+    }
+}
+
+/*
+    Add 2 numbers of 64 bits modulo 2^64, using only XOR and ANDP gates
+
+    The carry is computed as maj(a,b,carry) = carry ^ ((a ^ carry) & (b ^ carry)), where the AND is
+    obtained as andp(¬(a ^ carry), b ^ carry) and ¬(a ^ carry) = a ^ ¬carry:
+    =============================
+    bit  0:  r = xor(a,b)                     carry = andp(xor(a,1),b)
+    bit  i:  t = xor(b,carry)                 r = xor(a,t)
+             u = xor(a,xor(carry,1))          carry = xor(andp(u,t),carry)
+    bit 63:  t = xor(b,carry)                 r = xor(a,t)                 carry is not needed any more
+*/
+pub fn gate_u64_add(gate_state: &mut GateState, a: &GateU64, b: &GateU64, r: &mut GateU64) {
+    let zero_ref = gate_state.gate_config.zero_ref.unwrap();
+    let mut carry = GateBit { ref_: zero_ref, pin_id: PinId::A };
+
+    for i in 0..64 {
+        if i == 0 {
+            // Calculate result bit
+            r.bits[i].ref_ = gate_state.get_free_ref();
+            gate_state.xor(
+                a.bits[i].ref_,
+                a.bits[i].pin_id,
+                b.bits[i].ref_,
+                b.bits[i].pin_id,
+                r.bits[i].ref_,
+            );
+            r.bits[i].pin_id = PinId::D;
+
+            // Calculate carry bit
+            let not_a_ref = gate_state.get_free_ref();
+            gate_state.xor(a.bits[i].ref_, a.bits[i].pin_id, zero_ref, PinId::B, not_a_ref);
+
+            carry.ref_ = gate_state.get_free_ref();
+            gate_state.andp(not_a_ref, PinId::D, b.bits[i].ref_, b.bits[i].pin_id, carry.ref_);
+            carry.pin_id = PinId::D;
+            continue;
+        }
+
+        // Calculate result bit
+        let t_ref = gate_state.get_free_ref();
+        gate_state.xor(b.bits[i].ref_, b.bits[i].pin_id, carry.ref_, carry.pin_id, t_ref);
+
+        r.bits[i].ref_ = gate_state.get_free_ref();
+        gate_state.xor(a.bits[i].ref_, a.bits[i].pin_id, t_ref, PinId::D, r.bits[i].ref_);
+        r.bits[i].pin_id = PinId::D;
+
+        // Calculate carry bit
+        if i < 63 {
+            let not_carry_ref = gate_state.get_free_ref();
+            gate_state.xor(carry.ref_, carry.pin_id, zero_ref, PinId::B, not_carry_ref);
+
+            let u_ref = gate_state.get_free_ref();
+            gate_state.xor(a.bits[i].ref_, a.bits[i].pin_id, not_carry_ref, PinId::D, u_ref);
+
+            let and_ref = gate_state.get_free_ref();
+            gate_state.andp(u_ref, PinId::D, t_ref, PinId::D, and_ref);
+
+            let carry_ref = gate_state.get_free_ref();
+            gate_state.xor(and_ref, PinId::D, carry.ref_, carry.pin_id, carry_ref);
+            carry = GateBit { ref_: carry_ref, pin_id: PinId::D };
+        }
+    }
+}
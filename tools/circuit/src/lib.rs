@@ -2,13 +2,15 @@ mod gate;
 mod gate_config;
 mod gate_state;
 mod gate_u32;
+mod gate_u64;
 mod pin;
 mod utils;
 
 pub use gate::{Gate, GateOperation};
 pub use gate_config::GateConfig;
 pub use gate_state::GateState;
-pub use gate_u32::{gate_u32_add, gate_u32_and, gate_u32_not, gate_u32_xor, GateU32};
+pub use gate_u32::{gate_u32_add, gate_u32_and, gate_u32_not, gate_u32_xor, GateBit, GateU32};
+pub use gate_u64::{gate_u64_add, gate_u64_xor, GateU64};
 pub use pin::{Pin, PinId, PinSource};
 pub use utils::*;
 
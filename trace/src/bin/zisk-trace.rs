@@ -0,0 +1,89 @@
+//! Executable that inspects the ZisK traces written by `ziskemu -t TRACE_FILE`.
+
+use std::{
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Parser, Subcommand};
+use zisk_trace::{first_divergence, TraceError, TraceReader, TraceStep};
+
+#[derive(Parser)]
+#[command(version, about = "Inspects ZisK execution traces", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the steps of a trace, one per line
+    Dump {
+        /// Trace file
+        trace: PathBuf,
+        /// First step to print
+        #[arg(long, default_value_t = 0)]
+        from: u64,
+        /// Maximum number of steps to print
+        #[arg(long)]
+        count: Option<u64>,
+    },
+    /// Prints the first step where two traces diverge, exiting with code 1 if they do
+    Diff {
+        /// First trace file
+        left: PathBuf,
+        /// Second trace file
+        right: PathBuf,
+    },
+}
+
+fn dump(trace: &Path, from: u64, count: Option<u64>) -> Result<(), TraceError> {
+    let mut reader = TraceReader::open(trace)?;
+    let mut printed = 0;
+    while let Some(step) = reader.read_step()? {
+        if step.step < from {
+            continue;
+        }
+        if count.is_some_and(|count| printed >= count) {
+            break;
+        }
+        println!("{}", step.to_text(reader.op_name(step.op)));
+        printed += 1;
+    }
+    Ok(())
+}
+
+fn diff(left: &Path, right: &Path) -> Result<bool, TraceError> {
+    let mut left_reader = TraceReader::open(left)?;
+    let mut right_reader = TraceReader::open(right)?;
+    let Some(divergence) = first_divergence(&mut left_reader, &mut right_reader)? else {
+        println!("Traces are identical");
+        return Ok(false);
+    };
+
+    let describe = |step: &Option<TraceStep>, reader: &TraceReader<_>| match step {
+        Some(step) => step.to_text(reader.op_name(step.op)),
+        None => "end of trace".to_string(),
+    };
+    println!("Traces diverge");
+    println!("< {}", describe(&divergence.left, &left_reader));
+    println!("> {}", describe(&divergence.right, &right_reader));
+    Ok(true)
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let result = match &args.command {
+        Command::Dump { trace, from, count } => dump(trace, *from, *count).map(|_| false),
+        Command::Diff { left, right } => diff(left, right),
+    };
+    match result {
+        Ok(false) => {}
+        Ok(true) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
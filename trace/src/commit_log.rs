@@ -0,0 +1,137 @@
+//! RISC-V commit logs, in the format of the spike `--log-commits` option:
+//!
+//! ```text
+//! core   0: 3 0x0000000080000000 (0x00000297) x5  0x0000000080000000
+//! core   0: 3 0x0000000080000010 (0x0182b283) x5  0x0000000000000007 mem 0x0000000080000018
+//! core   0: 3 0x0000000080000014 (0x0052b023) mem 0x0000000080000020 0x0000000000000007
+//! ```
+//!
+//! Every line contains the privilege level, which is always machine mode, the pc, the instruction
+//! word, the written registers and the memory accesses, with the stored value for writes.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::{MemAccess, MemAccessKind};
+
+/// Privilege level reported in the commit log lines, i.e. machine mode
+const COMMIT_LOG_PRIV_LEVEL: u8 = 3;
+
+/// Effects of one RISC-V instruction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiscvCommit {
+    pub pc: u64,
+    /// Instruction word, or 0 if unknown
+    pub insn: u32,
+    /// Written registers, as (index, value) pairs
+    pub reg_writes: Vec<(u8, u64)>,
+    pub mem: Vec<MemAccess>,
+}
+
+/// Writes a RISC-V commit log, one instruction at a time
+pub struct CommitLogWriter<W: Write> {
+    writer: W,
+}
+
+impl CommitLogWriter<BufWriter<File>> {
+    /// Creates a commit log file at `path`
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> CommitLogWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Appends the line of one instruction
+    pub fn write_commit(&mut self, commit: &RiscvCommit) -> std::io::Result<()> {
+        let mut line = format!(
+            "core   0: {} 0x{:016x} (0x{:08x})",
+            COMMIT_LOG_PRIV_LEVEL, commit.pc, commit.insn
+        );
+        for (reg, value) in &commit.reg_writes {
+            line += &format!(" x{:<2} 0x{:016x}", reg, value);
+        }
+        for access in &commit.mem {
+            line += &format!(" mem 0x{:016x}", access.address);
+            if access.kind == MemAccessKind::Write {
+                line += &format!(" 0x{:0width$x}", access.value, width = access.width as usize * 2);
+            }
+        }
+        writeln!(self.writer, "{}", line)
+    }
+
+    /// Flushes the commit log and returns the underlying writer
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_line(commit: &RiscvCommit) -> String {
+        let mut writer = CommitLogWriter::new(Vec::new());
+        writer.write_commit(commit).unwrap();
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_commit_log_registers() {
+        let commit = RiscvCommit {
+            pc: 0x80000000,
+            insn: 0x00000297,
+            reg_writes: vec![(5, 0x80000000), (10, 7)],
+            mem: vec![],
+        };
+        assert_eq!(
+            commit_line(&commit),
+            "core   0: 3 0x0000000080000000 (0x00000297) x5  0x0000000080000000 \
+             x10 0x0000000000000007\n"
+        );
+
+        // Instructions without effects only have the privilege level, pc and instruction word
+        let commit = RiscvCommit { pc: 0x80000004, insn: 0x00000013, ..Default::default() };
+        assert_eq!(commit_line(&commit), "core   0: 3 0x0000000080000004 (0x00000013)\n");
+    }
+
+    #[test]
+    fn test_commit_log_memory() {
+        let load = RiscvCommit {
+            pc: 0x80000010,
+            insn: 0x0182b283,
+            reg_writes: vec![(5, 7)],
+            mem: vec![MemAccess::read(0x80000018, 8, 7)],
+        };
+        assert_eq!(
+            commit_line(&load),
+            "core   0: 3 0x0000000080000010 (0x0182b283) x5  0x0000000000000007 \
+             mem 0x0000000080000018\n"
+        );
+
+        // Stored values are printed with the width of the access
+        let stores = [(1, "0x78"), (2, "0x5678"), (4, "0x12345678"), (8, "0x0000000012345678")];
+        for (width, value) in stores {
+            let store = RiscvCommit {
+                pc: 0x80000014,
+                insn: 0x0052b023,
+                reg_writes: vec![],
+                mem: vec![MemAccess::write(0x80000020, width, 0x12345678)],
+            };
+            assert_eq!(
+                commit_line(&store),
+                format!(
+                    "core   0: 3 0x0000000080000014 (0x0052b023) mem 0x0000000080000020 {}\n",
+                    value
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Execution traces of the ZisK emulator, in a documented format that external tools can read.
+//!
+//! Two kinds of traces are supported:
+//!
+//! * ZisK traces, written by `ziskemu -t TRACE_FILE`: a binary file with one record per ZisK
+//!   step, containing the pc, the operation, the a, b and c registers, the flag and the memory
+//!   accesses of the step.  `TraceWriter` writes them and `TraceReader` streams them back.
+//! * RISC-V commit logs, written by `ziskemu --commit-log COMMIT_LOG_FILE`: a text file with one
+//!   line per RISC-V instruction, in the format of the spike `--log-commits` option, so that ZisK
+//!   executions can be compared with other RISC-V simulators.  `CommitLogWriter` writes them.
+//!
+//! # ZisK trace format
+//!
+//! All integers are little-endian.  The file starts with a header:
+//!
+//! ```text
+//! magic       [u8; 8]     b"ZISKTRC\0"
+//! version     u16         TRACE_VERSION
+//! ops         u16         number of operation names that follow
+//! ops times:
+//!   code      u8          operation code
+//!   len       u8          length of the operation name
+//!   name      [u8; len]   operation name, e.g. "add" or "keccak"
+//! ```
+//!
+//! followed by one record per step, until the end of the file:
+//!
+//! ```text
+//! step        u64         step number, starting at 0
+//! pc          u64         ZisK pc of the executed instruction
+//! op          u8          operation code
+//! flag        u8          flag register after the operation, 0 or 1
+//! accesses    u8          number of memory accesses that follow
+//! a           u64         a register
+//! b           u64         b register
+//! c           u64         c register
+//! accesses times:
+//!   kind      u8          0 = read, 1 = write
+//!   width     u8          access width in bytes: 1, 2, 4 or 8
+//!   address   u64         accessed address
+//!   value     u64         value read or written, zero-extended to 64 bits
+//! ```
+//!
+//! The memory accesses are the ones done by the a and b sources and the c store of the ZisK
+//! instruction.  The memory accesses done internally by precompiles are not recorded.
+//!
+//! # Tools
+//!
+//! The `zisk-trace` binary dumps ZisK traces as text and finds the first step where two traces
+//! diverge.
+
+mod commit_log;
+mod trace_errors;
+mod trace_format;
+mod trace_reader;
+mod trace_writer;
+
+pub use commit_log::*;
+pub use trace_errors::*;
+pub use trace_format::*;
+pub use trace_reader::*;
+pub use trace_writer::*;
@@ -0,0 +1,51 @@
+use std::{fmt, io};
+
+/// Error reading or writing a ZisK trace
+#[derive(Debug)]
+pub enum TraceError {
+    Io(io::Error),
+    /// The file does not start with `TRACE_MAGIC`
+    InvalidMagic,
+    /// The file was written with an unsupported version of the trace format
+    UnsupportedVersion(u16),
+    /// The file ends in the middle of the step record that starts at this step
+    Truncated(u64),
+    /// A step record contains an invalid field
+    InvalidRecord {
+        step: u64,
+        reason: String,
+    },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::Io(e) => write!(f, "trace I/O error: {}", e),
+            TraceError::InvalidMagic => write!(f, "not a ZisK trace file"),
+            TraceError::UnsupportedVersion(version) => {
+                write!(f, "unsupported ZisK trace version {}", version)
+            }
+            TraceError::Truncated(step) => {
+                write!(f, "ZisK trace truncated in the record of step {}", step)
+            }
+            TraceError::InvalidRecord { step, reason } => {
+                write!(f, "invalid ZisK trace record at step {}: {}", step, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TraceError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TraceError {
+    fn from(e: io::Error) -> Self {
+        TraceError::Io(e)
+    }
+}
@@ -0,0 +1,91 @@
+//! ZisK trace records, as described in the crate documentation
+
+use std::fmt;
+
+/// Magic bytes at the beginning of a ZisK trace file
+pub const TRACE_MAGIC: [u8; 8] = *b"ZISKTRC\0";
+
+/// Version of the ZisK trace format
+pub const TRACE_VERSION: u16 = 1;
+
+/// Kind of memory access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemAccessKind {
+    Read = 0,
+    Write = 1,
+}
+
+impl MemAccessKind {
+    /// Returns the memory access kind encoded as `code`, if valid
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(MemAccessKind::Read),
+            1 => Some(MemAccessKind::Write),
+            _ => None,
+        }
+    }
+}
+
+/// Memory access of a ZisK step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemAccess {
+    pub kind: MemAccessKind,
+    /// Width in bytes: 1, 2, 4 or 8
+    pub width: u8,
+    pub address: u64,
+    /// Value read or written, zero-extended to 64 bits
+    pub value: u64,
+}
+
+impl MemAccess {
+    /// Creates a memory read access
+    pub fn read(address: u64, width: u64, value: u64) -> Self {
+        Self { kind: MemAccessKind::Read, width: width as u8, address, value }
+    }
+
+    /// Creates a memory write access, truncating the value to the access width
+    pub fn write(address: u64, width: u64, value: u64) -> Self {
+        let value = if width >= 8 { value } else { value & ((1u64 << (width * 8)) - 1) };
+        Self { kind: MemAccessKind::Write, width: width as u8, address, value }
+    }
+}
+
+impl fmt::Display for MemAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            MemAccessKind::Read => "rd",
+            MemAccessKind::Write => "wr",
+        };
+        write!(f, "{}{}[0x{:x}]=0x{:x}", kind, self.width, self.address, self.value)
+    }
+}
+
+/// ZisK trace record of one step
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceStep {
+    pub step: u64,
+    pub pc: u64,
+    pub op: u8,
+    pub flag: bool,
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub mem: Vec<MemAccess>,
+}
+
+impl TraceStep {
+    /// Maximum number of memory accesses of a step
+    pub const MAX_MEM_ACCESSES: usize = u8::MAX as usize;
+
+    /// Formats the step as a single line of text, using `op_name` to get the operation name
+    pub fn to_text(&self, op_name: &str) -> String {
+        let mut line = format!(
+            "step={} pc=0x{:x} op={} a=0x{:x} b=0x{:x} c=0x{:x} flag={}",
+            self.step, self.pc, op_name, self.a, self.b, self.c, self.flag as u8
+        );
+        for access in &self.mem {
+            line += &format!(" {}", access);
+        }
+        line
+    }
+}
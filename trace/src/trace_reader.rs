@@ -0,0 +1,225 @@
+//! Streaming reader of ZisK traces
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, ErrorKind, Read},
+    path::Path,
+};
+
+use crate::{MemAccess, MemAccessKind, TraceError, TraceStep, TRACE_MAGIC, TRACE_VERSION};
+
+/// Reads a ZisK trace, one step at a time
+pub struct TraceReader<R: Read> {
+    reader: R,
+    /// Operation names, by code
+    ops: BTreeMap<u8, String>,
+    /// Step of the next record, used to report truncated traces
+    next_step: u64,
+}
+
+impl TraceReader<BufReader<File>> {
+    /// Opens the trace file at `path`, see `TraceReader::new()`
+    pub fn open(path: &Path) -> Result<Self, TraceError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> TraceReader<R> {
+    /// Creates a trace reader, reading and checking the trace header
+    pub fn new(mut reader: R) -> Result<Self, TraceError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|_| TraceError::InvalidMagic)?;
+        if magic != TRACE_MAGIC {
+            return Err(TraceError::InvalidMagic);
+        }
+
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        if version != TRACE_VERSION {
+            return Err(TraceError::UnsupportedVersion(version));
+        }
+
+        let num_ops = u16::from_le_bytes([header[2], header[3]]);
+        let mut ops = BTreeMap::new();
+        for _ in 0..num_ops {
+            let mut op = [0u8; 2];
+            reader.read_exact(&mut op)?;
+            let mut name = vec![0u8; op[1] as usize];
+            reader.read_exact(&mut name)?;
+            ops.insert(op[0], String::from_utf8_lossy(&name).into_owned());
+        }
+
+        Ok(Self { reader, ops, next_step: 0 })
+    }
+
+    /// Returns the operation names, by code
+    pub fn ops(&self) -> &BTreeMap<u8, String> {
+        &self.ops
+    }
+
+    /// Returns the name of the operation with code `op`, or `?` if it is unknown
+    pub fn op_name(&self, op: u8) -> &str {
+        self.ops.get(&op).map(|name| name.as_str()).unwrap_or("?")
+    }
+
+    /// Reads the next step record, returning `None` at the end of the trace
+    pub fn read_step(&mut self) -> Result<Option<TraceStep>, TraceError> {
+        let mut record = [0u8; 43];
+
+        // A trace can only end at a record boundary
+        let mut read = 0;
+        while read < record.len() {
+            match self.reader.read(&mut record[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(TraceError::Truncated(self.next_step)),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let u64_at =
+            |bytes: &[u8], pos: usize| u64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap());
+        let mut step = TraceStep {
+            step: u64_at(&record, 0),
+            pc: u64_at(&record, 8),
+            op: record[16],
+            flag: record[17] != 0,
+            a: u64_at(&record, 19),
+            b: u64_at(&record, 27),
+            c: u64_at(&record, 35),
+            mem: Vec::with_capacity(record[18] as usize),
+        };
+
+        for _ in 0..record[18] {
+            let mut access = [0u8; 18];
+            self.reader.read_exact(&mut access).map_err(|e| match e.kind() {
+                ErrorKind::UnexpectedEof => TraceError::Truncated(step.step),
+                _ => TraceError::Io(e),
+            })?;
+            let kind =
+                MemAccessKind::from_code(access[0]).ok_or_else(|| TraceError::InvalidRecord {
+                    step: step.step,
+                    reason: format!("invalid memory access kind {}", access[0]),
+                })?;
+            step.mem.push(MemAccess {
+                kind,
+                width: access[1],
+                address: u64_at(&access, 2),
+                value: u64_at(&access, 10),
+            });
+        }
+
+        self.next_step = step.step + 1;
+        Ok(Some(step))
+    }
+}
+
+impl<R: Read> Iterator for TraceReader<R> {
+    type Item = Result<TraceStep, TraceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_step().transpose()
+    }
+}
+
+/// First difference between two ZisK traces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDivergence {
+    /// Record of the first trace, or `None` if it ended before the second one
+    pub left: Option<TraceStep>,
+    /// Record of the second trace, or `None` if it ended before the first one
+    pub right: Option<TraceStep>,
+}
+
+/// Compares two ZisK traces record by record, returning their first difference, if any
+pub fn first_divergence<L: Read, R: Read>(
+    left: &mut TraceReader<L>,
+    right: &mut TraceReader<R>,
+) -> Result<Option<TraceDivergence>, TraceError> {
+    loop {
+        let (l, r) = (left.read_step()?, right.read_step()?);
+        match (l, r) {
+            (None, None) => return Ok(None),
+            (l, r) if l != r => return Ok(Some(TraceDivergence { left: l, right: r })),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TraceWriter;
+
+    fn sample_steps() -> Vec<TraceStep> {
+        vec![
+            TraceStep { step: 0, pc: 0x1000, op: 1, a: 2, b: 3, c: 5, ..Default::default() },
+            TraceStep {
+                step: 1,
+                pc: 0x80000000,
+                op: 2,
+                flag: true,
+                a: 0xa0000000,
+                b: 0x1234,
+                c: 0x1234,
+                mem: vec![
+                    MemAccess::read(0xa0000000, 8, 0x1234),
+                    MemAccess::write(0xa0000008, 2, 0x5678_1234),
+                ],
+            },
+        ]
+    }
+
+    fn write_trace(steps: &[TraceStep]) -> Vec<u8> {
+        let mut writer = TraceWriter::new(Vec::new(), &[(1, "add"), (2, "copyb")]).unwrap();
+        for step in steps {
+            writer.write_step(step).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_trace_roundtrip() {
+        let steps = sample_steps();
+        let data = write_trace(&steps);
+
+        let mut reader = TraceReader::new(data.as_slice()).unwrap();
+        assert_eq!(reader.op_name(2), "copyb");
+        assert_eq!(reader.op_name(3), "?");
+        let read: Vec<TraceStep> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, steps);
+        assert_eq!(read[1].mem[1].value, 0x1234);
+    }
+
+    #[test]
+    fn test_trace_truncated_and_divergence() {
+        let steps = sample_steps();
+        let data = write_trace(&steps);
+
+        let mut reader = TraceReader::new(&data[..data.len() - 4]).unwrap();
+        assert!(reader.read_step().unwrap().is_some());
+        assert!(matches!(reader.read_step(), Err(TraceError::Truncated(1))));
+
+        let mut other_steps = steps.clone();
+        other_steps[1].c = 0;
+        let other = write_trace(&other_steps);
+        let divergence = first_divergence(
+            &mut TraceReader::new(data.as_slice()).unwrap(),
+            &mut TraceReader::new(other.as_slice()).unwrap(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(divergence.left.unwrap().step, 1);
+        assert_eq!(divergence.right.unwrap().c, 0);
+
+        let same = first_divergence(
+            &mut TraceReader::new(data.as_slice()).unwrap(),
+            &mut TraceReader::new(data.as_slice()).unwrap(),
+        )
+        .unwrap();
+        assert!(same.is_none());
+    }
+}
@@ -0,0 +1,69 @@
+//! Streaming writer of ZisK traces
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::{TraceError, TraceStep, TRACE_MAGIC, TRACE_VERSION};
+
+/// Writes a ZisK trace, one step at a time
+pub struct TraceWriter<W: Write> {
+    writer: W,
+}
+
+impl TraceWriter<BufWriter<File>> {
+    /// Creates a trace file at `path`, see `TraceWriter::new()`
+    pub fn create(path: &Path, ops: &[(u8, &str)]) -> Result<Self, TraceError> {
+        Self::new(BufWriter::new(File::create(path)?), ops)
+    }
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Creates a trace writer and writes the trace header, including the names of the `ops`
+    /// operations, as (code, name) pairs
+    pub fn new(mut writer: W, ops: &[(u8, &str)]) -> Result<Self, TraceError> {
+        writer.write_all(&TRACE_MAGIC)?;
+        writer.write_all(&TRACE_VERSION.to_le_bytes())?;
+        writer.write_all(&(ops.len() as u16).to_le_bytes())?;
+        for (code, name) in ops {
+            assert!(name.len() <= u8::MAX as usize, "operation name {} is too long", name);
+            writer.write_all(&[*code, name.len() as u8])?;
+            writer.write_all(name.as_bytes())?;
+        }
+        Ok(Self { writer })
+    }
+
+    /// Appends the record of one step
+    pub fn write_step(&mut self, step: &TraceStep) -> Result<(), TraceError> {
+        assert!(step.mem.len() <= TraceStep::MAX_MEM_ACCESSES);
+
+        let mut record = [0u8; 43];
+        record[0..8].copy_from_slice(&step.step.to_le_bytes());
+        record[8..16].copy_from_slice(&step.pc.to_le_bytes());
+        record[16] = step.op;
+        record[17] = step.flag as u8;
+        record[18] = step.mem.len() as u8;
+        record[19..27].copy_from_slice(&step.a.to_le_bytes());
+        record[27..35].copy_from_slice(&step.b.to_le_bytes());
+        record[35..43].copy_from_slice(&step.c.to_le_bytes());
+        self.writer.write_all(&record)?;
+
+        for access in &step.mem {
+            let mut record = [0u8; 18];
+            record[0] = access.kind as u8;
+            record[1] = access.width;
+            record[2..10].copy_from_slice(&access.address.to_le_bytes());
+            record[10..18].copy_from_slice(&access.value.to_le_bytes());
+            self.writer.write_all(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the trace and returns the underlying writer
+    pub fn finish(mut self) -> Result<W, TraceError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
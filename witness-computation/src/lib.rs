@@ -1,7 +1,9 @@
+mod sm_plan_bundle;
 mod sm_static_bundle;
 mod static_data_bus;
 mod zisk_lib;
 
+pub use sm_plan_bundle::*;
 pub use sm_static_bundle::*;
 pub use static_data_bus::*;
 pub use zisk_lib::*;
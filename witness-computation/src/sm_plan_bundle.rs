@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use data_bus::{DataBus, DataBusTrait};
+use executor::SMBundle;
+use p3_field::PrimeField64;
+use precomp_arith_eq::{ArithEqCounterInputGen, ArithEqManager};
+use precomp_blake2br::Blake2brManager;
+use precomp_dma::DmaManager;
+use precomp_keccakf::KeccakfManager;
+use precomp_sha256f::Sha256fManager;
+use proofman_common::ProofCtx;
+use sm_arith::ArithSM;
+use sm_binary::{BinaryCounter, BinarySM};
+use sm_mem::{Mem, MemCounters};
+use sm_rom::RomSM;
+use zisk_common::{
+    BusDevice, BusDeviceMetrics, BusDeviceMode, ComponentBuilder, Instance, InstanceCtx, Plan,
+};
+
+use executor::NestedDeviceMetricsList;
+
+use crate::sm_static_bundle::plan_sec_by_id;
+use crate::static_data_bus::{
+    ARITH_EQ_SM_ID, ARITH_SM_ID, BINARY_SM_ID, BLAKE2_SM_ID, DMA_SM_ID, KECCAK_SM_ID, MAIN_SM_ID,
+    MEM_SM_ID, ROM_SM_ID, SHA256_SM_ID,
+};
+use crate::StaticDataBus;
+
+/// Bundle of the counters and planners of the secondary state machines, planned by id as in
+/// `StaticSMBundle`, to plan executions without the proving key.
+///
+/// The memory, binary and arith_eq state machines need the PIL2 standard library, so only their
+/// counters and planners are built.  The bundle cannot build instances.
+pub struct PlanSMBundle<F: PrimeField64> {
+    rom_sm: Arc<RomSM>,
+    arith_sm: Arc<ArithSM>,
+    keccakf_sm: Arc<KeccakfManager>,
+    sha256f_sm: Arc<Sha256fManager>,
+    blake2br_sm: Arc<Blake2brManager>,
+    dma_sm: Arc<DmaManager>,
+    _phantom: std::marker::PhantomData<F>,
+}
+
+impl<F: PrimeField64> PlanSMBundle<F> {
+    pub fn new(
+        rom_sm: Arc<RomSM>,
+        arith_sm: Arc<ArithSM>,
+        keccakf_sm: Arc<KeccakfManager>,
+        sha256f_sm: Arc<Sha256fManager>,
+        blake2br_sm: Arc<Blake2brManager>,
+        dma_sm: Arc<DmaManager>,
+    ) -> Self {
+        Self {
+            rom_sm,
+            arith_sm,
+            keccakf_sm,
+            sha256f_sm,
+            blake2br_sm,
+            dma_sm,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<F: PrimeField64> SMBundle<F> for PlanSMBundle<F> {
+    fn plan_sec(&self, vec_counters: NestedDeviceMetricsList) -> Vec<Vec<Plan>> {
+        plan_sec_by_id(vec_counters, |id, counters| match id {
+            MEM_SM_ID => Mem::<F>::build_mem_planner().plan(counters),
+            ROM_SM_ID => {
+                <RomSM as ComponentBuilder<F>>::build_planner(&*self.rom_sm).plan(counters)
+            }
+            BINARY_SM_ID => BinarySM::<F>::build_binary_planner().plan(counters),
+            ARITH_SM_ID => {
+                <ArithSM as ComponentBuilder<F>>::build_planner(&*self.arith_sm).plan(counters)
+            }
+            KECCAK_SM_ID => {
+                <KeccakfManager as ComponentBuilder<F>>::build_planner(&*self.keccakf_sm)
+                    .plan(counters)
+            }
+            SHA256_SM_ID => {
+                <Sha256fManager as ComponentBuilder<F>>::build_planner(&*self.sha256f_sm)
+                    .plan(counters)
+            }
+            ARITH_EQ_SM_ID => ArithEqManager::<F>::build_arith_eq_planner().plan(counters),
+            BLAKE2_SM_ID => {
+                <Blake2brManager as ComponentBuilder<F>>::build_planner(&*self.blake2br_sm)
+                    .plan(counters)
+            }
+            DMA_SM_ID => {
+                <DmaManager as ComponentBuilder<F>>::build_planner(&*self.dma_sm).plan(counters)
+            }
+            _ => unreachable!(),
+        })
+    }
+
+    fn configure_instances(&self, _pctx: &ProofCtx<F>, _plannings: &[Vec<Plan>]) {
+        unreachable!("PlanSMBundle only plans executions");
+    }
+
+    fn build_instance(&self, _idx: usize, _ictx: InstanceCtx) -> Box<dyn Instance<F>> {
+        unreachable!("PlanSMBundle only plans executions");
+    }
+
+    fn build_data_bus_counters(
+        &self,
+    ) -> impl DataBusTrait<u64, Box<dyn BusDeviceMetrics>> + Send + Sync + 'static {
+        StaticDataBus::new(
+            MemCounters::new(),
+            BinaryCounter::new(BusDeviceMode::Counter),
+            self.arith_sm.build_arith_counter(),
+            self.keccakf_sm.build_keccakf_counter(),
+            self.sha256f_sm.build_sha256f_counter(),
+            ArithEqCounterInputGen::new(BusDeviceMode::Counter),
+            self.blake2br_sm.build_blake2br_counter(),
+            self.dma_sm.build_dma_counter(),
+        )
+    }
+
+    fn main_counter_idx(&self) -> Option<usize> {
+        Some(MAIN_SM_ID)
+    }
+
+    fn build_data_bus_collectors(
+        &self,
+        _secn_instance: &mut Box<dyn Instance<F>>,
+        _chunks_to_execute: Vec<bool>,
+    ) -> Vec<Option<DataBus<u64, Box<dyn BusDevice<u64>>>>> {
+        unreachable!("PlanSMBundle only plans executions");
+    }
+}
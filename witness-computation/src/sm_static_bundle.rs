@@ -4,6 +4,8 @@ use data_bus::{DataBus, DataBusTrait};
 use executor::SMBundle;
 use p3_field::PrimeField64;
 use precomp_arith_eq::ArithEqManager;
+use precomp_blake2br::Blake2brManager;
+use precomp_dma::DmaManager;
 use precomp_keccakf::KeccakfManager;
 use precomp_sha256f::Sha256fManager;
 use proofman_common::ProofCtx;
@@ -17,19 +19,22 @@ use zisk_common::{
 
 use executor::NestedDeviceMetricsList;
 
+use crate::static_data_bus::{
+    ARITH_EQ_SM_ID, ARITH_SM_ID, BINARY_SM_ID, BLAKE2_SM_ID, DMA_SM_ID, KECCAK_SM_ID, MAIN_SM_ID,
+    MEM_SM_ID, NUM_SM_WITHOUT_MAIN, ROM_SM_ID, SHA256_SM_ID,
+};
 use crate::StaticDataBus;
 
-const NUM_SM: usize = 8;
-const NUM_SM_WITHOUT_MAIN: usize = NUM_SM - 1;
+/// Plans the secondary state machines, calling `plan` with the id of every one of them and its
+/// counters, so that every bundle plans them in the order of the `StaticDataBus` devices
+pub(crate) fn plan_sec_by_id(
+    vec_counters: NestedDeviceMetricsList,
+    mut plan: impl FnMut(usize, Vec<(ChunkId, Box<dyn BusDeviceMetrics>)>) -> Vec<Plan>,
+) -> Vec<Vec<Plan>> {
+    assert_eq!(vec_counters.len(), NUM_SM_WITHOUT_MAIN);
 
-const _MAIN_SM_ID: usize = 0;
-const MEM_SM_ID: usize = 1;
-const ROM_SM_ID: usize = 2;
-const BINARY_SM_ID: usize = 3;
-const ARITH_SM_ID: usize = 4;
-const KECCAK_SM_ID: usize = 5;
-const SHA256_SM_ID: usize = 6;
-const ARITH_EQ_SM_ID: usize = 7;
+    vec_counters.into_iter().enumerate().map(|(idx, counters)| plan(idx + 1, counters)).collect()
+}
 
 pub struct StaticSMBundle<F: PrimeField64> {
     mem_sm: Arc<Mem<F>>,
@@ -39,6 +44,8 @@ pub struct StaticSMBundle<F: PrimeField64> {
     keccakf_sm: Arc<KeccakfManager>,
     sha256f_sm: Arc<Sha256fManager>,
     arith_eq_sm: Arc<ArithEqManager<F>>,
+    blake2br_sm: Arc<Blake2brManager>,
+    dma_sm: Arc<DmaManager>,
 }
 
 impl<F: PrimeField64> StaticSMBundle<F> {
@@ -50,6 +57,8 @@ impl<F: PrimeField64> StaticSMBundle<F> {
         keccakf_sm: Arc<KeccakfManager>,
         sha256f_sm: Arc<Sha256fManager>,
         arith_eq_sm: Arc<ArithEqManager<F>>,
+        blake2br_sm: Arc<Blake2brManager>,
+        dma_sm: Arc<DmaManager>,
     ) -> Self {
         Self {
             // main_sm,
@@ -60,28 +69,41 @@ impl<F: PrimeField64> StaticSMBundle<F> {
             keccakf_sm,
             sha256f_sm,
             arith_eq_sm,
+            blake2br_sm,
+            dma_sm,
         }
     }
 }
 
 impl<F: PrimeField64> SMBundle<F> for StaticSMBundle<F> {
     fn plan_sec(&self, vec_counters: NestedDeviceMetricsList) -> Vec<Vec<Plan>> {
-        assert_eq!(vec_counters.len(), NUM_SM_WITHOUT_MAIN);
-
-        let mut it = vec_counters.into_iter();
-
-        vec![
-            self.mem_sm.build_planner().plan(it.next().unwrap()),
-            <RomSM as ComponentBuilder<F>>::build_planner(&*self.rom_sm).plan(it.next().unwrap()),
-            self.binary_sm.build_planner().plan(it.next().unwrap()),
-            <ArithSM as ComponentBuilder<F>>::build_planner(&*self.arith_sm)
-                .plan(it.next().unwrap()),
-            <KeccakfManager as ComponentBuilder<F>>::build_planner(&*self.keccakf_sm)
-                .plan(it.next().unwrap()),
-            <Sha256fManager as ComponentBuilder<F>>::build_planner(&*self.sha256f_sm)
-                .plan(it.next().unwrap()),
-            self.arith_eq_sm.build_planner().plan(it.next().unwrap()),
-        ]
+        plan_sec_by_id(vec_counters, |id, counters| match id {
+            MEM_SM_ID => self.mem_sm.build_planner().plan(counters),
+            ROM_SM_ID => {
+                <RomSM as ComponentBuilder<F>>::build_planner(&*self.rom_sm).plan(counters)
+            }
+            BINARY_SM_ID => self.binary_sm.build_planner().plan(counters),
+            ARITH_SM_ID => {
+                <ArithSM as ComponentBuilder<F>>::build_planner(&*self.arith_sm).plan(counters)
+            }
+            KECCAK_SM_ID => {
+                <KeccakfManager as ComponentBuilder<F>>::build_planner(&*self.keccakf_sm)
+                    .plan(counters)
+            }
+            SHA256_SM_ID => {
+                <Sha256fManager as ComponentBuilder<F>>::build_planner(&*self.sha256f_sm)
+                    .plan(counters)
+            }
+            ARITH_EQ_SM_ID => self.arith_eq_sm.build_planner().plan(counters),
+            BLAKE2_SM_ID => {
+                <Blake2brManager as ComponentBuilder<F>>::build_planner(&*self.blake2br_sm)
+                    .plan(counters)
+            }
+            DMA_SM_ID => {
+                <DmaManager as ComponentBuilder<F>>::build_planner(&*self.dma_sm).plan(counters)
+            }
+            _ => unreachable!(),
+        })
     }
 
     fn configure_instances(&self, pctx: &ProofCtx<F>, plannings: &[Vec<Plan>]) {
@@ -92,6 +114,8 @@ impl<F: PrimeField64> SMBundle<F> for StaticSMBundle<F> {
         self.keccakf_sm.configure_instances(pctx, &plannings[KECCAK_SM_ID - 1]);
         self.sha256f_sm.configure_instances(pctx, &plannings[SHA256_SM_ID - 1]);
         self.arith_eq_sm.configure_instances(pctx, &plannings[ARITH_EQ_SM_ID - 1]);
+        self.blake2br_sm.configure_instances(pctx, &plannings[BLAKE2_SM_ID - 1]);
+        self.dma_sm.configure_instances(pctx, &plannings[DMA_SM_ID - 1]);
     }
 
     fn build_instance(&self, idx: usize, ictx: InstanceCtx) -> Box<dyn Instance<F>> {
@@ -105,6 +129,8 @@ impl<F: PrimeField64> SMBundle<F> for StaticSMBundle<F> {
             KECCAK_SM_ID => self.keccakf_sm.build_instance(ictx),
             SHA256_SM_ID => self.sha256f_sm.build_instance(ictx),
             ARITH_EQ_SM_ID => self.arith_eq_sm.build_instance(ictx),
+            BLAKE2_SM_ID => self.blake2br_sm.build_instance(ictx),
+            DMA_SM_ID => self.dma_sm.build_instance(ictx),
             _ => unreachable!(),
         }
     }
@@ -119,11 +145,13 @@ impl<F: PrimeField64> SMBundle<F> for StaticSMBundle<F> {
             self.keccakf_sm.build_keccakf_counter(),
             self.sha256f_sm.build_sha256f_counter(),
             self.arith_eq_sm.build_arith_eq_counter(),
+            self.blake2br_sm.build_blake2br_counter(),
+            self.dma_sm.build_dma_counter(),
         )
     }
 
     fn main_counter_idx(&self) -> Option<usize> {
-        Some(0)
+        Some(MAIN_SM_ID)
     }
 
     fn build_data_bus_collectors(
@@ -163,6 +191,8 @@ impl<F: PrimeField64> SMBundle<F> for StaticSMBundle<F> {
                     add_generator!(keccakf_sm, KeccakfManager);
                     add_generator!(sha256f_sm, Sha256fManager);
                     add_generator!(arith_eq_sm, ArithEqManager<F>);
+                    add_generator!(blake2br_sm, Blake2brManager);
+                    add_generator!(dma_sm, DmaManager);
 
                     Some(data_bus)
                 } else {
@@ -7,6 +7,8 @@ use std::collections::VecDeque;
 
 use data_bus::DataBusTrait;
 use precomp_arith_eq::ArithEqCounterInputGen;
+use precomp_blake2br::Blake2brCounterInputGen;
+use precomp_dma::DmaCounterInputGen;
 use precomp_keccakf::KeccakfCounterInputGen;
 use precomp_sha256f::Sha256fCounterInputGen;
 use sm_arith::ArithCounterInputGen;
@@ -15,6 +17,23 @@ use sm_main::MainCounter;
 use sm_mem::MemCounters;
 use zisk_common::{BusDevice, BusDeviceMetrics, BusId, PayloadType, MEM_BUS_ID, OPERATION_BUS_ID};
 
+/// Number of state machines, including the main one
+pub(crate) const NUM_SM: usize = 10;
+pub(crate) const NUM_SM_WITHOUT_MAIN: usize = NUM_SM - 1;
+
+// Ids of the state machines, which are the positions of their counters in the devices of the bus,
+// and so the positions of their plans after the main one. The ROM has no counter.
+pub(crate) const MAIN_SM_ID: usize = 0;
+pub(crate) const MEM_SM_ID: usize = 1;
+pub(crate) const ROM_SM_ID: usize = 2;
+pub(crate) const BINARY_SM_ID: usize = 3;
+pub(crate) const ARITH_SM_ID: usize = 4;
+pub(crate) const KECCAK_SM_ID: usize = 5;
+pub(crate) const SHA256_SM_ID: usize = 6;
+pub(crate) const ARITH_EQ_SM_ID: usize = 7;
+pub(crate) const BLAKE2_SM_ID: usize = 8;
+pub(crate) const DMA_SM_ID: usize = 9;
+
 /// A bus system facilitating communication between multiple publishers and subscribers.
 ///
 /// The `DataBus` allows devices to register for specific bus IDs or act as global (omni) devices.
@@ -33,6 +52,8 @@ pub struct StaticDataBus<D> {
     pub keccakf_counter: KeccakfCounterInputGen,
     pub sha256f_counter: Sha256fCounterInputGen,
     pub arith_eq_counter: ArithEqCounterInputGen,
+    pub blake2br_counter: Blake2brCounterInputGen,
+    pub dma_counter: DmaCounterInputGen,
 
     /// Queue of pending data transfers to be processed.
     pending_transfers: VecDeque<(BusId, Vec<D>)>,
@@ -47,6 +68,8 @@ impl StaticDataBus<PayloadType> {
         keccakf_counter: KeccakfCounterInputGen,
         sha256f_counter: Sha256fCounterInputGen,
         arith_eq_counter: ArithEqCounterInputGen,
+        blake2br_counter: Blake2brCounterInputGen,
+        dma_counter: DmaCounterInputGen,
     ) -> Self {
         Self {
             main_counter: MainCounter::new(),
@@ -56,6 +79,8 @@ impl StaticDataBus<PayloadType> {
             keccakf_counter,
             sha256f_counter,
             arith_eq_counter,
+            blake2br_counter,
+            dma_counter,
             pending_transfers: VecDeque::new(),
         }
     }
@@ -93,6 +118,12 @@ impl StaticDataBus<PayloadType> {
                 if let Some(result) = self.arith_eq_counter.process_data(&bus_id, payload) {
                     self.pending_transfers.extend(result);
                 }
+                if let Some(result) = self.blake2br_counter.process_data(&bus_id, payload) {
+                    self.pending_transfers.extend(result);
+                }
+                if let Some(result) = self.dma_counter.process_data(&bus_id, payload) {
+                    self.pending_transfers.extend(result);
+                }
             }
             _ => (),
         }
@@ -117,6 +148,8 @@ impl DataBusTrait<PayloadType, Box<dyn BusDeviceMetrics>> for StaticDataBus<Payl
         self.keccakf_counter.on_close();
         self.sha256f_counter.on_close();
         self.arith_eq_counter.on_close();
+        self.blake2br_counter.on_close();
+        self.dma_counter.on_close();
     }
 
     fn into_devices(mut self, execute_on_close: bool) -> Vec<Option<Box<dyn BusDeviceMetrics>>> {
@@ -132,19 +165,22 @@ impl DataBusTrait<PayloadType, Box<dyn BusDeviceMetrics>> for StaticDataBus<Payl
             keccakf_counter,
             sha256f_counter,
             arith_eq_counter,
+            blake2br_counter,
+            dma_counter,
             pending_transfers: _,
         } = self;
 
-        let counters: Vec<Option<Box<dyn BusDeviceMetrics>>> = vec![
-            Some(Box::new(main_counter)),
-            Some(Box::new(mem_counter)),
-            None,
-            Some(Box::new(binary_counter)),
-            Some(Box::new(arith_counter)),
-            Some(Box::new(keccakf_counter)),
-            Some(Box::new(sha256f_counter)),
-            Some(Box::new(arith_eq_counter)),
-        ];
+        let mut counters: Vec<Option<Box<dyn BusDeviceMetrics>>> =
+            (0..NUM_SM).map(|_| None).collect();
+        counters[MAIN_SM_ID] = Some(Box::new(main_counter));
+        counters[MEM_SM_ID] = Some(Box::new(mem_counter));
+        counters[BINARY_SM_ID] = Some(Box::new(binary_counter));
+        counters[ARITH_SM_ID] = Some(Box::new(arith_counter));
+        counters[KECCAK_SM_ID] = Some(Box::new(keccakf_counter));
+        counters[SHA256_SM_ID] = Some(Box::new(sha256f_counter));
+        counters[ARITH_EQ_SM_ID] = Some(Box::new(arith_eq_counter));
+        counters[BLAKE2_SM_ID] = Some(Box::new(blake2br_counter));
+        counters[DMA_SM_ID] = Some(Box::new(dma_counter));
 
         counters
     }
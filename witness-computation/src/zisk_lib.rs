@@ -4,28 +4,45 @@
 //! This module leverages `WitnessLibrary` to orchestrate the setup of state machines,
 //! program conversion, and execution pipelines to generate required witnesses.
 
-use crate::StaticSMBundle;
-use executor::{/*DynSMBundle,*/ ZiskExecutor};
+use crate::{PlanSMBundle, StaticSMBundle};
+use asm_runner::{AsmRunnerOptions, AsmServerMT};
+use executor::{/*DynSMBundle,*/ SharedInputPath, ZiskExecutionPlan, ZiskExecutor};
 use p3_field::PrimeField64;
 use p3_goldilocks::Goldilocks;
 use pil_std_lib::Std;
 use precomp_arith_eq::ArithEqManager;
+use precomp_blake2br::Blake2brManager;
+use precomp_dma::DmaManager;
 use precomp_keccakf::KeccakfManager;
 use precomp_sha256f::Sha256fManager;
 use sm_arith::ArithSM;
 use sm_binary::BinarySM;
 use sm_mem::Mem;
 use sm_rom::RomSM;
-use std::{any::Any, path::PathBuf, sync::Arc};
+use std::{
+    any::Any,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+};
 use witness::{WitnessLibrary, WitnessManager};
-use zisk_core::Riscv2zisk;
+use zisk_core::{ResourceLimits, Riscv2zisk, UserFcalls, ZiskRom};
 
 pub struct WitnessLib<F: PrimeField64> {
     elf_path: PathBuf,
     asm_path: Option<PathBuf>,
     asm_rom_path: Option<PathBuf>,
-    input_data_path: Option<PathBuf>,
+    input_data_path: SharedInputPath,
     sha256f_script_path: PathBuf,
+    /// Whether to prove executions whose guest program failed
+    allow_panic: bool,
+    /// Resource limits of the guest program execution
+    resource_limits: ResourceLimits,
+    /// ZisK ROM, transpiled once and reused by every proof
+    zisk_rom: Option<Arc<ZiskRom>>,
+    /// Resident assembly emulator, only started when many inputs are proven in a row
+    asm_server: Option<Arc<Mutex<AsmServerMT>>>,
+    /// Host functions serving the user-defined fcalls of the guest, if any
+    user_fcalls: Option<Arc<UserFcalls>>,
     executor: Option<Arc<ZiskExecutor<F, StaticSMBundle<F>>>>,
 }
 
@@ -37,20 +54,101 @@ fn init_library(
     asm_rom_path: Option<PathBuf>,
     input_data_path: Option<PathBuf>,
     sha256f_script_path: PathBuf,
+    allow_panic: bool,
+    resource_limits: ResourceLimits,
 ) -> Result<Box<dyn witness::WitnessLibrary<Goldilocks>>, Box<dyn std::error::Error>> {
     proofman_common::initialize_logger(verbose_mode);
+    let result = Box::new(WitnessLib {
+        elf_path,
+        asm_path,
+        asm_rom_path,
+        input_data_path: Arc::new(RwLock::new(input_data_path)),
+        sha256f_script_path,
+        allow_panic,
+        resource_limits,
+        zisk_rom: None,
+        asm_server: None,
+        user_fcalls: None,
+        executor: None,
+    });
+
+    Ok(result)
+}
+
+/// Initializes the library to prove many inputs of the same program in a row.
+///
+/// The input of every proof is read from `input_data_path` when its witness is registered, and
+/// the ZisK ROM and the assembly emulator are kept loaded between proofs.  The assembly emulator
+/// is started with `asm_runner_options` and keeps them for the whole batch.
+#[no_mangle]
+fn init_library_batch(
+    verbose_mode: proofman_common::VerboseMode,
+    elf_path: PathBuf,
+    asm_path: Option<PathBuf>,
+    asm_rom_path: Option<PathBuf>,
+    input_data_path: SharedInputPath,
+    sha256f_script_path: PathBuf,
+    allow_panic: bool,
+    resource_limits: ResourceLimits,
+    asm_runner_options: AsmRunnerOptions,
+) -> Result<Box<dyn witness::WitnessLibrary<Goldilocks>>, Box<dyn std::error::Error>> {
+    proofman_common::initialize_logger(verbose_mode);
+    // The executor and the ROM state machine must serve the same fcalls as the server
+    let user_fcalls = asm_runner_options.user_fcalls.clone();
+    let asm_server = match &asm_path {
+        Some(asm_path) => {
+            Some(Arc::new(Mutex::new(AsmServerMT::start(asm_path, asm_runner_options)?)))
+        }
+        None => None,
+    };
     let result = Box::new(WitnessLib {
         elf_path,
         asm_path,
         asm_rom_path,
         input_data_path,
         sha256f_script_path,
+        allow_panic,
+        resource_limits,
+        zisk_rom: None,
+        asm_server,
+        user_fcalls,
         executor: None,
     });
 
     Ok(result)
 }
 
+/// Executes the program and plans its instances as the prover does, without the proving key.
+#[no_mangle]
+fn execute_library(
+    verbose_mode: proofman_common::VerboseMode,
+    elf_path: PathBuf,
+    asm_path: Option<PathBuf>,
+    input_data_path: Option<PathBuf>,
+    sha256f_script_path: PathBuf,
+    resource_limits: ResourceLimits,
+) -> Result<ZiskExecutionPlan, Box<dyn std::error::Error>> {
+    proofman_common::initialize_logger(verbose_mode);
+
+    let rv2zk = Riscv2zisk::new(elf_path.display().to_string());
+    let zisk_rom = Arc::new(rv2zk.run()?);
+
+    let sm_bundle = PlanSMBundle::<Goldilocks>::new(
+        RomSM::new(zisk_rom.clone(), None, None, None),
+        ArithSM::new(),
+        KeccakfManager::new::<Goldilocks>(),
+        Sha256fManager::new::<Goldilocks>(sha256f_script_path),
+        Blake2brManager::new::<Goldilocks>(),
+        DmaManager::new::<Goldilocks>(),
+    );
+
+    let executor =
+        ZiskExecutor::new_planner(elf_path, asm_path, input_data_path, zisk_rom, sm_bundle)
+            .with_resource_limits(resource_limits);
+
+    Ok(executor.execute_plan()?)
+}
+
 impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
     /// Registers the witness components and initializes the execution pipeline.
     ///
@@ -58,7 +156,8 @@ impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
     /// * `wcm` - An `Arc`-wrapped `WitnessManager` instance that orchestrates witness generation.
     ///
     /// This method performs the following steps:
-    /// 1. Converts a RISC-V program to the ZisK ROM format using `Riscv2zisk`.
+    /// 1. Converts a RISC-V program to the ZisK ROM format using `Riscv2zisk`, the first time
+    ///    it is called.
     /// 2. Initializes core and secondary state machines for witness generation.
     /// 3. Registers the state machines with the `ZiskExecutor`.
     /// 4. Registers the `ZiskExecutor` as a component in the `WitnessManager`.
@@ -66,17 +165,29 @@ impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
     /// # Panics
     /// Panics if the `Riscv2zisk` conversion fails or if required paths cannot be resolved.
     fn register_witness(&mut self, wcm: Arc<WitnessManager<F>>) {
-        // Step 1: Create an instance of the RISCV -> ZisK program converter
-        let rv2zk = Riscv2zisk::new(self.elf_path.display().to_string());
+        let zisk_rom = match &self.zisk_rom {
+            Some(zisk_rom) => zisk_rom.clone(),
+            None => {
+                // Step 1: Create an instance of the RISCV -> ZisK program converter
+                let rv2zk = Riscv2zisk::new(self.elf_path.display().to_string());
 
-        // Step 2: Convert program to ROM
-        let zisk_rom = rv2zk.run().unwrap_or_else(|e| panic!("Application error: {}", e));
-        let zisk_rom = Arc::new(zisk_rom);
+                // Step 2: Convert program to ROM
+                let zisk_rom = rv2zk.run().unwrap_or_else(|e| panic!("Application error: {}", e));
+                let zisk_rom = Arc::new(zisk_rom);
+                self.zisk_rom = Some(zisk_rom.clone());
+                zisk_rom
+            }
+        };
+        let input_data_path = self.input_data_path.read().unwrap().clone();
 
         // Step 3: Initialize the secondary state machines
         let std = Std::new(wcm.clone());
-        let rom_sm =
-            RomSM::new(zisk_rom.clone(), self.asm_rom_path.clone(), self.input_data_path.clone());
+        let rom_sm = RomSM::new(
+            zisk_rom.clone(),
+            self.asm_rom_path.clone(),
+            input_data_path.clone(),
+            self.user_fcalls.clone(),
+        );
         let binary_sm = BinarySM::new(std.clone());
         let arith_sm = ArithSM::new();
         let mem_sm = Mem::new(std.clone());
@@ -85,6 +196,8 @@ impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
         let keccakf_sm = KeccakfManager::new::<F>();
         let sha256f_sm = Sha256fManager::new::<F>(self.sha256f_script_path.clone());
         let arith_eq_sm = ArithEqManager::new(std.clone());
+        let blake2br_sm = Blake2brManager::new::<F>();
+        let dma_sm = DmaManager::new::<F>();
 
         // let sm_bundle = DynSMBundle::new(vec![
         //     mem_sm.clone(),
@@ -94,6 +207,7 @@ impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
         //     keccakf_sm.clone(),
         //     sha256f_sm.clone(),
         //     arith_eq_sm.clone(),
+        //     blake2br_sm.clone(),
         // ]);
 
         let sm_bundle = StaticSMBundle::new(
@@ -105,18 +219,28 @@ impl<F: PrimeField64> WitnessLibrary<F> for WitnessLib<F> {
             keccakf_sm.clone(),
             sha256f_sm.clone(),
             arith_eq_sm.clone(),
+            blake2br_sm.clone(),
+            dma_sm.clone(),
         );
 
         // Step 5: Create the executor and register the secondary state machines
-        let executor: ZiskExecutor<F, StaticSMBundle<F>> = ZiskExecutor::new(
+        let mut executor: ZiskExecutor<F, StaticSMBundle<F>> = ZiskExecutor::new(
             self.elf_path.clone(),
             self.asm_path.clone(),
             self.asm_rom_path.clone(),
-            self.input_data_path.clone(),
+            input_data_path,
             zisk_rom,
             std,
             sm_bundle,
-        );
+        )
+        .with_allow_panic(self.allow_panic)
+        .with_resource_limits(self.resource_limits.clone());
+        if let Some(user_fcalls) = &self.user_fcalls {
+            executor = executor.with_user_fcalls(UserFcalls::clone(user_fcalls));
+        }
+        if let Some(asm_server) = &self.asm_server {
+            executor = executor.with_asm_server(asm_server.clone());
+        }
 
         let executor = Arc::new(executor);
 
@@ -12,6 +12,10 @@ pub use zisklib::*;
 mod syscalls;
 pub use syscalls::*;
 
+#[cfg(any(test, all(target_os = "zkvm", target_vendor = "zisk", feature = "dma")))]
+mod mem_builtins;
+
+pub mod test_harness;
 pub mod ziskos_definitions;
 
 #[macro_export]
@@ -28,6 +32,82 @@ macro_rules! entrypoint {
     };
 }
 
+/// Declares the `#[test]` functions of a guest test target, built with `harness = false`, and
+/// generates its entry point, as `entrypoint!` does.  Every function of the block is a test, and
+/// can be documented and marked as `#[should_panic]`, `#[should_panic(expected = "..")]` or
+/// `#[ignore]`, as with the standard test harness.  See `test_harness` for how the tests are run.
+///
+/// ```ignore
+/// #![no_main]
+///
+/// ziskos::tests! {
+///     #[test]
+///     fn adds() {
+///         assert_eq!(1 + 1, 2);
+///     }
+///
+///     /// Overflows are checked
+///     #[test]
+///     #[should_panic(expected = "overflow")]
+///     fn overflows() {
+///         u8::MAX.checked_add(1).expect("overflow");
+///     }
+/// }
+/// ```
+///
+/// Unit tests are declared the same way in the crate root, with `#[cfg(test)]`, when the library
+/// or binary target is built with `harness = false` and `#![cfg_attr(test, no_main)]`.  A binary
+/// must then only declare its entry point when not testing, with
+/// `#[cfg(not(test))] ziskos::entrypoint!(main)`.
+#[macro_export]
+macro_rules! tests {
+    (@collect [$($tests:tt)*] $(#[$($attr:tt)*])* fn $test:ident() $body:block $($rest:tt)*) => {
+        fn $test() $body
+
+        $crate::tests!(@collect [$($tests)* ($test, [$([$($attr)*])*])] $($rest)*);
+    };
+    (@collect [$(($name:ident, [$([$($attr:tt)*])*]))*]) => {
+        /// Returns the tests declared with `ziskos::tests!`
+        fn zkvm_generated_tests() -> ::std::vec::Vec<$crate::test_harness::TestCase> {
+            ::std::vec![$({
+                #[allow(unused_mut)]
+                let mut test = $crate::test_harness::TestCase::new(stringify!($name), $name);
+                $($crate::tests!(@attr test $($attr)*);)*
+                test
+            }),*]
+        }
+    };
+    (@attr $test:ident test) => {};
+    (@attr $test:ident doc = $doc:literal) => {};
+    (@attr $test:ident should_panic) => {
+        $test.should_panic = true;
+    };
+    (@attr $test:ident should_panic = $expected:literal) => {
+        $test.should_panic = true;
+        $test.expected = Some($expected);
+    };
+    (@attr $test:ident should_panic(expected = $expected:literal)) => {
+        $test.should_panic = true;
+        $test.expected = Some($expected);
+    };
+    (@attr $test:ident ignore $(= $reason:literal)?) => {
+        $test.ignore = true;
+    };
+    (@attr $test:ident $($attr:tt)*) => {
+        compile_error!(concat!("Unsupported test attribute: #[", stringify!($($attr)*), "]"));
+    };
+    ($($tests:tt)*) => {
+        $crate::tests!(@collect [] $($tests)*);
+
+        mod zkvm_generated_main {
+            #[no_mangle]
+            fn main() {
+                $crate::test_harness::run_tests(&super::zkvm_generated_tests());
+            }
+        }
+    };
+}
+
 // #[macro_export]
 // macro_rules! ziskos_fcall_get {
 //     () => {{
@@ -103,6 +183,58 @@ pub fn set_output(id: usize, value: u32) {
     println!("public {}: {:#010x}", id, value);
 }
 
+/// Ends the program with the provided exit code, which is reported by the emulator and the prover;
+/// exit code 0 means success
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+pub fn exit(exit_code: u32) -> ! {
+    use std::arch::asm;
+    let arch_id_zisk: usize;
+
+    unsafe {
+        asm!(
+          "csrr {0}, marchid",
+          out(reg) arch_id_zisk,
+        )
+    };
+
+    if arch_id_zisk == ARCH_ID_ZISK as usize {
+        unsafe {
+            core::ptr::write_volatile(GUEST_EXIT_ADDR as *mut u64, exit_code as u64);
+            asm!("ecall", in("a0") exit_code, in("a7") 93usize, options(noreturn));
+        }
+    }
+
+    // QEMU test finisher: 0x5555 means pass, (code << 16) | 0x3333 means fail
+    let qemu_exit_code = if exit_code == 0 { 0x5555 } else { (exit_code << 16) | 0x3333 };
+    unsafe { core::ptr::write_volatile(QEMU_EXIT_ADDR as *mut u32, qemu_exit_code) };
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+pub fn exit(exit_code: u32) -> ! {
+    std::process::exit(exit_code as i32)
+}
+
+/// Panic hook that stores the panic message where the emulator can find it, prints it to the
+/// standard error, and ends the program with exit code 101, as a native Rust program would
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+fn panic_hook(message: &str) {
+    eprintln!("{}", message);
+
+    let len = message.len().min(GUEST_PANIC_MSG_MAX_SIZE as usize);
+    let addr = (GUEST_EXIT_ADDR + 16) as *mut u8;
+    unsafe {
+        for (i, byte) in message.as_bytes()[..len].iter().enumerate() {
+            core::ptr::write_volatile(addr.add(i), *byte);
+        }
+        core::ptr::write_volatile((GUEST_EXIT_ADDR + 8) as *mut u64, len as u64);
+    }
+
+    exit(101);
+}
+
 #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 mod ziskos {
     use crate::ziskos_definitions::ziskos_config::*;
@@ -137,8 +269,9 @@ mod ziskos {
           "sw t1, 0(t0)",
           "j 2f",
 
-          // Zisk exit
-          "1: li   a7, 93",
+          // Zisk exit, with exit code 0
+          "1: li   a0, 0",
+          "li   a7, 93",
           "ecall",
 
           "2: j 2b",
@@ -164,12 +297,13 @@ mod ziskos {
             extern "C" {
                 fn main();
             }
+            std::panic::set_hook(Box::new(|info| crate::panic_hook(&info.to_string())));
             main()
         }
     }
 
     #[no_mangle]
-    extern "C" fn sys_write(_fd: u32, write_ptr: *const u8, nbytes: usize) {
+    extern "C" fn sys_write(fd: u32, write_ptr: *const u8, nbytes: usize) {
         let arch_id_zisk: usize;
         let mut addr: *mut u8 = 0x1000_0000 as *mut u8;
 
@@ -180,7 +314,8 @@ mod ziskos {
             )
         };
         if arch_id_zisk == ARCH_ID_ZISK as usize {
-            addr = 0xa000_0200 as *mut u8;
+            // Keep the guest standard error apart from the standard output
+            addr = if fd == 2 { UART_ERR_ADDR as *mut u8 } else { UART_ADDR as *mut u8 };
         }
 
         for i in 0..nbytes {
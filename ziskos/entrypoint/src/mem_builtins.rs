@@ -0,0 +1,213 @@
+//! Overrides of the `memcpy`, `memmove` and `memset` compiler builtins, enabled with the `dma`
+//! feature, which process the 8-byte aligned bulk of the data with the `Memcpy` and `Memset`
+//! precompiled operations instead of a loop of RISC-V loads and stores.
+//!
+//! The unaligned head and tail bytes, and copies whose source and destination have a different
+//! alignment, are processed one byte at a time using volatile accesses, so that the compiler can
+//! not replace these loops with calls to the very functions being defined here.
+
+use core::ptr::{read_volatile, write_volatile};
+
+use crate::{
+    memcpy::{syscall_memcpy, SyscallMemcpyParams, SYSCALL_MEMCPY_MAX_WORDS},
+    memset::{syscall_memset, SyscallMemsetParams, SYSCALL_MEMSET_MAX_WORDS},
+};
+
+/// Splits an area of `n` bytes starting at `dst` into an unaligned head, an aligned bulk whose
+/// length is a multiple of 8, and an unaligned tail, returning the bulk `(start, end)` offsets
+#[inline(always)]
+fn split_aligned(dst: *const u8, n: usize) -> (usize, usize) {
+    let head = ((8 - (dst as usize & 0x7)) & 0x7).min(n);
+    (head, head + ((n - head) & !0x7))
+}
+
+#[inline(always)]
+unsafe fn copy_byte(dst: *mut u8, src: *const u8, i: usize) {
+    write_volatile(dst.add(i), read_volatile(src.add(i)));
+}
+
+#[inline(always)]
+unsafe fn copy_words(dst: *mut u8, src: *const u8, offset: usize, len: usize) {
+    let params = SyscallMemcpyParams {
+        dst: dst.add(offset) as *mut u64,
+        src: src.add(offset) as *const u64,
+        count: len / 8,
+    };
+    syscall_memcpy(&params);
+}
+
+/// Copies from the lowest to the highest address, valid if areas do not overlap or if `dst < src`
+unsafe fn copy_forward(dst: *mut u8, src: *const u8, n: usize) {
+    if (dst as usize ^ src as usize) & 0x7 != 0 {
+        for i in 0..n {
+            copy_byte(dst, src, i);
+        }
+        return;
+    }
+
+    let (bulk_start, bulk_end) = split_aligned(dst, n);
+
+    for i in 0..bulk_start {
+        copy_byte(dst, src, i);
+    }
+
+    let mut offset = bulk_start;
+    while offset < bulk_end {
+        let len = (bulk_end - offset).min(8 * SYSCALL_MEMCPY_MAX_WORDS);
+        copy_words(dst, src, offset, len);
+        offset += len;
+    }
+
+    for i in bulk_end..n {
+        copy_byte(dst, src, i);
+    }
+}
+
+/// Copies from the highest to the lowest address, valid if areas do not overlap or if `dst > src`
+unsafe fn copy_backward(dst: *mut u8, src: *const u8, n: usize) {
+    if (dst as usize ^ src as usize) & 0x7 != 0 {
+        for i in (0..n).rev() {
+            copy_byte(dst, src, i);
+        }
+        return;
+    }
+
+    let (bulk_start, bulk_end) = split_aligned(dst, n);
+
+    for i in (bulk_end..n).rev() {
+        copy_byte(dst, src, i);
+    }
+
+    // Every operation reads its whole source before writing, so only the order of blocks matters
+    let mut offset = bulk_end;
+    while offset > bulk_start {
+        let len = (offset - bulk_start).min(8 * SYSCALL_MEMCPY_MAX_WORDS);
+        offset -= len;
+        copy_words(dst, src, offset, len);
+    }
+
+    for i in (0..bulk_start).rev() {
+        copy_byte(dst, src, i);
+    }
+}
+
+unsafe fn fill(dst: *mut u8, c: u8, n: usize) {
+    let (bulk_start, bulk_end) = split_aligned(dst, n);
+
+    for i in 0..bulk_start {
+        write_volatile(dst.add(i), c);
+    }
+
+    let mut offset = bulk_start;
+    while offset < bulk_end {
+        let len = (bulk_end - offset).min(8 * SYSCALL_MEMSET_MAX_WORDS);
+        let params = SyscallMemsetParams {
+            dst: dst.add(offset) as *mut u64,
+            fill: u64::from_ne_bytes([c; 8]),
+            count: len / 8,
+        };
+        syscall_memset(&params);
+        offset += len;
+    }
+
+    for i in bulk_end..n {
+        write_volatile(dst.add(i), c);
+    }
+}
+
+unsafe fn copy(dst: *mut u8, src: *const u8, n: usize) {
+    if (dst as usize) <= (src as usize) || (dst as usize) >= (src as usize).wrapping_add(n) {
+        copy_forward(dst, src, n);
+    } else {
+        copy_backward(dst, src, n);
+    }
+}
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+#[no_mangle]
+pub unsafe extern "C" fn memcpy(dst: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    copy_forward(dst, src, n);
+    dst
+}
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+#[no_mangle]
+pub unsafe extern "C" fn memmove(dst: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    copy(dst, src, n);
+    dst
+}
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+#[no_mangle]
+pub unsafe extern "C" fn memset(dst: *mut u8, c: i32, n: usize) -> *mut u8 {
+    fill(dst, c as u8, n);
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 8 * SYSCALL_MEMCPY_MAX_WORDS * 3;
+
+    /// An aligned buffer with distinct bytes, to check the data movements byte by byte
+    #[repr(align(8))]
+    struct Buffer([u8; LEN]);
+
+    impl Buffer {
+        fn new() -> Self {
+            let mut buffer = Buffer([0; LEN]);
+            buffer.0.iter_mut().enumerate().for_each(|(i, byte)| *byte = (i * 7 + i / 256) as u8);
+            buffer
+        }
+    }
+
+    #[test]
+    fn test_memmove_overlapping() {
+        let offsets = [0, 1, 3, 8, 9, 16, 517];
+        let lengths = [0, 1, 7, 8, 15, 64, 8 * SYSCALL_MEMCPY_MAX_WORDS + 13, 1000];
+
+        for &dst_offset in &offsets {
+            for &src_offset in &offsets {
+                for &n in &lengths {
+                    let mut buffer = Buffer::new();
+                    let mut expected = buffer.0;
+                    expected.copy_within(src_offset..src_offset + n, dst_offset);
+
+                    let base = buffer.0.as_mut_ptr();
+                    unsafe { copy(base.add(dst_offset), base.add(src_offset), n) };
+                    assert_eq!(buffer.0, expected, "dst {dst_offset} src {src_offset} n {n}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_memcpy_disjoint() {
+        for &(dst_offset, src_offset) in &[(0, 1000), (3, 1003), (1000, 1), (1004, 2)] {
+            for n in [0, 5, 8, 40, 8 * SYSCALL_MEMCPY_MAX_WORDS + 3] {
+                let mut buffer = Buffer::new();
+                let mut expected = buffer.0;
+                expected.copy_within(src_offset..src_offset + n, dst_offset);
+
+                let base = buffer.0.as_mut_ptr();
+                unsafe { copy_forward(base.add(dst_offset), base.add(src_offset), n) };
+                assert_eq!(buffer.0, expected, "dst {dst_offset} src {src_offset} n {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_memset() {
+        for offset in [0, 1, 7, 8, 13] {
+            for n in [0, 1, 7, 8, 9, 100, 8 * SYSCALL_MEMSET_MAX_WORDS + 21] {
+                let mut buffer = Buffer::new();
+                let mut expected = buffer.0;
+                expected[offset..offset + n].fill(0xA5);
+
+                unsafe { fill(buffer.0.as_mut_ptr().add(offset), 0xA5, n) };
+                assert_eq!(buffer.0, expected, "offset {offset} n {n}");
+            }
+        }
+    }
+}
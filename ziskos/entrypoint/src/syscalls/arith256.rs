@@ -36,6 +36,8 @@ pub struct SyscallArith256Params<'a> {
 pub extern "C" fn syscall_arith256(params: &mut SyscallArith256Params) {
     #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
     ziskos_syscall!(0x801, params);
-    #[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    precompiles_helpers::arith256(params.a, params.b, params.c, params.dl, params.dh);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
     unreachable!()
 }
@@ -39,6 +39,8 @@ pub struct SyscallArith256ModParams<'a> {
 pub extern "C" fn syscall_arith256_mod(params: &mut SyscallArith256ModParams) {
     #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
     ziskos_syscall!(0x802, params);
-    #[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    precompiles_helpers::arith256_mod(params.a, params.b, params.c, params.module, params.d);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
     unreachable!()
 }
@@ -0,0 +1,29 @@
+//! syscall_blake2b_round system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+/// Executes a single round of the BLAKE2b compression function (RFC 7693).
+///
+/// The `syscall_blake2b_round` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Blake2br`.
+///
+/// The syscall takes as a parameter the address of 32 `u64` elements: the working vector `v` (16 words)
+/// followed by the message block `m` (16 words), already permuted with the round's message schedule
+/// (`m[i] = block[SIGMA[round % 10][i]]`). The result of the round (16 words) is stored at the same
+/// location as `v`, leaving `m` untouched.
+///
+/// ### Safety
+///
+/// The caller must ensure that the data is aligned to a 64-bit boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2b_round(data: *mut [u64; 32]) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x808, data);
+    #[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+    unreachable!()
+}
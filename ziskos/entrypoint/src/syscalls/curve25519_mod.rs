@@ -0,0 +1,44 @@
+//! Curve25519Mod system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+/// Executes the `Curve25519Mod` operation, performing a 256-bit multiplication and addition modulo
+/// the curve25519 base field prime: `d = (a * b + c) mod (2^255 - 19)`.
+///
+/// The `Curve25519Mod` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Curve25519Mod`.
+///
+/// `Curve25519Mod` operates on arrays of four `u64` elements. The first parameter is a pointer to a structure
+/// containing four values:
+/// - `a`
+/// - `b`
+/// - `c`
+/// - `d` (the result)
+///
+/// ### Safety
+///
+/// The caller must ensure that the data is aligned to a 64-bit boundary.
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SyscallCurve25519ModParams<'a> {
+    pub a: &'a [u64; 4],
+    pub b: &'a [u64; 4],
+    pub c: &'a [u64; 4],
+    pub d: &'a mut [u64; 4],
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_curve25519_mod(params: &mut SyscallCurve25519ModParams) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x806, params);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    precompiles_helpers::curve25519_mod(params.a, params.b, params.c, params.d);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!()
+}
@@ -0,0 +1,47 @@
+//! syscall_ed25519_add system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+use super::point256::SyscallPoint256;
+
+/// Executes the addition of two points on the Ed25519 twisted Edwards curve, storing the result in
+/// the first point.
+///
+/// The `syscall_ed25519_add` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Ed25519Add`.
+///
+/// `syscall_ed25519_add` operates on two points in affine coordinates, each coordinate consisting of 256 bits.
+/// Each coordinate is represented as an array of four `u64` elements. The syscall takes as a parameter
+/// the address of a structure containing points `p1` and `p2`, and the result of the addition is stored
+/// in `p1`. The addition uses the unified formulas, so `p1` and `p2` may be the same point.
+///
+/// ### Safety
+///
+/// The caller must ensure that the data is aligned to an eight-byte boundary, and that `p1` and `p2`
+/// are points of the curve.
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SyscallEd25519AddParams<'a> {
+    pub p1: &'a mut SyscallPoint256,
+    pub p2: &'a SyscallPoint256,
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed25519_add(params: &mut SyscallEd25519AddParams) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x809, params);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    {
+        let mut p3 = [0u64; 8];
+        precompiles_helpers::ed25519_add(&params.p1.to_u64s(), &params.p2.to_u64s(), &mut p3);
+        params.p1.set_u64s(&p3);
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!()
+}
@@ -0,0 +1,37 @@
+//! syscall_ed25519_dbl system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+use super::point256::SyscallPoint256;
+
+/// Executes the doubling of a point on the Ed25519 twisted Edwards curve.
+///
+/// The `syscall_ed25519_dbl` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Ed25519Dbl`.
+///
+/// `syscall_ed25519_dbl` operates on a point in affine coordinates, each coordinate consisting of 256 bits.
+/// Each coordinate is represented as an array of four `u64` elements. The syscall takes as a parameter
+/// the address of the point, and the result of the doubling operation is stored at the same location.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p1` is a valid pointer to data that is aligned to an eight-byte boundary,
+/// and that `p1` is a point of the curve.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed25519_dbl(p1: &mut SyscallPoint256) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x807, p1);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    {
+        let mut p3 = [0u64; 8];
+        precompiles_helpers::ed25519_dbl(&p1.to_u64s(), &mut p3);
+        p1.set_u64s(&p3);
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!()
+}
@@ -0,0 +1,43 @@
+//! syscall_memcpy system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+/// Maximum number of words copied by a single `syscall_memcpy` call
+pub const SYSCALL_MEMCPY_MAX_WORDS: usize = 64;
+
+/// Copies `count` 64-bit words from `src` to `dst`.
+///
+/// The `syscall_memcpy` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Memcpy`.
+///
+/// The whole source is read before writing the destination, so both areas may overlap.
+///
+/// ### Safety
+///
+/// The caller must ensure that `dst` and `src` are aligned to an eight-byte boundary, that `count`
+/// is between 1 and `SYSCALL_MEMCPY_MAX_WORDS`, and that both areas are valid.
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SyscallMemcpyParams {
+    pub dst: *mut u64,
+    pub src: *const u64,
+    pub count: usize,
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_memcpy(params: &SyscallMemcpyParams) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x80A, params);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    unsafe {
+        core::ptr::copy(params.src, params.dst, params.count)
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!()
+}
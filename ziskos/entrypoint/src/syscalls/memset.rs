@@ -0,0 +1,41 @@
+//! syscall_memset system call interception
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use crate::ziskos_syscall;
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use core::arch::asm;
+
+/// Maximum number of words filled by a single `syscall_memset` call
+pub const SYSCALL_MEMSET_MAX_WORDS: usize = 64;
+
+/// Writes the 64-bit word `fill` into `count` consecutive words starting at `dst`.
+///
+/// The `syscall_memset` system call executes a CSR set on a custom port. When transpiling from RISC-V to Zisk,
+/// this instruction is replaced with a precompiled operation—specifically, `Memset`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `dst` is aligned to an eight-byte boundary, that `count` is between
+/// 1 and `SYSCALL_MEMSET_MAX_WORDS`, and that the area is valid.
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SyscallMemsetParams {
+    pub dst: *mut u64,
+    pub fill: u64,
+    pub count: usize,
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_memset(params: &SyscallMemsetParams) {
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    ziskos_syscall!(0x80B, params);
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    for i in 0..params.count {
+        unsafe { params.dst.add(i).write(params.fill) }
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!()
+}
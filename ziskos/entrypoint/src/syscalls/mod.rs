@@ -1,6 +1,12 @@
 pub mod arith256;
 pub mod arith256_mod;
+pub mod blake2br;
+pub mod curve25519_mod;
+pub mod ed25519_add;
+pub mod ed25519_dbl;
 pub mod keccakf;
+pub mod memcpy;
+pub mod memset;
 pub mod point256;
 pub mod secp256k1_add;
 pub mod secp256k1_dbl;
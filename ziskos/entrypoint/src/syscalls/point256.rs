@@ -5,3 +5,20 @@ pub struct SyscallPoint256 {
     pub x: [u64; 4],
     pub y: [u64; 4],
 }
+
+#[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+impl SyscallPoint256 {
+    /// Returns the coordinates as the `[x, y]` little-endian limbs used by the precompiles
+    pub(crate) fn to_u64s(&self) -> [u64; 8] {
+        let mut p = [0u64; 8];
+        p[..4].copy_from_slice(&self.x);
+        p[4..].copy_from_slice(&self.y);
+        p
+    }
+
+    /// Sets the coordinates from the `[x, y]` little-endian limbs used by the precompiles
+    pub(crate) fn set_u64s(&mut self, p: &[u64; 8]) {
+        self.x.copy_from_slice(&p[..4]);
+        self.y.copy_from_slice(&p[4..]);
+    }
+}
@@ -6,3 +6,9 @@ pub const SYSCALL_ARITH256_MOD_ID: u16 = 0x802;
 pub const SYSCALL_SECP256K1_ADD_ID: u16 = 0x803;
 pub const SYSCALL_SECP256K1_DBL_ID: u16 = 0x804;
 pub const SYSCALL_SHA256F_ID: u16 = 0x805;
+pub const SYSCALL_CURVE25519_MOD_ID: u16 = 0x806;
+pub const SYSCALL_ED25519_DBL_ID: u16 = 0x807;
+pub const SYSCALL_BLAKE2BR_ID: u16 = 0x808;
+pub const SYSCALL_ED25519_ADD_ID: u16 = 0x809;
+pub const SYSCALL_MEMCPY_ID: u16 = 0x80A;
+pub const SYSCALL_MEMSET_ID: u16 = 0x80B;
@@ -0,0 +1,236 @@
+//! Test harness of the guest tests declared with `ziskos::tests!`.
+//!
+//! Inside ZisK every emulation runs a single test, selected by its name in the input data, so that
+//! a panicking test ends its own emulation only.  With no input data, the harness lists the tests
+//! instead, one per line as `<name>: test` or `<name>: should_panic`, followed by ` ignored` for
+//! the ignored tests.  This is the protocol that `cargo-zisk test` follows to run the tests of a
+//! guest crate.  The panic of a test with an expected message ends the program with exit code
+//! `PANIC_MISMATCH_EXIT_CODE` when the message does not contain it.
+//!
+//! Outside ZisK the harness runs all the tests that are not ignored in the same process, so that
+//! the same test target can also be run natively with `cargo test`.
+
+use std::any::Any;
+
+/// A test function declared with `ziskos::tests!`
+pub struct TestCase {
+    pub name: &'static str,
+    pub test_fn: fn(),
+    /// The test passes only if it panics
+    pub should_panic: bool,
+    /// Text the panic message must contain, from `#[should_panic(expected = "..")]`
+    pub expected: Option<&'static str>,
+    /// The test is only run on request, from `#[ignore]`
+    pub ignore: bool,
+}
+
+impl TestCase {
+    pub const fn new(name: &'static str, test_fn: fn()) -> Self {
+        Self { name, test_fn, should_panic: false, expected: None, ignore: false }
+    }
+
+    /// Returns true if the test ended as expected, given its panic message if it panicked
+    pub fn passed(&self, panic_message: Option<&str>) -> bool {
+        match (panic_message, self.expected) {
+            (None, _) => !self.should_panic,
+            (Some(message), Some(expected)) => self.should_panic && message.contains(expected),
+            (Some(_), None) => self.should_panic,
+        }
+    }
+}
+
+/// Exit code of the program when the requested test does not exist
+pub const TEST_NOT_FOUND_EXIT_CODE: u32 = 2;
+
+/// Exit code of the program when a test panics without the expected message
+pub const PANIC_MISMATCH_EXIT_CODE: u32 = 3;
+
+/// Returns the message of a panic payload, as the standard panic hook does
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload.downcast_ref::<String>().map_or("Box<dyn Any>", String::as_str),
+    }
+}
+
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+pub fn run_tests(tests: &[TestCase]) {
+    let input = crate::read_input();
+    if input.is_empty() {
+        for test in tests {
+            let kind = if test.should_panic { "should_panic" } else { "test" };
+            println!("{}: {}{}", test.name, kind, if test.ignore { " ignored" } else { "" });
+        }
+        return;
+    }
+
+    let name = String::from_utf8_lossy(&input);
+    match tests.iter().find(|test| test.name == name) {
+        Some(test) => {
+            // A panic ends the program through the ziskos panic hook, with exit code 101, so
+            // the expected message is checked before
+            if let Some(expected) = test.expected {
+                std::panic::set_hook(Box::new(move |info| {
+                    if panic_message(info.payload()).contains(expected) {
+                        crate::panic_hook(&info.to_string());
+                    } else {
+                        eprintln!("{}\nPanic did not contain the expected {:?}", info, expected);
+                        crate::exit(PANIC_MISMATCH_EXIT_CODE);
+                    }
+                }));
+            }
+            (test.test_fn)()
+        }
+        None => {
+            eprintln!("Test {} not found", name);
+            crate::exit(TEST_NOT_FOUND_EXIT_CODE);
+        }
+    }
+}
+
+#[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+pub fn run_tests(tests: &[TestCase]) {
+    println!("\nrunning {} tests", tests.len());
+
+    let mut failed = Vec::new();
+    let mut ignored = 0;
+    for test in tests {
+        if test.ignore {
+            println!("test {} ... ignored", test.name);
+            ignored += 1;
+        } else if run_test(test) {
+            println!("test {} ... ok", test.name);
+        } else {
+            println!("test {} ... FAILED", test.name);
+            failed.push(test.name);
+        }
+    }
+
+    // The entry point is called as the C main function, whose return value would be the exit
+    // code, so the harness always ends the program
+    let passed = tests.len() - failed.len() - ignored;
+    if failed.is_empty() {
+        println!("\ntest result: ok. {} passed; 0 failed; {} ignored\n", passed, ignored);
+        crate::exit(0);
+    }
+    println!("\nfailures:");
+    for name in &failed {
+        println!("    {}", name);
+    }
+    println!(
+        "\ntest result: FAILED. {} passed; {} failed; {} ignored\n",
+        passed,
+        failed.len(),
+        ignored
+    );
+    crate::exit(101);
+}
+
+/// Runs a test in this process, returning true if it passed
+#[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+fn run_test(test: &TestCase) -> bool {
+    let result = std::panic::catch_unwind(test.test_fn);
+    test.passed(result.err().as_deref().map(panic_message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::tests! {
+        @collect []
+
+        #[test]
+        fn passes() {}
+
+        /// Documented test
+        #[test]
+        #[ignore]
+        fn ignored() {}
+
+        #[test]
+        #[should_panic]
+        fn panics() {
+            panic!("boom");
+        }
+
+        #[ignore = "slow"]
+        #[test]
+        #[should_panic(expected = "boom")]
+        /// Documented after the attributes
+        fn panics_with_message() {
+            panic!("{}", "boom!");
+        }
+
+        #[test]
+        #[should_panic = "bang"]
+        fn panics_with_other_message() {
+            panic!("boom");
+        }
+
+        #[test]
+        fn fails() {
+            panic!("boom");
+        }
+    }
+
+    #[test]
+    fn test_macro() {
+        let tests = zkvm_generated_tests();
+        let attrs: Vec<_> = tests
+            .iter()
+            .map(|test| (test.name, test.should_panic, test.expected, test.ignore))
+            .collect();
+        assert_eq!(
+            attrs,
+            [
+                ("passes", false, None, false),
+                ("ignored", false, None, true),
+                ("panics", true, None, false),
+                ("panics_with_message", true, Some("boom"), true),
+                ("panics_with_other_message", true, Some("bang"), false),
+                ("fails", false, None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_test() {
+        let results: Vec<_> =
+            zkvm_generated_tests().iter().map(|test| (test.name, run_test(test))).collect();
+        assert_eq!(
+            results,
+            [
+                ("passes", true),
+                ("ignored", true),
+                ("panics", true),
+                ("panics_with_message", true),
+                ("panics_with_other_message", false),
+                ("fails", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_passed() {
+        let mut test = TestCase::new("test", || {});
+        assert!(test.passed(None));
+        assert!(!test.passed(Some("boom")));
+
+        test.should_panic = true;
+        assert!(!test.passed(None));
+        assert!(test.passed(Some("boom")));
+
+        test.expected = Some("boom");
+        assert!(test.passed(Some("big boom!")));
+        assert!(!test.passed(Some("bang")));
+        assert!(!test.passed(None));
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(&"boom"), "boom");
+        assert_eq!(panic_message(&"boom".to_string()), "boom");
+        assert_eq!(panic_message(&1), "Box<dyn Any>");
+    }
+}
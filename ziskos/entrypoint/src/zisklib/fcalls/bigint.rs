@@ -0,0 +1,81 @@
+//! fcall_bigint_div and fcall_bigint_modinv free calls
+use cfg_if::cfg_if;
+cfg_if! {
+    if #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))] {
+        use core::arch::asm;
+        use crate::{ziskos_fcall, ziskos_fcall_get, ziskos_fcall_param};
+        use crate::{FCALL_BIGINT_DIV_ID, FCALL_BIGINT_MODINV_ID};
+    }
+}
+
+/// Computes the quotient `q` and remainder `r` of the division `a / m`, where all the values are
+/// little-endian arrays of `u64` limbs.
+///
+/// `q` must have the same number of limbs as `a`, and `r` the same number of limbs as `m`.
+///
+/// Note that this is a *free-input call*, meaning the Zisk VM does not automatically verify the correctness
+/// of the result. It is the caller's responsibility to ensure it.
+#[allow(unused_variables)]
+pub fn fcall_bigint_div(a: &[u64], m: &[u64], q: &mut [u64], r: &mut [u64]) {
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    {
+        let mut params = vec![a.len() as u64, m.len() as u64];
+        params.extend_from_slice(a);
+        params.extend_from_slice(m);
+        let mut results = vec![0u64; q.len() + r.len()];
+        crate::fcall_proxy(crate::FCALL_BIGINT_DIV_ID as u64, &params, &mut results);
+        q.copy_from_slice(&results[..q.len()]);
+        r.copy_from_slice(&results[q.len()..]);
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!();
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    {
+        ziskos_fcall_param!(a.len(), 1);
+        ziskos_fcall_param!(m.len(), 1);
+        for &limb in a.iter().chain(m.iter()) {
+            ziskos_fcall_param!(limb, 1);
+        }
+        ziskos_fcall!(FCALL_BIGINT_DIV_ID);
+        for limb in q.iter_mut().chain(r.iter_mut()) {
+            *limb = ziskos_fcall_get();
+        }
+    }
+}
+
+/// Computes the multiplicative inverse of `a` modulo `m`, where both values are little-endian
+/// arrays of `u64` limbs of the same length.
+///
+/// If the inverse exists, it is written into `x` and `true` is returned. Otherwise, `gcd(a, m)`
+/// is written into `x` and `false` is returned.
+///
+/// Note that this is a *free-input call*, meaning the Zisk VM does not automatically verify the correctness
+/// of the result. It is the caller's responsibility to ensure it.
+#[allow(unused_variables)]
+pub fn fcall_bigint_modinv(a: &[u64], m: &[u64], x: &mut [u64]) -> bool {
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+    {
+        let mut params = vec![a.len() as u64];
+        params.extend_from_slice(a);
+        params.extend_from_slice(m);
+        let mut results = vec![0u64; 1 + x.len()];
+        crate::fcall_proxy(crate::FCALL_BIGINT_MODINV_ID as u64, &params, &mut results);
+        x.copy_from_slice(&results[1..]);
+        results[0] == 1
+    }
+    #[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), not(test)))]
+    unreachable!();
+    #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+    {
+        ziskos_fcall_param!(a.len(), 1);
+        for &limb in a.iter().chain(m.iter()) {
+            ziskos_fcall_param!(limb, 1);
+        }
+        ziskos_fcall!(FCALL_BIGINT_MODINV_ID);
+        let exists = ziskos_fcall_get() == 1;
+        for limb in x.iter_mut() {
+            *limb = ziskos_fcall_get();
+        }
+        exists
+    }
+}
@@ -0,0 +1,278 @@
+//! User-defined free calls, served by functions registered by the host.
+//!
+//! A guest declares a user fcall with `ziskos_user_fcall!`, giving it an id in the range
+//! `FCALL_USER_ID_FIRST..=FCALL_USER_ID_LAST`, and the host registers a function with the same id
+//! (see `UserFcalls` in `zisk-core`).  Parameters and results are typed: they are encoded as `u64`
+//! words with `FcallEncode` on one side and decoded with `FcallDecode` on the other one, so a guest
+//! parameter list `(a: u64, b: &[u64])` is received by the host as a `(u64, Vec<u64>)` tuple.
+//!
+//! ```ignore
+//! ziskos_user_fcall! {
+//!     /// Returns the permutation that sorts `values`
+//!     pub fn fcall_sort_permutation(values: &[u64]) -> Vec<u64> = 512;
+//! }
+//! ```
+//!
+//! Note that, as every free-input call, the Zisk VM does not verify the correctness of the results;
+//! it is the caller's responsibility to check them.
+
+/// First fcall id reserved to user-defined fcalls
+pub const FCALL_USER_ID_FIRST: u16 = 512;
+/// Last fcall id reserved to user-defined fcalls
+pub const FCALL_USER_ID_LAST: u16 = 1023;
+
+/// Returns true if `id` is in the range reserved to user-defined fcalls
+pub const fn is_user_fcall_id(id: u64) -> bool {
+    id >= FCALL_USER_ID_FIRST as u64 && id <= FCALL_USER_ID_LAST as u64
+}
+
+/// A value that can be sent through an fcall, as a sequence of `u64` words
+pub trait FcallEncode {
+    /// Calls `write` with every word of the value
+    fn encode(&self, write: &mut impl FnMut(u64));
+}
+
+/// A value that can be received through an fcall, as a sequence of `u64` words
+pub trait FcallDecode: Sized {
+    /// Reads the value from `words`, returning `None` if there are not enough of them
+    fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self>;
+}
+
+macro_rules! impl_fcall_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FcallEncode for $ty {
+                fn encode(&self, write: &mut impl FnMut(u64)) {
+                    write(*self as u64);
+                }
+            }
+
+            impl FcallDecode for $ty {
+                fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+                    words.next().map(|word| word as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_fcall_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl FcallEncode for bool {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        write(*self as u64);
+    }
+}
+
+impl FcallDecode for bool {
+    fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        words.next().map(|word| word != 0)
+    }
+}
+
+impl FcallEncode for () {
+    fn encode(&self, _write: &mut impl FnMut(u64)) {}
+}
+
+impl FcallDecode for () {
+    fn decode(_words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        Some(())
+    }
+}
+
+// Fixed-size arrays are encoded without their length
+impl<T: FcallEncode, const N: usize> FcallEncode for [T; N] {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        self.iter().for_each(|item| item.encode(write));
+    }
+}
+
+impl<T: FcallDecode, const N: usize> FcallDecode for [T; N] {
+    fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        let items: Vec<T> = (0..N).map(|_| T::decode(words)).collect::<Option<_>>()?;
+        items.try_into().ok()
+    }
+}
+
+// Slices and vectors are encoded as their length followed by their items
+impl<T: FcallEncode> FcallEncode for [T] {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        write(self.len() as u64);
+        self.iter().for_each(|item| item.encode(write));
+    }
+}
+
+impl<T: FcallEncode + ?Sized> FcallEncode for &T {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        (**self).encode(write);
+    }
+}
+
+impl<T: FcallEncode> FcallEncode for Vec<T> {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        self.as_slice().encode(write);
+    }
+}
+
+impl<T: FcallDecode> FcallDecode for Vec<T> {
+    fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        let len = words.next()?;
+        (0..len).map(|_| T::decode(words)).collect()
+    }
+}
+
+impl<T: FcallEncode> FcallEncode for Option<T> {
+    fn encode(&self, write: &mut impl FnMut(u64)) {
+        match self {
+            Some(value) => {
+                write(1);
+                value.encode(write);
+            }
+            None => write(0),
+        }
+    }
+}
+
+impl<T: FcallDecode> FcallDecode for Option<T> {
+    fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        match words.next()? {
+            0 => Some(None),
+            _ => T::decode(words).map(Some),
+        }
+    }
+}
+
+macro_rules! impl_fcall_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: FcallEncode),+> FcallEncode for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, write: &mut impl FnMut(u64)) {
+                let ($($name,)+) = self;
+                $($name.encode(write);)+
+            }
+        }
+
+        impl<$($name: FcallDecode),+> FcallDecode for ($($name,)+) {
+            fn decode(words: &mut impl Iterator<Item = u64>) -> Option<Self> {
+                Some(($($name::decode(words)?,)+))
+            }
+        }
+    };
+}
+
+impl_fcall_tuple!(A);
+impl_fcall_tuple!(A, B);
+impl_fcall_tuple!(A, B, C);
+impl_fcall_tuple!(A, B, C, D);
+impl_fcall_tuple!(A, B, C, D, E);
+impl_fcall_tuple!(A, B, C, D, E, F);
+
+/// Declares a guest function that calls a user-defined fcall, encoding its parameters and decoding
+/// its result.  The host receives the parameters as a tuple, e.g. `(u64, Vec<u64>)` for
+/// `(a: u64, b: &[u64])`, and the fcall id must be in `FCALL_USER_ID_FIRST..=FCALL_USER_ID_LAST`.
+#[macro_export]
+macro_rules! ziskos_user_fcall {
+    ($(#[$meta:meta])* $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty = $id:expr;) => {
+        $(#[$meta])*
+        #[allow(unused_variables, unexpected_cfgs)]
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            const _: () = assert!(
+                $crate::is_user_fcall_id($id as u64),
+                "user fcall id must be in FCALL_USER_ID_FIRST..=FCALL_USER_ID_LAST"
+            );
+            #[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
+            unreachable!();
+            #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+            {
+                use ::core::arch::asm;
+                $($crate::FcallEncode::encode(&$arg, &mut |word: u64| {
+                    $crate::ziskos_fcall_param!(word, 1);
+                });)*
+                $crate::ziskos_fcall!($id);
+                <$ret as $crate::FcallDecode>::decode(&mut ::core::iter::from_fn(|| {
+                    Some($crate::ziskos_fcall_get())
+                }))
+                .unwrap()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Debug;
+
+    fn encode<T: FcallEncode>(value: &T) -> Vec<u64> {
+        let mut words = Vec::new();
+        value.encode(&mut |word| words.push(word));
+        words
+    }
+
+    /// Checks that `value` is encoded as `words` and decoded back from them, using every word
+    fn check_round_trip<T: FcallEncode + FcallDecode + PartialEq + Debug>(value: T, words: &[u64]) {
+        assert_eq!(encode(&value), words);
+        let mut iter = words.iter().copied();
+        assert_eq!(T::decode(&mut iter), Some(value));
+        assert_eq!(iter.next(), None);
+
+        // Every word is needed
+        if let Some((_, words)) = words.split_last() {
+            assert_eq!(T::decode(&mut words.iter().copied()), None);
+        }
+    }
+
+    #[test]
+    fn test_ints_round_trip() {
+        check_round_trip(7u8, &[7]);
+        check_round_trip(u64::MAX, &[u64::MAX]);
+        check_round_trip(-1i32, &[u64::MAX]);
+        check_round_trip(true, &[1]);
+        check_round_trip(false, &[0]);
+        check_round_trip((), &[]);
+    }
+
+    #[test]
+    fn test_tuples_round_trip() {
+        check_round_trip((5u64,), &[5]);
+        check_round_trip((1u64, vec![2u64, 3]), &[1, 2, 2, 3]);
+        check_round_trip((1u8, 2u16, 3u32, 4u64, -5i64, true), &[1, 2, 3, 4, -5i64 as u64, 1]);
+    }
+
+    #[test]
+    fn test_vec_round_trip() {
+        check_round_trip(Vec::<u64>::new(), &[0]);
+        check_round_trip(vec![4u64, 5, 6], &[3, 4, 5, 6]);
+        check_round_trip(vec![vec![1u64], vec![]], &[2, 1, 1, 0]);
+
+        // Slices are encoded as vectors
+        assert_eq!(encode(&&[4u64, 5, 6][..]), [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_option_round_trip() {
+        check_round_trip(None::<u64>, &[0]);
+        check_round_trip(Some(9u64), &[1, 9]);
+        check_round_trip(Some(vec![1u64, 2]), &[1, 2, 1, 2]);
+
+        // Any nonzero tag is `Some`
+        assert_eq!(Option::<u64>::decode(&mut [2, 9].into_iter()), Some(Some(9)));
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        // Arrays are encoded without their length
+        check_round_trip([1u64, 2, 3], &[1, 2, 3]);
+        check_round_trip([[1u64, 2], [3, 4]], &[1, 2, 3, 4]);
+        check_round_trip([Some(1u64), None], &[1, 1, 0]);
+        check_round_trip([0u64; 0], &[]);
+    }
+
+    #[test]
+    fn test_is_user_fcall_id() {
+        assert!(!is_user_fcall_id(FCALL_USER_ID_FIRST as u64 - 1));
+        assert!(is_user_fcall_id(FCALL_USER_ID_FIRST as u64));
+        assert!(is_user_fcall_id(FCALL_USER_ID_LAST as u64));
+        assert!(!is_user_fcall_id(FCALL_USER_ID_LAST as u64 + 1));
+    }
+}
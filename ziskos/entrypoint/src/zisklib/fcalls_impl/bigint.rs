@@ -0,0 +1,162 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Zero};
+
+/// Computes the quotient and remainder of the division `a / m` of two little-endian multi-limb
+/// unsigned integers.
+///
+/// Parameters: `[len_a, len_m, a[0..len_a], m[0..len_m]]`
+/// Results: `[q[0..len_a], r[0..len_m]]`
+pub fn fcall_bigint_div(parameters: &[u64], results: &mut [u64]) -> i64 {
+    let len_a = parameters[0] as usize;
+    let len_m = parameters[1] as usize;
+    let a = &parameters[2..2 + len_a];
+    let m = &parameters[2 + len_a..2 + len_a + len_m];
+
+    let (q, r) = bigint_div(a, m);
+    to_limbs(&q, &mut results[..len_a]);
+    to_limbs(&r, &mut results[len_a..len_a + len_m]);
+    (len_a + len_m) as i64
+}
+
+/// Computes the multiplicative inverse of `a` modulo `m`, both `len` limbs long.
+///
+/// Parameters: `[len, a[0..len], m[0..len]]`
+/// Results: `[1, inv[0..len]]` if the inverse exists, otherwise `[0, gcd(a, m)[0..len]]`
+pub fn fcall_bigint_modinv(parameters: &[u64], results: &mut [u64]) -> i64 {
+    let len = parameters[0] as usize;
+    let a = &parameters[1..1 + len];
+    let m = &parameters[1 + len..1 + 2 * len];
+
+    match bigint_modinv(a, m) {
+        Ok(inv) => {
+            results[0] = 1;
+            to_limbs(&inv, &mut results[1..1 + len]);
+        }
+        Err(gcd) => {
+            results[0] = 0;
+            to_limbs(&gcd, &mut results[1..1 + len]);
+        }
+    }
+    (1 + len) as i64
+}
+
+fn bigint_div(a: &[u64], m: &[u64]) -> (BigUint, BigUint) {
+    let a = from_limbs(a);
+    let m = from_limbs(m);
+    if m.is_zero() {
+        panic!("Invalid input: division by zero");
+    }
+    (&a / &m, &a % &m)
+}
+
+/// Returns the inverse of `a` modulo `m`, or `gcd(a, m)` when it does not exist
+fn bigint_modinv(a: &[u64], m: &[u64]) -> Result<BigUint, BigUint> {
+    let m = BigInt::from_biguint(Sign::Plus, from_limbs(m));
+    if m.is_zero() {
+        panic!("Invalid input: modulus is zero");
+    }
+    let a = BigInt::from_biguint(Sign::Plus, from_limbs(a)) % &m;
+
+    // Extended Euclidean algorithm, keeping only the coefficients of a
+    let (mut old_r, mut r) = (a, m.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+        let new_r = &old_r - &quotient * &r;
+        old_r = std::mem::replace(&mut r, new_r);
+        let new_s = &old_s - &quotient * &s;
+        old_s = std::mem::replace(&mut s, new_s);
+    }
+
+    let gcd = old_r.magnitude().clone();
+    if !gcd.is_one() {
+        return Err(gcd);
+    }
+    let inv = ((old_s % &m) + &m) % &m;
+    Ok(inv.magnitude().clone())
+}
+
+fn from_limbs(limbs: &[u64]) -> BigUint {
+    let digits: Vec<u32> = limbs.iter().flat_map(|&l| [l as u32, (l >> 32) as u32]).collect();
+    BigUint::new(digits)
+}
+
+fn to_limbs(value: &BigUint, limbs: &mut [u64]) {
+    let digits = value.to_u64_digits();
+    assert!(digits.len() <= limbs.len(), "Result does not fit in {} limbs", limbs.len());
+    limbs.fill(0);
+    limbs[..digits.len()].copy_from_slice(&digits);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn random_limbs(rng: &mut StdRng, len: usize) -> Vec<u64> {
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_bigint_div() {
+        let mut rng = StdRng::seed_from_u64(0x26);
+        for bits in [256, 512, 1024, 2048, 4096] {
+            let len_m = bits / 64;
+            for _ in 0..8 {
+                let a = random_limbs(&mut rng, 2 * len_m);
+                let mut m = random_limbs(&mut rng, len_m);
+                // Also exercise moduli with leading zero limbs
+                let zeros = rng.gen_range(0..len_m);
+                m[len_m - zeros..].fill(0);
+
+                let mut params = vec![a.len() as u64, m.len() as u64];
+                params.extend(&a);
+                params.extend(&m);
+                let mut results = vec![0u64; a.len() + m.len()];
+                let size = fcall_bigint_div(&params, &mut results);
+                assert_eq!(size as usize, a.len() + m.len());
+
+                let q = from_limbs(&results[..a.len()]);
+                let r = from_limbs(&results[a.len()..]);
+                let (a, m) = (from_limbs(&a), from_limbs(&m));
+                assert!(r < m);
+                assert_eq!(q * &m + r, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bigint_modinv() {
+        let mut rng = StdRng::seed_from_u64(0x27);
+        for bits in [256, 512, 1024, 2048, 4096] {
+            let len = bits / 64;
+            for _ in 0..4 {
+                let a = random_limbs(&mut rng, len);
+                let mut m = random_limbs(&mut rng, len);
+                m[0] |= 1;
+
+                let mut params = vec![len as u64];
+                params.extend(&a);
+                params.extend(&m);
+                let mut results = vec![0u64; 1 + len];
+                fcall_bigint_modinv(&params, &mut results);
+
+                let x = from_limbs(&results[1..]);
+                let (a, m) = (from_limbs(&a), from_limbs(&m));
+                if results[0] == 1 {
+                    assert!(x < m);
+                    assert!((a * x % m).is_one());
+                } else {
+                    assert!(!x.is_one());
+                    assert!((&a % &x).is_zero() && (&m % &x).is_zero());
+                }
+            }
+        }
+
+        // Even modulus and even value never have an inverse
+        let params = [4, 6, 0, 0, 0, 10, 0, 0, 0];
+        let mut results = [0u64; 5];
+        fcall_bigint_modinv(&params, &mut results);
+        assert_eq!(results, [0, 2, 0, 0, 0]);
+    }
+}
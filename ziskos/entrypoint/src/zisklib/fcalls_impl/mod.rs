@@ -1,3 +1,4 @@
+mod bigint;
 mod msb_pos_256;
 mod proxy;
 mod secp256k1_fn_inv;
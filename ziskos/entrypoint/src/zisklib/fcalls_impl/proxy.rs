@@ -1,7 +1,9 @@
-use super::{msb_pos_256::*, secp256k1_fn_inv::*, secp256k1_fp_inv::*, secp256k1_fp_sqrt::*};
+use super::{
+    bigint::*, msb_pos_256::*, secp256k1_fn_inv::*, secp256k1_fp_inv::*, secp256k1_fp_sqrt::*,
+};
 use crate::{
-    FCALL_MSB_POS_256_ID, FCALL_SECP256K1_FN_INV_ID, FCALL_SECP256K1_FP_INV_ID,
-    FCALL_SECP256K1_FP_SQRT_ID,
+    FCALL_BIGINT_DIV_ID, FCALL_BIGINT_MODINV_ID, FCALL_MSB_POS_256_ID, FCALL_SECP256K1_FN_INV_ID,
+    FCALL_SECP256K1_FP_INV_ID, FCALL_SECP256K1_FP_SQRT_ID,
 };
 
 pub fn fcall_proxy(id: u64, params: &[u64], results: &mut [u64]) -> i64 {
@@ -10,6 +12,8 @@ pub fn fcall_proxy(id: u64, params: &[u64], results: &mut [u64]) -> i64 {
         FCALL_SECP256K1_FP_INV_ID => secp256k1_fp_inv(params, results),
         FCALL_SECP256K1_FP_SQRT_ID => secp256k1_fp_sqrt(params, results),
         FCALL_MSB_POS_256_ID => fcall_msb_pos_256(params, results),
+        FCALL_BIGINT_DIV_ID => fcall_bigint_div(params, results),
+        FCALL_BIGINT_MODINV_ID => fcall_bigint_modinv(params, results),
         _ => panic!("Unsupported fcall ID {}", id),
     }
 }
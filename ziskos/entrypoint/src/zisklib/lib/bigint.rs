@@ -0,0 +1,358 @@
+//! Arbitrary-width big integer arithmetic composed from the `Arith256` and `Arith256Mod` precompiles.
+//!
+//! Big integers are little-endian arrays of `u64` limbs whose length is a multiple of 4, so that
+//! they can be processed in 256-bit digits. Moduli from 256 to 4096 bits are supported.
+//!
+//! Quotients and inverses are obtained through free-input calls and verified here, so the results
+//! are sound regardless of the hints provided by the host.
+//! Hint results have no size limit, but the guest reads them one word per step, so every hint costs
+//! as many steps as limbs it returns, e.g. 192 steps for the quotient and remainder that reduce a
+//! 4096-bit modular product.
+
+use crate::{
+    arith256::{syscall_arith256, SyscallArith256Params},
+    arith256_mod::{syscall_arith256_mod, SyscallArith256ModParams},
+    fcall_bigint_div, fcall_bigint_modinv,
+};
+
+/// Maximum number of limbs of a modulus (4096 bits)
+pub const BIGINT_MAX_LIMBS: usize = 64;
+
+const ZERO_256: [u64; 4] = [0, 0, 0, 0];
+
+/// Given a big-endian byte array, returns its little-endian limbs padded to a multiple of 256 bits
+pub fn bigint_from_be_bytes(bytes: &[u8]) -> Vec<u64> {
+    let n_limbs = bytes.len().div_ceil(32).max(1) * 4;
+    let mut limbs = vec![0u64; n_limbs];
+    for (i, &byte) in bytes.iter().rev().enumerate() {
+        limbs[i / 8] |= (byte as u64) << (8 * (i % 8));
+    }
+    limbs
+}
+
+/// Given little-endian limbs, returns their big-endian byte representation with `len` bytes,
+/// truncating the most significant bytes if needed
+pub fn bigint_to_be_bytes(limbs: &[u64], len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    for (i, byte) in bytes.iter_mut().rev().enumerate() {
+        if i / 8 < limbs.len() {
+            *byte = (limbs[i / 8] >> (8 * (i % 8))) as u8;
+        }
+    }
+    bytes
+}
+
+/// Given two big integers `a` and `b`, computes their full product `a * b`,
+/// with `a.len() + b.len()` limbs
+pub fn bigint_mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    assert_digits(a);
+    assert_digits(b);
+
+    let mut result = vec![0u64; a.len() + b.len()];
+    for (i, ai) in a.chunks_exact(4).enumerate() {
+        let ai: &[u64; 4] = ai.try_into().unwrap();
+        if *ai == ZERO_256 {
+            continue;
+        }
+
+        let mut carry = ZERO_256;
+        for (j, bj) in b.chunks_exact(4).enumerate() {
+            let bj: &[u64; 4] = bj.try_into().unwrap();
+
+            // dh | dl = ai * bj + carry
+            let mut dl = ZERO_256;
+            let mut dh = ZERO_256;
+            let mut params =
+                SyscallArith256Params { a: ai, b: bj, c: &carry, dl: &mut dl, dh: &mut dh };
+            syscall_arith256(&mut params);
+
+            // result[i + j] += dl, propagating the carry bit into dh, which cannot overflow
+            // since ai * bj + carry <= 2^512 - 2^256
+            let offset = 4 * (i + j);
+            let bit = add_assign(&mut result[offset..offset + 4], &dl);
+            add_small(&mut dh, bit);
+            carry = dh;
+        }
+        let offset = 4 * (i + b.len() / 4);
+        result[offset..offset + 4].copy_from_slice(&carry);
+    }
+    result
+}
+
+/// Given a big integer `x` and a modulus `m`, computes `x mod m`, with `m.len()` limbs
+pub fn bigint_rem(x: &[u64], m: &[u64]) -> Vec<u64> {
+    assert_modulus(m);
+    assert_digits(x);
+
+    // Obtain the hint
+    let mut q = vec![0u64; x.len()];
+    let mut r = vec![0u64; m.len()];
+    fcall_bigint_div(x, m, &mut q, &mut r);
+
+    // Verify that r < m and x == q * m + r
+    assert!(lt(&r, m), "bigint_rem: remainder is not reduced");
+    let q = trim_digits(&q);
+    let mut qm = bigint_mul(q, m);
+    if add_assign(&mut qm, &r) != 0 || !eq_padded(&qm, x) {
+        panic!("bigint_rem: invalid quotient and remainder");
+    }
+    r
+}
+
+/// Given two big integers `a` and `b` and a modulus `m`, computes `(a * b) mod m`,
+/// with `m.len()` limbs
+pub fn bigint_modmul(a: &[u64], b: &[u64], m: &[u64]) -> Vec<u64> {
+    assert_modulus(m);
+
+    if m.len() == 4 && a.len() == 4 && b.len() == 4 {
+        let mut d = [0u64; 4];
+        let mut params = SyscallArith256ModParams {
+            a: a.try_into().unwrap(),
+            b: b.try_into().unwrap(),
+            c: &ZERO_256,
+            module: m.try_into().unwrap(),
+            d: &mut d,
+        };
+        syscall_arith256_mod(&mut params);
+        return d.to_vec();
+    }
+
+    bigint_rem(&bigint_mul(a, b), m)
+}
+
+/// Given a base `base`, an exponent `exp` and a modulus `m`, computes `base^exp mod m`,
+/// with `m.len()` limbs. The exponent can have any number of limbs.
+pub fn bigint_modexp(base: &[u64], exp: &[u64], m: &[u64]) -> Vec<u64> {
+    assert_modulus(m);
+
+    // Reduce the base, so that all intermediate values have the width of the modulus
+    let base =
+        if base.len() == m.len() && lt(base, m) { base.to_vec() } else { bigint_rem(base, m) };
+
+    let mut one = vec![0u64; m.len()];
+    one[0] = 1;
+    let mut result = if lt(&one, m) { one } else { vec![0u64; m.len()] };
+
+    // Left-to-right square-and-multiply
+    let mut started = false;
+    for i in (0..exp.len() * 64).rev() {
+        let bit = (exp[i / 64] >> (i % 64)) & 1;
+        if started {
+            result = bigint_modmul(&result, &result, m);
+        }
+        if bit == 1 {
+            result = if started { bigint_modmul(&result, &base, m) } else { base.clone() };
+            started = true;
+        }
+    }
+    result
+}
+
+/// Given a big integer `a` and a modulus `m`, computes the multiplicative inverse of `a` modulo `m`.
+/// Returns `None` if `a` is not invertible modulo `m`.
+pub fn bigint_modinv(a: &[u64], m: &[u64]) -> Option<Vec<u64>> {
+    assert_modulus(m);
+
+    // Modulo 1 every value is congruent to 0, which has no inverse
+    if m[0] == 1 && m[1..].iter().all(|&l| l == 0) {
+        return None;
+    }
+
+    let a = if a.len() == m.len() && lt(a, m) { a.to_vec() } else { bigint_rem(a, m) };
+
+    // Obtain the hint
+    let mut x = vec![0u64; m.len()];
+    if fcall_bigint_modinv(&a, m, &mut x) {
+        // Verify that x < m and a * x == 1 mod m
+        let ax = bigint_modmul(&a, &x, m);
+        let ok = lt(&x, m) && ax[0] == 1 && ax[1..].iter().all(|&l| l == 0);
+        assert!(ok, "bigint_modinv: invalid inverse");
+        Some(x)
+    } else {
+        // Verify that x = gcd(a, m) is a common divisor greater than 1, so no inverse exists
+        let gt_one = x[0] > 1 || x[1..].iter().any(|&l| l != 0);
+        let ok = gt_one && is_zero(&bigint_rem(&a, &x)) && is_zero(&bigint_rem(m, &x));
+        assert!(ok, "bigint_modinv: invalid non-invertibility witness");
+        None
+    }
+}
+
+fn assert_digits(x: &[u64]) {
+    assert!(!x.is_empty() && x.len() % 4 == 0, "bigint: length must be a multiple of 4 limbs");
+}
+
+fn assert_modulus(m: &[u64]) {
+    assert_digits(m);
+    assert!(m.len() <= BIGINT_MAX_LIMBS, "bigint: modulus wider than 4096 bits");
+    assert!(!is_zero(m), "bigint: modulus is zero");
+}
+
+/// Removes the most significant zero digits, keeping at least one digit
+fn trim_digits(x: &[u64]) -> &[u64] {
+    let mut len = x.len();
+    while len > 4 && x[len - 4..len] == ZERO_256 {
+        len -= 4;
+    }
+    &x[..len]
+}
+
+fn is_zero(x: &[u64]) -> bool {
+    x.iter().all(|&l| l == 0)
+}
+
+/// Returns true if `x < y`, both with the same number of limbs
+fn lt(x: &[u64], y: &[u64]) -> bool {
+    for i in (0..x.len()).rev() {
+        if x[i] != y[i] {
+            return x[i] < y[i];
+        }
+    }
+    false
+}
+
+/// Returns true if `x == y`, treating missing limbs as zeros
+fn eq_padded(x: &[u64], y: &[u64]) -> bool {
+    let len = x.len().max(y.len());
+    (0..len).all(|i| x.get(i).copied().unwrap_or(0) == y.get(i).copied().unwrap_or(0))
+}
+
+/// Computes `x += y` over the first `y.len()` limbs of `x`, propagating the carry through the
+/// remaining limbs of `x`, and returns the final carry
+fn add_assign(x: &mut [u64], y: &[u64]) -> u64 {
+    let mut carry = 0u64;
+    for i in 0..x.len() {
+        let yi = if i < y.len() {
+            y[i]
+        } else if carry == 0 {
+            break;
+        } else {
+            0
+        };
+        let (s1, c1) = x[i].overflowing_add(yi);
+        let (s2, c2) = s1.overflowing_add(carry);
+        x[i] = s2;
+        carry = (c1 | c2) as u64;
+    }
+    carry
+}
+
+/// Computes `x += bit` over a 256-bit value that is known not to overflow
+fn add_small(x: &mut [u64; 4], bit: u64) {
+    let mut carry = bit;
+    for limb in x.iter_mut() {
+        if carry == 0 {
+            break;
+        }
+        let (s, c) = limb.overflowing_add(carry);
+        *limb = s;
+        carry = c as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const BITS: [usize; 4] = [256, 1024, 2048, 4096];
+
+    fn random_limbs(rng: &mut StdRng, len: usize) -> Vec<u64> {
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    /// Random modulus of `len` limbs, odd and with its top limb set
+    fn random_modulus(rng: &mut StdRng, len: usize) -> Vec<u64> {
+        let mut m = random_limbs(rng, len);
+        m[0] |= 1;
+        m[len - 1] |= 1 << 63;
+        m
+    }
+
+    fn to_biguint(limbs: &[u64]) -> BigUint {
+        BigUint::from_bytes_be(&bigint_to_be_bytes(limbs, 8 * limbs.len()))
+    }
+
+    fn from_biguint(value: &BigUint, len: usize) -> Vec<u64> {
+        let mut limbs = value.to_u64_digits();
+        limbs.resize(len, 0);
+        limbs
+    }
+
+    #[test]
+    fn test_be_bytes_roundtrip() {
+        let bytes: Vec<u8> = (1..=40).collect();
+        let limbs = bigint_from_be_bytes(&bytes);
+        assert_eq!(limbs.len(), 8);
+        assert_eq!(to_biguint(&limbs), BigUint::from_bytes_be(&bytes));
+        assert_eq!(bigint_to_be_bytes(&limbs, bytes.len()), bytes);
+        assert_eq!(bigint_from_be_bytes(&[]), vec![0; 4]);
+    }
+
+    #[test]
+    fn test_bigint_modmul() {
+        let mut rng = StdRng::seed_from_u64(0x26);
+        for bits in BITS {
+            let len = bits / 64;
+            for _ in 0..4 {
+                let m = random_modulus(&mut rng, len);
+                let a = random_limbs(&mut rng, len);
+                // Also cover operands wider than the modulus
+                let b = random_limbs(&mut rng, 2 * len);
+
+                let expected = to_biguint(&a) * to_biguint(&b) % to_biguint(&m);
+                assert_eq!(bigint_modmul(&a, &b, &m), from_biguint(&expected, len), "{bits} bits");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bigint_modexp() {
+        let mut rng = StdRng::seed_from_u64(0x126);
+        for bits in BITS {
+            let len = bits / 64;
+            let m = random_modulus(&mut rng, len);
+            let base = random_limbs(&mut rng, len);
+            // Full-width exponents for the narrow moduli, and 256-bit ones for the wide moduli
+            let exp = random_limbs(&mut rng, if bits <= 1024 { len } else { 4 });
+
+            let expected = to_biguint(&base).modpow(&to_biguint(&exp), &to_biguint(&m));
+            assert_eq!(bigint_modexp(&base, &exp, &m), from_biguint(&expected, len), "{bits} bits");
+        }
+
+        // Corner cases: zero exponent, and modulus 1
+        let m = random_modulus(&mut rng, 4);
+        let base = random_limbs(&mut rng, 4);
+        assert_eq!(bigint_modexp(&base, &[0; 4], &m), vec![1, 0, 0, 0]);
+        assert_eq!(bigint_modexp(&base, &[3, 0, 0, 0], &[1, 0, 0, 0]), vec![0; 4]);
+    }
+
+    #[test]
+    fn test_bigint_modinv() {
+        let mut rng = StdRng::seed_from_u64(0x226);
+        for bits in BITS {
+            let len = bits / 64;
+            for _ in 0..4 {
+                let m = random_modulus(&mut rng, len);
+                let a = random_limbs(&mut rng, len);
+
+                let (a_big, m_big) = (to_biguint(&a), to_biguint(&m));
+                match a_big.modinv(&m_big) {
+                    Some(expected) => {
+                        assert_eq!(bigint_modinv(&a, &m), Some(from_biguint(&expected, len)))
+                    }
+                    None => assert_eq!(bigint_modinv(&a, &m), None),
+                }
+            }
+
+            // Values sharing a factor with the modulus are not invertible
+            let mut m = random_modulus(&mut rng, len);
+            m[0] &= !1;
+            let mut a = random_limbs(&mut rng, len);
+            a[0] &= !1;
+            assert_eq!(bigint_modinv(&a, &m), None, "{bits} bits");
+        }
+
+        assert_eq!(bigint_modinv(&[3, 0, 0, 0], &[1, 0, 0, 0]), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! BLAKE2b compression function `F` (RFC 7693, EIP-152) composed from the `Blake2br` precompile.
+//!
+//! The working vector initialization and the final feed-forward are computed here, whereas every
+//! round is delegated to the precompile.
+
+use crate::blake2br::syscall_blake2b_round;
+
+/// BLAKE2b initialization vector
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// BLAKE2b message schedule, the round `r` uses the permutation `SIGMA[r % 10]`
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Applies the BLAKE2b compression function `F` with the given number of `rounds` to the state `h`,
+/// using the message block `m`, the offset counters `t` and the final block indicator `f`, as
+/// specified in EIP-152. The result is stored in `h`.
+///
+/// BLAKE2b itself uses 12 rounds, but any number of rounds is supported.
+pub fn blake2b_compress(rounds: u32, h: &mut [u64; 8], m: &[u64; 16], t: [u64; 2], f: bool) {
+    // Layout expected by the precompile: the working vector v followed by the permuted message
+    let mut data = [0u64; 32];
+
+    // Initialize the working vector
+    data[..8].copy_from_slice(h);
+    data[8..16].copy_from_slice(&IV);
+    data[12] ^= t[0];
+    data[13] ^= t[1];
+    if f {
+        data[14] = !data[14];
+    }
+
+    for round in 0..rounds as usize {
+        let sigma = &SIGMA[round % 10];
+        for (word, &idx) in data[16..].iter_mut().zip(sigma.iter()) {
+            *word = m[idx];
+        }
+        syscall_blake2b_round(&mut data);
+    }
+
+    // Feed-forward the working vector into the state
+    for (i, word) in h.iter_mut().enumerate() {
+        *word ^= data[i] ^ data[i + 8];
+    }
+}
@@ -0,0 +1,548 @@
+//! Ed25519 signature verification (RFC 8032) composed from the `Curve25519Mod`, `Ed25519Add` and
+//! `Ed25519Dbl` precompiles.
+
+use crate::{
+    curve25519_mod::{syscall_curve25519_mod, SyscallCurve25519ModParams},
+    ed25519_add::{syscall_ed25519_add, SyscallEd25519AddParams},
+    ed25519_dbl::syscall_ed25519_dbl,
+    point256::SyscallPoint256,
+};
+
+use super::{bigint_modinv, bigint_rem, gt, sub};
+
+/// Curve25519 base field size, 2^255 - 19
+const P: [u64; 4] =
+    [0xFFFFFFFFFFFFFFED, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x7FFFFFFFFFFFFFFF];
+
+/// (P + 3) / 8, the exponent used to compute square roots
+const P_PLUS_3_DIV_8: [u64; 4] =
+    [0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x0FFFFFFFFFFFFFFF];
+
+/// Square root of -1 in the base field
+const SQRT_M1: [u64; 4] =
+    [0xC4EE1B274A0EA0B0, 0x2F431806AD2FE478, 0x2B4D00993DFBD7A7, 0x2B8324804FC1DF0B];
+
+/// Ed25519 curve parameter d = -121665/121666
+const D: [u64; 4] =
+    [0x75EB4DCA135978A3, 0x00700A4D4141D8AB, 0x8CC740797779E898, 0x52036CEE2B6FFE73];
+
+/// Ed25519 group order, 2^252 + 27742317777372353535851937790883648493
+const L: [u64; 4] =
+    [0x5812631A5CF5D3ED, 0x14DEF9DEA2F79CD6, 0x0000000000000000, 0x1000000000000000];
+
+/// Ed25519 group of points generator
+const B_X: [u64; 4] =
+    [0xC9562D608F25D51A, 0x692CC7609525A7B2, 0xC0A4E231FDD6DC5C, 0x216936D3CD6E53FE];
+const B_Y: [u64; 4] =
+    [0x6666666666666658, 0x6666666666666666, 0x6666666666666666, 0x6666666666666666];
+
+const ZERO: [u64; 4] = [0, 0, 0, 0];
+const ONE: [u64; 4] = [1, 0, 0, 0];
+
+/// Verifies an Ed25519 signature `sig` of the message `msg` under the public key `pk`, following
+/// RFC 8032. It returns true if the signature is valid; otherwise, false.
+///
+/// The check performed is `[s]B == R + [k]A`, where `k = SHA512(R || A || msg) mod L`, and the
+/// encodings of `A`, `R` and `s` are required to be canonical.
+pub fn ed25519_verify(pk: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    // Decode the public key
+    let a = match decompress(pk) {
+        Some(a) => a,
+        None => {
+            #[cfg(debug_assertions)]
+            println!("Invalid public key encoding: {:?}", pk);
+
+            return false;
+        }
+    };
+
+    // Decode s and check that it is reduced
+    let s = limbs_from_le_bytes(&sig[32..64]);
+    if !gt(&L, &s) {
+        #[cfg(debug_assertions)]
+        println!("s should be less than L: {:?}", s);
+
+        return false;
+    }
+
+    // Compute k = SHA512(R || A || msg) mod L
+    let mut hasher = Sha512::new();
+    hasher.update(&sig[0..32]);
+    hasher.update(pk);
+    hasher.update(msg);
+    let hash = hasher.finalize();
+    let mut hash_limbs = [0u64; 8];
+    for (i, limb) in hash_limbs.iter_mut().enumerate() {
+        *limb = u64::from_le_bytes(hash[8 * i..8 * i + 8].try_into().unwrap());
+    }
+    let k: [u64; 4] = bigint_rem(&hash_limbs, &L).try_into().unwrap();
+
+    // Compute R' = [s]B + [k](-A) and compare its encoding with R, which also rejects
+    // non-canonical encodings of R
+    let b = SyscallPoint256 { x: B_X, y: B_Y };
+    let minus_a = SyscallPoint256 { x: fp_sub(&ZERO, &a.x), y: a.y };
+    let r = double_scalar_mul(&s, &b, &k, &minus_a);
+
+    compress(&r) == sig[0..32]
+}
+
+/// Given a compressed point, returns its affine coordinates, or `None` if it is not a
+/// canonical encoding of a point of the curve
+fn decompress(bytes: &[u8; 32]) -> Option<SyscallPoint256> {
+    let mut y = limbs_from_le_bytes(bytes);
+    let x_parity = y[3] >> 63;
+    y[3] &= 0x7FFFFFFFFFFFFFFF;
+    if !gt(&P, &y) {
+        return None;
+    }
+
+    // From -x² + y² = 1 + d·x²·y², we get x² = (y² - 1) / (d·y² + 1), where the denominator
+    // is never zero since -1/d is not a square
+    let y_sq = fp_mul(&y, &y);
+    let u = fp_sub(&y_sq, &ONE);
+    let v = fp_mul_add(&D, &y_sq, &ONE);
+    let x_sq = fp_mul(&u, &fp_inv(&v));
+
+    // Compute the candidate root x = (x²)^((p+3)/8), which is a root of either x² or -x²
+    let mut x = fp_pow(&x_sq, &P_PLUS_3_DIV_8);
+    let x_cand_sq = fp_mul(&x, &x);
+    if x_cand_sq != x_sq {
+        if x_cand_sq != fp_sub(&ZERO, &x_sq) {
+            return None;
+        }
+        x = fp_mul(&x, &SQRT_M1);
+    }
+
+    if x == ZERO && x_parity == 1 {
+        return None;
+    }
+    if x[0] & 1 != x_parity {
+        x = fp_sub(&ZERO, &x);
+    }
+
+    Some(SyscallPoint256 { x, y })
+}
+
+/// Given a point `p`, returns its compressed encoding
+fn compress(p: &SyscallPoint256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for i in 0..4 {
+        bytes[8 * i..8 * i + 8].copy_from_slice(&p.y[i].to_le_bytes());
+    }
+    bytes[31] |= ((p.x[0] & 1) as u8) << 7;
+    bytes
+}
+
+/// Given scalars `k1`, `k2` and points `p1`, `p2`, computes `[k1]p1 + [k2]p2` using Shamir's trick
+fn double_scalar_mul(
+    k1: &[u64; 4],
+    p1: &SyscallPoint256,
+    k2: &[u64; 4],
+    p2: &SyscallPoint256,
+) -> SyscallPoint256 {
+    let mut p12 = SyscallPoint256 { x: p1.x, y: p1.y };
+    add_points_assign(&mut p12, p2);
+
+    // Start from the neutral element (0, 1)
+    let mut res = SyscallPoint256 { x: ZERO, y: ONE };
+    let mut started = false;
+    for i in (0..256).rev() {
+        let bit1 = (k1[i / 64] >> (i % 64)) & 1;
+        let bit2 = (k2[i / 64] >> (i % 64)) & 1;
+
+        // Doubling the neutral element is a no-op
+        if started {
+            double_point_assign(&mut res);
+        }
+        match (bit1, bit2) {
+            (0, 0) => continue,
+            (1, 0) => add_points_assign(&mut res, p1),
+            (0, 1) => add_points_assign(&mut res, p2),
+            _ => add_points_assign(&mut res, &p12),
+        }
+        started = true;
+    }
+    res
+}
+
+/// Given points `p1` and `p2`, performs the point addition `p1 + p2` and assigns the result to `p1`.
+/// It assumes that `p1` and `p2` are from the Ed25519 curve, for which the formulas are complete
+fn add_points_assign(p1: &mut SyscallPoint256, p2: &SyscallPoint256) {
+    let mut params = SyscallEd25519AddParams { p1, p2 };
+    syscall_ed25519_add(&mut params);
+}
+
+/// Given a point `p1`, performs the point doubling `2·p1` and assigns the result to `p1`.
+/// It assumes that `p1` is from the Ed25519 curve, so the doubling denominators are never zero
+fn double_point_assign(p1: &mut SyscallPoint256) {
+    syscall_ed25519_dbl(p1);
+}
+
+/// Computes `(a * b + c) mod P`
+fn fp_mul_add(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4]) -> [u64; 4] {
+    let mut d = [0u64; 4];
+    let mut params = SyscallCurve25519ModParams { a, b, c, d: &mut d };
+    syscall_curve25519_mod(&mut params);
+    d
+}
+
+fn fp_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    fp_mul_add(a, b, &ZERO)
+}
+
+/// Computes `(a - b) mod P`. It assumes that `b <= P`
+fn fp_sub(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    fp_mul_add(a, &ONE, &sub(&P, b))
+}
+
+/// Computes the inverse of a non-zero `a` modulo `P`, hinted and verified by `bigint_modinv`
+fn fp_inv(a: &[u64; 4]) -> [u64; 4] {
+    let inv = bigint_modinv(a, &P).expect("ed25519: zero has no inverse");
+    inv.try_into().unwrap()
+}
+
+fn fp_pow(base: &[u64; 4], exp: &[u64; 4]) -> [u64; 4] {
+    let mut result = ONE;
+    for i in (0..256).rev() {
+        result = fp_mul(&result, &result);
+        if (exp[i / 64] >> (i % 64)) & 1 == 1 {
+            result = fp_mul(&result, base);
+        }
+    }
+    result
+}
+
+fn limbs_from_le_bytes(bytes: &[u8]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        *limb = u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap());
+    }
+    limbs
+}
+
+/// Minimal SHA-512 (FIPS 180-4), needed to derive the challenge scalar
+struct Sha512 {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffer_len: usize,
+    total_len: u128,
+}
+
+const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+impl Sha512 {
+    fn new() -> Self {
+        Self {
+            state: [
+                0x6a09e667f3bcc908,
+                0xbb67ae8584caa73b,
+                0x3c6ef372fe94f82b,
+                0xa54ff53a5f1d36f1,
+                0x510e527fade682d1,
+                0x9b05688c2b3e6c1f,
+                0x1f83d9abfb41bd6b,
+                0x5be0cd19137e2179,
+            ],
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len += data.len() as u128;
+        while !data.is_empty() {
+            let n = (128 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+            if self.buffer_len == 128 {
+                let block = self.buffer;
+                self.compress(&block);
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 64] {
+        let bit_len = self.total_len * 8;
+        self.update(&[0x80]);
+        while self.buffer_len != 112 {
+            self.update(&[0]);
+        }
+        self.update(&bit_len.to_be_bytes());
+
+        let mut out = [0u8; 64];
+        for (i, word) in self.state.iter().enumerate() {
+            out[8 * i..8 * i + 8].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+
+    fn compress(&mut self, block: &[u8; 128]) {
+        let mut w = [0u64; 80];
+        for i in 0..16 {
+            w[i] = u64::from_be_bytes(block[8 * i..8 * i + 8].try_into().unwrap());
+        }
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..80 {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let t1 =
+                h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA512_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (s, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn sha512(msg: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(msg);
+        hasher.finalize().to_vec()
+    }
+
+    /// Test vectors from RFC 8032, section 7.1: (public key, message, signature)
+    const RFC8032_VECTORS: [(&str, &str, &str); 4] = [
+        // TEST 1
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        // TEST 2
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        // TEST 3
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+        // TEST SHA(abc)
+        (
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+        ),
+    ];
+
+    fn vector(i: usize) -> ([u8; 32], Vec<u8>, [u8; 64]) {
+        let (pk, msg, sig) = RFC8032_VECTORS[i];
+        (from_hex(pk).try_into().unwrap(), from_hex(msg), from_hex(sig).try_into().unwrap())
+    }
+
+    #[test]
+    fn test_sha512() {
+        let kats: [(&[u8], &str); 4] = [
+            (b"", "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"),
+            (b"abc", "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c33596fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445",
+            ),
+            (
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+                "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909",
+            ),
+        ];
+        for (msg, digest) in kats {
+            assert_eq!(sha512(msg), from_hex(digest), "message of {} bytes", msg.len());
+        }
+    }
+
+    #[test]
+    fn test_sha512_million_a() {
+        // Feed the message in uneven pieces to exercise the buffering across blocks
+        let mut hasher = Sha512::new();
+        let chunk = [b'a'; 997];
+        let mut left = 1_000_000;
+        while left > 0 {
+            let n = left.min(chunk.len());
+            hasher.update(&chunk[..n]);
+            left -= n;
+        }
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            from_hex("e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b")
+        );
+    }
+
+    #[test]
+    fn test_verify_rfc8032() {
+        for i in 0..RFC8032_VECTORS.len() {
+            let (pk, msg, sig) = vector(i);
+            assert!(ed25519_verify(&pk, &msg, &sig), "RFC 8032 vector {i}");
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let (pk, msg, sig) = vector(2);
+
+        // Altered message
+        let mut bad_msg = msg.clone();
+        bad_msg[0] ^= 1;
+        assert!(!ed25519_verify(&pk, &bad_msg, &sig));
+
+        // Altered R and s
+        for i in [0, 40] {
+            let mut bad_sig = sig;
+            bad_sig[i] ^= 1;
+            assert!(!ed25519_verify(&pk, &msg, &bad_sig));
+        }
+
+        // Signature checked against another vector's key
+        let (other_pk, _, _) = vector(1);
+        assert!(!ed25519_verify(&other_pk, &msg, &sig));
+    }
+
+    #[test]
+    fn test_verify_rejects_non_canonical() {
+        let (pk, msg, sig) = vector(0);
+
+        // s + L encodes the same scalar, but is not reduced
+        let s = limbs_from_le_bytes(&sig[32..64]);
+        let mut s_plus_l = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let sum = s[i] as u128 + L[i] as u128 + carry;
+            s_plus_l[i] = sum as u64;
+            carry = sum >> 64;
+        }
+        let mut bad_sig = sig;
+        for i in 0..4 {
+            bad_sig[32 + 8 * i..40 + 8 * i].copy_from_slice(&s_plus_l[i].to_le_bytes());
+        }
+        assert!(!ed25519_verify(&pk, &msg, &bad_sig));
+
+        // A public key with y >= P
+        let mut bad_pk = [0xFF; 32];
+        bad_pk[31] = 0x7F;
+        assert!(!ed25519_verify(&bad_pk, &msg, &sig));
+    }
+
+    #[test]
+    fn test_decompress_compress() {
+        for i in 0..RFC8032_VECTORS.len() {
+            let (pk, _, _) = vector(i);
+            let point = decompress(&pk).unwrap();
+            assert_eq!(compress(&point), pk);
+        }
+
+        // The generator is the point with y = 4/5 and positive x
+        let b = SyscallPoint256 { x: B_X, y: B_Y };
+        assert_eq!(decompress(&compress(&b)).unwrap().x, B_X);
+    }
+}
@@ -1,11 +1,26 @@
+mod bigint;
+mod blake2b;
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 mod ecrecover;
+mod ed25519;
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 mod exp_power_of_two;
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 mod secp256k1_fp_assert_nqr;
 mod secp256k1_msm;
 mod utils;
 
+pub use bigint::{
+    bigint_from_be_bytes, bigint_modexp, bigint_modinv, bigint_modmul, bigint_mul, bigint_rem,
+    bigint_to_be_bytes, BIGINT_MAX_LIMBS,
+};
+pub use blake2b::blake2b_compress;
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 pub use ecrecover::ecrecover;
+pub use ed25519::ed25519_verify;
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 pub use exp_power_of_two::{exp_power_of_two, exp_power_of_two_self};
-pub(self) use secp256k1_fp_assert_nqr::secp256k1_fp_assert_nqr;
-pub(self) use secp256k1_msm::secp256k1_double_scalar_mul_with_g;
-pub(self) use utils::{gt, sub};
+#[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
+use secp256k1_fp_assert_nqr::secp256k1_fp_assert_nqr;
+use secp256k1_msm::secp256k1_double_scalar_mul_with_g;
+use utils::{gt, sub};
@@ -2,6 +2,10 @@ mod fcalls;
 pub use fcalls::*;
 #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 mod lib;
+// On the host, the library is only built to be tested against the precompile helpers
+#[cfg(all(not(all(target_os = "zkvm", target_vendor = "zisk")), test))]
+#[allow(dead_code, unused_imports)]
+mod lib;
 #[cfg(all(target_os = "zkvm", target_vendor = "zisk"))]
 pub use lib::*;
 #[cfg(not(all(target_os = "zkvm", target_vendor = "zisk")))]
@@ -5,6 +5,10 @@ pub mod ziskos_config {
     pub const QEMU_EXIT_CODE: u64 = 0x5555;
     pub const INPUT_ADDR: u64 = 0x9000_0000;
     pub const OUTPUT_ADDR: u64 = 0xa001_0000;
+    pub const UART_ADDR: u64 = 0xa000_0200;
+    pub const UART_ERR_ADDR: u64 = 0xa000_0208;
+    pub const GUEST_EXIT_ADDR: u64 = 0xa000_0400;
+    pub const GUEST_PANIC_MSG_MAX_SIZE: u64 = 0x400 - 16;
     pub const ARCH_ID_ZISK: u64 = 0xFFFEEEE; // TEMPORARY  // TODO register one
 
     pub const MAX_INPUT: usize = 0x2000;